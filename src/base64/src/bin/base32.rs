@@ -0,0 +1,15 @@
+// ASD CoreUtils - base32
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use base64::Encoding;
+
+fn main() {
+    let matches = base64::command(
+        "base32",
+        "ASD CoreUtils base32 - base32 encode/decode data and print to standard output",
+    )
+    .get_matches();
+
+    base64::run("base32", &matches, Encoding::Base32);
+}
@@ -0,0 +1,44 @@
+// ASD CoreUtils - basenc
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use base64::Encoding;
+use clap::{Arg, ArgAction, ArgGroup};
+
+const ENCODINGS: &[(&str, &str, Encoding)] = &[
+    ("base64", "Same as the 'base64' program (RFC4648 section 4)", Encoding::Base64),
+    ("base64url", "File- and URL-safe base64 (RFC4648 section 5)", Encoding::Base64Url),
+    ("base32", "Same as the 'base32' program (RFC4648 section 6)", Encoding::Base32),
+    ("base32hex", "Extended hex alphabet base32 (RFC4648 section 7)", Encoding::Base32Hex),
+    ("base16", "Hex encoding (RFC4648 section 8)", Encoding::Base16),
+    ("base2msbf", "Bit string with most significant bit (msb) first", Encoding::Base2Msbf),
+    ("base2lsbf", "Bit string with least significant bit (lsb) first", Encoding::Base2Lsbf),
+    ("z85", "Ascii85-like encoding (ZeroMQ spec:32/Z85)", Encoding::Z85),
+];
+
+fn main() {
+    let mut command = base64::command(
+        "basenc",
+        "ASD CoreUtils basenc - encode/decode data with a chosen base encoding",
+    );
+
+    for &(name, help, _) in ENCODINGS {
+        command = command.arg(Arg::new(name).long(name).help(help).action(ArgAction::SetTrue));
+    }
+
+    let matches = command
+        .group(
+            ArgGroup::new("encoding")
+                .args(ENCODINGS.iter().map(|&(name, _, _)| name))
+                .required(true),
+        )
+        .get_matches();
+
+    let encoding = ENCODINGS
+        .iter()
+        .find(|(name, _, _)| matches.get_flag(name))
+        .map(|&(_, _, encoding)| encoding)
+        .unwrap();
+
+    base64::run("basenc", &matches, encoding);
+}
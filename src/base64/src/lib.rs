@@ -0,0 +1,485 @@
+// ASD CoreUtils - base64/base32/basenc shared codec
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod simd;
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const BASE32: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32HEX: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const BASE16: &[u8] = b"0123456789ABCDEF";
+const BASE2: &[u8] = b"01";
+const Z85: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+const PAD: u8 = b'=';
+const INVALID: u8 = 0xff;
+const DEFAULT_WRAP: usize = 76;
+
+// A multiple of every encoding's input block (3, 5, 4 and 1 bytes), so
+// only the final chunk of a stream can end in a partial block
+const CHUNK_SIZE: usize = 60 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Base64,
+    Base64Url,
+    Base32,
+    Base32Hex,
+    Base16,
+    Base2Msbf,
+    Base2Lsbf,
+    Z85,
+}
+
+#[derive(Clone, Copy)]
+enum Scheme {
+    // Big-endian groups of BITS bits, one symbol each
+    Bits {
+        alphabet: &'static [u8],
+        bits: usize,
+        padded: bool,
+    },
+    Base2Lsbf,
+    Z85,
+}
+
+struct Codec {
+    scheme: Scheme,
+    table: [u8; 256],
+    input_block: usize,
+    output_block: usize,
+}
+
+impl Codec {
+    fn new(encoding: Encoding) -> Self {
+        let scheme = match encoding {
+            Encoding::Base64 => Scheme::Bits {
+                alphabet: BASE64,
+                bits: 6,
+                padded: true,
+            },
+            Encoding::Base64Url => Scheme::Bits {
+                alphabet: BASE64URL,
+                bits: 6,
+                padded: true,
+            },
+            Encoding::Base32 => Scheme::Bits {
+                alphabet: BASE32,
+                bits: 5,
+                padded: true,
+            },
+            Encoding::Base32Hex => Scheme::Bits {
+                alphabet: BASE32HEX,
+                bits: 5,
+                padded: true,
+            },
+            Encoding::Base16 => Scheme::Bits {
+                alphabet: BASE16,
+                bits: 4,
+                padded: false,
+            },
+            Encoding::Base2Msbf => Scheme::Bits {
+                alphabet: BASE2,
+                bits: 1,
+                padded: false,
+            },
+            Encoding::Base2Lsbf => Scheme::Base2Lsbf,
+            Encoding::Z85 => Scheme::Z85,
+        };
+
+        let (alphabet, input_block, output_block) = match scheme {
+            Scheme::Bits { alphabet, bits, .. } => {
+                let block_bits = lcm(8, bits);
+                (alphabet, block_bits / 8, block_bits / bits)
+            }
+            Scheme::Base2Lsbf => (BASE2, 1, 8),
+            Scheme::Z85 => (Z85, 4, 5),
+        };
+
+        let mut table = [INVALID; 256];
+        for (value, &symbol) in alphabet.iter().enumerate() {
+            table[symbol as usize] = value as u8;
+        }
+        // Hex digits decode in either case
+        if encoding == Encoding::Base16 {
+            for (value, symbol) in (b'a'..=b'f').enumerate() {
+                table[symbol as usize] = 10 + value as u8;
+            }
+        }
+
+        Codec {
+            scheme,
+            table,
+            input_block,
+            output_block,
+        }
+    }
+
+    // The alphabet, for base64 and base64url, which have vectorised codecs
+    fn vector_alphabet(&self) -> Option<&'static [u8]> {
+        match self.scheme {
+            Scheme::Bits {
+                alphabet, bits: 6, ..
+            } => Some(alphabet),
+            _ => None,
+        }
+    }
+
+    fn is_padding(&self, byte: u8) -> bool {
+        matches!(self.scheme, Scheme::Bits { padded: true, .. }) && byte == PAD
+    }
+
+    // Encodes up to one input block; only the last block may be short
+    fn encode_block(&self, block: &[u8], encoded: &mut Vec<u8>) -> io::Result<()> {
+        match self.scheme {
+            Scheme::Bits {
+                alphabet,
+                bits,
+                padded,
+            } => {
+                let mut acc = 0u64;
+                for &byte in block {
+                    acc = (acc << 8) | byte as u64;
+                }
+                acc <<= 8 * (self.input_block - block.len());
+
+                let block_bits = self.input_block * 8;
+                let needed = (block.len() * 8).div_ceil(bits);
+                let mask = (1u64 << bits) - 1;
+
+                for index in 0..self.output_block {
+                    if index < needed {
+                        let shift = block_bits - (index + 1) * bits;
+                        encoded.push(alphabet[((acc >> shift) & mask) as usize]);
+                    } else if padded {
+                        encoded.push(PAD);
+                    }
+                }
+            }
+            Scheme::Base2Lsbf => {
+                for &byte in block {
+                    for bit in 0..8 {
+                        encoded.push(BASE2[((byte >> bit) & 1) as usize]);
+                    }
+                }
+            }
+            Scheme::Z85 => {
+                if block.len() != 4 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid input (length must be multiple of 4 characters)",
+                    ));
+                }
+
+                let mut value = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+                let mut symbols = [0u8; 5];
+                for slot in symbols.iter_mut().rev() {
+                    *slot = Z85[(value % 85) as usize];
+                    value /= 85;
+                }
+                encoded.extend_from_slice(&symbols);
+            }
+        }
+        Ok(())
+    }
+
+    // Decodes one complete group of symbols, returning whether it was padded
+    fn decode_group(&self, group: &[u8], decoded: &mut Vec<u8>) -> io::Result<bool> {
+        match self.scheme {
+            Scheme::Bits { bits, .. } => {
+                let padding = group.iter().rev().take_while(|&&b| b == PAD).count();
+                let symbols = &group[..group.len() - padding];
+                if symbols.contains(&PAD) {
+                    return Err(invalid_input());
+                }
+
+                // The symbol count must be exactly what encoding the
+                // remaining whole bytes would have produced
+                let bytes = symbols.len() * bits / 8;
+                if bytes == 0 || (bytes * 8).div_ceil(bits) != symbols.len() {
+                    return Err(invalid_input());
+                }
+
+                let mut acc = 0u64;
+                for &symbol in symbols {
+                    acc = (acc << bits) | self.table[symbol as usize] as u64;
+                }
+                acc <<= bits * padding;
+
+                for index in 0..bytes {
+                    decoded.push((acc >> (8 * (self.input_block - 1 - index))) as u8);
+                }
+                Ok(padding > 0)
+            }
+            Scheme::Base2Lsbf => {
+                let byte = group.iter().enumerate().fold(0u8, |byte, (bit, &symbol)| {
+                    byte | (self.table[symbol as usize] << bit)
+                });
+                decoded.push(byte);
+                Ok(false)
+            }
+            Scheme::Z85 => {
+                let value = group.iter().fold(0u64, |acc, &symbol| {
+                    acc * 85 + self.table[symbol as usize] as u64
+                });
+                let value = u32::try_from(value).map_err(|_| invalid_input())?;
+                decoded.extend_from_slice(&value.to_be_bytes());
+                Ok(false)
+            }
+        }
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
+fn invalid_input() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid input")
+}
+
+impl Encoding {
+    pub fn encode_stream(
+        self,
+        input: &mut impl Read,
+        out: &mut impl Write,
+        wrap: usize,
+    ) -> io::Result<()> {
+        let codec = Codec::new(self);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut encoded = Vec::with_capacity(CHUNK_SIZE * 8);
+        let mut column = 0;
+
+        loop {
+            let filled = read_full(input, &mut buffer)?;
+            if filled == 0 {
+                break;
+            }
+
+            encoded.clear();
+            let mut start = 0;
+            if let Some(alphabet) = codec.vector_alphabet() {
+                start = simd::encode(&buffer[..filled], alphabet, &mut encoded);
+            }
+            let mut result = Ok(());
+            for block in buffer[start..filled].chunks(codec.input_block) {
+                result = codec.encode_block(block, &mut encoded);
+                if result.is_err() {
+                    break;
+                }
+            }
+            write_wrapped(out, &encoded, wrap, &mut column)?;
+            result?;
+
+            if filled < buffer.len() {
+                break;
+            }
+        }
+
+        if column > 0 {
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn decode_stream(
+        self,
+        input: &mut impl Read,
+        out: &mut impl Write,
+        ignore_garbage: bool,
+    ) -> io::Result<()> {
+        let codec = Codec::new(self);
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let mut group = Vec::with_capacity(codec.output_block);
+        let mut decoded = Vec::with_capacity(CHUNK_SIZE);
+        let mut unwrapped = Vec::with_capacity(CHUNK_SIZE);
+        let mut finished = false;
+
+        loop {
+            let filled = read_full(input, &mut buffer)?;
+            if filled == 0 {
+                break;
+            }
+
+            decoded.clear();
+            let mut result = Ok(());
+
+            // Line breaks would stop the vectorised decoder at every line
+            unwrapped.clear();
+            for line in buffer[..filled].split(|&byte| byte == b'\n' || byte == b'\r') {
+                unwrapped.extend_from_slice(line);
+            }
+
+            let mut index = 0;
+            while index < unwrapped.len() {
+                if let (Some(alphabet), true) =
+                    (codec.vector_alphabet(), group.is_empty() && !finished)
+                {
+                    index += simd::decode(&unwrapped[index..], alphabet, &mut decoded);
+                    if index == unwrapped.len() {
+                        break;
+                    }
+                }
+                let byte = unwrapped[index];
+                index += 1;
+
+                let padding = codec.is_padding(byte);
+                if !padding && codec.table[byte as usize] == INVALID {
+                    if ignore_garbage {
+                        continue;
+                    }
+                    result = Err(invalid_input());
+                    break;
+                }
+
+                // Nothing but padding may follow the end of the encoded data
+                if finished {
+                    if !padding {
+                        result = Err(invalid_input());
+                        break;
+                    }
+                    continue;
+                }
+
+                group.push(byte);
+                if group.len() == codec.output_block {
+                    match codec.decode_group(&group, &mut decoded) {
+                        Ok(padded) => finished = padded,
+                        Err(err) => {
+                            result = Err(err);
+                            break;
+                        }
+                    }
+                    group.clear();
+                }
+            }
+
+            // Whatever decoded cleanly is still written out before an error, as GNU does
+            out.write_all(&decoded)?;
+            result?;
+        }
+
+        if !group.is_empty() {
+            return Err(invalid_input());
+        }
+        Ok(())
+    }
+}
+
+fn read_full(input: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match input.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+fn write_wrapped(
+    out: &mut impl Write,
+    mut data: &[u8],
+    wrap: usize,
+    column: &mut usize,
+) -> io::Result<()> {
+    if wrap == 0 {
+        return out.write_all(data);
+    }
+
+    while !data.is_empty() {
+        let take = (wrap - *column).min(data.len());
+        out.write_all(&data[..take])?;
+        data = &data[take..];
+        *column += take;
+
+        if *column == wrap {
+            out.write_all(b"\n")?;
+            *column = 0;
+        }
+    }
+    Ok(())
+}
+
+// The options every front end shares; callers add their own on top
+pub fn command(name: &'static str, about: &'static str) -> Command {
+    Command::new(name)
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about(about)
+        .arg(
+            Arg::new("decode")
+                .short('d')
+                .long("decode")
+                .help("Decode data")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ignore-garbage")
+                .short('i')
+                .long("ignore-garbage")
+                .help("When decoding, ignore non-alphabet characters")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("wrap")
+                .short('w')
+                .long("wrap")
+                .value_name("COLS")
+                .help(
+                "Wrap encoded lines after COLS characters (default 76); use 0 to disable wrapping",
+            ),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Input file, or - for standard input")
+                .default_value("-"),
+        )
+}
+
+pub fn run(name: &str, matches: &ArgMatches, encoding: Encoding) {
+    let wrap = match matches.get_one::<String>("wrap") {
+        Some(value) => value
+            .parse::<usize>()
+            .unwrap_or_else(|_| fail(name, &format!("invalid wrap size: '{}'", value))),
+        None => DEFAULT_WRAP,
+    };
+
+    let file = matches.get_one::<String>("file").unwrap();
+    let mut input: Box<dyn Read> = if file == "-" {
+        Box::new(io::stdin())
+    } else {
+        match File::open(file) {
+            Ok(f) => Box::new(f),
+            Err(err) => fail(name, &format!("{}: {}", file, strerror(&err))),
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let result = if matches.get_flag("decode") {
+        encoding.decode_stream(&mut input, &mut out, matches.get_flag("ignore-garbage"))
+    } else {
+        encoding.encode_stream(&mut input, &mut out, wrap)
+    };
+
+    let flushed = out.flush();
+    if let Err(err) = result.and(flushed) {
+        fail(name, &strerror(&err));
+    }
+}
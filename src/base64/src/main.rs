@@ -0,0 +1,15 @@
+// ASD CoreUtils - base64
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use base64::Encoding;
+
+fn main() {
+    let matches = base64::command(
+        "base64",
+        "ASD CoreUtils base64 - base64 encode/decode data and print to standard output",
+    )
+    .get_matches();
+
+    base64::run("base64", &matches, Encoding::Base64);
+}
@@ -0,0 +1,416 @@
+// ASD CoreUtils - base64/base32/basenc shared codec
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Vectorised base64 for the long runs in the middle of the input. Each
+// function does as much as whole vectors allow and returns how many input
+// bytes it used, leaving the rest, and anything it does not recognise, to
+// the scalar codec. The alphabet only differs between base64 and base64url
+// in its last two symbols, which are passed in; the instruction set is
+// picked when called: AVX2 or SSSE3 on x86-64, NEON on AArch64.
+
+// Encode whole groups of three bytes from the start of INPUT with the
+// 64-symbol ALPHABET, appending to ENCODED
+pub fn encode(input: &[u8], alphabet: &[u8], encoded: &mut Vec<u8>) -> usize {
+    let (c62, c63) = (alphabet[62], alphabet[63]);
+    encoded.reserve(input.len() / 3 * 4 + 64);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        let mut used = 0;
+        if is_x86_feature_detected!("avx2") {
+            used = unsafe { x86::encode_avx2(input, c62, c63, encoded) };
+        }
+        if is_x86_feature_detected!("ssse3") {
+            used += unsafe { x86::encode_ssse3(&input[used..], c62, c63, encoded) };
+        }
+        used
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            unsafe { neon::encode(input, alphabet, encoded) }
+        } else {
+            0
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        let _ = (c62, c63);
+        0
+    }
+}
+
+// Decode whole groups of four symbols from the start of INPUT, appending to
+// DECODED. Stops at the first vector holding anything but alphabet symbols,
+// so padding, line breaks and garbage are all left to the caller.
+pub fn decode(input: &[u8], alphabet: &[u8], decoded: &mut Vec<u8>) -> usize {
+    let (c62, c63) = (alphabet[62], alphabet[63]);
+    decoded.reserve(input.len() / 4 * 3 + 64);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        let mut used = 0;
+        if is_x86_feature_detected!("avx2") {
+            used = unsafe { x86::decode_avx2(input, c62, c63, decoded) };
+        }
+        if is_x86_feature_detected!("ssse3") {
+            used += unsafe { x86::decode_ssse3(&input[used..], c62, c63, decoded) };
+        }
+        used
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            unsafe { neon::decode(input, c62, c63, decoded) }
+        } else {
+            0
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        let _ = (c62, c63);
+        0
+    }
+}
+
+// What adding to a symbol's value gives the symbol, for the values 62 and 63
+fn special_offsets(c62: u8, c63: u8) -> (i8, i8) {
+    (c62.wrapping_sub(62) as i8, c63.wrapping_sub(63) as i8)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::special_offsets;
+    use std::arch::x86_64::*;
+
+    // The method is Wojciech Muła's: shuffle each three bytes into a 32-bit
+    // lane, move the four 6-bit fields into bytes with two multiplies, and
+    // turn values into symbols by adding an offset looked up by range.
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn split_ssse3(input: __m128i) -> __m128i {
+        let shuffled = _mm_shuffle_epi8(
+            input,
+            _mm_set_epi8(10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1),
+        );
+        let t0 = _mm_and_si128(shuffled, _mm_set1_epi32(0x0fc0_fc00));
+        let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x0400_0040));
+        let t2 = _mm_and_si128(shuffled, _mm_set1_epi32(0x003f_03f0));
+        let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x0100_0010));
+        _mm_or_si128(t1, t3)
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn symbols_ssse3(values: __m128i, offsets: __m128i) -> __m128i {
+        // 0 for values up to 51, 1 to 12 for 52 to 63, and 13 for 0 to 25
+        let mut range = _mm_subs_epu8(values, _mm_set1_epi8(51));
+        let low = _mm_cmpgt_epi8(_mm_set1_epi8(26), values);
+        range = _mm_or_si128(range, _mm_and_si128(low, _mm_set1_epi8(13)));
+        _mm_add_epi8(values, _mm_shuffle_epi8(offsets, range))
+    }
+
+    fn offset_table(c62: u8, c63: u8) -> [i8; 16] {
+        let (o62, o63) = special_offsets(c62, c63);
+        let digits = b'0' as i8 - 52;
+        [
+            b'a' as i8 - 26,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            digits,
+            o62,
+            o63,
+            b'A' as i8,
+            0,
+            0,
+        ]
+    }
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn encode_ssse3(input: &[u8], c62: u8, c63: u8, encoded: &mut Vec<u8>) -> usize {
+        let offsets = _mm_loadu_si128(offset_table(c62, c63).as_ptr() as *const __m128i);
+        let mut used = 0;
+        // Each step loads 16 bytes and uses 12 of them
+        while input.len() - used >= 16 {
+            let block = _mm_loadu_si128(input.as_ptr().add(used) as *const __m128i);
+            let symbols = symbols_ssse3(split_ssse3(block), offsets);
+            let end = encoded.len();
+            _mm_storeu_si128(encoded.as_mut_ptr().add(end) as *mut __m128i, symbols);
+            encoded.set_len(end + 16);
+            used += 12;
+        }
+        used
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn encode_avx2(input: &[u8], c62: u8, c63: u8, encoded: &mut Vec<u8>) -> usize {
+        let table = offset_table(c62, c63);
+        let offsets =
+            _mm256_broadcastsi128_si256(_mm_loadu_si128(table.as_ptr() as *const __m128i));
+        let shuffle = _mm256_broadcastsi128_si256(_mm_set_epi8(
+            10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1,
+        ));
+        let mut used = 0;
+        // Each step uses 24 bytes, 12 from each of two overlapping 16-byte loads
+        while input.len() - used >= 28 {
+            let from = input.as_ptr().add(used);
+            let low = _mm_loadu_si128(from as *const __m128i);
+            let high = _mm_loadu_si128(from.add(12) as *const __m128i);
+            let block = _mm256_inserti128_si256(_mm256_castsi128_si256(low), high, 1);
+
+            let shuffled = _mm256_shuffle_epi8(block, shuffle);
+            let t0 = _mm256_and_si256(shuffled, _mm256_set1_epi32(0x0fc0_fc00));
+            let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x0400_0040));
+            let t2 = _mm256_and_si256(shuffled, _mm256_set1_epi32(0x003f_03f0));
+            let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x0100_0010));
+            let values = _mm256_or_si256(t1, t3);
+
+            let mut range = _mm256_subs_epu8(values, _mm256_set1_epi8(51));
+            let low = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), values);
+            range = _mm256_or_si256(range, _mm256_and_si256(low, _mm256_set1_epi8(13)));
+            let symbols = _mm256_add_epi8(values, _mm256_shuffle_epi8(offsets, range));
+
+            let end = encoded.len();
+            _mm256_storeu_si256(encoded.as_mut_ptr().add(end) as *mut __m256i, symbols);
+            encoded.set_len(end + 32);
+            used += 24;
+        }
+        used
+    }
+
+    // Symbols are told apart by range, which works for either alphabet:
+    // signed compares leave bytes from 128 up out of every range
+
+    #[target_feature(enable = "ssse3")]
+    pub unsafe fn decode_ssse3(input: &[u8], c62: u8, c63: u8, decoded: &mut Vec<u8>) -> usize {
+        let (o62, o63) = special_offsets(c62, c63);
+        let pack = _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1);
+        let mut used = 0;
+        while input.len() - used >= 16 {
+            let block = _mm_loadu_si128(input.as_ptr().add(used) as *const __m128i);
+
+            let within = |low: u8, high: u8| {
+                _mm_and_si128(
+                    _mm_cmpgt_epi8(block, _mm_set1_epi8(low as i8 - 1)),
+                    _mm_cmpgt_epi8(_mm_set1_epi8(high as i8 + 1), block),
+                )
+            };
+            let upper = within(b'A', b'Z');
+            let lower = within(b'a', b'z');
+            let digit = within(b'0', b'9');
+            let is62 = _mm_cmpeq_epi8(block, _mm_set1_epi8(c62 as i8));
+            let is63 = _mm_cmpeq_epi8(block, _mm_set1_epi8(c63 as i8));
+
+            let valid = _mm_or_si128(
+                _mm_or_si128(upper, lower),
+                _mm_or_si128(digit, _mm_or_si128(is62, is63)),
+            );
+            if _mm_movemask_epi8(valid) != 0xffff {
+                break;
+            }
+
+            let offsets = _mm_or_si128(
+                _mm_or_si128(
+                    _mm_and_si128(upper, _mm_set1_epi8(-(b'A' as i8))),
+                    _mm_and_si128(lower, _mm_set1_epi8(26 - b'a' as i8)),
+                ),
+                _mm_or_si128(
+                    _mm_and_si128(digit, _mm_set1_epi8(52 - b'0' as i8)),
+                    _mm_or_si128(
+                        _mm_and_si128(is62, _mm_set1_epi8(o62.wrapping_neg())),
+                        _mm_and_si128(is63, _mm_set1_epi8(o63.wrapping_neg())),
+                    ),
+                ),
+            );
+            let values = _mm_add_epi8(block, offsets);
+
+            // Join each four 6-bit values into 24 bits, then take the bytes
+            // most significant first
+            let pairs = _mm_maddubs_epi16(values, _mm_set1_epi32(0x0140_0140));
+            let groups = _mm_madd_epi16(pairs, _mm_set1_epi32(0x0001_1000));
+            let bytes = _mm_shuffle_epi8(groups, pack);
+
+            let end = decoded.len();
+            _mm_storeu_si128(decoded.as_mut_ptr().add(end) as *mut __m128i, bytes);
+            decoded.set_len(end + 12);
+            used += 16;
+        }
+        used
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn decode_avx2(input: &[u8], c62: u8, c63: u8, decoded: &mut Vec<u8>) -> usize {
+        let (o62, o63) = special_offsets(c62, c63);
+        let pack = _mm256_broadcastsi128_si256(_mm_setr_epi8(
+            2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1,
+        ));
+        let gather = _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 3, 7);
+        let mut used = 0;
+        while input.len() - used >= 32 {
+            let block = _mm256_loadu_si256(input.as_ptr().add(used) as *const __m256i);
+
+            let within = |low: u8, high: u8| {
+                _mm256_and_si256(
+                    _mm256_cmpgt_epi8(block, _mm256_set1_epi8(low as i8 - 1)),
+                    _mm256_cmpgt_epi8(_mm256_set1_epi8(high as i8 + 1), block),
+                )
+            };
+            let upper = within(b'A', b'Z');
+            let lower = within(b'a', b'z');
+            let digit = within(b'0', b'9');
+            let is62 = _mm256_cmpeq_epi8(block, _mm256_set1_epi8(c62 as i8));
+            let is63 = _mm256_cmpeq_epi8(block, _mm256_set1_epi8(c63 as i8));
+
+            let valid = _mm256_or_si256(
+                _mm256_or_si256(upper, lower),
+                _mm256_or_si256(digit, _mm256_or_si256(is62, is63)),
+            );
+            if _mm256_movemask_epi8(valid) != -1 {
+                break;
+            }
+
+            let offsets = _mm256_or_si256(
+                _mm256_or_si256(
+                    _mm256_and_si256(upper, _mm256_set1_epi8(-(b'A' as i8))),
+                    _mm256_and_si256(lower, _mm256_set1_epi8(26 - b'a' as i8)),
+                ),
+                _mm256_or_si256(
+                    _mm256_and_si256(digit, _mm256_set1_epi8(52 - b'0' as i8)),
+                    _mm256_or_si256(
+                        _mm256_and_si256(is62, _mm256_set1_epi8(o62.wrapping_neg())),
+                        _mm256_and_si256(is63, _mm256_set1_epi8(o63.wrapping_neg())),
+                    ),
+                ),
+            );
+            let values = _mm256_add_epi8(block, offsets);
+
+            let pairs = _mm256_maddubs_epi16(values, _mm256_set1_epi32(0x0140_0140));
+            let groups = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x0001_1000));
+            // The shuffle works within each half; the permute closes the gap
+            // between the two halves' 12 bytes
+            let bytes = _mm256_permutevar8x32_epi32(_mm256_shuffle_epi8(groups, pack), gather);
+
+            let end = decoded.len();
+            _mm256_storeu_si256(decoded.as_mut_ptr().add(end) as *mut __m256i, bytes);
+            decoded.set_len(end + 24);
+            used += 32;
+        }
+        used
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use super::special_offsets;
+    use std::arch::aarch64::*;
+
+    // The structured loads and stores spread groups across registers, byte
+    // N of each group in register N, so the fields are plain shifts away
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn encode(input: &[u8], alphabet: &[u8], encoded: &mut Vec<u8>) -> usize {
+        let table = uint8x16x4_t(
+            vld1q_u8(alphabet.as_ptr()),
+            vld1q_u8(alphabet.as_ptr().add(16)),
+            vld1q_u8(alphabet.as_ptr().add(32)),
+            vld1q_u8(alphabet.as_ptr().add(48)),
+        );
+        let mut used = 0;
+        while input.len() - used >= 48 {
+            let bytes = vld3q_u8(input.as_ptr().add(used));
+            let first = vshrq_n_u8::<2>(bytes.0);
+            let second = vorrq_u8(
+                vshlq_n_u8::<4>(vandq_u8(bytes.0, vdupq_n_u8(0x03))),
+                vshrq_n_u8::<4>(bytes.1),
+            );
+            let third = vorrq_u8(
+                vshlq_n_u8::<2>(vandq_u8(bytes.1, vdupq_n_u8(0x0f))),
+                vshrq_n_u8::<6>(bytes.2),
+            );
+            let fourth = vandq_u8(bytes.2, vdupq_n_u8(0x3f));
+
+            let symbols = uint8x16x4_t(
+                vqtbl4q_u8(table, first),
+                vqtbl4q_u8(table, second),
+                vqtbl4q_u8(table, third),
+                vqtbl4q_u8(table, fourth),
+            );
+            let end = encoded.len();
+            vst4q_u8(encoded.as_mut_ptr().add(end), symbols);
+            encoded.set_len(end + 64);
+            used += 48;
+        }
+        used
+    }
+
+    // The values of the symbols in SYMBOLS, and which lanes held one
+    #[target_feature(enable = "neon")]
+    unsafe fn values(symbols: uint8x16_t, c62: u8, c63: u8) -> (uint8x16_t, uint8x16_t) {
+        let (o62, o63) = special_offsets(c62, c63);
+        let within = |low: u8, count: u8| {
+            vcleq_u8(vsubq_u8(symbols, vdupq_n_u8(low)), vdupq_n_u8(count - 1))
+        };
+        let upper = within(b'A', 26);
+        let lower = within(b'a', 26);
+        let digit = within(b'0', 10);
+        let is62 = vceqq_u8(symbols, vdupq_n_u8(c62));
+        let is63 = vceqq_u8(symbols, vdupq_n_u8(c63));
+
+        let valid = vorrq_u8(
+            vorrq_u8(upper, lower),
+            vorrq_u8(digit, vorrq_u8(is62, is63)),
+        );
+        let offsets = vorrq_u8(
+            vorrq_u8(
+                vandq_u8(upper, vdupq_n_u8(b'A'.wrapping_neg())),
+                vandq_u8(lower, vdupq_n_u8(26u8.wrapping_sub(b'a'))),
+            ),
+            vorrq_u8(
+                vandq_u8(digit, vdupq_n_u8(52u8.wrapping_sub(b'0'))),
+                vorrq_u8(
+                    vandq_u8(is62, vdupq_n_u8(o62.wrapping_neg() as u8)),
+                    vandq_u8(is63, vdupq_n_u8(o63.wrapping_neg() as u8)),
+                ),
+            ),
+        );
+        (vaddq_u8(symbols, offsets), valid)
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn decode(input: &[u8], c62: u8, c63: u8, decoded: &mut Vec<u8>) -> usize {
+        let mut used = 0;
+        while input.len() - used >= 64 {
+            let symbols = vld4q_u8(input.as_ptr().add(used));
+            let (a, valid_a) = values(symbols.0, c62, c63);
+            let (b, valid_b) = values(symbols.1, c62, c63);
+            let (c, valid_c) = values(symbols.2, c62, c63);
+            let (d, valid_d) = values(symbols.3, c62, c63);
+            let valid = vandq_u8(vandq_u8(valid_a, valid_b), vandq_u8(valid_c, valid_d));
+            if vminvq_u8(valid) != 0xff {
+                break;
+            }
+
+            let bytes = uint8x16x3_t(
+                vorrq_u8(vshlq_n_u8::<2>(a), vshrq_n_u8::<4>(b)),
+                vorrq_u8(vshlq_n_u8::<4>(b), vshrq_n_u8::<2>(c)),
+                vorrq_u8(vshlq_n_u8::<6>(c), d),
+            );
+            let end = decoded.len();
+            vst3q_u8(decoded.as_mut_ptr().add(end), bytes);
+            decoded.set_len(end + 48);
+            used += 64;
+        }
+        used
+    }
+}
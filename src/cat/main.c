@@ -1,3 +1,4 @@
+#define _GNU_SOURCE  // copy_file_range and splice
 #include <stdio.h>
 #include <stdlib.h>
 #include <unistd.h>
@@ -6,8 +7,14 @@
 #include <errno.h>
 #include <stdbool.h>
 #include <getopt.h>
+#include <sys/stat.h>
+#ifdef __linux__
+#include <sys/sendfile.h>
+#endif
 
 #define BUFFER_SIZE (16 * 1024)  // 16KB buffer size for optimal performance
+#define PLAIN_BUFFER_SIZE (256 * 1024)  // Larger reads when nothing is transformed
+#define KERNEL_CHUNK (1 << 30)  // Bytes asked of each in-kernel copy call
 #define VERSION "1.0.0"
 
 // Structure to hold program options
@@ -21,15 +28,29 @@ struct Options {
     bool number_nonblank;  // -b flag
 };
 
+// Where the transform path is in the output, carried from file to file so
+// that numbering and squeezing treat the files as one stream
+struct State {
+    long line_number;      // Number of the next numbered line
+    bool at_line_start;    // Nothing of the current line output yet
+    bool last_was_blank;   // The previous line was empty
+};
+
 // Function prototypes
 static void usage(const char *program_name);
 static void version(void);
-static int process_file(const char *filename, const struct Options *opts);
-static int process_stdin(const struct Options *opts);
+static int process_file(const char *filename, const struct Options *opts, struct State *state);
+static int process_stdin(const struct Options *opts, struct State *state);
+static bool needs_transform(const struct Options *opts);
+static bool input_is_output(int fd);
+static int copy_plain(int fd, const char *filename);
+static int copy_in_kernel(int fd, const char *filename);
+static int copy_transformed(int fd, const char *filename, const struct Options *opts, struct State *state);
 static void display_char(unsigned char c, const struct Options *opts);
 
 int main(int argc, char *argv[]) {
     struct Options opts = {0};
+    struct State state = {1, true, false};
     int c;
     int exit_status = EXIT_SUCCESS;
 
@@ -46,15 +67,23 @@ int main(int argc, char *argv[]) {
         {0, 0, 0, 0}
     };
 
-    while ((c = getopt_long(argc, argv, "EnsTvAbhV", long_options, NULL)) != -1) {
+    while ((c = getopt_long(argc, argv, "EnsTvAbetuhV", long_options, NULL)) != -1) {
         switch (c) {
             case 'E': opts.show_ends = true; break;
             case 'n': opts.number_lines = true; break;
             case 's': opts.squeeze_blank = true; break;
             case 'T': opts.show_tabs = true; break;
             case 'v': opts.show_nonprinting = true; break;
-            case 'A': opts.show_all = true; break;
+            case 'A':
+                opts.show_all = true;
+                opts.show_nonprinting = true;
+                opts.show_ends = true;
+                opts.show_tabs = true;
+                break;
             case 'b': opts.number_nonblank = true; break;
+            case 'e': opts.show_nonprinting = true; opts.show_ends = true; break;
+            case 't': opts.show_nonprinting = true; opts.show_tabs = true; break;
+            case 'u': break;  // Output is never held back anyway
             case 'h': usage(argv[0]); return EXIT_SUCCESS;
             case 'V': version(); return EXIT_SUCCESS;
             default: usage(argv[0]); return EXIT_FAILURE;
@@ -63,12 +92,12 @@ int main(int argc, char *argv[]) {
 
     // If no files specified, process stdin
     if (optind == argc) {
-        return process_stdin(&opts);
+        return process_stdin(&opts, &state);
     }
 
     // Process each file
     for (; optind < argc; optind++) {
-        if (process_file(argv[optind], &opts) != EXIT_SUCCESS) {
+        if (process_file(argv[optind], &opts, &state) != EXIT_SUCCESS) {
             exit_status = EXIT_FAILURE;
         }
     }
@@ -82,10 +111,13 @@ static void usage(const char *program_name) {
     fprintf(stderr, "Options:\n");
     fprintf(stderr, "  -A, --show-all          equivalent to -vET\n");
     fprintf(stderr, "  -b, --number-nonblank   number nonempty output lines\n");
+    fprintf(stderr, "  -e                      equivalent to -vE\n");
     fprintf(stderr, "  -E, --show-ends         display $ at end of each line\n");
     fprintf(stderr, "  -n, --number            number all output lines\n");
     fprintf(stderr, "  -s, --squeeze-blank     suppress repeated empty output lines\n");
+    fprintf(stderr, "  -t                      equivalent to -vT\n");
     fprintf(stderr, "  -T, --show-tabs         display TAB characters as ^I\n");
+    fprintf(stderr, "  -u                      (ignored)\n");
     fprintf(stderr, "  -v, --show-nonprinting  use ^ and M- notation, except for LFD and TAB\n");
     fprintf(stderr, "      --help              display this help and exit\n");
     fprintf(stderr, "      --version           output version information and exit\n");
@@ -98,90 +130,242 @@ static void version(void) {
     printf("License: Apache 2.0\n");
 }
 
-static int process_file(const char *filename, const struct Options *opts) {
+static int process_file(const char *filename, const struct Options *opts, struct State *state) {
     int fd;
-    char buffer[BUFFER_SIZE];
-    ssize_t bytes_read;
-    static int line_number = 1;
-    bool last_was_blank = false;
-    
+    int status;
+
     fd = strcmp(filename, "-") == 0 ? STDIN_FILENO : open(filename, O_RDONLY);
     if (fd == -1) {
         fprintf(stderr, "asd-cat: %s: %s\n", filename, strerror(errno));
         return EXIT_FAILURE;
     }
 
-    while ((bytes_read = read(fd, buffer, BUFFER_SIZE)) > 0) {
-        for (ssize_t i = 0; i < bytes_read; i++) {
-            unsigned char c = buffer[i];
-            
-            // Handle line numbering
-            if ((opts->number_lines || opts->number_nonblank) && 
-                (i == 0 || buffer[i-1] == '\n')) {
-                if (!opts->number_nonblank || c != '\n') {
-                    printf("%6d\t", line_number++);
-                }
+    // Reading what is being appended to would never reach the end
+    if (input_is_output(fd)) {
+        fprintf(stderr, "asd-cat: %s: input file is output file\n", filename);
+        if (fd != STDIN_FILENO) {
+            close(fd);
+        }
+        return EXIT_FAILURE;
+    }
+
+    if (needs_transform(opts)) {
+        status = copy_transformed(fd, filename, opts, state);
+    } else {
+        status = copy_plain(fd, filename);
+    }
+
+    if (fd != STDIN_FILENO) {
+        close(fd);
+    }
+    return status;
+}
+
+static int process_stdin(const struct Options *opts, struct State *state) {
+    return process_file("-", opts, state);
+}
+
+static bool needs_transform(const struct Options *opts) {
+    return opts->show_ends || opts->show_tabs || opts->show_nonprinting ||
+           opts->squeeze_blank || opts->number_lines || opts->number_nonblank;
+}
+
+// Whether FD is the regular file standard output writes to, with data left
+// to read before its end
+static bool input_is_output(int fd) {
+    struct stat in_stat, out_stat;
+    if (fstat(fd, &in_stat) == -1 || fstat(STDOUT_FILENO, &out_stat) == -1) {
+        return false;
+    }
+    if (!S_ISREG(out_stat.st_mode) || in_stat.st_dev != out_stat.st_dev ||
+        in_stat.st_ino != out_stat.st_ino) {
+        return false;
+    }
+    off_t offset = lseek(fd, 0, SEEK_CUR);
+    return offset != -1 && offset < in_stat.st_size;
+}
+
+// Copy FD to standard output unchanged, in the kernel where it can be,
+// else through a large buffer
+static int copy_plain(int fd, const char *filename) {
+    static char buffer[PLAIN_BUFFER_SIZE];
+    ssize_t bytes_read;
+
+    int copied = copy_in_kernel(fd, filename);
+    if (copied != 0) {
+        return copied > 0 ? EXIT_SUCCESS : EXIT_FAILURE;
+    }
+
+    while ((bytes_read = read(fd, buffer, PLAIN_BUFFER_SIZE)) != 0) {
+        if (bytes_read == -1) {
+            if (errno == EINTR) {
+                continue;
             }
+            fprintf(stderr, "asd-cat: %s: %s\n", filename, strerror(errno));
+            return EXIT_FAILURE;
+        }
 
-            // Handle squeeze blank lines
-            if (opts->squeeze_blank && c == '\n') {
-                if (last_was_blank) {
+        for (ssize_t written = 0; written < bytes_read; ) {
+            ssize_t n = write(STDOUT_FILENO, buffer + written, bytes_read - written);
+            if (n == -1) {
+                if (errno == EINTR) {
                     continue;
                 }
-                last_was_blank = true;
-            } else {
-                last_was_blank = false;
+                fprintf(stderr, "asd-cat: write error: %s\n", strerror(errno));
+                exit(EXIT_FAILURE);
             }
+            written += n;
+        }
+    }
+    return EXIT_SUCCESS;
+}
 
-            display_char(c, opts);
+// Move the rest of FD to standard output without it passing through user
+// space: copy_file_range between regular files, splice when either end is
+// a pipe, and sendfile from a regular file to anything else. Returns 1
+// once everything is copied, -1 on an error already reported, or 0 if
+// none of them applies and the data has to be read and written instead.
+static int copy_in_kernel(int fd, const char *filename) {
+#ifdef __linux__
+    struct stat in_stat, out_stat;
+    if (fstat(fd, &in_stat) == -1 || fstat(STDOUT_FILENO, &out_stat) == -1) {
+        return 0;
+    }
+
+    enum { COPY_FILE_RANGE, SPLICE, SENDFILE, DONE } method = COPY_FILE_RANGE;
+    bool copied_any = false;
+
+    while (method != DONE) {
+        ssize_t n;
+        if (method == COPY_FILE_RANGE) {
+            if (!S_ISREG(in_stat.st_mode) || !S_ISREG(out_stat.st_mode)) {
+                method = SPLICE;
+                continue;
+            }
+            n = copy_file_range(fd, NULL, STDOUT_FILENO, NULL, KERNEL_CHUNK, 0);
+        } else if (method == SPLICE) {
+            if (!S_ISFIFO(in_stat.st_mode) && !S_ISFIFO(out_stat.st_mode)) {
+                method = SENDFILE;
+                continue;
+            }
+            n = splice(fd, NULL, STDOUT_FILENO, NULL, KERNEL_CHUNK, SPLICE_F_MOVE);
+        } else {
+            if (!S_ISREG(in_stat.st_mode)) {
+                break;
+            }
+            n = sendfile(STDOUT_FILENO, fd, NULL, KERNEL_CHUNK);
+        }
+
+        if (n > 0) {
+            copied_any = true;
+        } else if (n == 0) {
+            return 1;
+        } else if (errno == EINTR) {
+            continue;
+        } else if (!copied_any && (errno == EINVAL || errno == EXDEV || errno == ENOSYS ||
+                                   errno == EOPNOTSUPP || errno == EBADF)) {
+            // Not supported for these files; the next method may be
+            method++;
+        } else {
+            if (errno == EINVAL || errno == ENOSYS) {
+                // Given up on partway; read and write what is left
+                return 0;
+            }
+            // The call reads and writes at once; these can only be the writing
+            if (errno == EPIPE || errno == ENOSPC || errno == EDQUOT || errno == EFBIG) {
+                fprintf(stderr, "asd-cat: write error: %s\n", strerror(errno));
+                exit(EXIT_FAILURE);
+            }
+            fprintf(stderr, "asd-cat: %s: %s\n", filename, strerror(errno));
+            return -1;
         }
     }
+#else
+    (void)fd;
+    (void)filename;
+#endif
+    return 0;
+}
 
-    if (bytes_read == -1) {
-        fprintf(stderr, "asd-cat: %s: %s\n", filename, strerror(errno));
-        if (fd != STDIN_FILENO) {
-            close(fd);
+// Copy FD to standard output with the numbering, squeezing and display
+// options applied
+static int copy_transformed(int fd, const char *filename, const struct Options *opts, struct State *state) {
+    char buffer[BUFFER_SIZE];
+    ssize_t bytes_read;
+
+    while ((bytes_read = read(fd, buffer, BUFFER_SIZE)) != 0) {
+        if (bytes_read == -1) {
+            if (errno == EINTR) {
+                continue;
+            }
+            fprintf(stderr, "asd-cat: %s: %s\n", filename, strerror(errno));
+            return EXIT_FAILURE;
+        }
+
+        for (ssize_t i = 0; i < bytes_read; i++) {
+            unsigned char c = buffer[i];
+
+            if (state->at_line_start) {
+                bool blank = c == '\n';
+
+                // Only the first of a run of empty lines is kept
+                if (opts->squeeze_blank && blank && state->last_was_blank) {
+                    continue;
+                }
+                state->last_was_blank = blank;
+
+                // -b takes precedence over -n
+                if (opts->number_nonblank ? !blank : opts->number_lines) {
+                    printf("%6ld\t", state->line_number++);
+                }
+            }
+
+            display_char(c, opts);
+            state->at_line_start = c == '\n';
         }
-        return EXIT_FAILURE;
     }
 
-    if (fd != STDIN_FILENO) {
-        close(fd);
+    if (fflush(stdout) == EOF) {
+        fprintf(stderr, "asd-cat: write error: %s\n", strerror(errno));
+        exit(EXIT_FAILURE);
     }
     return EXIT_SUCCESS;
 }
 
-static int process_stdin(const struct Options *opts) {
-    return process_file("-", opts);
-}
-
 static void display_char(unsigned char c, const struct Options *opts) {
+    if (c == '\n') {
+        if (opts->show_ends) {
+            putchar('$');
+        }
+        putchar(c);
+        return;
+    }
+
+    if (c == '\t') {
+        if (opts->show_tabs) {
+            printf("^I");
+        } else {
+            putchar(c);
+        }
+        return;
+    }
+
     if (opts->show_nonprinting) {
-        if (c < 32 && c != '\t' && c != '\n') {
+        if (c >= 128) {
+            putchar('M');
+            putchar('-');
+            c -= 128;
+        }
+        if (c < 32) {
             putchar('^');
             putchar(c + 64);
             return;
-        } else if (c >= 127) {
-            putchar('M');
-            putchar('-');
-            if (c >= 128 + 32) {
-                putchar(c - 128);
-            } else {
-                putchar('^');
-                putchar(c - 128 + 64);
-            }
+        } else if (c == 127) {
+            putchar('^');
+            putchar('?');
             return;
         }
     }
 
-    if (opts->show_tabs && c == '\t') {
-        printf("^I");
-        return;
-    }
-
     putchar(c);
-
-    if (opts->show_ends && c == '\n') {
-        putchar('$');
-    }
 }
\ No newline at end of file
@@ -0,0 +1,369 @@
+// ASD CoreUtils - chmod
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod mode;
+
+use asd_coreutils_core::error::{strerror, usage_error};
+use asd_coreutils_core::mode::permissions;
+use clap::{Arg, ArgAction, Command};
+use mode::Mode;
+use std::env;
+use std::fs::{self, Permissions};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::process;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Verbosity {
+    Silent,
+    Normal,
+    Changes,
+    Verbose,
+}
+
+struct Options {
+    verbosity: Verbosity,
+    recursive: bool,
+    preserve_root: bool,
+    umask: u32,
+    // Whether to warn when the umask kept back bits the mode asked for
+    diagnose_surprises: bool,
+}
+
+fn main() {
+    // Modes such as "-w" look like options; GNU chmod takes the first one
+    // before "--" as the mode, and so does this one
+    let mut args: Vec<String> = env::args().collect();
+    let mut dash_mode = None;
+    for i in 1..args.len() {
+        if args[i] == "--" {
+            break;
+        }
+        if is_dash_mode(&args[i]) {
+            dash_mode = Some(args.remove(i));
+            break;
+        }
+    }
+
+    let matches = Command::new("chmod")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils chmod - change file mode bits")
+        .arg(
+            Arg::new("changes")
+                .short('c')
+                .long("changes")
+                .action(ArgAction::SetTrue)
+                .help("Like verbose but report only when a change is made"),
+        )
+        .arg(
+            Arg::new("silent")
+                .short('f')
+                .long("silent")
+                .visible_alias("quiet")
+                .action(ArgAction::SetTrue)
+                .help("Suppress most error messages"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .help("Output a diagnostic for every file processed"),
+        )
+        .arg(
+            Arg::new("no-preserve-root")
+                .long("no-preserve-root")
+                .action(ArgAction::SetTrue)
+                .overrides_with("preserve-root")
+                .help("Do not treat '/' specially (the default)"),
+        )
+        .arg(
+            Arg::new("preserve-root")
+                .long("preserve-root")
+                .action(ArgAction::SetTrue)
+                .overrides_with("no-preserve-root")
+                .help("Fail to operate recursively on '/'"),
+        )
+        .arg(
+            Arg::new("reference")
+                .long("reference")
+                .value_name("RFILE")
+                .help("Use RFILE's mode instead of specifying MODE values"),
+        )
+        .arg(
+            Arg::new("recursive")
+                .short('R')
+                .long("recursive")
+                .action(ArgAction::SetTrue)
+                .help("Change files and directories recursively"),
+        )
+        .arg(
+            Arg::new("operands")
+                .value_name("MODE[,MODE]... FILE")
+                .help("Mode as in 'u+rwx,g-w,o=rx', 'a+X' or '644', then files")
+                .num_args(0..),
+        )
+        .after_help(
+            "Each MODE is of the form '[ugoa]*([-+=]([rwxXst]*|[ugo]))+|[-+=][0-7]+'.\n\
+             Clauses without u, g, o or a are limited by the umask.",
+        )
+        .get_matches_from(args);
+
+    let mut operands: Vec<String> = matches
+        .get_many::<String>("operands")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    let reference = matches.get_one::<String>("reference");
+    let diagnose_surprises = dash_mode.is_some();
+    let mode_text = match (reference, dash_mode) {
+        (Some(_), Some(mode)) => {
+            // --reference gives the mode, so "-w" could only be a mistake
+            usage_error("chmod", &format!("invalid option -- '{}'", &mode[1..2]));
+        }
+        (Some(_), None) => None,
+        (None, Some(mode)) => Some(mode),
+        (None, None) if operands.is_empty() => usage_error("chmod", "missing operand"),
+        (None, None) => Some(operands.remove(0)),
+    };
+    if operands.is_empty() {
+        match &mode_text {
+            Some(mode) => usage_error("chmod", &format!("missing operand after '{}'", mode)),
+            None => usage_error("chmod", "missing operand"),
+        }
+    }
+
+    let mode = match (&mode_text, reference) {
+        (Some(text), _) => Mode::parse(text).unwrap_or_else(|| {
+            usage_error("chmod", &format!("invalid mode: '{}'", text));
+        }),
+        (None, Some(file)) => match fs::metadata(file) {
+            Ok(metadata) => Mode::parse(&format!("{:05o}", metadata.mode() & 0o7777)).unwrap(),
+            Err(err) => {
+                eprintln!(
+                    "chmod: failed to get attributes of '{}': {}",
+                    file,
+                    strerror(&err)
+                );
+                process::exit(1);
+            }
+        },
+        (None, None) => unreachable!(),
+    };
+
+    let verbosity = if matches.get_flag("verbose") {
+        Verbosity::Verbose
+    } else if matches.get_flag("changes") {
+        Verbosity::Changes
+    } else if matches.get_flag("silent") {
+        Verbosity::Silent
+    } else {
+        Verbosity::Normal
+    };
+    let options = Options {
+        verbosity,
+        recursive: matches.get_flag("recursive"),
+        preserve_root: matches.get_flag("preserve-root"),
+        umask: current_umask(),
+        diagnose_surprises,
+    };
+
+    let mut ok = true;
+    for file in &operands {
+        ok &= change_operand(Path::new(file), &mode, &options);
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
+
+// "-w", "-rwx,g+s" or "-022": modes that start with a dash
+fn is_dash_mode(arg: &str) -> bool {
+    arg.len() > 1
+        && arg.starts_with('-')
+        && !arg.starts_with("--")
+        && arg[1..]
+            .chars()
+            .all(|c| "rwxXstugoa+-=,01234567".contains(c))
+}
+
+fn current_umask() -> u32 {
+    unsafe {
+        let mask = libc::umask(0);
+        libc::umask(mask);
+        mask as u32
+    }
+}
+
+// A file named on the command line; symbolic links there are followed
+fn change_operand(path: &Path, mode: &Mode, options: &Options) -> bool {
+    if options.recursive && options.preserve_root && is_root(path) {
+        let name = path.display().to_string();
+        if name == "/" {
+            eprintln!("chmod: it is dangerous to operate recursively on '/'");
+        } else {
+            eprintln!(
+                "chmod: it is dangerous to operate recursively on '{}' (same as '/')",
+                name
+            );
+        }
+        eprintln!("chmod: use --no-preserve-root to override this failsafe");
+        return false;
+    }
+
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            if options.verbosity != Verbosity::Silent {
+                eprintln!(
+                    "chmod: cannot access '{}': {}",
+                    path.display(),
+                    strerror(&err)
+                );
+            }
+            return false;
+        }
+    };
+
+    let mut ok = change_file(path, &metadata, mode, options);
+    if options.recursive && metadata.is_dir() {
+        ok &= change_tree(path, mode, options);
+    }
+    ok
+}
+
+fn is_root(path: &Path) -> bool {
+    fs::canonicalize(path).is_ok_and(|real| real == Path::new("/"))
+}
+
+// The contents of DIR, depth first; symbolic links inside are left alone
+fn change_tree(dir: &Path, mode: &Mode, options: &Options) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            if options.verbosity != Verbosity::Silent {
+                eprintln!(
+                    "chmod: cannot read directory '{}': {}",
+                    dir.display(),
+                    strerror(&err)
+                );
+            }
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                if options.verbosity != Verbosity::Silent {
+                    eprintln!(
+                        "chmod: cannot read directory '{}': {}",
+                        dir.display(),
+                        strerror(&err)
+                    );
+                }
+                ok = false;
+                continue;
+            }
+        };
+        let path = entry.path();
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                if options.verbosity != Verbosity::Silent {
+                    eprintln!(
+                        "chmod: cannot access '{}': {}",
+                        path.display(),
+                        strerror(&err)
+                    );
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        if metadata.file_type().is_symlink() {
+            if options.verbosity == Verbosity::Verbose {
+                println!(
+                    "neither symbolic link '{}' nor referent has been changed",
+                    path.display()
+                );
+            }
+            continue;
+        }
+
+        ok &= change_file(&path, &metadata, mode, options);
+        if metadata.is_dir() {
+            ok &= change_tree(&path, mode, options);
+        }
+    }
+    ok
+}
+
+fn change_file(path: &Path, metadata: &fs::Metadata, mode: &Mode, options: &Options) -> bool {
+    let old = metadata.mode() & 0o7777;
+    let is_dir = metadata.is_dir();
+    let new = mode.apply(old, is_dir, options.umask);
+    let name = path.display();
+
+    let result = if new == old {
+        Ok(())
+    } else {
+        fs::set_permissions(path, Permissions::from_mode(new))
+    };
+
+    match (&result, options.verbosity) {
+        (Err(_), Verbosity::Changes | Verbosity::Verbose) => println!(
+            "failed to change mode of '{}' from {:04o} ({}) to {:04o} ({})",
+            name,
+            old,
+            permissions(old),
+            new,
+            permissions(new)
+        ),
+        (Ok(()), Verbosity::Changes | Verbosity::Verbose) if new != old => println!(
+            "mode of '{}' changed from {:04o} ({}) to {:04o} ({})",
+            name,
+            old,
+            permissions(old),
+            new,
+            permissions(new)
+        ),
+        (Ok(()), Verbosity::Verbose) => {
+            println!(
+                "mode of '{}' retained as {:04o} ({})",
+                name,
+                new,
+                permissions(new)
+            )
+        }
+        _ => {}
+    }
+
+    if let Err(err) = result {
+        if options.verbosity != Verbosity::Silent {
+            eprintln!(
+                "chmod: changing permissions of '{}': {}",
+                name,
+                strerror(&err)
+            );
+        }
+        return false;
+    }
+
+    // "chmod -w" is easily taken for "chmod a-w", but the umask cuts it
+    // down; as GNU chmod does, say so when that left the file short of it
+    let unmasked = mode.apply(old, is_dir, 0);
+    if options.diagnose_surprises && unmasked != new {
+        eprintln!(
+            "chmod: {}: new permissions are {}, not {}",
+            name,
+            permissions(new),
+            permissions(unmasked)
+        );
+        return false;
+    }
+    true
+}
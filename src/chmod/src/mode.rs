@@ -0,0 +1,192 @@
+// ASD CoreUtils - chmod
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Mode expressions: an octal number, or comma separated symbolic clauses
+// of the form [ugoa]*([-+=]([rwxXst]*|[ugo]|[0-7]+))+. A clause is kept as
+// a list of changes and only applied to a file's mode later, since X and
+// the copying forms (g=u) depend on the mode the file has.
+
+const ALL_BITS: u32 = 0o7777;
+const SET_ID_BITS: u32 = 0o6000;
+const EXECUTE_BITS: u32 = 0o111;
+
+// What a change takes its bits from
+#[derive(Clone, Copy)]
+enum Source {
+    Bits,
+    // X: execute bits only for directories and files executable by someone
+    BitsOrSearch,
+    // u, g or o: the bits that class already has
+    Copy,
+}
+
+#[derive(Clone, Copy)]
+struct Change {
+    op: char,
+    source: Source,
+    // The classes named, or every bit when none were
+    affected: u32,
+    value: u32,
+    // Whether the classes were named, and so whether the umask applies
+    named: bool,
+    // Set-ID bits of directories are kept unless the change names them
+    mentioned: u32,
+}
+
+pub struct Mode {
+    changes: Vec<Change>,
+}
+
+impl Mode {
+    pub fn parse(spec: &str) -> Option<Mode> {
+        if !spec.is_empty() && spec.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+            let value = u32::from_str_radix(spec, 8)
+                .ok()
+                .filter(|&mode| mode <= ALL_BITS)?;
+            // As in GNU chmod, "755" keeps a directory's set-ID bits and
+            // "00755" clears them
+            let mentioned = if spec.len() > 4 {
+                ALL_BITS
+            } else {
+                value | (!SET_ID_BITS & ALL_BITS)
+            };
+            return Some(Mode {
+                changes: vec![Change {
+                    op: '=',
+                    source: Source::Bits,
+                    affected: ALL_BITS,
+                    value,
+                    named: true,
+                    mentioned,
+                }],
+            });
+        }
+
+        let mut changes = Vec::new();
+        for clause in spec.split(',') {
+            let who_len = clause.find(|c| !"ugoa".contains(c)).unwrap_or(clause.len());
+            let (who, mut rest) = clause.split_at(who_len);
+            let affected = who.chars().fold(0, |mask, c| mask | class_bits(c));
+            let named = affected != 0;
+            let affected = if named { affected } else { ALL_BITS };
+
+            if rest.is_empty() {
+                return None;
+            }
+
+            while let Some(op) = rest.chars().next().filter(|c| "+-=".contains(*c)) {
+                let perms_len = rest[1..]
+                    .find(|c| "+-=".contains(c))
+                    .map_or(rest.len(), |i| i + 1);
+                let perms = &rest[1..perms_len];
+                let (source, value) = parse_perms(perms)?;
+                // Octal after an operator stands for exactly those bits, and
+                // only goes without a class
+                let octal = perms.bytes().next().is_some_and(|b| b.is_ascii_digit());
+                if octal && named {
+                    return None;
+                }
+                let named = named || octal;
+                changes.push(Change {
+                    op,
+                    source,
+                    affected,
+                    value,
+                    named,
+                    mentioned: if named { affected & value } else { value },
+                });
+                rest = &rest[perms_len..];
+            }
+
+            if !rest.is_empty() {
+                return None;
+            }
+        }
+
+        Some(Mode { changes })
+    }
+
+    // The mode a file with OLD ends up with. UMASK limits the bits of
+    // clauses that name no class, as chmod(1) specifies.
+    pub fn apply(&self, old: u32, is_dir: bool, umask: u32) -> u32 {
+        let mut mode = old & ALL_BITS;
+
+        for change in &self.changes {
+            let kept_ids = if is_dir {
+                SET_ID_BITS & !change.mentioned
+            } else {
+                0
+            };
+            let mut value = change.value;
+            match change.source {
+                Source::Bits => {}
+                Source::BitsOrSearch => {
+                    if is_dir || mode & EXECUTE_BITS != 0 {
+                        value |= EXECUTE_BITS;
+                    }
+                }
+                Source::Copy => {
+                    value &= mode;
+                    let spread = |bits: u32| if value & bits != 0 { bits } else { 0 };
+                    value |= spread(0o444) | spread(0o222) | spread(0o111);
+                }
+            }
+
+            value &= change.affected & !kept_ids;
+            if !change.named {
+                value &= !umask;
+            }
+
+            match change.op {
+                '+' => mode |= value,
+                '-' => mode &= !value,
+                _ => {
+                    let preserved = if change.named { !change.affected } else { 0 } | kept_ids;
+                    mode = (mode & preserved & ALL_BITS) | value;
+                }
+            }
+        }
+
+        mode
+    }
+}
+
+fn class_bits(class: char) -> u32 {
+    match class {
+        'u' => 0o4700,
+        'g' => 0o2070,
+        'o' => 0o1007,
+        _ => ALL_BITS,
+    }
+}
+
+fn parse_perms(perms: &str) -> Option<(Source, u32)> {
+    if !perms.is_empty() && perms.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        let value = u32::from_str_radix(perms, 8)
+            .ok()
+            .filter(|&mode| mode <= ALL_BITS)?;
+        return Some((Source::Bits, value));
+    }
+    if perms.len() == 1 && "ugo".contains(perms) {
+        return Some((Source::Copy, class_bits(perms.chars().next()?) & 0o777));
+    }
+
+    let mut source = Source::Bits;
+    let mut value = 0;
+    for c in perms.chars() {
+        value |= match c {
+            'r' => 0o444,
+            'w' => 0o222,
+            'x' => EXECUTE_BITS,
+            'X' => {
+                source = Source::BitsOrSearch;
+                0
+            }
+            's' => SET_ID_BITS,
+            't' => 0o1000,
+            _ => return None,
+        };
+    }
+    Some((source, value))
+}
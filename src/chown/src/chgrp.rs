@@ -0,0 +1,59 @@
+// ASD CoreUtils - chgrp
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod owner;
+
+use asd_coreutils_core::error::{fail, usage_error};
+use owner::Owner;
+use std::process;
+
+fn main() {
+    let matches = owner::command("chgrp", "ASD CoreUtils chgrp - change group ownership")
+        .override_usage(
+            "chgrp [OPTION]... GROUP FILE...\n       chgrp [OPTION]... --reference=RFILE FILE...",
+        )
+        .get_matches();
+
+    let mut operands: Vec<String> = matches
+        .get_many::<String>("operands")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    let new_group = match matches.get_one::<String>("reference") {
+        Some(file) => {
+            let reference = owner::reference_owner("chgrp", file);
+            Owner {
+                gid: reference.gid,
+                group_name: reference.group_name,
+                ..Owner::default()
+            }
+        }
+        None => {
+            if operands.is_empty() {
+                usage_error("chgrp", "missing operand");
+            }
+            let group = operands.remove(0);
+            if operands.is_empty() {
+                usage_error("chgrp", &format!("missing operand after '{}'", group));
+            }
+            if group.is_empty() {
+                Owner::default()
+            } else {
+                let gid = owner::lookup_group(&group).unwrap_or_else(|| {
+                    fail("chgrp", &format!("invalid group: '{}'", group));
+                });
+                Owner {
+                    gid: Some(gid),
+                    group_name: Some(group),
+                    ..Owner::default()
+                }
+            }
+        }
+    };
+    if operands.is_empty() {
+        usage_error("chgrp", "missing operand");
+    }
+
+    process::exit(owner::run("chgrp", &matches, &new_group, &operands));
+}
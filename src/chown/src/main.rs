@@ -0,0 +1,50 @@
+// ASD CoreUtils - chown
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod owner;
+
+use asd_coreutils_core::error::{fail, usage_error};
+use std::process;
+
+fn main() {
+    let matches = owner::command("chown", "ASD CoreUtils chown - change file owner and group")
+        .override_usage("chown [OPTION]... [OWNER][:[GROUP]] FILE...\n       chown [OPTION]... --reference=RFILE FILE...")
+        .after_help(
+            "Owner is unchanged if missing. Group is unchanged if missing, but changed\n\
+             to the login group if implied by a ':' following a symbolic OWNER.\n\
+             OWNER and GROUP may be numeric as well as symbolic.",
+        )
+        .get_matches();
+
+    let mut operands: Vec<String> = matches
+        .get_many::<String>("operands")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    let new_owner = match matches.get_one::<String>("reference") {
+        Some(file) => owner::reference_owner("chown", file),
+        None => {
+            if operands.is_empty() {
+                usage_error("chown", "missing operand");
+            }
+            let spec = operands.remove(0);
+            if operands.is_empty() {
+                usage_error("chown", &format!("missing operand after '{}'", spec));
+            }
+            let mut new_owner = owner::parse_spec("chown", &spec).unwrap_or_else(|message| {
+                fail("chown", &message);
+            });
+            // ":GROUP" still reports as a change of ownership, as GNU's does
+            if new_owner.user_name.is_none() && new_owner.group_name.is_some() {
+                new_owner.user_name = Some(String::new());
+            }
+            new_owner
+        }
+    };
+    if operands.is_empty() {
+        usage_error("chown", "missing operand");
+    }
+
+    process::exit(owner::run("chown", &matches, &new_owner, &operands));
+}
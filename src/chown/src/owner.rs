@@ -0,0 +1,666 @@
+// ASD CoreUtils - chown
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// What chown and chgrp share: the options, owner specs, and walking the
+// files named, with GNU's -H, -L and -P policies for symbolic links.
+// Directories are changed after their contents, as GNU chown does.
+
+use asd_coreutils_core::error::{fail, strerror};
+use asd_coreutils_core::metadata;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::collections::HashSet;
+use std::ffi::{CStr, CString};
+use std::fs::{self, Metadata};
+use std::io;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::ptr;
+
+// The ids to set, and how to name them in -v output. A name is None
+// where that id is left alone.
+#[derive(Default)]
+pub struct Owner {
+    pub uid: Option<libc::uid_t>,
+    pub gid: Option<libc::gid_t>,
+    pub user_name: Option<String>,
+    pub group_name: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Verbosity {
+    Silent,
+    Normal,
+    Changes,
+    Verbose,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Traversal {
+    // -P: follow no symbolic links
+    Physical,
+    // -H: follow those named on the command line
+    CommandLine,
+    // -L: follow every one
+    Logical,
+}
+
+struct Options {
+    program: &'static str,
+    verbosity: Verbosity,
+    recursive: bool,
+    traversal: Traversal,
+    // Whether a symbolic link's referent changes, rather than the link
+    dereference: bool,
+    preserve_root: bool,
+    from: Owner,
+}
+
+// The options both commands take; the owner or group operand is added by
+// the caller
+pub fn command(name: &'static str, about: &'static str) -> Command {
+    Command::new(name)
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about(about)
+        // -h is --no-dereference here
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("changes")
+                .short('c')
+                .long("changes")
+                .action(ArgAction::SetTrue)
+                .help("Like verbose but report only when a change is made"),
+        )
+        .arg(
+            Arg::new("silent")
+                .short('f')
+                .long("silent")
+                .visible_alias("quiet")
+                .action(ArgAction::SetTrue)
+                .help("Suppress most error messages"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .help("Output a diagnostic for every file processed"),
+        )
+        .arg(
+            Arg::new("dereference")
+                .long("dereference")
+                .action(ArgAction::SetTrue)
+                .overrides_with("no-dereference")
+                .help("Affect the referent of each symbolic link (the default), not the link"),
+        )
+        .arg(
+            Arg::new("no-dereference")
+                .short('h')
+                .long("no-dereference")
+                .action(ArgAction::SetTrue)
+                .overrides_with("dereference")
+                .help("Affect symbolic links instead of any referenced file"),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_name("CURRENT_OWNER:CURRENT_GROUP")
+                .help("Change only files whose owner and/or group match these"),
+        )
+        .arg(
+            Arg::new("no-preserve-root")
+                .long("no-preserve-root")
+                .action(ArgAction::SetTrue)
+                .overrides_with("preserve-root")
+                .help("Do not treat '/' specially (the default)"),
+        )
+        .arg(
+            Arg::new("preserve-root")
+                .long("preserve-root")
+                .action(ArgAction::SetTrue)
+                .overrides_with("no-preserve-root")
+                .help("Fail to operate recursively on '/'"),
+        )
+        .arg(
+            Arg::new("reference")
+                .long("reference")
+                .value_name("RFILE")
+                .help("Use RFILE's ownership rather than specifying values"),
+        )
+        .arg(
+            Arg::new("recursive")
+                .short('R')
+                .long("recursive")
+                .action(ArgAction::SetTrue)
+                .help("Operate on files and directories recursively"),
+        )
+        .arg(
+            Arg::new("H")
+                .short('H')
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["L", "P"])
+                .help("With -R, traverse symbolic links to directories named on the command line"),
+        )
+        .arg(
+            Arg::new("L")
+                .short('L')
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["H", "P"])
+                .help("With -R, traverse every symbolic link to a directory"),
+        )
+        .arg(
+            Arg::new("P")
+                .short('P')
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["H", "L"])
+                .help("With -R, do not traverse any symbolic links (the default)"),
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .arg(Arg::new("operands").value_name("FILE").num_args(0..))
+}
+
+// The owner and group of --reference's file, named as -v shows them
+pub fn reference_owner(program: &str, file: &str) -> Owner {
+    match fs::metadata(file) {
+        Ok(metadata) => Owner {
+            uid: Some(metadata.uid()),
+            gid: Some(metadata.gid()),
+            user_name: Some(user_name(metadata.uid())),
+            group_name: Some(group_name(metadata.gid())),
+        },
+        Err(err) => fail(
+            program,
+            &format!("failed to get attributes of '{}': {}", file, strerror(&err)),
+        ),
+    }
+}
+
+// OWNER, OWNER:GROUP, OWNER:, :GROUP or the old OWNER.GROUP
+pub fn parse_spec(program: &str, spec: &str) -> Result<Owner, String> {
+    let (user, group) = match spec.find(':') {
+        Some(colon) => (&spec[..colon], Some(&spec[colon + 1..])),
+        // A dot only separates when the whole is not a user's name
+        None => match spec.find('.') {
+            Some(dot) if lookup_user(spec).is_none() => {
+                eprintln!("{}: warning: '.' should be ':': '{}'", program, spec);
+                (&spec[..dot], Some(&spec[dot + 1..]))
+            }
+            _ => (spec, None),
+        },
+    };
+
+    let mut owner = Owner::default();
+    if !user.is_empty() {
+        let Some((uid, login_gid)) = lookup_user(user) else {
+            return Err(match group {
+                Some("") => format!("invalid spec: '{}'", spec),
+                _ => format!("invalid user: '{}'", spec),
+            });
+        };
+        owner.uid = Some(uid);
+        owner.user_name = Some(user.to_string());
+
+        // "OWNER:" means the owner's login group
+        if group == Some("") {
+            let Some(gid) = login_gid else {
+                return Err(format!("invalid spec: '{}'", spec));
+            };
+            owner.gid = Some(gid);
+            owner.group_name = Some(group_name(gid));
+        }
+    }
+
+    if let Some(group) = group.filter(|group| !group.is_empty()) {
+        let Some(gid) = lookup_group(group) else {
+            return Err(format!("invalid group: '{}'", spec));
+        };
+        owner.gid = Some(gid);
+        owner.group_name = Some(group.to_string());
+    }
+    Ok(owner)
+}
+
+// Runs the command over FILES, giving the exit status
+pub fn run(program: &'static str, matches: &ArgMatches, owner: &Owner, files: &[String]) -> i32 {
+    let from = match matches.get_one::<String>("from") {
+        Some(spec) => parse_spec(program, spec).unwrap_or_else(|message| fail(program, &message)),
+        None => Owner::default(),
+    };
+
+    let recursive = matches.get_flag("recursive");
+    let traversal = if matches.get_flag("L") {
+        Traversal::Logical
+    } else if matches.get_flag("H") {
+        Traversal::CommandLine
+    } else {
+        Traversal::Physical
+    };
+
+    // Without -R, links are followed unless -h says not to; with -R -P
+    // nothing is followed, so the links themselves change
+    let dereference = if matches.get_flag("no-dereference") {
+        false
+    } else if recursive && traversal == Traversal::Physical {
+        if matches.get_flag("dereference") {
+            fail(program, "-R --dereference requires either -H or -L");
+        }
+        false
+    } else {
+        true
+    };
+
+    let verbosity = if matches.get_flag("verbose") {
+        Verbosity::Verbose
+    } else if matches.get_flag("changes") {
+        Verbosity::Changes
+    } else if matches.get_flag("silent") {
+        Verbosity::Silent
+    } else {
+        Verbosity::Normal
+    };
+
+    let options = Options {
+        program,
+        verbosity,
+        recursive,
+        traversal,
+        dereference,
+        preserve_root: matches.get_flag("preserve-root"),
+        from,
+    };
+
+    let mut ok = true;
+    for file in files {
+        ok &= change_operand(Path::new(file), owner, &options);
+    }
+    if ok {
+        0
+    } else {
+        1
+    }
+}
+
+fn change_operand(path: &Path, owner: &Owner, options: &Options) -> bool {
+    if options.recursive && options.preserve_root && is_root(path) {
+        let name = path.display().to_string();
+        if name == "/" {
+            eprintln!(
+                "{}: it is dangerous to operate recursively on '/'",
+                options.program
+            );
+        } else {
+            eprintln!(
+                "{}: it is dangerous to operate recursively on '{}' (same as '/')",
+                options.program, name
+            );
+        }
+        eprintln!(
+            "{}: use --no-preserve-root to override this failsafe",
+            options.program
+        );
+        return false;
+    }
+
+    let mut ancestors = HashSet::new();
+    change_path(path, owner, options, true, &mut ancestors)
+}
+
+fn is_root(path: &Path) -> bool {
+    fs::canonicalize(path).is_ok_and(|real| real == Path::new("/"))
+}
+
+// One file and, with -R, what is under it. ANCESTORS holds the directories
+// being walked, so that -L does not go round a loop of links forever.
+fn change_path(
+    path: &Path,
+    owner: &Owner,
+    options: &Options,
+    command_line: bool,
+    ancestors: &mut HashSet<(u64, u64)>,
+) -> bool {
+    let link_metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            if options.verbosity != Verbosity::Silent {
+                eprintln!(
+                    "{}: cannot access '{}': {}",
+                    options.program,
+                    path.display(),
+                    strerror(&err)
+                );
+            }
+            if options.verbosity == Verbosity::Verbose {
+                report_failure(path, owner, None);
+            }
+            return false;
+        }
+    };
+
+    let is_link = link_metadata.file_type().is_symlink();
+    let traverse_link = match options.traversal {
+        Traversal::Physical => false,
+        Traversal::CommandLine => command_line,
+        Traversal::Logical => true,
+    };
+    let mut ok = true;
+    if options.recursive {
+        let target = if is_link && traverse_link {
+            fs::metadata(path).ok()
+        } else {
+            Some(link_metadata.clone())
+        };
+        if let Some(target) = target.filter(|target| target.is_dir()) {
+            let key = (target.dev(), target.ino());
+            // A link back up the tree (only -L meets these) is not gone
+            // down again, as with GNU's fts
+            if ancestors.insert(key) {
+                ok &= change_contents(path, owner, options, ancestors);
+                ancestors.remove(&key);
+            }
+        }
+    }
+
+    ok & change_file(
+        path,
+        &link_metadata,
+        is_link && options.dereference,
+        owner,
+        options,
+    )
+}
+
+fn change_contents(
+    dir: &Path,
+    owner: &Owner,
+    options: &Options,
+    ancestors: &mut HashSet<(u64, u64)>,
+) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            if options.verbosity != Verbosity::Silent {
+                eprintln!(
+                    "{}: cannot read directory '{}': {}",
+                    options.program,
+                    dir.display(),
+                    strerror(&err)
+                );
+            }
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for entry in entries {
+        match entry {
+            Ok(entry) => ok &= change_path(&entry.path(), owner, options, false, ancestors),
+            Err(err) => {
+                if options.verbosity != Verbosity::Silent {
+                    eprintln!(
+                        "{}: cannot read directory '{}': {}",
+                        options.program,
+                        dir.display(),
+                        strerror(&err)
+                    );
+                }
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+// Changes PATH, or what it links to when DEREFERENCE is set
+fn change_file(
+    path: &Path,
+    link_metadata: &Metadata,
+    dereference: bool,
+    owner: &Owner,
+    options: &Options,
+) -> bool {
+    let metadata = if dereference {
+        match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                if options.verbosity != Verbosity::Silent {
+                    eprintln!(
+                        "{}: cannot dereference '{}': {}",
+                        options.program,
+                        path.display(),
+                        strerror(&err)
+                    );
+                }
+                if options.verbosity == Verbosity::Verbose {
+                    report_failure(path, owner, None);
+                }
+                return false;
+            }
+        }
+    } else {
+        link_metadata.clone()
+    };
+
+    // --from: leave files owned by anyone else alone
+    let matches_from = options.from.uid.is_none_or(|uid| uid == metadata.uid())
+        && options.from.gid.is_none_or(|gid| gid == metadata.gid());
+    if !matches_from {
+        if options.verbosity == Verbosity::Verbose {
+            report_retained(path, owner, &metadata);
+        }
+        return true;
+    }
+
+    if let Err(err) = set_owner(path, owner, dereference) {
+        if options.verbosity != Verbosity::Silent {
+            let what = if owner.user_name.is_some() {
+                "ownership"
+            } else {
+                "group"
+            };
+            eprintln!(
+                "{}: changing {} of '{}': {}",
+                options.program,
+                what,
+                path.display(),
+                strerror(&err)
+            );
+        }
+        if options.verbosity == Verbosity::Verbose {
+            report_failure(path, owner, Some(&metadata));
+        }
+        return false;
+    }
+
+    let changed = owner.uid.is_some_and(|uid| uid != metadata.uid())
+        || owner.gid.is_some_and(|gid| gid != metadata.gid());
+    if changed && options.verbosity >= Verbosity::Changes {
+        let (old, new) = (old_spec(owner, &metadata), new_spec(owner));
+        if owner.user_name.is_some() {
+            println!(
+                "changed ownership of '{}' from {} to {}",
+                path.display(),
+                old,
+                new
+            );
+        } else {
+            println!(
+                "changed group of '{}' from {} to {}",
+                path.display(),
+                old,
+                new
+            );
+        }
+    } else if !changed && options.verbosity == Verbosity::Verbose {
+        report_retained(path, owner, &metadata);
+    }
+    true
+}
+
+fn set_owner(path: &Path, owner: &Owner, dereference: bool) -> io::Result<()> {
+    let name = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    // -1 leaves that id unchanged
+    let uid = owner.uid.unwrap_or(libc::uid_t::MAX);
+    let gid = owner.gid.unwrap_or(libc::gid_t::MAX);
+
+    let result = if dereference {
+        unsafe { libc::chown(name.as_ptr(), uid, gid) }
+    } else {
+        unsafe { libc::lchown(name.as_ptr(), uid, gid) }
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+fn report_retained(path: &Path, owner: &Owner, metadata: &Metadata) {
+    match (&owner.user_name, &owner.group_name) {
+        (None, None) => println!("ownership of '{}' retained", path.display()),
+        (Some(_), _) => println!(
+            "ownership of '{}' retained as {}",
+            path.display(),
+            old_spec(owner, metadata)
+        ),
+        (None, Some(_)) => println!(
+            "group of '{}' retained as {}",
+            path.display(),
+            old_spec(owner, metadata)
+        ),
+    }
+}
+
+fn report_failure(path: &Path, owner: &Owner, metadata: Option<&Metadata>) {
+    let what = if owner.user_name.is_some() {
+        "ownership"
+    } else {
+        "group"
+    };
+    match metadata {
+        Some(metadata) => println!(
+            "failed to change {} of '{}' from {} to {}",
+            what,
+            path.display(),
+            old_spec(owner, metadata),
+            new_spec(owner)
+        ),
+        None => println!(
+            "failed to change {} of '{}' to {}",
+            what,
+            path.display(),
+            new_spec(owner)
+        ),
+    }
+}
+
+// The file's owner and group, as far as the change concerns them
+fn old_spec(owner: &Owner, metadata: &Metadata) -> String {
+    let user = owner.user_name.as_ref().map(|_| user_name(metadata.uid()));
+    let group = owner
+        .group_name
+        .as_ref()
+        .map(|_| group_name(metadata.gid()));
+    join_spec(user.as_deref(), group.as_deref())
+}
+
+fn new_spec(owner: &Owner) -> String {
+    join_spec(owner.user_name.as_deref(), owner.group_name.as_deref())
+}
+
+fn join_spec(user: Option<&str>, group: Option<&str>) -> String {
+    match (user, group) {
+        (Some(user), Some(group)) => format!("{}:{}", user, group),
+        (Some(user), None) => user.to_string(),
+        (None, Some(group)) => group.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+// Accepts a user name, falling back to a numeric id; also gives the
+// user's login group where there is a passwd entry
+fn lookup_user(name: &str) -> Option<(libc::uid_t, Option<libc::gid_t>)> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwnam_r(
+            c_name.as_ptr(),
+            &mut pwd,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        );
+        if !result.is_null() {
+            return Some((pwd.pw_uid, Some(pwd.pw_gid)));
+        }
+    }
+
+    let uid = name.parse().ok()?;
+    Some((uid, user_entry(uid).map(|(_, gid)| gid)))
+}
+
+// Accepts a group name, falling back to a numeric id
+pub fn lookup_group(name: &str) -> Option<libc::gid_t> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut grp: libc::group = mem::zeroed();
+        let mut result: *mut libc::group = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getgrnam_r(
+            c_name.as_ptr(),
+            &mut grp,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        );
+        if !result.is_null() {
+            return Some(grp.gr_gid);
+        }
+    }
+
+    name.parse().ok()
+}
+
+fn user_entry(uid: libc::uid_t) -> Option<(String, libc::gid_t)> {
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwuid_r(
+            uid,
+            &mut pwd,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        );
+        if result.is_null() {
+            return None;
+        }
+        let name = CStr::from_ptr(pwd.pw_name).to_string_lossy().into_owned();
+        Some((name, pwd.pw_gid))
+    }
+}
+
+// The user's name, or the number when there is none
+pub fn user_name(uid: libc::uid_t) -> String {
+    user_entry(uid).map_or_else(|| uid.to_string(), |(name, _)| name)
+}
+
+pub fn group_name(gid: libc::gid_t) -> String {
+    metadata::group_name(gid).unwrap_or_else(|| gid.to_string())
+}
@@ -0,0 +1,277 @@
+// ASD CoreUtils - chroot
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail_status, strerror, usage_error_status};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::ffi::{CString, OsString};
+use std::io;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::process;
+use std::ptr;
+
+const EXIT_FAILURE: i32 = 125;
+const EXIT_CANNOT_INVOKE: i32 = 126;
+const EXIT_ENOENT: i32 = 127;
+
+// Identity to switch to after the chroot; anything left as None is kept
+#[derive(Default)]
+struct Credentials {
+    uid: Option<libc::uid_t>,
+    gid: Option<libc::gid_t>,
+    groups: Option<Vec<libc::gid_t>>,
+}
+
+struct User {
+    uid: libc::uid_t,
+    gid: libc::gid_t,
+    name: CString,
+}
+
+fn main() {
+    let matches = Command::new("chroot")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils chroot - run command or interactive shell with special root directory")
+        .after_help("If no command is given, run '\"$SHELL\" -i' (default: '/bin/sh -i').")
+        .trailing_var_arg(true)
+        .arg(
+            Arg::new("userspec")
+                .long("userspec")
+                .value_name("USER:GROUP")
+                .help("Specify user and group (ID or name) to use"),
+        )
+        .arg(
+            Arg::new("groups")
+                .long("groups")
+                .value_name("G_LIST")
+                .help("Specify supplementary groups as g1,g2,..,gN"),
+        )
+        .arg(
+            Arg::new("skip-chdir")
+                .long("skip-chdir")
+                .help("Do not change working directory to '/'")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("newroot")
+                .value_name("NEWROOT")
+                .help("Directory to use as the new root")
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .arg(
+            Arg::new("command")
+                .value_name("COMMAND")
+                .help("Command to run, followed by its arguments")
+                .num_args(1..)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .try_get_matches()
+        .unwrap_or_else(|err| {
+            let code = if err.use_stderr() { EXIT_FAILURE } else { 0 };
+            let _ = err.print();
+            process::exit(code);
+        });
+
+    let Some(newroot) = matches.get_one::<OsString>("newroot") else {
+        usage_error_status("chroot", EXIT_FAILURE, "missing operand");
+    };
+
+    // Names are resolved against the current root, whose databases are the
+    // ones the caller knows; the new root may not even have /etc/passwd
+    let credentials = match resolve_credentials(
+        matches.get_one::<String>("userspec").map(String::as_str),
+        matches.get_one::<String>("groups").map(String::as_str),
+    ) {
+        Ok(credentials) => credentials,
+        Err(message) => fail_status("chroot", EXIT_FAILURE, &message),
+    };
+
+    let display_root = newroot.to_string_lossy();
+    let c_root = CString::new(newroot.as_bytes()).unwrap_or_else(|_| {
+        fail_status(
+            "chroot",
+            EXIT_FAILURE,
+            &format!("cannot change root directory to '{}': invalid file name", display_root),
+        )
+    });
+
+    if unsafe { libc::chroot(c_root.as_ptr()) } != 0 {
+        eprintln!(
+            "chroot: cannot change root directory to '{}': {}",
+            display_root,
+            strerror(&io::Error::last_os_error())
+        );
+        process::exit(EXIT_FAILURE);
+    }
+
+    if !matches.get_flag("skip-chdir") {
+        if let Err(err) = env::set_current_dir("/") {
+            fail_status(
+                "chroot",
+                EXIT_FAILURE,
+                &format!("cannot change directory to '/': {}", strerror(&err)),
+            );
+        }
+    }
+
+    if let Err(message) = drop_privileges(&credentials) {
+        fail_status("chroot", EXIT_FAILURE, &message);
+    }
+
+    let (program, args): (OsString, Vec<OsString>) = match matches.get_many::<OsString>("command") {
+        Some(mut command) => (command.next().unwrap().clone(), command.cloned().collect()),
+        None => (
+            env::var_os("SHELL").unwrap_or_else(|| OsString::from("/bin/sh")),
+            vec![OsString::from("-i")],
+        ),
+    };
+
+    let err = process::Command::new(&program).args(args).exec();
+
+    let code = if err.kind() == io::ErrorKind::NotFound {
+        EXIT_ENOENT
+    } else {
+        EXIT_CANNOT_INVOKE
+    };
+    eprintln!("chroot: failed to run command '{}': {}", program.to_string_lossy(), strerror(&err));
+    process::exit(code);
+}
+
+// Parses --userspec=USER:GROUP and --groups=G1,G2 into numeric ids
+fn resolve_credentials(userspec: Option<&str>, groups: Option<&str>) -> Result<Credentials, String> {
+    let mut credentials = Credentials::default();
+    let mut user = None;
+
+    if let Some(spec) = userspec {
+        let (user_part, group_part) = match spec.split_once(':') {
+            Some((user, group)) => (user, Some(group)),
+            None => (spec, None),
+        };
+
+        if !user_part.is_empty() {
+            match lookup_user(user_part) {
+                Some(found) => {
+                    credentials.uid = Some(found.uid);
+                    user = Some(found);
+                }
+                None => {
+                    let uid = user_part.parse().map_err(|_| "invalid user".to_string())?;
+                    credentials.uid = Some(uid);
+                }
+            }
+        }
+
+        match group_part.filter(|group| !group.is_empty()) {
+            Some(group) => {
+                let gid = lookup_group(group).ok_or_else(|| "invalid group".to_string())?;
+                credentials.gid = Some(gid);
+            }
+            // A user on its own also brings its login group
+            None => match (&user, credentials.uid) {
+                (Some(user), _) => credentials.gid = Some(user.gid),
+                (None, Some(uid)) => return Err(format!("no group specified for unknown uid: {}", uid)),
+                (None, None) => {}
+            },
+        }
+    }
+
+    if let Some(list) = groups {
+        let mut gids = Vec::new();
+        for name in list.split(',').filter(|name| !name.is_empty()) {
+            gids.push(lookup_group(name).ok_or_else(|| format!("invalid group '{}'", name))?);
+        }
+        credentials.groups = Some(gids);
+    } else if let (Some(user), Some(gid)) = (&user, credentials.gid) {
+        // Without --groups, a named user gets its usual supplementary groups
+        credentials.groups = Some(group_list(user, gid)?);
+    }
+
+    Ok(credentials)
+}
+
+// Groups go first, since changing them needs the privileges that giving
+// up the user id takes away
+fn drop_privileges(credentials: &Credentials) -> Result<(), String> {
+    if let Some(groups) = &credentials.groups {
+        if unsafe { libc::setgroups(groups.len(), groups.as_ptr()) } != 0 {
+            return Err(format!("failed to set supplemental groups: {}", strerror(&io::Error::last_os_error())));
+        }
+    }
+
+    if let Some(gid) = credentials.gid {
+        if unsafe { libc::setgid(gid) } != 0 {
+            return Err(format!("failed to set group-ID: {}", strerror(&io::Error::last_os_error())));
+        }
+    }
+
+    if let Some(uid) = credentials.uid {
+        if unsafe { libc::setuid(uid) } != 0 {
+            return Err(format!("failed to set user-ID: {}", strerror(&io::Error::last_os_error())));
+        }
+    }
+
+    Ok(())
+}
+
+fn lookup_user(name: &str) -> Option<User> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwnam_r(c_name.as_ptr(), &mut pwd, buffer.as_mut_ptr(), buffer.len(), &mut result);
+        if result.is_null() {
+            return None;
+        }
+
+        Some(User {
+            uid: pwd.pw_uid,
+            gid: pwd.pw_gid,
+            name: c_name,
+        })
+    }
+}
+
+// Accepts a group name, falling back to a numeric id
+fn lookup_group(name: &str) -> Option<libc::gid_t> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut grp: libc::group = mem::zeroed();
+        let mut result: *mut libc::group = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getgrnam_r(c_name.as_ptr(), &mut grp, buffer.as_mut_ptr(), buffer.len(), &mut result);
+        if !result.is_null() {
+            return Some(grp.gr_gid);
+        }
+    }
+
+    name.parse().ok()
+}
+
+// The user's supplementary groups from the group database, plus GID
+fn group_list(user: &User, gid: libc::gid_t) -> Result<Vec<libc::gid_t>, String> {
+    let mut groups: Vec<libc::gid_t> = vec![0; 64];
+
+    loop {
+        let mut count = groups.len() as libc::c_int;
+        let found = unsafe { libc::getgrouplist(user.name.as_ptr(), gid, groups.as_mut_ptr(), &mut count) };
+
+        if found >= 0 {
+            groups.truncate(count as usize);
+            return Ok(groups);
+        }
+        // count now holds the number needed
+        if count as usize <= groups.len() {
+            return Err(format!("failed to get supplemental groups for '{}'", user.name.to_string_lossy()));
+        }
+        groups.resize(count as usize, 0);
+    }
+}
@@ -0,0 +1,167 @@
+// ASD CoreUtils - comm
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+struct Columns {
+    show: [bool; 3],
+    delimiter: Vec<u8>,
+}
+
+impl Columns {
+    // Writes LINE in COLUMN (0-based), indented by one delimiter per
+    // visible column to its left
+    fn write(&self, out: &mut impl Write, column: usize, line: &[u8]) -> io::Result<()> {
+        if !self.show[column] {
+            return Ok(());
+        }
+
+        for shown in &self.show[..column] {
+            if *shown {
+                out.write_all(&self.delimiter)?;
+            }
+        }
+        out.write_all(line)?;
+        out.write_all(b"\n")
+    }
+}
+
+fn main() {
+    let matches = Command::new("comm")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils comm - compare two sorted files line by line")
+        .after_help(
+            "With no options, produce three-column output. Column one contains\n\
+             lines unique to FILE1, column two contains lines unique to FILE2,\n\
+             and column three contains lines common to both files.",
+        )
+        .arg(
+            Arg::new("suppress-1")
+                .short('1')
+                .help("Suppress column 1 (lines unique to FILE1)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("suppress-2")
+                .short('2')
+                .help("Suppress column 2 (lines unique to FILE2)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("suppress-3")
+                .short('3')
+                .help("Suppress column 3 (lines that appear in both files)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output-delimiter")
+                .long("output-delimiter")
+                .value_name("STR")
+                .help("Separate columns with STR instead of a TAB"),
+        )
+        .arg(
+            Arg::new("file1")
+                .value_name("FILE1")
+                .help("First sorted file, or - for standard input")
+                .required(true),
+        )
+        .arg(
+            Arg::new("file2")
+                .value_name("FILE2")
+                .help("Second sorted file, or - for standard input")
+                .required(true),
+        )
+        .get_matches();
+
+    let columns = Columns {
+        show: [
+            !matches.get_flag("suppress-1"),
+            !matches.get_flag("suppress-2"),
+            !matches.get_flag("suppress-3"),
+        ],
+        delimiter: matches
+            .get_one::<String>("output-delimiter")
+            .map(|d| d.as_bytes().to_vec())
+            .unwrap_or_else(|| b"\t".to_vec()),
+    };
+
+    let file1 = matches.get_one::<String>("file1").unwrap();
+    let file2 = matches.get_one::<String>("file2").unwrap();
+    if file1 == "-" && file2 == "-" {
+        fail("comm", "both files cannot be standard input");
+    }
+
+    let mut input1 = open_input(file1);
+    let mut input2 = open_input(file2);
+
+    if let Err(err) = compare(&mut input1, &mut input2, &columns) {
+        fail("comm", &strerror(&err));
+    }
+}
+
+fn open_input(path: &str) -> Box<dyn BufRead> {
+    if path == "-" {
+        return Box::new(BufReader::new(io::stdin()));
+    }
+
+    match File::open(path) {
+        Ok(file) => Box::new(BufReader::new(file)),
+        Err(err) => fail("comm", &format!("{}: {}", path, strerror(&err))),
+    }
+}
+
+fn read_line(input: &mut dyn BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut line = Vec::new();
+    if input.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+fn compare(input1: &mut dyn BufRead, input2: &mut dyn BufRead, columns: &Columns) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let mut line1 = read_line(input1)?;
+    let mut line2 = read_line(input2)?;
+
+    loop {
+        match (&line1, &line2) {
+            (None, None) => break,
+            (Some(a), None) => {
+                columns.write(&mut out, 0, a)?;
+                line1 = read_line(input1)?;
+            }
+            (None, Some(b)) => {
+                columns.write(&mut out, 1, b)?;
+                line2 = read_line(input2)?;
+            }
+            (Some(a), Some(b)) => match a.cmp(b) {
+                Ordering::Less => {
+                    columns.write(&mut out, 0, a)?;
+                    line1 = read_line(input1)?;
+                }
+                Ordering::Greater => {
+                    columns.write(&mut out, 1, b)?;
+                    line2 = read_line(input2)?;
+                }
+                Ordering::Equal => {
+                    columns.write(&mut out, 2, a)?;
+                    line1 = read_line(input1)?;
+                    line2 = read_line(input2)?;
+                }
+            },
+        }
+    }
+
+    out.flush()
+}
@@ -0,0 +1,181 @@
+#define _GNU_SOURCE  // copy_file_range, SEEK_DATA and SEEK_HOLE
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+#include <errno.h>
+
+#include "copy.h"
+#include "progress.h"
+
+// Buffer for copies that go through userspace
+#define BUFFER_SIZE (1024 * 1024)
+
+volatile sig_atomic_t copy_interrupted = 0;
+
+// Amount to hand to the kernel per call, small enough to redraw in between
+size_t copy_chunk(void) {
+    return progress.mode == PROGRESS_NEVER ? KERNEL_COPY_CHUNK : PROGRESS_CHUNK;
+}
+
+// Errors meaning a kernel copy method cannot be used for this pair of files,
+// so the next one should be tried
+int copy_unsupported(int err) {
+    return err == ENOSYS || err == EXDEV || err == EINVAL || err == EOPNOTSUPP ||
+           err == ENOTTY || err == EBADF || err == EPERM || err == ETXTBSY;
+}
+
+// Copy LEN bytes at OFFSET in SRC_FD to the same offset in DST_FD, with
+// copy_file_range first if KERNEL_COPY is set
+static int copy_range(int src_fd, int dst_fd, off_t offset, off_t len, int kernel_copy,
+                      CopyFailure *failure) {
+    static char buffer[BUFFER_SIZE];
+    off_t in = offset, out = offset;
+    off_t end = offset + len;
+
+#ifdef __linux__
+    if (kernel_copy) {
+        ssize_t n = 0;
+        while (!copy_interrupted && in < end) {
+            size_t chunk = end - in < (off_t)copy_chunk() ? (size_t)(end - in) : copy_chunk();
+            if ((n = copy_file_range(src_fd, &in, dst_fd, &out, chunk, 0)) <= 0) {
+                break;
+            }
+            progress_advance(n);
+        }
+        if (n == -1 && errno != EINTR && !copy_unsupported(errno)) {
+            *failure = COPY_WRITE;
+            return -1;
+        }
+        // copy_file_range advances both offsets together
+        out = in;
+    }
+#else
+    (void)kernel_copy;
+#endif
+
+    while (!copy_interrupted && in < end) {
+        size_t chunk = end - in < BUFFER_SIZE ? (size_t)(end - in) : BUFFER_SIZE;
+        ssize_t bytes_read = pread(src_fd, buffer, chunk, in);
+        if (bytes_read == -1) {
+            if (errno == EINTR) {
+                continue;
+            }
+            *failure = COPY_READ;
+            return -1;
+        }
+        // The file shrank while being copied
+        if (bytes_read == 0) {
+            break;
+        }
+        ssize_t bytes_written = pwrite(dst_fd, buffer, bytes_read, out);
+        if (bytes_written != bytes_read) {
+            if (bytes_written >= 0) {
+                errno = ENOSPC;
+            }
+            *failure = COPY_WRITE;
+            return -1;
+        }
+        in += bytes_read;
+        out += bytes_read;
+        progress_advance(bytes_read);
+    }
+
+    return 0;
+}
+
+// Copy only the data regions SEEK_DATA and SEEK_HOLE report, recreating
+// the holes between them. Returns 1 if the filesystem cannot report them,
+// and -1 with errno and FAILURE set on an error. An interrupted copy
+// stops early but returns 0; the caller checks copy_interrupted.
+int copy_extents(int src_fd, int dst_fd, off_t size, int kernel_copy, CopyFailure *failure) {
+#ifdef SEEK_DATA
+    off_t data = 0;
+
+    while (!copy_interrupted && data < size) {
+        data = lseek(src_fd, data, SEEK_DATA);
+        if (data == -1) {
+            // Nothing but a hole up to the end of the file
+            if (errno == ENXIO) {
+                break;
+            }
+            if (errno == EINVAL || errno == ENOTSUP) {
+                return 1;
+            }
+            *failure = COPY_SEEK;
+            return -1;
+        }
+
+        off_t hole = lseek(src_fd, data, SEEK_HOLE);
+        if (hole == -1 || hole > size) {
+            hole = size;
+        }
+
+        if (copy_range(src_fd, dst_fd, data, hole - data, kernel_copy, failure) == -1) {
+            return -1;
+        }
+        data = hole;
+    }
+
+    if (!copy_interrupted && ftruncate(dst_fd, size) == -1) {
+        *failure = COPY_EXTEND;
+        return -1;
+    }
+    return 0;
+#else
+    (void)src_fd;
+    (void)dst_fd;
+    (void)size;
+    (void)kernel_copy;
+    (void)failure;
+    return 1;
+#endif
+}
+
+// Destination of each source file with several hard links copied so far,
+// so that later links to it are made links to the copy
+typedef struct {
+    dev_t dev;
+    ino_t ino;
+    char *dst;
+} LinkEntry;
+
+static LinkEntry *copied_links = NULL;
+static size_t copied_link_count = 0;
+
+const char *find_copied_link(const struct stat *src_stat) {
+    for (size_t i = 0; i < copied_link_count; i++) {
+        if (copied_links[i].dev == src_stat->st_dev && copied_links[i].ino == src_stat->st_ino) {
+            return copied_links[i].dst;
+        }
+    }
+    return NULL;
+}
+
+void remember_copied_link(const struct stat *src_stat, const char *dst) {
+    LinkEntry *grown = realloc(copied_links, (copied_link_count + 1) * sizeof(LinkEntry));
+    char *name = strdup(dst);
+    if (!grown || !name) {
+        // Later links just become separate copies
+        free(name);
+        if (grown) {
+            copied_links = grown;
+        }
+        return;
+    }
+    copied_links = grown;
+    copied_links[copied_link_count].dev = src_stat->st_dev;
+    copied_links[copied_link_count].ino = src_stat->st_ino;
+    copied_links[copied_link_count].dst = name;
+    copied_link_count++;
+}
+
+// Start over with no copies remembered, as when those made so far are
+// under a name that is about to go away
+void forget_copied_links(void) {
+    for (size_t i = 0; i < copied_link_count; i++) {
+        free(copied_links[i].dst);
+    }
+    free(copied_links);
+    copied_links = NULL;
+    copied_link_count = 0;
+}
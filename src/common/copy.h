@@ -0,0 +1,37 @@
+// Copying of file data shared by cp and mv: sparse files, hard links and
+// the pace at which progress is reported
+#ifndef ASD_COMMON_COPY_H
+#define ASD_COMMON_COPY_H
+
+#include <signal.h>
+#include <stddef.h>
+#include <sys/stat.h>
+#include <sys/types.h>
+
+// Largest amount handed to copy_file_range or sendfile in one call
+#define KERNEL_COPY_CHUNK (1024 * 1024 * 1024)
+
+// While progress is shown, copies are split up so that it can move
+#define PROGRESS_CHUNK (16 * 1024 * 1024)
+
+// What a copy failed at, for the caller to report with errno: seeking in
+// or reading the source, or writing or extending the destination
+typedef enum {
+    COPY_SEEK,
+    COPY_READ,
+    COPY_WRITE,
+    COPY_EXTEND
+} CopyFailure;
+
+// Signal that cut a copy short, or 0; set by the program's signal handler
+extern volatile sig_atomic_t copy_interrupted;
+
+size_t copy_chunk(void);
+int copy_unsupported(int err);
+int copy_extents(int src_fd, int dst_fd, off_t size, int kernel_copy, CopyFailure *failure);
+
+const char *find_copied_link(const struct stat *src_stat);
+void remember_copied_link(const struct stat *src_stat, const char *dst);
+void forget_copied_links(void);
+
+#endif
@@ -0,0 +1,110 @@
+#include <stdio.h>
+#include <string.h>
+#include <unistd.h>
+#include <sys/ioctl.h>
+
+#include "progress.h"
+
+Progress progress;
+
+double seconds_since(const struct timespec *then) {
+    struct timespec now;
+    clock_gettime(CLOCK_MONOTONIC, &now);
+    return (now.tv_sec - then->tv_sec) + (now.tv_nsec - then->tv_nsec) / 1e9;
+}
+
+// Format a byte count with a binary unit, such as "12.3 MiB"
+void format_size(double bytes, char *buf, size_t len) {
+    static const char *units[] = {"B", "KiB", "MiB", "GiB", "TiB", "PiB"};
+    int unit = 0;
+
+    while (bytes >= 1024 && unit < 5) {
+        bytes /= 1024;
+        unit++;
+    }
+    if (unit == 0) {
+        snprintf(buf, len, "%.0f %s", bytes, units[unit]);
+    } else {
+        snprintf(buf, len, "%.1f %s", bytes, units[unit]);
+    }
+}
+
+// Remove the progress line, so other output starts on a clean line
+void progress_clear(void) {
+    if (progress.shown) {
+        fputs("\r\033[K", stderr);
+        progress.shown = 0;
+    }
+}
+
+// Draw the progress line: the current file, then all files together, with
+// the overall throughput and the time left at that rate
+void progress_draw(void) {
+    double elapsed = seconds_since(&progress.start);
+    double rate = elapsed > 0 ? progress.done_bytes / elapsed : 0;
+    char file_done[16], file_size[16], done[16], total[16], speed[16];
+    char eta[32] = "--:--";
+    const char *name = strrchr(progress.file, '/') ? strrchr(progress.file, '/') + 1 : progress.file;
+    struct winsize ws;
+
+    format_size(progress.file_done, file_done, sizeof(file_done));
+    format_size(progress.file_size, file_size, sizeof(file_size));
+    format_size(progress.done_bytes, done, sizeof(done));
+    format_size(progress.total_bytes, total, sizeof(total));
+    format_size(rate, speed, sizeof(speed));
+
+    if (rate > 0 && progress.total_bytes >= progress.done_bytes) {
+        long left = (long)((progress.total_bytes - progress.done_bytes) / rate);
+        snprintf(eta, sizeof(eta), "%ld:%02ld:%02ld", left / 3600, left / 60 % 60, left % 60);
+    }
+
+    int file_percent = progress.file_size > 0 ? (int)(100 * progress.file_done / progress.file_size) : 100;
+    int total_percent = progress.total_bytes > 0 ? (int)(100 * progress.done_bytes / progress.total_bytes) : 100;
+
+    char stats[160];
+    int stats_len = snprintf(stats, sizeof(stats), " %3d%% %s/%s | total %3d%% %s/%s | %s/s | ETA %s",
+                             file_percent, file_done, file_size, total_percent, done, total, speed, eta);
+
+    // On a terminal, the file name gives way to the numbers if the line
+    // would not fit
+    int name_width = (int)strlen(name);
+    if (ioctl(STDERR_FILENO, TIOCGWINSZ, &ws) == 0 && ws.ws_col > 0 && name_width > ws.ws_col - 1 - stats_len) {
+        name_width = ws.ws_col - 1 - stats_len > 0 ? ws.ws_col - 1 - stats_len : 0;
+    }
+    fprintf(stderr, "\r%.*s%s\033[K", name_width, name, stats);
+    fflush(stderr);
+
+    progress.shown = 1;
+    clock_gettime(CLOCK_MONOTONIC, &progress.last_draw);
+}
+
+// Account for BYTES more of the current file and redraw if it is time to
+void progress_advance(off_t bytes) {
+    progress.file_done += bytes;
+    progress.done_bytes += bytes;
+
+    if (progress.mode == PROGRESS_NEVER) {
+        return;
+    }
+    if (progress.mode == PROGRESS_AUTO && !progress.shown && seconds_since(&progress.start) < PROGRESS_DELAY) {
+        return;
+    }
+    if (!progress.shown || seconds_since(&progress.last_draw) >= PROGRESS_INTERVAL) {
+        progress_draw();
+    }
+}
+
+// Start counting FILE, of SIZE bytes, as the file being copied
+void progress_start_file(const char *file, off_t size) {
+    progress.file = file;
+    progress.file_size = size;
+    progress.file_done = 0;
+}
+
+// Count whatever of the file was not copied byte by byte, such as holes or
+// a clone, as done
+void progress_end_file(void) {
+    if (progress.file_done < progress.file_size) {
+        progress_advance(progress.file_size - progress.file_done);
+    }
+}
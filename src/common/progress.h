@@ -0,0 +1,44 @@
+// Progress line shown on stderr by cp and mv while they copy file data
+#ifndef ASD_COMMON_PROGRESS_H
+#define ASD_COMMON_PROGRESS_H
+
+#include <stddef.h>
+#include <time.h>
+#include <sys/types.h>
+
+// With --progress=auto, a copy still running after this many seconds
+// starts showing progress on a terminal
+#define PROGRESS_DELAY 1.0
+
+// Seconds between redraws of the progress line
+#define PROGRESS_INTERVAL 0.2
+
+// When to show progress
+#define PROGRESS_NEVER 0
+#define PROGRESS_AUTO 1
+#define PROGRESS_ALWAYS 2
+
+// State of the progress line drawn on stderr
+typedef struct {
+    int mode;               // PROGRESS_* setting in effect
+    int shown;              // The line is currently on screen
+    off_t total_bytes;      // Size of everything to be copied
+    off_t done_bytes;       // Bytes copied so far, all files included
+    const char *file;       // File being copied; only its last component is shown
+    off_t file_size;
+    off_t file_done;
+    struct timespec start;
+    struct timespec last_draw;
+} Progress;
+
+extern Progress progress;
+
+double seconds_since(const struct timespec *then);
+void format_size(double bytes, char *buf, size_t len);
+void progress_clear(void);
+void progress_draw(void);
+void progress_advance(off_t bytes);
+void progress_start_file(const char *file, off_t size);
+void progress_end_file(void);
+
+#endif
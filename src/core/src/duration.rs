@@ -0,0 +1,53 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Lengths of time as sleep and timeout take them: a number of seconds,
+// minutes, hours or days
+
+// Parses "10", "0.25", "1.5m", "2d", "1e3" or "infinity" into seconds
+pub fn parse_duration(spec: &str) -> Option<f64> {
+    let (number, multiplier) = match spec.char_indices().last()? {
+        (index, 's') => (&spec[..index], 1.0),
+        (index, 'm') => (&spec[..index], 60.0),
+        (index, 'h') => (&spec[..index], 3600.0),
+        (index, 'd') => (&spec[..index], 86400.0),
+        _ => (spec, 1.0),
+    };
+
+    // f64's parser also takes "nan", which is no length of time, and
+    // negative numbers, which are not either
+    let seconds = number.parse::<f64>().ok()? * multiplier;
+    (seconds >= 0.0).then_some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(parse_duration("10"), Some(10.0));
+        assert_eq!(parse_duration("10s"), Some(10.0));
+        assert_eq!(parse_duration("1.5m"), Some(90.0));
+        assert_eq!(parse_duration("2h"), Some(7200.0));
+        assert_eq!(parse_duration("1d"), Some(86400.0));
+        assert_eq!(parse_duration(".5"), Some(0.5));
+    }
+
+    #[test]
+    fn exponents_and_infinity() {
+        assert_eq!(parse_duration("1e3"), Some(1000.0));
+        assert_eq!(parse_duration("2e1m"), Some(1200.0));
+        assert_eq!(parse_duration("infinity"), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn not_lengths_of_time() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("-1"), None);
+        assert_eq!(parse_duration("nan"), None);
+        assert_eq!(parse_duration("5x"), None);
+    }
+}
@@ -0,0 +1,59 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Error messages in the form GNU's utilities print them
+
+use std::io;
+use std::process;
+
+// The description of ERR as C's strerror gives it, without the
+// " (os error N)" Rust adds
+pub fn strerror(err: &io::Error) -> String {
+    let text = err.to_string();
+    match text.find(" (os error") {
+        Some(end) => text[..end].to_string(),
+        None => text,
+    }
+}
+
+// A mistake on the command line of UTILITY, followed by where to look
+pub fn usage_error(utility: &str, message: &str) -> ! {
+    usage_error_status(utility, 1, message)
+}
+
+// An error UTILITY can't go on after
+pub fn fail(utility: &str, message: &str) -> ! {
+    fail_status(utility, 1, message)
+}
+
+// As usage_error, exiting with STATUS; utilities that run a command, such
+// as env and timeout, keep 125 for their own errors
+pub fn usage_error_status(utility: &str, status: i32, message: &str) -> ! {
+    eprintln!("{}: {}", utility, message);
+    eprintln!("Try '{} --help' for more information.", utility);
+    process::exit(status);
+}
+
+// As fail, exiting with STATUS
+pub fn fail_status(utility: &str, status: i32, message: &str) -> ! {
+    eprintln!("{}: {}", utility, message);
+    process::exit(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strerror_drops_the_error_number() {
+        let err = io::Error::from_raw_os_error(libc::ENOENT);
+        assert_eq!(strerror(&err), "No such file or directory");
+    }
+
+    #[test]
+    fn strerror_keeps_other_messages() {
+        let err = io::Error::other("unable to perform all requested operations");
+        assert_eq!(strerror(&err), "unable to perform all requested operations");
+    }
+}
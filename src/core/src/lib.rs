@@ -0,0 +1,14 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// What more than one utility needs, kept here rather than copied into each
+// of them: error messages, mode strings, sizes, signals, lengths of time
+// and file metadata
+
+pub mod duration;
+pub mod error;
+pub mod metadata;
+pub mod mode;
+pub mod signal;
+pub mod size;
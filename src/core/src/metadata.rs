@@ -0,0 +1,123 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Looking up files, and the users and groups that own them
+
+use std::ffi::CStr;
+use std::fs::{self, Metadata};
+use std::io;
+use std::mem;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use std::ptr;
+
+// The metadata of PATH itself or, with FOLLOW, of what a symbolic link
+// there points to
+pub fn metadata(path: impl AsRef<Path>, follow: bool) -> io::Result<Metadata> {
+    if follow {
+        fs::metadata(path)
+    } else {
+        fs::symlink_metadata(path)
+    }
+}
+
+// The type of file, in the words stat uses for it
+pub fn file_type(metadata: &Metadata) -> &'static str {
+    let file_type = metadata.file_type();
+    if file_type.is_dir() {
+        "directory"
+    } else if file_type.is_symlink() {
+        "symbolic link"
+    } else if file_type.is_char_device() {
+        "character special file"
+    } else if file_type.is_block_device() {
+        "block special file"
+    } else if file_type.is_fifo() {
+        "fifo"
+    } else if file_type.is_socket() {
+        "socket"
+    } else if metadata.len() == 0 {
+        "regular empty file"
+    } else {
+        "regular file"
+    }
+}
+
+// The name of user UID, if there is a user with that ID
+pub fn user_name(uid: libc::uid_t) -> Option<String> {
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwuid_r(
+            uid,
+            &mut pwd,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        );
+        if result.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(pwd.pw_name).to_string_lossy().into_owned())
+    }
+}
+
+// The name of group GID, if there is a group with that ID
+pub fn group_name(gid: libc::gid_t) -> Option<String> {
+    unsafe {
+        let mut grp: libc::group = mem::zeroed();
+        let mut result: *mut libc::group = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getgrgid_r(
+            gid,
+            &mut grp,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &mut result,
+        );
+        if result.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(grp.gr_name).to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn follows_symbolic_links_only_when_asked() {
+        let link = env::temp_dir().join(format!("asd-coreutils-core-{}", std::process::id()));
+        let _ = fs::remove_file(&link);
+        symlink("/", &link).unwrap();
+
+        let own = metadata(&link, false);
+        let target = metadata(&link, true);
+        fs::remove_file(&link).unwrap();
+
+        assert_eq!(file_type(&own.unwrap()), "symbolic link");
+        assert_eq!(file_type(&target.unwrap()), "directory");
+    }
+
+    #[test]
+    fn names_file_types() {
+        assert_eq!(
+            file_type(&fs::metadata("/dev/null").unwrap()),
+            "character special file"
+        );
+        assert_eq!(file_type(&fs::metadata("/").unwrap()), "directory");
+    }
+
+    #[test]
+    fn looks_up_users() {
+        assert_eq!(user_name(0).as_deref(), Some("root"));
+        assert!(group_name(0).is_some());
+    }
+}
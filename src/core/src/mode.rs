@@ -0,0 +1,80 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// File modes written out the way ls -l shows them
+
+// "rwxr-sr-t": the permission bits of MODE, with set-user-ID, set-group-ID
+// and sticky shown in place of the execute bits they share a column with,
+// in capitals when that execute bit is off
+pub fn permissions(mode: u32) -> String {
+    let mut text = String::with_capacity(9);
+    for (shift, special, set, unset) in [
+        (6, 0o4000, 's', 'S'),
+        (3, 0o2000, 's', 'S'),
+        (0, 0o1000, 't', 'T'),
+    ] {
+        let bits = mode >> shift;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(match (mode & special != 0, bits & 0o1 != 0) {
+            (true, true) => set,
+            (true, false) => unset,
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+    text
+}
+
+// The letter ls -l gives the type of file in MODE
+pub fn type_letter(mode: u32) -> char {
+    match mode & libc::S_IFMT {
+        libc::S_IFDIR => 'd',
+        libc::S_IFLNK => 'l',
+        libc::S_IFCHR => 'c',
+        libc::S_IFBLK => 'b',
+        libc::S_IFIFO => 'p',
+        libc::S_IFSOCK => 's',
+        _ => '-',
+    }
+}
+
+// "drwxr-xr-x": the type of file in MODE, then its permissions
+pub fn mode_string(mode: u32) -> String {
+    let mut text = String::with_capacity(10);
+    text.push(type_letter(mode));
+    text.push_str(&permissions(mode));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_permissions() {
+        assert_eq!(permissions(0o644), "rw-r--r--");
+        assert_eq!(permissions(0o755), "rwxr-xr-x");
+        assert_eq!(permissions(0), "---------");
+    }
+
+    #[test]
+    fn special_bits_share_the_execute_columns() {
+        assert_eq!(permissions(0o4755), "rwsr-xr-x");
+        assert_eq!(permissions(0o2644), "rw-r-Sr--");
+        assert_eq!(permissions(0o1777), "rwxrwxrwt");
+        assert_eq!(permissions(0o1776), "rwxrwxrwT");
+    }
+
+    #[test]
+    fn file_types() {
+        assert_eq!(mode_string(libc::S_IFDIR | 0o755), "drwxr-xr-x");
+        assert_eq!(mode_string(libc::S_IFREG | 0o600), "-rw-------");
+        assert_eq!(mode_string(libc::S_IFLNK | 0o777), "lrwxrwxrwx");
+        assert_eq!(mode_string(libc::S_IFCHR | 0o666), "crw-rw-rw-");
+        assert_eq!(mode_string(libc::S_IFBLK | 0o660), "brw-rw----");
+        assert_eq!(mode_string(libc::S_IFIFO | 0o644), "prw-r--r--");
+        assert_eq!(mode_string(libc::S_IFSOCK | 0o755), "srwxr-xr-x");
+    }
+}
@@ -0,0 +1,149 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Signals given by name or number, as kill and timeout take them
+
+// Signal names without the "SIG" prefix. The first name for a number is
+// the one to show for it; the aliases come last
+pub const SIGNALS: &[(&str, i32)] = &[
+    ("HUP", libc::SIGHUP),
+    ("INT", libc::SIGINT),
+    ("QUIT", libc::SIGQUIT),
+    ("ILL", libc::SIGILL),
+    ("TRAP", libc::SIGTRAP),
+    ("ABRT", libc::SIGABRT),
+    ("BUS", libc::SIGBUS),
+    ("FPE", libc::SIGFPE),
+    ("KILL", libc::SIGKILL),
+    ("USR1", libc::SIGUSR1),
+    ("SEGV", libc::SIGSEGV),
+    ("USR2", libc::SIGUSR2),
+    ("PIPE", libc::SIGPIPE),
+    ("ALRM", libc::SIGALRM),
+    ("TERM", libc::SIGTERM),
+    ("STKFLT", libc::SIGSTKFLT),
+    ("CHLD", libc::SIGCHLD),
+    ("CONT", libc::SIGCONT),
+    ("STOP", libc::SIGSTOP),
+    ("TSTP", libc::SIGTSTP),
+    ("TTIN", libc::SIGTTIN),
+    ("TTOU", libc::SIGTTOU),
+    ("URG", libc::SIGURG),
+    ("XCPU", libc::SIGXCPU),
+    ("XFSZ", libc::SIGXFSZ),
+    ("VTALRM", libc::SIGVTALRM),
+    ("PROF", libc::SIGPROF),
+    ("WINCH", libc::SIGWINCH),
+    ("IO", libc::SIGIO),
+    ("PWR", libc::SIGPWR),
+    ("SYS", libc::SIGSYS),
+    ("IOT", libc::SIGABRT),
+    ("CLD", libc::SIGCHLD),
+    ("POLL", libc::SIGPOLL),
+];
+
+// Accepts "TERM", "SIGTERM" or a plain signal number such as "15"
+pub fn parse_signal(spec: &str) -> Option<i32> {
+    if let Ok(number) = spec.parse::<i32>() {
+        return if (0..=libc::SIGRTMAX()).contains(&number) {
+            Some(number)
+        } else {
+            None
+        };
+    }
+    signal_number(spec)
+}
+
+// The number of a signal name, with or without "SIG", real-time signals
+// included as "RTMIN+N" and "RTMAX-N"
+pub fn signal_number(name: &str) -> Option<i32> {
+    let name = name.to_ascii_uppercase();
+    let name = name.strip_prefix("SIG").unwrap_or(&name);
+    if let Some(&(_, number)) = SIGNALS.iter().find(|(candidate, _)| *candidate == name) {
+        return Some(number);
+    }
+
+    let (min, max) = (libc::SIGRTMIN(), libc::SIGRTMAX());
+    let offset = |text: &str| match text {
+        "" => Some(0),
+        _ => text[1..]
+            .parse::<i32>()
+            .ok()
+            .filter(|n| (0..=max - min).contains(n)),
+    };
+    if let Some(rest) = name
+        .strip_prefix("RTMIN")
+        .filter(|rest| rest.is_empty() || rest.starts_with('+'))
+    {
+        offset(rest).map(|n| min + n)
+    } else if let Some(rest) = name
+        .strip_prefix("RTMAX")
+        .filter(|rest| rest.is_empty() || rest.starts_with('-'))
+    {
+        offset(rest).map(|n| max - n)
+    } else {
+        None
+    }
+}
+
+// The name of signal NUMBER; real-time signals are counted from
+// whichever end of their range is nearer
+pub fn signal_name(number: i32) -> Option<String> {
+    if let Some(&(name, _)) = SIGNALS.iter().find(|&&(_, candidate)| candidate == number) {
+        return Some(name.to_string());
+    }
+
+    let (min, max) = (libc::SIGRTMIN(), libc::SIGRTMAX());
+    if !(min..=max).contains(&number) {
+        return None;
+    }
+    Some(if number - min <= (max - min) / 2 {
+        match number - min {
+            0 => "RTMIN".to_string(),
+            n => format!("RTMIN+{}", n),
+        }
+    } else {
+        match max - number {
+            0 => "RTMAX".to_string(),
+            n => format!("RTMAX-{}", n),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_numbers() {
+        assert_eq!(parse_signal("TERM"), Some(libc::SIGTERM));
+        assert_eq!(parse_signal("SIGKILL"), Some(libc::SIGKILL));
+        assert_eq!(parse_signal("hup"), Some(libc::SIGHUP));
+        assert_eq!(parse_signal("9"), Some(9));
+        assert_eq!(parse_signal("0"), Some(0));
+        assert_eq!(parse_signal("-1"), None);
+        assert_eq!(parse_signal("BOGUS"), None);
+        assert_eq!(parse_signal("1000"), None);
+    }
+
+    #[test]
+    fn aliases_name_their_signal() {
+        assert_eq!(parse_signal("IOT"), Some(libc::SIGABRT));
+        assert_eq!(parse_signal("CLD"), Some(libc::SIGCHLD));
+        assert_eq!(signal_name(libc::SIGABRT).as_deref(), Some("ABRT"));
+        assert_eq!(signal_name(libc::SIGCHLD).as_deref(), Some("CHLD"));
+    }
+
+    #[test]
+    fn real_time_signals() {
+        let (min, max) = (libc::SIGRTMIN(), libc::SIGRTMAX());
+        assert_eq!(signal_number("RTMIN"), Some(min));
+        assert_eq!(signal_number("RTMIN+1"), Some(min + 1));
+        assert_eq!(signal_number("RTMAX-1"), Some(max - 1));
+        assert_eq!(signal_number("RTMIN-1"), None);
+        assert_eq!(signal_name(min + 1).as_deref(), Some("RTMIN+1"));
+        assert_eq!(signal_name(max).as_deref(), Some("RTMAX"));
+        assert_eq!(signal_name(max + 1), None);
+    }
+}
@@ -0,0 +1,261 @@
+// ASD CoreUtils - shared code for the individual utilities
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Sizes as given on the command line, such as "4K" or "10MB", and as
+// printed: whole blocks of a given size, rounded up, or scaled to a unit the
+// way GNU's human_readable does it, for -h and --si
+
+const UNITS: &[u8] = b"KMGTPEZYRQ";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Scale {
+    // Blocks of SIZE bytes, followed by SUFFIX as given with -B
+    Blocks { size: u64, suffix: String },
+    // The unit that fits best, in powers of BASE
+    Human { base: u64 },
+}
+
+impl Scale {
+    pub fn format(&self, bytes: u64) -> String {
+        match self {
+            Scale::Blocks { size, suffix } => format!("{}{}", bytes.div_ceil(*size), suffix),
+            Scale::Human { base } => human(bytes, *base),
+        }
+    }
+}
+
+// A -B argument: a number of bytes with an optional unit, K being 1024 and
+// KB 1000. A unit alone is also printed after each size.
+pub fn parse_block_size(text: &str) -> Result<Scale, String> {
+    match text {
+        "human-readable" => return Ok(Scale::Human { base: 1024 }),
+        "si" => return Ok(Scale::Human { base: 1000 }),
+        _ => {}
+    }
+    let invalid = || format!("invalid -B argument '{}'", text);
+    let spec = text.strip_prefix('\'').unwrap_or(text);
+    let digits = spec
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(spec.len());
+    let (number, unit) = spec.split_at(digits);
+
+    let size = scaled(number, unit).ok_or_else(|| {
+        if unit.is_empty() || unit_size(unit).is_some() {
+            format!("-B argument '{}' too large", text)
+        } else if !number.is_empty() {
+            format!("invalid suffix in -B argument '{}'", text)
+        } else {
+            invalid()
+        }
+    })?;
+    if size == 0 {
+        return Err(invalid());
+    }
+    let suffix = match (number.is_empty(), unit) {
+        (false, _) => String::new(),
+        // The SI prefix for kilo is lower case
+        (true, "KB") => "kB".to_string(),
+        (true, unit) => unit.to_string(),
+    };
+    Ok(Scale::Blocks { size, suffix })
+}
+
+// TEXT as a number of bytes: digits, then an optional unit such as K for
+// 1024, KB for 1000 or MiB. EXTRA adds the units only some utilities take,
+// such as b for 512-byte blocks, with the sizes they stand for.
+pub fn parse_size(text: &str, extra: &[(&str, u64)]) -> Option<u64> {
+    let digits = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(digits);
+    let number: u64 = number.parse().ok()?;
+    let multiplier = match extra.iter().find(|(name, _)| *name == unit) {
+        Some(&(_, size)) => size,
+        None => unit_size(unit)?,
+    };
+    number.checked_mul(multiplier)
+}
+
+// NUMBER, or 1 if empty, times the size of UNIT
+fn scaled(number: &str, unit: &str) -> Option<u64> {
+    let number: u64 = if number.is_empty() {
+        1
+    } else {
+        number.parse().ok()?
+    };
+    number.checked_mul(unit_size(unit)?)
+}
+
+// The bytes UNIT stands for: a power of 1024, or of 1000 after a B, with
+// the letter giving the power, in either case; none at all is 1
+pub fn unit_size(unit: &str) -> Option<u64> {
+    let mut chars = unit.chars();
+    let Some(letter) = chars.next() else {
+        return Some(1);
+    };
+    let power = UNITS
+        .iter()
+        .position(|&u| u as char == letter.to_ascii_uppercase())? as u32
+        + 1;
+    let base: u64 = match chars.as_str() {
+        "" | "iB" => 1024,
+        "B" => 1000,
+        _ => return None,
+    };
+    base.checked_pow(power)
+}
+
+// BYTES in the largest unit of powers of BASE leaving at least 1, rounded
+// up, with one decimal below 10
+pub fn human(bytes: u64, base: u64) -> String {
+    if bytes < base {
+        return bytes.to_string();
+    }
+
+    // Divide down keeping the first decimal and whether anything was lost
+    // after it, as two bits: half or more, and anything at all
+    let mut amount = bytes;
+    let mut tenths = 0;
+    let mut rounding = 0;
+    let mut exponent = 0;
+    loop {
+        let r10 = (amount % base) * 10 + tenths;
+        let r2 = (r10 % base) * 2 + (rounding >> 1);
+        amount /= base;
+        tenths = r10 / base;
+        rounding = if r2 < base {
+            u64::from(r2 + rounding != 0)
+        } else {
+            2 + u64::from(base < r2 + rounding)
+        };
+        exponent += 1;
+        if amount < base || exponent == UNITS.len() {
+            break;
+        }
+    }
+
+    let unit = |exponent| unit_letter(exponent, base);
+
+    if amount < 10 {
+        if rounding > 0 {
+            tenths += 1;
+            if tenths == 10 {
+                amount += 1;
+                tenths = 0;
+            }
+        }
+        if amount < 10 {
+            return format!("{}.{}{}", amount, tenths, unit(exponent));
+        }
+        tenths = 0;
+        rounding = 0;
+    }
+
+    if rounding + tenths > 0 {
+        amount += 1;
+        if amount == base && exponent < UNITS.len() {
+            return format!("1.0{}", unit(exponent + 1));
+        }
+    }
+    format!("{}{}", amount, unit(exponent))
+}
+
+// The letter for BASE to the power EXPONENT; the SI prefix for kilo is
+// lower case
+fn unit_letter(exponent: usize, base: u64) -> char {
+    match UNITS[exponent - 1] {
+        b'K' if base == 1000 => 'k',
+        letter => letter as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn human_sizes() {
+        assert_eq!(human(0, 1024), "0");
+        assert_eq!(human(1023, 1024), "1023");
+        assert_eq!(human(1024, 1024), "1.0K");
+        assert_eq!(human(1536, 1024), "1.5K");
+        assert_eq!(human(10 * 1024, 1024), "10K");
+        assert_eq!(human(1000, 1000), "1.0k");
+        assert_eq!(human(1 << 30, 1024), "1.0G");
+    }
+
+    #[test]
+    fn human_sizes_round_up() {
+        assert_eq!(human(1025, 1024), "1.1K");
+        assert_eq!(human(10 * 1024 + 1, 1024), "11K");
+        assert_eq!(human(1024 * 1024 - 1, 1024), "1.0M");
+    }
+
+    #[test]
+    fn block_scales() {
+        assert_eq!(
+            Scale::Blocks {
+                size: 1024,
+                suffix: String::new()
+            }
+            .format(1025),
+            "2"
+        );
+        assert_eq!(Scale::Human { base: 1024 }.format(2048), "2.0K");
+    }
+
+    #[test]
+    fn block_sizes() {
+        let blocks = |size, suffix: &str| Scale::Blocks {
+            size,
+            suffix: suffix.to_string(),
+        };
+        assert_eq!(parse_block_size("512"), Ok(blocks(512, "")));
+        assert_eq!(parse_block_size("4K"), Ok(blocks(4096, "")));
+        assert_eq!(parse_block_size("M"), Ok(blocks(1 << 20, "M")));
+        assert_eq!(parse_block_size("KB"), Ok(blocks(1000, "kB")));
+        assert_eq!(parse_block_size("si"), Ok(Scale::Human { base: 1000 }));
+        assert_eq!(
+            parse_block_size("human-readable"),
+            Ok(Scale::Human { base: 1024 })
+        );
+    }
+
+    #[test]
+    fn bad_block_sizes() {
+        assert_eq!(
+            parse_block_size("0"),
+            Err("invalid -B argument '0'".to_string())
+        );
+        assert_eq!(
+            parse_block_size("x"),
+            Err("invalid -B argument 'x'".to_string())
+        );
+        assert_eq!(
+            parse_block_size("4X"),
+            Err("invalid suffix in -B argument '4X'".to_string())
+        );
+        assert_eq!(
+            parse_block_size("16E"),
+            Err("-B argument '16E' too large".to_string())
+        );
+    }
+
+    #[test]
+    fn sizes_with_units() {
+        assert_eq!(parse_size("10", &[]), Some(10));
+        assert_eq!(parse_size("3kB", &[]), Some(3000));
+        assert_eq!(parse_size("2MiB", &[]), Some(2 << 20));
+        assert_eq!(parse_size("1Ki", &[]), None);
+        assert_eq!(parse_size("K", &[]), None);
+        assert_eq!(parse_size("20E", &[]), None);
+    }
+
+    #[test]
+    fn sizes_with_extra_units() {
+        assert_eq!(parse_size("4b", &[("b", 512)]), Some(2048));
+        assert_eq!(parse_size("4b", &[]), None);
+        assert_eq!(parse_size("4K", &[("b", 512)]), Some(4096));
+    }
+}
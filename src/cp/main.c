@@ -1,118 +1,534 @@
+#define _GNU_SOURCE
 #include <stdio.h>
 #include <stdlib.h>
 #include <string.h>
 #include <unistd.h>
 #include <fcntl.h>
 #include <errno.h>
+#include <getopt.h>
+#include <stdint.h>
 #include <sys/stat.h>
 #include <sys/types.h>
+#include <sys/ioctl.h>
+#include <sys/xattr.h>
 #include <dirent.h>
 #include <libgen.h>
 #include <time.h>
+#ifdef __linux__
+#include <sys/sendfile.h>
+#include <linux/fs.h>
+#endif
+
+#include "../common/copy.h"
+#include "../common/progress.h"
 
 #define BUFFER_SIZE (1024 * 1024)  // 1MB buffer for efficient copying
 #define PATH_MAX 4096
 
+// When to share data blocks with the source instead of copying them
+#define REFLINK_NEVER 0
+#define REFLINK_AUTO 1
+#define REFLINK_ALWAYS 2
+
+// When to leave holes in the destination
+#define SPARSE_NEVER 0
+#define SPARSE_AUTO 1
+#define SPARSE_ALWAYS 2
+
+// How an existing destination is kept before being overwritten
+#define BACKUP_NONE 0
+#define BACKUP_SIMPLE 1         // DEST~
+#define BACKUP_NUMBERED 2       // DEST.~N~
+#define BACKUP_EXISTING 3       // Numbered if DEST already has numbered backups, else simple
+
+// Attributes --preserve can keep
+#define PRESERVE_MODE 1         // Permission bits and ACLs
+#define PRESERVE_OWNERSHIP 2
+#define PRESERVE_TIMESTAMPS 4
+#define PRESERVE_LINKS 8        // Hard links between the copied files
+#define PRESERVE_XATTR 16       // Extended attributes
+#define PRESERVE_DEFAULT (PRESERVE_MODE | PRESERVE_OWNERSHIP | PRESERVE_TIMESTAMPS)
+#define PRESERVE_ALL (PRESERVE_DEFAULT | PRESERVE_LINKS | PRESERVE_XATTR)
+
 typedef struct {
     int recursive;           // -r, -R option
     int force;              // -f option
     int interactive;        // -i option
-    int preserve;           // -p option
+    int preserve;           // PRESERVE_* bits from -p, -a or --preserve
+    int xattr_required;     // xattr was named explicitly, so failing to copy them is reported
+    int no_dereference;     // -P option: copy symbolic links rather than what they point to;
+                            // the default for -r
     int verbose;            // -v option
     int no_target_dir;      // -T option
     int update;             // -u option
+    int backup;             // -b, --backup: one of the BACKUP_* methods
+    const char *suffix;     // -S option: suffix of simple backups
+    int reflink;            // --reflink: REFLINK_NEVER, REFLINK_AUTO or REFLINK_ALWAYS
+    int sparse;             // --sparse: SPARSE_NEVER, SPARSE_AUTO or SPARSE_ALWAYS
+    int progress;           // --progress: PROGRESS_NEVER, PROGRESS_AUTO or PROGRESS_ALWAYS
 } CopyOptions;
 
 // Function prototypes
 static int copy_file(const char *src, const char *dst, const CopyOptions *options);
+static int copy_data(int src_fd, int dst_fd, const char *src, const char *dst,
+                     const struct stat *src_stat, const CopyOptions *options);
 static int copy_directory(const char *src, const char *dst, const CopyOptions *options);
+static int copy_special(const char *src, const char *dst, const struct stat *src_stat);
+static int preserve_attributes(const char *src, const char *dst, const struct stat *src_stat,
+                               const CopyOptions *options);
 static int create_path(const char *path);
 static void print_usage(const char *program_name);
 static int check_update_condition(const char *src, const char *dst);
+static int make_backup(const char *dst, const CopyOptions *options, char *backup, size_t size);
+
+// Add up the sizes of the regular files a copy of PATH will read
+static off_t total_size(const char *path, const CopyOptions *options) {
+    struct stat st;
+    off_t total = 0;
+
+    if ((options->no_dereference ? lstat(path, &st) : stat(path, &st)) == -1) {
+        return 0;
+    }
+    if (S_ISREG(st.st_mode)) {
+        return st.st_size;
+    }
+    if (!S_ISDIR(st.st_mode) || !options->recursive) {
+        return 0;
+    }
+
+    DIR *dir = opendir(path);
+    if (!dir) {
+        return 0;
+    }
+    struct dirent *entry;
+    while ((entry = readdir(dir)) != NULL) {
+        char child[PATH_MAX];
+        if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0)
+            continue;
+        snprintf(child, sizeof(child), "%s/%s", path, entry->d_name);
+        total += total_size(child, options);
+    }
+    closedir(dir);
+    return total;
+}
+
+// Ask whether DST may be overwritten; anything but an answer starting with
+// 'y' declines
+static int confirm_overwrite(const char *dst) {
+    int c, answer;
+
+    progress_clear();
+    fprintf(stderr, "cp: overwrite '%s'? ", dst);
+    answer = c = getchar();
+    while (c != EOF && c != '\n') {
+        c = getchar();
+    }
+    return answer == 'y' || answer == 'Y';
+}
+
+static int same_file(const struct stat *a, const struct stat *b) {
+    return a->st_dev == b->st_dev && a->st_ino == b->st_ino;
+}
+
+// Report a copy for -v, with the backup made of the old destination if any
+static void print_copied(const char *src, const char *dst, const char *backup) {
+    progress_clear();
+    if (backup[0]) {
+        printf("'%s' -> '%s' (backup: '%s')\n", src, dst, backup);
+    } else {
+        printf("'%s' -> '%s'\n", src, dst);
+    }
+}
 
 // Implementation of main copy logic
 static int copy_file(const char *src, const char *dst, const CopyOptions *options) {
-    char buffer[BUFFER_SIZE];
     struct stat src_stat;
     int src_fd, dst_fd;
-    ssize_t bytes_read, bytes_written;
     mode_t mode;
 
-    if (stat(src, &src_stat) == -1) {
+    if ((options->no_dereference ? lstat(src, &src_stat) : stat(src, &src_stat)) == -1) {
         fprintf(stderr, "Error: Cannot stat source file '%s': %s\n", 
                 src, strerror(errno));
         return -1;
     }
 
     // Check if destination exists and handle according to options
-    if (access(dst, F_OK) == 0) {
-        if (options->interactive) {
-            printf("overwrite '%s'? (y/n [n]) ", dst);
-            char response = getchar();
-            while (getchar() != '\n'); // Clear input buffer
-            if (response != 'y' && response != 'Y') {
-                return 0;
+    struct stat dst_stat;
+    char backup[PATH_MAX] = "";
+    const char *from = src;
+    if (lstat(dst, &dst_stat) == 0) {
+        // Opening DST to write it would truncate the source itself, also when
+        // DST is a link to it that gets followed. Like GNU cp, only --force
+        // with a backup goes ahead, copying from the backup made of it.
+        struct stat target_stat;
+        int same = same_file(&src_stat, &dst_stat) ||
+                   (!options->no_dereference && S_ISLNK(dst_stat.st_mode) &&
+                    stat(dst, &target_stat) == 0 && same_file(&src_stat, &target_stat));
+        if (same && !(options->force && options->backup != BACKUP_NONE && S_ISREG(dst_stat.st_mode))) {
+            fprintf(stderr, "Error: '%s' and '%s' are the same file\n", src, dst);
+            return -1;
+        }
+        if (options->update && !check_update_condition(src, dst)) {
+            return 0;
+        }
+        if (options->interactive && !confirm_overwrite(dst)) {
+            return 0;
+        }
+        if (options->backup != BACKUP_NONE && !S_ISDIR(dst_stat.st_mode) &&
+            make_backup(dst, options, backup, sizeof(backup)) == -1) {
+            return -1;
+        }
+        if (same) {
+            from = backup;
+        }
+    }
+
+    int track_links = (options->preserve & PRESERVE_LINKS) && src_stat.st_nlink > 1;
+    if (track_links) {
+        const char *linked = find_copied_link(&src_stat);
+        if (linked) {
+            if ((unlink(dst) == -1 && errno != ENOENT) || link(linked, dst) == -1) {
+                fprintf(stderr, "Error: Cannot create hard link '%s' to '%s': %s\n",
+                        dst, linked, strerror(errno));
+                return -1;
             }
-        } else if (!options->force) {
-            if (options->update && !check_update_condition(src, dst)) {
-                return 0;
+            if (options->verbose) {
+                print_copied(src, dst, backup);
             }
+            return 0;
         }
     }
 
-    // Open source file
-    src_fd = open(src, O_RDONLY);
-    if (src_fd == -1) {
-        fprintf(stderr, "Error: Cannot open source file '%s': %s\n", 
-                src, strerror(errno));
-        return -1;
-    }
+    // Recursive copies recreate special files instead of reading them
+    if (S_ISLNK(src_stat.st_mode) || (options->recursive && !S_ISREG(src_stat.st_mode))) {
+        if (copy_special(from, dst, &src_stat) == -1) {
+            return -1;
+        }
+    } else {
+        // Open source file
+        src_fd = open(from, O_RDONLY);
+        if (src_fd == -1) {
+            fprintf(stderr, "Error: Cannot open source file '%s': %s\n", 
+                    from, strerror(errno));
+            return -1;
+        }
+
+        // Create destination file; a new file gets the source's permissions
+        // less the umask, and the exact ones only once its owner is right
+        mode = src_stat.st_mode & 0777;
+        dst_fd = open(dst, O_WRONLY | O_CREAT | O_TRUNC, mode);
+        if (dst_fd == -1) {
+            fprintf(stderr, "Error: Cannot create destination file '%s': %s\n", 
+                    dst, strerror(errno));
+            close(src_fd);
+            return -1;
+        }
+
+        // Copy file contents
+        progress_start_file(dst, src_stat.st_size);
+        if (copy_data(src_fd, dst_fd, from, dst, &src_stat, options) == -1) {
+            close(src_fd);
+            close(dst_fd);
+            return -1;
+        }
+        progress_end_file();
 
-    // Create destination file
-    mode = options->preserve ? src_stat.st_mode : 0666;
-    dst_fd = open(dst, O_WRONLY | O_CREAT | O_TRUNC, mode);
-    if (dst_fd == -1) {
-        fprintf(stderr, "Error: Cannot create destination file '%s': %s\n", 
-                dst, strerror(errno));
         close(src_fd);
+        if (close(dst_fd) == -1) {
+            fprintf(stderr, "Error: Write failed for '%s': %s\n", dst, strerror(errno));
+            return -1;
+        }
+    }
+
+    preserve_attributes(from, dst, &src_stat, options);
+
+    if (track_links) {
+        remember_copied_link(&src_stat, dst);
+    }
+
+    if (options->verbose) {
+        print_copied(src, dst, backup);
+    }
+
+    return 0;
+}
+
+// Recreate a symbolic link, FIFO, device node or socket at DST
+static int copy_special(const char *src, const char *dst, const struct stat *src_stat) {
+    if (unlink(dst) == -1 && errno != ENOENT) {
+        fprintf(stderr, "Error: Cannot remove '%s': %s\n", dst, strerror(errno));
         return -1;
     }
 
-    // Copy file contents
-    while ((bytes_read = read(src_fd, buffer, BUFFER_SIZE)) > 0) {
-        bytes_written = write(dst_fd, buffer, bytes_read);
-        if (bytes_written != bytes_read) {
-            fprintf(stderr, "Error: Write failed for '%s': %s\n", 
-                    dst, strerror(errno));
-            close(src_fd);
-            close(dst_fd);
+    if (S_ISLNK(src_stat->st_mode)) {
+        char target[PATH_MAX];
+        ssize_t len = readlink(src, target, sizeof(target) - 1);
+        if (len == -1) {
+            fprintf(stderr, "Error: Cannot read symbolic link '%s': %s\n", src, strerror(errno));
+            return -1;
+        }
+        target[len] = '\0';
+        if (symlink(target, dst) == -1) {
+            fprintf(stderr, "Error: Cannot create symbolic link '%s': %s\n", dst, strerror(errno));
             return -1;
         }
+        return 0;
+    }
+
+    int result = S_ISFIFO(src_stat->st_mode)
+        ? mkfifo(dst, src_stat->st_mode & 07777)
+        : mknod(dst, src_stat->st_mode, src_stat->st_rdev);
+    if (result == -1) {
+        fprintf(stderr, "Error: Cannot create special file '%s': %s\n", dst, strerror(errno));
+        return -1;
+    }
+    return 0;
+}
+
+// Copy the extended attributes of SRC to DST; when only the mode is being
+// preserved, just the POSIX ACLs stored among them
+static void copy_xattrs(const char *src, const char *dst, const CopyOptions *options) {
+    ssize_t size = llistxattr(src, NULL, 0);
+    if (size <= 0) {
+        if (size == -1 && errno != ENOTSUP && options->xattr_required) {
+            fprintf(stderr, "Warning: Could not read extended attributes of '%s': %s\n",
+                    src, strerror(errno));
+        }
+        return;
+    }
+
+    char *names = malloc(size);
+    if (!names || (size = llistxattr(src, names, size)) == -1) {
+        free(names);
+        return;
+    }
+
+    for (char *name = names; name < names + size; name += strlen(name) + 1) {
+        int is_acl = strncmp(name, "system.posix_acl_", 17) == 0;
+        if (!is_acl && !(options->preserve & PRESERVE_XATTR)) {
+            continue;
+        }
+
+        ssize_t value_size = lgetxattr(src, name, NULL, 0);
+        char *value = value_size > 0 ? malloc(value_size) : NULL;
+        if (value_size > 0 && value) {
+            value_size = lgetxattr(src, name, value, value_size);
+        }
+
+        if (value_size >= 0 && lsetxattr(dst, name, value, value_size, 0) == -1) {
+            // A filesystem without extended attributes loses nothing
+            // anybody asked for unless xattr was named explicitly
+            if (options->xattr_required || (is_acl && errno != ENOTSUP)) {
+                fprintf(stderr, "Warning: Could not preserve attribute '%s' for '%s': %s\n",
+                        name, dst, strerror(errno));
+            }
+        }
+        free(value);
+    }
+
+    free(names);
+}
+
+// Give DST the attributes of SRC selected with --preserve. Ownership goes
+// first, as changing it clears the set-ID bits, and timestamps last, as
+// everything else counts as a change to the file.
+static int preserve_attributes(const char *src, const char *dst, const struct stat *src_stat,
+                               const CopyOptions *options) {
+    mode_t mode = src_stat->st_mode & 07777;
+    int status = 0;
+
+    if (options->preserve & PRESERVE_OWNERSHIP) {
+        if (lchown(dst, src_stat->st_uid, src_stat->st_gid) == -1) {
+            int err = errno;
+
+            // Only root may give files away, but the group can still be one
+            // we belong to. Set-ID bits must not be kept for the wrong owner.
+            if (lchown(dst, (uid_t)-1, src_stat->st_gid) == -1) {
+                mode &= ~S_ISGID;
+            }
+            mode &= ~S_ISUID;
+            if (err != EPERM) {
+                fprintf(stderr, "Warning: Could not preserve ownership for '%s'\n", dst);
+                status = -1;
+            }
+        }
+    }
+
+    if ((options->preserve & PRESERVE_MODE) && !S_ISLNK(src_stat->st_mode)) {
+        if (chmod(dst, mode) == -1) {
+            fprintf(stderr, "Warning: Could not preserve permissions for '%s'\n", dst);
+            status = -1;
+        }
     }
 
-    // Preserve attributes if requested
-    if (options->preserve) {
+    if (options->preserve & (PRESERVE_MODE | PRESERVE_XATTR)) {
+        copy_xattrs(src, dst, options);
+    }
+
+    if (options->preserve & PRESERVE_TIMESTAMPS) {
         struct timespec times[2];
-        times[0] = src_stat.st_atim;
-        times[1] = src_stat.st_mtim;
-        if (futimens(dst_fd, times) == -1) {
+        times[0] = src_stat->st_atim;
+        times[1] = src_stat->st_mtim;
+        if (utimensat(AT_FDCWD, dst, times, AT_SYMLINK_NOFOLLOW) == -1) {
             fprintf(stderr, "Warning: Could not preserve timestamps for '%s'\n", dst);
-        }
-        if (fchown(dst_fd, src_stat.st_uid, src_stat.st_gid) == -1) {
-            fprintf(stderr, "Warning: Could not preserve ownership for '%s'\n", dst);
+            status = -1;
         }
     }
 
-    close(src_fd);
-    close(dst_fd);
+    return status;
+}
 
-    if (options->verbose) {
-        printf("'%s' -> '%s'\n", src, dst);
+// Copy the rest of SRC_FD to DST_FD through a buffer. With a nonzero
+// HOLE_BLOCK, aligned blocks of that size that are entirely zero are
+// skipped over instead of written, leaving holes in the destination.
+static int copy_by_reading(int src_fd, int dst_fd, const char *src, const char *dst, size_t hole_block) {
+    static char buffer[BUFFER_SIZE];
+    ssize_t bytes_read, bytes_written;
+    int skipped = 0;
+
+    while ((bytes_read = read(src_fd, buffer, BUFFER_SIZE)) > 0) {
+        size_t block = hole_block ? hole_block : (size_t)bytes_read;
+
+        for (ssize_t pos = 0; pos < bytes_read; pos += block) {
+            size_t len = (size_t)(bytes_read - pos) < block ? (size_t)(bytes_read - pos) : block;
+            const char *data = buffer + pos;
+
+            // A block is zero if its first byte is and every byte equals
+            // the next one
+            if (hole_block && data[0] == 0 && memcmp(data, data + 1, len - 1) == 0) {
+                if (lseek(dst_fd, len, SEEK_CUR) == -1) {
+                    fprintf(stderr, "Error: Cannot seek in '%s': %s\n", dst, strerror(errno));
+                    return -1;
+                }
+                skipped = 1;
+                continue;
+            }
+
+            bytes_written = write(dst_fd, data, len);
+            if (bytes_written != (ssize_t)len) {
+                fprintf(stderr, "Error: Write failed for '%s': %s\n", 
+                        dst, strerror(errno));
+                return -1;
+            }
+            skipped = 0;
+        }
+        progress_advance(bytes_read);
+    }
+    if (bytes_read == -1) {
+        fprintf(stderr, "Error: Read failed for '%s': %s\n", src, strerror(errno));
+        return -1;
+    }
+
+    // A hole at the very end only exists once the size is set
+    if (skipped) {
+        off_t end = lseek(dst_fd, 0, SEEK_CUR);
+        if (end == -1 || ftruncate(dst_fd, end) == -1) {
+            fprintf(stderr, "Error: Cannot extend '%s': %s\n", dst, strerror(errno));
+            return -1;
+        }
     }
 
     return 0;
 }
 
+// Copy the contents of SRC_FD to DST_FD, both positioned at their start.
+// The cheapest method that works is used: sharing the source's blocks
+// (FICLONE, on btrfs, XFS and the like), then copy_file_range, which lets
+// the filesystem copy without the data passing through userspace, then
+// sendfile, and finally a plain read/write loop. Holes in a sparse source
+// are recreated unless --sparse=never; --sparse=always also turns runs of
+// zeros into holes.
+static int copy_data(int src_fd, int dst_fd, const char *src, const char *dst,
+                     const struct stat *src_stat, const CopyOptions *options) {
+    struct stat dst_stat;
+
+#ifdef __linux__
+    if (options->reflink != REFLINK_NEVER) {
+        if (ioctl(dst_fd, FICLONE, src_fd) == 0) {
+            return 0;
+        }
+        if (options->reflink == REFLINK_ALWAYS) {
+            fprintf(stderr, "Error: Failed to clone '%s' from '%s': %s\n",
+                    dst, src, strerror(errno));
+            return -1;
+        }
+    }
+#endif
+
+    // Holes can only be made in a regular file, and a file taking up fewer
+    // blocks than its size calls for has some
+    if (options->sparse != SPARSE_NEVER && S_ISREG(src_stat->st_mode) &&
+        fstat(dst_fd, &dst_stat) == 0 && S_ISREG(dst_stat.st_mode)) {
+        int has_holes = src_stat->st_blocks < src_stat->st_size / 512;
+
+        if (options->sparse == SPARSE_AUTO && has_holes) {
+            CopyFailure failure;
+            int result = copy_extents(src_fd, dst_fd, src_stat->st_size,
+                                      options->reflink != REFLINK_NEVER, &failure);
+            if (result == -1) {
+                switch (failure) {
+                    case COPY_SEEK:
+                        fprintf(stderr, "Error: Cannot seek in '%s': %s\n", src, strerror(errno));
+                        break;
+                    case COPY_READ:
+                        fprintf(stderr, "Error: Read failed for '%s': %s\n", src, strerror(errno));
+                        break;
+                    case COPY_WRITE:
+                        fprintf(stderr, "Error: Write failed for '%s': %s\n", dst, strerror(errno));
+                        break;
+                    case COPY_EXTEND:
+                        fprintf(stderr, "Error: Cannot extend '%s': %s\n", dst, strerror(errno));
+                        break;
+                }
+            }
+            if (result != 1) {
+                return result;
+            }
+        }
+        if (options->sparse == SPARSE_ALWAYS || has_holes) {
+            return copy_by_reading(src_fd, dst_fd, src, dst, dst_stat.st_blksize);
+        }
+    }
+
+#ifdef __linux__
+    // copy_file_range may itself share blocks, which --reflink=never
+    // rules out
+    if (options->reflink != REFLINK_NEVER) {
+        off_t copied = 0;
+        ssize_t n;
+        while ((n = copy_file_range(src_fd, NULL, dst_fd, NULL, copy_chunk(), 0)) > 0) {
+            copied += n;
+            progress_advance(n);
+        }
+        if (n == -1 && !copy_unsupported(errno)) {
+            fprintf(stderr, "Error: Write failed for '%s': %s\n", dst, strerror(errno));
+            return -1;
+        }
+        // Files in /proc and the like report nothing to copy; read them
+        // to be sure
+        if (n == 0 && copied > 0) {
+            return 0;
+        }
+    }
+
+    off_t sent = 0;
+    ssize_t n;
+    while ((n = sendfile(dst_fd, src_fd, NULL, copy_chunk())) > 0) {
+        sent += n;
+        progress_advance(n);
+    }
+    if (n == -1 && !copy_unsupported(errno)) {
+        fprintf(stderr, "Error: Write failed for '%s': %s\n", dst, strerror(errno));
+        return -1;
+    }
+    if (n == 0 && sent > 0) {
+        return 0;
+    }
+#endif
+
+    return copy_by_reading(src_fd, dst_fd, src, dst, 0);
+}
+
 // Directory copying implementation
 static int copy_directory(const char *src, const char *dst, const CopyOptions *options) {
     DIR *dir;
@@ -120,6 +536,13 @@ static int copy_directory(const char *src, const char *dst, const CopyOptions *o
     char src_path[PATH_MAX];
     char dst_path[PATH_MAX];
     struct stat statbuf;
+    struct stat src_stat;
+    int status = 0;
+
+    if (stat(src, &src_stat) == -1) {
+        fprintf(stderr, "Error: Cannot stat '%s': %s\n", src, strerror(errno));
+        return -1;
+    }
 
     // Create destination directory if it doesn't exist
     if (mkdir(dst, 0777) == -1 && errno != EEXIST) {
@@ -128,6 +551,11 @@ static int copy_directory(const char *src, const char *dst, const CopyOptions *o
         return -1;
     }
 
+    if (options->verbose) {
+        progress_clear();
+        printf("'%s' -> '%s'\n", src, dst);
+    }
+
     dir = opendir(src);
     if (!dir) {
         fprintf(stderr, "Error: Cannot open directory '%s': %s\n", 
@@ -142,7 +570,7 @@ static int copy_directory(const char *src, const char *dst, const CopyOptions *o
         snprintf(src_path, PATH_MAX, "%s/%s", src, entry->d_name);
         snprintf(dst_path, PATH_MAX, "%s/%s", dst, entry->d_name);
 
-        if (lstat(src_path, &statbuf) == -1) {
+        if ((options->no_dereference ? lstat(src_path, &statbuf) : stat(src_path, &statbuf)) == -1) {
             fprintf(stderr, "Error: Cannot stat '%s': %s\n", 
                     src_path, strerror(errno));
             continue;
@@ -151,20 +579,25 @@ static int copy_directory(const char *src, const char *dst, const CopyOptions *o
         if (S_ISDIR(statbuf.st_mode)) {
             if (options->recursive) {
                 if (copy_directory(src_path, dst_path, options) == -1) {
-                    closedir(dir);
-                    return -1;
+                    status = -1;
+                    break;
                 }
             }
         } else {
             if (copy_file(src_path, dst_path, options) == -1) {
-                closedir(dir);
-                return -1;
+                status = -1;
+                break;
             }
         }
     }
 
     closedir(dir);
-    return 0;
+
+    // Done last, so that the timestamps stay put and a read-only
+    // directory could still be filled
+    preserve_attributes(src, dst, &src_stat, options);
+
+    return status;
 }
 
 // Helper function to check if source is newer than destination
@@ -175,7 +608,90 @@ static int check_update_condition(const char *src, const char *dst) {
         return 1;  // If we can't stat, assume update is needed
     }
 
-    return src_stat.st_mtime > dst_stat.st_mtime;
+    if (src_stat.st_mtim.tv_sec != dst_stat.st_mtim.tv_sec) {
+        return src_stat.st_mtim.tv_sec > dst_stat.st_mtim.tv_sec;
+    }
+    return src_stat.st_mtim.tv_nsec > dst_stat.st_mtim.tv_nsec;
+}
+
+// Highest N among the DST.~N~ backups next to DST, or 0 if there are none
+static unsigned long last_backup_number(const char *dst) {
+    char *dir_copy = strdup(dst);
+    char *name_copy = strdup(dst);
+    unsigned long highest = 0;
+
+    if (!dir_copy || !name_copy) {
+        free(dir_copy);
+        free(name_copy);
+        return 0;
+    }
+
+    const char *name = basename(name_copy);
+    size_t name_len = strlen(name);
+    DIR *dir = opendir(dirname(dir_copy));
+    if (dir) {
+        struct dirent *entry;
+        while ((entry = readdir(dir)) != NULL) {
+            const char *p = entry->d_name;
+            char *end;
+
+            if (strncmp(p, name, name_len) != 0 || strncmp(p + name_len, ".~", 2) != 0) {
+                continue;
+            }
+            p += name_len + 2;
+            if (*p < '1' || *p > '9') {
+                continue;
+            }
+            unsigned long number = strtoul(p, &end, 10);
+            if (strcmp(end, "~") == 0 && number > highest) {
+                highest = number;
+            }
+        }
+        closedir(dir);
+    }
+
+    free(dir_copy);
+    free(name_copy);
+    return highest;
+}
+
+// Move the existing DST aside according to the backup method, storing the
+// name it got in BACKUP
+static int make_backup(const char *dst, const CopyOptions *options, char *backup, size_t size) {
+    unsigned long last = 0;
+
+    if (options->backup != BACKUP_SIMPLE) {
+        last = last_backup_number(dst);
+    }
+    if (options->backup == BACKUP_NUMBERED || (options->backup == BACKUP_EXISTING && last > 0)) {
+        snprintf(backup, size, "%s.~%lu~", dst, last + 1);
+    } else {
+        snprintf(backup, size, "%s%s", dst, options->suffix);
+    }
+
+    if (rename(dst, backup) == -1) {
+        fprintf(stderr, "Error: Cannot back up '%s' to '%s': %s\n", dst, backup, strerror(errno));
+        backup[0] = '\0';
+        return -1;
+    }
+    return 0;
+}
+
+// Parse a --backup method the way GNU tools name them, or -1
+static int parse_backup_method(const char *method) {
+    if (strcmp(method, "none") == 0 || strcmp(method, "off") == 0) {
+        return BACKUP_NONE;
+    }
+    if (strcmp(method, "simple") == 0 || strcmp(method, "never") == 0) {
+        return BACKUP_SIMPLE;
+    }
+    if (strcmp(method, "numbered") == 0 || strcmp(method, "t") == 0) {
+        return BACKUP_NUMBERED;
+    }
+    if (strcmp(method, "existing") == 0 || strcmp(method, "nil") == 0) {
+        return BACKUP_EXISTING;
+    }
+    return -1;
 }
 
 // Create directory path recursively
@@ -199,18 +715,96 @@ static int create_path(const char *path) {
     return mkdir(tmp, 0777);
 }
 
+// Parse a comma separated --preserve list into PRESERVE_* bits, or -1
+static int parse_preserve_list(const char *list, int *xattr_named) {
+    int bits = 0;
+    char *copy = strdup(list);
+    char *save = NULL;
+
+    if (!copy) {
+        return -1;
+    }
+    for (char *name = strtok_r(copy, ",", &save); name; name = strtok_r(NULL, ",", &save)) {
+        if (strcmp(name, "mode") == 0) {
+            bits |= PRESERVE_MODE;
+        } else if (strcmp(name, "ownership") == 0) {
+            bits |= PRESERVE_OWNERSHIP;
+        } else if (strcmp(name, "timestamps") == 0) {
+            bits |= PRESERVE_TIMESTAMPS;
+        } else if (strcmp(name, "links") == 0) {
+            bits |= PRESERVE_LINKS;
+        } else if (strcmp(name, "xattr") == 0) {
+            bits |= PRESERVE_XATTR;
+            *xattr_named = 1;
+        } else if (strcmp(name, "all") == 0) {
+            bits |= PRESERVE_ALL;
+        } else {
+            free(copy);
+            return -1;
+        }
+    }
+    free(copy);
+    return bits;
+}
+
+// Copy one SOURCE operand to DST
+static int copy_operand(const char *src, const char *dst, const CopyOptions *options) {
+    struct stat src_stat;
+
+    if ((options->no_dereference ? lstat(src, &src_stat) : stat(src, &src_stat)) == -1) {
+        fprintf(stderr, "Error: Cannot stat '%s': %s\n", src, strerror(errno));
+        return -1;
+    }
+
+    if (S_ISDIR(src_stat.st_mode)) {
+        if (!options->recursive) {
+            fprintf(stderr, "Error: Omitting directory '%s'\n", src);
+            return -1;
+        }
+        return copy_directory(src, dst, options);
+    }
+    return copy_file(src, dst, options);
+}
+
 static void print_usage(const char *program_name) {
     fprintf(stderr, "Usage: %s [OPTION]... SOURCE DEST\n", program_name);
     fprintf(stderr, "  or:  %s [OPTION]... SOURCE... DIRECTORY\n", program_name);
     fprintf(stderr, "\nOptions:\n");
+    fprintf(stderr, "  -a, --archive       same as -dR --preserve=all\n");
+    fprintf(stderr, "      --backup[=CONTROL]  make a backup of each existing destination file\n");
+    fprintf(stderr, "  -b                  like --backup but does not accept an argument\n");
+    fprintf(stderr, "  -d                  same as --no-dereference --preserve=links\n");
     fprintf(stderr, "  -f, --force         force overwrite of destination file\n");
     fprintf(stderr, "  -i, --interactive   prompt before overwrite\n");
-    fprintf(stderr, "  -p, --preserve      preserve file attributes\n");
+    fprintf(stderr, "  -L, --dereference   always follow symbolic links in SOURCE\n");
+    fprintf(stderr, "  -P, --no-dereference  never follow symbolic links in SOURCE\n");
+    fprintf(stderr, "  -p                  same as --preserve=mode,ownership,timestamps\n");
+    fprintf(stderr, "      --preserve[=ATTR_LIST]  preserve the specified attributes\n");
+    fprintf(stderr, "                      (default: mode,ownership,timestamps); also\n");
+    fprintf(stderr, "                      links, xattr and all\n");
+    fprintf(stderr, "      --no-preserve=ATTR_LIST  don't preserve the specified attributes\n");
+    fprintf(stderr, "      --progress[=WHEN]  show per-file and total progress with throughput\n");
+    fprintf(stderr, "                      and time left on stderr; WHEN is 'always',\n");
+    fprintf(stderr, "                      'auto' (the default: on a terminal, once a copy\n");
+    fprintf(stderr, "                      takes more than a second) or 'never'\n");
     fprintf(stderr, "  -r, -R, --recursive copy directories recursively\n");
+    fprintf(stderr, "  -S, --suffix=SUFFIX override the usual backup suffix\n");
+    fprintf(stderr, "      --reflink[=WHEN]  control clone/CoW copies; WHEN is 'always',\n");
+    fprintf(stderr, "                      'auto' (the default) or 'never'\n");
+    fprintf(stderr, "      --sparse=WHEN   control creation of sparse files; WHEN is 'auto'\n");
+    fprintf(stderr, "                      (recreate the source's holes, the default),\n");
+    fprintf(stderr, "                      'always' (also turn zero blocks into holes) or 'never'\n");
     fprintf(stderr, "  -u, --update        copy only when source is newer\n");
     fprintf(stderr, "  -v, --verbose       explain what is being done\n");
     fprintf(stderr, "  -T, --no-target-directory  treat DEST as a normal file\n");
     fprintf(stderr, "      --help          display this help and exit\n");
+    fprintf(stderr, "\nThe backup suffix is '~', unless set with --suffix or SIMPLE_BACKUP_SUFFIX.\n");
+    fprintf(stderr, "The version control method may be selected via the --backup option or through\n");
+    fprintf(stderr, "the VERSION_CONTROL environment variable.  Here are the values:\n\n");
+    fprintf(stderr, "  none, off       never make backups (even if --backup is given)\n");
+    fprintf(stderr, "  numbered, t     make numbered backups\n");
+    fprintf(stderr, "  existing, nil   numbered if numbered backups exist, simple otherwise\n");
+    fprintf(stderr, "  simple, never   always make simple backups\n");
 }
 
 int main(int argc, char *argv[]) {
@@ -219,30 +813,169 @@ int main(int argc, char *argv[]) {
     char *target;
     struct stat target_stat;
 
+    options.reflink = REFLINK_AUTO;
+    options.sparse = SPARSE_AUTO;
+    options.no_dereference = -1;
+    options.progress = PROGRESS_AUTO;
+    options.suffix = getenv("SIMPLE_BACKUP_SUFFIX");
+    if (!options.suffix || !options.suffix[0] || strchr(options.suffix, '/')) {
+        options.suffix = "~";
+    }
+    int backup_wanted = 0;
+    const char *backup_method = NULL;
+
+    enum { REFLINK_OPTION = 256, SPARSE_OPTION, PRESERVE_OPTION, NO_PRESERVE_OPTION, PROGRESS_OPTION,
+           BACKUP_OPTION, HELP_OPTION };
+    static struct option long_options[] = {
+        {"archive", no_argument, 0, 'a'},
+        {"backup", optional_argument, 0, BACKUP_OPTION},
+        {"recursive", no_argument, 0, 'r'},
+        {"force", no_argument, 0, 'f'},
+        {"interactive", no_argument, 0, 'i'},
+        {"dereference", no_argument, 0, 'L'},
+        {"no-dereference", no_argument, 0, 'P'},
+        {"preserve", optional_argument, 0, PRESERVE_OPTION},
+        {"no-preserve", required_argument, 0, NO_PRESERVE_OPTION},
+        {"verbose", no_argument, 0, 'v'},
+        {"no-target-directory", no_argument, 0, 'T'},
+        {"update", no_argument, 0, 'u'},
+        {"suffix", required_argument, 0, 'S'},
+        {"reflink", optional_argument, 0, REFLINK_OPTION},
+        {"sparse", required_argument, 0, SPARSE_OPTION},
+        {"progress", optional_argument, 0, PROGRESS_OPTION},
+        {"help", no_argument, 0, HELP_OPTION},
+        {0, 0, 0, 0}
+    };
+
     // Parse command line options
-    for (i = 1; i < argc && argv[i][0] == '-'; i++) {
-        char *opt = argv[i];
-        if (strcmp(opt, "--help") == 0) {
-            print_usage(argv[0]);
-            return 0;
-        }
-        while (*++opt) {
-            switch (*opt) {
-                case 'r':
-                case 'R': options.recursive = 1; break;
-                case 'f': options.force = 1; break;
-                case 'i': options.interactive = 1; break;
-                case 'p': options.preserve = 1; break;
-                case 'v': options.verbose = 1; break;
-                case 'T': options.no_target_dir = 1; break;
-                case 'u': options.update = 1; break;
-                default:
-                    fprintf(stderr, "Unknown option: -%c\n", *opt);
-                    print_usage(argv[0]);
+    int c;
+    while ((c = getopt_long(argc, argv, "abdrRfiLPpS:vTu", long_options, NULL)) != -1) {
+        switch (c) {
+            case 'a':
+                options.recursive = 1;
+                options.no_dereference = 1;
+                options.preserve |= PRESERVE_ALL;
+                break;
+            case 'd':
+                options.no_dereference = 1;
+                options.preserve |= PRESERVE_LINKS;
+                break;
+            case 'r':
+            case 'R': options.recursive = 1; break;
+            case 'f': options.force = 1; break;
+            case 'i': options.interactive = 1; break;
+            case 'L': options.no_dereference = 0; break;
+            case 'P': options.no_dereference = 1; break;
+            case 'p': options.preserve |= PRESERVE_DEFAULT; break;
+            case PRESERVE_OPTION:
+            case NO_PRESERVE_OPTION: {
+                int xattr_named = 0;
+                int bits = PRESERVE_DEFAULT;
+                if (optarg && (bits = parse_preserve_list(optarg, &xattr_named)) == -1) {
+                    fprintf(stderr, "Error: Invalid argument '%s' for '--%s'\n", optarg,
+                            c == PRESERVE_OPTION ? "preserve" : "no-preserve");
+                    fprintf(stderr, "Valid arguments are: 'mode', 'ownership', 'timestamps', 'links', 'xattr', 'all'\n");
                     return 1;
+                }
+                if (c == PRESERVE_OPTION) {
+                    options.preserve |= bits;
+                    options.xattr_required |= xattr_named;
+                } else {
+                    options.preserve &= ~bits;
+                    if (bits & PRESERVE_XATTR) {
+                        options.xattr_required = 0;
+                    }
+                }
+                break;
             }
+            case 'b': backup_wanted = 1; break;
+            case BACKUP_OPTION:
+                backup_wanted = 1;
+                if (optarg) {
+                    backup_method = optarg;
+                }
+                break;
+            case 'S':
+                backup_wanted = 1;
+                options.suffix = optarg;
+                break;
+            case 'v': options.verbose = 1; break;
+            case 'T': options.no_target_dir = 1; break;
+            case 'u': options.update = 1; break;
+            case REFLINK_OPTION:
+                if (optarg == NULL || strcmp(optarg, "always") == 0) {
+                    options.reflink = REFLINK_ALWAYS;
+                } else if (strcmp(optarg, "auto") == 0) {
+                    options.reflink = REFLINK_AUTO;
+                } else if (strcmp(optarg, "never") == 0) {
+                    options.reflink = REFLINK_NEVER;
+                } else {
+                    fprintf(stderr, "Error: Invalid argument '%s' for '--reflink'\n", optarg);
+                    fprintf(stderr, "Valid arguments are: 'auto', 'always', 'never'\n");
+                    return 1;
+                }
+                break;
+            case SPARSE_OPTION:
+                if (strcmp(optarg, "auto") == 0) {
+                    options.sparse = SPARSE_AUTO;
+                } else if (strcmp(optarg, "always") == 0) {
+                    options.sparse = SPARSE_ALWAYS;
+                } else if (strcmp(optarg, "never") == 0) {
+                    options.sparse = SPARSE_NEVER;
+                } else {
+                    fprintf(stderr, "Error: Invalid argument '%s' for '--sparse'\n", optarg);
+                    fprintf(stderr, "Valid arguments are: 'auto', 'always', 'never'\n");
+                    return 1;
+                }
+                break;
+            case PROGRESS_OPTION:
+                if (optarg == NULL || strcmp(optarg, "always") == 0) {
+                    options.progress = PROGRESS_ALWAYS;
+                } else if (strcmp(optarg, "auto") == 0) {
+                    options.progress = PROGRESS_AUTO;
+                } else if (strcmp(optarg, "never") == 0) {
+                    options.progress = PROGRESS_NEVER;
+                } else {
+                    fprintf(stderr, "Error: Invalid argument '%s' for '--progress'\n", optarg);
+                    fprintf(stderr, "Valid arguments are: 'auto', 'always', 'never'\n");
+                    return 1;
+                }
+                break;
+            case HELP_OPTION:
+                print_usage(argv[0]);
+                return 0;
+            default:
+                print_usage(argv[0]);
+                return 1;
         }
     }
+    i = optind;
+
+    // Without a method on the command line, VERSION_CONTROL picks one
+    if (backup_wanted) {
+        const char *source = "--backup";
+        if (!backup_method && getenv("VERSION_CONTROL") && getenv("VERSION_CONTROL")[0]) {
+            backup_method = getenv("VERSION_CONTROL");
+            source = "$VERSION_CONTROL";
+        }
+        options.backup = backup_method ? parse_backup_method(backup_method) : BACKUP_EXISTING;
+        if (options.backup == -1) {
+            fprintf(stderr, "Error: Invalid argument '%s' for '%s'\n", backup_method, source);
+            fprintf(stderr, "Valid arguments are: 'none', 'off', 'simple', 'never', 'existing', 'nil', 'numbered', 't'\n");
+            return 1;
+        }
+    }
+
+    // Recursive copies keep symbolic links as they are unless told otherwise
+    if (options.no_dereference == -1) {
+        options.no_dereference = options.recursive;
+    }
+
+    // A clone shares the source's blocks, holes and all
+    if (options.reflink == REFLINK_ALWAYS && options.sparse != SPARSE_AUTO) {
+        fprintf(stderr, "Error: --reflink can be used only with --sparse=auto\n");
+        return 1;
+    }
 
     // Check for sufficient arguments
     if (argc - i < 2) {
@@ -254,55 +987,48 @@ int main(int argc, char *argv[]) {
     // Get target (last argument)
     target = argv[argc - 1];
 
-    // Multiple source files case
-    if (argc - i > 2) {
-        if (!options.no_target_dir && stat(target, &target_stat) == 0 && S_ISDIR(target_stat.st_mode)) {
-            // Copy multiple files to directory
-            for (; i < argc - 1; i++) {
-                char dst_path[PATH_MAX];
-                char *basename_ptr = basename(argv[i]);
-                snprintf(dst_path, sizeof(dst_path), "%s/%s", target, basename_ptr);
-                
-                struct stat src_stat;
-                if (stat(argv[i], &src_stat) == 0) {
-                    if (S_ISDIR(src_stat.st_mode)) {
-                        if (options.recursive) {
-                            if (copy_directory(argv[i], dst_path, &options) == -1)
-                                return 1;
-                        } else {
-                            fprintf(stderr, "Error: Omitting directory '%s'\n", argv[i]);
-                        }
-                    } else {
-                        if (copy_file(argv[i], dst_path, &options) == -1)
-                            return 1;
-                    }
-                }
-            }
-        } else {
-            fprintf(stderr, "Error: Target '%s' is not a directory\n", target);
-            return 1;
+    // Sources are copied into an existing target directory under their own
+    // names
+    int into_directory = !options.no_target_dir && stat(target, &target_stat) == 0 &&
+                         S_ISDIR(target_stat.st_mode);
+    if (argc - i > 2 && !into_directory) {
+        fprintf(stderr, "Error: Target '%s' is not a directory\n", target);
+        return 1;
+    }
+
+    // Totals are only worth the extra walk over the sources if they may
+    // be shown
+    progress.mode = options.progress;
+    if (progress.mode == PROGRESS_AUTO && !isatty(STDERR_FILENO)) {
+        progress.mode = PROGRESS_NEVER;
+    }
+    if (progress.mode != PROGRESS_NEVER) {
+        for (int j = i; j < argc - 1; j++) {
+            progress.total_bytes += total_size(argv[j], &options);
         }
-    } else {
-        // Single source file case
-        struct stat src_stat;
-        if (stat(argv[i], &src_stat) == 0) {
-            if (S_ISDIR(src_stat.st_mode)) {
-                if (options.recursive) {
-                    if (copy_directory(argv[i], target, &options) == -1)
-                        return 1;
-                } else {
-                    fprintf(stderr, "Error: Omitting directory '%s'\n", argv[i]);
-                    return 1;
-                }
-            } else {
-                if (copy_file(argv[i], target, &options) == -1)
-                    return 1;
-            }
-        } else {
-            fprintf(stderr, "Error: Cannot stat '%s': %s\n", argv[i], strerror(errno));
-            return 1;
+    }
+    clock_gettime(CLOCK_MONOTONIC, &progress.start);
+
+    int status = 0;
+    for (; i < argc - 1; i++) {
+        char dst_path[PATH_MAX];
+        const char *dst = target;
+
+        if (into_directory) {
+            char *basename_ptr = basename(argv[i]);
+            snprintf(dst_path, sizeof(dst_path), "%s/%s", target, basename_ptr);
+            dst = dst_path;
+        }
+        if (copy_operand(argv[i], dst, &options) == -1) {
+            status = 1;
         }
     }
 
-    return 0;
-}
\ No newline at end of file
+    // Leave the final totals on screen
+    if (progress.shown) {
+        progress_draw();
+        fputc('\n', stderr);
+    }
+
+    return status;
+}
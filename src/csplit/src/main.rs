@@ -0,0 +1,323 @@
+// ASD CoreUtils - csplit
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use regex::bytes::Regex;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process;
+
+enum Pattern {
+    // Split before this 1-based line number
+    Line(usize),
+    // Split at (or skip up to, for %REGEX%) the next matching line
+    Regex {
+        source: String,
+        regex: Regex,
+        offset: i64,
+        skip: bool,
+    },
+}
+
+enum Repeat {
+    Times(usize),
+    Forever,
+}
+
+struct Spec {
+    pattern: Pattern,
+    repeat: Repeat,
+}
+
+struct Output {
+    prefix: String,
+    digits: usize,
+    quiet: bool,
+    elide_empty: bool,
+    created: Vec<String>,
+}
+
+impl Output {
+    fn write_section(&mut self, lines: &[Vec<u8>]) -> io::Result<()> {
+        if lines.is_empty() && self.elide_empty {
+            return Ok(());
+        }
+
+        let name = format!("{}{:0width$}", self.prefix, self.created.len(), width = self.digits);
+        let mut file = File::create(&name)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", name, strerror(&err))))?;
+        self.created.push(name);
+
+        let mut bytes = 0;
+        for line in lines {
+            file.write_all(line)?;
+            bytes += line.len();
+        }
+
+        if !self.quiet {
+            println!("{}", bytes);
+        }
+        Ok(())
+    }
+
+    fn remove_created(&self) {
+        for name in &self.created {
+            let _ = fs::remove_file(name);
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("csplit")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils csplit - split a file into sections determined by context lines")
+        .after_help(
+            "Each PATTERN may be:\n  \
+             INTEGER            copy up to but not including specified line number\n  \
+             /REGEXP/[OFFSET]   copy up to but not including a matching line\n  \
+             %REGEXP%[OFFSET]   skip to, but not including a matching line\n  \
+             {INTEGER}          repeat the previous pattern specified number of times\n  \
+             {*}                repeat the previous pattern as many times as possible",
+        )
+        .arg(
+            Arg::new("prefix")
+                .short('f')
+                .long("prefix")
+                .value_name("PREFIX")
+                .default_value("xx")
+                .help("Use PREFIX instead of 'xx'"),
+        )
+        .arg(
+            Arg::new("digits")
+                .short('n')
+                .long("digits")
+                .value_name("DIGITS")
+                .default_value("2")
+                .help("Use specified number of digits instead of 2"),
+        )
+        .arg(
+            Arg::new("keep-files")
+                .short('k')
+                .long("keep-files")
+                .help("Do not remove output files on errors")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('s')
+                .long("quiet")
+                .visible_alias("silent")
+                .help("Do not print counts of output file sizes")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("elide-empty-files")
+                .short('z')
+                .long("elide-empty-files")
+                .help("Remove empty output files")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Input file, or - for standard input")
+                .required(true),
+        )
+        .arg(
+            Arg::new("pattern")
+                .value_name("PATTERN")
+                .help("Split points")
+                .required(true)
+                .allow_hyphen_values(true)
+                .num_args(1..),
+        )
+        .get_matches();
+
+    let digits = match matches.get_one::<String>("digits").unwrap().parse::<usize>() {
+        Ok(digits) => digits,
+        Err(_) => fail("csplit", "invalid number of digits"),
+    };
+
+    let raw_patterns: Vec<&String> = matches.get_many::<String>("pattern").unwrap().collect();
+    let specs = match parse_specs(&raw_patterns) {
+        Ok(specs) => specs,
+        Err(err) => fail("csplit", &err),
+    };
+
+    let file = matches.get_one::<String>("file").unwrap();
+    let lines = match read_lines(file) {
+        Ok(lines) => lines,
+        Err(err) => fail("csplit", &format!("cannot open '{}' for reading: {}", file, strerror(&err))),
+    };
+
+    let mut output = Output {
+        prefix: matches.get_one::<String>("prefix").unwrap().clone(),
+        digits,
+        quiet: matches.get_flag("quiet"),
+        elide_empty: matches.get_flag("elide-empty-files"),
+        created: Vec::new(),
+    };
+
+    if let Err(err) = split(&lines, &specs, &mut output) {
+        eprintln!("csplit: {}", err);
+        if !matches.get_flag("keep-files") {
+            output.remove_created();
+        }
+        process::exit(1);
+    }
+}
+
+fn read_lines(file: &str) -> io::Result<Vec<Vec<u8>>> {
+    let input: Box<dyn Read> = if file == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(file)?)
+    };
+
+    let mut reader = BufReader::new(input);
+    let mut lines = Vec::new();
+    loop {
+        let mut line = Vec::new();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(lines);
+        }
+        lines.push(line);
+    }
+}
+
+fn parse_specs(args: &[&String]) -> Result<Vec<Spec>, String> {
+    let mut specs: Vec<Spec> = Vec::new();
+
+    for arg in args {
+        if let Some(count) = arg.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')) {
+            let spec = specs
+                .last_mut()
+                .ok_or_else(|| format!("'{}': no pattern to repeat", arg))?;
+            spec.repeat = if count == "*" {
+                Repeat::Forever
+            } else {
+                let times = count
+                    .parse()
+                    .map_err(|_| format!("{}: integer required between '{{' and '}}'", arg))?;
+                Repeat::Times(times)
+            };
+            continue;
+        }
+
+        specs.push(Spec {
+            pattern: parse_pattern(arg)?,
+            repeat: Repeat::Times(0),
+        });
+    }
+
+    Ok(specs)
+}
+
+fn parse_pattern(arg: &str) -> Result<Pattern, String> {
+    let delimiter = match arg.chars().next() {
+        Some(c @ ('/' | '%')) => c,
+        _ => {
+            return match arg.parse::<usize>() {
+                Ok(line) if line > 0 => Ok(Pattern::Line(line)),
+                _ => Err(format!("{}: invalid pattern", arg)),
+            };
+        }
+    };
+
+    let body = &arg[1..];
+    let end = body
+        .rfind(delimiter)
+        .ok_or_else(|| format!("{}: closing delimiter '{}' missing", arg, delimiter))?;
+
+    let source = &body[..end];
+    let offset_text = &body[end + 1..];
+    let offset = if offset_text.is_empty() {
+        0
+    } else {
+        offset_text
+            .parse::<i64>()
+            .map_err(|_| format!("{}: integer expected after delimiter", arg))?
+    };
+
+    let regex = Regex::new(source).map_err(|err| format!("{}: invalid regular expression: {}", arg, err))?;
+
+    Ok(Pattern::Regex {
+        source: arg.to_string(),
+        regex,
+        offset,
+        skip: delimiter == '%',
+    })
+}
+
+fn split(lines: &[Vec<u8>], specs: &[Spec], output: &mut Output) -> Result<(), String> {
+    let io_err = |err: io::Error| strerror(&err);
+
+    // `start` is the first line of the pending section; regex searches
+    // resume after the previous match so {*} can't match the same line twice
+    let mut start = 0usize;
+    let mut search_from = 0usize;
+
+    for spec in specs {
+        let iterations = match spec.repeat {
+            Repeat::Times(times) => Some(times + 1),
+            Repeat::Forever => None,
+        };
+
+        let mut iteration = 0;
+        while iterations.is_none_or(|limit| iteration < limit) {
+            let forever = iterations.is_none();
+
+            match &spec.pattern {
+                Pattern::Line(line) => {
+                    let target = line * (iteration + 1);
+                    if target - 1 < start || target > lines.len() {
+                        if forever {
+                            break;
+                        }
+                        return Err(format!("'{}': line number out of range", line));
+                    }
+
+                    output.write_section(&lines[start..target - 1]).map_err(io_err)?;
+                    start = target - 1;
+                    search_from = start;
+                }
+                Pattern::Regex {
+                    source,
+                    regex,
+                    offset,
+                    skip,
+                } => {
+                    let found = (search_from..lines.len()).find(|&index| regex.is_match(strip_newline(&lines[index])));
+                    let index = match found {
+                        Some(index) => index,
+                        None if forever => break,
+                        None => return Err(format!("'{}': match not found", source)),
+                    };
+
+                    let split_at = index as i64 + offset;
+                    if split_at < start as i64 || split_at > lines.len() as i64 {
+                        return Err(format!("'{}': line number out of range", source));
+                    }
+                    let split_at = split_at as usize;
+
+                    if !skip {
+                        output.write_section(&lines[start..split_at]).map_err(io_err)?;
+                    }
+                    start = split_at;
+                    search_from = split_at.max(index + 1);
+                }
+            }
+
+            iteration += 1;
+        }
+    }
+
+    output.write_section(&lines[start..]).map_err(io_err)
+}
+
+fn strip_newline(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\n").unwrap_or(line)
+}
@@ -0,0 +1,249 @@
+// ASD CoreUtils - date
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// +FORMAT conversions as GNU date has them, in the C locale, including the
+// flags -, _, 0, ^ and #, field widths, %N and %:z
+
+use crate::local::Moment;
+use chrono::{Datelike, Timelike};
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+// How a conversion asked to be padded
+#[derive(Clone, Copy, Default)]
+struct Flags {
+    // '-' for none, '_' for spaces, '0' for zeros
+    pad: Option<char>,
+    upper: bool,
+    swap_case: bool,
+    width: Option<usize>,
+}
+
+enum Field {
+    // Value, default width and default padding
+    Number(i64, usize, char),
+    Text(String),
+}
+
+pub fn format(pattern: &str, moment: &Moment) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        // Kept to be printed as is if the conversion is unknown
+        let mut spec = String::from("%");
+        let mut flags = Flags::default();
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                '-' | '_' | '0' => flags.pad = Some(flag),
+                '^' => flags.upper = true,
+                '#' => flags.swap_case = true,
+                _ => break,
+            }
+            spec.push(flag);
+            chars.next();
+        }
+
+        let mut width = String::new();
+        while let Some(&digit) = chars.peek().filter(|c| c.is_ascii_digit()) {
+            width.push(digit);
+            spec.push(digit);
+            chars.next();
+        }
+        flags.width = width.parse().ok();
+
+        let mut colons = 0;
+        while chars.peek() == Some(&':') {
+            colons += 1;
+            spec.push(':');
+            chars.next();
+        }
+
+        // The E and O modifiers choose locale alternatives; there are none
+        if let Some(&modifier) = chars.peek().filter(|&&c| c == 'E' || c == 'O') {
+            spec.push(modifier);
+            chars.next();
+        }
+
+        let Some(conversion) = chars.next() else {
+            out.push_str(&spec);
+            break;
+        };
+        spec.push(conversion);
+
+        if colons > 0 && conversion != 'z' {
+            out.push_str(&spec);
+            continue;
+        }
+        match conversion {
+            'N' => out.push_str(&nanoseconds(moment.timestamp.nanos, flags)),
+            'z' => match zone_offset(moment.offset, colons) {
+                Some(offset) => out.push_str(&offset),
+                None => out.push_str(&spec),
+            },
+            _ => match field(conversion, moment) {
+                Some(Field::Number(value, width, pad)) => {
+                    out.push_str(&pad_number(value, width, pad, flags))
+                }
+                Some(Field::Text(text)) => out.push_str(&pad_text(&text, conversion, flags)),
+                None => out.push_str(&spec),
+            },
+        }
+    }
+    out
+}
+
+fn field(conversion: char, moment: &Moment) -> Option<Field> {
+    let civil = &moment.civil;
+    let weekday = civil.weekday().num_days_from_sunday() as usize;
+    let month = civil.month0() as usize;
+    let hour12 = match civil.hour() % 12 {
+        0 => 12,
+        hour => hour,
+    };
+    let number = |value: i64, width: usize| Some(Field::Number(value, width, '0'));
+    let spaced = |value: i64, width: usize| Some(Field::Number(value, width, ' '));
+    let text = |text: &str| Some(Field::Text(text.to_string()));
+
+    match conversion {
+        '%' => text("%"),
+        'n' => text("\n"),
+        't' => text("\t"),
+        'a' => text(&WEEKDAYS[weekday][..3]),
+        'A' => text(WEEKDAYS[weekday]),
+        'b' | 'h' => text(&MONTHS[month][..3]),
+        'B' => text(MONTHS[month]),
+        'c' => text(&format("%a %b %e %H:%M:%S %Y", moment)),
+        'C' => number(civil.year().div_euclid(100) as i64, 2),
+        'd' => number(civil.day() as i64, 2),
+        'D' | 'x' => text(&format("%m/%d/%y", moment)),
+        'e' => spaced(civil.day() as i64, 2),
+        'F' => text(&format("%Y-%m-%d", moment)),
+        'g' => number(civil.iso_week().year().rem_euclid(100) as i64, 2),
+        'G' => number(civil.iso_week().year() as i64, 4),
+        'H' => number(civil.hour() as i64, 2),
+        'I' => number(hour12 as i64, 2),
+        'j' => number(civil.ordinal() as i64, 3),
+        'k' => spaced(civil.hour() as i64, 2),
+        'l' => spaced(hour12 as i64, 2),
+        'm' => number(civil.month() as i64, 2),
+        'M' => number(civil.minute() as i64, 2),
+        'p' => text(if civil.hour() < 12 { "AM" } else { "PM" }),
+        'P' => text(if civil.hour() < 12 { "am" } else { "pm" }),
+        'q' => number(civil.month0() as i64 / 3 + 1, 1),
+        'r' => text(&format("%I:%M:%S %p", moment)),
+        'R' => text(&format("%H:%M", moment)),
+        's' => number(moment.timestamp.seconds, 1),
+        'S' => number(civil.second() as i64, 2),
+        'T' | 'X' => text(&format("%H:%M:%S", moment)),
+        'u' => number(civil.weekday().number_from_monday() as i64, 1),
+        'U' => number((civil.ordinal0() as i64 + 7 - weekday as i64) / 7, 2),
+        'V' => number(civil.iso_week().week() as i64, 2),
+        'w' => number(weekday as i64, 1),
+        'W' => {
+            let from_monday = civil.weekday().num_days_from_monday() as i64;
+            number((civil.ordinal0() as i64 + 7 - from_monday) / 7, 2)
+        }
+        'y' => number(civil.year().rem_euclid(100) as i64, 2),
+        'Y' => number(civil.year() as i64, 1),
+        'Z' => Some(Field::Text(moment.zone.clone())),
+        _ => None,
+    }
+}
+
+fn pad_number(value: i64, default_width: usize, default_pad: char, flags: Flags) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let sign = if value < 0 { "-" } else { "" };
+    let pad = match flags.pad {
+        Some('-') => return format!("{}{}", sign, digits),
+        Some('_') => ' ',
+        Some('0') => '0',
+        _ => default_pad,
+    };
+
+    let width = flags.width.unwrap_or(default_width);
+    let fill = width.saturating_sub(sign.len() + digits.len());
+    if pad == '0' {
+        format!("{}{}{}", sign, "0".repeat(fill), digits)
+    } else {
+        format!("{}{}{}", " ".repeat(fill), sign, digits)
+    }
+}
+
+fn pad_text(text: &str, conversion: char, flags: Flags) -> String {
+    // GNU's "opposite case" for #: names go up, AM/PM and zones down
+    let text = match (flags.upper, flags.swap_case, conversion) {
+        (_, true, 'p' | 'Z') => text.to_lowercase(),
+        (true, _, _) | (_, true, _) => text.to_uppercase(),
+        _ => text.to_string(),
+    };
+
+    let fill = flags.width.unwrap_or(0).saturating_sub(text.chars().count());
+    match flags.pad {
+        Some('-') => text,
+        Some('0') => format!("{}{}", "0".repeat(fill), text),
+        _ => format!("{}{}", " ".repeat(fill), text),
+    }
+}
+
+// %N: the fraction of the second to WIDTH digits, nine by default
+fn nanoseconds(nanos: u32, flags: Flags) -> String {
+    let width = flags.width.unwrap_or(9);
+    let mut digits = format!("{:09}", nanos);
+    if width < 9 {
+        digits.truncate(width);
+    } else {
+        digits.push_str(&"0".repeat(width - 9));
+    }
+
+    if flags.pad == Some('_') {
+        let kept = digits.trim_end_matches('0').len().max(1);
+        digits.replace_range(kept.., &" ".repeat(width - kept));
+    }
+    digits
+}
+
+// %z, %:z, %::z and %:::z, the last only as precise as needed
+fn zone_offset(offset: i64, colons: usize) -> Option<String> {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+
+    match colons {
+        0 => Some(format!("{}{:02}{:02}", sign, hours, minutes)),
+        1 => Some(format!("{}{:02}:{:02}", sign, hours, minutes)),
+        2 => Some(format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds)),
+        3 if seconds > 0 => Some(format!("{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds)),
+        3 if minutes > 0 => Some(format!("{}{:02}:{:02}", sign, hours, minutes)),
+        3 => Some(format!("{}{:02}", sign, hours)),
+        _ => None,
+    }
+}
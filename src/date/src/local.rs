@@ -0,0 +1,99 @@
+// ASD CoreUtils - date
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Converting between points in time and the local calendar, through the C
+// library so that TZ and the system time zone rules apply as they do for
+// every other program
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use std::ffi::CStr;
+use std::mem;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Timestamp {
+    pub seconds: i64,
+    pub nanos: u32,
+}
+
+impl Timestamp {
+    pub fn now() -> Self {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(since) => Timestamp {
+                seconds: since.as_secs() as i64,
+                nanos: since.subsec_nanos(),
+            },
+            Err(err) => {
+                let before = err.duration();
+                let mut seconds = -(before.as_secs() as i64);
+                let mut nanos = before.subsec_nanos();
+                if nanos > 0 {
+                    seconds -= 1;
+                    nanos = 1_000_000_000 - nanos;
+                }
+                Timestamp { seconds, nanos }
+            }
+        }
+    }
+}
+
+// A point in time as the local clock shows it
+pub struct Moment {
+    pub timestamp: Timestamp,
+    pub civil: NaiveDateTime,
+    // Seconds east of UTC
+    pub offset: i64,
+    pub zone: String,
+}
+
+pub fn moment(timestamp: Timestamp) -> Option<Moment> {
+    let seconds = libc::time_t::try_from(timestamp.seconds).ok()?;
+    let mut tm: libc::tm = unsafe { mem::zeroed() };
+    if unsafe { libc::localtime_r(&seconds, &mut tm) }.is_null() {
+        return None;
+    }
+
+    let date = NaiveDate::from_ymd_opt(tm.tm_year + 1900, tm.tm_mon as u32 + 1, tm.tm_mday as u32)?;
+    let civil = date.and_hms_nano_opt(
+        tm.tm_hour as u32,
+        tm.tm_min as u32,
+        tm.tm_sec.min(59) as u32,
+        timestamp.nanos,
+    )?;
+    let zone = if tm.tm_zone.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(tm.tm_zone) }
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    Some(Moment {
+        timestamp,
+        civil,
+        offset: tm.tm_gmtoff,
+        zone,
+    })
+}
+
+// The seconds since the epoch at which the local clock shows CIVIL. When
+// EXACT is set the clock has to really show it, so times skipped over when
+// the clocks go forward are refused; otherwise they are moved past the gap.
+pub fn from_civil(civil: NaiveDateTime, exact: bool) -> Option<i64> {
+    let mut tm: libc::tm = unsafe { mem::zeroed() };
+    tm.tm_year = civil.year() - 1900;
+    tm.tm_mon = civil.month0() as i32;
+    tm.tm_mday = civil.day() as i32;
+    tm.tm_hour = civil.hour() as i32;
+    tm.tm_min = civil.minute() as i32;
+    tm.tm_sec = civil.second() as i32;
+    tm.tm_isdst = -1;
+
+    let seconds = unsafe { libc::mktime(&mut tm) };
+    let shown = (tm.tm_hour as u32, tm.tm_min as u32, tm.tm_mday as u32);
+    if exact && shown != (civil.hour(), civil.minute(), civil.day()) {
+        return None;
+    }
+    Some(seconds as i64)
+}
@@ -0,0 +1,294 @@
+// ASD CoreUtils - date
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod format;
+mod local;
+mod parse;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use local::Timestamp;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::MetadataExt;
+use std::process;
+
+const DEFAULT_FORMAT: &str = "%a %b %e %H:%M:%S %Z %Y";
+const RESOLUTION_FORMAT: &str = "%s.%N";
+
+fn main() {
+    let matches = Command::new("date")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils date - print or set the date and time")
+        .arg(
+            Arg::new("date")
+                .short('d')
+                .long("date")
+                .value_name("STRING")
+                .help("Display the time described by STRING, not 'now'")
+                .allow_hyphen_values(true)
+                .overrides_with("date"),
+        )
+        .arg(
+            Arg::new("file")
+                .short('f')
+                .long("file")
+                .value_name("DATEFILE")
+                .help("Like --date, once for each line of DATEFILE"),
+        )
+        .arg(
+            Arg::new("reference")
+                .short('r')
+                .long("reference")
+                .value_name("FILE")
+                .help("Display the last modification time of FILE"),
+        )
+        .arg(
+            Arg::new("resolution")
+                .long("resolution")
+                .action(ArgAction::SetTrue)
+                .help("Output the available resolution of timestamps"),
+        )
+        .arg(
+            Arg::new("set")
+                .short('s')
+                .long("set")
+                .value_name("STRING")
+                .allow_hyphen_values(true)
+                .help("Set the time described by STRING"),
+        )
+        .arg(
+            Arg::new("iso-8601")
+                .short('I')
+                .long("iso-8601")
+                .value_name("FMT")
+                .num_args(0..=1)
+                .default_missing_value("date")
+                .value_parser(["date", "hours", "minutes", "seconds", "ns"])
+                .help("Output in ISO 8601 format to the precision FMT (default: date)"),
+        )
+        .arg(
+            Arg::new("rfc-email")
+                .short('R')
+                .long("rfc-email")
+                .action(ArgAction::SetTrue)
+                .help("Output in RFC 5322 format, as in 'Mon, 14 Aug 2006 02:34:56 -0600'"),
+        )
+        .arg(
+            Arg::new("rfc-3339")
+                .long("rfc-3339")
+                .value_name("FMT")
+                .value_parser(["date", "seconds", "ns"])
+                .help("Output in RFC 3339 format to the precision FMT"),
+        )
+        .arg(
+            Arg::new("utc")
+                .short('u')
+                .long("utc")
+                .visible_alias("universal")
+                .action(ArgAction::SetTrue)
+                .help("Print or take times in Coordinated Universal Time (UTC)"),
+        )
+        .arg(
+            Arg::new("format")
+                .value_name("+FORMAT|MMDDhhmm[[CC]YY][.ss]")
+                .help("Output format, as in '+%Y-%m-%d', or the time to set"),
+        )
+        .get_matches();
+
+    // An operand that is not a format is the time to set, in POSIX form
+    let (format_operand, set_operand) = match matches.get_one::<String>("format") {
+        Some(operand) if !operand.starts_with('+') => (None, Some(operand)),
+        operand => (operand, None),
+    };
+
+    let printing = ["date", "file", "reference"]
+        .iter()
+        .filter(|id| matches.contains_id(id))
+        .count()
+        + matches.get_flag("resolution") as usize;
+    let setting = matches.contains_id("set") || set_operand.is_some();
+    if printing > 0 && setting {
+        usage_error("date", "the options to print and set the time may not be used together");
+    }
+    if printing > 1 {
+        usage_error("date", "the options to specify dates for printing are mutually exclusive");
+    }
+
+    let formats = [
+        matches.get_one::<String>("iso-8601").map(|precision| iso_8601(precision)),
+        matches.get_flag("rfc-email").then(|| "%a, %d %b %Y %H:%M:%S %z".to_string()),
+        matches.get_one::<String>("rfc-3339").map(|precision| rfc_3339(precision)),
+        format_operand.map(|format| format[1..].to_string()),
+    ];
+    let mut formats = formats.into_iter().flatten();
+    let format = formats.next().unwrap_or_else(|| {
+        if matches.get_flag("resolution") {
+            RESOLUTION_FORMAT.to_string()
+        } else {
+            DEFAULT_FORMAT.to_string()
+        }
+    });
+    if formats.next().is_some() {
+        fail("date", "multiple output formats specified");
+    }
+
+    // Everything is then worked out in UTC, as if it were the local zone
+    if matches.get_flag("utc") {
+        env::set_var("TZ", "UTC0");
+    }
+
+    let now = Timestamp::now();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    if let Some(file) = matches.get_one::<String>("file") {
+        let status = print_dates_from(file, &format, now, &mut out);
+        process::exit(status);
+    }
+
+    let timestamp = if let Some(text) = matches.get_one::<String>("date") {
+        parse::parse_date(text, now).unwrap_or_else(|| invalid_date(text))
+    } else if let Some(file) = matches.get_one::<String>("reference") {
+        modification_time(file).unwrap_or_else(|err| fail("date", &format!("{}: {}", file, strerror(&err))))
+    } else if matches.get_flag("resolution") {
+        resolution()
+    } else {
+        now
+    };
+
+    let mut status = 0;
+    let timestamp = if let Some(text) = matches.get_one::<String>("set") {
+        let timestamp = parse::parse_date(text, now).unwrap_or_else(|| invalid_date(text));
+        status = set_clock(timestamp);
+        timestamp
+    } else if let Some(text) = set_operand {
+        let timestamp = parse::parse_posix(text, now).unwrap_or_else(|| invalid_date(text));
+        status = set_clock(timestamp);
+        timestamp
+    } else {
+        timestamp
+    };
+
+    if let Err(err) = print_date(timestamp, &format, &mut out) {
+        fail("date", &strerror(&err));
+    }
+    process::exit(status);
+}
+
+fn invalid_date(text: &str) -> ! {
+    fail("date", &format!("invalid date '{}'", text));
+}
+
+fn modification_time(file: &str) -> io::Result<Timestamp> {
+    let metadata = fs::metadata(file)?;
+    Ok(Timestamp {
+        seconds: metadata.mtime(),
+        nanos: metadata.mtime_nsec() as u32,
+    })
+}
+
+// The smallest step the system clock takes
+fn resolution() -> Timestamp {
+    let mut resolution = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    if unsafe { libc::clock_getres(libc::CLOCK_REALTIME, &mut resolution) } != 0 {
+        // No answer; assume the finest that can be shown
+        return Timestamp {
+            seconds: 0,
+            nanos: 1,
+        };
+    }
+    Timestamp {
+        seconds: resolution.tv_sec,
+        nanos: resolution.tv_nsec as u32,
+    }
+}
+
+// Set the system clock, returning the exit status. The date is printed
+// either way, as GNU date does.
+fn set_clock(timestamp: Timestamp) -> i32 {
+    let time = libc::timespec {
+        tv_sec: timestamp.seconds as libc::time_t,
+        tv_nsec: timestamp.nanos as libc::c_long,
+    };
+    if unsafe { libc::clock_settime(libc::CLOCK_REALTIME, &time) } == 0 {
+        return 0;
+    }
+
+    let err = io::Error::last_os_error();
+    eprintln!("date: cannot set date: {}", strerror(&err));
+    if err.raw_os_error() == Some(libc::EPERM) {
+        eprintln!("date: setting the clock needs root or the CAP_SYS_TIME capability");
+    }
+    1
+}
+
+fn iso_8601(precision: &str) -> String {
+    match precision {
+        "hours" => "%Y-%m-%dT%H%:z",
+        "minutes" => "%Y-%m-%dT%H:%M%:z",
+        "seconds" => "%Y-%m-%dT%H:%M:%S%:z",
+        "ns" => "%Y-%m-%dT%H:%M:%S,%N%:z",
+        _ => "%Y-%m-%d",
+    }
+    .to_string()
+}
+
+fn rfc_3339(precision: &str) -> String {
+    match precision {
+        "seconds" => "%Y-%m-%d %H:%M:%S%:z",
+        "ns" => "%Y-%m-%d %H:%M:%S.%N%:z",
+        _ => "%Y-%m-%d",
+    }
+    .to_string()
+}
+
+fn print_date(timestamp: Timestamp, format: &str, out: &mut impl Write) -> io::Result<()> {
+    let moment = local::moment(timestamp).ok_or_else(|| {
+        io::Error::other(format!("time {} is out of range", timestamp.seconds))
+    })?;
+    writeln!(out, "{}", format::format(format, &moment))?;
+    out.flush()
+}
+
+// -f: print the date each line describes; returns the exit status
+fn print_dates_from(file: &str, format: &str, now: Timestamp, out: &mut impl Write) -> i32 {
+    let reader: Box<dyn BufRead> = if file == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        match File::open(file) {
+            Ok(f) => Box::new(BufReader::new(f)),
+            Err(err) => {
+                eprintln!("date: {}: {}", file, strerror(&err));
+                return 1;
+            }
+        }
+    };
+
+    let mut status = 0;
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("date: {}: {}", file, strerror(&err));
+                return 1;
+            }
+        };
+        let Some(timestamp) = parse::parse_date(&line, now) else {
+            eprintln!("date: invalid date '{}'", line);
+            status = 1;
+            continue;
+        };
+        if let Err(err) = print_date(timestamp, format, out) {
+            eprintln!("date: {}", strerror(&err));
+            return 1;
+        }
+    }
+    status
+}
@@ -0,0 +1,733 @@
+// ASD CoreUtils - date
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// -d expressions in the style GNU date accepts: calendar dates in ISO,
+// US and written-out forms, times of day, time zones, weekdays such as
+// "next friday", relative items such as "2 weeks ago" or "tomorrow", and
+// "@SECONDS"
+
+use crate::local::{self, Timestamp};
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, Timelike};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Token {
+    // Digits as written, how many there were, and a fraction in nanoseconds
+    Number(u64, usize, Option<u32>),
+    // A number with a sign in front
+    Signed(i64, usize, Option<u32>),
+    Char(char),
+    // Letters, kept alongside the token in lower case
+    Word,
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Years,
+    Months,
+    Days(i64),
+    Seconds(i64),
+}
+
+struct Date {
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+}
+
+struct Time {
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanos: u32,
+}
+
+#[derive(Default)]
+struct Relative {
+    years: i64,
+    months: i64,
+    days: i64,
+    seconds: i64,
+}
+
+impl Relative {
+    fn add(&mut self, count: i64, unit: Unit) -> Option<()> {
+        match unit {
+            Unit::Years => self.years = self.years.checked_add(count)?,
+            Unit::Months => self.months = self.months.checked_add(count)?,
+            Unit::Days(days) => self.days = self.days.checked_add(count.checked_mul(days)?)?,
+            Unit::Seconds(seconds) => {
+                self.seconds = self.seconds.checked_add(count.checked_mul(seconds)?)?
+            }
+        }
+        Some(())
+    }
+}
+
+// What an expression says, before it is worked out against the clock
+#[derive(Default)]
+struct Expression {
+    timestamp: Option<Timestamp>,
+    date: Option<Date>,
+    time: Option<Time>,
+    // Seconds east of UTC
+    zone: Option<i64>,
+    // Ordinal and day, Sunday being 0
+    weekday: Option<(i64, u32)>,
+    relative: Relative,
+    relative_seen: bool,
+}
+
+struct Parser {
+    tokens: Vec<(Token, String)>,
+    position: usize,
+    expression: Expression,
+    // The relative item "ago" would turn around
+    last_relative: Option<(i64, Unit)>,
+}
+
+// Work out the point in time TEXT describes, taking NOW for whatever it
+// leaves out. Returns None if TEXT is not a valid date.
+pub fn parse_date(text: &str, now: Timestamp) -> Option<Timestamp> {
+    let mut parser = Parser {
+        tokens: tokenize(&text.to_lowercase())?,
+        position: 0,
+        expression: Expression::default(),
+        last_relative: None,
+    };
+    while parser.position < parser.tokens.len() {
+        parser.item()?;
+    }
+    resolve(parser.expression, now)
+}
+
+// The operand form for setting the clock, MMDDhhmm[[CC]YY][.ss], in local
+// time; the year defaults to the current one
+pub fn parse_posix(text: &str, now: Timestamp) -> Option<Timestamp> {
+    let (digits, seconds) = match text.split_once('.') {
+        Some((digits, seconds)) if seconds.len() == 2 => (digits, seconds.parse::<u32>().ok()?),
+        Some(_) => return None,
+        None => (text, 0),
+    };
+    if !digits.bytes().all(|byte| byte.is_ascii_digit()) || !text.is_ascii() {
+        return None;
+    }
+
+    let field = |start: usize| digits[start..start + 2].parse::<u32>().ok();
+    let year = match digits.len() {
+        8 => local::moment(now)?.civil.year(),
+        10 => full_year(digits[8..].parse().ok()?, 2)?,
+        12 => digits[8..].parse().ok()?,
+        _ => return None,
+    };
+    let civil = NaiveDate::from_ymd_opt(year, field(0)?, field(2)?)?.and_hms_opt(
+        field(4)?,
+        field(6)?,
+        seconds,
+    )?;
+    Some(Timestamp {
+        seconds: local::from_civil(civil, true)?,
+        nanos: 0,
+    })
+}
+
+// Splits into numbers, words and single characters, leaving out white space
+fn tokenize(text: &str) -> Option<Vec<(Token, String)>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() {
+            // Dots are allowed inside words, as in "a.m."
+            let mut word = String::new();
+            while i < chars.len() && (chars[i].is_ascii_alphabetic() || chars[i] == '.') {
+                if chars[i] != '.' {
+                    word.push(chars[i]);
+                }
+                i += 1;
+            }
+            tokens.push((Token::Word, word));
+        } else if c.is_ascii_digit() {
+            let (value, digits, fraction, end) = number(&chars, i)?;
+            tokens.push((Token::Number(value, digits, fraction), String::new()));
+            i = end;
+        } else if (c == '+' || c == '-') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            let (value, digits, fraction, end) = number(&chars, i + 1)?;
+            let value = i64::try_from(value).ok()?;
+            let value = if c == '-' { -value } else { value };
+            tokens.push((Token::Signed(value, digits, fraction), String::new()));
+            i = end;
+        } else {
+            tokens.push((Token::Char(c), String::new()));
+            i += 1;
+        }
+    }
+    Some(tokens)
+}
+
+// Digits from START, with a decimal fraction after '.' or ','
+fn number(chars: &[char], start: usize) -> Option<(u64, usize, Option<u32>, usize)> {
+    let mut end = start;
+    let mut value: u64 = 0;
+    while end < chars.len() && chars[end].is_ascii_digit() {
+        value = value
+            .checked_mul(10)?
+            .checked_add(chars[end].to_digit(10)? as u64)?;
+        end += 1;
+    }
+    let digits = end - start;
+
+    let has_fraction = matches!(chars.get(end), Some('.') | Some(','))
+        && chars.get(end + 1).is_some_and(char::is_ascii_digit);
+    if !has_fraction {
+        return Some((value, digits, None, end));
+    }
+
+    end += 1;
+    let mut nanos = 0u32;
+    let mut scale = 100_000_000;
+    while end < chars.len() && chars[end].is_ascii_digit() {
+        nanos += chars[end].to_digit(10)? * scale;
+        scale /= 10;
+        end += 1;
+    }
+    Some((value, digits, Some(nanos), end))
+}
+
+impl Parser {
+    fn token(&self, offset: usize) -> Option<Token> {
+        self.tokens.get(self.position + offset).map(|(token, _)| *token)
+    }
+
+    fn word(&self, offset: usize) -> Option<&str> {
+        match self.tokens.get(self.position + offset)? {
+            (Token::Word, word) => Some(word),
+            _ => None,
+        }
+    }
+
+    fn item(&mut self) -> Option<()> {
+        if let Some(word) = self.word(0) {
+            let word = word.to_string();
+            return self.word_item(&word);
+        }
+
+        match self.token(0)? {
+            Token::Char('@') => self.timestamp(),
+            Token::Char(',') => {
+                self.position += 1;
+                Some(())
+            }
+            Token::Number(value, digits, fraction) => self.number_item(value, digits, fraction),
+            Token::Signed(value, _, fraction) => {
+                if fraction.is_none() {
+                    if let Some(unit) = self.word(1).and_then(unit) {
+                        self.position += 2;
+                        return self.relative(value, unit);
+                    }
+                }
+                None
+            }
+            Token::Char(_) | Token::Word => None,
+        }
+    }
+
+    fn word_item(&mut self, word: &str) -> Option<()> {
+        self.position += 1;
+
+        if word == "ago" {
+            let (count, unit) = self.last_relative.take()?;
+            return self.expression.relative.add(-2 * count, unit);
+        }
+        if let Some(days) = match word {
+            "yesterday" => Some(-1),
+            "today" | "now" => Some(0),
+            "tomorrow" => Some(1),
+            _ => None,
+        } {
+            return self.relative(days, Unit::Days(1));
+        }
+        if let Some(month) = month(word) {
+            return self.month_first(month);
+        }
+        if let Some(day) = weekday(word) {
+            return self.weekday(0, day);
+        }
+        if let Some(ordinal) = ordinal(word) {
+            let next = self.word(0)?.to_string();
+            self.position += 1;
+            if let Some(unit) = unit(&next) {
+                return self.relative(ordinal, unit);
+            }
+            return self.weekday(ordinal, weekday(&next)?);
+        }
+        if let Some(unit) = unit(word) {
+            return self.relative(1, unit);
+        }
+        if let Some(offset) = zone(word) {
+            // As in "UTC+2"
+            let adjustment = match self.token(0) {
+                Some(Token::Signed(value, digits, None)) => {
+                    self.position += 1;
+                    offset_seconds(value, digits)?
+                }
+                _ => 0,
+            };
+            return set(&mut self.expression.zone, offset + adjustment);
+        }
+        // The T between an ISO 8601 date and time
+        if word == "t" && self.expression.date.is_some() && self.token(1) == Some(Token::Char(':')) {
+            if let Some(Token::Number(hour, _, None)) = self.token(0) {
+                self.position += 1;
+                return self.time(hour);
+            }
+        }
+        None
+    }
+
+    fn number_item(&mut self, value: u64, digits: usize, fraction: Option<u32>) -> Option<()> {
+        self.position += 1;
+
+        match (self.token(0), self.token(1), self.word(0)) {
+            (Some(Token::Char(':')), _, _) if fraction.is_none() => self.time(value),
+            // YYYY-MM-DD
+            (Some(Token::Signed(month, _, None)), Some(Token::Signed(day, _, None)), _)
+                if month < 0 && day < 0 && fraction.is_none() =>
+            {
+                self.position += 2;
+                let year = full_year(value, digits)?;
+                self.date(Some(year), month.unsigned_abs(), day.unsigned_abs())
+            }
+            // MM/DD or MM/DD/YYYY
+            (Some(Token::Char('/')), Some(Token::Number(day, _, None)), _) => {
+                self.position += 2;
+                let year = match (self.token(0), self.token(1)) {
+                    (Some(Token::Char('/')), Some(Token::Number(year, digits, None))) => {
+                        self.position += 2;
+                        Some(full_year(year, digits)?)
+                    }
+                    _ => None,
+                };
+                self.date(year, value, day)
+            }
+            // DD-month-YYYY
+            (Some(Token::Char('-')), _, _) => {
+                let month = month(self.word(1)?)?;
+                self.position += 2;
+                let year = match self.token(0) {
+                    Some(Token::Signed(year, digits, None)) if year < 0 => {
+                        self.position += 1;
+                        Some(full_year(year.unsigned_abs(), digits)?)
+                    }
+                    _ => None,
+                };
+                self.date(year, month as u64, value)
+            }
+            (_, _, Some(word)) if fraction.is_none() => {
+                let word = word.to_string();
+                if let Some(month) = month(&word) {
+                    // DD month [YYYY]
+                    self.position += 1;
+                    let year = self.year()?;
+                    return self.date(year, month as u64, value);
+                }
+                if let Some(unit) = unit(&word) {
+                    self.position += 1;
+                    return self.relative(i64::try_from(value).ok()?, unit);
+                }
+                if let Some(pm) = meridian(&word) {
+                    self.position += 1;
+                    let hour = hour12(value, pm)?;
+                    return self.set_time(hour, 0, 0, 0);
+                }
+                self.bare_number(value, digits)
+            }
+            _ if fraction.is_none() => self.bare_number(value, digits),
+            _ => None,
+        }
+    }
+
+    // A number on its own is a year after a date without one, a time after
+    // a date, a date if it is long, and an hour or HHMM otherwise
+    fn bare_number(&mut self, value: u64, digits: usize) -> Option<()> {
+        let expression = &self.expression;
+        if expression.date.as_ref().is_some_and(|date| date.year.is_none())
+            && expression.time.is_some()
+            && digits == 4
+        {
+            let year = i32::try_from(value).ok()?;
+            self.expression.date.as_mut()?.year = Some(year);
+            return Some(());
+        }
+        if digits > 4 {
+            let year = full_year(value / 10000, digits - 4)?;
+            return self.date(Some(year), value / 100 % 100, value % 100);
+        }
+        if digits <= 2 {
+            return self.set_time(u32::try_from(value).ok()?, 0, 0, 0);
+        }
+        self.set_time((value / 100) as u32, (value % 100) as u32, 0, 0)
+    }
+
+    // @SECONDS, which has to stand alone
+    fn timestamp(&mut self) -> Option<()> {
+        if self.position != 0 || self.tokens.len() != 2 {
+            return None;
+        }
+        let (seconds, fraction) = match self.token(1)? {
+            Token::Number(value, _, fraction) => (i64::try_from(value).ok()?, fraction),
+            Token::Signed(value, _, fraction) => (value, fraction),
+            Token::Char(_) | Token::Word => return None,
+        };
+        let mut timestamp = Timestamp {
+            seconds,
+            nanos: fraction.unwrap_or(0),
+        };
+        if seconds < 0 && timestamp.nanos > 0 {
+            timestamp.seconds -= 1;
+            timestamp.nanos = 1_000_000_000 - timestamp.nanos;
+        }
+        self.position += 2;
+        self.expression.timestamp = Some(timestamp);
+        Some(())
+    }
+
+    // HH:MM[:SS[.FRACTION]] [am|pm] [zone], with the position just past HH
+    fn time(&mut self, hour: u64) -> Option<()> {
+        let Some(Token::Number(minute, _, None)) = self.token(1) else {
+            return None;
+        };
+        self.position += 2;
+
+        let (mut second, mut nanos) = (0, 0);
+        if self.token(0) == Some(Token::Char(':')) {
+            let Some(Token::Number(value, _, fraction)) = self.token(1) else {
+                return None;
+            };
+            self.position += 2;
+            second = value;
+            nanos = fraction.unwrap_or(0);
+        }
+
+        let mut hour = u32::try_from(hour).ok()?;
+        if let Some(pm) = self.word(0).and_then(meridian) {
+            self.position += 1;
+            hour = hour12(hour as u64, pm)?;
+        }
+        self.set_time(
+            hour,
+            u32::try_from(minute).ok()?,
+            u32::try_from(second).ok()?,
+            nanos,
+        )?;
+
+        // A signed number straight after a time is its zone, unless it
+        // counts a unit, as in "10:00 -3 hours"
+        if let Some(Token::Signed(value, digits, None)) = self.token(0) {
+            if self.word(1).and_then(unit).is_none() {
+                let offset = match (self.token(1), self.token(2)) {
+                    (Some(Token::Char(':')), Some(Token::Number(minutes, 2, None))) if digits <= 2 => {
+                        self.position += 2;
+                        let minutes = i64::try_from(minutes).ok().filter(|&m| m < 60)?;
+                        let seconds = value.abs() * 3600 + minutes * 60;
+                        if value < 0 {
+                            -seconds
+                        } else {
+                            seconds
+                        }
+                    }
+                    _ => offset_seconds(value, digits)?,
+                };
+                self.position += 1;
+                return set(&mut self.expression.zone, offset);
+            }
+        }
+        Some(())
+    }
+
+    fn set_time(&mut self, hour: u32, minute: u32, second: u32, nanos: u32) -> Option<()> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        set(
+            &mut self.expression.time,
+            Time {
+                hour,
+                minute,
+                second,
+                nanos,
+            },
+        )
+    }
+
+    fn date(&mut self, year: Option<i32>, month: u64, day: u64) -> Option<()> {
+        let month = u32::try_from(month).ok()?;
+        let day = u32::try_from(day).ok()?;
+        set(&mut self.expression.date, Date { year, month, day })
+    }
+
+    // month DD[,] [YYYY], with the position just past the month
+    fn month_first(&mut self, month: u32) -> Option<()> {
+        let Some(Token::Number(day, _, None)) = self.token(0) else {
+            return None;
+        };
+        if self.token(1) == Some(Token::Char(':')) {
+            return None;
+        }
+        self.position += 1;
+        if self.token(0) == Some(Token::Char(',')) {
+            self.position += 1;
+        }
+        let year = self.year()?;
+        self.date(year, month as u64, day)
+    }
+
+    // An optional year after a day and month; a number followed by a colon
+    // or a unit is something else
+    fn year(&mut self) -> Option<Option<i32>> {
+        let Some(Token::Number(year, digits, None)) = self.token(0) else {
+            return Some(None);
+        };
+        if self.token(1) == Some(Token::Char(':'))
+            || self.word(1).is_some_and(|word| unit(word).is_some() || meridian(word).is_some())
+        {
+            return Some(None);
+        }
+        self.position += 1;
+        Some(Some(full_year(year, digits)?))
+    }
+
+    fn weekday(&mut self, ordinal: i64, day: u32) -> Option<()> {
+        if self.token(0) == Some(Token::Char(',')) {
+            self.position += 1;
+        }
+        set(&mut self.expression.weekday, (ordinal, day))
+    }
+
+    fn relative(&mut self, count: i64, unit: Unit) -> Option<()> {
+        self.expression.relative.add(count, unit)?;
+        self.expression.relative_seen = true;
+        self.last_relative = Some((count, unit));
+        Some(())
+    }
+}
+
+// Each kind of item may be given only once
+fn set<T>(slot: &mut Option<T>, value: T) -> Option<()> {
+    if slot.is_some() {
+        return None;
+    }
+    *slot = Some(value);
+    Some(())
+}
+
+// Two-digit years are 1969 to 2068
+fn full_year(year: u64, digits: usize) -> Option<i32> {
+    let year = i32::try_from(year).ok()?;
+    match (digits, year) {
+        (2, 0..=68) => Some(year + 2000),
+        (2, _) => Some(year + 1900),
+        _ => Some(year),
+    }
+}
+
+fn hour12(hour: u64, pm: bool) -> Option<u32> {
+    match hour {
+        1..=11 if pm => Some(hour as u32 + 12),
+        1..=11 => Some(hour as u32),
+        12 if pm => Some(12),
+        12 => Some(0),
+        _ => None,
+    }
+}
+
+// +HH or +HHMM as seconds east of UTC
+fn offset_seconds(value: i64, digits: usize) -> Option<i64> {
+    let magnitude = value.abs();
+    let (hours, minutes) = match digits {
+        1 | 2 => (magnitude, 0),
+        3 | 4 => (magnitude / 100, magnitude % 100),
+        _ => return None,
+    };
+    if hours > 24 || minutes > 59 {
+        return None;
+    }
+    let seconds = hours * 3600 + minutes * 60;
+    Some(if value < 0 { -seconds } else { seconds })
+}
+
+fn meridian(word: &str) -> Option<bool> {
+    match word {
+        "am" => Some(false),
+        "pm" => Some(true),
+        _ => None,
+    }
+}
+
+fn month(word: &str) -> Option<u32> {
+    let month = match word {
+        "january" | "jan" => 1,
+        "february" | "feb" => 2,
+        "march" | "mar" => 3,
+        "april" | "apr" => 4,
+        "may" => 5,
+        "june" | "jun" => 6,
+        "july" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "october" | "oct" => 10,
+        "november" | "nov" => 11,
+        "december" | "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+fn weekday(word: &str) -> Option<u32> {
+    let day = match word {
+        "sunday" | "sun" => 0,
+        "monday" | "mon" => 1,
+        "tuesday" | "tue" | "tues" => 2,
+        "wednesday" | "wed" | "wednes" => 3,
+        "thursday" | "thu" | "thur" | "thurs" => 4,
+        "friday" | "fri" => 5,
+        "saturday" | "sat" => 6,
+        _ => return None,
+    };
+    Some(day)
+}
+
+// "second" is left out, being a unit
+fn ordinal(word: &str) -> Option<i64> {
+    let ordinal = match word {
+        "last" => -1,
+        "this" => 0,
+        "next" | "first" => 1,
+        "third" => 3,
+        "fourth" => 4,
+        "fifth" => 5,
+        "sixth" => 6,
+        "seventh" => 7,
+        "eighth" => 8,
+        "ninth" => 9,
+        "tenth" => 10,
+        "eleventh" => 11,
+        "twelfth" => 12,
+        _ => return None,
+    };
+    Some(ordinal)
+}
+
+fn unit(word: &str) -> Option<Unit> {
+    let unit = match word {
+        "year" | "years" => Unit::Years,
+        "month" | "months" => Unit::Months,
+        "fortnight" | "fortnights" => Unit::Days(14),
+        "week" | "weeks" => Unit::Days(7),
+        "day" | "days" => Unit::Days(1),
+        "hour" | "hours" => Unit::Seconds(3600),
+        "minute" | "minutes" | "min" | "mins" => Unit::Seconds(60),
+        "second" | "seconds" | "sec" | "secs" => Unit::Seconds(1),
+        _ => return None,
+    };
+    Some(unit)
+}
+
+// Common zone abbreviations, in seconds east of UTC
+fn zone(word: &str) -> Option<i64> {
+    let hours = match word {
+        "utc" | "ut" | "gmt" | "z" | "wet" => 0.0,
+        "west" | "bst" | "cet" | "met" => 1.0,
+        "cest" | "mest" | "eet" => 2.0,
+        "eest" | "msk" => 3.0,
+        "jst" | "kst" => 9.0,
+        "acst" => 9.5,
+        "aest" => 10.0,
+        "aedt" => 11.0,
+        "nzst" => 12.0,
+        "nzdt" => 13.0,
+        "hst" => -10.0,
+        "akst" => -9.0,
+        "akdt" | "pst" => -8.0,
+        "pdt" | "mst" => -7.0,
+        "mdt" | "cst" => -6.0,
+        "cdt" | "est" => -5.0,
+        "edt" => -4.0,
+        _ => return None,
+    };
+    Some((hours * 3600.0) as i64)
+}
+
+fn resolve(expression: Expression, now: Timestamp) -> Option<Timestamp> {
+    if let Some(timestamp) = expression.timestamp {
+        return Some(timestamp);
+    }
+    let current = local::moment(now)?.civil;
+
+    // Anything but relative items, or nothing at all, means the start of
+    // the day unless a time is given
+    let absolute = expression.date.is_some()
+        || expression.time.is_some()
+        || expression.zone.is_some()
+        || expression.weekday.is_some()
+        || !expression.relative_seen;
+
+    let mut date = match &expression.date {
+        Some(date) => NaiveDate::from_ymd_opt(
+            date.year.unwrap_or(current.year()),
+            date.month,
+            date.day,
+        )?,
+        None => current.date(),
+    };
+    let (hour, minute, second, nanos) = match &expression.time {
+        Some(time) => (time.hour, time.minute, time.second, time.nanos),
+        None if absolute => (0, 0, 0, 0),
+        None => (current.hour(), current.minute(), current.second(), now.nanos),
+    };
+
+    // A date and time given outright have to exist on the local clock
+    if expression.zone.is_none() && (expression.date.is_some() || expression.time.is_some()) {
+        local::from_civil(date.and_hms_opt(hour, minute, second)?, true)?;
+    }
+
+    if let (Some((ordinal, day)), None) = (expression.weekday, &expression.date) {
+        let today = date.weekday().num_days_from_sunday() as i64;
+        let day = day as i64;
+        let ahead = (day - today + 7) % 7 + 7 * (ordinal - (ordinal > 0 && today != day) as i64);
+        date = add_days(date, ahead)?;
+    }
+
+    let relative = &expression.relative;
+    if relative.years != 0 || relative.months != 0 || relative.days != 0 {
+        // Months past the end of the month carry over, as with mktime
+        let months = (date.year() as i64 * 12 + date.month0() as i64)
+            .checked_add(relative.years.checked_mul(12)?)?
+            .checked_add(relative.months)?;
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let first = NaiveDate::from_ymd_opt(year, months.rem_euclid(12) as u32 + 1, 1)?;
+        date = add_days(first, (date.day() as i64 - 1).checked_add(relative.days)?)?;
+    }
+
+    let civil: NaiveDateTime = date.and_hms_opt(hour, minute, second)?;
+    let seconds = match expression.zone {
+        Some(offset) => civil.and_utc().timestamp() - offset,
+        None => local::from_civil(civil, false)?,
+    };
+    Some(Timestamp {
+        seconds: seconds.checked_add(relative.seconds)?,
+        nanos,
+    })
+}
+
+fn add_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }
+}
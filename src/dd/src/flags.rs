@@ -0,0 +1,67 @@
+// ASD CoreUtils - dd
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The comma-separated symbols of conv=, iflag= and oflag=
+
+#[derive(Clone, Copy, Default)]
+pub struct Conversions {
+    // Pad every input block to the block size with NULs
+    pub sync: bool,
+    // Go on after read errors
+    pub noerror: bool,
+    // Leave the output file at its length
+    pub notrunc: bool,
+    pub fsync: bool,
+    pub fdatasync: bool,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Flags {
+    // O_* flags the file is opened with
+    pub open: libc::c_int,
+    // Read again until a block is full or the input ends (iflag only)
+    pub fullblock: bool,
+    // count= and skip= are in bytes, taken from iflag; seek= is, from oflag.
+    // Either accepts all three, as GNU dd does.
+    pub count_bytes: bool,
+    pub skip_bytes: bool,
+    pub seek_bytes: bool,
+}
+
+pub fn parse_conversions(value: &str, conversions: &mut Conversions) -> Result<(), String> {
+    for symbol in value.split(',') {
+        match symbol {
+            "sync" => conversions.sync = true,
+            "noerror" => conversions.noerror = true,
+            "notrunc" => conversions.notrunc = true,
+            "fsync" => conversions.fsync = true,
+            "fdatasync" => conversions.fdatasync = true,
+            _ => return Err(format!("invalid conversion: '{}'", symbol)),
+        }
+    }
+    Ok(())
+}
+
+// Add the symbols in VALUE to FLAGS, for iflag= if INPUT, else for oflag=
+pub fn parse_flags(value: &str, flags: &mut Flags, input: bool) -> Result<(), String> {
+    for symbol in value.split(',') {
+        match (symbol, input) {
+            ("append", _) => flags.open |= libc::O_APPEND,
+            ("direct", _) => flags.open |= libc::O_DIRECT,
+            ("dsync", _) => flags.open |= libc::O_DSYNC,
+            ("sync", _) => flags.open |= libc::O_SYNC,
+            ("nonblock", _) => flags.open |= libc::O_NONBLOCK,
+            ("noatime", _) => flags.open |= libc::O_NOATIME,
+            ("noctty", _) => flags.open |= libc::O_NOCTTY,
+            ("nofollow", _) => flags.open |= libc::O_NOFOLLOW,
+            ("fullblock", true) => flags.fullblock = true,
+            ("count_bytes", _) => flags.count_bytes = true,
+            ("skip_bytes", _) => flags.skip_bytes = true,
+            ("seek_bytes", _) => flags.seek_bytes = true,
+            (_, true) => return Err(format!("invalid input flag: '{}'", symbol)),
+            (_, false) => return Err(format!("invalid output flag: '{}'", symbol)),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,664 @@
+// ASD CoreUtils - dd
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod flags;
+
+use asd_coreutils_core::error::{fail, strerror};
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, Command};
+use flags::{Conversions, Flags};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::{Deref, DerefMut};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+const DEFAULT_BLOCK_SIZE: usize = 512;
+
+// Time between updates of the status=progress line
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+// Set by the SIGUSR1 (or SIGINFO) handler, asking for the statistics so far
+static INFO_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// What is written to stderr, from status=
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Default,
+    None,
+    Noxfer,
+    Progress,
+}
+
+struct Settings {
+    input: Option<String>,
+    output: Option<String>,
+    block_size: usize,
+    count: Option<u64>,
+    // Offsets into the input and output, in bytes
+    skip: u64,
+    seek: u64,
+    status: Status,
+    conv: Conversions,
+    iflags: Flags,
+    oflags: Flags,
+}
+
+#[derive(Default)]
+struct Stats {
+    records_in_full: u64,
+    records_in_partial: u64,
+    records_out_full: u64,
+    records_out_partial: u64,
+    bytes: u64,
+}
+
+fn main() {
+    let matches = Command::new("dd")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils dd - convert and copy a file")
+        .override_usage("dd [OPERAND]...")
+        .after_help(
+            "Operands:\n  \
+             bs=BYTES     read and write up to BYTES bytes at a time (default: 512)\n  \
+             conv=CONVS   convert the file as per the comma separated symbol list\n  \
+             count=N      copy only N input blocks\n  \
+             if=FILE      read from FILE instead of stdin\n  \
+             iflag=FLAGS  read as per the comma separated symbol list\n  \
+             of=FILE      write to FILE instead of stdout\n  \
+             oflag=FLAGS  write as per the comma separated symbol list\n  \
+             seek=N       skip N obs-sized blocks at start of output\n  \
+             skip=N       skip N ibs-sized blocks at start of input\n  \
+             status=LEVEL the LEVEL of information to print to stderr;\n               \
+             'none' suppresses everything but error messages,\n               \
+             'noxfer' suppresses the final transfer statistics,\n               \
+             'progress' shows periodic transfer statistics\n\n\
+             N and BYTES may be followed by the multiplicative suffixes c=1, w=2,\n\
+             b=512, kB=1000, K=1024, MB=1000*1000, M=1024*1024, and so on for G, T, P.\n\n\
+             Each CONV symbol may be:\n  \
+             sync         pad every input block with NULs to ibs-size\n  \
+             noerror      continue after read errors\n  \
+             notrunc      do not truncate the output file\n  \
+             fdatasync    physically write output file data before finishing\n  \
+             fsync        likewise, but also write metadata\n\n\
+             Each FLAG symbol may be:\n  \
+             append       append mode (makes sense only for output; conv=notrunc suggested)\n  \
+             direct       use direct I/O for data\n  \
+             dsync        use synchronized I/O for data\n  \
+             sync         likewise, but also for metadata\n  \
+             fullblock    accumulate full blocks of input (iflag only)\n  \
+             nonblock     use non-blocking I/O\n  \
+             noatime      do not update access time\n  \
+             noctty       do not assign controlling terminal from file\n  \
+             nofollow     do not follow symlinks\n  \
+             count_bytes  treat 'count=N' as a byte count (iflag only)\n  \
+             skip_bytes   treat 'skip=N' as a byte count (iflag only)\n  \
+             seek_bytes   treat 'seek=N' as a byte count (oflag only)\n\n\
+             Sending a USR1 signal to a running dd process makes it print I/O\n\
+             statistics to standard error and then resume copying.",
+        )
+        .arg(
+            Arg::new("operand")
+                .value_name("OPERAND")
+                .help("Operands in KEY=VALUE form")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let operands: Vec<&String> = matches
+        .get_many::<String>("operand")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+
+    let settings = match parse_operands(&operands) {
+        Ok(settings) => settings,
+        Err(err) => fail("dd", &err),
+    };
+
+    install_info_handler();
+    let mut reporter = Reporter::new(settings.status);
+    let mut stats = Stats::default();
+    let result = copy(&settings, &mut stats, &mut reporter);
+
+    reporter.summary(&stats);
+
+    if let Err(err) = result {
+        fail("dd", &strerror(&err));
+    }
+}
+
+fn parse_operands(operands: &[&String]) -> Result<Settings, String> {
+    let mut settings = Settings {
+        input: None,
+        output: None,
+        block_size: DEFAULT_BLOCK_SIZE,
+        count: None,
+        skip: 0,
+        seek: 0,
+        status: Status::Default,
+        conv: Conversions::default(),
+        iflags: Flags::default(),
+        oflags: Flags::default(),
+    };
+    let (mut skip_text, mut seek_text) = ("", "");
+
+    for operand in operands {
+        let (key, value) = operand
+            .split_once('=')
+            .ok_or_else(|| format!("unrecognized operand '{}'", operand))?;
+
+        match key {
+            "if" => settings.input = Some(value.to_string()),
+            "of" => settings.output = Some(value.to_string()),
+            "bs" => {
+                let size = parse_number(value)?;
+                if size == 0 {
+                    return Err(format!("invalid number: '{}'", value));
+                }
+                settings.block_size =
+                    usize::try_from(size).map_err(|_| format!("invalid number: '{}'", value))?;
+            }
+            "count" => settings.count = Some(parse_number(value)?),
+            "skip" => {
+                settings.skip = parse_number(value)?;
+                skip_text = value;
+            }
+            "seek" => {
+                settings.seek = parse_number(value)?;
+                seek_text = value;
+            }
+            "conv" => flags::parse_conversions(value, &mut settings.conv)?,
+            "iflag" => flags::parse_flags(value, &mut settings.iflags, true)?,
+            "oflag" => flags::parse_flags(value, &mut settings.oflags, false)?,
+            "status" => {
+                settings.status = match value {
+                    "none" => Status::None,
+                    "noxfer" => Status::Noxfer,
+                    "progress" => Status::Progress,
+                    _ => return Err(format!("invalid status level: '{}'", value)),
+                }
+            }
+            _ => return Err(format!("unrecognized operand '{}'", operand)),
+        }
+    }
+
+    // skip and seek count blocks unless the flags say they are bytes; the
+    // offset in bytes must still fit
+    let block_size = settings.block_size as u64;
+    if !settings.iflags.skip_bytes {
+        settings.skip = settings
+            .skip
+            .checked_mul(block_size)
+            .ok_or_else(|| format!("invalid number: '{}'", skip_text))?;
+    }
+    if !settings.oflags.seek_bytes {
+        settings.seek = settings
+            .seek
+            .checked_mul(block_size)
+            .ok_or_else(|| format!("invalid number: '{}'", seek_text))?;
+    }
+
+    Ok(settings)
+}
+
+// Parses a count such as "4K", "1MB" or "2x512"
+fn parse_number(value: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid number: '{}'", value);
+
+    let mut product: u64 = 1;
+    for factor in value.split('x') {
+        let number = parse_size(factor, &[("c", 1), ("w", 2), ("b", 512)]).ok_or_else(invalid)?;
+        product = product.checked_mul(number).ok_or_else(invalid)?;
+    }
+
+    Ok(product)
+}
+
+fn open_input(settings: &Settings) -> io::Result<File> {
+    match &settings.input {
+        Some(path) => OpenOptions::new()
+            .read(true)
+            .custom_flags(settings.iflags.open)
+            .open(path)
+            .map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("failed to open '{}': {}", path, strerror(&err)),
+                )
+            }),
+        None => {
+            let file = dup_fd(libc::STDIN_FILENO)?;
+            add_flags(&file, settings.iflags.open, "standard input")?;
+            Ok(file)
+        }
+    }
+}
+
+fn open_output(settings: &Settings) -> io::Result<File> {
+    match &settings.output {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .custom_flags(settings.oflags.open)
+                .open(path)
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("failed to open '{}': {}", path, strerror(&err)),
+                    )
+                })?;
+
+            // Like GNU dd, keep whatever precedes the seek position and drop the rest
+            if !settings.conv.notrunc && file.metadata()?.is_file() {
+                file.set_len(settings.seek)?;
+            }
+            Ok(file)
+        }
+        None => {
+            let file = dup_fd(libc::STDOUT_FILENO)?;
+            add_flags(&file, settings.oflags.open, "standard output")?;
+            Ok(file)
+        }
+    }
+}
+
+// Duplicating the standard descriptors lets them be handled as ordinary
+// files without closing the originals on drop
+fn dup_fd(fd: libc::c_int) -> io::Result<File> {
+    let new_fd = unsafe { libc::dup(fd) };
+    if new_fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { File::from_raw_fd(new_fd) })
+}
+
+// Give an open descriptor the flags asked for, those of them that can be
+// changed once it is open
+fn add_flags(file: &File, flags: libc::c_int, name: &str) -> io::Result<()> {
+    if flags == 0 {
+        return Ok(());
+    }
+    let fd = file.as_raw_fd();
+    let old = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    if old < 0 || unsafe { libc::fcntl(fd, libc::F_SETFL, old | flags) } != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("setting flags for '{}': {}", name, strerror(&err)),
+        ));
+    }
+    Ok(())
+}
+
+fn copy(settings: &Settings, stats: &mut Stats, reporter: &mut Reporter) -> io::Result<()> {
+    let mut input = open_input(settings)?;
+    let mut output = open_output(settings)?;
+    let block_size = settings.block_size;
+    let mut buffer = AlignedBuffer::new(block_size);
+    let input_name = match &settings.input {
+        Some(path) => format!("'{}'", path),
+        None => "standard input".to_string(),
+    };
+
+    if settings.skip > 0 {
+        skip_input(&mut input, settings.skip, &mut buffer)?;
+    }
+
+    if settings.seek > 0 {
+        output.seek(SeekFrom::Start(settings.seek))?;
+    }
+
+    let mut bytes_in: u64 = 0;
+    let mut direct = settings.oflags.open & libc::O_DIRECT != 0;
+    loop {
+        let mut want = block_size;
+        if let Some(count) = settings.count {
+            if settings.iflags.count_bytes {
+                if bytes_in >= count {
+                    break;
+                }
+                want = (count - bytes_in).min(block_size as u64) as usize;
+            } else if stats.records_in_full + stats.records_in_partial >= count {
+                break;
+            }
+        }
+
+        let (read, error) = read_input(
+            &mut input,
+            &mut buffer[..want],
+            settings.iflags.fullblock,
+            stats,
+            reporter,
+        );
+        if let Some(err) = error {
+            let message = format!("error reading {}: {}", input_name, strerror(&err));
+            if !settings.conv.noerror {
+                return Err(io::Error::new(err.kind(), message));
+            }
+            if settings.status != Status::None {
+                eprintln!("dd: {}", message);
+            }
+            reporter.summary(stats);
+            // Go on after the bad block, where the input can seek
+            let _ = input.seek(SeekFrom::Current((want - read) as i64));
+            if read == 0 && !settings.conv.sync {
+                continue;
+            }
+        } else if read == 0 {
+            break;
+        }
+        bytes_in += read as u64;
+
+        if read == block_size {
+            stats.records_in_full += 1;
+        } else {
+            stats.records_in_partial += 1;
+        }
+
+        let mut length = read;
+        if settings.conv.sync && read < block_size {
+            buffer[read..].fill(0);
+            length = block_size;
+        }
+        write_block(&mut output, &buffer[..length], block_size, &mut direct)?;
+
+        if length == block_size {
+            stats.records_out_full += 1;
+        } else {
+            stats.records_out_partial += 1;
+        }
+        stats.bytes += length as u64;
+        reporter.update(stats);
+    }
+
+    output.flush()?;
+    let synced = if settings.conv.fsync {
+        output.sync_all()
+    } else if settings.conv.fdatasync {
+        output.sync_data()
+    } else {
+        Ok(())
+    };
+    synced.map_err(|err| {
+        let name = settings.output.as_deref().unwrap_or("standard output");
+        io::Error::new(
+            err.kind(),
+            format!("fsync failed for '{}': {}", name, strerror(&err)),
+        )
+    })
+}
+
+// Read a block into BUFFER, with fullblock reading again until it is full
+// or the input ends. What was read before an error is kept.
+fn read_input(
+    input: &mut File,
+    buffer: &mut [u8],
+    fullblock: bool,
+    stats: &Stats,
+    reporter: &mut Reporter,
+) -> (usize, Option<io::Error>) {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match input.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => {
+                filled += read;
+                if !fullblock {
+                    break;
+                }
+            }
+            // A signal asking for statistics interrupts a read that waits
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => reporter.update(stats),
+            Err(err) => return (filled, Some(err)),
+        }
+    }
+    (filled, None)
+}
+
+// Direct I/O takes whole blocks only, so like GNU dd it is turned off for
+// the short block at the end
+fn write_block(
+    output: &mut File,
+    data: &[u8],
+    block_size: usize,
+    direct: &mut bool,
+) -> io::Result<()> {
+    if *direct && data.len() < block_size {
+        let fd = output.as_raw_fd();
+        unsafe {
+            let flags = libc::fcntl(fd, libc::F_GETFL);
+            if flags >= 0 {
+                libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_DIRECT);
+            }
+        }
+        *direct = false;
+    }
+    output.write_all(data)
+}
+
+fn skip_input(input: &mut File, offset: u64, buffer: &mut [u8]) -> io::Result<()> {
+    let target = input
+        .stream_position()
+        .ok()
+        .and_then(|start| start.checked_add(offset));
+    if let Some(target) = target {
+        if input.seek(SeekFrom::Start(target)).is_ok() {
+            return Ok(());
+        }
+    }
+
+    // Pipes and terminals can't seek, so the skipped blocks are read and
+    // dropped, a short read counting as a whole block
+    let mut left = offset;
+    while left > 0 {
+        let want = left.min(buffer.len() as u64) as usize;
+        if read_block(input, &mut buffer[..want])? == 0 {
+            eprintln!("dd: cannot skip to specified offset");
+            break;
+        }
+        left -= want as u64;
+    }
+    Ok(())
+}
+
+fn read_block(input: &mut File, buffer: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match input.read(buffer) {
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }
+}
+
+// A block buffer starting on a page boundary, as direct I/O needs memory
+// aligned to the device's block size
+struct AlignedBuffer {
+    storage: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl AlignedBuffer {
+    fn new(len: usize) -> Self {
+        let align = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as usize;
+        let storage = vec![0; len + align];
+        let start = storage.as_ptr().align_offset(align);
+        AlignedBuffer {
+            storage,
+            start,
+            len,
+        }
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.storage[self.start..self.start + self.len]
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.storage[self.start..self.start + self.len]
+    }
+}
+
+extern "C" fn request_info(_: libc::c_int) {
+    INFO_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+// SIGUSR1 asks for the statistics so far, as does SIGINFO where there is
+// one. Without SA_RESTART a read that waits is interrupted, so they are
+// printed right away.
+fn install_info_handler() {
+    let signals = [
+        libc::SIGUSR1,
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "dragonfly"
+        ))]
+        libc::SIGINFO,
+    ];
+
+    for signal in signals {
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = request_info as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+            libc::sigaction(signal, &action, std::ptr::null_mut());
+        }
+    }
+}
+
+// Statistics on stderr: the progress line, and the summary at the end or
+// when a signal asks for it
+struct Reporter {
+    status: Status,
+    start: Instant,
+    next_progress: Instant,
+    // Length of the progress line shown, for blanking what a shorter one
+    // leaves of it; 0 when there is none
+    progress_len: usize,
+}
+
+impl Reporter {
+    fn new(status: Status) -> Self {
+        let start = Instant::now();
+        Reporter {
+            status,
+            start,
+            next_progress: start + PROGRESS_INTERVAL,
+            progress_len: 0,
+        }
+    }
+
+    fn update(&mut self, stats: &Stats) {
+        if INFO_REQUESTED.swap(false, Ordering::Relaxed) {
+            self.summary(stats);
+        }
+        if self.status != Status::Progress {
+            return;
+        }
+        let now = Instant::now();
+        if now < self.next_progress {
+            return;
+        }
+        while self.next_progress <= now {
+            self.next_progress += PROGRESS_INTERVAL;
+        }
+
+        let line = transfer_line(stats.bytes, (now - self.start).as_secs_f64(), true);
+        let blank = self.progress_len.saturating_sub(line.len());
+        eprint!("\r{}{}", line, " ".repeat(blank));
+        self.progress_len = line.len();
+    }
+
+    fn summary(&mut self, stats: &Stats) {
+        if self.status == Status::None {
+            return;
+        }
+        if self.progress_len > 0 {
+            eprintln!();
+            self.progress_len = 0;
+        }
+
+        eprintln!(
+            "{}+{} records in",
+            stats.records_in_full, stats.records_in_partial
+        );
+        eprintln!(
+            "{}+{} records out",
+            stats.records_out_full, stats.records_out_partial
+        );
+        if self.status != Status::Noxfer {
+            let elapsed = self.start.elapsed().as_secs_f64();
+            eprintln!("{}", transfer_line(stats.bytes, elapsed, false));
+        }
+    }
+}
+
+// The bytes copied and the rate, with whole seconds on the progress line
+fn transfer_line(bytes: u64, elapsed: f64, progress: bool) -> String {
+    let rate = if elapsed > 0.0 {
+        format_si(bytes as f64 / elapsed)
+    } else {
+        "Infinity B".to_string()
+    };
+    let elapsed = if progress {
+        format!("{:.0} s", elapsed)
+    } else {
+        format!("{:.6} s", elapsed)
+    };
+
+    if bytes < 1000 {
+        format!("{} bytes copied, {}, {}/s", bytes, elapsed, rate)
+    } else {
+        format!(
+            "{} bytes ({}, {}) copied, {}, {}/s",
+            bytes,
+            format_si(bytes as f64),
+            format_iec(bytes as f64),
+            elapsed,
+            rate
+        )
+    }
+}
+
+fn format_si(value: f64) -> String {
+    format_scaled(value, 1000.0, &["B", "kB", "MB", "GB", "TB", "PB", "EB"])
+}
+
+fn format_iec(value: f64) -> String {
+    format_scaled(
+        value,
+        1024.0,
+        &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"],
+    )
+}
+
+fn format_scaled(mut value: f64, base: f64, units: &[&str]) -> String {
+    let mut unit = 0;
+    while value >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{:.0} {}", value, units[unit])
+    } else if value < 10.0 {
+        format!("{:.1} {}", value, units[unit])
+    } else {
+        format!("{:.0} {}", value, units[unit])
+    }
+}
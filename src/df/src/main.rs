@@ -0,0 +1,588 @@
+// ASD CoreUtils - df
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod mounts;
+mod units;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::size::{parse_block_size, Scale};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use mounts::{Mount, Usage};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::process;
+
+// Everything --output can show, in the order it shows them without a list
+const ALL_FIELDS: &str =
+    "source,fstype,itotal,iused,iavail,ipcent,size,used,avail,pcent,file,target";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Source,
+    Fstype,
+    Itotal,
+    Iused,
+    Iavail,
+    Ipcent,
+    Size,
+    Used,
+    Avail,
+    Pcent,
+    File,
+    Target,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Field> {
+        Some(match name {
+            "source" => Field::Source,
+            "fstype" => Field::Fstype,
+            "itotal" => Field::Itotal,
+            "iused" => Field::Iused,
+            "iavail" => Field::Iavail,
+            "ipcent" => Field::Ipcent,
+            "size" => Field::Size,
+            "used" => Field::Used,
+            "avail" => Field::Avail,
+            "pcent" => Field::Pcent,
+            "file" => Field::File,
+            "target" => Field::Target,
+            _ => return None,
+        })
+    }
+
+    // Columns are at least this wide, as in GNU df
+    fn min_width(self) -> usize {
+        match self {
+            Field::Source => 14,
+            Field::Fstype | Field::Ipcent | Field::Pcent => 4,
+            Field::File | Field::Target => 0,
+            _ => 5,
+        }
+    }
+
+    fn left_aligned(self) -> bool {
+        matches!(
+            self,
+            Field::Source | Field::Fstype | Field::File | Field::Target
+        )
+    }
+}
+
+// Which set of headings is used
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Default,
+    Posix,
+    Output,
+}
+
+struct Table {
+    fields: Vec<Field>,
+    mode: Mode,
+    scale: Scale,
+}
+
+// Which file systems are shown, from -a, -l, -t and -x
+struct Filter {
+    all: bool,
+    local: bool,
+    types: Vec<String>,
+    excluded: Vec<String>,
+}
+
+impl Filter {
+    fn shows(&self, mount: &Mount) -> bool {
+        !(self.local && mount.is_remote())
+            && (self.types.is_empty() || self.types.contains(&mount.fstype))
+            && !self.excluded.contains(&mount.fstype)
+    }
+
+    // Dummy and empty file systems are left out of the full list, unless
+    // all are asked for or types are picked by name
+    fn hides_dummies(&self) -> bool {
+        !self.all && self.types.is_empty()
+    }
+}
+
+impl Table {
+    fn human(&self) -> bool {
+        matches!(self.scale, Scale::Human { .. })
+    }
+
+    fn header(&self, field: Field) -> String {
+        match field {
+            Field::Source => "Filesystem".to_string(),
+            Field::Fstype => "Type".to_string(),
+            Field::Itotal => "Inodes".to_string(),
+            Field::Iused => "IUsed".to_string(),
+            Field::Iavail => "IFree".to_string(),
+            Field::Ipcent => "IUse%".to_string(),
+            Field::Size => match &self.scale {
+                Scale::Human { .. } => "Size".to_string(),
+                Scale::Blocks { size, .. } if self.mode == Mode::Posix => {
+                    format!("{}-blocks", size)
+                }
+                Scale::Blocks { size, .. } => units::block_header(*size),
+            },
+            Field::Used => "Used".to_string(),
+            Field::Avail if self.human() || self.mode == Mode::Output => "Avail".to_string(),
+            Field::Avail => "Available".to_string(),
+            Field::Pcent if !self.human() && self.mode == Mode::Posix => "Capacity".to_string(),
+            Field::Pcent => "Use%".to_string(),
+            Field::File => "File".to_string(),
+            Field::Target => "Mounted on".to_string(),
+        }
+    }
+
+    fn cell(&self, field: Field, mount: &Mount, usage: &Usage, file: Option<&str>) -> Vec<u8> {
+        let count = |count: u64| match self.scale {
+            Scale::Human { .. } => self.scale.format(count),
+            Scale::Blocks { .. } => count.to_string(),
+        };
+        let text = match field {
+            Field::Source => return mount.source.clone(),
+            Field::Fstype => mount.fstype.clone(),
+            Field::Itotal => count(usage.inodes),
+            Field::Iused => count(usage.iused),
+            Field::Iavail => count(usage.iavail),
+            Field::Ipcent => percent(usage.iused, usage.iavail),
+            Field::Size => self.scale.format(usage.size),
+            Field::Used => self.scale.format(usage.used),
+            Field::Avail => self.scale.format(usage.avail),
+            Field::Pcent => percent(usage.used, usage.avail),
+            Field::File => file.unwrap_or("-").to_string(),
+            Field::Target => return mount.target.clone(),
+        };
+        text.into_bytes()
+    }
+}
+
+fn main() {
+    let matches = Command::new("df")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils df - report file system space usage")
+        .after_help(
+            "Show information about the file system on which each FILE resides,\n\
+             or all file systems by default.\n\n\
+             Display values are in units of the first available SIZE from --block-size,\n\
+             and the DF_BLOCK_SIZE, BLOCK_SIZE and BLOCKSIZE environment variables.\n\
+             Otherwise, units default to 1024 bytes (or 512 if POSIXLY_CORRECT is set).\n\n\
+             FIELD_LIST is a comma-separated list of columns to be included. Valid\n\
+             field names are: 'source', 'fstype', 'itotal', 'iused', 'iavail', 'ipcent',\n\
+             'size', 'used', 'avail', 'pcent', 'file' and 'target'.",
+        )
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .help("Include pseudo, duplicate and inaccessible file systems")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("block-size")
+                .short('B')
+                .long("block-size")
+                .value_name("SIZE")
+                .help("Scale sizes by SIZE before printing them"),
+        )
+        .arg(
+            Arg::new("human-readable")
+                .short('h')
+                .long("human-readable")
+                .help("Print sizes in powers of 1024 (e.g., 1023M)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("si")
+                .short('H')
+                .long("si")
+                .help("Print sizes in powers of 1000 (e.g., 1.1G)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("inodes")
+                .short('i')
+                .long("inodes")
+                .help("List inode information instead of block usage")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("kilobytes")
+                .short('k')
+                .help("Like --block-size=1K")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("local")
+                .short('l')
+                .long("local")
+                .help("Limit listing to local file systems")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FIELD_LIST")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value(ALL_FIELDS)
+                .action(ArgAction::Append)
+                .help("Use the output format defined by FIELD_LIST, or print all fields if FIELD_LIST is omitted"),
+        )
+        .arg(
+            Arg::new("portability")
+                .short('P')
+                .long("portability")
+                .help("Use the POSIX output format")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("total")
+                .long("total")
+                .help("Produce a grand total")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("type")
+                .short('t')
+                .long("type")
+                .value_name("TYPE")
+                .action(ArgAction::Append)
+                .help("Limit listing to file systems of type TYPE"),
+        )
+        .arg(
+            Arg::new("print-type")
+                .short('T')
+                .long("print-type")
+                .help("Print file system type")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("exclude-type")
+                .short('x')
+                .long("exclude-type")
+                .value_name("TYPE")
+                .action(ArgAction::Append)
+                .help("Limit listing to file systems not of type TYPE"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .help("Show the file system holding each FILE")
+                .num_args(0..),
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .args_override_self(true)
+        .get_matches();
+
+    let table = table(&matches);
+    let types = |name: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(name)
+            .into_iter()
+            .flatten()
+            .flat_map(|list| list.split(','))
+            .map(str::to_string)
+            .collect()
+    };
+    let filter = Filter {
+        all: matches.get_flag("all"),
+        local: matches.get_flag("local"),
+        types: types("type"),
+        excluded: types("exclude-type"),
+    };
+    if let Some(both) = filter.types.iter().find(|name| filter.excluded.contains(name)) {
+        fail("df", &format!("file system type '{}' both selected and excluded", both));
+    }
+
+    let mounts = match mounts::read_mounts() {
+        Ok(mounts) => mounts,
+        Err(err) => {
+            eprintln!(
+                "df: cannot read table of mounted file systems: {}",
+                strerror(&err)
+            );
+            process::exit(1);
+        }
+    };
+
+    let mut ok = true;
+    let files: Vec<&String> = matches
+        .get_many::<String>("files")
+        .into_iter()
+        .flatten()
+        .collect();
+    let listing = files.is_empty();
+    let chosen: Vec<(&Mount, Option<&str>)> = if listing {
+        let mounts = if filter.all {
+            mounts.iter().collect()
+        } else {
+            mounts::without_duplicates(&mounts)
+        };
+        mounts
+            .into_iter()
+            .filter(|mount| !(filter.hides_dummies() && mount.is_dummy()))
+            .map(|mount| (mount, None))
+            .collect()
+    } else {
+        files
+            .iter()
+            .filter_map(|file| match mount_of(file, &mounts) {
+                Ok(mount) => Some((mount, Some(file.as_str()))),
+                Err(err) => {
+                    eprintln!("df: {}: {}", file, strerror(&err));
+                    ok = false;
+                    None
+                }
+            })
+            .collect()
+    };
+
+    let mut rows = Vec::new();
+    let mut total = Usage::default();
+    for (mount, file) in chosen.into_iter().filter(|(mount, _)| filter.shows(mount)) {
+        match mounts::usage(&mount.target) {
+            Ok(usage) if listing && filter.hides_dummies() && usage.size == 0 => {}
+            Ok(usage) => {
+                total.add(&usage);
+                rows.push(
+                    table
+                        .fields
+                        .iter()
+                        .map(|&field| table.cell(field, mount, &usage, file))
+                        .collect(),
+                );
+            }
+            Err(err) => {
+                eprintln!(
+                    "df: {}: {}",
+                    String::from_utf8_lossy(&mount.target),
+                    strerror(&err)
+                );
+                ok = false;
+            }
+        }
+    }
+
+    if rows.is_empty() && ok {
+        fail("df", "no file systems processed");
+    }
+    if !rows.is_empty() && matches.get_flag("total") {
+        let summary = Mount {
+            source: b"total".to_vec(),
+            target: b"-".to_vec(),
+            fstype: "-".to_string(),
+            dev: 0,
+        };
+        let row = table
+            .fields
+            .iter()
+            .map(|&field| table.cell(field, &summary, &total, Some("-")))
+            .collect();
+        rows.push(row);
+    }
+
+    if !rows.is_empty() {
+        let header = table
+            .fields
+            .iter()
+            .map(|&field| table.header(field).into_bytes())
+            .collect();
+        rows.insert(0, header);
+        if print_rows(&table.fields, &rows).is_err() {
+            process::exit(1);
+        }
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
+
+// The columns and headings chosen by -i, -T, -P and --output
+fn table(matches: &ArgMatches) -> Table {
+    let posix = matches.get_flag("portability");
+    let scale = scale(matches, posix);
+    let Some(lists) = matches.get_many::<String>("output") else {
+        let mut fields = vec![Field::Source];
+        if matches.get_flag("print-type") {
+            fields.push(Field::Fstype);
+        }
+        if matches.get_flag("inodes") {
+            fields.extend([Field::Itotal, Field::Iused, Field::Iavail, Field::Ipcent]);
+        } else {
+            fields.extend([Field::Size, Field::Used, Field::Avail, Field::Pcent]);
+        }
+        fields.push(Field::Target);
+        let mode = if posix { Mode::Posix } else { Mode::Default };
+        return Table {
+            fields,
+            mode,
+            scale,
+        };
+    };
+
+    for (option, name) in [("inodes", 'i'), ("print-type", 'T'), ("portability", 'P')] {
+        if matches.get_flag(option) {
+            usage_error("df", &format!(
+                "options -{} and --output are mutually exclusive",
+                name
+            ));
+        }
+    }
+    let mut fields = Vec::new();
+    for name in lists.flat_map(|list| list.split(',')) {
+        let Some(field) = Field::parse(name) else {
+            usage_error("df", &format!("option --output: field '{}' unknown", name));
+        };
+        if fields.contains(&field) {
+            usage_error("df", &format!(
+                "option --output: field '{}' used more than once",
+                name
+            ));
+        }
+        fields.push(field);
+    }
+    Table {
+        fields,
+        mode: Mode::Output,
+        scale,
+    }
+}
+
+// How sizes are printed: the last of -h, -H, -k and -B wins; without any,
+// -P means 1024-byte blocks and otherwise the environment has a say
+fn scale(matches: &ArgMatches, posix: bool) -> Scale {
+    let blocks = |size| Scale::Blocks {
+        size,
+        suffix: String::new(),
+    };
+    let chosen = [
+        ("human-readable", Scale::Human { base: 1024 }),
+        ("si", Scale::Human { base: 1000 }),
+        ("kilobytes", blocks(1024)),
+    ]
+    .into_iter()
+    .filter(|(name, _)| matches.get_flag(name))
+    .map(|(name, scale)| (matches.index_of(name).unwrap_or(0), scale))
+    .chain(matches.get_one::<String>("block-size").map(|size| {
+        let scale = parse_block_size(size).unwrap_or_else(|err| fail("df", &err));
+        (matches.index_of("block-size").unwrap_or(0), scale)
+    }))
+    .max_by_key(|(index, _)| *index);
+    if let Some((_, scale)) = chosen {
+        return scale;
+    }
+
+    let posixly_correct = env::var_os("POSIXLY_CORRECT").is_some();
+    if !posix {
+        for name in ["DF_BLOCK_SIZE", "BLOCK_SIZE", "BLOCKSIZE"] {
+            if let Some(Ok(scale)) = env::var(name)
+                .ok()
+                .map(|size| parse_block_size(&size))
+            {
+                return scale;
+            }
+        }
+    }
+    blocks(if posixly_correct { 512 } else { 1024 })
+}
+
+// USED as a share of what could be used, rounded up
+fn percent(used: u64, avail: u64) -> String {
+    let total = u128::from(used) + u128::from(avail);
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{}%", (u128::from(used) * 100).div_ceil(total))
+}
+
+// The mounted file system FILE is on: the one mounted from it if it is a
+// device, else the last mounted on the longest leading part of its path
+fn mount_of<'a>(file: &str, mounts: &'a [Mount]) -> io::Result<&'a Mount> {
+    let metadata = fs::metadata(file)?;
+    let path = fs::canonicalize(file)?;
+    let path = path.as_os_str().as_bytes();
+
+    if metadata.file_type().is_block_device() {
+        let from_device = mounts.iter().find(|mount| {
+            fs::canonicalize(std::ffi::OsStr::from_bytes(&mount.source))
+                .is_ok_and(|source| source.as_os_str().as_bytes() == path)
+        });
+        if let Some(mount) = from_device {
+            return Ok(mount);
+        }
+    }
+
+    let under = |mount: &&Mount| {
+        let target = &mount.target;
+        target.as_slice() == b"/"
+            || path == target.as_slice()
+            || (path.starts_with(target) && path.get(target.len()) == Some(&b'/'))
+    };
+    let longest = |candidates: Vec<&'a Mount>| {
+        candidates
+            .into_iter()
+            .max_by_key(|mount| mount.target.len())
+    };
+    let same_device = mounts
+        .iter()
+        .filter(under)
+        .filter(|mount| mount.dev == metadata.dev())
+        .collect();
+    longest(same_device)
+        .or_else(|| longest(mounts.iter().filter(under).collect()))
+        .ok_or_else(|| io::Error::from_raw_os_error(libc::ENOENT))
+}
+
+// Width of a cell in characters, counting each UTF-8 sequence once
+fn width(cell: &[u8]) -> usize {
+    cell.iter().filter(|&&c| c & 0xc0 != 0x80).count()
+}
+
+fn print_rows(fields: &[Field], rows: &[Vec<Vec<u8>>]) -> io::Result<()> {
+    let widths: Vec<usize> = fields
+        .iter()
+        .enumerate()
+        .map(|(column, field)| {
+            rows.iter()
+                .map(|row| width(&row[column]))
+                .max()
+                .unwrap_or(0)
+                .max(field.min_width())
+        })
+        .collect();
+
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    for row in rows {
+        let mut line = Vec::new();
+        for (column, cell) in row.iter().enumerate() {
+            if column > 0 {
+                line.push(b' ');
+            }
+            let padding = widths[column] - width(cell);
+            if fields[column].left_aligned() {
+                line.extend_from_slice(cell);
+                if column + 1 < row.len() {
+                    line.resize(line.len() + padding, b' ');
+                }
+            } else {
+                line.resize(line.len() + padding, b' ');
+                line.extend_from_slice(cell);
+            }
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,193 @@
+// ASD CoreUtils - df
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The table of mounted file systems, as the kernel gives it in
+// /proc/self/mountinfo, and the usage statvfs reports for each
+
+use std::ffi::CString;
+use std::fs;
+use std::io;
+
+const MOUNTINFO: &str = "/proc/self/mountinfo";
+
+// File systems with no storage of their own, or none worth listing by
+// default: kernel interfaces, memory-backed ones and container layers
+const DUMMY_TYPES: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "none",
+    "nsfs",
+    "overlay",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rootfs",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+// File systems whose storage is on another machine
+const REMOTE_TYPES: &[&str] = &[
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "coda",
+    "fuse.sshfs",
+    "gfs",
+    "gfs2",
+    "glusterfs",
+    "lustre",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+];
+
+pub struct Mount {
+    // What is mounted, a device or a name like "tmpfs"
+    pub source: Vec<u8>,
+    pub target: Vec<u8>,
+    pub fstype: String,
+    pub dev: u64,
+}
+
+impl Mount {
+    pub fn is_dummy(&self) -> bool {
+        DUMMY_TYPES.contains(&self.fstype.as_str())
+    }
+
+    // Also anything mounted from "host:path" or "//host/share"
+    pub fn is_remote(&self) -> bool {
+        REMOTE_TYPES.contains(&self.fstype.as_str())
+            || self.source.starts_with(b"//")
+            || (self.source.contains(&b':') && !self.source.starts_with(b"/"))
+    }
+}
+
+// Sizes in bytes, and counts of inodes
+#[derive(Clone, Copy, Default)]
+pub struct Usage {
+    pub size: u64,
+    pub used: u64,
+    pub avail: u64,
+    pub inodes: u64,
+    pub iused: u64,
+    pub iavail: u64,
+}
+
+impl Usage {
+    pub fn add(&mut self, other: &Usage) {
+        self.size = self.size.saturating_add(other.size);
+        self.used = self.used.saturating_add(other.used);
+        self.avail = self.avail.saturating_add(other.avail);
+        self.inodes = self.inodes.saturating_add(other.inodes);
+        self.iused = self.iused.saturating_add(other.iused);
+        self.iavail = self.iavail.saturating_add(other.iavail);
+    }
+}
+
+pub fn read_mounts() -> io::Result<Vec<Mount>> {
+    let table = fs::read(MOUNTINFO)?;
+    Ok(table
+        .split(|&c| c == b'\n')
+        .filter_map(parse_line)
+        .collect())
+}
+
+// A line is "ID PARENT MAJOR:MINOR ROOT TARGET OPTIONS [TAGS...] - TYPE
+// SOURCE SUPER-OPTIONS"
+fn parse_line(line: &[u8]) -> Option<Mount> {
+    let fields: Vec<&[u8]> = line.split(|&c| c == b' ').collect();
+    let (major, minor) = std::str::from_utf8(fields.get(2)?).ok()?.split_once(':')?;
+    let dev = libc::makedev(major.parse().ok()?, minor.parse().ok()?);
+    let target = unescape(fields.get(4)?);
+    let separator = fields.iter().skip(6).position(|&field| field == b"-")? + 6;
+    let fstype = String::from_utf8_lossy(&unescape(fields.get(separator + 1)?)).into_owned();
+    let source = unescape(fields.get(separator + 2)?);
+    Some(Mount {
+        source,
+        target,
+        fstype,
+        dev,
+    })
+}
+
+// MOUNTS with each device listed once, where it is mounted closest to the
+// root, and each mount point once, for whatever was mounted there last;
+// bind mounts and hidden mounts would only say the same again or mislead
+pub fn without_duplicates(mounts: &[Mount]) -> Vec<&Mount> {
+    let mut kept: Vec<&Mount> = Vec::new();
+    for mount in mounts {
+        if let Some(seen) = kept.iter_mut().find(|seen| seen.target == mount.target) {
+            *seen = mount;
+            continue;
+        }
+        match kept.iter_mut().find(|seen| seen.dev == mount.dev) {
+            Some(seen) if mount.target.len() < seen.target.len() => *seen = mount,
+            Some(_) => {}
+            None => kept.push(mount),
+        }
+    }
+    kept
+}
+
+// Undo the octal escapes used for spaces, tabs, newlines and backslashes
+fn unescape(field: &[u8]) -> Vec<u8> {
+    let mut text = Vec::with_capacity(field.len());
+    let mut rest = field;
+    while let Some((&c, after)) = rest.split_first() {
+        let digits = after
+            .get(..3)
+            .filter(|digits| digits.iter().all(|d| (b'0'..=b'7').contains(d)));
+        match digits {
+            Some(digits) if c == b'\\' => {
+                let value = digits
+                    .iter()
+                    .fold(0u32, |n, &d| n * 8 + u32::from(d - b'0'));
+                text.push(value as u8);
+                rest = &after[3..];
+            }
+            _ => {
+                text.push(c);
+                rest = after;
+            }
+        }
+    }
+    text
+}
+
+pub fn usage(target: &[u8]) -> io::Result<Usage> {
+    let path = CString::new(target).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let block = stat.f_frsize as u64;
+    let iavail = stat.f_ffree as u64;
+    Ok(Usage {
+        size: stat.f_blocks as u64 * block,
+        used: (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64) * block,
+        avail: stat.f_bavail as u64 * block,
+        inodes: stat.f_files as u64,
+        iused: (stat.f_files as u64).saturating_sub(iavail),
+        iavail,
+    })
+}
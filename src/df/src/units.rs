@@ -0,0 +1,31 @@
+// ASD CoreUtils - df
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Headings for sizes in blocks; the sizes themselves are formatted by
+// asd_coreutils_core::size
+
+use asd_coreutils_core::size::human;
+
+// The heading of the size column for blocks of SIZE bytes, as "1K-blocks":
+// in powers of 1024 if SIZE is a multiple of one, else of 1000 with a B
+pub fn block_header(size: u64) -> String {
+    let (mut q1000, mut q1024) = (size, size);
+    let by_1024 = loop {
+        let by_1000 = q1000 % 1000 == 0;
+        let by_1024 = q1024 % 1024 == 0;
+        q1000 /= 1000;
+        q1024 /= 1024;
+        if !(by_1000 && by_1024) {
+            break by_1024;
+        }
+    };
+    let base = if by_1024 { 1024 } else { 1000 };
+    // Without a decimal that is zero, as in 1K rather than 1.0K
+    let number = human(size, base).replacen(".0", "", 1);
+    if by_1024 {
+        format!("{}-blocks", number)
+    } else {
+        format!("{}B-blocks", number)
+    }
+}
@@ -0,0 +1,268 @@
+// ASD CoreUtils - dircolors
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, ArgGroup, Command};
+use std::env;
+use std::ffi::CString;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+const DEFAULT_DATABASE: &str = include_str!("default.dircolors");
+
+// Database keywords and the two-letter LS_COLORS codes they stand for
+const KEYWORDS: &[(&str, &str)] = &[
+    ("NORMAL", "no"),
+    ("NORM", "no"),
+    ("FILE", "fi"),
+    ("RESET", "rs"),
+    ("DIR", "di"),
+    ("LNK", "ln"),
+    ("LINK", "ln"),
+    ("SYMLINK", "ln"),
+    ("ORPHAN", "or"),
+    ("MISSING", "mi"),
+    ("FIFO", "pi"),
+    ("PIPE", "pi"),
+    ("SOCK", "so"),
+    ("BLK", "bd"),
+    ("BLOCK", "bd"),
+    ("CHR", "cd"),
+    ("CHAR", "cd"),
+    ("DOOR", "do"),
+    ("EXEC", "ex"),
+    ("LEFT", "lc"),
+    ("LEFTCODE", "lc"),
+    ("RIGHT", "rc"),
+    ("RIGHTCODE", "rc"),
+    ("END", "ec"),
+    ("ENDCODE", "ec"),
+    ("SUID", "su"),
+    ("SETUID", "su"),
+    ("SGID", "sg"),
+    ("SETGID", "sg"),
+    ("STICKY", "st"),
+    ("OWR", "ow"),
+    ("OTHER_WRITABLE", "ow"),
+    ("OWT", "tw"),
+    ("STICKY_OTHER_WRITABLE", "tw"),
+    ("CAPABILITY", "ca"),
+    ("MULTIHARDLINK", "mh"),
+    ("CLRTOEOL", "cl"),
+];
+
+// Accepted for compatibility with other dircolors implementations
+const IGNORED_KEYWORDS: &[&str] = &["OPTIONS", "COLOR", "EIGHTBIT"];
+
+#[derive(Clone, Copy)]
+enum Shell {
+    Bourne,
+    CShell,
+}
+
+// Whether the entries being read apply to the current terminal
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Global,
+    TermNo,
+    TermYes,
+    TermSure,
+}
+
+fn main() {
+    let matches = Command::new("dircolors")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils dircolors - output commands to set the LS_COLORS environment variable")
+        .arg(
+            Arg::new("bourne-shell")
+                .short('b')
+                .long("bourne-shell")
+                .visible_alias("sh")
+                .help("Output Bourne shell code to set LS_COLORS")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("c-shell")
+                .short('c')
+                .long("c-shell")
+                .visible_alias("csh")
+                .help("Output C shell code to set LS_COLORS")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("print-database")
+                .short('p')
+                .long("print-database")
+                .help("Output defaults")
+                .action(ArgAction::SetTrue),
+        )
+        .group(ArgGroup::new("mode").args(["bourne-shell", "c-shell", "print-database"]))
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Database to read instead of the built-in defaults"),
+        )
+        .get_matches();
+
+    if matches.get_flag("print-database") {
+        if let Some(file) = matches.get_one::<String>("file") {
+            eprintln!("dircolors: extra operand '{}'", file);
+            eprintln!("file operands cannot be combined with --print-database (-p)");
+            process::exit(1);
+        }
+        print!("{}", DEFAULT_DATABASE);
+        return;
+    }
+
+    let shell = if matches.get_flag("bourne-shell") {
+        Shell::Bourne
+    } else if matches.get_flag("c-shell") {
+        Shell::CShell
+    } else {
+        guess_shell().unwrap_or_else(|| {
+            fail("dircolors", "no SHELL environment variable, and no shell type option given")
+        })
+    };
+
+    let (name, database) = match matches.get_one::<String>("file") {
+        Some(file) => {
+            let contents = if file == "-" {
+                io::read_to_string(io::stdin())
+            } else {
+                fs::read_to_string(file)
+            };
+            match contents {
+                Ok(contents) => (file.as_str(), contents),
+                Err(err) => fail("dircolors", &format!("{}: {}", file, strerror(&err))),
+            }
+        }
+        None => ("<internal>", DEFAULT_DATABASE.to_string()),
+    };
+
+    let entries = match parse_database(name, &database) {
+        Ok(entries) => entries,
+        Err(errors) => {
+            for error in errors {
+                eprintln!("dircolors: {}", error);
+            }
+            process::exit(1);
+        }
+    };
+
+    let value: String = entries.iter().map(|entry| format!("{}:", entry)).collect();
+    let quoted = value.replace('\'', "'\\''");
+
+    let output = match shell {
+        Shell::Bourne => format!("LS_COLORS='{}';\nexport LS_COLORS\n", quoted),
+        Shell::CShell => format!("setenv LS_COLORS '{}'\n", quoted),
+    };
+
+    if let Err(err) = io::stdout().write_all(output.as_bytes()) {
+        fail("dircolors", &strerror(&err));
+    }
+}
+
+fn guess_shell() -> Option<Shell> {
+    let shell = env::var("SHELL").ok().filter(|shell| !shell.is_empty())?;
+    let name = Path::new(&shell).file_name()?.to_string_lossy().into_owned();
+
+    if name == "csh" || name == "tcsh" {
+        Some(Shell::CShell)
+    } else {
+        Some(Shell::Bourne)
+    }
+}
+
+// Turns the database into LS_COLORS entries such as "di=01;34" or
+// "*.tar=01;31", keeping only those meant for the current terminal
+fn parse_database(name: &str, database: &str) -> Result<Vec<String>, Vec<String>> {
+    let term = env::var("TERM").ok().filter(|term| !term.is_empty()).unwrap_or_else(|| "none".to_string());
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let mut state = State::Global;
+
+    for (number, line) in database.lines().enumerate() {
+        let Some((keyword, value)) = split_line(line) else {
+            continue;
+        };
+
+        if keyword.eq_ignore_ascii_case("TERM") || keyword.eq_ignore_ascii_case("COLORTERM") {
+            // Consecutive TERM lines form one block that applies if any matches
+            let subject = if keyword.eq_ignore_ascii_case("TERM") { &term } else { &colorterm };
+            if glob_matches(value, subject) {
+                state = State::TermSure;
+            } else if state != State::TermSure {
+                state = State::TermNo;
+            }
+            continue;
+        }
+
+        if state == State::TermSure {
+            state = State::TermYes;
+        }
+        if state == State::TermNo {
+            continue;
+        }
+
+        if let Some(extension) = keyword.strip_prefix('.') {
+            entries.push(format!("*.{}={}", escape_pattern(extension), value));
+        } else if keyword.starts_with('*') {
+            entries.push(format!("{}={}", escape_pattern(keyword), value));
+        } else if IGNORED_KEYWORDS.iter().any(|ignored| keyword.eq_ignore_ascii_case(ignored)) {
+            continue;
+        } else if let Some((_, code)) = KEYWORDS.iter().find(|(known, _)| keyword.eq_ignore_ascii_case(known)) {
+            entries.push(format!("{}={}", code, value));
+        } else {
+            errors.push(format!("{}:{}: unrecognized keyword {}", name, number + 1, keyword));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(entries)
+    } else {
+        Err(errors)
+    }
+}
+
+// Splits a line into its keyword and value, dropping comments; blank and
+// malformed lines yield None
+fn split_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (keyword, rest) = line.split_once(|c: char| c.is_ascii_whitespace())?;
+    let rest = rest.trim_start();
+
+    // A '#' only starts a comment at the beginning of a word
+    let end = rest
+        .char_indices()
+        .find(|&(index, c)| c == '#' && index > 0 && rest[..index].ends_with(|c: char| c.is_ascii_whitespace()))
+        .map_or(rest.len(), |(index, _)| index);
+    let value = rest[..end].trim_end();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some((keyword, value))
+    }
+}
+
+// ':' separates LS_COLORS entries, so it has to be escaped inside patterns
+fn escape_pattern(pattern: &str) -> String {
+    pattern.replace(':', "\\:")
+}
+
+fn glob_matches(pattern: &str, subject: &str) -> bool {
+    match (CString::new(pattern), CString::new(subject)) {
+        (Ok(pattern), Ok(subject)) => unsafe { libc::fnmatch(pattern.as_ptr(), subject.as_ptr(), 0) == 0 },
+        _ => false,
+    }
+}
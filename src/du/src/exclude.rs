@@ -0,0 +1,60 @@
+// ASD CoreUtils - du
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Shell patterns for --exclude and --exclude-from. As in GNU du a pattern
+// is matched against the whole path and against every part of it after a
+// slash, and wildcards match slashes too, so that 'deep' and '*/deep' both
+// leave out ./sub/deep.
+
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+
+#[derive(Default)]
+pub struct Exclude {
+    patterns: Vec<CString>,
+}
+
+impl Exclude {
+    pub fn add(&mut self, pattern: &[u8]) {
+        // A pattern cannot contain a NUL, and no path does either
+        if let Ok(pattern) = CString::new(pattern) {
+            self.patterns.push(pattern);
+        }
+    }
+
+    // Add the patterns in FILE, one per line, - being standard input
+    pub fn add_file(&mut self, file: &str) -> io::Result<()> {
+        let reader: Box<dyn Read> = if file == "-" {
+            Box::new(io::stdin())
+        } else {
+            Box::new(File::open(file)?)
+        };
+        for line in BufReader::new(reader).split(b'\n') {
+            let line = line?;
+            if !line.is_empty() {
+                self.add(&line);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, path: &[u8]) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let Ok(whole) = CString::new(path) else {
+            return false;
+        };
+        let whole = whole.as_bytes_with_nul();
+        let slashes = path.iter().enumerate().filter(|(_, &c)| c == b'/');
+        let mut starts = std::iter::once(0).chain(slashes.map(|(slash, _)| slash + 1));
+        starts.any(|start| {
+            let name = whole[start..].as_ptr() as *const libc::c_char;
+            self.patterns
+                .iter()
+                .any(|pattern| unsafe { libc::fnmatch(pattern.as_ptr(), name, 0) } == 0)
+        })
+    }
+}
@@ -0,0 +1,551 @@
+// ASD CoreUtils - du
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod exclude;
+mod walk;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::metadata::metadata;
+use asd_coreutils_core::size::{parse_block_size, parse_size, unit_size, Scale};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use exclude::Exclude;
+use std::collections::HashSet;
+use std::env;
+use std::io::{self, BufWriter, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use walk::{Dir, Meta};
+
+// Threads reading directories by default. Reading waits on the disk or
+// the network far more than on the processor, so more than one per CPU.
+const MIN_DEFAULT_THREADS: usize = 4;
+const MAX_DEFAULT_THREADS: usize = 16;
+
+// How the walked trees are reported
+struct Report {
+    all: bool,
+    apparent: bool,
+    max_depth: usize,
+    separate_dirs: bool,
+    count_links: bool,
+    // Count every file and directory once, not only those with several
+    // links: with several operands, which may overlap, and with -L
+    hash_all: bool,
+    scale: Scale,
+    // Sizes below this are not printed, or above its magnitude if negative
+    threshold: i64,
+    terminator: u8,
+}
+
+impl Report {
+    fn size(&self, meta: &Meta) -> u64 {
+        if self.apparent {
+            meta.apparent
+        } else {
+            meta.disk
+        }
+    }
+
+    fn shown(&self, size: u64) -> bool {
+        let size = i64::try_from(size).unwrap_or(i64::MAX);
+        if self.threshold >= 0 {
+            size >= self.threshold
+        } else {
+            size <= -self.threshold
+        }
+    }
+}
+
+// Writes the lines for the trees read, remembering what was counted
+struct Reporter<'a> {
+    report: &'a Report,
+    dirs: &'a [Dir],
+    seen: HashSet<(u64, u64)>,
+    out: BufWriter<io::StdoutLock<'static>>,
+    failed: bool,
+}
+
+impl Reporter<'_> {
+    // Whether META was counted already, and should not be again
+    fn counted(&mut self, meta: &Meta) -> bool {
+        let hashed = self.report.hash_all || (!meta.is_dir && meta.nlink > 1);
+        hashed && !self.report.count_links && !self.seen.insert((meta.dev, meta.ino))
+    }
+
+    fn print(&mut self, size: u64, path: &[u8]) -> io::Result<()> {
+        let size = self.report.scale.format(size);
+        self.out.write_all(size.as_bytes())?;
+        self.out.write_all(b"\t")?;
+        self.out.write_all(path)?;
+        self.out.write_all(&[self.report.terminator])
+    }
+
+    // The usage of directory ID at PATH and DEPTH, printing the lines for
+    // it and what is below it. The path is restored before returning.
+    fn directory(
+        &mut self,
+        id: usize,
+        meta: &Meta,
+        path: &mut Vec<u8>,
+        depth: usize,
+    ) -> io::Result<u64> {
+        let dir = &self.dirs[id];
+        let report = self.report;
+        let mut total = report.size(meta);
+        total += if report.apparent {
+            dir.files.0
+        } else {
+            dir.files.1
+        };
+
+        for error in &dir.errors {
+            eprintln!("du: {}", error);
+            self.failed = true;
+        }
+
+        let length = path.len();
+        for entry in &dir.entries {
+            if self.counted(&entry.meta) {
+                continue;
+            }
+            if path.last() != Some(&b'/') {
+                path.push(b'/');
+            }
+            path.extend_from_slice(entry.name.as_bytes());
+
+            match entry.dir {
+                Some(child) => {
+                    let size = self.directory(child, &entry.meta, path, depth + 1)?;
+                    if !report.separate_dirs {
+                        total += size;
+                    }
+                }
+                None => {
+                    let size = report.size(&entry.meta);
+                    total += size;
+                    if report.all && depth < report.max_depth && report.shown(size) {
+                        self.print(size, path)?;
+                    }
+                }
+            }
+            path.truncate(length);
+        }
+
+        if depth <= report.max_depth && report.shown(total) {
+            self.print(total, path)?;
+        }
+        Ok(total)
+    }
+}
+
+fn main() {
+    let matches = Command::new("du")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils du - estimate file space usage")
+        .after_help(
+            "Summarize device usage of the set of FILEs, recursively for directories.\n\n\
+             Display values are in units of the first available SIZE from --block-size,\n\
+             and the DU_BLOCK_SIZE, BLOCK_SIZE and BLOCKSIZE environment variables.\n\
+             Otherwise, units default to 1024 bytes (or 512 if POSIXLY_CORRECT is set).\n\n\
+             The SIZE argument is an integer and optional unit (example: 10K is 10*1024).\n\
+             Units are K,M,G,T,P,E,Z,Y,R,Q (powers of 1024) or KB,MB,... (powers of 1000).\n\n\
+             Directories are read by several threads at once; the output is the same\n\
+             as with one.",
+        )
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("null")
+                .short('0')
+                .long("null")
+                .help("End each output line with NUL, not newline")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .help("Write counts for all files, not just directories")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("apparent-size")
+                .long("apparent-size")
+                .help("Print apparent sizes rather than device usage")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("block-size")
+                .short('B')
+                .long("block-size")
+                .value_name("SIZE")
+                .help("Scale sizes by SIZE before printing them"),
+        )
+        .arg(
+            Arg::new("bytes")
+                .short('b')
+                .long("bytes")
+                .help("Equivalent to '--apparent-size --block-size=1'")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("total")
+                .short('c')
+                .long("total")
+                .help("Produce a grand total")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dereference-args")
+                .short('D')
+                .short_alias('H')
+                .long("dereference-args")
+                .help("Dereference only symlinks that are listed on the command line")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("max-depth")
+                .short('d')
+                .long("max-depth")
+                .value_name("N")
+                .help("Print the total for a directory only if it is N or fewer levels below the command line argument"),
+        )
+        .arg(
+            Arg::new("human-readable")
+                .short('h')
+                .long("human-readable")
+                .help("Print sizes in human readable format (e.g., 1K 234M 2G)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("si")
+                .long("si")
+                .help("Like -h, but use powers of 1000 not 1024")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("kilobytes")
+                .short('k')
+                .help("Like --block-size=1K")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("threshold")
+                .short('t')
+                .long("threshold")
+                .value_name("SIZE")
+                .allow_hyphen_values(true)
+                .help("Exclude entries smaller than SIZE if positive, or entries greater than SIZE if negative"),
+        )
+        .arg(
+            Arg::new("dereference")
+                .short('L')
+                .long("dereference")
+                .help("Dereference all symbolic links")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("count-links")
+                .short('l')
+                .long("count-links")
+                .help("Count sizes many times if hard linked")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("megabytes")
+                .short('m')
+                .help("Like --block-size=1M")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-dereference")
+                .short('P')
+                .long("no-dereference")
+                .help("Don't follow any symbolic links (this is the default)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("separate-dirs")
+                .short('S')
+                .long("separate-dirs")
+                .help("For directories do not include size of subdirectories")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("summarize")
+                .short('s')
+                .long("summarize")
+                .help("Display only a total for each argument")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("one-file-system")
+                .short('x')
+                .long("one-file-system")
+                .help("Skip directories on different file systems")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("exclude-from")
+                .short('X')
+                .long("exclude-from")
+                .value_name("FILE")
+                .help("Exclude files that match any pattern in FILE")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("exclude")
+                .long("exclude")
+                .value_name("PATTERN")
+                .help("Exclude files that match PATTERN")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("parallel")
+                .long("parallel")
+                .value_name("N")
+                .help("Read up to N directories at once"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .help("Files and directories to summarize")
+                .num_args(0..),
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .args_override_self(true)
+        .get_matches();
+
+    let files: Vec<String> = matches
+        .get_many::<String>("files")
+        .map(|files| files.cloned().collect())
+        .unwrap_or_else(|| vec![".".to_string()]);
+
+    let dereference = matches.get_flag("dereference") && !matches.get_flag("no-dereference");
+    let report = Report {
+        all: matches.get_flag("all"),
+        apparent: matches.get_flag("apparent-size") || matches.get_flag("bytes"),
+        max_depth: max_depth(&matches),
+        separate_dirs: matches.get_flag("separate-dirs"),
+        count_links: matches.get_flag("count-links"),
+        hash_all: files.len() > 1 || dereference,
+        scale: scale(&matches),
+        threshold: threshold(&matches),
+        terminator: if matches.get_flag("null") {
+            b'\0'
+        } else {
+            b'\n'
+        },
+    };
+
+    let threads = match matches.get_one::<String>("parallel") {
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => usage_error("du", &format!("invalid --parallel argument '{}'", count)),
+        },
+        None => thread::available_parallelism()
+            .map_or(1, |count| count.get() * 2)
+            .clamp(MIN_DEFAULT_THREADS, MAX_DEFAULT_THREADS),
+    };
+
+    let mut exclude = Exclude::default();
+    for pattern in matches.get_many::<String>("exclude").into_iter().flatten() {
+        exclude.add(pattern.as_bytes());
+    }
+    for file in matches
+        .get_many::<String>("exclude-from")
+        .into_iter()
+        .flatten()
+    {
+        if let Err(err) = exclude.add_file(file) {
+            usage_error("du", &format!("{}: {}", file, strerror(&err)));
+        }
+    }
+
+    let mut ok = true;
+    let follow_args = dereference || matches.get_flag("dereference-args");
+    let mut operands: Vec<(Vec<u8>, Meta)> = Vec::new();
+    for file in &files {
+        let path = operand_path(file);
+        if exclude.matches(&path) {
+            continue;
+        }
+        match metadata(file, follow_args) {
+            Ok(metadata) => operands.push((path, Meta::new(&metadata))),
+            Err(err) => {
+                eprintln!("du: cannot access '{}': {}", file, strerror(&err));
+                ok = false;
+            }
+        }
+    }
+
+    let roots: Vec<(PathBuf, Meta)> = operands
+        .iter()
+        .filter(|(_, meta)| meta.is_dir)
+        .map(|(path, meta)| (PathBuf::from(std::ffi::OsStr::from_bytes(path)), *meta))
+        .collect();
+    let options = walk::Options {
+        dereference,
+        keep_files: report.all || report.hash_all,
+        exclude,
+        one_file_system: matches.get_flag("one-file-system"),
+        threads,
+    };
+    let dirs = walk::walk(&roots, &options);
+
+    let mut reporter = Reporter {
+        report: &report,
+        dirs: &dirs,
+        seen: HashSet::new(),
+        out: BufWriter::new(io::stdout().lock()),
+        failed: false,
+    };
+    let result = (|| -> io::Result<()> {
+        let mut total = 0;
+        let mut next_dir = 0;
+        for (path, meta) in &operands {
+            let id = next_dir;
+            if meta.is_dir {
+                next_dir += 1;
+            }
+            if reporter.counted(meta) {
+                continue;
+            }
+            total += if meta.is_dir {
+                reporter.directory(id, meta, &mut path.clone(), 0)?
+            } else {
+                let size = report.size(meta);
+                if report.shown(size) {
+                    reporter.print(size, path)?;
+                }
+                size
+            };
+        }
+        if matches.get_flag("total") {
+            reporter.print(total, b"total")?;
+        }
+        reporter.out.flush()
+    })();
+
+    if let Err(err) = result {
+        fail("du", &format!("write error: {}", strerror(&err)));
+    }
+    process::exit(if ok && !reporter.failed { 0 } else { 1 });
+}
+
+// -d, or -s as -d 0, which only -a contradicts
+fn max_depth(matches: &ArgMatches) -> usize {
+    let depth = matches.get_one::<String>("max-depth").map(|depth| {
+        depth
+            .parse::<usize>()
+            .unwrap_or_else(|_| usage_error("du", &format!("invalid maximum depth '{}'", depth)))
+    });
+    if matches.get_flag("summarize") {
+        if matches.get_flag("all") {
+            usage_error("du", "cannot both summarize and show all entries");
+        }
+        match depth {
+            Some(depth) if depth != 0 => {
+                usage_error(
+                    "du",
+                    &format!("warning: summarizing conflicts with --max-depth={}", depth),
+                );
+            }
+            _ => return 0,
+        }
+    }
+    depth.unwrap_or(usize::MAX)
+}
+
+// The -t argument, a number of bytes with an optional unit as for -B
+fn threshold(matches: &ArgMatches) -> i64 {
+    let Some(text) = matches.get_one::<String>("threshold") else {
+        return 0;
+    };
+    let (negative, spec) = match text.strip_prefix('-') {
+        Some(spec) => (true, spec),
+        None => (false, text.as_str()),
+    };
+    let digits = spec
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(spec.len());
+    let (number, unit) = spec.split_at(digits);
+    // Of the units only K and M may be lower case
+    let known = unit.is_empty() || "kKmMGTPEZYRQ".contains(&unit[..1]);
+    if number.is_empty() && (unit.is_empty() || !known) {
+        fail("du", &format!("invalid -t argument '{}'", text));
+    }
+    let size = match unit_size(unit) {
+        Some(multiplier) if known => match number {
+            "" => Some(multiplier),
+            _ => parse_size(spec, &[]),
+        }
+        .unwrap_or_else(|| fail("du", &format!("-t argument '{}' too large", text))),
+        _ => fail("du", &format!("invalid suffix in -t argument '{}'", text)),
+    };
+    let size = i64::try_from(size)
+        .unwrap_or_else(|_| fail("du", &format!("-t argument '{}' too large", text)));
+    if negative && size == 0 {
+        fail("du", &format!("invalid --threshold argument '{}'", text));
+    }
+    if negative {
+        -size
+    } else {
+        size
+    }
+}
+
+// How sizes are printed: the last of -h, --si, -k, -m, -b and -B wins, as
+// they override one another; without any, the environment has a say
+fn scale(matches: &ArgMatches) -> Scale {
+    let blocks = |size| Scale::Blocks {
+        size,
+        suffix: String::new(),
+    };
+    let chosen = [
+        ("human-readable", Scale::Human { base: 1024 }),
+        ("si", Scale::Human { base: 1000 }),
+        ("kilobytes", blocks(1024)),
+        ("megabytes", blocks(1 << 20)),
+        ("bytes", blocks(1)),
+    ]
+    .into_iter()
+    .filter(|(name, _)| matches.get_flag(name))
+    .map(|(name, scale)| (matches.index_of(name).unwrap_or(0), scale))
+    .chain(matches.get_one::<String>("block-size").map(|size| {
+        let scale = parse_block_size(size).unwrap_or_else(|err| fail("du", &err));
+        (matches.index_of("block-size").unwrap_or(0), scale)
+    }))
+    .max_by_key(|(index, _)| *index);
+    if let Some((_, scale)) = chosen {
+        return scale;
+    }
+
+    for name in ["DU_BLOCK_SIZE", "BLOCK_SIZE", "BLOCKSIZE"] {
+        if let Some(Ok(scale)) = env::var(name).ok().map(|size| parse_block_size(&size)) {
+            return scale;
+        }
+    }
+    blocks(if env::var_os("POSIXLY_CORRECT").is_some() {
+        512
+    } else {
+        1024
+    })
+}
+
+// FILE as printed and walked: with runs of trailing slashes cut to one
+fn operand_path(file: &str) -> Vec<u8> {
+    let mut path = file.as_bytes().to_vec();
+    while path.len() > 1 && path.ends_with(b"//") {
+        path.pop();
+    }
+    path
+}
@@ -0,0 +1,295 @@
+// ASD CoreUtils - du
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Reading directory trees with a pool of threads: each directory found is
+// queued and read by whichever thread is free. What is read is kept as a
+// tree in the order the entries came, so that reporting it afterwards gives
+// the same output, and counts the same hard links, as a walk by one thread.
+// Errors are kept with the directory they came from for the same reason.
+
+use crate::exclude::Exclude;
+use asd_coreutils_core::error::strerror;
+use std::ffi::CString;
+use std::ffi::OsString;
+use std::fs::{self, Metadata};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{DirEntryExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+// Large directories are read in inode order, as GNU's fts does: stat then
+// goes through the inode table in order instead of jumping about in it
+const INODE_SORT_THRESHOLD: usize = 10000;
+const TMPFS_MAGIC: i64 = 0x0102_1994;
+const NFS_MAGIC: i64 = 0x6969;
+
+// What du needs to know about a file
+#[derive(Clone, Copy)]
+pub struct Meta {
+    pub dev: u64,
+    pub ino: u64,
+    pub nlink: u64,
+    pub is_dir: bool,
+    pub apparent: u64,
+    // Bytes allocated on disk
+    pub disk: u64,
+}
+
+impl Meta {
+    pub fn new(metadata: &Metadata) -> Meta {
+        Meta {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+            nlink: metadata.nlink(),
+            is_dir: metadata.is_dir(),
+            apparent: metadata.size(),
+            disk: metadata.blocks() * 512,
+        }
+    }
+}
+
+pub struct Entry {
+    pub name: OsString,
+    pub meta: Meta,
+    // The directory's place in the tree, if it is one that was read
+    pub dir: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct Dir {
+    pub entries: Vec<Entry>,
+    // Files with one link not kept as entries, summed when they can never
+    // be told apart: apparent size and disk usage
+    pub files: (u64, u64),
+    // Messages for what could not be read
+    pub errors: Vec<String>,
+}
+
+pub struct Options {
+    pub dereference: bool,
+    // Keep every file as an entry, for -a and for hashing all files
+    pub keep_files: bool,
+    pub exclude: Exclude,
+    // Leave out what is on another file system than its operand
+    pub one_file_system: bool,
+    pub threads: usize,
+}
+
+// Directories on the way down to one, to stop following symbolic links
+// around in circles
+struct Ancestor {
+    dev: u64,
+    ino: u64,
+    parent: Option<Arc<Ancestor>>,
+}
+
+struct Job {
+    id: usize,
+    path: PathBuf,
+    // The device of the operand the directory is under
+    dev: u64,
+    ancestors: Option<Arc<Ancestor>>,
+}
+
+struct State {
+    jobs: Vec<Job>,
+    busy: usize,
+    dirs: Vec<Option<Dir>>,
+}
+
+struct Walker<'a> {
+    options: &'a Options,
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+// Read the trees under ROOTS, which are directories, the Nth becoming
+// directory N of the result
+pub fn walk(roots: &[(PathBuf, Meta)], options: &Options) -> Vec<Dir> {
+    let jobs = roots
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(id, (path, meta))| Job {
+            id,
+            path: path.clone(),
+            dev: meta.dev,
+            ancestors: options.dereference.then(|| {
+                Arc::new(Ancestor {
+                    dev: meta.dev,
+                    ino: meta.ino,
+                    parent: None,
+                })
+            }),
+        })
+        .collect();
+    let walker = Walker {
+        options,
+        state: Mutex::new(State {
+            jobs,
+            busy: 0,
+            dirs: roots.iter().map(|_| None).collect(),
+        }),
+        wake: Condvar::new(),
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..options.threads.max(1) {
+            scope.spawn(|| walker.work());
+        }
+    });
+
+    let dirs = walker.state.into_inner().unwrap().dirs;
+    dirs.into_iter().map(Option::unwrap_or_default).collect()
+}
+
+impl Walker<'_> {
+    fn work(&self) {
+        loop {
+            let job = {
+                let mut state = self.state.lock().unwrap();
+                loop {
+                    if let Some(job) = state.jobs.pop() {
+                        state.busy += 1;
+                        break job;
+                    }
+                    if state.busy == 0 {
+                        self.wake.notify_all();
+                        return;
+                    }
+                    state = self.wake.wait(state).unwrap();
+                }
+            };
+
+            let (mut dir, subdirs) = self.read(&job);
+
+            let mut state = self.state.lock().unwrap();
+            for (index, meta) in subdirs.into_iter().rev() {
+                let id = state.dirs.len();
+                state.dirs.push(None);
+                dir.entries[index].dir = Some(id);
+                state.jobs.push(Job {
+                    id,
+                    path: job.path.join(&dir.entries[index].name),
+                    dev: job.dev,
+                    ancestors: job.ancestors.as_ref().map(|parent| {
+                        Arc::new(Ancestor {
+                            dev: meta.dev,
+                            ino: meta.ino,
+                            parent: Some(parent.clone()),
+                        })
+                    }),
+                });
+            }
+            state.dirs[job.id] = Some(dir);
+            state.busy -= 1;
+            self.wake.notify_all();
+        }
+    }
+
+    // The entries of one directory, and which of them are directories to
+    // read in turn
+    fn read(&self, job: &Job) -> (Dir, Vec<(usize, Meta)>) {
+        let mut dir = Dir::default();
+        let mut subdirs = Vec::new();
+
+        let entries = match fs::read_dir(&job.path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                dir.errors.push(format!(
+                    "cannot read directory '{}': {}",
+                    job.path.display(),
+                    strerror(&err)
+                ));
+                return (dir, subdirs);
+            }
+        };
+
+        let mut listed = Vec::new();
+        for entry in entries {
+            match entry {
+                Ok(entry) => listed.push(entry),
+                Err(err) => {
+                    dir.errors.push(format!(
+                        "cannot read directory '{}': {}",
+                        job.path.display(),
+                        strerror(&err)
+                    ));
+                    break;
+                }
+            }
+        }
+        if listed.len() > INODE_SORT_THRESHOLD && inode_order_helps(&job.path) {
+            listed.sort_by_key(|entry| entry.ino());
+        }
+
+        for entry in listed {
+            let path = entry.path();
+            if self.options.exclude.matches(path.as_os_str().as_bytes()) {
+                continue;
+            }
+            let metadata = if self.options.dereference {
+                fs::metadata(&path)
+            } else {
+                entry.metadata()
+            };
+            let meta = match metadata {
+                Ok(metadata) => Meta::new(&metadata),
+                Err(err) => {
+                    dir.errors.push(format!(
+                        "cannot access '{}': {}",
+                        path.display(),
+                        strerror(&err)
+                    ));
+                    continue;
+                }
+            };
+
+            if self.options.one_file_system && meta.dev != job.dev {
+                continue;
+            }
+            if meta.is_dir {
+                if in_cycle(&job.ancestors, &meta) {
+                    continue;
+                }
+                subdirs.push((dir.entries.len(), meta));
+            } else if meta.nlink == 1 && !self.options.keep_files {
+                dir.files.0 += meta.apparent;
+                dir.files.1 += meta.disk;
+                continue;
+            }
+            dir.entries.push(Entry {
+                name: entry.file_name(),
+                meta,
+                dir: None,
+            });
+        }
+        (dir, subdirs)
+    }
+}
+
+// Whether the file system holding PATH keeps inodes where their order
+// means something, which it does not for those in memory or over NFS
+fn inode_order_helps(path: &Path) -> bool {
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return true;
+    }
+    !matches!(stat.f_type as i64, TMPFS_MAGIC | NFS_MAGIC)
+}
+
+fn in_cycle(ancestors: &Option<Arc<Ancestor>>, meta: &Meta) -> bool {
+    let mut ancestor = ancestors.as_deref();
+    while let Some(dir) = ancestor {
+        if dir.dev == meta.dev && dir.ino == meta.ino {
+            return true;
+        }
+        ancestor = dir.parent.as_deref();
+    }
+    false
+}
@@ -9,13 +9,15 @@ const std = @import("std");
 /// - -n flag to omit trailing newline
 /// - -e flag to enable interpretation of backslash escapes
 /// - -E flag to disable interpretation of backslash escapes (default)
+/// - POSIXLY_CORRECT (XPG) mode: escapes always interpreted, no options
 /// - Optimized for performance with minimal allocations
 
+/// Build as an XPG echo, as if POSIXLY_CORRECT were always set
+const default_xpg = false;
+
 const Options = struct {
     interpret_escapes: bool = false,
     newline: bool = true,
-    help: bool = false,
-    version: bool = false,
 };
 
 fn printVersion() void {
@@ -32,6 +34,8 @@ fn printHelp() void {
     std.debug.print("  -E             disable interpretation of backslash escapes (default)\n", .{});
     std.debug.print("  --help         display this help and exit\n", .{});
     std.debug.print("  --version      output version information and exit\n\n", .{});
+    std.debug.print("If POSIXLY_CORRECT is set, escapes are always interpreted and every\n", .{});
+    std.debug.print("argument, -n included, is output as a STRING.\n\n", .{});
     std.debug.print("If -e is in effect, the following sequences are recognized:\n", .{});
     std.debug.print("  \\\\      backslash\n", .{});
     std.debug.print("  \\a      alert (BEL)\n", .{});
@@ -43,6 +47,8 @@ fn printHelp() void {
     std.debug.print("  \\r      carriage return\n", .{});
     std.debug.print("  \\t      horizontal tab\n", .{});
     std.debug.print("  \\v      vertical tab\n", .{});
+    std.debug.print("  \\0NNN   byte with octal value NNN (1 to 3 digits)\n", .{});
+    std.debug.print("  \\xHH    byte with hexadecimal value HH (1 to 2 digits)\n", .{});
 }
 
 fn getEscapeSequence(escape_char: u8) ?[]const u8 {
@@ -50,7 +56,6 @@ fn getEscapeSequence(escape_char: u8) ?[]const u8 {
         '\\' => "\\",
         'a' => "\x07", // Alert (BEL)
         'b' => "\x08", // Backspace
-        'c' => "",     // Produce no further output
         'e' => "\x1B", // Escape
         'f' => "\x0C", // Form feed
         'n' => "\n",   // Newline
@@ -61,33 +66,72 @@ fn getEscapeSequence(escape_char: u8) ?[]const u8 {
     };
 }
 
-fn processEscapes(allocator: std.mem.Allocator, input: []const u8) ![]u8 {
-    var result = std.ArrayList(u8).init(allocator);
-    errdefer result.deinit();
-    
+/// Writes input with its escapes interpreted. Returns true if a \c was
+/// met, after which nothing more is output, not even the newline.
+fn writeEscaped(writer: anytype, input: []const u8) !bool {
     var i: usize = 0;
     while (i < input.len) {
-        if (input[i] == '\\' and i + 1 < input.len) {
-            const escape_char = input[i + 1];
-            if (getEscapeSequence(escape_char)) |replacement| {
-                try result.appendSlice(replacement);
-                // Special case for \c which stops all output
-                if (escape_char == 'c') {
-                    break;
-                }
-            } else {
-                // Unknown escape sequence, keep as is
-                try result.append('\\');
-                try result.append(escape_char);
-            }
-            i += 2;
-        } else {
-            try result.append(input[i]);
+        if (input[i] != '\\' or i + 1 == input.len) {
+            try writer.writeByte(input[i]);
             i += 1;
+            continue;
+        }
+
+        const escape_char = input[i + 1];
+        i += 2;
+        switch (escape_char) {
+            'c' => return true,
+            // \0NNN: up to three octal digits after the 0
+            '0' => {
+                var value: u8 = 0;
+                var digits: usize = 0;
+                while (digits < 3 and i < input.len and input[i] >= '0' and input[i] <= '7') : (digits += 1) {
+                    value = value *% 8 +% (input[i] - '0');
+                    i += 1;
+                }
+                try writer.writeByte(value);
+            },
+            // \xHH: one or two hex digits; without any it is left as is
+            'x' => {
+                var value: u8 = 0;
+                var digits: usize = 0;
+                while (digits < 2 and i < input.len) : (digits += 1) {
+                    const digit = std.fmt.charToDigit(input[i], 16) catch break;
+                    value = value * 16 + digit;
+                    i += 1;
+                }
+                if (digits == 0) {
+                    try writer.writeAll("\\x");
+                } else {
+                    try writer.writeByte(value);
+                }
+            },
+            else => {
+                if (getEscapeSequence(escape_char)) |replacement| {
+                    try writer.writeAll(replacement);
+                } else {
+                    // Unknown escape sequence, keep as is
+                    try writer.writeByte('\\');
+                    try writer.writeByte(escape_char);
+                }
+            },
         }
     }
-    
-    return result.toOwnedSlice();
+    return false;
+}
+
+/// Whether arg is a cluster of echo's own options, such as -n or -neE;
+/// anything else that starts with '-' is a string to print
+fn isOptionCluster(arg: []const u8) bool {
+    if (arg.len < 2 or arg[0] != '-') {
+        return false;
+    }
+    for (arg[1..]) |c| {
+        if (c != 'n' and c != 'e' and c != 'E') {
+            return false;
+        }
+    }
+    return true;
 }
 
 pub fn main() !void {
@@ -95,44 +139,42 @@ pub fn main() !void {
     defer _ = gpa.deinit();
     const allocator = gpa.allocator();
     
-    const stdout = std.io.getStdOut().writer();
+    // Everything goes out in one write, flushed on the way out
+    var buffered = std.io.bufferedWriter(std.io.getStdOut().writer());
+    const stdout = buffered.writer();
     
     const args = try std.process.argsAlloc(allocator);
     defer std.process.argsFree(allocator, args);
     
-    var options = Options{};
+    // POSIX echo takes no options and always interprets escapes
+    const xpg = default_xpg or std.process.hasEnvVarConstant("POSIXLY_CORRECT");
+    var options = Options{ .interpret_escapes = xpg };
     var arg_index: usize = 1;
-    
-    // Parse options
-    while (arg_index < args.len) : (arg_index += 1) {
-        const arg = args[arg_index];
-        if (!std.mem.startsWith(u8, arg, "-")) {
-            break;
-        } else if (std.mem.eql(u8, arg, "-n")) {
-            options.newline = false;
-        } else if (std.mem.eql(u8, arg, "-e")) {
-            options.interpret_escapes = true;
-        } else if (std.mem.eql(u8, arg, "-E")) {
-            options.interpret_escapes = false;
-        } else if (std.mem.eql(u8, arg, "--help")) {
-            options.help = true;
-        } else if (std.mem.eql(u8, arg, "--version")) {
-            options.version = true;
-        } else {
-            break;
+
+    if (!xpg) {
+        // --help and --version only count on their own
+        if (args.len == 2 and std.mem.eql(u8, args[1], "--help")) {
+            printHelp();
+            return;
+        }
+        if (args.len == 2 and std.mem.eql(u8, args[1], "--version")) {
+            printVersion();
+            return;
+        }
+
+        // Parse options
+        while (arg_index < args.len and isOptionCluster(args[arg_index])) : (arg_index += 1) {
+            for (args[arg_index][1..]) |c| {
+                switch (c) {
+                    'n' => options.newline = false,
+                    'e' => options.interpret_escapes = true,
+                    'E' => options.interpret_escapes = false,
+                    else => unreachable,
+                }
+            }
         }
     }
-    
-    if (options.help) {
-        printHelp();
-        return;
-    }
-    
-    if (options.version) {
-        printVersion();
-        return;
-    }
-    
+
     // Print arguments
     var first = true;
     while (arg_index < args.len) : (arg_index += 1) {
@@ -140,17 +182,19 @@ pub fn main() !void {
             try stdout.writeByte(' ');
         }
         first = false;
-        
+
         if (options.interpret_escapes) {
-            const processed = try processEscapes(allocator, args[arg_index]);
-            defer allocator.free(processed);
-            try stdout.writeAll(processed);
+            if (try writeEscaped(stdout, args[arg_index])) {
+                try buffered.flush();
+                return;
+            }
         } else {
             try stdout.writeAll(args[arg_index]);
         }
     }
-    
+
     if (options.newline) {
         try stdout.writeByte('\n');
     }
+    try buffered.flush();
 }
\ No newline at end of file
@@ -0,0 +1,269 @@
+// ASD CoreUtils - env
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod split;
+
+use asd_coreutils_core::error::{fail_status, strerror, usage_error_status};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::process;
+
+const EXIT_FAILURE: i32 = 125;
+const EXIT_CANNOT_INVOKE: i32 = 126;
+const EXIT_ENOENT: i32 = 127;
+
+fn main() {
+    let args = expand_split_strings(env::args_os().collect());
+
+    let matches = Command::new("env")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils env - run a program in a modified environment")
+        .after_help(
+            "Set each NAME to VALUE in the environment and run COMMAND. A mere - implies -i.\n\
+             If no COMMAND, print the resulting environment.\n\n\
+             The STRING of -S is split into arguments at blanks, with '...' and \"...\"\n\
+             quoting, backslash escapes, ${NAME} expansion and '#' comments, so that a\n\
+             script can start with '#!/usr/bin/env -S COMMAND ARG...'.",
+        )
+        .trailing_var_arg(true)
+        .arg(
+            Arg::new("ignore-environment")
+                .short('i')
+                .long("ignore-environment")
+                .action(ArgAction::SetTrue)
+                .help("Start with an empty environment"),
+        )
+        .arg(
+            Arg::new("null")
+                .short('0')
+                .long("null")
+                .action(ArgAction::SetTrue)
+                .help("End each output line with NUL, not newline"),
+        )
+        .arg(
+            Arg::new("unset")
+                .short('u')
+                .long("unset")
+                .value_name("NAME")
+                .action(ArgAction::Append)
+                .value_parser(clap::value_parser!(OsString))
+                .help("Remove variable from the environment"),
+        )
+        .arg(
+            Arg::new("chdir")
+                .short('C')
+                .long("chdir")
+                .value_name("DIR")
+                .value_parser(clap::value_parser!(OsString))
+                .help("Change working directory to DIR"),
+        )
+        .arg(
+            Arg::new("split-string")
+                .short('S')
+                .long("split-string")
+                .value_name("STRING")
+                .help("Process and split STRING into separate arguments"),
+        )
+        .arg(
+            Arg::new("operands")
+                .value_name("NAME=VALUE]... [COMMAND [ARG]...")
+                .num_args(0..)
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|err| {
+            let code = if err.use_stderr() { EXIT_FAILURE } else { 0 };
+            let _ = err.print();
+            process::exit(code);
+        });
+
+    let mut operands: Vec<OsString> = matches
+        .get_many::<OsString>("operands")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    let mut ignore_environment = matches.get_flag("ignore-environment");
+    if operands.first().is_some_and(|first| first == "-") {
+        ignore_environment = true;
+        operands.remove(0);
+    }
+
+    if ignore_environment {
+        for (name, _) in env::vars_os() {
+            env::remove_var(name);
+        }
+    }
+
+    for name in matches.get_many::<OsString>("unset").into_iter().flatten() {
+        if name.is_empty() || name.as_bytes().contains(&b'=') {
+            fail_status(
+                "env",
+                EXIT_FAILURE,
+                &format!(
+                    "cannot unset '{}': Invalid argument",
+                    name.to_string_lossy()
+                ),
+            );
+        }
+        env::remove_var(name);
+    }
+
+    let mut operands = operands.into_iter().peekable();
+    while let Some(assignment) = operands.next_if(|operand| operand.as_bytes().contains(&b'=')) {
+        let bytes = assignment.as_bytes();
+        let split = bytes.iter().position(|&c| c == b'=').unwrap();
+        if split == 0 {
+            fail_status(
+                "env",
+                EXIT_FAILURE,
+                &format!(
+                    "cannot set '{}': Invalid argument",
+                    assignment.to_string_lossy()
+                ),
+            );
+        }
+        env::set_var(
+            OsStr::from_bytes(&bytes[..split]),
+            OsStr::from_bytes(&bytes[split + 1..]),
+        );
+    }
+
+    let null = matches.get_flag("null");
+    let chdir = matches.get_one::<OsString>("chdir");
+    let Some(program) = operands.next() else {
+        if chdir.is_some() {
+            usage_error_status(
+                "env",
+                EXIT_FAILURE,
+                "must specify command with --chdir (-C)",
+            );
+        }
+        print_environment(null);
+        process::exit(0);
+    };
+    if null {
+        usage_error_status(
+            "env",
+            EXIT_FAILURE,
+            "cannot specify --null (-0) with command",
+        );
+    }
+
+    if let Some(dir) = chdir {
+        if let Err(err) = env::set_current_dir(dir) {
+            fail_status(
+                "env",
+                EXIT_FAILURE,
+                &format!(
+                    "cannot change directory to '{}': {}",
+                    dir.to_string_lossy(),
+                    strerror(&err)
+                ),
+            );
+        }
+    }
+
+    // exec only comes back on failure
+    let err = process::Command::new(&program).args(operands).exec();
+    eprintln!("env: '{}': {}", program.to_string_lossy(), strerror(&err));
+    process::exit(if err.kind() == io::ErrorKind::NotFound {
+        EXIT_ENOENT
+    } else {
+        EXIT_CANNOT_INVOKE
+    });
+}
+
+fn print_environment(null: bool) {
+    let terminator = if null { b'\0' } else { b'\n' };
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    let result = env::vars_os().try_for_each(|(name, value)| {
+        out.write_all(name.as_bytes())?;
+        out.write_all(b"=")?;
+        out.write_all(value.as_bytes())?;
+        out.write_all(&[terminator])
+    });
+    if let Err(err) = result.and_then(|()| out.flush()) {
+        fail_status(
+            "env",
+            EXIT_FAILURE,
+            &format!("write error: {}", strerror(&err)),
+        );
+    }
+}
+
+// Replaces each -S STRING among the options with the words of STRING, which
+// may hold options, assignments and the command in turn. Options stop at
+// the first operand, as they do for clap here, so nothing after it is
+// touched.
+fn expand_split_strings(mut args: Vec<OsString>) -> Vec<OsString> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_bytes().to_vec();
+        if arg == b"--" || arg == b"-" || !arg.starts_with(b"-") {
+            break;
+        }
+
+        // Where the STRING is: after "=", in the rest of a cluster such as
+        // "-iSperl -w", or in the next argument
+        let mut split_at = None;
+        if let Some(long) = arg.strip_prefix(b"--") {
+            match long.iter().position(|&c| c == b'=') {
+                Some(eq) if &long[..eq] == b"split-string" => split_at = Some((0, Some(eq + 3))),
+                Some(_) => {}
+                None if long == b"split-string" => split_at = Some((0, None)),
+                // The value of these is the next argument, and may look
+                // like an option
+                None if long == b"unset" || long == b"chdir" => i += 1,
+                None => {}
+            }
+        } else {
+            for (j, &c) in arg.iter().enumerate().skip(1) {
+                match c {
+                    b'S' => {
+                        split_at = Some((j, (j + 1 < arg.len()).then_some(j + 1)));
+                        break;
+                    }
+                    b'u' | b'C' => {
+                        if j + 1 == arg.len() {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let Some((option, value_at)) = split_at else {
+            i += 1;
+            continue;
+        };
+        let (text, used) = match value_at {
+            Some(start) => (arg[start..].to_vec(), 1),
+            // Missing: clap reports that
+            None if i + 1 == args.len() => break,
+            None => (args[i + 1].as_bytes().to_vec(), 2),
+        };
+        let words = split::split(&text)
+            .unwrap_or_else(|message| fail_status("env", EXIT_FAILURE, &message));
+
+        // Options clustered before the S stay where they were
+        let mut replacement = Vec::new();
+        if option > 1 {
+            replacement.push(OsStr::from_bytes(&arg[..option]).to_os_string());
+            i += 1;
+        }
+        replacement.extend(words);
+        let start = i - usize::from(option > 1);
+        args.splice(start..start + used, replacement);
+    }
+    args
+}
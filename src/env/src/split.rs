@@ -0,0 +1,139 @@
+// ASD CoreUtils - env
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use std::env;
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Quote {
+    None,
+    Single,
+    Double,
+}
+
+// Splits the argument of -S into words the way a shell would, roughly:
+// blanks separate words, '...' and "..." quote them, backslash escapes and
+// ${NAME} expands inside double quotes and outside quotes, and '#' at the
+// start of a word comments out the rest. This is what lets a script start
+// with "#!/usr/bin/env -S interpreter -flags", where the kernel passes all
+// of it as one argument.
+pub fn split(text: &[u8]) -> Result<Vec<OsString>, String> {
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+    // Quotes start a word even when nothing ends up in it, as in ""
+    let mut in_word = false;
+    let mut quote = Quote::None;
+    let mut i = 0;
+
+    while i < text.len() {
+        let c = text[i];
+        i += 1;
+        match (quote, c) {
+            (Quote::Single, b'\'') | (Quote::Double, b'"') => quote = Quote::None,
+            (Quote::None, b'\'') => {
+                quote = Quote::Single;
+                in_word = true;
+            }
+            (Quote::None, b'"') => {
+                quote = Quote::Double;
+                in_word = true;
+            }
+            (Quote::None, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r') => {
+                if in_word {
+                    words.push(OsString::from_vec(std::mem::take(&mut word)));
+                    in_word = false;
+                }
+            }
+            (Quote::None, b'#') if !in_word => break,
+            (Quote::Single, b'\\') => {
+                // Only \\ and \' mean anything between single quotes
+                match text.get(i) {
+                    Some(&next @ (b'\\' | b'\'')) => {
+                        word.push(next);
+                        i += 1;
+                    }
+                    _ => word.push(c),
+                }
+            }
+            (_, b'\\') => {
+                let Some(&next) = text.get(i) else {
+                    return Err("invalid backslash at end of string in -S".to_string());
+                };
+                i += 1;
+                match next {
+                    b'_' if quote == Quote::Double => word.push(b' '),
+                    b'_' => {
+                        if in_word {
+                            words.push(OsString::from_vec(std::mem::take(&mut word)));
+                            in_word = false;
+                        }
+                        continue;
+                    }
+                    b'c' if quote == Quote::Double => {
+                        return Err("'\\c' must not appear in double-quoted -S string".to_string());
+                    }
+                    b'c' => {
+                        quote = Quote::None;
+                        break;
+                    }
+                    b'"' | b'#' | b'$' | b'\'' | b'\\' => word.push(next),
+                    b'f' => word.push(b'\x0c'),
+                    b'n' => word.push(b'\n'),
+                    b'r' => word.push(b'\r'),
+                    b't' => word.push(b'\t'),
+                    b'v' => word.push(b'\x0b'),
+                    _ => {
+                        return Err(format!(
+                            "invalid sequence '\\{}' in -S",
+                            String::from_utf8_lossy(&[next])
+                        ));
+                    }
+                }
+                in_word = true;
+            }
+            (Quote::None | Quote::Double, b'$') => {
+                let start = i - 1;
+                let name = variable_name(&text[i..]).ok_or_else(|| {
+                    format!(
+                        "only ${{VARNAME}} expansion is supported, error at: {}",
+                        String::from_utf8_lossy(&text[start..])
+                    )
+                })?;
+                i += name.len() + 2;
+                // An unset or empty variable outside quotes makes no word
+                if let Some(value) = env::var_os(std::ffi::OsStr::from_bytes(name)) {
+                    word.extend_from_slice(value.as_bytes());
+                }
+                if !word.is_empty() {
+                    in_word = true;
+                }
+            }
+            _ => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if quote != Quote::None {
+        return Err("no terminating quote in -S string".to_string());
+    }
+    if in_word {
+        words.push(OsString::from_vec(word));
+    }
+    Ok(words)
+}
+
+// The NAME of "{NAME}...", which must be a valid variable name
+fn variable_name(text: &[u8]) -> Option<&[u8]> {
+    let rest = text.strip_prefix(b"{")?;
+    let end = rest.iter().position(|&c| c == b'}')?;
+    let name = &rest[..end];
+    let valid = name
+        .first()
+        .is_some_and(|c| c.is_ascii_alphabetic() || *c == b'_')
+        && name.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_');
+    valid.then_some(name)
+}
@@ -0,0 +1,99 @@
+// ASD CoreUtils - unexpand
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use clap::{Arg, ArgAction, Command};
+use expand::TabStops;
+use std::io::{self, Write};
+
+fn main() {
+    let matches = Command::new("unexpand")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils unexpand - convert spaces to tabs")
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .help("Convert all blanks, instead of just initial blanks")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("first-only")
+                .long("first-only")
+                .help("Convert only leading sequences of blanks (overrides -a)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(expand::tabs_arg())
+        .arg(expand::file_arg())
+        .get_matches();
+
+    let stops = expand::tab_stops("unexpand", &matches);
+
+    // An explicit tab list implies -a unless --first-only says otherwise
+    let all = !matches.get_flag("first-only") && (matches.get_flag("all") || matches.contains_id("tabs"));
+
+    expand::run("unexpand", &matches, |line, out| unexpand_line(line, &stops, all, out));
+}
+
+fn unexpand_line(line: &[u8], stops: &TabStops, all: bool, out: &mut dyn Write) -> io::Result<()> {
+    let mut column: usize = 0;
+    // Blanks seen since the last tab stop or non-blank, not yet written
+    let mut pending: Vec<u8> = Vec::new();
+    // A single space that landed on a stop only becomes a tab when it is
+    // part of a longer run of blanks
+    let mut lone_space_at_stop = false;
+
+    for (index, &byte) in line.iter().enumerate() {
+        let blank = is_blank(byte);
+        let after_blank = index > 0 && is_blank(line[index - 1]);
+        let next = stops.next_stop(column);
+
+        // Past the last stop nothing more is converted
+        if lone_space_at_stop {
+            out.write_all(if blank && next.is_some() { b"\t" } else { b" " })?;
+            lone_space_at_stop = false;
+        }
+
+        if !blank {
+            out.write_all(&pending)?;
+            pending.clear();
+
+            if !all {
+                return out.write_all(&line[index..]);
+            }
+
+            out.write_all(&[byte])?;
+            if byte == b'\x08' {
+                column = column.saturating_sub(1);
+            } else if expand::advances(byte) {
+                column += 1;
+            }
+            continue;
+        }
+
+        column = match (byte, next) {
+            (b'\t', Some(stop)) => stop,
+            _ => column + 1,
+        };
+        pending.push(byte);
+
+        if next == Some(column) {
+            if pending == b" " && !after_blank {
+                lone_space_at_stop = true;
+            } else {
+                out.write_all(b"\t")?;
+            }
+            pending.clear();
+        }
+    }
+
+    if lone_space_at_stop {
+        out.write_all(b" ")?;
+    }
+    out.write_all(&pending)
+}
+
+fn is_blank(byte: u8) -> bool {
+    byte == b' ' || byte == b'\t'
+}
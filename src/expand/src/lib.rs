@@ -0,0 +1,191 @@
+// ASD CoreUtils - expand/unexpand shared tab stop handling
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgMatches};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+const DEFAULT_TAB_SIZE: usize = 8;
+
+// What happens past the last explicitly listed stop
+#[derive(Clone, Copy)]
+enum Tail {
+    // No further stops
+    None,
+    // Stops at every multiple of N ("/N", or a single size)
+    Every(usize),
+    // Stops every N columns after the last listed one ("+N")
+    After(usize),
+}
+
+pub struct TabStops {
+    stops: Vec<usize>,
+    tail: Tail,
+}
+
+impl Default for TabStops {
+    fn default() -> Self {
+        TabStops {
+            stops: Vec::new(),
+            tail: Tail::Every(DEFAULT_TAB_SIZE),
+        }
+    }
+}
+
+impl TabStops {
+    // Accepts a single size or a comma/blank separated list of columns,
+    // the last of which may carry a '/' or '+' repeat specifier
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let items: Vec<&str> = spec
+            .split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|item| !item.is_empty())
+            .collect();
+
+        let mut stops: Vec<usize> = Vec::new();
+        let mut tail = Tail::None;
+
+        for (index, item) in items.iter().enumerate() {
+            let last = index + 1 == items.len();
+            let (specifier, digits) = match item.as_bytes()[0] {
+                b'/' | b'+' => (Some(item.as_bytes()[0]), &item[1..]),
+                _ => (None, *item),
+            };
+
+            if let Some(specifier) = specifier {
+                if !last {
+                    return Err(format!("'{}' specifier only allowed with the last value", specifier as char));
+                }
+            }
+
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(format!("tab size contains invalid character(s): '{}'", item));
+            }
+            let value: usize = digits
+                .parse()
+                .map_err(|_| format!("tab stop is too large '{}'", digits))?;
+            if value == 0 {
+                return Err("tab size cannot be 0".to_string());
+            }
+
+            match specifier {
+                Some(b'/') => tail = Tail::Every(value),
+                Some(_) => tail = Tail::After(value),
+                None => {
+                    if stops.last().is_some_and(|&previous| value <= previous) {
+                        return Err("tab sizes must be ascending".to_string());
+                    }
+                    stops.push(value);
+                }
+            }
+        }
+
+        // A lone number is a tab size rather than a single stop
+        if stops.len() == 1 && matches!(tail, Tail::None) {
+            tail = Tail::Every(stops.pop().unwrap());
+        }
+
+        if stops.is_empty() && matches!(tail, Tail::None) {
+            return Ok(TabStops::default());
+        }
+
+        Ok(TabStops { stops, tail })
+    }
+
+    // The first stop strictly after COLUMN, or None once past the last one
+    pub fn next_stop(&self, column: usize) -> Option<usize> {
+        if let Some(&stop) = self.stops.iter().find(|&&stop| stop > column) {
+            return Some(stop);
+        }
+
+        match self.tail {
+            Tail::None => None,
+            Tail::Every(size) => Some((column / size + 1) * size),
+            Tail::After(size) => {
+                let base = self.stops.last().copied().unwrap_or(0);
+                Some(base + ((column - base) / size + 1) * size)
+            }
+        }
+    }
+}
+
+// Bytes that move the cursor one column; UTF-8 continuation bytes belong
+// to the character before them
+pub fn advances(byte: u8) -> bool {
+    !(0x80..0xc0).contains(&byte)
+}
+
+pub fn tabs_arg() -> Arg {
+    Arg::new("tabs")
+        .short('t')
+        .long("tabs")
+        .value_name("LIST")
+        .help("Use comma separated list of tab positions, or a single tab size N")
+}
+
+pub fn tab_stops(name: &str, matches: &ArgMatches) -> TabStops {
+    match matches.get_one::<String>("tabs") {
+        Some(spec) => TabStops::parse(spec).unwrap_or_else(|err| fail(name, &err)),
+        None => TabStops::default(),
+    }
+}
+
+pub fn file_arg() -> Arg {
+    Arg::new("file")
+        .value_name("FILE")
+        .help("Files to convert, or - for standard input")
+        .num_args(0..)
+}
+
+// Feeds every line of every FILE operand through CONVERT and exits
+pub fn run<F>(name: &str, matches: &ArgMatches, mut convert: F)
+where
+    F: FnMut(&[u8], &mut dyn Write) -> io::Result<()>,
+{
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files {
+        let result = if file == "-" {
+            convert_lines(&mut io::stdin().lock(), &mut out, &mut convert)
+        } else {
+            File::open(file).and_then(|f| convert_lines(&mut BufReader::new(f), &mut out, &mut convert))
+        };
+
+        if let Err(err) = result {
+            let _ = out.flush();
+            eprintln!("{}: {}: {}", name, file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("{}: {}", name, strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn convert_lines<F>(input: &mut impl BufRead, out: &mut dyn Write, convert: &mut F) -> io::Result<()>
+where
+    F: FnMut(&[u8], &mut dyn Write) -> io::Result<()>,
+{
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+        convert(&line, out)?;
+    }
+}
@@ -0,0 +1,61 @@
+// ASD CoreUtils - expand
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use clap::{Arg, ArgAction, Command};
+use expand::TabStops;
+use std::io::{self, Write};
+
+fn main() {
+    let matches = Command::new("expand")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils expand - convert tabs to spaces")
+        .arg(
+            Arg::new("initial")
+                .short('i')
+                .long("initial")
+                .help("Do not convert tabs after non blanks")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(expand::tabs_arg())
+        .arg(expand::file_arg())
+        .get_matches();
+
+    let stops = expand::tab_stops("expand", &matches);
+    let initial = matches.get_flag("initial");
+
+    expand::run("expand", &matches, |line, out| expand_line(line, &stops, initial, out));
+}
+
+fn expand_line(line: &[u8], stops: &TabStops, initial: bool, out: &mut dyn Write) -> io::Result<()> {
+    let mut column = 0;
+
+    for (index, &byte) in line.iter().enumerate() {
+        match byte {
+            b'\t' => {
+                // Past the last listed stop a tab becomes a single space
+                let stop = stops.next_stop(column).unwrap_or(column + 1);
+                out.write_all(&b" ".repeat(stop - column))?;
+                column = stop;
+            }
+            b' ' => {
+                out.write_all(b" ")?;
+                column += 1;
+            }
+            _ if initial => return out.write_all(&line[index..]),
+            b'\x08' => {
+                out.write_all(&[byte])?;
+                column = column.saturating_sub(1);
+            }
+            _ => {
+                out.write_all(&[byte])?;
+                if expand::advances(byte) {
+                    column += 1;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// ASD CoreUtils - factor
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::strerror;
+use clap::{Arg, Command};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+// Enough to fully factor any number below 1000^2 by trial division, and
+// to strip small factors quickly before Pollard's rho takes over
+const SMALL_PRIMES: &[u64] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101,
+    103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193, 197, 199,
+];
+
+// Witnesses that make Miller-Rabin deterministic for every 64-bit number
+const WITNESSES: &[u64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+fn main() {
+    let matches = Command::new("factor")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils factor - print the prime factors of each specified integer NUMBER")
+        .arg(
+            Arg::new("number")
+                .value_name("NUMBER")
+                .help("Numbers to factor; read from standard input if none are given")
+                .allow_hyphen_values(true)
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    let mut handle = |token: &str, out: &mut BufWriter<io::StdoutLock>| -> io::Result<()> {
+        match parse_number(token) {
+            Ok(number) => write_factors(out, number),
+            Err(err) => {
+                out.flush()?;
+                eprintln!("factor: {}", err);
+                exit_code = 1;
+                Ok(())
+            }
+        }
+    };
+
+    let result = match matches.get_many::<String>("number") {
+        Some(numbers) => numbers.into_iter().try_for_each(|number| handle(number, &mut out)),
+        None => io::stdin().lock().lines().try_for_each(|line| {
+            line?.split_whitespace().try_for_each(|token| handle(token, &mut out))
+        }),
+    };
+
+    if let Err(err) = result.and_then(|()| out.flush()) {
+        eprintln!("factor: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn parse_number(token: &str) -> Result<u64, String> {
+    let digits = token.strip_prefix('+').unwrap_or(token);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("'{}' is not a valid positive integer", token));
+    }
+    digits.parse().map_err(|_| format!("'{}' is too large", token))
+}
+
+fn write_factors(out: &mut impl Write, number: u64) -> io::Result<()> {
+    let mut factors = factorize(number);
+    factors.sort_unstable();
+
+    write!(out, "{}:", number)?;
+    for factor in factors {
+        write!(out, " {}", factor)?;
+    }
+    writeln!(out)
+}
+
+fn factorize(mut number: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    if number == 0 {
+        return factors;
+    }
+
+    for &prime in SMALL_PRIMES {
+        while number.is_multiple_of(prime) {
+            factors.push(prime);
+            number /= prime;
+        }
+    }
+
+    let mut pending = vec![number];
+    while let Some(n) = pending.pop() {
+        if n == 1 {
+            continue;
+        }
+        if is_prime(n) {
+            factors.push(n);
+            continue;
+        }
+        let divisor = pollard_rho(n);
+        pending.push(divisor);
+        pending.push(n / divisor);
+    }
+
+    factors
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if let Some(&prime) = WITNESSES.iter().find(|&&prime| n.is_multiple_of(prime)) {
+        return n == prime;
+    }
+
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+
+    WITNESSES.iter().all(|&witness| {
+        let mut x = pow_mod(witness, odd, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..shift {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+// Brent's variant of Pollard's rho, batching gcd computations; N must be
+// odd and composite
+fn pollard_rho(n: u64) -> u64 {
+    const BATCH: u64 = 128;
+
+    for increment in 1.. {
+        let step = |x: u64| (mul_mod(x, x, n) + increment) % n;
+        let mut y = 2;
+        let mut x = y;
+        let mut saved = y;
+        let mut product = 1;
+        let mut divisor = 1;
+        let mut cycle = 1;
+
+        while divisor == 1 {
+            x = y;
+            for _ in 0..cycle {
+                y = step(y);
+            }
+
+            let mut done = 0;
+            while done < cycle && divisor == 1 {
+                saved = y;
+                for _ in 0..BATCH.min(cycle - done) {
+                    y = step(y);
+                    product = mul_mod(product, x.abs_diff(y), n);
+                }
+                divisor = gcd(product, n);
+                done += BATCH;
+            }
+            cycle *= 2;
+        }
+
+        // The batch overshot; retrace it one step at a time
+        if divisor == n {
+            loop {
+                saved = step(saved);
+                divisor = gcd(x.abs_diff(saved), n);
+                if divisor != 1 {
+                    break;
+                }
+            }
+        }
+
+        if divisor != n {
+            return divisor;
+        }
+    }
+
+    unreachable!("every composite number has a nontrivial divisor")
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
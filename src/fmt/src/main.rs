@@ -0,0 +1,353 @@
+// ASD CoreUtils - fmt
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+const DEFAULT_WIDTH: usize = 75;
+const TAB_WIDTH: usize = 8;
+
+struct Word {
+    text: String,
+    // Spaces to put after the word when it isn't last on a line: as many as
+    // followed it in the input, or with -u two after a sentence that was
+    // followed by two spaces and one otherwise; a line end counts as two
+    // after a sentence and one elsewhere
+    space: usize,
+}
+
+struct Paragraph {
+    indent: String,
+    // With -c, the indentation of the second line, kept for all the rest
+    other_indent: Option<String>,
+    words: Vec<Word>,
+}
+
+struct Options {
+    width: usize,
+    goal: usize,
+    crown: bool,
+    split_only: bool,
+    uniform: bool,
+    // With leading and trailing blanks taken off
+    prefix: Option<String>,
+}
+
+fn main() {
+    let matches = Command::new("fmt")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils fmt - simple optimal text formatter")
+        .arg(
+            Arg::new("crown-margin")
+                .short('c')
+                .long("crown-margin")
+                .help("Preserve the indentation of the first two lines")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("prefix")
+                .short('p')
+                .long("prefix")
+                .value_name("STRING")
+                .allow_hyphen_values(true)
+                .help("Reformat only lines beginning with STRING, reattaching the prefix to reformatted lines"),
+        )
+        .arg(
+            Arg::new("split-only")
+                .short('s')
+                .long("split-only")
+                .help("Split long lines, but do not refill")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("uniform-spacing")
+                .short('u')
+                .long("uniform-spacing")
+                .help("One space between words, two after sentences")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("width")
+                .short('w')
+                .long("width")
+                .value_name("WIDTH")
+                .help("Maximum line width (default of 75 columns)"),
+        )
+        .arg(
+            Arg::new("goal")
+                .short('g')
+                .long("goal")
+                .value_name("WIDTH")
+                .help("Goal width (default of 93% of width)"),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to format, or - for standard input")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let width = matches.get_one::<String>("width").map(|value| match value.parse::<usize>() {
+        Ok(width) if width > 0 => width,
+        _ => invalid_width(value, ""),
+    });
+    let goal = matches.get_one::<String>("goal").map(|value| match value.parse::<usize>() {
+        Ok(goal) => (goal, value),
+        _ => invalid_width(value, ""),
+    });
+
+    // Like GNU fmt, aim a little short of the maximum so lines even out;
+    // given only a goal, allow lines a little longer than it
+    let (width, goal) = match (width, goal) {
+        (Some(width), Some((goal, value))) if goal > width => {
+            invalid_width(value, ": Numerical result out of range")
+        }
+        (Some(width), Some((goal, _))) => (width, goal),
+        (None, Some((goal, _))) => (goal + 10, goal),
+        (width, None) => {
+            let width = width.unwrap_or(DEFAULT_WIDTH);
+            (width, width * 93 / 100)
+        }
+    };
+    let options = Options {
+        width,
+        goal,
+        crown: matches.get_flag("crown-margin"),
+        split_only: matches.get_flag("split-only"),
+        uniform: matches.get_flag("uniform-spacing"),
+        prefix: matches
+            .get_one::<String>("prefix")
+            .map(|prefix| prefix.trim().to_string()),
+    };
+
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files {
+        let result = if file == "-" {
+            format_input(&mut io::stdin().lock(), &mut out, &options)
+        } else {
+            File::open(file).and_then(|f| format_input(&mut BufReader::new(f), &mut out, &options))
+        };
+
+        if let Err(err) = result {
+            eprintln!("fmt: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("fmt: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn invalid_width(value: &str, reason: &str) -> ! {
+    fail("fmt", &format!("invalid width: '{}'{}", value, reason));
+}
+
+fn format_input(input: &mut impl BufRead, out: &mut impl Write, options: &Options) -> io::Result<()> {
+    let mut paragraph: Option<Paragraph> = None;
+    let mut raw = Vec::new();
+
+    loop {
+        raw.clear();
+        if input.read_until(b'\n', &mut raw)? == 0 {
+            break;
+        }
+
+        let line = String::from_utf8_lossy(&raw);
+        let line = line.trim_end_matches(['\n', '\r']);
+
+        // With -p, the prefix is part of the indentation of the lines that
+        // have it, and lines without it are left alone
+        let body = match &options.prefix {
+            Some(prefix) => {
+                let lead = line.len() - line.trim_start().len();
+                line[lead..].starts_with(prefix.as_str()).then(|| lead + prefix.len())
+            }
+            None => Some(0),
+        };
+
+        // Blank lines end a paragraph and are kept as they are, as are
+        // lines without the prefix
+        let Some(body) = body.filter(|&start| !line[start..].trim().is_empty()) else {
+            if let Some(done) = paragraph.take() {
+                write_paragraph(&done, out, options)?;
+            }
+            out.write_all(line.trim_end().as_bytes())?;
+            out.write_all(b"\n")?;
+            continue;
+        };
+
+        let start = line.len() - line[body..].trim_start().len();
+        let indent = &line[..start];
+
+        // Lines with a different indentation are never joined, except that
+        // with -c the second line may set one for the rest; with -s no
+        // lines are joined at all
+        if let Some(current) = paragraph.as_mut() {
+            let joins = if options.split_only {
+                false
+            } else if !options.crown {
+                current.indent == indent
+            } else {
+                match &current.other_indent {
+                    Some(other) => other == indent,
+                    None => {
+                        current.other_indent = Some(indent.to_string());
+                        true
+                    }
+                }
+            };
+            if !joins {
+                write_paragraph(&paragraph.take().unwrap(), out, options)?;
+            }
+        }
+
+        let current = paragraph.get_or_insert_with(|| Paragraph {
+            indent: indent.to_string(),
+            other_indent: None,
+            words: Vec::new(),
+        });
+        add_words(&mut current.words, line, start, options.uniform);
+    }
+
+    if let Some(done) = paragraph {
+        write_paragraph(&done, out, options)?;
+    }
+    Ok(())
+}
+
+// The words of LINE from byte START on
+fn add_words(words: &mut Vec<Word>, line: &str, start: usize, uniform: bool) {
+    let mut rest = &line[start..];
+
+    while !rest.is_empty() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let text = &rest[..end];
+        let after = &rest[end..];
+        let gap = after.len() - after.trim_start().len();
+
+        let sentence = ends_sentence(text);
+        let space = if after.trim_start().is_empty() {
+            if sentence {
+                2
+            } else {
+                1
+            }
+        } else if uniform {
+            if sentence && gap >= 2 {
+                2
+            } else {
+                1
+            }
+        } else {
+            // Measured in columns, for gaps with tabs in them
+            let word_end = line.len() - after.len();
+            display_width(&line[..word_end + gap]) - display_width(&line[..word_end])
+        };
+
+        words.push(Word {
+            text: text.to_string(),
+            space,
+        });
+        rest = after.trim_start();
+    }
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end_matches(['"', '\'', ')', ']'])
+        .ends_with(['.', '?', '!'])
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().fold(0, |col, c| {
+        if c == '\t' {
+            col + TAB_WIDTH - col % TAB_WIDTH
+        } else {
+            col + 1
+        }
+    })
+}
+
+fn write_paragraph(paragraph: &Paragraph, out: &mut impl Write, options: &Options) -> io::Result<()> {
+    let other_indent = paragraph.other_indent.as_ref().unwrap_or(&paragraph.indent);
+    let indent_widths = (display_width(&paragraph.indent), display_width(other_indent));
+
+    for line in break_lines(&paragraph.words, indent_widths, options) {
+        let indent = if line.start == 0 { &paragraph.indent } else { other_indent };
+        out.write_all(indent.as_bytes())?;
+
+        for (index, word) in paragraph.words[line.clone()].iter().enumerate() {
+            out.write_all(word.text.as_bytes())?;
+            if line.start + index + 1 < line.end {
+                out.write_all(" ".repeat(word.space).as_bytes())?;
+            }
+        }
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+// Chooses line breaks that minimise the squared distance of every line but
+// the last from the goal width, never exceeding the maximum width unless a
+// single word is wider than that on its own. INDENT_WIDTHS are those of
+// the first line and of the rest
+fn break_lines(words: &[Word], indent_widths: (usize, usize), options: &Options) -> Vec<std::ops::Range<usize>> {
+    let count = words.len();
+    let mut best_cost = vec![u64::MAX; count + 1];
+    let mut best_break = vec![count; count + 1];
+    best_cost[count] = 0;
+
+    for start in (0..count).rev() {
+        let mut length = if start == 0 { indent_widths.0 } else { indent_widths.1 };
+
+        for end in start + 1..=count {
+            length += words[end - 1].text.chars().count();
+            if end - 1 > start {
+                length += words[end - 2].space;
+            }
+
+            if length > options.width && end - 1 > start {
+                break;
+            }
+
+            let line_cost = if end == count {
+                0
+            } else {
+                let gap = options.goal.abs_diff(length) as u64;
+                gap * gap
+            };
+
+            let total = line_cost.saturating_add(best_cost[end]);
+            if total < best_cost[start] {
+                best_cost[start] = total;
+                best_break[start] = end;
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < count {
+        let end = best_break[start];
+        lines.push(start..end);
+        start = end;
+    }
+    lines
+}
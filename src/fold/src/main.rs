@@ -0,0 +1,191 @@
+// ASD CoreUtils - fold
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+const DEFAULT_WIDTH: usize = 80;
+const TAB_WIDTH: usize = 8;
+
+struct Options {
+    width: usize,
+    count_bytes: bool,
+    break_at_spaces: bool,
+}
+
+fn main() {
+    let matches = Command::new("fold")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils fold - wrap each input line to fit in specified width")
+        .arg(
+            Arg::new("bytes")
+                .short('b')
+                .long("bytes")
+                .help("Count bytes rather than columns")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("spaces")
+                .short('s')
+                .long("spaces")
+                .help("Break at spaces")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("width")
+                .short('w')
+                .long("width")
+                .value_name("WIDTH")
+                .help("Use WIDTH columns instead of 80"),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to wrap, or - for standard input")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let width = match matches.get_one::<String>("width") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(width) if width > 0 => width,
+            _ => fail("fold", &format!("invalid number of columns: '{}'", value)),
+        },
+        None => DEFAULT_WIDTH,
+    };
+
+    let options = Options {
+        width,
+        count_bytes: matches.get_flag("bytes"),
+        break_at_spaces: matches.get_flag("spaces"),
+    };
+
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files {
+        let result = if file == "-" {
+            fold_input(&mut io::stdin().lock(), &mut out, &options)
+        } else {
+            File::open(file).and_then(|f| fold_input(&mut BufReader::new(f), &mut out, &options))
+        };
+
+        if let Err(err) = result {
+            eprintln!("fold: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("fold: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn fold_input(input: &mut impl BufRead, out: &mut impl Write, options: &Options) -> io::Result<()> {
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+
+        let has_newline = line.last() == Some(&b'\n');
+        if has_newline {
+            line.pop();
+        }
+
+        fold_line(&line, out, options)?;
+
+        if has_newline {
+            out.write_all(b"\n")?;
+        }
+    }
+}
+
+fn fold_line(line: &[u8], out: &mut impl Write, options: &Options) -> io::Result<()> {
+    let units = split_units(line, options.count_bytes);
+
+    let mut pending: Vec<&[u8]> = Vec::new();
+    let mut column = 0;
+
+    for unit in units {
+        let next_column = advance(column, unit, options.count_bytes);
+
+        if next_column > options.width && !pending.is_empty() {
+            let mut split = pending.len();
+
+            if options.break_at_spaces {
+                if let Some(space) = pending.iter().rposition(|u| *u == b" ") {
+                    split = space + 1;
+                }
+            }
+
+            for piece in &pending[..split] {
+                out.write_all(piece)?;
+            }
+            out.write_all(b"\n")?;
+
+            pending.drain(..split);
+            column = pending
+                .iter()
+                .fold(0, |col, u| advance(col, u, options.count_bytes));
+        }
+
+        column = advance(column, unit, options.count_bytes);
+        pending.push(unit);
+    }
+
+    for piece in pending {
+        out.write_all(piece)?;
+    }
+    Ok(())
+}
+
+// Splits a line into the units that occupy columns: single bytes with -b,
+// otherwise whole UTF-8 characters (invalid bytes stand alone)
+fn split_units(line: &[u8], count_bytes: bool) -> Vec<&[u8]> {
+    if count_bytes {
+        return line.chunks(1).collect();
+    }
+
+    let mut units = Vec::with_capacity(line.len());
+    for chunk in line.utf8_chunks() {
+        let valid = chunk.valid();
+        let mut offset = 0;
+        for c in valid.chars() {
+            let len = c.len_utf8();
+            units.push(&valid.as_bytes()[offset..offset + len]);
+            offset += len;
+        }
+        units.extend(chunk.invalid().chunks(1));
+    }
+    units
+}
+
+fn advance(column: usize, unit: &[u8], count_bytes: bool) -> usize {
+    if count_bytes {
+        return column + 1;
+    }
+
+    match unit {
+        b"\t" => column + TAB_WIDTH - column % TAB_WIDTH,
+        b"\x08" => column.saturating_sub(1),
+        b"\r" => 0,
+        _ => column + 1,
+    }
+}
@@ -0,0 +1,26 @@
+// ASD CoreUtils - b2sum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::fail;
+use clap::Arg;
+use hashsum::Algorithm;
+
+fn main() {
+    let matches = hashsum::command("b2sum", "ASD CoreUtils b2sum - print BLAKE2b checksums")
+        .arg(
+            Arg::new("length")
+                .short('l')
+                .long("length")
+                .value_name("BITS")
+                .help("Digest length in bits; must not exceed 512 and must be a multiple of 8"),
+        )
+        .get_matches();
+
+    let bits = matches.get_one::<String>("length").map(|value| match value.parse::<usize>() {
+        Ok(bits) if bits > 0 && bits <= 512 && bits % 8 == 0 => bits,
+        _ => fail("b2sum", &format!("invalid length: '{}'", value)),
+    });
+
+    hashsum::run("b2sum", &matches, Algorithm::Blake2b, bits);
+}
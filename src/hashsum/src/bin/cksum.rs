@@ -0,0 +1,137 @@
+// ASD CoreUtils - cksum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, Command};
+use hashsum::crc::crc_reader;
+use hashsum::parallel::in_parallel;
+use hashsum::{Algorithm, OutputStyle};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+fn main() {
+    let matches = hashsum::check_args(Command::new("cksum"))
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils cksum - print CRC checksum and byte counts")
+        .arg(
+            Arg::new("algorithm")
+                .short('a')
+                .long("algorithm")
+                .value_name("TYPE")
+                .default_value("crc")
+                .value_parser(["crc", "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "blake2b"])
+                .help("Select the digest type to use"),
+        )
+        .arg(
+            Arg::new("length")
+                .short('l')
+                .long("length")
+                .value_name("BITS")
+                .help("Digest length in bits for blake2b; must not exceed 512 and must be a multiple of 8"),
+        )
+        .arg(
+            Arg::new("untagged")
+                .long("untagged")
+                .help("Create a reversed style checksum, without digest type")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to checksum, or - for standard input")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let name = matches.get_one::<String>("algorithm").unwrap();
+    let algorithm = Algorithm::from_name(name);
+
+    let bits = matches.get_one::<String>("length").map(|value| {
+        if algorithm != Some(Algorithm::Blake2b) {
+            fail("cksum", "--length is only supported with --algorithm=blake2b");
+        }
+        match value.parse::<usize>() {
+            Ok(bits) if bits > 0 && bits <= 512 && bits % 8 == 0 => bits,
+            _ => fail("cksum", &format!("invalid length: '{}'", value)),
+        }
+    });
+
+    let check_options = hashsum::check_options("cksum", &matches);
+    if matches.get_flag("check") {
+        // CRCs are printed without anything saying what they are, so a
+        // list of them cannot be checked; without -a each line is tagged
+        // with its own algorithm
+        if algorithm.is_none()
+            && matches.value_source("algorithm") == Some(ValueSource::CommandLine)
+        {
+            fail("cksum", "--check is not supported with --algorithm=crc");
+        }
+        hashsum::run_check("cksum", &matches, algorithm, bits, &check_options);
+    }
+
+    let style = OutputStyle {
+        tag: !matches.get_flag("untagged"),
+        binary: false,
+        zero: false,
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    let checksum = |file: &String| match algorithm {
+        // Classic cksum output: CRC, byte count, then the name unless stdin
+        None => checksum_crc(file).map(|(crc, length)| {
+            if file == "-" {
+                format!("{} {}", crc, length)
+            } else {
+                format!("{} {} {}", crc, length, file)
+            }
+        }),
+        Some(algorithm) => hashsum::digest_file(file, algorithm.hasher(bits)).map(|digest| {
+            let output_bits = bits.unwrap_or_else(|| algorithm.default_bits());
+            hashsum::format_line(algorithm, output_bits, file, &digest, &style)
+        }),
+    };
+
+    in_parallel(
+        &hashsum::files(&matches),
+        hashsum::reads_stdin,
+        checksum,
+        |file, line| {
+            match line {
+                Ok(line) => {
+                    if writeln!(out, "{}", line).is_err() {
+                        exit_code = 1;
+                        return false;
+                    }
+                }
+                Err(err) => {
+                    let _ = out.flush();
+                    eprintln!("cksum: {}: {}", file, strerror(&err));
+                    exit_code = 1;
+                }
+            }
+            true
+        },
+    );
+
+    if let Err(err) = out.flush() {
+        eprintln!("cksum: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn checksum_crc(path: &str) -> io::Result<(u32, u64)> {
+    if path == "-" {
+        crc_reader(&mut io::stdin().lock())
+    } else {
+        crc_reader(&mut File::open(path)?)
+    }
+}
@@ -0,0 +1,15 @@
+// ASD CoreUtils - md5sum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use hashsum::Algorithm;
+
+fn main() {
+    let matches = hashsum::command(
+        "md5sum",
+        "ASD CoreUtils md5sum - print MD5 (128-bit) checksums",
+    )
+    .get_matches();
+
+    hashsum::run("md5sum", &matches, Algorithm::Md5, None);
+}
@@ -0,0 +1,15 @@
+// ASD CoreUtils - sha1sum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use hashsum::Algorithm;
+
+fn main() {
+    let matches = hashsum::command(
+        "sha1sum",
+        "ASD CoreUtils sha1sum - print SHA1 (160-bit) checksums",
+    )
+    .get_matches();
+
+    hashsum::run("sha1sum", &matches, Algorithm::Sha1, None);
+}
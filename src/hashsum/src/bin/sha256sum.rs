@@ -0,0 +1,15 @@
+// ASD CoreUtils - sha256sum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use hashsum::Algorithm;
+
+fn main() {
+    let matches = hashsum::command(
+        "sha256sum",
+        "ASD CoreUtils sha256sum - print SHA256 (256-bit) checksums",
+    )
+    .get_matches();
+
+    hashsum::run("sha256sum", &matches, Algorithm::Sha256, None);
+}
@@ -0,0 +1,15 @@
+// ASD CoreUtils - sha512sum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use hashsum::Algorithm;
+
+fn main() {
+    let matches = hashsum::command(
+        "sha512sum",
+        "ASD CoreUtils sha512sum - print SHA512 (512-bit) checksums",
+    )
+    .get_matches();
+
+    hashsum::run("sha512sum", &matches, Algorithm::Sha512, None);
+}
@@ -0,0 +1,335 @@
+// ASD CoreUtils - shared digest engine for md5sum, sha*sum, b2sum and cksum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// --check: reading back lists of checksums in the formats the tools print,
+// "HEX  NAME" or "HEX *NAME", or tagged as "TAG (NAME) = HEX", and
+// verifying the files named in them
+
+use asd_coreutils_core::error::strerror;
+use crate::parallel::in_parallel;
+use crate::{digest_file, Algorithm};
+use std::fs;
+use std::io::{self, Read, Write};
+
+#[derive(Clone, Copy, Default)]
+pub struct CheckOptions {
+    // Don't print OK for each verified file
+    pub quiet: bool,
+    // Print nothing; the exit status tells
+    pub status: bool,
+    // Report each improperly formatted line
+    pub warn: bool,
+    // Fail if any line is improperly formatted
+    pub strict: bool,
+    // Skip files that do not exist instead of failing them
+    pub ignore_missing: bool,
+}
+
+struct Entry {
+    file: String,
+    algorithm: Algorithm,
+    bits: usize,
+    expected: Vec<u8>,
+}
+
+enum Line {
+    Entry(Entry),
+    Improper(usize),
+}
+
+// Verify the files listed in SUMS, "-" being standard input. ALGORITHM is
+// the one the tool computes; cksum passes None and takes it from each
+// tagged line. BITS is the length given with -l, if any. Returns whether
+// every listed file matched.
+pub fn check_file(
+    name: &str,
+    sums: &str,
+    algorithm: Option<Algorithm>,
+    bits: Option<usize>,
+    options: &CheckOptions,
+    out: &mut impl Write,
+) -> bool {
+    let shown = if sums == "-" { "standard input" } else { sums };
+    let contents = if sums == "-" {
+        let mut contents = Vec::new();
+        io::stdin()
+            .lock()
+            .read_to_end(&mut contents)
+            .map(|_| contents)
+    } else {
+        fs::read(sums)
+    };
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{}: {}: {}", name, sums, strerror(&err));
+            return false;
+        }
+    };
+
+    let lines: Vec<Line> = contents
+        .split(|&byte| byte == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && line[0] != b'#')
+        .map(|(index, line)| match parse_line(line, algorithm, bits) {
+            Some(entry) => Line::Entry(entry),
+            None => Line::Improper(index + 1),
+        })
+        .collect();
+
+    let mut improper = 0;
+    let mut unreadable = 0;
+    let mut mismatched = 0;
+    let mut verified = 0;
+    let mut write_failed = false;
+
+    let reads_stdin = |line: &Line| matches!(line, Line::Entry(entry) if entry.file == "-");
+    let hash = |line: &Line| match line {
+        Line::Entry(entry) => Some(digest_file(
+            &entry.file,
+            entry.algorithm.hasher(Some(entry.bits)),
+        )),
+        Line::Improper(_) => None,
+    };
+
+    in_parallel(&lines, reads_stdin, hash, |line, result| {
+        let (entry, result) = match (line, result) {
+            (Line::Entry(entry), Some(result)) => (entry, result),
+            (Line::Improper(number), _) => {
+                improper += 1;
+                if options.warn {
+                    let _ = out.flush();
+                    let label = algorithm
+                        .map_or(String::new(), |algorithm| format!("{} ", algorithm.tag()));
+                    eprintln!(
+                        "{}: {}: {}: improperly formatted {}checksum line",
+                        name, shown, number, label
+                    );
+                }
+                return true;
+            }
+            _ => return true,
+        };
+
+        let status = match result {
+            Err(err) if options.ignore_missing && err.kind() == io::ErrorKind::NotFound => {
+                return true
+            }
+            Err(err) => {
+                unreadable += 1;
+                let _ = out.flush();
+                eprintln!("{}: {}: {}", name, entry.file, strerror(&err));
+                "FAILED open or read"
+            }
+            Ok(digest) if digest == entry.expected => {
+                verified += 1;
+                if options.quiet {
+                    return true;
+                }
+                "OK"
+            }
+            Ok(_) => {
+                verified += 1;
+                mismatched += 1;
+                "FAILED"
+            }
+        };
+
+        if !options.status {
+            let (prefix, file) = display_name(&entry.file);
+            if writeln!(out, "{}{}: {}", prefix, file, status).is_err() {
+                write_failed = true;
+                return false;
+            }
+        }
+        true
+    });
+
+    if write_failed {
+        return false;
+    }
+    let _ = out.flush();
+
+    if improper == lines.len() {
+        eprintln!(
+            "{}: {}: no properly formatted checksum lines found",
+            name,
+            quote(shown)
+        );
+        return false;
+    }
+
+    if !options.status {
+        if improper > 0 {
+            let lines = if improper == 1 {
+                "line is"
+            } else {
+                "lines are"
+            };
+            eprintln!(
+                "{}: WARNING: {} {} improperly formatted",
+                name, improper, lines
+            );
+        }
+        if unreadable > 0 {
+            let files = if unreadable == 1 {
+                "listed file"
+            } else {
+                "listed files"
+            };
+            eprintln!(
+                "{}: WARNING: {} {} could not be read",
+                name, unreadable, files
+            );
+        }
+        if mismatched > 0 {
+            let sums = if mismatched == 1 {
+                "computed checksum"
+            } else {
+                "computed checksums"
+            };
+            eprintln!("{}: WARNING: {} {} did NOT match", name, mismatched, sums);
+        }
+    }
+
+    if options.ignore_missing && verified == 0 && unreadable == 0 {
+        eprintln!("{}: {}: no file was verified", name, quote(shown));
+        return false;
+    }
+
+    mismatched == 0 && unreadable == 0 && !(options.strict && improper > 0)
+}
+
+// Quote a name for a message if it has anything but the plainest characters
+fn quote(name: &str) -> String {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./+,:@%^=".contains(c))
+    {
+        name.to_string()
+    } else {
+        format!("'{}'", name)
+    }
+}
+
+// As GNU does, only names that would break the line are escaped, and the
+// line is then marked with a leading backslash
+fn display_name(file: &str) -> (&'static str, String) {
+    if file.contains(['\n', '\r']) {
+        let escaped = file
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        ("\\", escaped)
+    } else {
+        ("", file.to_string())
+    }
+}
+
+fn parse_line(line: &[u8], algorithm: Option<Algorithm>, bits: Option<usize>) -> Option<Entry> {
+    let line = std::str::from_utf8(line).ok()?;
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let line = line.trim_start_matches([' ', '\t']);
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (file, algorithm, bits, hex) = match parse_tagged(line, algorithm, bits) {
+        Some(tagged) => tagged,
+        None => {
+            let algorithm = algorithm?;
+            let (hex, rest) = line.split_once(' ')?;
+            let file = rest.strip_prefix([' ', '*'])?;
+            (file, algorithm, bits, hex)
+        }
+    };
+
+    let expected = from_hex(hex)?;
+    let bits = match (algorithm, bits) {
+        // Untagged BLAKE2b lines give their length by how long they are
+        (Algorithm::Blake2b, None) => expected.len() * 8,
+        (_, bits) => bits.unwrap_or_else(|| algorithm.default_bits()),
+    };
+    if expected.is_empty() || expected.len() * 8 != bits || bits > algorithm.default_bits() {
+        return None;
+    }
+
+    let file = if escaped {
+        unescape(file)?
+    } else {
+        file.to_string()
+    };
+    if file.is_empty() {
+        return None;
+    }
+    Some(Entry {
+        file,
+        algorithm,
+        bits,
+        expected,
+    })
+}
+
+// "TAG (NAME) = HEX", where BLAKE2b may be tagged with its length, as in
+// "BLAKE2b-256". Only the tool's own algorithm is accepted unless it is
+// None.
+fn parse_tagged(
+    line: &str,
+    algorithm: Option<Algorithm>,
+    bits: Option<usize>,
+) -> Option<(&str, Algorithm, Option<usize>, &str)> {
+    let (label, rest) = line.split_once(" (")?;
+    let (file, hex) = rest.rsplit_once(") = ")?;
+
+    let (tag, tagged_bits) = match label.split_once('-') {
+        Some((tag, length)) => (tag, Some(length.parse::<usize>().ok()?)),
+        None => (label, None),
+    };
+    let tagged = Algorithm::ALL
+        .into_iter()
+        .find(|candidate| candidate.tag() == tag)?;
+    if algorithm.is_some_and(|algorithm| algorithm != tagged) {
+        return None;
+    }
+    if tagged_bits.is_some() && tagged != Algorithm::Blake2b {
+        return None;
+    }
+
+    let bits = match (tagged_bits, bits) {
+        (Some(tagged_bits), Some(bits)) if tagged_bits != bits => return None,
+        (Some(tagged_bits), _) => Some(tagged_bits),
+        (None, bits) if tagged == Algorithm::Blake2b => Some(bits.unwrap_or(tagged.default_bits())),
+        (None, bits) => bits,
+    };
+    Some((file, tagged, bits, hex))
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+fn unescape(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => unescaped.push('\\'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
@@ -0,0 +1,81 @@
+// ASD CoreUtils - POSIX cksum CRC
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use std::io::{self, Read};
+
+// CRC-32 with polynomial 0x04C11DB7, processed most significant bit first
+// and without reflection, as POSIX specifies for cksum
+const POLYNOMIAL: u32 = 0x04c1_1db7;
+
+const TABLE: [u32; 256] = build_table();
+
+const fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = (index as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+}
+
+pub struct Crc {
+    crc: u32,
+    length: u64,
+}
+
+impl Default for Crc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc {
+    pub fn new() -> Self {
+        Crc { crc: 0, length: 0 }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.crc = (self.crc << 8) ^ TABLE[((self.crc >> 24) as u8 ^ byte) as usize];
+        }
+        self.length += data.len() as u64;
+    }
+
+    // Returns the checksum and the number of bytes it covers
+    pub fn finish(mut self) -> (u32, u64) {
+        // The length is folded in least significant byte first, using only
+        // as many bytes as it needs
+        let mut length = self.length;
+        while length > 0 {
+            self.crc = (self.crc << 8) ^ TABLE[((self.crc >> 24) as u8 ^ length as u8) as usize];
+            length >>= 8;
+        }
+        (!self.crc, self.length)
+    }
+}
+
+pub fn crc_reader(reader: &mut impl Read) -> io::Result<(u32, u64)> {
+    let mut crc = Crc::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(crc.finish()),
+            Ok(n) => crc.update(&buffer[..n]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -0,0 +1,399 @@
+// ASD CoreUtils - shared digest engine for md5sum, sha*sum, b2sum and cksum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{strerror, usage_error};
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use digest::DynDigest;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::process;
+
+pub mod check;
+pub mod crc;
+pub mod parallel;
+
+use check::CheckOptions;
+use parallel::in_parallel;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake2b,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 7] = [
+        Algorithm::Md5,
+        Algorithm::Sha1,
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Blake2b,
+    ];
+
+    // Parses the names accepted by cksum -a
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "md5" => Some(Algorithm::Md5),
+            "sha1" => Some(Algorithm::Sha1),
+            "sha224" => Some(Algorithm::Sha224),
+            "sha256" => Some(Algorithm::Sha256),
+            "sha384" => Some(Algorithm::Sha384),
+            "sha512" => Some(Algorithm::Sha512),
+            "blake2b" => Some(Algorithm::Blake2b),
+            _ => None,
+        }
+    }
+
+    // Name used by --tag (BSD-style) output
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha1 => "SHA1",
+            Algorithm::Sha224 => "SHA224",
+            Algorithm::Sha256 => "SHA256",
+            Algorithm::Sha384 => "SHA384",
+            Algorithm::Sha512 => "SHA512",
+            Algorithm::Blake2b => "BLAKE2b",
+        }
+    }
+
+    // Digest size in bits when no explicit length is requested
+    pub fn default_bits(&self) -> usize {
+        match self {
+            Algorithm::Md5 => 128,
+            Algorithm::Sha1 => 160,
+            Algorithm::Sha224 => 224,
+            Algorithm::Sha256 => 256,
+            Algorithm::Sha384 => 384,
+            Algorithm::Sha512 | Algorithm::Blake2b => 512,
+        }
+    }
+
+    pub fn hasher(&self, bits: Option<usize>) -> Hasher {
+        match self {
+            Algorithm::Md5 => Hasher::Fixed(Box::new(md5::Md5::default())),
+            Algorithm::Sha1 => Hasher::Fixed(Box::new(sha1::Sha1::default())),
+            Algorithm::Sha224 => Hasher::Fixed(Box::new(sha2::Sha224::default())),
+            Algorithm::Sha256 => Hasher::Fixed(Box::new(sha2::Sha256::default())),
+            Algorithm::Sha384 => Hasher::Fixed(Box::new(sha2::Sha384::default())),
+            Algorithm::Sha512 => Hasher::Fixed(Box::new(sha2::Sha512::default())),
+            Algorithm::Blake2b => {
+                let bytes = bits.unwrap_or(512) / 8;
+                Hasher::Blake2b(Blake2bVar::new(bytes).expect("BLAKE2b length validated by caller"))
+            }
+        }
+    }
+}
+
+// Streaming state for one input; BLAKE2b is kept apart because its output
+// length is chosen at run time
+pub enum Hasher {
+    Fixed(Box<dyn DynDigest>),
+    Blake2b(Blake2bVar),
+}
+
+impl Hasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Fixed(digest) => digest.update(data),
+            Hasher::Blake2b(digest) => digest.update(data),
+        }
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        match self {
+            Hasher::Fixed(digest) => digest.finalize().into_vec(),
+            Hasher::Blake2b(digest) => {
+                let mut output = vec![0u8; digest.output_size()];
+                digest
+                    .finalize_variable(&mut output)
+                    .expect("output buffer sized from the hasher");
+                output
+            }
+        }
+    }
+}
+
+pub fn digest_reader(reader: &mut impl Read, mut hasher: Hasher) -> io::Result<Vec<u8>> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(hasher.finish()),
+            Ok(n) => hasher.update(&buffer[..n]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+pub fn digest_file(path: &str, hasher: Hasher) -> io::Result<Vec<u8>> {
+    if path == "-" {
+        digest_reader(&mut io::stdin().lock(), hasher)
+    } else {
+        digest_reader(&mut File::open(path)?, hasher)
+    }
+}
+
+pub fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// GNU marks names containing a backslash or newline with a leading
+// backslash and escapes them, so every output line stays parseable
+fn escape_name(name: &str) -> (bool, String) {
+    if !name.contains(['\\', '\n', '\r']) {
+        return (false, name.to_string());
+    }
+
+    let escaped = name
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    (true, escaped)
+}
+
+pub struct OutputStyle {
+    pub tag: bool,
+    pub binary: bool,
+    pub zero: bool,
+}
+
+pub fn format_line(algorithm: Algorithm, bits: usize, name: &str, digest: &[u8], style: &OutputStyle) -> String {
+    // -z ends lines with NUL, so names are printed as they are
+    let (escaped, name) = if style.zero {
+        (false, name.to_string())
+    } else {
+        escape_name(name)
+    };
+    let prefix = if escaped { "\\" } else { "" };
+    let hex = to_hex(digest);
+
+    if style.tag {
+        let label = if algorithm == Algorithm::Blake2b && bits != algorithm.default_bits() {
+            format!("{}-{}", algorithm.tag(), bits)
+        } else {
+            algorithm.tag().to_string()
+        };
+        format!("{}{} ({}) = {}", prefix, label, name, hex)
+    } else {
+        let mode = if style.binary { '*' } else { ' ' };
+        format!("{}{} {}{}", prefix, hex, mode, name)
+    }
+}
+
+// The options of --check, shared with cksum
+pub fn check_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("check")
+                .short('c')
+                .long("check")
+                .help("Read checksums from the FILEs and check them")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ignore-missing")
+                .long("ignore-missing")
+                .help("Don't fail or report status for missing files")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .help("Don't print OK for each successfully verified file")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("status")
+                .long("status")
+                .help("Don't output anything, status code shows success")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .help("Exit non-zero for improperly formatted checksum lines")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("warn")
+                .short('w')
+                .long("warn")
+                .help("Warn about improperly formatted checksum lines")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+// The --check options given, after making sure they are only given with
+// --check
+pub fn check_options(name: &str, matches: &ArgMatches) -> CheckOptions {
+    let check = matches.get_flag("check");
+    for option in ["ignore-missing", "quiet", "status", "strict", "warn"] {
+        if !check && matches.get_flag(option) {
+            usage_error(
+                name,
+                &format!(
+                    "the --{} option is meaningful only when verifying checksums",
+                    option
+                ),
+            );
+        }
+    }
+    CheckOptions {
+        quiet: matches.get_flag("quiet"),
+        status: matches.get_flag("status"),
+        warn: matches.get_flag("warn"),
+        strict: matches.get_flag("strict"),
+        ignore_missing: matches.get_flag("ignore-missing"),
+    }
+}
+
+// Whether hashing FILE reads standard input, which has to happen in order
+pub fn reads_stdin(file: &String) -> bool {
+    file == "-"
+}
+
+// The options every front end shares; callers add their own on top
+pub fn command(name: &'static str, about: &'static str) -> Command {
+    check_args(Command::new(name))
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about(about)
+        .arg(
+            Arg::new("binary")
+                .short('b')
+                .long("binary")
+                .help("Read in binary mode")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("text")
+                .short('t')
+                .long("text")
+                .help("Read in text mode (default)")
+                .overrides_with("binary")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("tag")
+                .long("tag")
+                .help("Create a BSD-style checksum")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("zero")
+                .short('z')
+                .long("zero")
+                .help("End each output line with NUL, not newline")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to checksum, or - for standard input")
+                .num_args(0..),
+        )
+}
+
+pub fn files(matches: &ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("file")
+        .map(|files| files.cloned().collect())
+        .unwrap_or_else(|| vec!["-".to_string()])
+}
+
+pub fn run(name: &str, matches: &ArgMatches, algorithm: Algorithm, bits: Option<usize>) {
+    let check_options = check_options(name, matches);
+    if matches.get_flag("check") {
+        if matches.get_flag("tag") {
+            usage_error(
+                name,
+                "the --tag option is meaningless when verifying checksums",
+            );
+        }
+        if matches.get_flag("binary") || matches.get_flag("text") {
+            usage_error(
+                name,
+                "the --binary and --text options are meaningless when verifying checksums",
+            );
+        }
+        run_check(name, matches, Some(algorithm), bits, &check_options);
+    }
+
+    let style = OutputStyle {
+        tag: matches.get_flag("tag"),
+        binary: matches.get_flag("binary"),
+        zero: matches.get_flag("zero"),
+    };
+    let terminator = if style.zero { '\0' } else { '\n' };
+    let output_bits = bits.unwrap_or_else(|| algorithm.default_bits());
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    let hash = |file: &String| digest_file(file, algorithm.hasher(bits));
+    in_parallel(&files(matches), reads_stdin, hash, |file, result| {
+        match result {
+            Ok(digest) => {
+                let line = format_line(algorithm, output_bits, file, &digest, &style);
+                if write!(out, "{}{}", line, terminator).is_err() {
+                    exit_code = 1;
+                    return false;
+                }
+            }
+            Err(err) => {
+                let _ = out.flush();
+                eprintln!("{}: {}: {}", name, file, strerror(&err));
+                exit_code = 1;
+            }
+        }
+        true
+    });
+
+    if let Err(err) = out.flush() {
+        eprintln!("{}: {}", name, strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+// Verify the checksum lists given as FILEs and exit
+pub fn run_check(
+    name: &str,
+    matches: &ArgMatches,
+    algorithm: Option<Algorithm>,
+    bits: Option<usize>,
+    options: &CheckOptions,
+) -> ! {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files(matches) {
+        if !check::check_file(name, &file, algorithm, bits, options, &mut out) {
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("{}: {}", name, strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
@@ -0,0 +1,78 @@
+// ASD CoreUtils - shared digest engine for md5sum, sha*sum, b2sum and cksum
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+// Run WORK on each of ITEMS on a pool of threads, handing the results to
+// REPORT in the order of ITEMS, so output is the same as working through
+// them one by one. REPORT returns false to stop early. Items for which
+// IN_ORDER is true, those reading standard input, are worked on by the
+// calling thread when their turn comes, so that they see the input in the
+// order they were given.
+pub fn in_parallel<I, T>(
+    items: &[I],
+    in_order: impl Fn(&I) -> bool + Sync,
+    work: impl Fn(&I) -> T + Sync,
+    mut report: impl FnMut(&I, T) -> bool,
+) where
+    I: Sync,
+    T: Send,
+{
+    let threads = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(items.len());
+    if threads <= 1 {
+        for item in items {
+            if !report(item, work(item)) {
+                break;
+            }
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<T>>> = Mutex::new(items.iter().map(|_| None).collect());
+    let ready = Condvar::new();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if in_order(item) {
+                        continue;
+                    }
+                    let result = work(item);
+                    results.lock().unwrap()[index] = Some(result);
+                    ready.notify_all();
+                }
+            });
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            let result = if in_order(item) {
+                work(item)
+            } else {
+                let mut results = results.lock().unwrap();
+                loop {
+                    if let Some(result) = results[index].take() {
+                        break result;
+                    }
+                    results = ready.wait(results).unwrap();
+                }
+            };
+            if !report(item, result) {
+                break;
+            }
+        }
+        stop.store(true, Ordering::Relaxed);
+    });
+}
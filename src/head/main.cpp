@@ -13,6 +13,12 @@
  #include <vector>
  #include <chrono>
  #include <algorithm>
+ #include <deque>
+ #include <cctype>
+ #include <cerrno>
+ #include <climits>
+ #include <cstdlib>
+ #include <cstring>
  #include <unistd.h>
  #include <getopt.h>
  
@@ -21,6 +27,8 @@
      // Default settings
      int64_t numLines = 10;
      int64_t numBytes = -1;
+     bool allButLast = false;
+     char delimiter = '\n';
      bool quietMode = false;
      bool verboseMode = false;
      bool showFilename = false;
@@ -33,12 +41,20 @@
                    << "Print the first 10 lines of each FILE to standard output.\n"
                    << "With more than one FILE, prefix each with a header giving the file name.\n\n"
                    << "Options:\n"
-                   << "  -n, --lines=N         print the first N lines instead of the first 10\n"
-                   << "  -c, --bytes=N         print the first N bytes\n"
+                   << "  -n, --lines=[-]N      print the first N lines instead of the first 10;\n"
+                   << "                        with the leading '-', print all but the last\n"
+                   << "                        N lines of each file\n"
+                   << "  -c, --bytes=[-]N      print the first N bytes of each file;\n"
+                   << "                        with the leading '-', print all but the last\n"
+                   << "                        N bytes of each file\n"
                    << "  -q, --quiet           never print headers giving file names\n"
                    << "  -v, --verbose         always print headers giving file names\n"
+                   << "  -z, --zero-terminated line delimiter is NUL, not newline\n"
                    << "  -h, --help            display this help and exit\n"
                    << "  -V, --version         output version information and exit\n\n"
+                   << "N may have a multiplier suffix: b 512, kB 1000, K 1024, MB 1000*1000,\n"
+                   << "M 1024*1024, GB 1000*1000*1000, G 1024*1024*1024, and so on for T, P, E.\n"
+                   << "Binary prefixes can be used, too: KiB=K, MiB=M, and so on.\n\n"
                    << "If no FILE is specified, or when FILE is -, read standard input.\n\n"
                    << "Part of ASD CoreUtils - https://github.com/ASD-Projects/coreutils\n";
      }
@@ -56,6 +72,136 @@
          std::cerr << "Processing completed in " << duration.count() / 1000.0 << " ms\n";
      }
  
+     // Value of a multiplier suffix on a count, or 0 if it is not one
+     static uint64_t suffixMultiplier(const char* suffix) {
+         static const char prefixes[] = "KMGTPE";
+ 
+         if (*suffix == '\0') {
+             return 1;
+         }
+         if (std::strcmp(suffix, "b") == 0) {
+             return 512;
+         }
+ 
+         char prefix = (*suffix == 'k' || *suffix == 'm') ? std::toupper(*suffix) : *suffix;
+         const char* found = std::strchr(prefixes, prefix);
+         if (prefix == '\0' || found == nullptr) {
+             return 0;
+         }
+ 
+         uint64_t base;
+         if (suffix[1] == '\0' || std::strcmp(suffix + 1, "iB") == 0) {
+             base = 1024;
+         } else if (std::strcmp(suffix + 1, "B") == 0) {
+             base = 1000;
+         } else {
+             return 0;
+         }
+ 
+         uint64_t multiplier = 1;
+         for (const char* p = prefixes; p <= found; p++) {
+             multiplier *= base;
+         }
+         return multiplier;
+     }
+ 
+     // Parse the N of -n or -c; a leading '-' asks for all but the last N.
+     // Counts too large to represent are taken as the largest there is.
+     static bool parseCount(const char* arg, int64_t& count, bool& negative) {
+         const char* p = arg;
+         negative = *p == '-';
+         if (negative) {
+             p++;
+         }
+         if (!std::isdigit(static_cast<unsigned char>(*p))) {
+             return false;
+         }
+ 
+         char* end;
+         errno = 0;
+         unsigned long long value = std::strtoull(p, &end, 10);
+         bool overflow = errno == ERANGE;
+         uint64_t multiplier = suffixMultiplier(end);
+         if (multiplier == 0) {
+             return false;
+         }
+ 
+         if (overflow || value > static_cast<uint64_t>(INT64_MAX) / multiplier) {
+             count = INT64_MAX;
+         } else {
+             count = static_cast<int64_t>(value * multiplier);
+         }
+         return true;
+     }
+ 
+     void copyBytes(std::istream& input) {
+         char buffer[8192];
+         int64_t remaining = numBytes;
+ 
+         while (remaining > 0 && input) {
+             input.read(buffer, std::min<int64_t>(remaining, sizeof(buffer)));
+             std::streamsize bytesRead = input.gcount();
+             std::cout.write(buffer, bytesRead);
+             remaining -= bytesRead;
+         }
+     }
+ 
+     // Everything but the last numBytes bytes, keeping back only as much as
+     // might still be among them
+     void copyAllButLastBytes(std::istream& input) {
+         char buffer[8192];
+         std::string held;
+ 
+         while (input) {
+             input.read(buffer, sizeof(buffer));
+             held.append(buffer, input.gcount());
+             if (static_cast<uint64_t>(held.size()) > static_cast<uint64_t>(numBytes) + sizeof(buffer)) {
+                 size_t ready = held.size() - numBytes;
+                 std::cout.write(held.data(), ready);
+                 held.erase(0, ready);
+             }
+         }
+ 
+         if (static_cast<uint64_t>(held.size()) > static_cast<uint64_t>(numBytes)) {
+             std::cout.write(held.data(), held.size() - numBytes);
+         }
+     }
+ 
+     // Read one line with its delimiter, which the last line may lack
+     bool readLine(std::istream& input, std::string& line) {
+         if (!std::getline(input, line, delimiter)) {
+             return false;
+         }
+         if (!input.eof()) {
+             line += delimiter;
+         }
+         return true;
+     }
+ 
+     void copyLines(std::istream& input) {
+         std::string line;
+         int64_t lineCount = 0;
+ 
+         while (lineCount < numLines && readLine(input, line)) {
+             std::cout << line;
+             lineCount++;
+         }
+     }
+ 
+     // Everything but the last numLines lines, holding back that many
+     void copyAllButLastLines(std::istream& input) {
+         std::deque<std::string> held;
+         std::string line;
+ 
+         while (readLine(input, line)) {
+             held.push_back(std::move(line));
+             if (static_cast<uint64_t>(held.size()) > static_cast<uint64_t>(numLines)) {
+                 std::cout << held.front();
+                 held.pop_front();
+             }
+         }
+     }
+ 
      void processFile(const std::string& filename) {
          std::istream* input;
          std::ifstream file;
@@ -78,27 +224,16 @@
              std::cout << "==> standard input <==\n";
          }
  
-         // Process by bytes if specified
-         if (numBytes >= 0) {
-             std::vector<char> buffer(numBytes);
-             input->read(buffer.data(), numBytes);
-             size_t bytesRead = input->gcount();
-             std::cout.write(buffer.data(), bytesRead);
-             if (filenames.size() > 1 && !quietMode && filename != filenames.back()) {
-                 std::cout << "\n";
-             }
-             return;
+         if (numBytes >= 0 && allButLast) {
+             copyAllButLastBytes(*input);
+         } else if (numBytes >= 0) {
+             copyBytes(*input);
+         } else if (allButLast) {
+             copyAllButLastLines(*input);
+         } else {
+             copyLines(*input);
          }
  
-         // Process by lines
-         std::string line;
-         int64_t lineCount = 0;
-         
-         while (lineCount < numLines && std::getline(*input, line)) {
-             std::cout << line << "\n";
-             lineCount++;
-         }
-         
          if (filenames.size() > 1 && !quietMode && filename != filenames.back()) {
              std::cout << "\n";
          }
@@ -117,20 +252,27 @@
              {"verbose", no_argument, 0, 'v'},
              {"help", no_argument, 0, 'h'},
              {"version", no_argument, 0, 'V'},
+             {"zero-terminated", no_argument, 0, 'z'},
              {0, 0, 0, 0}
          };
  
          int optionIndex = 0;
          int opt;
  
-         while ((opt = getopt_long(argc, argv, "c:n:qvhV", longOptions, &optionIndex)) != -1) {
+         while ((opt = getopt_long(argc, argv, "c:n:qvzhV", longOptions, &optionIndex)) != -1) {
              switch (opt) {
                  case 'c':
-                     numBytes = std::stoll(optarg);
+                     if (!parseCount(optarg, numBytes, allButLast)) {
+                         std::cerr << "asd-head: invalid number of bytes: '" << optarg << "'\n";
+                         return false;
+                     }
                      numLines = -1; // If bytes specified, don't use lines
                      break;
                  case 'n':
-                     numLines = std::stoll(optarg);
+                     if (!parseCount(optarg, numLines, allButLast)) {
+                         std::cerr << "asd-head: invalid number of lines: '" << optarg << "'\n";
+                         return false;
+                     }
                      numBytes = -1; // If lines specified, don't use bytes
                      break;
                  case 'q':
@@ -142,6 +284,9 @@
                      showFilename = true;
                      quietMode = false;
                      break;
+                 case 'z':
+                     delimiter = '\0';
+                     break;
                  case 'h':
                      printUsage();
                      return false;
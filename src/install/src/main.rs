@@ -0,0 +1,495 @@
+// ASD CoreUtils - install
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use std::ffi::CString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::mem;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command as Process};
+use std::ptr;
+
+const DEFAULT_MODE: u32 = 0o755;
+const MAX_ATTEMPTS: u32 = 10_000;
+
+struct Attributes {
+    mode: u32,
+    owner: Option<libc::uid_t>,
+    group: Option<libc::gid_t>,
+}
+
+struct Options {
+    attributes: Attributes,
+    verbose: bool,
+    // The program -s runs on each installed file
+    strip: Option<String>,
+    compare: bool,
+}
+
+fn main() {
+    let matches = Command::new("install")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils install - copy files and set attributes")
+        .override_usage(
+            "install [OPTIONS] SOURCE DEST\n       \
+             install [OPTIONS] SOURCE... DIRECTORY\n       \
+             install [OPTIONS] -t DIRECTORY SOURCE...\n       \
+             install [OPTIONS] -d DIRECTORY...",
+        )
+        .arg(
+            Arg::new("compare")
+                .short('C')
+                .long("compare")
+                .help("Compare content of source and destination files, and if no change to content, ownership, and permissions, do not modify the destination at all")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("directory")
+                .short('d')
+                .long("directory")
+                .help("Treat all arguments as directory names; create all components of the specified directories")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("parents")
+                .short('D')
+                .help("Create all leading components of DEST except the last, or all components of --target-directory, then copy SOURCE to DEST")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("group")
+                .short('g')
+                .long("group")
+                .value_name("GROUP")
+                .help("Set group ownership, instead of process' current group"),
+        )
+        .arg(
+            Arg::new("mode")
+                .short('m')
+                .long("mode")
+                .value_name("MODE")
+                .help("Set permission mode (as in chmod), instead of rwxr-xr-x"),
+        )
+        .arg(
+            Arg::new("owner")
+                .short('o')
+                .long("owner")
+                .value_name("OWNER")
+                .help("Set ownership (super-user only)"),
+        )
+        .arg(
+            Arg::new("strip")
+                .short('s')
+                .long("strip")
+                .help("Strip symbol tables")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("compare"),
+        )
+        .arg(
+            Arg::new("strip-program")
+                .long("strip-program")
+                .value_name("PROGRAM")
+                .help("Program used to strip binaries"),
+        )
+        .arg(
+            Arg::new("target-directory")
+                .short('t')
+                .long("target-directory")
+                .value_name("DIRECTORY")
+                .help("Copy all SOURCE arguments into DIRECTORY")
+                .conflicts_with("directory"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Print the name of each created file or directory")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Sources and destination, or directories with -d")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let mode = match matches.get_one::<String>("mode") {
+        Some(spec) => parse_mode(spec, 0, 0).unwrap_or_else(|| fail("install", &format!("invalid mode '{}'", spec))),
+        None => DEFAULT_MODE,
+    };
+
+    let owner = matches.get_one::<String>("owner").map(|name| {
+        lookup_user(name).unwrap_or_else(|| fail("install", &format!("invalid user '{}'", name)))
+    });
+
+    let group = matches.get_one::<String>("group").map(|name| {
+        lookup_group(name).unwrap_or_else(|| fail("install", &format!("invalid group '{}'", name)))
+    });
+
+    // Only the permission bits are compared, so other bits always mean a
+    // fresh copy
+    let mut compare = matches.get_flag("compare");
+    if compare && mode & 0o7000 != 0 {
+        eprintln!("install: the --compare (-C) option is ignored when you specify a mode with non-permission bits");
+        compare = false;
+    }
+
+    let verbose = matches.get_flag("verbose");
+    let options = Options {
+        attributes: Attributes { mode, owner, group },
+        verbose,
+        strip: matches.get_flag("strip").then(|| {
+            matches
+                .get_one::<String>("strip-program")
+                .cloned()
+                .unwrap_or_else(|| "strip".to_string())
+        }),
+        compare,
+    };
+    let parents = matches.get_flag("parents");
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("file")
+        .map(|files| files.collect())
+        .unwrap_or_default();
+
+    let mut exit_code = 0;
+
+    if matches.get_flag("directory") {
+        if files.is_empty() {
+            usage_error("install", "missing operand");
+        }
+        for dir in files {
+            if let Err(err) = install_directory(Path::new(dir), &options.attributes, verbose) {
+                eprintln!("install: cannot create directory '{}': {}", dir, strerror(&err));
+                exit_code = 1;
+            }
+        }
+        process::exit(exit_code);
+    }
+
+    let (sources, target_dir): (&[&String], Option<&Path>) = match matches.get_one::<String>("target-directory") {
+        Some(dir) => (&files, Some(Path::new(dir))),
+        None => match files.len() {
+            0 => usage_error("install", "missing file operand"),
+            1 => usage_error("install", &format!("missing destination file operand after '{}'", files[0])),
+            2 if !Path::new(files[1]).is_dir() => {
+                let dest = Path::new(files[1]);
+                let result = match dest.parent().filter(|parent| parents && !parent.as_os_str().is_empty()) {
+                    Some(parent) => make_parents(parent, verbose)
+                        .map_err(context("cannot create directory", parent)),
+                    None => Ok(()),
+                };
+                if let Err(err) = result.and_then(|()| install_file(Path::new(files[0]), dest, &options)) {
+                    eprintln!("install: {}", strerror(&err));
+                    exit_code = 1;
+                }
+                process::exit(exit_code);
+            }
+            n => (&files[..n - 1], Some(Path::new(files[n - 1]))),
+        },
+    };
+
+    let target_dir = target_dir.unwrap();
+    if sources.is_empty() {
+        usage_error("install", "missing file operand");
+    }
+    if parents && matches.contains_id("target-directory") {
+        if let Err(err) = make_parents(target_dir, verbose) {
+            fail(
+                "install",
+                &format!("cannot create directory '{}': {}", target_dir.display(), strerror(&err)),
+            );
+        }
+    }
+    if !target_dir.is_dir() {
+        fail("install", &format!("target '{}' is not a directory", target_dir.display()));
+    }
+
+    for source in sources {
+        let source = Path::new(source);
+        let dest = match source.file_name() {
+            Some(name) => target_dir.join(name),
+            None => {
+                eprintln!("install: cannot install '{}': invalid file name", source.display());
+                exit_code = 1;
+                continue;
+            }
+        };
+        if let Err(err) = install_file(source, &dest, &options) {
+            eprintln!("install: {}", strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+// Copies SOURCE to a fresh DEST. The copy is made under a temporary name
+// next to DEST and only renamed over it once stripped and given its owner
+// and mode, so DEST is never seen half written or with the wrong
+// attributes, and a running binary being replaced keeps its old inode
+fn install_file(source: &Path, dest: &Path, options: &Options) -> io::Result<()> {
+    let mut input = File::open(source).map_err(context("cannot stat", source))?;
+    if input.metadata()?.is_dir() {
+        return Err(io::Error::other(format!("omitting directory '{}'", source.display())));
+    }
+
+    // Nothing is copied, so -v has nothing to report either
+    if options.compare && up_to_date(source, dest, &options.attributes) {
+        return Ok(());
+    }
+
+    let (temp, mut output) = create_temporary(dest).map_err(context("cannot create regular file", dest))?;
+    let result = io::copy(&mut input, &mut output)
+        .map_err(context("error writing", dest))
+        .and_then(|_| finish(&temp, output, dest, options));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result?;
+
+    if options.verbose {
+        println!("'{}' -> '{}'", source.display(), dest.display());
+    }
+
+    Ok(())
+}
+
+// Strips the copy at TEMP if asked to, sets its attributes and moves it to
+// DEST
+fn finish(temp: &Path, mut output: File, dest: &Path, options: &Options) -> io::Result<()> {
+    // strip writes a new file in place of the old one, so the attributes
+    // are set on whatever it leaves behind
+    if let Some(program) = &options.strip {
+        drop(output);
+        strip(program, temp)?;
+        output = File::open(temp)?;
+    }
+
+    // Ownership goes first: chown clears set-user-ID and set-group-ID bits
+    change_owner(&output, &options.attributes).map_err(context("cannot change ownership of", dest))?;
+    output
+        .set_permissions(fs::Permissions::from_mode(options.attributes.mode))
+        .map_err(context("cannot change permissions of", dest))?;
+    drop(output);
+
+    fs::rename(temp, dest).map_err(context("cannot create regular file", dest))
+}
+
+// An exclusively created file in DEST's directory, readable by nobody else
+// until it is finished
+fn create_temporary(dest: &Path) -> io::Result<(PathBuf, File)> {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    for attempt in 0..MAX_ATTEMPTS {
+        let temp = dest.with_file_name(format!(".{}.{}.{}", name, process::id(), attempt));
+        match OpenOptions::new().write(true).create_new(true).mode(0o600).open(&temp) {
+            Ok(file) => return Ok((temp, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Error::from(io::ErrorKind::AlreadyExists))
+}
+
+fn strip(program: &str, path: &Path) -> io::Result<()> {
+    let status = Process::new(program)
+        .arg(path)
+        .status()
+        .map_err(|err| io::Error::new(err.kind(), format!("cannot run '{}': {}", program, strerror(&err))))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("strip process terminated abnormally"))
+    }
+}
+
+// -C: whether DEST already is what installing SOURCE would make it, in
+// content, owner, group and mode
+fn up_to_date(source: &Path, dest: &Path, attributes: &Attributes) -> bool {
+    let (Ok(from), Ok(to)) = (fs::metadata(source), fs::symlink_metadata(dest)) else {
+        return false;
+    };
+    if !from.is_file() || !to.is_file() || from.len() != to.len() || to.mode() & 0o7777 != attributes.mode {
+        return false;
+    }
+
+    let uid = attributes.owner.unwrap_or_else(|| unsafe { libc::geteuid() });
+    let gid = attributes.group.unwrap_or_else(|| unsafe { libc::getegid() });
+    if to.uid() != uid || to.gid() != gid {
+        return false;
+    }
+
+    same_contents(source, dest).unwrap_or(false)
+}
+
+fn same_contents(first: &Path, second: &Path) -> io::Result<bool> {
+    let mut first = File::open(first)?;
+    let mut second = File::open(second)?;
+    let mut a = vec![0; 64 * 1024];
+    let mut b = vec![0; 64 * 1024];
+    loop {
+        let count = first.read(&mut a)?;
+        if count == 0 {
+            return Ok(second.read(&mut b[..1])? == 0);
+        }
+        second.read_exact(&mut b[..count])?;
+        if a[..count] != b[..count] {
+            return Ok(false);
+        }
+    }
+}
+
+// Prefixes an error with the action and file it concerns
+fn context<'a>(action: &'a str, path: &'a Path) -> impl Fn(io::Error) -> io::Error + 'a {
+    move |err| io::Error::new(err.kind(), format!("{} '{}': {}", action, path.display(), strerror(&err)))
+}
+
+fn install_directory(dir: &Path, attributes: &Attributes, verbose: bool) -> io::Result<()> {
+    // Missing parents are created like mkdir -p; only the named directory
+    // gets the requested attributes
+    make_parents(dir, verbose)?;
+
+    let handle = File::open(dir)?;
+    change_owner(&handle, attributes)?;
+    fs::set_permissions(dir, fs::Permissions::from_mode(attributes.mode))
+}
+
+// Creates DIR and whatever leads up to it, as mkdir -p does
+fn make_parents(dir: &Path, verbose: bool) -> io::Result<()> {
+    let mut created = PathBuf::new();
+    for component in dir.components() {
+        created.push(component);
+        match fs::DirBuilder::new().mode(DEFAULT_MODE).create(&created) {
+            Ok(()) => {
+                if verbose {
+                    println!("install: creating directory '{}'", created.display());
+                }
+            }
+            Err(_) if created.is_dir() => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+fn change_owner(file: &File, attributes: &Attributes) -> io::Result<()> {
+    if attributes.owner.is_none() && attributes.group.is_none() {
+        return Ok(());
+    }
+
+    // -1 leaves that id unchanged
+    let uid = attributes.owner.unwrap_or(libc::uid_t::MAX);
+    let gid = attributes.group.unwrap_or(libc::gid_t::MAX);
+
+    if unsafe { libc::fchown(file.as_raw_fd(), uid, gid) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Accepts a user name, falling back to a numeric id
+fn lookup_user(name: &str) -> Option<libc::uid_t> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwnam_r(c_name.as_ptr(), &mut pwd, buffer.as_mut_ptr(), buffer.len(), &mut result);
+        if !result.is_null() {
+            return Some(pwd.pw_uid);
+        }
+    }
+
+    name.parse().ok()
+}
+
+// Accepts a group name, falling back to a numeric id
+fn lookup_group(name: &str) -> Option<libc::gid_t> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut grp: libc::group = mem::zeroed();
+        let mut result: *mut libc::group = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getgrnam_r(c_name.as_ptr(), &mut grp, buffer.as_mut_ptr(), buffer.len(), &mut result);
+        if !result.is_null() {
+            return Some(grp.gr_gid);
+        }
+    }
+
+    name.parse().ok()
+}
+
+// Accepts an octal mode or a comma separated list of symbolic clauses such
+// as "u=rw,go-w"; clauses without a user class are limited by the umask
+fn parse_mode(spec: &str, base: u32, umask: u32) -> Option<u32> {
+    if !spec.is_empty() && spec.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return u32::from_str_radix(spec, 8).ok().filter(|&mode| mode <= 0o7777);
+    }
+
+    let mut mode = base;
+
+    for clause in spec.split(',') {
+        let who_len = clause.find(|c| !"ugoa".contains(c)).unwrap_or(clause.len());
+        let (who, mut rest) = clause.split_at(who_len);
+
+        let (mask, limit) = if who.is_empty() {
+            (0o7777, !umask)
+        } else {
+            let mask = who.chars().fold(0, |mask, c| {
+                mask | match c {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o0007,
+                    _ => 0o7777,
+                }
+            });
+            (mask, 0o7777)
+        };
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        while let Some(op) = rest.chars().next().filter(|c| "+-=".contains(*c)) {
+            let perms_len = rest[1..].find(|c| "+-=".contains(c)).map_or(rest.len(), |i| i + 1);
+            let mut bits = 0;
+            for c in rest[1..perms_len].chars() {
+                bits |= match c {
+                    'r' => 0o444,
+                    'w' => 0o222,
+                    'x' => 0o111,
+                    's' => 0o6000,
+                    't' => 0o1000,
+                    _ => return None,
+                };
+            }
+            let bits = bits & mask & limit;
+
+            match op {
+                '+' => mode |= bits,
+                '-' => mode &= !bits,
+                _ => mode = (mode & !mask) | bits,
+            }
+            rest = &rest[perms_len..];
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+
+    Some(mode)
+}
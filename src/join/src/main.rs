@@ -0,0 +1,566 @@
+// ASD CoreUtils - join
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+struct Record {
+    number: usize,
+    line: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum OrderCheck {
+    // Only once some line has turned out to be unpairable
+    Default,
+    // --check-order: the first misplaced line is fatal
+    Enabled,
+    // --nocheck-order
+    Disabled,
+}
+
+// One item of -o: the join field, or field FIELD of file FILE
+#[derive(Clone, Copy)]
+enum OutputField {
+    Key,
+    Field(usize, usize),
+}
+
+enum Format {
+    // Every field of each line, the join field first
+    Lines,
+    // -o auto: as many fields as the first line of each file has
+    Auto,
+    List(Vec<OutputField>),
+}
+
+struct Settings {
+    separator: u8,
+    // -a and -v: the files whose unpairable lines are printed
+    unpaired: [bool; 2],
+    // Cleared by -v
+    paired: bool,
+    empty: Option<Vec<u8>>,
+    format: Format,
+    header: bool,
+    order: OrderCheck,
+}
+
+// One sorted input, read a group of equal-keyed lines at a time
+struct Input {
+    name: String,
+    reader: Box<dyn BufRead>,
+    field: usize,
+    separator: Option<u8>,
+    pending: Option<Record>,
+    line_number: usize,
+    // The key of the line read last, to check the order against
+    previous: Option<Vec<u8>>,
+    checked: usize,
+    checking: bool,
+    fatal: bool,
+    disordered: bool,
+}
+
+impl Input {
+    fn open(path: &str, field: usize, separator: Option<u8>, order: OrderCheck) -> io::Result<Self> {
+        let reader: Box<dyn BufRead> = if path == "-" {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+
+        Ok(Input {
+            name: path.to_string(),
+            reader,
+            field,
+            separator,
+            pending: None,
+            line_number: 0,
+            previous: None,
+            checked: 0,
+            checking: order == OrderCheck::Enabled,
+            fatal: order == OrderCheck::Enabled,
+            disordered: false,
+        })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut line = Vec::new();
+        if self.reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        self.line_number += 1;
+
+        Ok(Some(Record {
+            number: self.line_number,
+            fields: split_fields(&line, self.separator),
+            line,
+        }))
+    }
+
+    // Reports the first line whose key sorts before the one above it. A
+    // line read ahead is checked when it is taken into a group, or when a
+    // pairing looks past the group, as GNU join would have read it then
+    fn check_order(&mut self, record: &Record) -> io::Result<()> {
+        if record.number <= self.checked {
+            return Ok(());
+        }
+        self.checked = record.number;
+        let key = self.key(record).to_vec();
+        let misplaced = matches!(&self.previous, Some(previous) if *previous > key);
+        if misplaced && self.checking && !self.disordered {
+            let message = format!(
+                "{}:{}: is not sorted: {}",
+                self.name,
+                record.number,
+                String::from_utf8_lossy(&record.line)
+            );
+            if self.fatal {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+            eprintln!("join: {}", message);
+            self.disordered = true;
+        }
+        self.previous = Some(key);
+        Ok(())
+    }
+
+    fn check_pending(&mut self) -> io::Result<()> {
+        match self.pending.take() {
+            Some(record) => {
+                let result = self.check_order(&record);
+                self.pending = Some(record);
+                result
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn key<'a>(&self, record: &'a Record) -> &'a [u8] {
+        record.fields.get(self.field).map(|f| f.as_slice()).unwrap_or(b"")
+    }
+
+    fn next_group(&mut self) -> io::Result<Vec<Record>> {
+        let first = match self.pending.take() {
+            Some(record) => record,
+            None => match self.read_record()? {
+                Some(record) => record,
+                None => return Ok(Vec::new()),
+            },
+        };
+
+        self.check_order(&first)?;
+        let mut group = vec![first];
+        while let Some(record) = self.read_record()? {
+            if self.key(&record) == self.key(&group[0]) {
+                self.check_order(&record)?;
+                group.push(record);
+            } else {
+                self.pending = Some(record);
+                break;
+            }
+        }
+
+        Ok(group)
+    }
+}
+
+fn main() {
+    let matches = Command::new("join")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils join - join lines of two files on a common field")
+        .arg(
+            Arg::new("unpaired")
+                .short('a')
+                .value_name("FILENUM")
+                .action(ArgAction::Append)
+                .help("Also print unpairable lines from file FILENUM, where FILENUM is 1 or 2"),
+        )
+        .arg(
+            Arg::new("only-unpaired")
+                .short('v')
+                .value_name("FILENUM")
+                .action(ArgAction::Append)
+                .help("Like -a FILENUM, but suppress joined output lines"),
+        )
+        .arg(
+            Arg::new("empty")
+                .short('e')
+                .value_name("EMPTY")
+                .help("Replace missing input fields with EMPTY"),
+        )
+        .arg(
+            Arg::new("format")
+                .short('o')
+                .value_name("FORMAT")
+                .action(ArgAction::Append)
+                .help("Obey FORMAT while constructing output line"),
+        )
+        .arg(
+            Arg::new("field1")
+                .short('1')
+                .value_name("FIELD")
+                .help("Join on this FIELD of file 1"),
+        )
+        .arg(
+            Arg::new("field2")
+                .short('2')
+                .value_name("FIELD")
+                .help("Join on this FIELD of file 2"),
+        )
+        .arg(
+            Arg::new("field")
+                .short('j')
+                .value_name("FIELD")
+                .help("Equivalent to '-1 FIELD -2 FIELD'"),
+        )
+        .arg(
+            Arg::new("separator")
+                .short('t')
+                .value_name("CHAR")
+                .help("Use CHAR as input and output field separator"),
+        )
+        .arg(
+            Arg::new("check-order")
+                .long("check-order")
+                .action(ArgAction::SetTrue)
+                .overrides_with("nocheck-order")
+                .help("Check that the input is correctly sorted, even if all input lines are pairable"),
+        )
+        .arg(
+            Arg::new("nocheck-order")
+                .long("nocheck-order")
+                .action(ArgAction::SetTrue)
+                .overrides_with("check-order")
+                .help("Do not check that the input is correctly sorted"),
+        )
+        .arg(
+            Arg::new("header")
+                .long("header")
+                .action(ArgAction::SetTrue)
+                .help("Treat the first line in each file as field headers, print them without trying to pair them"),
+        )
+        .arg(
+            Arg::new("file1")
+                .value_name("FILE1")
+                .help("First input file, or - for standard input")
+                .required(true),
+        )
+        .arg(
+            Arg::new("file2")
+                .value_name("FILE2")
+                .help("Second input file, or - for standard input")
+                .required(true),
+        )
+        .after_help(
+            "Unless -t CHAR is given, leading blanks separate fields and are ignored,\n\
+             else fields are separated by CHAR. Any FIELD is a field number counted\n\
+             from 1. FORMAT is one or more comma or blank separated specifications,\n\
+             each being 'FILENUM.FIELD' or '0'. Default FORMAT outputs the join field,\n\
+             the remaining fields from FILE1, the remaining fields from FILE2, all\n\
+             separated by CHAR. If FORMAT is the keyword 'auto', then the first\n\
+             line of each file determines the number of fields output for each line.",
+        )
+        .get_matches();
+
+    let shared = matches.get_one::<String>("field");
+    let field1 = parse_field(matches.get_one::<String>("field1").or(shared));
+    let field2 = parse_field(matches.get_one::<String>("field2").or(shared));
+
+    let separator = matches.get_one::<String>("separator").map(|sep| {
+        let bytes = sep.as_bytes();
+        if bytes.len() != 1 {
+            fail("join", &format!("multi-character tab '{}'", sep));
+        }
+        bytes[0]
+    });
+
+    let mut unpaired = [false; 2];
+    for file in matches.get_many::<String>("unpaired").into_iter().flatten() {
+        unpaired[parse_file_number(file)] = true;
+    }
+    let mut paired = true;
+    for file in matches.get_many::<String>("only-unpaired").into_iter().flatten() {
+        unpaired[parse_file_number(file)] = true;
+        paired = false;
+    }
+
+    let mut format = Format::Lines;
+    for text in matches.get_many::<String>("format").into_iter().flatten() {
+        if text == "auto" {
+            format = Format::Auto;
+            continue;
+        }
+        let fields = parse_format(text);
+        match &mut format {
+            Format::List(list) => list.extend(fields),
+            _ => format = Format::List(fields),
+        }
+    }
+
+    let order = if matches.get_flag("check-order") {
+        OrderCheck::Enabled
+    } else if matches.get_flag("nocheck-order") {
+        OrderCheck::Disabled
+    } else {
+        OrderCheck::Default
+    };
+
+    let settings = Settings {
+        separator: separator.unwrap_or(b' '),
+        unpaired,
+        paired,
+        empty: matches
+            .get_one::<String>("empty")
+            .map(|empty| empty.as_bytes().to_vec()),
+        format,
+        header: matches.get_flag("header"),
+        order,
+    };
+
+    let file1 = matches.get_one::<String>("file1").unwrap();
+    let file2 = matches.get_one::<String>("file2").unwrap();
+    if file1 == "-" && file2 == "-" {
+        fail("join", "both files cannot be standard input");
+    }
+
+    let open = |path: &str, field: usize| match Input::open(path, field, separator, order) {
+        Ok(input) => input,
+        Err(err) => fail("join", &format!("{}: {}", path, strerror(&err))),
+    };
+
+    let mut input1 = open(file1, field1);
+    let mut input2 = open(file2, field2);
+
+    if let Err(err) = join(&mut input1, &mut input2, settings) {
+        fail("join", &strerror(&err));
+    }
+    if input1.disordered || input2.disordered {
+        fail("join", "input is not in sorted order");
+    }
+}
+
+// Converts a 1-based field number from the command line to an index
+fn parse_field(value: Option<&String>) -> usize {
+    match value {
+        None => 0,
+        Some(text) => parse_field_number(text),
+    }
+}
+
+fn parse_field_number(text: &str) -> usize {
+    match text.parse::<usize>() {
+        Ok(field) if field > 0 => field - 1,
+        _ => fail("join", &format!("invalid field number: '{}'", text)),
+    }
+}
+
+// The FILENUM of -a and -v, as an index
+fn parse_file_number(text: &str) -> usize {
+    match text {
+        "1" => 0,
+        "2" => 1,
+        _ => fail("join", &format!("invalid field number: '{}'", text)),
+    }
+}
+
+// A -o list: '0' and FILENUM.FIELD items, separated by commas or blanks
+fn parse_format(text: &str) -> Vec<OutputField> {
+    text.split([',', ' ', '\t'])
+        .map(|item| {
+            if item == "0" {
+                return OutputField::Key;
+            }
+            match item.split_once('.') {
+                Some(("1", field)) => OutputField::Field(0, parse_field_number(field)),
+                Some(("2", field)) => OutputField::Field(1, parse_field_number(field)),
+                _ => fail("join", &format!("invalid file number in field spec: '{}'", item)),
+            }
+        })
+        .collect()
+}
+
+fn split_fields(line: &[u8], separator: Option<u8>) -> Vec<Vec<u8>> {
+    match separator {
+        Some(sep) => line.split(|&b| b == sep).map(|f| f.to_vec()).collect(),
+        None => line
+            .split(|&b| b == b' ' || b == b'\t')
+            .filter(|f| !f.is_empty())
+            .map(|f| f.to_vec())
+            .collect(),
+    }
+}
+
+fn join(input1: &mut Input, input2: &mut Input, mut settings: Settings) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let (header1, header2) = if settings.header {
+        (input1.read_record()?, input2.read_record()?)
+    } else {
+        (None, None)
+    };
+    let mut group1 = input1.next_group()?;
+    let mut group2 = input2.next_group()?;
+
+    // -o auto takes its field counts from the first line of each file
+    if let Format::Auto = settings.format {
+        let count = |header: &Option<Record>, group: &[Record]| {
+            header.as_ref().or(group.first()).map_or(0, |record| record.fields.len())
+        };
+        let mut list = vec![OutputField::Key];
+        list.extend(
+            (0..count(&header1, &group1))
+                .filter(|&index| index != input1.field)
+                .map(|index| OutputField::Field(0, index)),
+        );
+        list.extend(
+            (0..count(&header2, &group2))
+                .filter(|&index| index != input2.field)
+                .map(|index| OutputField::Field(1, index)),
+        );
+        settings.format = Format::List(list);
+    }
+
+    if header1.is_some() || header2.is_some() {
+        write_joined(&mut out, &settings, input1, header1.as_ref(), input2, header2.as_ref())?;
+    }
+
+    while !group1.is_empty() && !group2.is_empty() {
+        match input1.key(&group1[0]).cmp(input2.key(&group2[0])) {
+            Ordering::Less => {
+                saw_unpairable(input1, input2, &settings);
+                if settings.unpaired[0] {
+                    for record1 in &group1 {
+                        write_joined(&mut out, &settings, input1, Some(record1), input2, None)?;
+                    }
+                }
+                group1 = input1.next_group()?;
+            }
+            Ordering::Greater => {
+                saw_unpairable(input1, input2, &settings);
+                if settings.unpaired[1] {
+                    for record2 in &group2 {
+                        write_joined(&mut out, &settings, input1, None, input2, Some(record2))?;
+                    }
+                }
+                group2 = input2.next_group()?;
+            }
+            Ordering::Equal => {
+                input1.check_pending()?;
+                input2.check_pending()?;
+                if settings.paired {
+                    for record1 in &group1 {
+                        for record2 in &group2 {
+                            write_joined(
+                                &mut out,
+                                &settings,
+                                input1,
+                                Some(record1),
+                                input2,
+                                Some(record2),
+                            )?;
+                        }
+                    }
+                }
+                group1 = input1.next_group()?;
+                group2 = input2.next_group()?;
+            }
+        }
+    }
+
+    // Whatever is left of either file has nothing to pair with
+    while !group1.is_empty() {
+        saw_unpairable(input1, input2, &settings);
+        if settings.unpaired[0] {
+            for record1 in &group1 {
+                write_joined(&mut out, &settings, input1, Some(record1), input2, None)?;
+            }
+        }
+        group1 = input1.next_group()?;
+    }
+    while !group2.is_empty() {
+        saw_unpairable(input1, input2, &settings);
+        if settings.unpaired[1] {
+            for record2 in &group2 {
+                write_joined(&mut out, &settings, input1, None, input2, Some(record2))?;
+            }
+        }
+        group2 = input2.next_group()?;
+    }
+
+    out.flush()
+}
+
+// By default the order only matters, and is only checked, once some line
+// goes unpaired
+fn saw_unpairable(input1: &mut Input, input2: &mut Input, settings: &Settings) {
+    if settings.order == OrderCheck::Default {
+        input1.checking = true;
+        input2.checking = true;
+    }
+}
+
+// Prints one output line for a pair of lines, or for a line that has no
+// partner in the other file
+fn write_joined(
+    out: &mut impl Write,
+    settings: &Settings,
+    input1: &Input,
+    record1: Option<&Record>,
+    input2: &Input,
+    record2: Option<&Record>,
+) -> io::Result<()> {
+    let separator = settings.separator;
+    let key = match (record1, record2) {
+        (Some(record1), _) => input1.key(record1),
+        (None, Some(record2)) => input2.key(record2),
+        (None, None) => b"",
+    };
+
+    let list = match &settings.format {
+        Format::List(list) => list,
+        _ => {
+            // The join field followed by the remaining fields of each line
+            out.write_all(key)?;
+            for (input, record) in [(input1, record1), (input2, record2)] {
+                let Some(record) = record else { continue };
+                for (index, field) in record.fields.iter().enumerate() {
+                    if index != input.field {
+                        out.write_all(&[separator])?;
+                        out.write_all(field)?;
+                    }
+                }
+            }
+            return out.write_all(b"\n");
+        }
+    };
+
+    let empty = settings.empty.as_deref().unwrap_or(b"");
+    for (position, item) in list.iter().enumerate() {
+        if position > 0 {
+            out.write_all(&[separator])?;
+        }
+        let field = match *item {
+            OutputField::Key => Some(key),
+            OutputField::Field(file, index) => [record1, record2][file]
+                .and_then(|record| record.fields.get(index))
+                .map(|field| field.as_slice()),
+        };
+        match field {
+            Some(field) if !field.is_empty() || settings.empty.is_none() => out.write_all(field)?,
+            _ => out.write_all(empty)?,
+        }
+    }
+    out.write_all(b"\n")
+}
@@ -0,0 +1,189 @@
+// ASD CoreUtils - kill
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::signal::{parse_signal, signal_name, signal_number};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::io;
+use std::process;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let mut signal = libc::SIGTERM;
+    let mut signal_given = false;
+
+    // "kill -9 PID" and "kill -KILL PID" can't be described to clap, so a
+    // leading signal operand is taken off the argument list before parsing
+    if let Some(spec) = args.get(1).and_then(|arg| arg.strip_prefix('-')) {
+        if let Some(sig) = parse_signal(spec) {
+            signal = sig;
+            signal_given = true;
+            args.remove(1);
+        } else if looks_like_signal(spec) {
+            fail("kill", &format!("invalid signal: {}", spec));
+        }
+    }
+
+    let matches = Command::new("kill")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils kill - send a signal to processes")
+        .override_usage(
+            "kill [-s SIGNAL | -SIGNAL] PID...\n       \
+             kill -l [SIGNAL]...\n       \
+             kill -t [SIGNAL]...",
+        )
+        .arg(
+            Arg::new("signal")
+                .short('s')
+                .long("signal")
+                .value_name("SIGNAL")
+                .help("Specify the signal to send, by name or number"),
+        )
+        .arg(
+            Arg::new("list")
+                .short('l')
+                .long("list")
+                .action(ArgAction::SetTrue)
+                .help("List signal names, or convert signal names to and from numbers"),
+        )
+        .arg(
+            Arg::new("table")
+                .short('t')
+                .visible_short_alias('L')
+                .long("table")
+                .action(ArgAction::SetTrue)
+                .help("List signal names and numbers in a table"),
+        )
+        .arg(
+            Arg::new("pid")
+                .value_name("PID")
+                .help("Processes to signal; a negative PID names a whole process group")
+                .required_unless_present_any(["list", "table"])
+                .allow_negative_numbers(true)
+                .num_args(1..),
+        )
+        .get_matches_from(args);
+
+    let table = matches.get_flag("table");
+    if matches.get_flag("list") || table {
+        if signal_given || matches.contains_id("signal") {
+            usage_error("kill", "cannot combine signal with -l or -t options");
+        }
+        let operands: Vec<&String> = matches
+            .get_many::<String>("pid")
+            .map(|values| values.collect())
+            .unwrap_or_default();
+        // The list is often piped to head or grep
+        unsafe {
+            libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+        }
+        process::exit(list_signals(&operands, table));
+    }
+
+    if let Some(spec) = matches.get_one::<String>("signal") {
+        signal = match parse_signal(spec) {
+            Some(sig) => sig,
+            None => fail("kill", &format!("invalid signal: {}", spec)),
+        };
+    }
+
+    let mut exit_code = 0;
+
+    for target in matches.get_many::<String>("pid").unwrap() {
+        let pid: libc::pid_t = match target.parse() {
+            Ok(pid) => pid,
+            Err(_) => {
+                eprintln!("kill: failed to parse argument: '{}'", target);
+                exit_code = 1;
+                continue;
+            }
+        };
+
+        if unsafe { libc::kill(pid, signal) } != 0 {
+            eprintln!(
+                "kill: ({}) - {}",
+                pid,
+                strerror(&io::Error::last_os_error())
+            );
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+// -l and -t: every signal, or the given ones translated, names to numbers
+// and numbers to names. A number may also be the exit status of a process
+// killed by a signal, as a shell reports it. Returns the exit status
+fn list_signals(operands: &[&String], table: bool) -> i32 {
+    let name_width = (1..=libc::SIGRTMAX())
+        .filter_map(signal_name)
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0);
+    let number_width = libc::SIGRTMAX().to_string().len();
+    let row = |number: i32, name: &str| {
+        println!(
+            "{:>nw$} {:<w$}",
+            number,
+            name,
+            nw = number_width,
+            w = name_width
+        );
+    };
+
+    if operands.is_empty() {
+        for number in 1..=libc::SIGRTMAX() {
+            if let Some(name) = signal_name(number) {
+                if table {
+                    row(number, &name);
+                } else {
+                    println!("{}", name);
+                }
+            }
+        }
+        return 0;
+    }
+
+    let mut exit_code = 0;
+    for operand in operands {
+        let by_number = operand.starts_with(|c: char| c.is_ascii_digit());
+        let number = if by_number {
+            operand
+                .parse::<i32>()
+                .ok()
+                .map(|status| match status & 0x7f {
+                    0 | 0x7f => status,
+                    signal => signal,
+                })
+        } else {
+            signal_number(operand)
+        };
+        let Some((number, name)) = number.and_then(|n| Some((n, signal_name(n)?))) else {
+            eprintln!("kill: invalid signal: {}", operand);
+            exit_code = 1;
+            continue;
+        };
+
+        if table {
+            row(number, &name);
+        } else if by_number {
+            println!("{}", name);
+        } else {
+            println!("{}", number);
+        }
+    }
+    exit_code
+}
+
+// Distinguishes a mistyped signal ("-TREM") from a real option ("-s")
+fn looks_like_signal(spec: &str) -> bool {
+    spec.len() > 1
+        && !spec.starts_with('-')
+        && spec
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
@@ -0,0 +1,51 @@
+// ASD CoreUtils - link
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, Command};
+use std::ffi::CString;
+use std::io;
+
+fn main() {
+    let matches = Command::new("link")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils link - call the link function to create a link named FILE2 to an existing FILE1")
+        .override_usage("link FILE1 FILE2")
+        .arg(
+            Arg::new("operands")
+                .value_name("FILE")
+                .help("Existing file and the name of the new link")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let operands: Vec<&String> = matches
+        .get_many::<String>("operands")
+        .map(|operands| operands.collect())
+        .unwrap_or_default();
+
+    // Exactly two operands, no more and no fewer, as POSIX specifies
+    match operands.len() {
+        0 => usage_error("link", "missing operand"),
+        1 => usage_error("link", &format!("missing operand after '{}'", operands[0])),
+        2 => {}
+        _ => usage_error("link", &format!("extra operand '{}'", operands[2])),
+    }
+
+    if let Err(err) = link(operands[0], operands[1]) {
+        fail("link", &format!("cannot create link '{}' to '{}': {}", operands[1], operands[0], strerror(&err)));
+    }
+}
+
+fn link(existing: &str, new: &str) -> io::Result<()> {
+    let existing = CString::new(existing).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    let new = CString::new(new).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    if unsafe { libc::link(existing.as_ptr(), new.as_ptr()) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
@@ -0,0 +1,522 @@
+// ASD CoreUtils - ln
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::metadata::metadata;
+use clap::{Arg, ArgAction, Command};
+use std::ffi::{CString, OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, BufRead, Read, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+
+const NAME_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+const MAX_ATTEMPTS: usize = 100;
+
+struct Options {
+    symbolic: bool,
+    force: bool,
+    interactive: bool,
+    relative: bool,
+    // -L: hard link to what a symbolic link points to, not the link
+    logical: bool,
+    verbose: bool,
+}
+
+fn main() {
+    let matches = Command::new("ln")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils ln - make links between files")
+        .override_usage(
+            "ln [OPTION]... TARGET LINK_NAME\n       \
+             ln [OPTION]... TARGET\n       \
+             ln [OPTION]... TARGET... DIRECTORY\n       \
+             ln [OPTION]... -t DIRECTORY TARGET...",
+        )
+        .arg(
+            Arg::new("force")
+                .short('f')
+                .long("force")
+                .action(ArgAction::SetTrue)
+                .overrides_with("interactive")
+                .help("Replace existing destination files"),
+        )
+        .arg(
+            Arg::new("interactive")
+                .short('i')
+                .long("interactive")
+                .action(ArgAction::SetTrue)
+                .overrides_with("force")
+                .help("Prompt whether to remove destinations"),
+        )
+        .arg(
+            Arg::new("logical")
+                .short('L')
+                .long("logical")
+                .action(ArgAction::SetTrue)
+                .overrides_with("physical")
+                .help("Dereference TARGETs that are symbolic links"),
+        )
+        .arg(
+            Arg::new("physical")
+                .short('P')
+                .long("physical")
+                .action(ArgAction::SetTrue)
+                .overrides_with("logical")
+                .help("Make hard links directly to symbolic links (the default)"),
+        )
+        .arg(
+            Arg::new("no-dereference")
+                .short('n')
+                .long("no-dereference")
+                .action(ArgAction::SetTrue)
+                .help("Treat LINK_NAME as a normal file if it is a symbolic link to a directory"),
+        )
+        .arg(
+            Arg::new("relative")
+                .short('r')
+                .long("relative")
+                .action(ArgAction::SetTrue)
+                .help("With -s, create links relative to link location"),
+        )
+        .arg(
+            Arg::new("symbolic")
+                .short('s')
+                .long("symbolic")
+                .action(ArgAction::SetTrue)
+                .help("Make symbolic links instead of hard links"),
+        )
+        .arg(
+            Arg::new("target-directory")
+                .short('t')
+                .long("target-directory")
+                .value_name("DIRECTORY")
+                .help("Specify the DIRECTORY in which to create the links"),
+        )
+        .arg(
+            Arg::new("no-target-directory")
+                .short('T')
+                .long("no-target-directory")
+                .action(ArgAction::SetTrue)
+                .help("Treat LINK_NAME as a normal file always"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .help("Print name of each linked file"),
+        )
+        .arg(Arg::new("operands").value_name("TARGET").num_args(0..))
+        .after_help(
+            "With -f, an existing LINK_NAME is replaced atomically: the new link is made\n\
+             under a temporary name beside it and renamed over it, so 'ln -sfn' never\n\
+             leaves a moment with no link.",
+        )
+        .get_matches();
+
+    let options = Options {
+        symbolic: matches.get_flag("symbolic"),
+        force: matches.get_flag("force"),
+        interactive: matches.get_flag("interactive"),
+        relative: matches.get_flag("relative"),
+        logical: matches.get_flag("logical"),
+        verbose: matches.get_flag("verbose"),
+    };
+    let no_dereference = matches.get_flag("no-dereference");
+    let no_target_directory = matches.get_flag("no-target-directory");
+
+    if options.relative && !options.symbolic {
+        fail("ln", "cannot do --relative without --symbolic");
+    }
+
+    let mut operands: Vec<OsString> = matches
+        .get_many::<String>("operands")
+        .map(|values| values.map(OsString::from).collect())
+        .unwrap_or_default();
+
+    // Each target with the name of the link to make for it
+    let links: Vec<(OsString, PathBuf)> =
+        if let Some(dir) = matches.get_one::<String>("target-directory") {
+            if no_target_directory {
+                fail(
+                    "ln",
+                    "cannot combine --target-directory and --no-target-directory",
+                );
+            }
+            match fs::metadata(dir) {
+                Ok(metadata) if metadata.is_dir() => {}
+                Ok(_) => fail("ln", &format!("target '{}' is not a directory", dir)),
+                Err(err) => fail(
+                    "ln",
+                    &format!("failed to access '{}': {}", dir, strerror(&err)),
+                ),
+            }
+            if operands.is_empty() {
+                usage_error("ln", "missing file operand");
+            }
+            operands
+                .into_iter()
+                .map(|target| {
+                    let link = in_directory(Path::new(dir), &target);
+                    (target, link)
+                })
+                .collect()
+        } else if no_target_directory {
+            match operands.len() {
+                0 => usage_error("ln", "missing file operand"),
+                1 => usage_error(
+                    "ln",
+                    &format!(
+                        "missing destination file operand after '{}'",
+                        operands[0].to_string_lossy()
+                    ),
+                ),
+                2 => {}
+                _ => usage_error(
+                    "ln",
+                    &format!("extra operand '{}'", operands[2].to_string_lossy()),
+                ),
+            }
+            let link = PathBuf::from(operands.pop().unwrap());
+            vec![(operands.pop().unwrap(), link)]
+        } else {
+            match operands.len() {
+                0 => usage_error("ln", "missing file operand"),
+                // A lone target is linked into the current directory
+                1 => {
+                    let link = in_directory(Path::new("."), &operands[0]);
+                    vec![(operands.pop().unwrap(), link)]
+                }
+                count => {
+                    let last = PathBuf::from(operands.pop().unwrap());
+                    // -n: a symbolic link to a directory is a name to replace,
+                    // not a directory to link into
+                    let is_dir = if no_dereference && is_symlink(&last) {
+                        Ok(false)
+                    } else {
+                        fs::metadata(&last).map(|metadata| metadata.is_dir())
+                    };
+                    match is_dir {
+                        Ok(true) => operands
+                            .into_iter()
+                            .map(|target| {
+                                let link = in_directory(&last, &target);
+                                (target, link)
+                            })
+                            .collect(),
+                        _ if count == 2 => vec![(operands.pop().unwrap(), last)],
+                        Ok(false) => fail(
+                            "ln",
+                            &format!("target '{}': Not a directory", last.display()),
+                        ),
+                        Err(err) => fail(
+                            "ln",
+                            &format!("target '{}': {}", last.display(), strerror(&err)),
+                        ),
+                    }
+                }
+            }
+        };
+
+    let mut ok = true;
+    for (target, link) in &links {
+        ok &= make_link(target, link, &options);
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+// DIR/NAME, where NAME is TARGET's last component ("e/sub/" gives "sub")
+fn in_directory(dir: &Path, target: &OsStr) -> PathBuf {
+    let bytes = target.as_bytes();
+    let trimmed = match bytes.iter().rposition(|&b| b != b'/') {
+        Some(end) => &bytes[..=end],
+        None => bytes,
+    };
+    let name = match trimmed.iter().rposition(|&b| b == b'/') {
+        Some(slash) => &trimmed[slash + 1..],
+        None => trimmed,
+    };
+
+    let mut link = dir.as_os_str().as_bytes().to_vec();
+    if !link.ends_with(b"/") {
+        link.push(b'/');
+    }
+    link.extend_from_slice(name);
+    PathBuf::from(OsString::from_vec(link))
+}
+
+fn make_link(target: &OsStr, link: &Path, options: &Options) -> bool {
+    let target_name = target.to_string_lossy();
+    let link_name = link.display();
+
+    if !options.symbolic {
+        match metadata(target, options.logical) {
+            Ok(metadata) if metadata.is_dir() => {
+                eprintln!("ln: {}: hard link not allowed for directory", target_name);
+                return false;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("ln: failed to access '{}': {}", target_name, strerror(&err));
+                return false;
+            }
+        }
+    }
+
+    let contents = if options.relative {
+        relative_target(Path::new(target), link)
+    } else {
+        PathBuf::from(target)
+    };
+
+    let existing = fs::symlink_metadata(link).ok();
+    let replace = match &existing {
+        Some(existing) if options.force || options.interactive => {
+            if is_same_entry(Path::new(target), link, existing, options.symbolic) {
+                eprintln!(
+                    "ln: '{}' and '{}' are the same file",
+                    target_name, link_name
+                );
+                return false;
+            }
+            if options.interactive && !confirm(&format!("ln: replace '{}'? ", link_name)) {
+                return true;
+            }
+            if existing.is_dir() {
+                eprintln!("ln: {}: cannot overwrite directory", link_name);
+                return false;
+            }
+            true
+        }
+        _ => false,
+    };
+
+    let result = if replace {
+        replace_link(&contents, link, options)
+    } else {
+        create_link(&contents, link, options)
+    };
+
+    if let Err(err) = result {
+        if options.symbolic {
+            if target.is_empty() {
+                eprintln!(
+                    "ln: failed to create symbolic link '{}' -> '{}': {}",
+                    link_name,
+                    contents.display(),
+                    strerror(&err)
+                );
+            } else {
+                eprintln!(
+                    "ln: failed to create symbolic link '{}': {}",
+                    link_name,
+                    strerror(&err)
+                );
+            }
+        } else {
+            match err.raw_os_error() {
+                Some(libc::EDQUOT | libc::EEXIST | libc::ENOSPC | libc::EROFS) => {
+                    eprintln!(
+                        "ln: failed to create hard link '{}': {}",
+                        link_name,
+                        strerror(&err)
+                    )
+                }
+                _ => eprintln!(
+                    "ln: failed to create hard link '{}' => '{}': {}",
+                    link_name,
+                    target_name,
+                    strerror(&err)
+                ),
+            }
+        }
+        return false;
+    }
+
+    if options.verbose {
+        let arrow = if options.symbolic { "->" } else { "=>" };
+        println!("'{}' {} '{}'", link_name, arrow, contents.display());
+    }
+    true
+}
+
+// Whether LINK is the very entry TARGET names, which -f would otherwise
+// remove before linking to it
+fn is_same_entry(target: &Path, link: &Path, existing: &fs::Metadata, symbolic: bool) -> bool {
+    let target_metadata = match fs::symlink_metadata(target) {
+        Ok(metadata) => metadata,
+        Err(_) => return false,
+    };
+    if (target_metadata.dev(), target_metadata.ino()) != (existing.dev(), existing.ino()) {
+        return false;
+    }
+    // Hard links to one file may be replaced by each other; for symbolic
+    // links only the same name in the same directory matters
+    if !symbolic && target.file_name() != link.file_name() {
+        return false;
+    }
+    let parent = |path: &Path| {
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        fs::canonicalize(dir.unwrap_or(Path::new(".")))
+    };
+    matches!((parent(target), parent(link)), (Ok(a), Ok(b)) if a == b)
+        && target.file_name() == link.file_name()
+}
+
+fn confirm(prompt: &str) -> bool {
+    eprint!("{}", prompt);
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    answer.starts_with('y') || answer.starts_with('Y')
+}
+
+fn create_link(contents: &Path, link: &Path, options: &Options) -> io::Result<()> {
+    if options.symbolic {
+        std::os::unix::fs::symlink(contents, link)
+    } else {
+        let from = c_path(contents)?;
+        let to = c_path(link)?;
+        let flags = if options.logical {
+            libc::AT_SYMLINK_FOLLOW
+        } else {
+            0
+        };
+        if unsafe {
+            libc::linkat(
+                libc::AT_FDCWD,
+                from.as_ptr(),
+                libc::AT_FDCWD,
+                to.as_ptr(),
+                flags,
+            )
+        } == 0
+        {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+// Makes the link under a temporary name in LINK's directory and renames it
+// over LINK, so that LINK always exists, as the old file or the new link
+fn replace_link(contents: &Path, link: &Path, options: &Options) -> io::Result<()> {
+    let dir = link
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut random = File::open("/dev/urandom")?;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let temporary = dir.join(format!(".ln{}", random_name(&mut random, 6)?));
+        match create_link(contents, &temporary, options) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+
+        if let Err(err) = fs::rename(&temporary, link) {
+            let _ = fs::remove_file(&temporary);
+            return Err(err);
+        }
+        // Renaming one hard link of a file over another does nothing and
+        // leaves both names, so the temporary one may still be there
+        if !options.symbolic {
+            let _ = fs::remove_file(&temporary);
+        }
+        return Ok(());
+    }
+
+    Err(io::Error::from(io::ErrorKind::AlreadyExists))
+}
+
+fn random_name(random: &mut File, length: usize) -> io::Result<String> {
+    let mut bytes = vec![0u8; length];
+    random.read_exact(&mut bytes)?;
+
+    // 256 is not a multiple of 62, but the small bias doesn't matter for
+    // names that are only used if they don't exist yet
+    Ok(bytes
+        .iter()
+        .map(|&byte| NAME_CHARS[byte as usize % NAME_CHARS.len()] as char)
+        .collect())
+}
+
+fn c_path(path: &Path) -> io::Result<CString> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))
+}
+
+// -r: TARGET as seen from the directory LINK will be in, both with
+// symbolic links resolved as far as they exist
+fn relative_target(target: &Path, link: &Path) -> PathBuf {
+    let target = resolve_missing(target);
+    let link_dir = resolve_missing(
+        link.parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new(".")),
+    );
+
+    let target_parts: Vec<Component> = target.components().collect();
+    let dir_parts: Vec<Component> = link_dir.components().collect();
+    let common = target_parts
+        .iter()
+        .zip(&dir_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..dir_parts.len() {
+        relative.push("..");
+    }
+    for part in &target_parts[common..] {
+        relative.push(part);
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+// The absolute form of PATH with symbolic links resolved in the part that
+// exists; the rest is only tidied of "." and ".."
+fn resolve_missing(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+
+    let mut resolved = PathBuf::from("/");
+    let mut missing = false;
+    for component in absolute.components() {
+        match component {
+            Component::RootDir | Component::Prefix(_) | Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(name) => {
+                resolved.push(name);
+                if !missing {
+                    match fs::canonicalize(&resolved) {
+                        Ok(real) => resolved = real,
+                        Err(_) => missing = true,
+                    }
+                }
+            }
+        }
+    }
+    resolved
+}
@@ -0,0 +1,32 @@
+// ASD CoreUtils - logname
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::fail;
+use clap::Command;
+use std::ffi::CStr;
+
+fn main() {
+    Command::new("logname")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils logname - print the name of the current user's login")
+        .get_matches();
+
+    // getlogin() looks up the controlling terminal's utmp entry, so this is
+    // the user who logged in, not whoever the process now runs as; POSIX
+    // leaves no fallback when there is none
+    let name = unsafe {
+        let name = libc::getlogin();
+        if name.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+        }
+    };
+
+    match name {
+        Some(name) => println!("{}", name),
+        None => fail("logname", "no login name"),
+    }
+}
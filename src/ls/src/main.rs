@@ -1,3 +1,6 @@
+use asd_coreutils_core::error::strerror;
+use asd_coreutils_core::mode::mode_string;
+use asd_coreutils_core::size::human;
 use chrono::{DateTime, Local};
 use clap::{App, Arg};
 use colored::Colorize;
@@ -112,7 +115,7 @@ fn main() -> io::Result<()> {
         ) {
             Ok(_) => (),
             Err(e) => {
-                eprintln!("Error listing '{}': {}", path, e);
+                eprintln!("Error listing '{}': {}", path, strerror(&e));
                 process::exit(1);
             }
         }
@@ -121,6 +124,7 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn list_directory(
     dir_path: &str,
     show_hidden: bool,
@@ -221,10 +225,10 @@ fn list_directory(
 
     if long_format {
         for file in &files {
-            let permissions = format_permissions(file.permissions);
+            let permissions = mode_string(file.permissions);
             let modified_time = file.modified.format("%b %d %H:%M").to_string();
             let size = if human_readable {
-                format_size(file.size)
+                human(file.size, 1024)
             } else {
                 file.size.to_string()
             };
@@ -267,53 +271,6 @@ fn list_directory(
     Ok(())
 }
 
-fn format_permissions(mode: u32) -> String {
-    let file_type = match mode & 0o170000 {
-        0o040000 => 'd', // directory
-        0o120000 => 'l', // symbolic link
-        _ => '-',        // regular file
-    };
-
-    let user_r = if mode & 0o400 != 0 { 'r' } else { '-' };
-    let user_w = if mode & 0o200 != 0 { 'w' } else { '-' };
-    let user_x = if mode & 0o100 != 0 { 'x' } else { '-' };
-
-    let group_r = if mode & 0o040 != 0 { 'r' } else { '-' };
-    let group_w = if mode & 0o020 != 0 { 'w' } else { '-' };
-    let group_x = if mode & 0o010 != 0 { 'x' } else { '-' };
-
-    let other_r = if mode & 0o004 != 0 { 'r' } else { '-' };
-    let other_w = if mode & 0o002 != 0 { 'w' } else { '-' };
-    let other_x = if mode & 0o001 != 0 { 'x' } else { '-' };
-
-    format!(
-        "{}{}{}{}{}{}{}{}{}{}",
-        file_type,
-        user_r, user_w, user_x,
-        group_r, group_w, group_x,
-        other_r, other_w, other_x
-    )
-}
-
-fn format_size(size: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if size < KB {
-        format!("{}B", size)
-    } else if size < MB {
-        format!("{:.1}K", size as f64 / KB as f64)
-    } else if size < GB {
-        format!("{:.1}M", size as f64 / MB as f64)
-    } else if size < TB {
-        format!("{:.1}G", size as f64 / GB as f64)
-    } else {
-        format!("{:.1}T", size as f64 / TB as f64)
-    }
-}
-
 fn format_name(name: &str, is_dir: bool, is_symlink: bool, use_color: bool) -> String {
     if !use_color {
         if is_dir {
@@ -6,6 +6,7 @@
 #include <errno.h>
 #include <cstdlib>
 #include <cstring>
+#include <getopt.h>
 #include <unistd.h>
 
 //
@@ -27,6 +28,7 @@ void print_usage() {
     std::cout << std::endl;
     std::cout << "OPTIONS:" << std::endl;
     std::cout << "  -m, --mode=MODE   set file mode (as in chmod), not a=rwx - umask" << std::endl;
+    std::cout << "                    the mode is applied exactly, whatever the umask" << std::endl;
     std::cout << "  -p, --parents     no error if existing, make parent directories as needed" << std::endl;
     std::cout << "  -v, --verbose     print a message for each created directory" << std::endl;
     std::cout << "  --version         output version information and exit" << std::endl;
@@ -36,49 +38,182 @@ void print_usage() {
     std::cout << "  mkdir test        Create a directory named 'test'." << std::endl;
     std::cout << "  mkdir -p a/b/c    Create directories 'a', 'a/b', and 'a/b/c'." << std::endl;
     std::cout << "  mkdir -m 755 dir   Create a directory 'dir' with mode 755." << std::endl;
+    std::cout << "  mkdir -pm u=rwx,go= a/b   Create 'a' as usual and 'a/b' with mode 700." << std::endl;
 }
 
-int create_directory(const std::string& path, mode_t mode, bool parents, bool verbose) {
-    if (!parents) {
-        if (mkdir(path.c_str(), mode) == 0) {
-            if (verbose) {
-                std::cout << "mkdir: created directory '" << path << "'" << std::endl;
-            }
+// Parents made by -p get the usual a=rwx - umask, plus u+wx so that the
+// next one can be made inside
+mode_t parent_mode(mode_t umask_value) {
+    return (static_cast<mode_t>(S_IRWXU | S_IRWXG | S_IRWXO) & ~umask_value) | S_IWUSR | S_IXUSR;
+}
+
+// Makes one directory that may already exist, as -p allows for parents and
+// for the last one. Checking after mkdir fails, rather than before, means
+// another process making the same directory at the same time is no error.
+// Returns 1 if it was made, 0 if it was there, and -1 with errno set if a
+// parent is not a directory or the last one exists as something else.
+int make_existing_ok(const std::string& path, mode_t mode, bool verbose, bool last) {
+    if (mkdir(path.c_str(), mode) == 0) {
+        if (verbose) {
+            std::cout << "mkdir: created directory '" << path << "'" << std::endl;
+        }
+        return 1;
+    }
+
+    int saved = errno;
+    struct stat info;
+    if (saved == EEXIST && stat(path.c_str(), &info) == 0) {
+        if (S_ISDIR(info.st_mode)) {
             return 0;
-        } else {
-            std::cerr << "mkdir: cannot create directory '" << path << "': " << strerror(errno) << std::endl;
-            return 1;
         }
+        saved = last ? EEXIST : ENOTDIR;
+    }
+    errno = saved;
+    return -1;
+}
+
+// MODE is applied exactly, with the umask cleared; when -m asked for the
+// set-ID or sticky bits they need a chmod, since mkdir may drop them
+int create_directory(const std::string& path, mode_t mode, bool exact, mode_t umask_value,
+                     bool parents, bool verbose) {
+    if (parents) {
+        // Each parent in turn: "a", "a/b" for "a/b/c", skipping runs of slashes
+        for (size_t i = path.find('/', 1); i != std::string::npos; i = path.find('/', i + 1)) {
+            if (path[i - 1] == '/' || path.find_first_not_of('/', i) == std::string::npos) {
+                continue;
+            }
+            std::string parent = path.substr(0, i);
+            if (make_existing_ok(parent, parent_mode(umask_value), verbose, false) < 0) {
+                std::cerr << "mkdir: cannot create directory '" << parent << "': " << strerror(errno) << std::endl;
+                return 1;
+            }
+        }
+    }
+
+    int made;
+    if (parents) {
+        made = make_existing_ok(path, mode & (S_IRWXU | S_IRWXG | S_IRWXO), verbose, true);
     } else {
-        std::string current_path;
-        for (size_t i = 0; i < path.length(); ++i) {
-            current_path += path[i];
-            if (path[i] == '/' || i == path.length() - 1) {
-                if (!current_path.empty()) {
-                    if (mkdir(current_path.c_str(), mode) != 0 && errno != EEXIST) {
-                        std::cerr << "mkdir: cannot create directory '" << current_path << "': " << strerror(errno) << std::endl;
-                        return 1;
-                    } else if (errno != EEXIST && verbose) {
-                        std::cout << "mkdir: created directory '" << current_path << "'" << std::endl;
+        made = mkdir(path.c_str(), mode & (S_IRWXU | S_IRWXG | S_IRWXO)) == 0 ? 1 : -1;
+        if (made > 0 && verbose) {
+            std::cout << "mkdir: created directory '" << path << "'" << std::endl;
+        }
+    }
+    if (made < 0) {
+        std::cerr << "mkdir: cannot create directory '" << path << "': " << strerror(errno) << std::endl;
+        return 1;
+    }
+
+    // Only a directory made here gets the mode; an existing one is left be
+    if (made > 0 && exact && (mode & (S_ISUID | S_ISGID | S_ISVTX)) && chmod(path.c_str(), mode) != 0) {
+        std::cerr << "mkdir: cannot set permissions of '" << path << "': " << strerror(errno) << std::endl;
+        return 1;
+    }
+    return 0;
+}
+
+// The permission bits a class letter covers
+mode_t class_bits(char c) {
+    switch (c) {
+        case 'u': return S_ISUID | S_IRWXU;
+        case 'g': return S_ISGID | S_IRWXG;
+        case 'o': return S_ISVTX | S_IRWXO;
+        default: return 07777;
+    }
+}
+
+// An octal mode or symbolic clauses as chmod takes them, such as
+// "u=rwx,go=rx" or "a+X", applied to a=rwx. Clauses that name no class are
+// limited by the umask.
+bool parse_mode(const std::string& spec, mode_t umask_value, mode_t& result) {
+    if (!spec.empty() && spec.find_first_not_of("01234567") == std::string::npos) {
+        if (spec.length() > 8) {
+            return false;
+        }
+        unsigned long value = std::strtoul(spec.c_str(), nullptr, 8);
+        if (value > 07777) {
+            return false;
+        }
+        result = static_cast<mode_t>(value);
+        return true;
+    }
+
+    mode_t mode = S_IRWXU | S_IRWXG | S_IRWXO;
+    size_t pos = 0;
+    while (true) {
+        mode_t affected = 0;
+        while (pos < spec.length() && std::strchr("ugoa", spec[pos])) {
+            affected |= class_bits(spec[pos++]);
+        }
+        bool named = affected != 0;
+        if (!named) {
+            affected = 07777;
+        }
+
+        if (pos >= spec.length() || !std::strchr("+-=", spec[pos])) {
+            return false;
+        }
+        while (pos < spec.length() && std::strchr("+-=", spec[pos])) {
+            char op = spec[pos++];
+            mode_t value = 0;
+            bool limited = !named;
+
+            if (pos < spec.length() && spec[pos] >= '0' && spec[pos] <= '7') {
+                // "+755": exactly these bits, and only without a class
+                if (named) {
+                    return false;
+                }
+                size_t end = spec.find_first_not_of("01234567", pos);
+                if (end == std::string::npos) {
+                    end = spec.length();
+                }
+                value = static_cast<mode_t>(std::strtoul(spec.substr(pos, end - pos).c_str(), nullptr, 8));
+                if (end - pos > 4 || value > 07777) {
+                    return false;
+                }
+                limited = false;
+                pos = end;
+            } else if (pos < spec.length() && std::strchr("ugo", spec[pos])) {
+                // "g=u": copy what that class has to the others
+                mode_t bits = mode & class_bits(spec[pos++]) & (S_IRWXU | S_IRWXG | S_IRWXO);
+                if (bits & (S_IRUSR | S_IRGRP | S_IROTH)) value |= S_IRUSR | S_IRGRP | S_IROTH;
+                if (bits & (S_IWUSR | S_IWGRP | S_IWOTH)) value |= S_IWUSR | S_IWGRP | S_IWOTH;
+                if (bits & (S_IXUSR | S_IXGRP | S_IXOTH)) value |= S_IXUSR | S_IXGRP | S_IXOTH;
+            } else {
+                while (pos < spec.length() && std::strchr("rwxXst", spec[pos])) {
+                    switch (spec[pos++]) {
+                        case 'r': value |= S_IRUSR | S_IRGRP | S_IROTH; break;
+                        case 'w': value |= S_IWUSR | S_IWGRP | S_IWOTH; break;
+                        // X is x for a directory
+                        case 'x': case 'X': value |= S_IXUSR | S_IXGRP | S_IXOTH; break;
+                        case 's': value |= S_ISUID | S_ISGID; break;
+                        case 't': value |= S_ISVTX; break;
                     }
                 }
             }
+
+            value &= affected;
+            if (limited) {
+                value &= ~umask_value;
+            }
+            switch (op) {
+                case '+': mode |= value; break;
+                case '-': mode &= ~value; break;
+                default: mode = (mode & (named ? ~affected : 0)) | value; break;
+            }
         }
-        return 0;
-    }
-}
 
-mode_t parse_mode(const std::string& mode_str) {
-    mode_t mode = 0;
-    for (char c : mode_str) {
-        if (c >= '0' && c <= '7') {
-            mode = (mode << 3) | (c - '0');
-        } else {
-            std::cerr << "mkdir: invalid mode: '" << mode_str << "'" << std::endl;
-            return -1; // Indicate an error
+        if (pos == spec.length()) {
+            break;
+        }
+        if (spec[pos] != ',') {
+            return false;
         }
+        pos++;
     }
-    return mode;
+
+    result = mode & 07777;
+    return true;
 }
 
 } // namespace Mkdir
@@ -88,22 +223,30 @@ int main(int argc, char *argv[]) {
     bool parents = false;
     bool verbose = false;
     mode_t mode = S_IRWXU | S_IRWXG | S_IRWXO; // a=rwx by default
+    const char* mode_spec = nullptr;
     std::vector<std::string> paths;
 
+    static const struct option long_options[] = {
+        {"mode", required_argument, nullptr, 'm'},
+        {"parents", no_argument, nullptr, 'p'},
+        {"verbose", no_argument, nullptr, 'v'},
+        {"help", no_argument, nullptr, 'h'},
+        {"version", no_argument, nullptr, 'V'},
+        {nullptr, 0, nullptr, 0}
+    };
+
     int opt;
-    while ((opt = getopt(argc, argv, "pm:vh")) != -1) {
+    while ((opt = getopt_long(argc, argv, "pm:vh", long_options, nullptr)) != -1) {
         switch (opt) {
             case 'p':
                 parents = true;
                 break;
-            case 'm': {
-                mode_t parsed_mode = ASDCoreUtils::Mkdir::parse_mode(optarg);
-                if (parsed_mode == static_cast<mode_t>(-1)) {
-                    return 1;
-                }
-                mode = parsed_mode;
+            case 'm':
+                mode_spec = optarg;
                 break;
-            }
+            case 'V':
+                std::cout << "mkdir (ASD CoreUtils) " << ASDCoreUtils::Mkdir::version << std::endl;
+                return 0;
             case 'v':
                 verbose = true;
                 break;
@@ -135,9 +278,23 @@ int main(int argc, char *argv[]) {
         return 1;
     }
 
+    mode_t umask_value = umask(0);
+    umask(umask_value);
+    if (mode_spec) {
+        if (!ASDCoreUtils::Mkdir::parse_mode(mode_spec, umask_value, mode)) {
+            std::cerr << "mkdir: invalid mode '" << mode_spec << "'" << std::endl;
+            return 1;
+        }
+    } else {
+        mode &= ~umask_value;
+    }
+    // Modes are worked out here, parents' included, so the umask is done with
+    umask(0);
+
     int exit_code = 0;
     for (const auto& path : paths) {
-        if (ASDCoreUtils::Mkdir::create_directory(path, mode, parents, verbose) != 0) {
+        if (ASDCoreUtils::Mkdir::create_directory(path, mode, mode_spec != nullptr, umask_value,
+                                                  parents, verbose) != 0) {
             exit_code = 1;
         }
     }
@@ -0,0 +1,152 @@
+// ASD CoreUtils - mkfifo
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, Command};
+use std::ffi::CString;
+use std::io;
+use std::process;
+
+const DEFAULT_MODE: u32 = 0o666;
+
+fn main() {
+    let matches = Command::new("mkfifo")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils mkfifo - make FIFOs (named pipes)")
+        .arg(
+            Arg::new("mode")
+                .short('m')
+                .long("mode")
+                .value_name("MODE")
+                .help("Set file permission bits to MODE, not a=rw - umask"),
+        )
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .help("FIFOs to create")
+                .required(true)
+                .num_args(1..),
+        )
+        .get_matches();
+
+    let umask = current_umask();
+
+    // An explicit mode is applied exactly, so the umask is cleared for the
+    // mkfifo calls; otherwise the usual a=rw minus umask applies
+    let mode = match matches.get_one::<String>("mode") {
+        Some(spec) => match parse_mode(spec, DEFAULT_MODE, umask) {
+            Some(mode) if mode & !0o777 != 0 => {
+                fail("mkfifo", "mode must specify only file permission bits")
+            }
+            Some(mode) => {
+                unsafe { libc::umask(0) };
+                mode
+            }
+            None => fail("mkfifo", &format!("invalid mode '{}'", spec)),
+        },
+        None => DEFAULT_MODE,
+    };
+
+    let mut exit_code = 0;
+
+    for name in matches.get_many::<String>("name").unwrap() {
+        if let Err(err) = make_fifo(name, mode) {
+            eprintln!("mkfifo: cannot create fifo '{}': {}", name, describe(&err));
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+fn current_umask() -> u32 {
+    unsafe {
+        let mask = libc::umask(0);
+        libc::umask(mask);
+        mask as u32
+    }
+}
+
+fn make_fifo(name: &str, mode: u32) -> io::Result<()> {
+    let path = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    if unsafe { libc::mkfifo(path.as_ptr(), mode as libc::mode_t) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Some file systems (FAT, many network mounts) refuse special files
+// outright; the bare errno text doesn't say so
+fn describe(err: &io::Error) -> String {
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::ENOTSUP) | Some(libc::EINVAL) => {
+            format!("{} (the file system may not support FIFOs)", strerror(err))
+        }
+        _ => strerror(err),
+    }
+}
+
+// Accepts an octal mode or a comma separated list of symbolic clauses such
+// as "u=rw,go-w"; clauses without a user class are limited by the umask
+fn parse_mode(spec: &str, base: u32, umask: u32) -> Option<u32> {
+    if !spec.is_empty() && spec.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return u32::from_str_radix(spec, 8).ok().filter(|&mode| mode <= 0o7777);
+    }
+
+    let mut mode = base;
+
+    for clause in spec.split(',') {
+        let who_len = clause.find(|c| !"ugoa".contains(c)).unwrap_or(clause.len());
+        let (who, mut rest) = clause.split_at(who_len);
+
+        let (mask, limit) = if who.is_empty() {
+            (0o7777, !umask)
+        } else {
+            let mask = who.chars().fold(0, |mask, c| {
+                mask | match c {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o0007,
+                    _ => 0o7777,
+                }
+            });
+            (mask, 0o7777)
+        };
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        while let Some(op) = rest.chars().next().filter(|c| "+-=".contains(*c)) {
+            let perms_len = rest[1..].find(|c| "+-=".contains(c)).map_or(rest.len(), |i| i + 1);
+            let mut bits = 0;
+            for c in rest[1..perms_len].chars() {
+                bits |= match c {
+                    'r' => 0o444,
+                    'w' => 0o222,
+                    'x' => 0o111,
+                    's' => 0o6000,
+                    't' => 0o1000,
+                    _ => return None,
+                };
+            }
+            let bits = bits & mask & limit;
+
+            match op {
+                '+' => mode |= bits,
+                '-' => mode &= !bits,
+                _ => mode = (mode & !mask) | bits,
+            }
+            rest = &rest[perms_len..];
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+
+    Some(mode)
+}
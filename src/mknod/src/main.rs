@@ -0,0 +1,238 @@
+// ASD CoreUtils - mknod
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, Command};
+use std::ffi::CString;
+use std::io;
+
+const DEFAULT_MODE: u32 = 0o666;
+
+#[derive(Clone, Copy)]
+enum NodeType {
+    Block,
+    Character,
+    Fifo,
+}
+
+impl NodeType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "b" => Some(NodeType::Block),
+            "c" | "u" => Some(NodeType::Character),
+            "p" => Some(NodeType::Fifo),
+            _ => None,
+        }
+    }
+
+    fn file_type(&self) -> libc::mode_t {
+        match self {
+            NodeType::Block => libc::S_IFBLK,
+            NodeType::Character => libc::S_IFCHR,
+            NodeType::Fifo => libc::S_IFIFO,
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("mknod")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils mknod - make block or character special files")
+        .after_help(
+            "Both MAJOR and MINOR must be specified when TYPE is b, c, or u, and they\n\
+             must be omitted when TYPE is p. TYPE may be:\n  \
+             b      create a block (buffered) special file\n  \
+             c, u   create a character (unbuffered) special file\n  \
+             p      create a FIFO",
+        )
+        .arg(
+            Arg::new("mode")
+                .short('m')
+                .long("mode")
+                .value_name("MODE")
+                .help("Set file permission bits to MODE, not a=rw - umask"),
+        )
+        .arg(
+            Arg::new("operands")
+                .value_name("NAME TYPE [MAJOR MINOR]")
+                .help("Name and type of the node, plus device numbers for b and c")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let operands: Vec<&String> = matches
+        .get_many::<String>("operands")
+        .map(|operands| operands.collect())
+        .unwrap_or_default();
+
+    let (name, node_type, device) = check_operands(&operands);
+
+    let umask = current_umask();
+
+    // An explicit mode is applied exactly, so the umask is cleared for the
+    // mknod call; otherwise the usual a=rw minus umask applies
+    let mode = match matches.get_one::<String>("mode") {
+        Some(spec) => match parse_mode(spec, DEFAULT_MODE, umask) {
+            Some(mode) if mode & !0o777 != 0 => {
+                fail("mknod", "mode must specify only file permission bits")
+            }
+            Some(mode) => {
+                unsafe { libc::umask(0) };
+                mode
+            }
+            None => fail("mknod", &format!("invalid mode '{}'", spec)),
+        },
+        None => DEFAULT_MODE,
+    };
+
+    if let Err(err) = make_node(name, node_type.file_type() | mode as libc::mode_t, device) {
+        fail("mknod", &format!("{}: {}", name, strerror(&err)));
+    }
+}
+
+// Validates the operand count against the node type the way GNU mknod
+// reports it, exiting on any mismatch
+fn check_operands<'a>(operands: &[&'a String]) -> (&'a str, NodeType, libc::dev_t) {
+    match operands.len() {
+        0 => usage_error("mknod", "missing operand"),
+        1 => usage_error("mknod", &format!("missing operand after '{}'", operands[0])),
+        _ => {}
+    }
+
+    let node_type = NodeType::parse(operands[1]);
+    let wants_device = !matches!(node_type, Some(NodeType::Fifo));
+
+    if wants_device && operands.len() == 2 {
+        usage_error(
+            "mknod",
+            &format!(
+                "missing operand after '{}'\nSpecial files require major and minor device numbers.",
+                operands[1]
+            ),
+        );
+    }
+    if !wants_device && operands.len() > 2 {
+        usage_error(
+            "mknod",
+            &format!(
+                "extra operand '{}'\nFifos do not have major and minor device numbers.",
+                operands[2]
+            ),
+        );
+    }
+    if operands.len() == 3 {
+        usage_error("mknod", &format!("missing operand after '{}'", operands[2]));
+    }
+    if operands.len() > 4 {
+        usage_error("mknod", &format!("extra operand '{}'", operands[4]));
+    }
+
+    let node_type = node_type
+        .unwrap_or_else(|| fail("mknod", &format!("invalid device type '{}'", operands[1])));
+
+    let device = if wants_device {
+        let major = parse_device_number(operands[2], "major");
+        let minor = parse_device_number(operands[3], "minor");
+        libc::makedev(major, minor)
+    } else {
+        0
+    };
+
+    (operands[0].as_str(), node_type, device)
+}
+
+// Device numbers follow strtoul conventions: 0x for hex, leading 0 for octal
+fn parse_device_number(value: &str, which: &str) -> u32 {
+    let parsed = if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16)
+    } else if value.len() > 1 && value.starts_with('0') {
+        u32::from_str_radix(&value[1..], 8)
+    } else {
+        value.parse()
+    };
+
+    parsed.unwrap_or_else(|_| fail("mknod", &format!("invalid {} device number '{}'", which, value)))
+}
+
+fn current_umask() -> u32 {
+    unsafe {
+        let mask = libc::umask(0);
+        libc::umask(mask);
+        mask as u32
+    }
+}
+
+// Block and character nodes need CAP_MKNOD, so EPERM is the usual failure
+// for unprivileged callers and is reported as is
+fn make_node(name: &str, mode: libc::mode_t, device: libc::dev_t) -> io::Result<()> {
+    let path = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    if unsafe { libc::mknod(path.as_ptr(), mode, device) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Accepts an octal mode or a comma separated list of symbolic clauses such
+// as "u=rw,go-w"; clauses without a user class are limited by the umask
+fn parse_mode(spec: &str, base: u32, umask: u32) -> Option<u32> {
+    if !spec.is_empty() && spec.bytes().all(|b| (b'0'..=b'7').contains(&b)) {
+        return u32::from_str_radix(spec, 8).ok().filter(|&mode| mode <= 0o7777);
+    }
+
+    let mut mode = base;
+
+    for clause in spec.split(',') {
+        let who_len = clause.find(|c| !"ugoa".contains(c)).unwrap_or(clause.len());
+        let (who, mut rest) = clause.split_at(who_len);
+
+        let (mask, limit) = if who.is_empty() {
+            (0o7777, !umask)
+        } else {
+            let mask = who.chars().fold(0, |mask, c| {
+                mask | match c {
+                    'u' => 0o4700,
+                    'g' => 0o2070,
+                    'o' => 0o0007,
+                    _ => 0o7777,
+                }
+            });
+            (mask, 0o7777)
+        };
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        while let Some(op) = rest.chars().next().filter(|c| "+-=".contains(*c)) {
+            let perms_len = rest[1..].find(|c| "+-=".contains(c)).map_or(rest.len(), |i| i + 1);
+            let mut bits = 0;
+            for c in rest[1..perms_len].chars() {
+                bits |= match c {
+                    'r' => 0o444,
+                    'w' => 0o222,
+                    'x' => 0o111,
+                    's' => 0o6000,
+                    't' => 0o1000,
+                    _ => return None,
+                };
+            }
+            let bits = bits & mask & limit;
+
+            match op {
+                '+' => mode |= bits,
+                '-' => mode &= !bits,
+                _ => mode = (mode & !mask) | bits,
+            }
+            rest = &rest[perms_len..];
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+    }
+
+    Some(mode)
+}
@@ -0,0 +1,215 @@
+// ASD CoreUtils - mktemp
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io::{self, Read};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::PathBuf;
+use std::process;
+
+const DEFAULT_TEMPLATE: &str = "tmp.XXXXXXXXXX";
+const MIN_RANDOM_CHARS: usize = 3;
+const MAX_ATTEMPTS: usize = 10_000;
+const NAME_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+fn main() {
+    let matches = Command::new("mktemp")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils mktemp - create a temporary file or directory and print its name")
+        .arg(
+            Arg::new("directory")
+                .short('d')
+                .long("directory")
+                .help("Create a directory, not a file")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .short('u')
+                .long("dry-run")
+                .help("Do not create anything; merely print a name (unsafe)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .help("Suppress diagnostics about file/dir-creation failure")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("suffix")
+                .long("suffix")
+                .value_name("SUFF")
+                .help("Append SUFF to TEMPLATE; SUFF must not contain a slash"),
+        )
+        .arg(
+            Arg::new("tmpdir")
+                .long("tmpdir")
+                .value_name("DIR")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("")
+                .help("Interpret TEMPLATE relative to DIR, or to $TMPDIR (else /tmp) if DIR is not given"),
+        )
+        .arg(
+            Arg::new("tmpdir-short")
+                .short('p')
+                .value_name("DIR")
+                .help("Use DIR as a prefix, like --tmpdir=DIR"),
+        )
+        .arg(
+            Arg::new("template")
+                .value_name("TEMPLATE")
+                .help("Name template with at least 3 consecutive X's, last in it or before SUFF (default: tmp.XXXXXXXXXX in the temporary directory)"),
+        )
+        .get_matches();
+
+    let tmpdir = matches
+        .get_one::<String>("tmpdir-short")
+        .or(matches.get_one::<String>("tmpdir"));
+    let given = matches.get_one::<String>("template");
+    let template = given.map_or(DEFAULT_TEMPLATE, String::as_str);
+
+    // The random part is the last run of X's. With --suffix it must end
+    // the template; without, whatever follows it is the suffix
+    let (template, suffix_len) = match matches.get_one::<String>("suffix") {
+        Some(suffix) => {
+            if !template.ends_with('X') {
+                fail("mktemp", &format!("with --suffix, template '{}' must end in X", template));
+            }
+            (format!("{}{}", template, suffix), suffix.len())
+        }
+        None => {
+            let end = template.rfind('X').map_or(template.len(), |at| at + 1);
+            (template.to_string(), template.len() - end)
+        }
+    };
+    let suffix = &template[template.len() - suffix_len..];
+    if suffix.contains('/') {
+        fail("mktemp", &format!("invalid suffix '{}', contains directory separator", suffix));
+    }
+    let stem = &template[..template.len() - suffix_len];
+    let random_chars = stem.len() - stem.trim_end_matches('X').len();
+    if random_chars < MIN_RANDOM_CHARS {
+        fail("mktemp", &format!("too few X's in template '{}'", template));
+    }
+
+    // Without a template, or with --tmpdir, the name goes into the
+    // temporary directory; otherwise it is used as is, relative to the
+    // current directory
+    let template = if tmpdir.is_some() || given.is_none() {
+        if tmpdir.is_some() && template.starts_with('/') {
+            fail("mktemp", &format!(
+                "invalid template, '{}'; with --tmpdir, it may not be absolute",
+                template
+            ));
+        }
+        let dir = match tmpdir {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => temp_dir(),
+        };
+        dir.join(template).to_string_lossy().into_owned()
+    } else {
+        template
+    };
+
+    let directory = matches.get_flag("directory");
+    let dry_run = matches.get_flag("dry-run");
+
+    match create(&template, random_chars, suffix_len, directory, dry_run) {
+        Ok(path) => println!("{}", path.display()),
+        Err(err) => {
+            if !matches.get_flag("quiet") {
+                eprintln!("mktemp: {}", strerror(&err));
+            }
+            process::exit(1);
+        }
+    }
+}
+
+fn temp_dir() -> PathBuf {
+    match env::var_os("TMPDIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from("/tmp"),
+    }
+}
+
+// Replaces the RANDOM_CHARS X's that come SUFFIX_LEN bytes before the end
+// of TEMPLATE until the name is new, and creates it unless DRY_RUN
+fn create(
+    template: &str,
+    random_chars: usize,
+    suffix_len: usize,
+    directory: bool,
+    dry_run: bool,
+) -> io::Result<PathBuf> {
+    let prefix = &template[..template.len() - suffix_len - random_chars];
+    let suffix = &template[template.len() - suffix_len..];
+
+    let mut random = File::open("/dev/urandom")?;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let name = random_name(&mut random, random_chars)?;
+        let path = PathBuf::from(format!("{}{}{}", prefix, name, suffix));
+
+        // Both calls fail rather than reuse an existing entry, so a name
+        // another process raced us to is simply retried. A dry run only
+        // looks, which is why the name it prints is not safe to use
+        let result = if dry_run {
+            match fs::symlink_metadata(&path) {
+                Ok(_) => Err(io::Error::from(io::ErrorKind::AlreadyExists)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(err),
+            }
+        } else if directory {
+            DirBuilder::new().mode(0o700).create(&path)
+        } else {
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)
+                .map(drop)
+        };
+
+        match result {
+            Ok(()) => return Ok(path),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                let kind = if directory { "directory" } else { "file" };
+                return Err(io::Error::new(
+                    err.kind(),
+                    format!(
+                        "failed to create {} via template '{}': {}",
+                        kind,
+                        template,
+                        strerror(&err)
+                    ),
+                ));
+            }
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        format!("failed to create a unique name from template '{}'", template),
+    ))
+}
+
+fn random_name(random: &mut File, length: usize) -> io::Result<String> {
+    let mut bytes = vec![0u8; length];
+    random.read_exact(&mut bytes)?;
+
+    // 256 is not a multiple of 62, but the small bias doesn't matter for
+    // names that are protected by O_EXCL anyway
+    Ok(bytes
+        .iter()
+        .map(|&byte| NAME_CHARS[byte as usize % NAME_CHARS.len()] as char)
+        .collect())
+}
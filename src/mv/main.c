@@ -8,6 +8,15 @@
 #include <fcntl.h>
 #include <libgen.h>
 #include <getopt.h>
+#include <time.h>
+#include <signal.h>
+#include <dirent.h>
+#include <limits.h>
+#include <sys/ioctl.h>
+#include <sys/xattr.h>
+
+#include "../common/copy.h"
+#include "../common/progress.h"
 
 #define BUFFER_SIZE 8192
 #define VERSION "1.0.0"
@@ -25,6 +34,10 @@ void print_usage(const char *program_name) {
     fprintf(stderr, "  -f, --force         force move, override destination if exists\n");
     fprintf(stderr, "  -i, --interactive   prompt before overwrite\n");
     fprintf(stderr, "  -n, --no-clobber    do not overwrite existing files\n");
+    fprintf(stderr, "      --progress[=WHEN]  show progress of moves to another filesystem,\n");
+    fprintf(stderr, "                      with throughput and time left; WHEN is 'always',\n");
+    fprintf(stderr, "                      'auto' (the default: on a terminal, once copying\n");
+    fprintf(stderr, "                      takes more than a second) or 'never'\n");
     fprintf(stderr, "  -v, --verbose       explain what is being done\n");
     fprintf(stderr, "      --version       display version information\n");
     fprintf(stderr, "      --help          display this help\n");
@@ -38,6 +51,46 @@ void print_version(void) {
     exit(EXIT_SUCCESS);
 }
 
+// Bytes of regular file data under PATH
+static off_t tree_size(const char *path) {
+    struct stat st;
+    off_t total = 0;
+
+    if (lstat(path, &st) != 0) {
+        return 0;
+    }
+    if (S_ISREG(st.st_mode)) {
+        return st.st_size;
+    }
+    if (S_ISDIR(st.st_mode)) {
+        DIR *dir = opendir(path);
+        struct dirent *entry;
+        char child[PATH_MAX];
+
+        if (!dir) {
+            return 0;
+        }
+        while ((entry = readdir(dir)) != NULL) {
+            if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0) {
+                continue;
+            }
+            snprintf(child, sizeof(child), "%s/%s", path, entry->d_name);
+            total += tree_size(child);
+        }
+        closedir(dir);
+    }
+    return total;
+}
+
+// Size of SOURCE if moving it to a filesystem DEST_DEV means copying it
+static off_t copy_size(const char *source, dev_t dest_dev) {
+    struct stat st;
+    if (lstat(source, &st) != 0 || st.st_dev == dest_dev) {
+        return 0;
+    }
+    return tree_size(source);
+}
+
 int confirm_overwrite(const char *dest) {
     char response[10];
     printf("mv: overwrite '%s'? ", dest);
@@ -59,71 +112,307 @@ int check_same_file(const char *source, const char *dest) {
     return 0;
 }
 
-int move_file(const char *source, const char *dest) {
-    // Try simple rename first
-    if (rename(source, dest) == 0) {
-        if (verbose_flag) {
-            printf("'%s' -> '%s'\n", source, dest);
+static void note_interrupt(int sig) {
+    copy_interrupted = sig;
+}
+
+// Remove PATH and everything below it
+static int remove_tree(const char *path) {
+    struct stat st;
+
+    if (lstat(path, &st) != 0) {
+        return errno == ENOENT ? 0 : -1;
+    }
+    if (S_ISDIR(st.st_mode)) {
+        DIR *dir = opendir(path);
+        struct dirent *entry;
+        char child[PATH_MAX];
+        int status = 0;
+
+        if (!dir) {
+            return -1;
         }
-        return 0;
+        while ((entry = readdir(dir)) != NULL) {
+            if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0) {
+                continue;
+            }
+            snprintf(child, sizeof(child), "%s/%s", path, entry->d_name);
+            if (remove_tree(child) != 0) {
+                status = -1;
+            }
+        }
+        closedir(dir);
+        return status == 0 ? rmdir(path) : -1;
     }
-    
-    // If rename fails due to cross-device move, fallback to copy and delete
-    if (errno == EXDEV) {
-        int source_fd, dest_fd;
-        ssize_t bytes_read, bytes_written;
-        char buffer[BUFFER_SIZE];
-        struct stat source_stat;
-        
-        // Get source file stats
-        if (stat(source, &source_stat) != 0) {
-            perror("mv: failed to stat source file");
+    return unlink(path);
+}
+
+// Give DEST the owner, permissions, extended attributes and timestamps of
+// a source described by SOURCE_STAT; a failure to change the owner is
+// expected for unprivileged users and not reported
+static void copy_metadata(const char *source, const char *dest, const struct stat *source_stat) {
+    mode_t mode = source_stat->st_mode & 07777;
+    struct timespec times[2] = { source_stat->st_atim, source_stat->st_mtim };
+
+    if (lchown(dest, source_stat->st_uid, source_stat->st_gid) != 0) {
+        // Set-ID bits are not kept for a file that changed hands
+        mode &= ~(S_ISUID | S_ISGID);
+    }
+    if (!S_ISLNK(source_stat->st_mode) && chmod(dest, mode) != 0) {
+        fprintf(stderr, "mv: preserving permissions for '%s': %s\n", dest, strerror(errno));
+    }
+
+    ssize_t list_size = llistxattr(source, NULL, 0);
+    if (list_size > 0) {
+        char *names = malloc(list_size);
+        if (names && (list_size = llistxattr(source, names, list_size)) > 0) {
+            for (char *name = names; name < names + list_size; name += strlen(name) + 1) {
+                ssize_t value_size = lgetxattr(source, name, NULL, 0);
+                char *value = value_size >= 0 ? malloc(value_size + 1) : NULL;
+                if (value && (value_size = lgetxattr(source, name, value, value_size)) >= 0) {
+                    lsetxattr(dest, name, value, value_size, 0);
+                }
+                free(value);
+            }
+        }
+        free(names);
+    }
+
+    utimensat(AT_FDCWD, dest, times, AT_SYMLINK_NOFOLLOW);
+}
+
+// Copy the rest of SOURCE_FD to DEST_FD through a buffer
+static int copy_by_reading(int source_fd, int dest_fd, const char *source, const char *dest) {
+    char buffer[BUFFER_SIZE];
+    ssize_t bytes_read, bytes_written;
+
+    while (!copy_interrupted && (bytes_read = read(source_fd, buffer, BUFFER_SIZE)) != 0) {
+        if (bytes_read < 0) {
+            if (errno != EINTR) {
+                progress_clear();
+                fprintf(stderr, "mv: error reading '%s': %s\n", source, strerror(errno));
+                return -1;
+            }
+            continue;
+        }
+        bytes_written = write(dest_fd, buffer, bytes_read);
+        if (bytes_written != bytes_read) {
+            progress_clear();
+            fprintf(stderr, "mv: error writing '%s': %s\n", dest,
+                    strerror(bytes_written < 0 ? errno : ENOSPC));
             return -1;
         }
-        
-        // Open source file
-        source_fd = open(source, O_RDONLY);
-        if (source_fd == -1) {
-            perror("mv: failed to open source file");
+        progress_advance(bytes_read);
+    }
+    return 0;
+}
+
+// Copy the data of the regular file SOURCE into a new file DEST. A source
+// with fewer blocks than its size needs has holes, which are kept.
+static int copy_regular(const char *source, const char *dest, const struct stat *source_stat) {
+    int source_fd, dest_fd;
+    int status = 1;
+    
+    // Open source file
+    source_fd = open(source, O_RDONLY);
+    if (source_fd == -1) {
+        fprintf(stderr, "mv: cannot open '%s' for reading: %s\n", source, strerror(errno));
+        return -1;
+    }
+    
+    // Only the owner may use the copy until its attributes are set
+    dest_fd = open(dest, O_WRONLY | O_CREAT | O_EXCL, S_IRUSR | S_IWUSR);
+    if (dest_fd == -1) {
+        fprintf(stderr, "mv: cannot create regular file '%s': %s\n", dest, strerror(errno));
+        close(source_fd);
+        return -1;
+    }
+    
+    // Copy data
+    progress_start_file(source, source_stat->st_size);
+    if ((off_t)source_stat->st_blocks * 512 < source_stat->st_size) {
+        CopyFailure failure;
+        status = copy_extents(source_fd, dest_fd, source_stat->st_size, 0, &failure);
+        if (status == -1) {
+            progress_clear();
+            if (failure == COPY_SEEK) {
+                fprintf(stderr, "mv: cannot seek in '%s': %s\n", source, strerror(errno));
+            } else if (failure == COPY_READ) {
+                fprintf(stderr, "mv: error reading '%s': %s\n", source, strerror(errno));
+            } else {
+                fprintf(stderr, "mv: error writing '%s': %s\n", dest, strerror(errno));
+            }
+        }
+    }
+    if (status == 1) {
+        status = copy_by_reading(source_fd, dest_fd, source, dest);
+    }
+    if (status == 0) {
+        progress_end_file();
+    }
+    
+    close(source_fd);
+    if (close(dest_fd) != 0 && status == 0) {
+        progress_clear();
+        fprintf(stderr, "mv: error writing '%s': %s\n", dest, strerror(errno));
+        status = -1;
+    }
+    return copy_interrupted ? -1 : status;
+}
+
+// Recreate SOURCE and everything below it at DEST, which must not exist
+static int copy_tree(const char *source, const char *dest) {
+    struct stat source_stat;
+    int status = 0;
+
+    if (copy_interrupted) {
+        return -1;
+    }
+    if (lstat(source, &source_stat) != 0) {
+        fprintf(stderr, "mv: cannot stat '%s': %s\n", source, strerror(errno));
+        return -1;
+    }
+
+    // Other links to a file already copied become links to its copy
+    int track_links = !S_ISDIR(source_stat.st_mode) && source_stat.st_nlink > 1;
+    if (track_links) {
+        const char *linked = find_copied_link(&source_stat);
+        if (linked) {
+            if (link(linked, dest) != 0) {
+                fprintf(stderr, "mv: cannot create hard link '%s' to '%s': %s\n", dest, linked, strerror(errno));
+                return -1;
+            }
+            return 0;
+        }
+    }
+
+    if (S_ISREG(source_stat.st_mode)) {
+        status = copy_regular(source, dest, &source_stat);
+    } else if (S_ISDIR(source_stat.st_mode)) {
+        DIR *dir;
+        struct dirent *entry;
+        char source_child[PATH_MAX], dest_child[PATH_MAX];
+
+        if (mkdir(dest, S_IRWXU) != 0) {
+            fprintf(stderr, "mv: cannot create directory '%s': %s\n", dest, strerror(errno));
             return -1;
         }
-        
-        // Create destination file with same permissions
-        dest_fd = open(dest, O_WRONLY | O_CREAT | O_TRUNC, source_stat.st_mode);
-        if (dest_fd == -1) {
-            close(source_fd);
-            perror("mv: failed to create destination file");
+        dir = opendir(source);
+        if (!dir) {
+            fprintf(stderr, "mv: cannot open directory '%s': %s\n", source, strerror(errno));
             return -1;
         }
-        
-        // Copy data
-        while ((bytes_read = read(source_fd, buffer, BUFFER_SIZE)) > 0) {
-            bytes_written = write(dest_fd, buffer, bytes_read);
-            if (bytes_written != bytes_read) {
-                close(source_fd);
-                close(dest_fd);
-                unlink(dest);
-                perror("mv: write error");
-                return -1;
+        while (status == 0 && (entry = readdir(dir)) != NULL) {
+            if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0) {
+                continue;
             }
+            snprintf(source_child, sizeof(source_child), "%s/%s", source, entry->d_name);
+            snprintf(dest_child, sizeof(dest_child), "%s/%s", dest, entry->d_name);
+            status = copy_tree(source_child, dest_child);
         }
-        
-        // Clean up
-        close(source_fd);
-        close(dest_fd);
-        
-        // Copy succeeded, remove source file
-        if (unlink(source) != 0) {
-            perror("mv: failed to remove source file");
+        closedir(dir);
+    } else if (S_ISLNK(source_stat.st_mode)) {
+        char target[PATH_MAX];
+        ssize_t len = readlink(source, target, sizeof(target) - 1);
+        if (len < 0) {
+            fprintf(stderr, "mv: cannot read symbolic link '%s': %s\n", source, strerror(errno));
             return -1;
         }
-        
+        target[len] = '\0';
+        if (symlink(target, dest) != 0) {
+            fprintf(stderr, "mv: cannot create symbolic link '%s': %s\n", dest, strerror(errno));
+            return -1;
+        }
+    } else if (mknod(dest, source_stat.st_mode & S_IFMT, source_stat.st_rdev) != 0) {
+        fprintf(stderr, "mv: cannot create special file '%s': %s\n", dest, strerror(errno));
+        return -1;
+    }
+
+    if (status == 0 && !copy_interrupted) {
+        copy_metadata(source, dest, &source_stat);
+        if (track_links) {
+            remember_copied_link(&source_stat, dest);
+        }
+    }
+    return copy_interrupted ? -1 : status;
+}
+
+// Move SOURCE to DEST on another filesystem. The copy is built under a
+// temporary name next to DEST and renamed over it only once complete, so
+// an error or a signal part way through leaves both SOURCE and any old DEST
+// untouched; the source goes only after that.
+static int move_across_filesystems(const char *source, const char *dest) {
+    static const int signals[] = { SIGHUP, SIGINT, SIGQUIT, SIGPIPE, SIGTERM };
+    struct sigaction action, saved[sizeof(signals) / sizeof(signals[0])];
+    char *dest_copy = strdup(dest);
+    char temp[PATH_MAX];
+    int status;
+
+    snprintf(temp, sizeof(temp), "%s/.mv-%ld-%s", dirname(dest_copy), (long)getpid(),
+             strrchr(dest, '/') ? strrchr(dest, '/') + 1 : dest);
+    free(dest_copy);
+
+    // No SA_RESTART, so that a signal also cuts a blocked read short;
+    // signals that were ignored stay ignored
+    memset(&action, 0, sizeof(action));
+    action.sa_handler = note_interrupt;
+    sigemptyset(&action.sa_mask);
+    for (size_t i = 0; i < sizeof(signals) / sizeof(signals[0]); i++) {
+        sigaction(signals[i], &action, &saved[i]);
+        if (saved[i].sa_handler == SIG_IGN) {
+            sigaction(signals[i], &saved[i], NULL);
+        }
+    }
+
+    status = copy_tree(source, temp);
+    forget_copied_links();
+    if (status == 0 && rename(temp, dest) != 0) {
+        fprintf(stderr, "mv: cannot move '%s' to '%s': %s\n", source, dest, strerror(errno));
+        status = -1;
+    }
+    if (status != 0) {
+        remove_tree(temp);
+    }
+
+    for (size_t i = 0; i < sizeof(signals) / sizeof(signals[0]); i++) {
+        sigaction(signals[i], &saved[i], NULL);
+    }
+    if (copy_interrupted) {
+        progress_clear();
+        raise(copy_interrupted);
+        return -1;
+    }
+    if (status != 0) {
+        return -1;
+    }
+
+    // Copy succeeded, remove source
+    if (remove_tree(source) != 0) {
+        fprintf(stderr, "mv: cannot remove '%s': %s\n", source, strerror(errno));
+        return -1;
+    }
+    
+    if (verbose_flag) {
+        progress_clear();
+        printf("'%s' -> '%s'\n", source, dest);
+    }
+    return 0;
+}
+
+int move_file(const char *source, const char *dest) {
+    // Try simple rename first
+    if (rename(source, dest) == 0) {
         if (verbose_flag) {
             printf("'%s' -> '%s'\n", source, dest);
         }
         return 0;
     }
     
+    // If rename fails due to cross-device move, fallback to copy and delete
+    if (errno == EXDEV) {
+        return move_across_filesystems(source, dest);
+    }
+    
     perror("mv: rename failed");
     return -1;
 }
@@ -132,11 +421,17 @@ int main(int argc, char *argv[]) {
     int c;
     struct stat dest_stat;
     
+    // Only moves to another filesystem copy data and draw progress;
+    // renames take no time worth reporting
+    progress.mode = PROGRESS_AUTO;
+    
+    enum { PROGRESS_OPTION = 256 };
     static struct option long_options[] = {
         {"force", no_argument, 0, 'f'},
         {"interactive", no_argument, 0, 'i'},
         {"no-clobber", no_argument, 0, 'n'},
         {"verbose", no_argument, 0, 'v'},
+        {"progress", optional_argument, 0, PROGRESS_OPTION},
         {"version", no_argument, 0, 'V'},
         {"help", no_argument, 0, 'h'},
         {0, 0, 0, 0}
@@ -162,6 +457,19 @@ int main(int argc, char *argv[]) {
             case 'v':
                 verbose_flag = 1;
                 break;
+            case PROGRESS_OPTION:
+                if (optarg == NULL || strcmp(optarg, "always") == 0) {
+                    progress.mode = PROGRESS_ALWAYS;
+                } else if (strcmp(optarg, "auto") == 0) {
+                    progress.mode = PROGRESS_AUTO;
+                } else if (strcmp(optarg, "never") == 0) {
+                    progress.mode = PROGRESS_NEVER;
+                } else {
+                    fprintf(stderr, "mv: invalid argument '%s' for '--progress'\n", optarg);
+                    fprintf(stderr, "Valid arguments are: 'auto', 'always', 'never'\n");
+                    exit(EXIT_FAILURE);
+                }
+                break;
             case 'V':
                 print_version();
                 break;
@@ -189,7 +497,26 @@ int main(int argc, char *argv[]) {
         exit(EXIT_FAILURE);
     }
     
+    // Only moves to another filesystem copy anything, so only they count
+    // towards the total
+    if (progress.mode == PROGRESS_AUTO && !isatty(STDERR_FILENO)) {
+        progress.mode = PROGRESS_NEVER;
+    }
+    if (progress.mode != PROGRESS_NEVER) {
+        struct stat dir_stat;
+        char *dest_copy = strdup(dest);
+        const char *dest_dir = dest_is_dir ? dest : dirname(dest_copy);
+        if (stat(dest_dir, &dir_stat) == 0) {
+            for (int i = optind; i < argc - 1; i++) {
+                progress.total_bytes += copy_size(argv[i], dir_stat.st_dev);
+            }
+        }
+        free(dest_copy);
+    }
+    clock_gettime(CLOCK_MONOTONIC, &progress.start);
+    
     // Process each source file
+    int status = EXIT_SUCCESS;
     for (int i = optind; i < argc - 1; i++) {
         const char *source = argv[i];
         char *final_dest;
@@ -197,16 +524,18 @@ int main(int argc, char *argv[]) {
         // Skip if source doesn't exist
         if (access(source, F_OK) != 0) {
             fprintf(stderr, "mv: cannot stat '%s': No such file or directory\n", source);
+            status = EXIT_FAILURE;
             continue;
         }
         
         if (dest_is_dir) {
-            char *base = basename(strdup(source));
+            char *source_copy = strdup(source);
+            char *base = basename(source_copy);
             size_t dest_len = strlen(dest);
             size_t base_len = strlen(base);
             final_dest = malloc(dest_len + base_len + 2);
             sprintf(final_dest, "%s/%s", dest, base);
-            free(base);
+            free(source_copy);
         } else {
             final_dest = strdup(dest);
         }
@@ -242,10 +571,17 @@ int main(int argc, char *argv[]) {
         
         if (move_file(source, final_dest) != 0) {
             fprintf(stderr, "mv: failed to move '%s' to '%s'\n", source, final_dest);
+            status = EXIT_FAILURE;
         }
         
         free(final_dest);
     }
     
-    return EXIT_SUCCESS;
+    // Leave the final totals on screen
+    if (progress.shown) {
+        progress_draw();
+        fputc('\n', stderr);
+    }
+    
+    return status;
 }
\ No newline at end of file
@@ -0,0 +1,226 @@
+// ASD CoreUtils - nl
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, Command};
+use regex::bytes::Regex;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+enum Style {
+    All,
+    NonEmpty,
+    None,
+    Pattern(Regex),
+}
+
+impl Style {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "a" => Ok(Style::All),
+            "t" => Ok(Style::NonEmpty),
+            "n" => Ok(Style::None),
+            _ => match value.strip_prefix('p') {
+                Some(pattern) => Regex::new(pattern)
+                    .map(Style::Pattern)
+                    .map_err(|err| format!("invalid regular expression '{}': {}", pattern, err)),
+                None => Err(format!("invalid body numbering style: '{}'", value)),
+            },
+        }
+    }
+
+    fn numbers(&self, line: &[u8]) -> bool {
+        match self {
+            Style::All => true,
+            Style::NonEmpty => !line.is_empty(),
+            Style::None => false,
+            Style::Pattern(regex) => regex.is_match(line),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum NumberFormat {
+    LeftAligned,
+    RightAligned,
+    RightZeroes,
+}
+
+struct Options {
+    style: Style,
+    format: NumberFormat,
+    width: usize,
+    separator: String,
+    increment: i64,
+}
+
+fn main() {
+    let matches = Command::new("nl")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils nl - number lines of files")
+        .after_help(
+            "STYLE is one of:\n  \
+             a       number all lines\n  \
+             t       number only nonempty lines\n  \
+             n       number no lines\n  \
+             pBRE    number only lines that contain a match for the regular expression BRE\n\n\
+             FORMAT is one of:\n  \
+             ln      left justified, no leading zeros\n  \
+             rn      right justified, no leading zeros\n  \
+             rz      right justified, leading zeros",
+        )
+        .arg(
+            Arg::new("body-numbering")
+                .short('b')
+                .long("body-numbering")
+                .value_name("STYLE")
+                .default_value("t")
+                .help("Use STYLE for numbering body lines"),
+        )
+        .arg(
+            Arg::new("line-increment")
+                .short('i')
+                .long("line-increment")
+                .value_name("NUMBER")
+                .default_value("1")
+                .allow_negative_numbers(true)
+                .help("Line number increment at each line"),
+        )
+        .arg(
+            Arg::new("number-format")
+                .short('n')
+                .long("number-format")
+                .value_name("FORMAT")
+                .default_value("rn")
+                .value_parser(["ln", "rn", "rz"])
+                .help("Insert line numbers according to FORMAT"),
+        )
+        .arg(
+            Arg::new("number-separator")
+                .short('s')
+                .long("number-separator")
+                .value_name("STRING")
+                .default_value("\t")
+                .hide_default_value(true)
+                .help("Add STRING after (possible) line number"),
+        )
+        .arg(
+            Arg::new("starting-line-number")
+                .short('v')
+                .long("starting-line-number")
+                .value_name("NUMBER")
+                .default_value("1")
+                .allow_negative_numbers(true)
+                .help("First line number"),
+        )
+        .arg(
+            Arg::new("number-width")
+                .short('w')
+                .long("number-width")
+                .value_name("NUMBER")
+                .default_value("6")
+                .help("Use NUMBER columns for line numbers"),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to number, or - for standard input")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let style = Style::parse(matches.get_one::<String>("body-numbering").unwrap())
+        .unwrap_or_else(|err| fail("nl", &err));
+
+    let format = match matches.get_one::<String>("number-format").unwrap().as_str() {
+        "ln" => NumberFormat::LeftAligned,
+        "rz" => NumberFormat::RightZeroes,
+        _ => NumberFormat::RightAligned,
+    };
+
+    let increment = parse_number::<i64>(&matches, "line-increment", "invalid line number increment");
+    let start = parse_number::<i64>(&matches, "starting-line-number", "invalid starting line number");
+    let width = parse_number::<usize>(&matches, "number-width", "invalid line number field width");
+    if width == 0 {
+        fail("nl", "invalid line number field width: '0'");
+    }
+
+    let options = Options {
+        style,
+        format,
+        width,
+        separator: matches.get_one::<String>("number-separator").unwrap().clone(),
+        increment,
+    };
+
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut number = start;
+    let mut exit_code = 0;
+
+    // Numbering continues across files, as with GNU nl
+    for file in files {
+        let result = if file == "-" {
+            number_lines(&mut io::stdin().lock(), &mut out, &options, &mut number)
+        } else {
+            File::open(file).and_then(|f| number_lines(&mut BufReader::new(f), &mut out, &options, &mut number))
+        };
+
+        if let Err(err) = result {
+            eprintln!("nl: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("nl: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn parse_number<T: std::str::FromStr>(matches: &clap::ArgMatches, id: &str, message: &str) -> T {
+    let value = matches.get_one::<String>(id).unwrap();
+    value
+        .parse()
+        .unwrap_or_else(|_| fail("nl", &format!("{}: '{}'", message, value)))
+}
+
+fn number_lines(input: &mut impl BufRead, out: &mut impl Write, options: &Options, number: &mut i64) -> io::Result<()> {
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+
+        let content = line.strip_suffix(b"\n").unwrap_or(&line);
+
+        if options.style.numbers(content) {
+            let formatted = match options.format {
+                NumberFormat::LeftAligned => format!("{:<width$}", number, width = options.width),
+                NumberFormat::RightAligned => format!("{:>width$}", number, width = options.width),
+                NumberFormat::RightZeroes => format!("{:0width$}", number, width = options.width),
+            };
+            out.write_all(formatted.as_bytes())?;
+            out.write_all(options.separator.as_bytes())?;
+            *number += options.increment;
+        } else {
+            // Unnumbered lines are padded so their text stays aligned
+            let padding = options.width + options.separator.len();
+            out.write_all(" ".repeat(padding).as_bytes())?;
+        }
+
+        out.write_all(&line)?;
+    }
+}
@@ -0,0 +1,132 @@
+// ASD CoreUtils - nproc
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::fail;
+use clap::{Arg, ArgAction, Command};
+use std::fs;
+use std::mem;
+use std::path::Path;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+fn main() {
+    let matches = Command::new("nproc")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils nproc - print the number of processing units available to the current process")
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .help("Print the number of installed processors")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ignore")
+                .long("ignore")
+                .value_name("N")
+                .help("If possible, exclude N processing units"),
+        )
+        .get_matches();
+
+    let ignore = match matches.get_one::<String>("ignore") {
+        Some(value) => value
+            .parse::<usize>()
+            .unwrap_or_else(|_| fail("nproc", &format!("invalid number: '{}'", value))),
+        None => 0,
+    };
+
+    let count = if matches.get_flag("all") {
+        installed_processors()
+    } else {
+        available_processors()
+    };
+
+    // At least one unit is always reported
+    println!("{}", count.saturating_sub(ignore).max(1));
+}
+
+fn installed_processors() -> usize {
+    let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    if count > 0 {
+        count as usize
+    } else {
+        1
+    }
+}
+
+// The online processors, narrowed by the affinity mask and then by any
+// cgroup CPU quota, which caps usage without restricting the mask
+fn available_processors() -> usize {
+    let mut count = affinity_processors().unwrap_or_else(online_processors);
+
+    if let Some(quota) = cgroup_quota() {
+        count = count.min(quota);
+    }
+
+    count
+}
+
+fn online_processors() -> usize {
+    let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+    if count > 0 {
+        count as usize
+    } else {
+        1
+    }
+}
+
+fn affinity_processors() -> Option<usize> {
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return None;
+        }
+        match libc::CPU_COUNT(&set) {
+            0 => None,
+            count => Some(count as usize),
+        }
+    }
+}
+
+// Quota in whole CPUs, rounded up: cgroup v2 keeps "QUOTA PERIOD" in
+// cpu.max, v1 splits it over cpu.cfs_quota_us and cpu.cfs_period_us
+fn cgroup_quota() -> Option<usize> {
+    let membership = fs::read_to_string("/proc/self/cgroup").ok()?;
+
+    for line in membership.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (_, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+        let path = path.trim_start_matches('/');
+
+        let quota = if controllers.is_empty() {
+            let dir = Path::new(CGROUP_ROOT).join(path);
+            fs::read_to_string(dir.join("cpu.max")).ok().and_then(|max| {
+                let mut fields = max.split_whitespace();
+                let quota = fields.next()?.parse::<u64>().ok()?;
+                let period = fields.next()?.parse::<u64>().ok()?;
+                Some((quota, period))
+            })
+        } else if controllers.split(',').any(|controller| controller == "cpu") {
+            let dir = Path::new(CGROUP_ROOT).join(controllers).join(path);
+            let read = |name: &str| {
+                fs::read_to_string(dir.join(name))
+                    .ok()
+                    .and_then(|value| value.trim().parse::<i64>().ok())
+            };
+            match (read("cpu.cfs_quota_us"), read("cpu.cfs_period_us")) {
+                (Some(quota), Some(period)) if quota > 0 && period > 0 => Some((quota as u64, period as u64)),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        // "max" or -1 mean unlimited and fail to parse above
+        if let Some((quota, period)) = quota.filter(|&(_, period)| period > 0) {
+            return Some(quota.div_ceil(period).max(1) as usize);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,443 @@
+// ASD CoreUtils - numfmt
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, Command};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+const SUFFIXES: &[u8] = b"KMGTPEZY";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scale {
+    None,
+    Si,
+    Iec,
+    IecI,
+    Auto,
+}
+
+impl Scale {
+    fn parse(value: &str, allow_auto: bool) -> Option<Self> {
+        match value {
+            "none" => Some(Scale::None),
+            "si" => Some(Scale::Si),
+            "iec" => Some(Scale::Iec),
+            "iec-i" => Some(Scale::IecI),
+            "auto" if allow_auto => Some(Scale::Auto),
+            _ => None,
+        }
+    }
+
+    fn base(&self) -> f64 {
+        match self {
+            Scale::Si => 1000.0,
+            _ => 1024.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Round {
+    Up,
+    Down,
+    FromZero,
+    TowardsZero,
+    Nearest,
+}
+
+impl Round {
+    fn apply(&self, value: f64) -> f64 {
+        match self {
+            Round::Up => value.ceil(),
+            Round::Down => value.floor(),
+            Round::FromZero => {
+                if value < 0.0 {
+                    value.floor()
+                } else {
+                    value.ceil()
+                }
+            }
+            Round::TowardsZero => value.trunc(),
+            Round::Nearest => value.round(),
+        }
+    }
+}
+
+enum FieldRange {
+    All,
+    Span(usize, usize),
+}
+
+struct Options {
+    from: Scale,
+    to: Scale,
+    round: Round,
+    suffix: Option<String>,
+    padding: Option<isize>,
+    fields: Vec<FieldRange>,
+    delimiter: Option<char>,
+}
+
+impl Options {
+    fn selects(&self, field: usize) -> bool {
+        self.fields.iter().any(|range| match range {
+            FieldRange::All => true,
+            FieldRange::Span(start, end) => (*start..=*end).contains(&field),
+        })
+    }
+}
+
+fn main() {
+    let matches = Command::new("numfmt")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils numfmt - convert numbers from/to human-readable strings")
+        .after_help(
+            "UNIT options:\n  \
+             none     no auto-scaling is done; suffixes will trigger an error\n  \
+             auto     accept optional single/two letter suffix: 1K = 1000, 1Ki = 1024\n  \
+             si       accept optional single letter suffix: 1K = 1000, 1M = 1000000, ...\n  \
+             iec      accept optional single letter suffix: 1K = 1024, 1M = 1048576, ...\n  \
+             iec-i    accept optional two-letter suffix: 1Ki = 1024, 1Mi = 1048576, ...",
+        )
+        .arg(
+            Arg::new("delimiter")
+                .short('d')
+                .long("delimiter")
+                .value_name("X")
+                .help("Use X instead of whitespace for field delimiter"),
+        )
+        .arg(
+            Arg::new("field")
+                .long("field")
+                .value_name("FIELDS")
+                .default_value("1")
+                .help("Replace the numbers in these input fields (N, N-M, or a comma separated list)"),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_name("UNIT")
+                .default_value("none")
+                .help("Auto-scale input numbers to UNITs"),
+        )
+        .arg(
+            Arg::new("header")
+                .long("header")
+                .value_name("N")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("1")
+                .help("Print (without converting) the first N header lines"),
+        )
+        .arg(
+            Arg::new("padding")
+                .long("padding")
+                .value_name("N")
+                .allow_hyphen_values(true)
+                .help("Pad the output to N characters; positive N will right-align, negative N will left-align"),
+        )
+        .arg(
+            Arg::new("round")
+                .long("round")
+                .value_name("METHOD")
+                .default_value("from-zero")
+                .value_parser(["up", "down", "from-zero", "towards-zero", "nearest"])
+                .help("Use METHOD for rounding when scaling"),
+        )
+        .arg(
+            Arg::new("suffix")
+                .long("suffix")
+                .value_name("SUFFIX")
+                .help("Add SUFFIX to output numbers, and accept optional SUFFIX in input numbers"),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("UNIT")
+                .default_value("none")
+                .help("Auto-scale output numbers to UNITs"),
+        )
+        .arg(
+            Arg::new("number")
+                .value_name("NUMBER")
+                .help("Numbers to convert; lines of standard input are used if none are given")
+                .allow_negative_numbers(true)
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let scale = |id: &str, allow_auto: bool| {
+        let value = matches.get_one::<String>(id).unwrap();
+        Scale::parse(value, allow_auto).unwrap_or_else(|| {
+            fail("numfmt", &format!("invalid argument '{}' for '--{}'", value, id))
+        })
+    };
+
+    let round = match matches.get_one::<String>("round").unwrap().as_str() {
+        "up" => Round::Up,
+        "down" => Round::Down,
+        "towards-zero" => Round::TowardsZero,
+        "nearest" => Round::Nearest,
+        _ => Round::FromZero,
+    };
+
+    let padding = matches.get_one::<String>("padding").map(|value| match value.parse::<isize>() {
+        Ok(padding) if padding != 0 => padding,
+        _ => fail("numfmt", &format!("invalid padding value '{}'", value)),
+    });
+
+    let field_spec = matches.get_one::<String>("field").unwrap();
+    let fields = parse_fields(field_spec)
+        .unwrap_or_else(|| fail("numfmt", &format!("invalid field value '{}'", field_spec)));
+
+    let delimiter = matches.get_one::<String>("delimiter").map(|value| {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c,
+            _ => fail("numfmt", &format!("the delimiter must be a single character: '{}'", value)),
+        }
+    });
+
+    let header = match matches.get_one::<String>("header") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => fail("numfmt", &format!("invalid header value '{}'", value)),
+        },
+        None => 0,
+    };
+
+    let options = Options {
+        from: scale("from", true),
+        to: scale("to", false),
+        round,
+        suffix: matches.get_one::<String>("suffix").cloned(),
+        padding,
+        fields,
+        delimiter,
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let result = match matches.get_many::<String>("number") {
+        Some(numbers) => numbers.into_iter().try_for_each(|number| {
+            let converted = convert(number, &options)?;
+            writeln!(out, "{}", pad(&converted, options.padding)).map_err(|err| strerror(&err))
+        }),
+        None => convert_lines(&mut io::stdin().lock(), &mut out, &options, header),
+    };
+
+    // Conversion errors abort the run, as with GNU numfmt's default
+    // --invalid=abort
+    let flushed = out.flush();
+    if let Err(err) = result {
+        eprintln!("numfmt: {}", err);
+        process::exit(2);
+    }
+    if let Err(err) = flushed {
+        fail("numfmt", &strerror(&err));
+    }
+}
+
+fn parse_fields(spec: &str) -> Option<Vec<FieldRange>> {
+    spec.split(',')
+        .map(|item| {
+            if item == "-" {
+                return Some(FieldRange::All);
+            }
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (
+                    if start.is_empty() { 1 } else { start.parse().ok()? },
+                    if end.is_empty() { usize::MAX } else { end.parse().ok()? },
+                ),
+                None => {
+                    let field = item.parse().ok()?;
+                    (field, field)
+                }
+            };
+            (start > 0 && start <= end).then_some(FieldRange::Span(start, end))
+        })
+        .collect()
+}
+
+fn convert_lines(input: &mut impl BufRead, out: &mut impl Write, options: &Options, header: usize) -> Result<(), String> {
+    for (index, line) in input.lines().enumerate() {
+        let line = line.map_err(|err| strerror(&err))?;
+        let converted = if index < header {
+            line
+        } else {
+            convert_line(&line, options)?
+        };
+        writeln!(out, "{}", converted).map_err(|err| strerror(&err))?;
+    }
+    Ok(())
+}
+
+fn convert_line(line: &str, options: &Options) -> Result<String, String> {
+    let mut output = String::with_capacity(line.len());
+
+    match options.delimiter {
+        Some(delimiter) => {
+            for (index, field) in line.split(delimiter).enumerate() {
+                if index > 0 {
+                    output.push(delimiter);
+                }
+                if options.selects(index + 1) {
+                    output.push_str(&pad(&convert(field, options)?, options.padding));
+                } else {
+                    output.push_str(field);
+                }
+            }
+        }
+        None => {
+            // Each field carries the blanks in front of it, and a converted
+            // value is right-aligned in that width so columns stay put
+            let mut rest = line;
+            let mut index = 0;
+            while !rest.is_empty() {
+                let blanks = rest.len() - rest.trim_start().len();
+                let end = rest[blanks..].find(char::is_whitespace).map_or(rest.len(), |i| blanks + i);
+                let field = &rest[..end];
+                index += 1;
+
+                if options.selects(index) && end > blanks {
+                    let converted = convert(&field[blanks..], options)?;
+                    let padding = options.padding.unwrap_or(field.len() as isize);
+                    output.push_str(&pad(&converted, Some(padding)));
+                } else {
+                    output.push_str(field);
+                }
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+fn pad(value: &str, padding: Option<isize>) -> String {
+    match padding {
+        Some(width) if width < 0 => format!("{:<width$}", value, width = width.unsigned_abs()),
+        Some(width) => format!("{:>width$}", value, width = width as usize),
+        None => value.to_string(),
+    }
+}
+
+fn convert(text: &str, options: &Options) -> Result<String, String> {
+    let (value, precision) = parse_value(text, options)?;
+    let formatted = format_value(value, precision, options)?;
+
+    Ok(match &options.suffix {
+        Some(suffix) => format!("{}{}", formatted, suffix),
+        None => formatted,
+    })
+}
+
+// Returns the value and, for unscaled input, the number of decimal places
+// it was written with
+fn parse_value(text: &str, options: &Options) -> Result<(f64, Option<usize>), String> {
+    let trimmed = text.trim();
+    let body = match &options.suffix {
+        Some(suffix) => trimmed.strip_suffix(suffix.as_str()).unwrap_or(trimmed),
+        None => trimmed,
+    };
+
+    let bytes = body.as_bytes();
+    let mut end = usize::from(bytes.first() == Some(&b'-'));
+    let digits_start = end;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    let mut decimals = 0;
+    if end < bytes.len() && bytes[end] == b'.' {
+        end += 1;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+            decimals += 1;
+        }
+    }
+
+    if !bytes[digits_start..end].iter().any(u8::is_ascii_digit) {
+        return Err(format!("invalid number: '{}'", text));
+    }
+    let number: f64 = body[..end].parse().map_err(|_| format!("invalid number: '{}'", text))?;
+
+    let unit = &body[end..];
+    let missing_i = || format!("missing 'i' suffix in input: '{}' (e.g Ki/Mi/Gi)", text);
+    if unit.is_empty() {
+        if options.from == Scale::IecI {
+            return Err(missing_i());
+        }
+        return Ok((number, Some(decimals)));
+    }
+
+    let power = match SUFFIXES.iter().position(|&s| s == unit.as_bytes()[0]) {
+        Some(index) => index as i32 + 1,
+        None => return Err(format!("invalid suffix in input: '{}'", text)),
+    };
+    let extra = &unit[1..];
+
+    let base = match (options.from, extra) {
+        (Scale::None, _) => return Err(format!("rejecting suffix in input: '{}' (consider using --from)", text)),
+        (Scale::Si, "") => 1000.0,
+        (Scale::Iec, "") => 1024.0,
+        (Scale::IecI, "i") | (Scale::Auto, "i") => 1024.0,
+        (Scale::Auto, "") => 1000.0,
+        (Scale::IecI, _) => return Err(missing_i()),
+        _ => return Err(format!("invalid suffix in input '{}': '{}'", text, extra)),
+    };
+
+    Ok((number * f64::powi(base, power), None))
+}
+
+fn format_value(value: f64, precision: Option<usize>, options: &Options) -> Result<String, String> {
+    if options.to == Scale::None {
+        return Ok(match precision {
+            Some(places) if places > 0 => format!("{:.places$}", value),
+            _ => format!("{}", options.round.apply(value)),
+        });
+    }
+
+    let base = options.to.base();
+    // Small values get no suffix; below 10 they are rounded to tenths first
+    // and the printed integer is then the nearest one
+    if value.abs() < base {
+        if value.abs() < 10.0 {
+            return Ok(format!("{:.0}", options.round.apply(value * 10.0) / 10.0));
+        }
+        return Ok(format!("{}", options.round.apply(value)));
+    }
+
+    let mut power = 0;
+    while value.abs() / f64::powi(base, power + 1) >= 1.0 {
+        power += 1;
+    }
+
+    // Values below 10 in their unit keep one decimal place; anything that
+    // rounds up to a full unit moves on to the next suffix
+    loop {
+        if power as usize > SUFFIXES.len() {
+            return Err(format!("value too large to be printed: '{}'", value));
+        }
+
+        let unit = f64::powi(base, power);
+        let tenths = options.round.apply(value * 10.0 / unit);
+        let text = if tenths.abs() < 100.0 {
+            format!("{:.1}", tenths / 10.0)
+        } else {
+            let whole = options.round.apply(value / unit);
+            if whole.abs() >= base {
+                power += 1;
+                continue;
+            }
+            format!("{}", whole)
+        };
+
+        let suffix = SUFFIXES[power as usize - 1] as char;
+        let i = if options.to == Scale::IecI { "i" } else { "" };
+        return Ok(format!("{}{}{}", text, suffix, i));
+    }
+}
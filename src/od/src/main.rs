@@ -0,0 +1,666 @@
+// ASD CoreUtils - od
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use asd_coreutils_core::size::unit_size;
+use clap::{Arg, ArgAction, Command};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::process;
+
+const BYTES_PER_LINE: usize = 16;
+
+// Names -t a gives the control characters, indexed by their code
+const CONTROL_NAMES: [&str; 33] = [
+    "nul", "soh", "stx", "etx", "eot", "enq", "ack", "bel", "bs", "ht", "nl", "vt", "ff", "cr",
+    "so", "si", "dle", "dc1", "dc2", "dc3", "dc4", "nak", "syn", "etb", "can", "em", "sub", "esc",
+    "fs", "gs", "rs", "us", "sp",
+];
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Octal,
+    Hex,
+    Unsigned,
+    Signed,
+    Char,
+    // -t a: named characters, ignoring the high bit
+    Named,
+    Float,
+}
+
+#[derive(Clone, Copy)]
+struct Spec {
+    kind: Kind,
+    size: usize,
+    // A 'z' suffix: the bytes as text at the end of the line
+    show_text: bool,
+}
+
+// How -A shows the offset at the start of each line
+#[derive(Clone, Copy)]
+enum Radix {
+    Octal,
+    Decimal,
+    Hex,
+    None,
+}
+
+impl Radix {
+    fn width(&self) -> usize {
+        match self {
+            Radix::Octal | Radix::Decimal => 7,
+            Radix::Hex => 6,
+            Radix::None => 0,
+        }
+    }
+
+    fn render(&self, address: u64) -> String {
+        let width = self.width();
+        match self {
+            Radix::Octal => format!("{:0width$o}", address),
+            Radix::Decimal => format!("{:0width$}", address),
+            Radix::Hex => format!("{:0width$x}", address),
+            Radix::None => String::new(),
+        }
+    }
+}
+
+impl Spec {
+    // Widest rendering of a single value of this type
+    fn width(&self) -> usize {
+        match self.kind {
+            Kind::Octal => (self.size * 8).div_ceil(3),
+            Kind::Hex => self.size * 2,
+            Kind::Unsigned => match self.size {
+                1 => 3,
+                2 => 5,
+                4 => 10,
+                _ => 20,
+            },
+            Kind::Signed => match self.size {
+                1 => 4,
+                2 => 6,
+                4 => 11,
+                _ => 20,
+            },
+            Kind::Char | Kind::Named => 3,
+            Kind::Float if self.size == 4 => 15,
+            Kind::Float => 24,
+        }
+    }
+
+    fn render(&self, bytes: &[u8]) -> String {
+        let value = read_unsigned(bytes);
+        let width = self.width();
+
+        match self.kind {
+            Kind::Octal => format!("{:0width$o}", value),
+            Kind::Hex => format!("{:0width$x}", value),
+            Kind::Unsigned => format!("{:>width$}", value),
+            Kind::Signed => {
+                let shift = 64 - self.size * 8;
+                let signed = ((value << shift) as i64) >> shift;
+                format!("{:>width$}", signed)
+            }
+            Kind::Char => format!("{:>width$}", render_char(bytes[0])),
+            Kind::Named => format!("{:>width$}", render_named(bytes[0])),
+            Kind::Float if self.size == 4 => {
+                let float = f32::from_bits(value as u32);
+                let text = render_float(format!("{:e}", float), float.is_sign_negative(), 6);
+                format!("{:>width$}", text)
+            }
+            Kind::Float => {
+                let float = f64::from_bits(value);
+                let text = render_float(format!("{:e}", float), float.is_sign_negative(), 15);
+                format!("{:>width$}", text)
+            }
+        }
+    }
+}
+
+// Multi-byte values use the host byte order, as od always has
+fn read_unsigned(bytes: &[u8]) -> u64 {
+    let mut buffer = [0u8; 8];
+    if cfg!(target_endian = "little") {
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buffer)
+    } else {
+        buffer[8 - bytes.len()..].copy_from_slice(bytes);
+        u64::from_be_bytes(buffer)
+    }
+}
+
+fn render_char(byte: u8) -> String {
+    match byte {
+        0 => "\\0".to_string(),
+        7 => "\\a".to_string(),
+        8 => "\\b".to_string(),
+        9 => "\\t".to_string(),
+        10 => "\\n".to_string(),
+        11 => "\\v".to_string(),
+        12 => "\\f".to_string(),
+        13 => "\\r".to_string(),
+        0x20..=0x7e => (byte as char).to_string(),
+        _ => format!("{:03o}", byte),
+    }
+}
+
+fn render_named(byte: u8) -> String {
+    match byte & 0x7f {
+        0x7f => "del".to_string(),
+        code @ 0..=0x20 => CONTROL_NAMES[code as usize].to_string(),
+        code => (code as char).to_string(),
+    }
+}
+
+// As printf's "%.*g" shows a float, given in Rust's shortest exponent
+// form: with the fewest digits that read back as the same value, but never
+// fewer than DIGITS before switching to an exponent, as GNU od prints them
+fn render_float(scientific: String, negative: bool, digits: i32) -> String {
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        // "inf", "-inf" and "NaN", which has a sign too
+        return match scientific.as_str() {
+            "NaN" if negative => "-nan".to_string(),
+            "NaN" => "nan".to_string(),
+            _ => scientific,
+        };
+    };
+    let exponent: i32 = exponent.parse().unwrap();
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", mantissa),
+    };
+    let significant: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let precision = digits.max(significant.len() as i32);
+
+    if exponent < -4 || exponent >= precision {
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+        return format!("{}{}e{}{:02}", sign, mantissa, exponent_sign, exponent.abs());
+    }
+    if exponent < 0 {
+        let zeros = "0".repeat((-exponent - 1) as usize);
+        return format!("{}0.{}{}", sign, zeros, significant);
+    }
+    let whole = exponent as usize + 1;
+    if significant.len() <= whole {
+        format!("{}{}{}", sign, significant, "0".repeat(whole - significant.len()))
+    } else {
+        format!("{}{}.{}", sign, &significant[..whole], &significant[whole..])
+    }
+}
+
+// The bytes as -t ...z shows them after the values
+fn render_text(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x20..=0x7e => byte as char,
+            _ => '.',
+        })
+        .collect()
+}
+
+// Concatenates all operands into a single stream, as od treats them
+struct Input {
+    files: Vec<String>,
+    current: Option<Box<dyn Read>>,
+    failed: bool,
+}
+
+impl Input {
+    fn new(files: Vec<String>) -> Self {
+        Input {
+            files,
+            current: None,
+            failed: false,
+        }
+    }
+
+    // Fills BUFFER as far as possible, moving on to the next file at EOF
+    fn read_full(&mut self, buffer: &mut [u8]) -> usize {
+        let mut filled = 0;
+
+        while filled < buffer.len() {
+            if self.current.is_none() {
+                if self.files.is_empty() {
+                    break;
+                }
+                let name = self.files.remove(0);
+                self.current = open_file(&name);
+                if self.current.is_none() {
+                    self.failed = true;
+                    continue;
+                }
+            }
+
+            match self.current.as_mut().unwrap().read(&mut buffer[filled..]) {
+                Ok(0) => self.current = None,
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    eprintln!("od: read error: {}", strerror(&err));
+                    self.failed = true;
+                    self.current = None;
+                }
+            }
+        }
+
+        filled
+    }
+
+    // Reads and drops COUNT bytes; false if the input ends first
+    fn skip(&mut self, mut count: u64) -> bool {
+        let mut buffer = [0u8; 8192];
+        while count > 0 {
+            let wanted = count.min(buffer.len() as u64) as usize;
+            let got = self.read_full(&mut buffer[..wanted]);
+            if got == 0 {
+                return false;
+            }
+            count -= got as u64;
+        }
+        true
+    }
+}
+
+fn open_file(name: &str) -> Option<Box<dyn Read>> {
+    if name == "-" {
+        return Some(Box::new(io::stdin()));
+    }
+
+    match File::open(name) {
+        Ok(file) => Some(Box::new(file)),
+        Err(err) => {
+            eprintln!("od: {}: {}", name, strerror(&err));
+            None
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("od")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils od - dump files in octal and other formats")
+        .arg(
+            Arg::new("address-radix")
+                .short('A')
+                .long("address-radix")
+                .value_name("RADIX")
+                .help("Output format for file offsets; RADIX is one of [doxn], for Decimal, Octal, Hex or None"),
+        )
+        .arg(
+            Arg::new("skip-bytes")
+                .short('j')
+                .long("skip-bytes")
+                .value_name("BYTES")
+                .allow_hyphen_values(true)
+                .help("Skip BYTES input bytes first"),
+        )
+        .arg(
+            Arg::new("read-bytes")
+                .short('N')
+                .long("read-bytes")
+                .value_name("BYTES")
+                .allow_hyphen_values(true)
+                .help("Limit dump to BYTES input bytes"),
+        )
+        .arg(
+            Arg::new("format")
+                .short('t')
+                .long("format")
+                .value_name("TYPE")
+                .action(ArgAction::Append)
+                .help("Select output format or formats"),
+        )
+        .arg(
+            Arg::new("output-duplicates")
+                .short('v')
+                .long("output-duplicates")
+                .action(ArgAction::SetTrue)
+                .help("Do not use * to mark line suppression"),
+        )
+        .arg(format_flag("octal-bytes", 'b', "Select octal bytes, same as -t o1"))
+        .arg(format_flag("chars", 'c', "Select printable characters or backslash escapes, same as -t c"))
+        .arg(format_flag("unsigned-words", 'd', "Select unsigned decimal 2-byte units, same as -t u2"))
+        .arg(format_flag("octal-words", 'o', "Select octal 2-byte units (default), same as -t o2"))
+        .arg(format_flag("signed-words", 's', "Select decimal 2-byte units, same as -t d2"))
+        .arg(format_flag("hex-words", 'x', "Select hexadecimal 2-byte units, same as -t x2"))
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to dump, or - for standard input")
+                .num_args(0..),
+        )
+        .after_help(
+            "TYPE is made up of one or more of these specifications:\n  \
+             a          named character, ignoring high-order bit\n  \
+             c          printable character or backslash escape\n  \
+             d[SIZE]    signed decimal, SIZE bytes per integer\n  \
+             f[SIZE]    floating point, SIZE bytes per float\n  \
+             o[SIZE]    octal, SIZE bytes per integer\n  \
+             u[SIZE]    unsigned decimal, SIZE bytes per integer\n  \
+             x[SIZE]    hexadecimal, SIZE bytes per integer\n\n\
+             SIZE is a number. For TYPE in [doux], SIZE may also be C for sizeof(char),\n\
+             S for sizeof(short), I for sizeof(int) or L for sizeof(long). If TYPE is f,\n\
+             SIZE may also be F for sizeof(float) or D for sizeof(double).\n\n\
+             Adding a z suffix to any type displays printable characters at the end of\n\
+             each output line.\n\n\
+             BYTES is hex with 0x or 0X prefix, octal with a 0 prefix, and may have a\n\
+             multiplier suffix: b 512, KB 1000, K 1024, MB 1000*1000, M 1024*1024, and\n\
+             so on for G and T.",
+        )
+        .get_matches();
+
+    // Formats are printed in the order they were given on the command line
+    let mut chosen: Vec<(usize, Spec)> = [
+        ("octal-bytes", Kind::Octal, 1),
+        ("chars", Kind::Char, 1),
+        ("unsigned-words", Kind::Unsigned, 2),
+        ("octal-words", Kind::Octal, 2),
+        ("signed-words", Kind::Signed, 2),
+        ("hex-words", Kind::Hex, 2),
+    ]
+    .into_iter()
+    .filter(|(id, _, _)| matches.get_flag(id))
+    .map(|(id, kind, size)| {
+        let spec = Spec {
+            kind,
+            size,
+            show_text: false,
+        };
+        (matches.index_of(id).unwrap_or(0), spec)
+    })
+    .collect();
+    if let (Some(types), Some(indices)) = (
+        matches.get_many::<String>("format"),
+        matches.indices_of("format"),
+    ) {
+        for (text, index) in types.zip(indices) {
+            let specs = parse_type(text).unwrap_or_else(|message| fail("od", &message));
+            chosen.extend(specs.into_iter().map(|spec| (index, spec)));
+        }
+    }
+    // Stable, so the specs of one -t keep their order
+    chosen.sort_by_key(|(index, _)| *index);
+
+    let mut specs: Vec<Spec> = chosen.into_iter().map(|(_, spec)| spec).collect();
+    if specs.is_empty() {
+        specs.push(Spec {
+            kind: Kind::Octal,
+            size: 2,
+            show_text: false,
+        });
+    }
+
+    let radix = match matches.get_one::<String>("address-radix").map(String::as_str) {
+        None | Some("o") => Radix::Octal,
+        Some("d") => Radix::Decimal,
+        Some("x") => Radix::Hex,
+        Some("n") => Radix::None,
+        Some(other) => fail(
+            "od",
+            &format!(
+                "invalid output address radix '{}'; it must be one character from [doxn]",
+                other
+            ),
+        ),
+    };
+    let byte_count = |id: &str, option: &str| {
+        matches.get_one::<String>(id).map(|text| {
+            parse_byte_count(text)
+                .unwrap_or_else(|| fail("od", &format!("invalid {} argument '{}'", option, text)))
+        })
+    };
+    let skip = byte_count("skip-bytes", "-j").unwrap_or(0);
+    let limit = byte_count("read-bytes", "-N");
+
+    let files: Vec<String> = matches
+        .get_many::<String>("file")
+        .map(|files| files.cloned().collect())
+        .unwrap_or_else(|| vec!["-".to_string()]);
+
+    let mut input = Input::new(files);
+
+    if !input.skip(skip) {
+        fail("od", "cannot skip past end of combined input");
+    }
+
+    let options = DumpOptions {
+        radix,
+        start: skip,
+        limit,
+        duplicates: matches.get_flag("output-duplicates"),
+    };
+    if let Err(err) = dump(&mut input, &specs, &options) {
+        fail("od", &strerror(&err));
+    }
+
+    if input.failed {
+        process::exit(1);
+    }
+}
+
+// The specs of one -t TYPE, such as "x1z" or "d4u2c"
+fn parse_type(text: &str) -> Result<Vec<Spec>, String> {
+    let bytes = text.as_bytes();
+    let mut specs = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let letter = bytes[i];
+        i += 1;
+        let kind = match letter {
+            b'a' => Kind::Named,
+            b'c' => Kind::Char,
+            b'd' => Kind::Signed,
+            b'u' => Kind::Unsigned,
+            b'o' => Kind::Octal,
+            b'x' => Kind::Hex,
+            b'f' => Kind::Float,
+            _ => {
+                return Err(format!(
+                    "invalid character '{}' in type string '{}'",
+                    letter as char, text
+                ))
+            }
+        };
+
+        let size = match (letter, bytes.get(i)) {
+            (b'a' | b'c', _) => 1,
+            (_, Some(digit)) if digit.is_ascii_digit() => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|c| !c.is_ascii_digit())
+                    .map_or(bytes.len(), |offset| i + offset);
+                let size = text[i..end].parse().unwrap_or(usize::MAX);
+                i = end;
+                size
+            }
+            (b'f', Some(b'F')) => {
+                i += 1;
+                4
+            }
+            (b'f', Some(b'D')) => {
+                i += 1;
+                8
+            }
+            (b'f', Some(b'L')) => {
+                i += 1;
+                16
+            }
+            (b'f', _) => 8,
+            (_, Some(b'C')) => {
+                i += 1;
+                1
+            }
+            (_, Some(b'S')) => {
+                i += 1;
+                2
+            }
+            (_, Some(b'I')) => {
+                i += 1;
+                4
+            }
+            (_, Some(b'L')) => {
+                i += 1;
+                8
+            }
+            _ => 4,
+        };
+
+        let supported = match kind {
+            Kind::Float => matches!(size, 4 | 8),
+            _ => matches!(size, 1 | 2 | 4 | 8),
+        };
+        if !supported {
+            let what = if letter == b'f' {
+                "floating point"
+            } else {
+                "integral"
+            };
+            return Err(format!(
+                "invalid type string '{}';\nthis system doesn't provide a {}-byte {} type",
+                &text[start..],
+                size,
+                what
+            ));
+        }
+
+        let show_text = bytes.get(i) == Some(&b'z');
+        if show_text {
+            i += 1;
+        }
+        specs.push(Spec {
+            kind,
+            size,
+            show_text,
+        });
+    }
+    Ok(specs)
+}
+
+// A count for -j or -N: decimal, 0x hex or 0 octal, with an optional
+// multiplier suffix
+fn parse_byte_count(text: &str) -> Option<u64> {
+    let (digits, radix) = if let Some(hex) = text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+        (hex, 16)
+    } else if text.len() > 1 && text.starts_with('0') {
+        (&text[1..], 8)
+    } else {
+        (text, 10)
+    };
+    let end = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    if end == 0 {
+        return None;
+    }
+    let number = u64::from_str_radix(&digits[..end], radix).ok()?;
+
+    let multiplier = match &digits[end..] {
+        "b" => 512,
+        unit => unit_size(unit)?,
+    };
+    number.checked_mul(multiplier)
+}
+
+fn format_flag(id: &'static str, short: char, help: &'static str) -> Arg {
+    Arg::new(id).short(short).help(help).action(ArgAction::SetTrue)
+}
+
+struct DumpOptions {
+    radix: Radix,
+    // Offset of the first byte shown, after -j
+    start: u64,
+    limit: Option<u64>,
+    // -v: show repeated lines rather than a '*'
+    duplicates: bool,
+}
+
+fn dump(input: &mut Input, specs: &[Spec], options: &DumpOptions) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    // Every format gets the same line width so columns line up when several
+    // are shown; the narrower ones spread the difference over their fields
+    let line_width = |spec: &Spec| (BYTES_PER_LINE / spec.size) * (spec.width() + 1);
+    let widest = specs.iter().map(line_width).max().unwrap_or(0);
+    let address_width = options.radix.width();
+
+    let mut buffer = [0u8; BYTES_PER_LINE];
+    let mut previous: Option<[u8; BYTES_PER_LINE]> = None;
+    let mut suppressing = false;
+    let mut address = options.start;
+    let mut remaining = options.limit.unwrap_or(u64::MAX);
+
+    loop {
+        let wanted = remaining.min(BYTES_PER_LINE as u64) as usize;
+        let filled = input.read_full(&mut buffer[..wanted]);
+        if filled == 0 {
+            break;
+        }
+        remaining -= filled as u64;
+
+        // A full line the same as the one before is shown as a single '*'
+        if filled == BYTES_PER_LINE && !options.duplicates && previous == Some(buffer) {
+            if !suppressing {
+                writeln!(out, "*")?;
+                suppressing = true;
+            }
+            address += filled as u64;
+            continue;
+        }
+        suppressing = false;
+        previous = Some(buffer);
+
+        for (row, spec) in specs.iter().enumerate() {
+            if row == 0 {
+                write!(out, "{}", options.radix.render(address))?;
+            } else {
+                write!(out, "{:width$}", "", width = address_width)?;
+            }
+
+            let fields = BYTES_PER_LINE / spec.size;
+            let extra = widest - line_width(spec);
+
+            // A trailing partial value is zero-padded up to the type size
+            let mut chunk = [0u8; BYTES_PER_LINE];
+            chunk[..filled].copy_from_slice(&buffer[..filled]);
+
+            let mut written = 0;
+            for (index, value) in chunk[..filled.div_ceil(spec.size) * spec.size]
+                .chunks(spec.size)
+                .enumerate()
+            {
+                let pad = extra * (index + 1) / fields - extra * index / fields;
+                let field = spec.render(value);
+                write!(out, "{:width$}{}", "", field, width = pad + 1)?;
+                written += pad + 1 + field.len();
+            }
+            if spec.show_text {
+                write!(
+                    out,
+                    "{:width$}  >{}<",
+                    "",
+                    render_text(&buffer[..filled]),
+                    width = widest - written
+                )?;
+            }
+            writeln!(out)?;
+        }
+
+        address += filled as u64;
+        if filled < wanted {
+            break;
+        }
+    }
+
+    if address_width > 0 {
+        writeln!(out, "{}", options.radix.render(address))?;
+    }
+    out.flush()
+}
@@ -0,0 +1,175 @@
+// ASD CoreUtils - pathchk
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
+
+// Limits from POSIX, used with -p; the path limit includes the trailing NUL
+const POSIX_NAME_MAX: usize = 14;
+const POSIX_PATH_MAX: usize = 256;
+
+struct Checks {
+    portable: bool,
+    extra: bool,
+}
+
+fn main() {
+    let matches = Command::new("pathchk")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils pathchk - check whether file names are valid or portable")
+        .arg(
+            Arg::new("posix")
+                .short('p')
+                .help("Check for most POSIX systems")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("extra")
+                .short('P')
+                .help("Check for empty names and leading \"-\"")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("portability")
+                .long("portability")
+                .help("Check for all POSIX systems (equivalent to -p -P)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("name")
+                .value_name("NAME")
+                .help("File names to check")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let names: Vec<&String> = matches
+        .get_many::<String>("name")
+        .map(|names| names.collect())
+        .unwrap_or_default();
+
+    if names.is_empty() {
+        usage_error("pathchk", "missing operand");
+    }
+
+    let checks = Checks {
+        portable: matches.get_flag("posix") || matches.get_flag("portability"),
+        extra: matches.get_flag("extra") || matches.get_flag("portability"),
+    };
+
+    let mut exit_code = 0;
+
+    for name in names {
+        if let Err(message) = check(name, &checks) {
+            eprintln!("pathchk: {}", message);
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+fn check(name: &str, checks: &Checks) -> Result<(), String> {
+    if name.is_empty() && (checks.portable || checks.extra) {
+        return Err("empty file name".to_string());
+    }
+
+    if checks.extra && name.split('/').any(|component| component.starts_with('-')) {
+        return Err(format!("leading '-' in a component of file name '{}'", name));
+    }
+
+    if checks.portable {
+        if let Some(byte) = name.bytes().find(|&b| !is_portable(b)) {
+            return Err(format!(
+                "nonportable character '{}' in file name '{}'",
+                describe_byte(byte),
+                name
+            ));
+        }
+    }
+
+    // Without -p the limits are those of the file system the name would
+    // live on; a name that already exists is valid by definition
+    let (name_max, path_max) = if checks.portable {
+        (POSIX_NAME_MAX, POSIX_PATH_MAX - 1)
+    } else {
+        match fs::symlink_metadata(name) {
+            Ok(_) => return Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !name.is_empty() => {}
+            Err(err) => return Err(format!("{}: {}", name, strerror(&err))),
+        }
+        system_limits(name)
+    };
+
+    if name.len() > path_max {
+        return Err(format!(
+            "limit {} exceeded by length {} of file name '{}'",
+            path_max,
+            name.len(),
+            name
+        ));
+    }
+
+    if let Some(component) = name.split('/').find(|component| component.len() > name_max) {
+        return Err(format!(
+            "limit {} exceeded by length {} of file name component '{}'",
+            name_max,
+            component.len(),
+            component
+        ));
+    }
+
+    Ok(())
+}
+
+// The POSIX portable filename character set
+fn is_portable(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'-' | b'/')
+}
+
+fn describe_byte(byte: u8) -> String {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        (byte as char).to_string()
+    } else {
+        format!("\\{:03o}", byte)
+    }
+}
+
+// NAME_MAX and PATH_MAX as reported for the deepest existing directory on
+// the way to NAME
+fn system_limits(name: &str) -> (usize, usize) {
+    let mut dir = Path::new(name).parent();
+    while let Some(candidate) = dir {
+        if candidate.as_os_str().is_empty() || candidate.is_dir() {
+            break;
+        }
+        dir = candidate.parent();
+    }
+
+    let dir = match dir {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_string_lossy().into_owned(),
+        _ => ".".to_string(),
+    };
+
+    let limit = |setting, default: usize| {
+        let Ok(path) = CString::new(dir.as_str()) else {
+            return default;
+        };
+        match unsafe { libc::pathconf(path.as_ptr(), setting) } {
+            value if value > 0 => value as usize,
+            _ => default,
+        }
+    };
+
+    (
+        limit(libc::_PC_NAME_MAX, 255),
+        limit(libc::_PC_PATH_MAX, 4096) - 1,
+    )
+}
@@ -0,0 +1,301 @@
+// ASD CoreUtils - pinky
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use chrono::{Local, TimeZone};
+use clap::{Arg, ArgAction, Command};
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::mem;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::ptr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TIME_FORMAT: &str = "%b %e %H:%M";
+const TIME_WIDTH: usize = 12;
+
+struct ShortOptions {
+    heading: bool,
+    full_name: bool,
+    idle: bool,
+    host: bool,
+}
+
+struct LongOptions {
+    home_and_shell: bool,
+    project: bool,
+    plan: bool,
+}
+
+// The parts of a utmp login record pinky shows
+struct Session {
+    user: String,
+    line: String,
+    host: String,
+    login_time: i64,
+}
+
+struct Account {
+    name: String,
+    full_name: String,
+    home: String,
+    shell: String,
+}
+
+fn main() {
+    let matches = Command::new("pinky")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils pinky - a lightweight finger: print user information")
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .arg(flag("long", 'l', "Produce long format output for the specified USERs"))
+        .arg(flag("omit-home-shell", 'b', "Omit the user's home directory and shell in long format"))
+        .arg(flag("omit-project", 'h', "Omit the user's project file in long format"))
+        .arg(flag("omit-plan", 'p', "Omit the user's plan file in long format"))
+        .arg(flag("short", 's', "Do short format output, this is the default"))
+        .arg(flag("omit-heading", 'f', "Omit the line of column headings in short format"))
+        .arg(flag("omit-name", 'w', "Omit the user's full name in short format"))
+        .arg(flag("omit-name-host", 'i', "Omit the user's full name and remote host in short format"))
+        .arg(flag(
+            "omit-name-host-idle",
+            'q',
+            "Omit the user's full name, remote host and idle time in short format",
+        ))
+        .arg(
+            Arg::new("user")
+                .value_name("USER")
+                .help("Users to show; all logged in users by default")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let users: Vec<&String> = matches
+        .get_many::<String>("user")
+        .map(|users| users.collect())
+        .unwrap_or_default();
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let result = if matches.get_flag("long") {
+        if users.is_empty() {
+            usage_error(
+                "pinky",
+                "no username specified; at least one must be specified when using -l",
+            );
+        }
+        let options = LongOptions {
+            home_and_shell: !matches.get_flag("omit-home-shell"),
+            project: !matches.get_flag("omit-project"),
+            plan: !matches.get_flag("omit-plan"),
+        };
+        users.iter().try_for_each(|user| write_long(&mut out, user, &options))
+    } else {
+        let quiet = matches.get_flag("omit-name-host-idle");
+        let no_host = quiet || matches.get_flag("omit-name-host");
+        let options = ShortOptions {
+            heading: !matches.get_flag("omit-heading"),
+            full_name: !(no_host || matches.get_flag("omit-name")),
+            idle: !quiet,
+            host: !no_host,
+        };
+        write_short(&mut out, &users, &options)
+    };
+
+    if let Err(err) = result.and_then(|()| out.flush()) {
+        fail("pinky", &strerror(&err));
+    }
+}
+
+fn flag(id: &'static str, short: char, help: &'static str) -> Arg {
+    Arg::new(id).short(short).help(help).action(ArgAction::SetTrue)
+}
+
+fn write_short(out: &mut impl Write, users: &[&String], options: &ShortOptions) -> io::Result<()> {
+    if options.heading {
+        write!(out, "{:<8}", "Login")?;
+        if options.full_name {
+            write!(out, " {:<19}", "Name")?;
+        }
+        write!(out, " {:<9}", " TTY")?;
+        if options.idle {
+            write!(out, " {:<6}", "Idle")?;
+        }
+        write!(out, " {:<width$}", "When", width = TIME_WIDTH)?;
+        if options.host {
+            write!(out, " Where")?;
+        }
+        writeln!(out)?;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64);
+
+    for session in read_sessions() {
+        if !users.is_empty() && !users.iter().any(|user| **user == session.user) {
+            continue;
+        }
+
+        write!(out, "{:<8}", session.user)?;
+
+        if options.full_name {
+            let full_name = lookup_account(&session.user).map_or_else(|| "???".to_string(), |a| a.full_name);
+            let truncated: String = full_name.chars().take(19).collect();
+            write!(out, " {:<19}", truncated)?;
+        }
+
+        // '*' marks terminals that refuse messages (no group write)
+        let device = Path::new("/dev").join(&session.line);
+        let metadata = fs::metadata(&device).ok();
+        let mesg = match &metadata {
+            Some(metadata) if metadata.permissions().mode() & 0o020 != 0 => ' ',
+            Some(_) => '*',
+            None => '?',
+        };
+        write!(out, " {}{:<8}", mesg, session.line)?;
+
+        if options.idle {
+            let idle = match &metadata {
+                Some(metadata) => idle_string(now - metadata.atime()),
+                None => "???".to_string(),
+            };
+            write!(out, " {:<6}", idle)?;
+        }
+
+        let when = Local
+            .timestamp_opt(session.login_time, 0)
+            .single()
+            .map_or_else(String::new, |time| time.format(TIME_FORMAT).to_string());
+        write!(out, " {}", when)?;
+
+        if options.host && !session.host.is_empty() {
+            write!(out, " {}", session.host)?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+fn idle_string(seconds: i64) -> String {
+    if seconds < 60 {
+        "     ".to_string()
+    } else if seconds < 24 * 60 * 60 {
+        format!("{:02}:{:02}", seconds / 3600, seconds % 3600 / 60)
+    } else {
+        format!("{}d", seconds / (24 * 60 * 60))
+    }
+}
+
+fn write_long(out: &mut impl Write, user: &str, options: &LongOptions) -> io::Result<()> {
+    let Some(account) = lookup_account(user) else {
+        writeln!(out, "Login name: {:<28}In real life:  ???", user)?;
+        return Ok(());
+    };
+
+    writeln!(out, "Login name: {:<28}In real life:  {}", account.name, account.full_name)?;
+
+    if options.home_and_shell {
+        writeln!(out, "Directory: {:<29}Shell:  {}", account.home, account.shell)?;
+    }
+
+    let home = Path::new(&account.home);
+    if options.project {
+        if let Ok(project) = fs::read(home.join(".project")) {
+            write!(out, "Project: ")?;
+            out.write_all(&project)?;
+        }
+    }
+    if options.plan {
+        if let Ok(plan) = fs::read(home.join(".plan")) {
+            writeln!(out, "Plan:")?;
+            out.write_all(&plan)?;
+        }
+    }
+
+    writeln!(out)
+}
+
+fn read_sessions() -> Vec<Session> {
+    let mut sessions = Vec::new();
+
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+            let entry = &*entry;
+            if entry.ut_type != libc::USER_PROCESS {
+                continue;
+            }
+            sessions.push(Session {
+                user: c_field(&entry.ut_user),
+                line: c_field(&entry.ut_line),
+                host: c_field(&entry.ut_host),
+                login_time: entry.ut_tv.tv_sec as i64,
+            });
+        }
+        libc::endutxent();
+    }
+
+    sessions
+}
+
+// utmp fields are fixed-size arrays that are only NUL-terminated when
+// shorter than the array
+fn c_field(field: &[libc::c_char]) -> String {
+    let bytes: Vec<u8> = field.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn lookup_account(name: &str) -> Option<Account> {
+    let c_name = CString::new(name).ok()?;
+
+    unsafe {
+        let mut pwd: libc::passwd = mem::zeroed();
+        let mut result: *mut libc::passwd = ptr::null_mut();
+        let mut buffer = vec![0; 16384];
+
+        libc::getpwnam_r(c_name.as_ptr(), &mut pwd, buffer.as_mut_ptr(), buffer.len(), &mut result);
+        if result.is_null() {
+            return None;
+        }
+
+        let text = |field: *const libc::c_char| CStr::from_ptr(field).to_string_lossy().into_owned();
+        let login = text(pwd.pw_name);
+        let gecos = text(pwd.pw_gecos);
+
+        Some(Account {
+            full_name: full_name(&gecos, &login),
+            name: login,
+            home: text(pwd.pw_dir),
+            shell: text(pwd.pw_shell),
+        })
+    }
+}
+
+// The first GECOS field, with '&' standing for the capitalized login name
+fn full_name(gecos: &str, login: &str) -> String {
+    let name = gecos.split(',').next().unwrap_or("");
+    if !name.contains('&') {
+        return name.to_string();
+    }
+
+    let mut capitalized = login.to_string();
+    if let Some(first) = capitalized.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    name.replace('&', &capitalized)
+}
@@ -0,0 +1,344 @@
+// ASD CoreUtils - pr
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use chrono::{DateTime, Local};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+const DEFAULT_PAGE_LENGTH: usize = 66;
+const PAGE_WIDTH: usize = 72;
+const HEADER_LINES: usize = 5;
+const TRAILER_LINES: usize = 5;
+const TAB_WIDTH: usize = 8;
+
+struct Options {
+    page_length: usize,
+    columns: usize,
+    omit_header: bool,
+    header: Option<String>,
+}
+
+impl Options {
+    // Pages too short to hold a header and trailer are printed without them
+    fn has_header(&self) -> bool {
+        !self.omit_header && self.page_length > HEADER_LINES + TRAILER_LINES
+    }
+
+    fn body_lines(&self) -> usize {
+        if self.has_header() {
+            self.page_length - HEADER_LINES - TRAILER_LINES
+        } else {
+            self.page_length
+        }
+    }
+
+    // Columns share the page width, with one position between them
+    fn column_width(&self) -> usize {
+        (PAGE_WIDTH - (self.columns - 1)) / self.columns
+    }
+}
+
+fn main() {
+    // "-COLUMN" can't be described to clap, so it is rewritten first
+    let args: Vec<String> = env::args()
+        .map(|arg| match arg.strip_prefix('-') {
+            Some(digits) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+                format!("--columns={}", digits)
+            }
+            _ => arg,
+        })
+        .collect();
+
+    let matches = Command::new("pr")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils pr - paginate or columnate files for printing")
+        .override_usage("pr [OPTIONS] [-COLUMN] [FILE]...")
+        .arg(
+            Arg::new("columns")
+                .long("columns")
+                .value_name("COLUMN")
+                .help("Output COLUMN columns, filled down (also -COLUMN)"),
+        )
+        .arg(
+            Arg::new("header")
+                .short('h')
+                .long("header")
+                .value_name("HEADER")
+                .help("Use a centered HEADER instead of the file name in the page header"),
+        )
+        .arg(
+            Arg::new("length")
+                .short('l')
+                .long("length")
+                .value_name("PAGE_LENGTH")
+                .help("Set the page length to PAGE_LENGTH lines (default: 66)"),
+        )
+        .arg(
+            Arg::new("omit-header")
+                .short('t')
+                .long("omit-header")
+                .help("Omit page headers and trailers")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to print, or - for standard input")
+                .num_args(0..),
+        )
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .get_matches_from(args);
+
+    let page_length = parse_count(matches.get_one::<String>("length"), DEFAULT_PAGE_LENGTH, "invalid page length");
+    let columns = parse_count(matches.get_one::<String>("columns"), 1, "invalid number of columns");
+    if columns > PAGE_WIDTH / 2 {
+        fail("pr", "page width too narrow");
+    }
+
+    let options = Options {
+        page_length,
+        columns,
+        omit_header: matches.get_flag("omit-header"),
+        header: matches.get_one::<String>("header").cloned(),
+    };
+
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files {
+        let result = if file == "-" {
+            paginate(&mut io::stdin().lock(), &mut out, &options, "", Local::now())
+        } else {
+            File::open(file).and_then(|f| {
+                let modified = DateTime::from(f.metadata()?.modified()?);
+                paginate(&mut BufReader::new(f), &mut out, &options, file, modified)
+            })
+        };
+
+        if let Err(err) = result {
+            let _ = out.flush();
+            eprintln!("pr: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("pr: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn parse_count(value: Option<&String>, default: usize, message: &str) -> usize {
+    match value {
+        None => default,
+        Some(value) => match value.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => fail("pr", &format!("{}: '{}'", message, value)),
+        },
+    }
+}
+
+fn paginate(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    options: &Options,
+    name: &str,
+    date: DateTime<Local>,
+) -> io::Result<()> {
+    let body = options.body_lines();
+    let capacity = body * options.columns;
+    let title = options.header.as_deref().unwrap_or(name);
+    let date = date.format("%Y-%m-%d %H:%M").to_string();
+    let mut page_number = 1;
+
+    loop {
+        let lines = read_lines(input, capacity)?;
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        // A short final page has its columns balanced instead of filling
+        // the first one to the bottom
+        let rows = if lines.len() < capacity {
+            lines.len().div_ceil(options.columns)
+        } else {
+            body
+        };
+
+        if options.has_header() {
+            write!(out, "\n\n{}\n\n\n", header_line(&date, title, page_number))?;
+        }
+
+        // Leftover lines go to the leftmost columns, one each
+        let shortest = lines.len() / options.columns;
+        let extra = lines.len() % options.columns;
+        for row in 0..rows {
+            let cells: Vec<&[u8]> = (0..options.columns)
+                .filter(|&column| row < shortest + usize::from(column < extra))
+                .map(|column| lines[column * shortest + column.min(extra) + row].as_slice())
+                .collect();
+            write_row(out, &cells, options)?;
+        }
+
+        if options.has_header() {
+            out.write_all(&b"\n".repeat(body - rows + TRAILER_LINES))?;
+        }
+
+        if lines.len() < capacity {
+            return Ok(());
+        }
+        page_number += 1;
+    }
+}
+
+fn read_lines(input: &mut impl BufRead, limit: usize) -> io::Result<Vec<Vec<u8>>> {
+    let mut lines = Vec::new();
+
+    while lines.len() < limit {
+        let mut line = Vec::new();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        lines.push(line);
+    }
+
+    Ok(lines)
+}
+
+// Date on the left, page number on the right and the title centered in
+// the space between
+fn header_line(date: &str, title: &str, page_number: usize) -> String {
+    let page = format!("Page {}", page_number);
+    let used = date.chars().count() + title.chars().count() + page.len();
+    let available = PAGE_WIDTH.saturating_sub(used).max(2);
+    let left = available / 2;
+
+    format!(
+        "{}{:left$}{}{:right$}{}",
+        date,
+        "",
+        title,
+        "",
+        page,
+        left = left,
+        right = available - left
+    )
+}
+
+fn write_row(out: &mut impl Write, cells: &[&[u8]], options: &Options) -> io::Result<()> {
+    if options.columns == 1 {
+        out.write_all(cells[0])?;
+        return out.write_all(b"\n");
+    }
+
+    let width = options.column_width();
+    let mut line = Line::default();
+
+    for (index, cell) in cells.iter().enumerate() {
+        let start = index * (width + 1);
+        if index > 0 {
+            line.pending = start - line.position;
+            line.flush_blanks(out)?;
+        }
+        line.write_cell(out, cell, start, width)?;
+    }
+
+    // Blanks at the end of a row are never written
+    out.write_all(b"\n")
+}
+
+// Output position within a row; blanks are held back until something
+// visible follows, then written with tabs where they fit
+#[derive(Default)]
+struct Line {
+    position: usize,
+    pending: usize,
+}
+
+impl Line {
+    fn flush_blanks(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let goal = self.position + self.pending;
+
+        while goal - self.position > 1 {
+            let next = (self.position / TAB_WIDTH + 1) * TAB_WIDTH;
+            if next > goal {
+                break;
+            }
+            out.write_all(b"\t")?;
+            self.position = next;
+        }
+        out.write_all(&b" ".repeat(goal - self.position))?;
+
+        self.position = goal;
+        self.pending = 0;
+        Ok(())
+    }
+
+    // Writes as much of CELL as fits in WIDTH positions from START; input
+    // tabs are expanded relative to the column
+    fn write_cell(&mut self, out: &mut impl Write, cell: &[u8], start: usize, width: usize) -> io::Result<()> {
+        let mut column = 0;
+
+        for (index, &byte) in cell.iter().enumerate() {
+            let next = match byte {
+                b'\t' => (column / TAB_WIDTH + 1) * TAB_WIDTH,
+                b'\x08' => column.saturating_sub(1),
+                0x80..=0xbf => column,
+                _ => column + 1,
+            };
+            if next > width {
+                break;
+            }
+
+            match byte {
+                b' ' | b'\t' => self.pending += next - column,
+                b'\x08' => {
+                    self.flush_blanks(out)?;
+                    out.write_all(&[byte])?;
+                    self.position = start + next;
+                }
+                _ => {
+                    self.flush_blanks(out)?;
+                    out.write_all(&[byte])?;
+                    if next > column {
+                        self.position += 1;
+                    }
+                    // Keep a character's continuation bytes with it even
+                    // when the column is full
+                    if next == width {
+                        let rest = &cell[index + 1..];
+                        let tail = rest.iter().take_while(|&&b| (0x80..0xc0).contains(&b)).count();
+                        out.write_all(&rest[..tail])?;
+                        break;
+                    }
+                }
+            }
+            column = next;
+        }
+
+        Ok(())
+    }
+}
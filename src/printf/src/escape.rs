@@ -0,0 +1,108 @@
+// ASD CoreUtils - printf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Backslash escapes, in the format and in %b arguments. The two differ only
+// in octal: the format takes "\NNN", %b also "\0NNN".
+
+pub enum Escape {
+    Continue,
+    // \c: no further output at all
+    Stop,
+}
+
+// Interprets the escape whose backslash is just before TEXT[*I], appending
+// what it stands for to OUT and moving I past it
+pub fn unescape(
+    text: &[u8],
+    i: &mut usize,
+    out: &mut Vec<u8>,
+    octal_zero: bool,
+) -> Result<Escape, String> {
+    let Some(&c) = text.get(*i) else {
+        out.push(b'\\');
+        return Ok(Escape::Continue);
+    };
+    *i += 1;
+
+    match c {
+        b'x' => {
+            let value = digits(text, i, 16, 2);
+            match value {
+                Some(value) => out.push(value as u8),
+                None => return Err("missing hexadecimal number in escape".to_string()),
+            }
+        }
+        b'0'..=b'7' => {
+            if !(octal_zero && c == b'0') {
+                *i -= 1;
+            }
+            out.push(digits(text, i, 8, 3).unwrap_or(0) as u8);
+        }
+        b'u' | b'U' => {
+            let length = if c == b'u' { 4 } else { 8 };
+            // Exactly that many digits
+            if count_hex(text, *i) < length {
+                return Err("missing hexadecimal number in escape".to_string());
+            }
+            let value = digits(text, i, 16, length).unwrap_or(0);
+            out.extend_from_slice(
+                universal(c, value, length)?
+                    .encode_utf8(&mut [0; 4])
+                    .as_bytes(),
+            );
+        }
+        b'"' => out.push(b'"'),
+        b'\\' => out.push(b'\\'),
+        b'a' => out.push(0x07),
+        b'b' => out.push(0x08),
+        b'c' => return Ok(Escape::Stop),
+        b'e' => out.push(0x1b),
+        b'f' => out.push(0x0c),
+        b'n' => out.push(b'\n'),
+        b'r' => out.push(b'\r'),
+        b't' => out.push(b'\t'),
+        b'v' => out.push(0x0b),
+        // Anything else is left as it is
+        other => {
+            out.push(b'\\');
+            out.push(other);
+        }
+    }
+    Ok(Escape::Continue)
+}
+
+// Up to MAX digits in RADIX at I, or None if there are none
+fn digits(text: &[u8], i: &mut usize, radix: u32, max: usize) -> Option<u32> {
+    let mut value = None;
+    for _ in 0..max {
+        let Some(digit) = text.get(*i).and_then(|&c| (c as char).to_digit(radix)) else {
+            break;
+        };
+        value = Some(value.unwrap_or(0) * radix + digit);
+        *i += 1;
+    }
+    value
+}
+
+fn count_hex(text: &[u8], from: usize) -> usize {
+    text[from..]
+        .iter()
+        .take_while(|c| c.is_ascii_hexdigit())
+        .count()
+}
+
+// The character a \u or \U names, which C99 limits to those outside the
+// basic character set, bar '$', '@' and '`'
+fn universal(c: u8, value: u32, length: usize) -> Result<char, String> {
+    let basic = value < 0xa0 && value != 0x24 && value != 0x40 && value != 0x60;
+    match char::from_u32(value) {
+        Some(character) if !basic => Ok(character),
+        _ => Err(format!(
+            "invalid universal character name \\{}{:0width$x}",
+            c as char,
+            value,
+            width = length
+        )),
+    }
+}
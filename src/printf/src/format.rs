@@ -0,0 +1,317 @@
+// ASD CoreUtils - printf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Turning one converted argument into text, as C's printf would for the
+// same directive.
+
+#[derive(Default)]
+pub struct Spec {
+    pub left: bool,
+    pub sign: bool,
+    pub space: bool,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: usize,
+    pub precision: Option<usize>,
+    pub conversion: u8,
+}
+
+impl Spec {
+    // TEXT padded out to the width with spaces, or, for numbers with the
+    // '0' flag, with zeros after the first PREFIX bytes (the sign and 0x)
+    fn pad(&self, text: Vec<u8>, prefix: usize, zeros: bool) -> Vec<u8> {
+        let fill = self.width.saturating_sub(text.len());
+        if fill == 0 {
+            return text;
+        }
+        let mut padded = Vec::with_capacity(self.width);
+        if self.left {
+            padded.extend_from_slice(&text);
+            padded.resize(self.width, b' ');
+        } else if zeros {
+            padded.extend_from_slice(&text[..prefix]);
+            padded.resize(prefix + fill, b'0');
+            padded.extend_from_slice(&text[prefix..]);
+        } else {
+            padded.resize(fill, b' ');
+            padded.extend_from_slice(&text);
+        }
+        padded
+    }
+
+    pub fn string(&self, text: &[u8]) -> Vec<u8> {
+        let text = match self.precision {
+            Some(precision) => &text[..precision.min(text.len())],
+            None => text,
+        };
+        self.pad(text.to_vec(), 0, false)
+    }
+
+    fn sign_of(&self, negative: bool) -> &'static str {
+        if negative {
+            "-"
+        } else if self.sign {
+            "+"
+        } else if self.space {
+            " "
+        } else {
+            ""
+        }
+    }
+
+    pub fn signed(&self, value: i64) -> Vec<u8> {
+        self.integer(self.sign_of(value < 0), value.unsigned_abs())
+    }
+
+    pub fn unsigned(&self, value: u64) -> Vec<u8> {
+        self.integer("", value)
+    }
+
+    fn integer(&self, sign: &str, value: u64) -> Vec<u8> {
+        let mut digits = match self.conversion {
+            b'o' => format!("{:o}", value),
+            b'x' => format!("{:x}", value),
+            b'X' => format!("{:X}", value),
+            _ => value.to_string(),
+        };
+        // A precision is the least number of digits; zero itself may then
+        // have none at all
+        if let Some(precision) = self.precision {
+            if precision == 0 && value == 0 {
+                digits.clear();
+            }
+            if digits.len() < precision {
+                digits = format!("{}{}", "0".repeat(precision - digits.len()), digits);
+            }
+        }
+        let prefix = match self.conversion {
+            b'o' if self.alternate && !digits.starts_with('0') => "0",
+            b'x' if self.alternate && value != 0 => "0x",
+            b'X' if self.alternate && value != 0 => "0X",
+            _ => "",
+        };
+        let text = format!("{}{}{}", sign, prefix, digits);
+        let zeros = self.zero && self.precision.is_none();
+        self.pad(text.into_bytes(), sign.len() + prefix.len(), zeros)
+    }
+
+    pub fn float(&self, value: f64) -> Vec<u8> {
+        let precision = self.precision.unwrap_or(6);
+        let magnitude = value.abs();
+        let mut body = if value.is_nan() {
+            "nan".to_string()
+        } else if value.is_infinite() {
+            "inf".to_string()
+        } else {
+            match self.conversion.to_ascii_lowercase() {
+                b'f' => fixed(magnitude, precision, self.alternate),
+                b'e' => exponential(magnitude, precision, self.alternate),
+                b'a' => hexadecimal(magnitude, self.precision, self.alternate),
+                _ => general(magnitude, precision, self.alternate),
+            }
+        };
+        if self.conversion.is_ascii_uppercase() {
+            body = body.to_ascii_uppercase();
+        }
+
+        let sign = self.sign_of(value.is_sign_negative() && !value.is_nan());
+        let prefix = if body.starts_with("0x") || body.starts_with("0X") {
+            sign.len() + 2
+        } else {
+            sign.len()
+        };
+        let text = format!("{}{}", sign, body);
+        self.pad(text.into_bytes(), prefix, self.zero && value.is_finite())
+    }
+}
+
+// %f
+fn fixed(value: f64, precision: usize, alternate: bool) -> String {
+    let mut text = format!("{:.*}", precision, value);
+    if alternate && precision == 0 {
+        text.push('.');
+    }
+    text
+}
+
+// %e, with the exponent signed and at least two digits long
+fn exponential(value: f64, precision: usize, alternate: bool) -> String {
+    let (mut mantissa, exponent) = split_exponent(value, precision);
+    if alternate && precision == 0 {
+        mantissa.push('.');
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{}{:02}", mantissa, sign, exponent.unsigned_abs())
+}
+
+// %g: %e when the exponent is below -4 or not below the precision, %f
+// otherwise, without trailing zeros unless '#' asks for them
+fn general(value: f64, precision: usize, alternate: bool) -> String {
+    let precision = precision.max(1);
+    let (_, exponent) = split_exponent(value, precision - 1);
+    let mut text = if exponent < -4 || exponent >= precision as i32 {
+        exponential(value, precision - 1, alternate)
+    } else {
+        fixed(value, (precision as i32 - 1 - exponent) as usize, alternate)
+    };
+    if alternate {
+        return text;
+    }
+
+    let exponent_part = text
+        .find('e')
+        .map(|at| text.split_off(at))
+        .unwrap_or_default();
+    if text.contains('.') {
+        let kept = text.trim_end_matches('0').trim_end_matches('.').len();
+        text.truncate(kept);
+    }
+    text + &exponent_part
+}
+
+// VALUE rounded to PRECISION digits after the first, and its exponent
+fn split_exponent(value: f64, precision: usize) -> (String, i32) {
+    let text = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    (mantissa.to_string(), exponent.parse().unwrap_or(0))
+}
+
+// %a, laid out as for a long double: the first hex digit holds the top
+// four bits of the mantissa, so that 1 is 0x8p-3
+fn hexadecimal(value: f64, precision: Option<usize>, alternate: bool) -> String {
+    if value == 0.0 {
+        let zeros = "0".repeat(precision.unwrap_or(0));
+        let point = if zeros.is_empty() && !alternate {
+            ""
+        } else {
+            "."
+        };
+        return format!("0x0{}{}p+0", point, zeros);
+    }
+
+    let bits = value.to_bits();
+    let biased = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    // The mantissa with its top bit set, and the power of two for it
+    let (mut mantissa, mut exponent) = if biased == 0 {
+        let shift = fraction.leading_zeros();
+        (fraction << shift, -1022 - (shift as i32 - 11) - 3)
+    } else {
+        ((1 << 63) | (fraction << 11), biased - 1023 - 3)
+    };
+
+    let mut digits = 15;
+    if let Some(precision) = precision.filter(|&precision| precision < 15) {
+        // Round the 60 bits after the first digit to PRECISION digits,
+        // half to even
+        let dropped = 60 - 4 * precision as u32;
+        let unit = 1u64 << dropped;
+        let remainder = mantissa & (unit - 1);
+        let half = unit >> 1;
+        mantissa &= !(unit - 1);
+        let odd = mantissa & unit != 0;
+        if remainder > half || (remainder == half && odd) {
+            match mantissa.checked_add(unit) {
+                Some(rounded) => mantissa = rounded,
+                // Carried out of the first digit: 0x10p+N is 0x8p+(N+1)
+                None => {
+                    mantissa = 1 << 63;
+                    exponent += 1;
+                }
+            }
+        }
+        digits = precision;
+    }
+
+    let first = mantissa >> 60;
+    let mut rest = format!("{:015x}", mantissa & ((1 << 60) - 1));
+    rest.truncate(digits);
+    if precision.is_none() {
+        let kept = rest.trim_end_matches('0').len();
+        rest.truncate(kept);
+    } else if let Some(precision) = precision {
+        rest.extend(std::iter::repeat_n(
+            '0',
+            precision.saturating_sub(rest.len()),
+        ));
+    }
+    let point = if rest.is_empty() && !alternate {
+        ""
+    } else {
+        "."
+    };
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!(
+        "0x{:x}{}{}p{}{}",
+        first,
+        point,
+        rest,
+        sign,
+        exponent.unsigned_abs()
+    )
+}
+
+// %q: ARG quoted so that a POSIX shell reads it back as it is, and left
+// alone when it needs no quoting
+pub fn shell_quote(arg: &[u8]) -> Vec<u8> {
+    if arg.is_empty() {
+        return b"''".to_vec();
+    }
+    let special = |i: usize, c: u8| match c {
+        b'#' | b'~' => i == 0,
+        b'{' | b'}' => arg.len() == 1,
+        b' ' | b'!' | b'"' | b'$' | b'&' | b'(' | b')' | b'*' | b';' | b'<' | b'=' | b'>'
+        | b'?' | b'[' | b'\\' | b'^' | b'`' | b'|' | b'\'' => true,
+        _ => !c.is_ascii_graphic(),
+    };
+    if !arg.iter().enumerate().any(|(i, &c)| special(i, c)) {
+        return arg.to_vec();
+    }
+
+    let printable = |c: u8| c.is_ascii_graphic() || c == b' ';
+    // A single quote and nothing a shell would expand between double
+    // quotes: those read better
+    if arg.contains(&b'\'')
+        && arg
+            .iter()
+            .all(|&c| printable(c) && !b"$`\\!\"".contains(&c))
+    {
+        return [b"\"", arg, b"\""].concat();
+    }
+
+    // Single quotes, with $'...' for what cannot go inside them
+    let mut quoted = b"'".to_vec();
+    let mut in_escape = false;
+    for &c in arg {
+        if printable(c) {
+            // Close the $'...' and open a plain quote again
+            if in_escape {
+                quoted.extend_from_slice(b"''");
+                in_escape = false;
+            }
+            if c == b'\'' {
+                quoted.extend_from_slice(b"'\\''");
+            } else {
+                quoted.push(c);
+            }
+            continue;
+        }
+        if !in_escape {
+            quoted.extend_from_slice(b"'$'");
+            in_escape = true;
+        }
+        match c {
+            0x07 => quoted.extend_from_slice(b"\\a"),
+            0x08 => quoted.extend_from_slice(b"\\b"),
+            0x0c => quoted.extend_from_slice(b"\\f"),
+            b'\n' => quoted.extend_from_slice(b"\\n"),
+            b'\r' => quoted.extend_from_slice(b"\\r"),
+            b'\t' => quoted.extend_from_slice(b"\\t"),
+            0x0b => quoted.extend_from_slice(b"\\v"),
+            _ => quoted.extend_from_slice(format!("\\{:03o}", c).as_bytes()),
+        }
+    }
+    quoted.push(b'\'');
+    quoted
+}
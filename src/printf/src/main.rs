@@ -0,0 +1,345 @@
+// ASD CoreUtils - printf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod escape;
+mod format;
+mod number;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, Command};
+use escape::Escape;
+use format::Spec;
+use number::Parsed;
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, BufWriter, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::process;
+
+// Conversions each directive may end in, narrowed down as flags and a
+// precision rule some of them out
+const CONVERSIONS: &[u8] = b"aAcdeEfFgGiosuxX";
+
+struct Printer<'a, W: Write> {
+    out: W,
+    arguments: &'a [OsString],
+    next: usize,
+    failed: bool,
+}
+
+fn main() {
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    // --help and --version are options only on their own; anywhere else
+    // they are a format or an argument like any other
+    let options = env::args_os().len() <= 2;
+    let matches = Command::new("printf")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils printf - print ARGUMENT(s) according to FORMAT")
+        .override_usage("printf FORMAT [ARGUMENT]...\n       printf OPTION")
+        .disable_help_flag(!options)
+        .disable_version_flag(!options)
+        .arg(
+            Arg::new("format")
+                .value_name("FORMAT")
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .arg(
+            Arg::new("arguments")
+                .value_name("ARGUMENT")
+                .num_args(0..)
+                .trailing_var_arg(true)
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .after_help(
+            "FORMAT controls the output as in C printf. Interpreted sequences are:\n\n  \
+             \\\"      double quote\n  \
+             \\\\      backslash\n  \
+             \\a      alert (BEL)\n  \
+             \\b      backspace\n  \
+             \\c      produce no further output\n  \
+             \\e      escape\n  \
+             \\f      form feed\n  \
+             \\n      new line\n  \
+             \\r      carriage return\n  \
+             \\t      horizontal tab\n  \
+             \\v      vertical tab\n  \
+             \\NNN    byte with octal value NNN (1 to 3 digits)\n  \
+             \\xHH    byte with hexadecimal value HH (1 to 2 digits)\n  \
+             \\uHHHH  Unicode (ISO/IEC 10646) character with hex value HHHH (4 digits)\n  \
+             \\UHHHHHHHH  Unicode character with hex value HHHHHHHH (8 digits)\n  \
+             %%      a single %\n  \
+             %b      ARGUMENT as a string with '\\' escapes interpreted,\n          \
+             except that octal escapes are of the form \\0 or \\0NNN\n  \
+             %q      ARGUMENT is printed in a format that can be reused as shell input,\n          \
+             escaping non-printable characters with the proposed POSIX $'' syntax.\n\n\
+             and all C format specifications ending with one of diouxXfeEgGcs, with\n\
+             ARGUMENTs converted to proper type first. Variable widths are handled.\n\
+             The FORMAT is reused as necessary to consume all of the ARGUMENTs.",
+        )
+        .get_matches();
+
+    let Some(format) = matches.get_one::<OsString>("format") else {
+        usage_error("printf", "missing operand");
+    };
+    let arguments: Vec<OsString> = matches
+        .get_many::<OsString>("arguments")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    let stdout = io::stdout();
+    let mut printer = Printer {
+        out: BufWriter::new(stdout.lock()),
+        arguments: &arguments,
+        next: 0,
+        failed: false,
+    };
+
+    // The format is gone through again for as long as arguments remain and
+    // each pass uses some of them
+    loop {
+        let start = printer.next;
+        let result = printer.print(format.as_bytes());
+        match result {
+            Ok(Escape::Continue) => {}
+            Ok(Escape::Stop) => break,
+            Err(message) => {
+                let _ = printer.out.flush();
+                fail("printf", &message);
+            }
+        }
+        if printer.next == start || printer.next >= arguments.len() {
+            break;
+        }
+    }
+    if let Some(excess) = arguments.get(printer.next) {
+        eprintln!(
+            "printf: warning: ignoring excess arguments, starting with {}",
+            number::quote(excess.as_bytes())
+        );
+    }
+
+    if let Err(err) = printer.out.flush() {
+        fail("printf", &format!("write error: {}", strerror(&err)));
+    }
+    if printer.failed {
+        process::exit(1);
+    }
+}
+
+impl<W: Write> Printer<'_, W> {
+    // One pass over FORMAT. An error is fatal: a bad directive or escape
+    fn print(&mut self, format: &[u8]) -> Result<Escape, String> {
+        let mut literal = Vec::new();
+        let mut i = 0;
+        while i < format.len() {
+            match format[i] {
+                b'\\' => {
+                    i += 1;
+                    let escape = escape::unescape(format, &mut i, &mut literal, false)?;
+                    if let Escape::Stop = escape {
+                        self.write(&literal);
+                        return Ok(Escape::Stop);
+                    }
+                }
+                b'%' => {
+                    self.write(&literal);
+                    literal.clear();
+                    if let Escape::Stop = self.directive(format, &mut i)? {
+                        return Ok(Escape::Stop);
+                    }
+                }
+                c => {
+                    literal.push(c);
+                    i += 1;
+                }
+            }
+        }
+        self.write(&literal);
+        Ok(Escape::Continue)
+    }
+
+    // The directive starting at the '%' at FORMAT[*I], moving I past it
+    fn directive(&mut self, format: &[u8], i: &mut usize) -> Result<Escape, String> {
+        let start = *i;
+        *i += 1;
+        let invalid = |end: usize| {
+            let end = (end + 1).min(format.len());
+            format!(
+                "{}: invalid conversion specification",
+                String::from_utf8_lossy(&format[start..end])
+            )
+        };
+
+        // %%, %b and %q take no flags, width or precision
+        match format.get(*i) {
+            Some(b'%') => {
+                *i += 1;
+                self.write(b"%");
+                return Ok(Escape::Continue);
+            }
+            Some(b'b') => {
+                *i += 1;
+                let argument = self.argument().unwrap_or_default();
+                let mut text = Vec::new();
+                let mut j = 0;
+                let mut result = Escape::Continue;
+                while j < argument.len() {
+                    if argument[j] == b'\\' {
+                        j += 1;
+                        result = escape::unescape(&argument, &mut j, &mut text, true)?;
+                        if let Escape::Stop = result {
+                            break;
+                        }
+                    } else {
+                        text.push(argument[j]);
+                        j += 1;
+                    }
+                }
+                self.write(&text);
+                return Ok(result);
+            }
+            Some(b'q') => {
+                *i += 1;
+                let argument = self.argument().unwrap_or_default();
+                self.write(&format::shell_quote(&argument));
+                return Ok(Escape::Continue);
+            }
+            _ => {}
+        }
+
+        let mut allowed = CONVERSIONS.to_vec();
+        let mut disallow = |conversions: &[u8]| allowed.retain(|c| !conversions.contains(c));
+        let mut spec = Spec::default();
+        while let Some(&flag) = format.get(*i) {
+            match flag {
+                b'-' => spec.left = true,
+                b'+' => spec.sign = true,
+                b' ' => spec.space = true,
+                b'#' => {
+                    spec.alternate = true;
+                    disallow(b"cdisu");
+                }
+                b'0' => {
+                    spec.zero = true;
+                    disallow(b"cs");
+                }
+                // Grouping, which the C locale has none of
+                b'\'' => disallow(b"aAceEosxX"),
+                _ => break,
+            }
+            *i += 1;
+        }
+
+        if format.get(*i) == Some(&b'*') {
+            *i += 1;
+            let width = self.star("field width")?;
+            spec.left |= width < 0;
+            spec.width = width.unsigned_abs() as usize;
+        } else {
+            spec.width = number_at(format, i);
+        }
+        if format.get(*i) == Some(&b'.') {
+            *i += 1;
+            disallow(b"c");
+            if format.get(*i) == Some(&b'*') {
+                *i += 1;
+                // A negative precision is as good as none
+                let precision = self.star("precision")?;
+                spec.precision = usize::try_from(precision).ok();
+            } else {
+                spec.precision = Some(number_at(format, i));
+            }
+        }
+        // Length modifiers mean nothing here: every integer is intmax_t and
+        // every floating point number long double
+        while format.get(*i).is_some_and(|c| b"hlLjzt".contains(c)) {
+            *i += 1;
+        }
+
+        let Some(&conversion) = format.get(*i).filter(|c| allowed.contains(c)) else {
+            return Err(invalid(*i));
+        };
+        *i += 1;
+        spec.conversion = conversion;
+
+        let argument = self.argument();
+        let argument = argument.as_deref();
+        let text = match conversion {
+            b'c' => spec.string(&[argument.and_then(|arg| arg.first().copied()).unwrap_or(0)]),
+            b's' => spec.string(argument.unwrap_or_default()),
+            b'd' | b'i' => {
+                let value = self.number(argument, number::signed);
+                spec.signed(value)
+            }
+            b'o' | b'u' | b'x' | b'X' => {
+                let value = self.number(argument, number::unsigned);
+                spec.unsigned(value)
+            }
+            _ => {
+                let value = self.number(argument, number::float);
+                spec.float(value)
+            }
+        };
+        self.write(&text);
+        Ok(Escape::Continue)
+    }
+
+    // The next argument, if any are left
+    fn argument(&mut self) -> Option<Vec<u8>> {
+        let argument = self.arguments.get(self.next)?;
+        self.next += 1;
+        Some(argument.as_bytes().to_vec())
+    }
+
+    // ARGUMENT converted by PARSE, zero when it is missing; a bad one is
+    // diagnosed and makes the exit status 1, but what could be read of it
+    // is still printed
+    fn number<T: Default>(&mut self, argument: Option<&[u8]>, parse: fn(&[u8]) -> Parsed<T>) -> T {
+        let Some(argument) = argument else {
+            return T::default();
+        };
+        parse(argument).unwrap_or_else(|(value, message)| {
+            let _ = self.out.flush();
+            eprintln!("printf: {}", message);
+            self.failed = true;
+            value
+        })
+    }
+
+    // A width or precision given as '*', from the next argument
+    fn star(&mut self, what: &str) -> Result<i64, String> {
+        let argument = self.argument();
+        let value = self.number(argument.as_deref(), number::signed);
+        if i32::try_from(value).is_err() {
+            let argument = argument.unwrap_or_default();
+            return Err(format!("invalid {}: {}", what, number::quote(&argument)));
+        }
+        Ok(value)
+    }
+
+    // A failed write ends printf there and then
+    fn write(&mut self, data: &[u8]) {
+        if let Err(err) = self.out.write_all(data) {
+            fail("printf", &format!("write error: {}", strerror(&err)));
+        }
+    }
+}
+
+// Decimal digits at FORMAT[*I], moving I past them
+fn number_at(format: &[u8], i: &mut usize) -> usize {
+    let mut value: usize = 0;
+    while let Some(&c) = format.get(*i).filter(|c| c.is_ascii_digit()) {
+        value = value
+            .saturating_mul(10)
+            .saturating_add(usize::from(c - b'0'));
+        *i += 1;
+    }
+    value
+}
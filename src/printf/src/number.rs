@@ -0,0 +1,244 @@
+// ASD CoreUtils - printf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Numeric arguments, read the way strtoimax, strtoumax and strtold read
+// them, or as the code of the character after a leading quote. A bad one
+// still yields a value, the part that could be read, along with the error.
+
+pub type Parsed<T> = Result<T, (T, String)>;
+
+// What is left of ARG once a numeric prefix has been read: the whole of it
+// when nothing could be
+fn check_rest<T>(arg: &[u8], value: T, rest: usize, read_any: bool) -> Parsed<T> {
+    if !read_any {
+        Err((value, format!("{}: expected a numeric value", quote(arg))))
+    } else if rest < arg.len() {
+        Err((
+            value,
+            format!("{}: value not completely converted", quote(arg)),
+        ))
+    } else {
+        Ok(value)
+    }
+}
+
+// 'c or "c: the code of c; anything after it is ignored with a warning.
+// An empty argument counts as a zero
+fn character(arg: &[u8]) -> Option<u8> {
+    if arg.is_empty() {
+        return Some(0);
+    }
+    if arg.len() < 2 || (arg[0] != b'\'' && arg[0] != b'"') {
+        return None;
+    }
+    if arg.len() > 2 {
+        eprintln!(
+            "printf: warning: {}: character(s) following character constant have been ignored",
+            String::from_utf8_lossy(&arg[2..])
+        );
+    }
+    Some(arg[1])
+}
+
+// The sign, magnitude and extent of an integer at the start of ARG; the
+// magnitude is None when it does not fit in 128 bits
+fn integer(arg: &[u8]) -> (bool, Option<u128>, usize, bool) {
+    let mut i = arg.iter().take_while(|c| c.is_ascii_whitespace()).count();
+    let negative = arg.get(i) == Some(&b'-');
+    if matches!(arg.get(i), Some(b'-') | Some(b'+')) {
+        i += 1;
+    }
+
+    let mut radix = 10;
+    if arg.get(i) == Some(&b'0') {
+        radix = 8;
+        if matches!(arg.get(i + 1), Some(b'x') | Some(b'X'))
+            && arg.get(i + 2).is_some_and(u8::is_ascii_hexdigit)
+        {
+            radix = 16;
+            i += 2;
+        }
+    }
+
+    let start = i;
+    let mut magnitude = Some(0u128);
+    while let Some(digit) = arg.get(i).and_then(|&c| (c as char).to_digit(radix)) {
+        magnitude = magnitude
+            .and_then(|m| m.checked_mul(u128::from(radix)))
+            .and_then(|m| m.checked_add(u128::from(digit)));
+        i += 1;
+    }
+    (negative, magnitude, i, i > start)
+}
+
+pub fn signed(arg: &[u8]) -> Parsed<i64> {
+    if let Some(c) = character(arg) {
+        return Ok(i64::from(c));
+    }
+    let (negative, magnitude, end, read_any) = integer(arg);
+    let limit = if negative {
+        i64::MIN.unsigned_abs()
+    } else {
+        i64::MAX.unsigned_abs()
+    };
+    let value = match magnitude {
+        Some(magnitude) if magnitude <= u128::from(limit) => {
+            let magnitude = magnitude as i128;
+            (if negative { -magnitude } else { magnitude }) as i64
+        }
+        _ => {
+            let value = if negative { i64::MIN } else { i64::MAX };
+            return Err((
+                value,
+                format!("{}: Numerical result out of range", quote(arg)),
+            ));
+        }
+    };
+    check_rest(arg, value, end, read_any)
+}
+
+pub fn unsigned(arg: &[u8]) -> Parsed<u64> {
+    if let Some(c) = character(arg) {
+        return Ok(u64::from(c));
+    }
+    let (negative, magnitude, end, read_any) = integer(arg);
+    let value = match magnitude.and_then(|magnitude| u64::try_from(magnitude).ok()) {
+        // As strtoumax does, a negative number wraps around
+        Some(magnitude) if negative => magnitude.wrapping_neg(),
+        Some(magnitude) => magnitude,
+        None => {
+            return Err((
+                u64::MAX,
+                format!("{}: Numerical result out of range", quote(arg)),
+            ));
+        }
+    };
+    check_rest(arg, value, end, read_any)
+}
+
+pub fn float(arg: &[u8]) -> Parsed<f64> {
+    if let Some(c) = character(arg) {
+        return Ok(f64::from(c));
+    }
+
+    let mut i = arg.iter().take_while(|c| c.is_ascii_whitespace()).count();
+    let start = i;
+    let negative = arg.get(i) == Some(&b'-');
+    if matches!(arg.get(i), Some(b'-') | Some(b'+')) {
+        i += 1;
+    }
+    let body = &arg[i..];
+    let lower = body.to_ascii_lowercase();
+
+    let (magnitude, length) = if lower.starts_with(b"infinity") {
+        (f64::INFINITY, 8)
+    } else if lower.starts_with(b"inf") {
+        (f64::INFINITY, 3)
+    } else if lower.starts_with(b"nan") {
+        (f64::NAN, 3)
+    } else if lower.starts_with(b"0x")
+        && body
+            .get(2)
+            .is_some_and(|c| c.is_ascii_hexdigit() || *c == b'.')
+    {
+        hex_float(body)
+    } else {
+        decimal_float(body)
+    };
+    if length == 0 {
+        return check_rest(arg, 0.0, start, false);
+    }
+    let value = if negative { -magnitude } else { magnitude };
+    if value.is_infinite() && !lower.starts_with(b"inf") {
+        return Err((
+            value,
+            format!("{}: Numerical result out of range", quote(arg)),
+        ));
+    }
+    check_rest(arg, value, i + length, true)
+}
+
+// Digits, a point, more digits and an exponent, as far as they go
+fn decimal_float(body: &[u8]) -> (f64, usize) {
+    let count = |from: usize| {
+        body[from..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+    let whole = count(0);
+    let mut end = whole;
+    let mut fraction = 0;
+    if body.get(end) == Some(&b'.') {
+        fraction = count(end + 1);
+        if whole + fraction > 0 {
+            end += 1 + fraction;
+        }
+    }
+    if whole + fraction == 0 {
+        return (0.0, 0);
+    }
+    if matches!(body.get(end), Some(b'e') | Some(b'E')) {
+        let mut exponent = end + 1;
+        if matches!(body.get(exponent), Some(b'-') | Some(b'+')) {
+            exponent += 1;
+        }
+        let digits = count(exponent);
+        if digits > 0 {
+            end = exponent + digits;
+        }
+    }
+    let text = String::from_utf8_lossy(&body[..end]);
+    (text.parse().unwrap_or(0.0), end)
+}
+
+// 0xH.HHHpD
+fn hex_float(body: &[u8]) -> (f64, usize) {
+    let mut i = 2;
+    let mut mantissa = 0.0f64;
+    let mut exponent = 0i32;
+    let mut any = false;
+    let mut after_point = false;
+    loop {
+        match body.get(i) {
+            Some(&c) if c.is_ascii_hexdigit() => {
+                mantissa = mantissa * 16.0 + f64::from((c as char).to_digit(16).unwrap_or(0));
+                if after_point {
+                    exponent -= 4;
+                }
+                any = true;
+            }
+            Some(b'.') if !after_point => after_point = true,
+            _ => break,
+        }
+        i += 1;
+    }
+    if !any {
+        // Just the "0" of "0x"
+        return (0.0, 1);
+    }
+    if matches!(body.get(i), Some(b'p') | Some(b'P')) {
+        let mut j = i + 1;
+        let negative = body.get(j) == Some(&b'-');
+        if matches!(body.get(j), Some(b'-') | Some(b'+')) {
+            j += 1;
+        }
+        let start = j;
+        let mut power = 0i32;
+        while let Some(&c) = body.get(j).filter(|c| c.is_ascii_digit()) {
+            power = power.saturating_mul(10).saturating_add(i32::from(c - b'0'));
+            j += 1;
+        }
+        if j > start {
+            exponent += if negative { -power } else { power };
+            i = j;
+        }
+    }
+    (mantissa * 2f64.powi(exponent), i)
+}
+
+// ARG as diagnostics show it, in single quotes
+pub fn quote(arg: &[u8]) -> String {
+    format!("'{}'", String::from_utf8_lossy(arg).replace('\'', "'\\''"))
+}
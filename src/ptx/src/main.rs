@@ -0,0 +1,1016 @@
+// ASD CoreUtils - ptx
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use regex::bytes::Regex;
+use std::cmp::Ordering;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+
+// Ends a context at the end of a sentence or of a line, like GNU ptx
+const SENTENCE_REGEX: &str = r#"(?m)[.?!][\]"')}]*(?:$|\t|  )[ \t\n]*"#;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Dumb,
+    Roff,
+    Tex,
+}
+
+struct Options {
+    traditional: bool,
+    auto_reference: bool,
+    input_reference: bool,
+    right_reference: bool,
+    ignore_case: bool,
+    format: Format,
+    macro_name: String,
+    truncation: Vec<u8>,
+    gap: isize,
+    width: isize,
+}
+
+// How words are recognised: a set of word characters, or a regex
+enum Words {
+    Chars([bool; 256]),
+    Regex { search: Regex, anchored: Regex },
+}
+
+impl Words {
+    // The next word within text[cursor..end], possibly empty with a regex
+    fn find(&self, text: &[u8], cursor: usize, end: usize) -> Option<(usize, usize)> {
+        match self {
+            Words::Chars(map) => {
+                let start = (cursor..end).find(|&i| map[text[i] as usize])?;
+                let stop = (start..end).find(|&i| !map[text[i] as usize]).unwrap_or(end);
+                Some((start, stop))
+            }
+            Words::Regex { search, .. } => search.find_at(&text[..end], cursor).map(|m| (m.start(), m.end())),
+        }
+    }
+
+    // Steps over one word, or a single other character
+    fn skip(&self, text: &[u8], cursor: isize, limit: isize) -> isize {
+        match self {
+            Words::Chars(map) => {
+                if cursor >= limit || !map[text[cursor as usize] as usize] {
+                    return cursor + 1;
+                }
+                let mut cursor = cursor;
+                while cursor < limit && map[text[cursor as usize] as usize] {
+                    cursor += 1;
+                }
+                cursor
+            }
+            Words::Regex { anchored, .. } => {
+                let length = if cursor < limit {
+                    anchored
+                        .find(&text[cursor as usize..limit as usize])
+                        .map_or(0, |m| m.end())
+                } else {
+                    0
+                };
+                cursor + length.max(1) as isize
+            }
+        }
+    }
+}
+
+struct Input {
+    // None for standard input, which gets an empty name in references
+    name: Option<String>,
+    text: Vec<u8>,
+    // Cumulative line count when the file was done, for references
+    line_count: usize,
+}
+
+struct Occurrence {
+    file: usize,
+    key_start: usize,
+    key_len: usize,
+    context_start: usize,
+    context_end: usize,
+    // Line number for -A, or where the reference starts for -r
+    reference: usize,
+}
+
+// A span of the input text; empty or reversed spans print nothing
+#[derive(Clone, Copy)]
+struct Field {
+    start: isize,
+    end: isize,
+}
+
+impl Field {
+    fn len(&self) -> isize {
+        self.end - self.start
+    }
+}
+
+// Output geometry shared by every line
+struct Layout {
+    half_width: isize,
+    before_max: isize,
+    keyafter_max: isize,
+    reference_width: isize,
+    longest_word: isize,
+}
+
+// Where the parts of one output line come from, and which are truncated
+struct Line {
+    tail: Field,
+    before: Field,
+    keyafter: Field,
+    head: Field,
+    tail_truncated: bool,
+    before_truncated: bool,
+    keyafter_truncated: bool,
+    head_truncated: bool,
+}
+
+fn main() {
+    let matches = Command::new("ptx")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils ptx - produce a permuted index of file contents")
+        .override_usage("ptx [OPTION]... [INPUT]...   (without -G)\n       ptx -G [OPTION]... [INPUT [OUTPUT]]")
+        .after_help("With no FILE, or when FILE is -, read standard input.")
+        .arg(
+            Arg::new("auto-reference")
+                .short('A')
+                .long("auto-reference")
+                .help("Output automatically generated references")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("traditional")
+                .short('G')
+                .long("traditional")
+                .help("Behave more like System V 'ptx'")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("flag-truncation")
+                .short('F')
+                .long("flag-truncation")
+                .value_name("STRING")
+                .help("Use STRING for flagging line truncations (default '/')"),
+        )
+        .arg(
+            Arg::new("macro-name")
+                .short('M')
+                .long("macro-name")
+                .value_name("STRING")
+                .help("Macro name to use instead of 'xx'"),
+        )
+        .arg(
+            Arg::new("roff")
+                .short('O')
+                .help("Generate output as roff directives")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("tex")
+                .short('T')
+                .help("Generate output as TeX directives")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["roff", "tex"])
+                .help("Generate output as roff or TeX directives"),
+        )
+        .arg(
+            Arg::new("right-side-refs")
+                .short('R')
+                .long("right-side-refs")
+                .help("Put references at right, not counted in -w")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("sentence-regexp")
+                .short('S')
+                .long("sentence-regexp")
+                .value_name("REGEXP")
+                .help("For end of lines or end of sentences"),
+        )
+        .arg(
+            Arg::new("word-regexp")
+                .short('W')
+                .long("word-regexp")
+                .value_name("REGEXP")
+                .help("Use REGEXP to match each keyword"),
+        )
+        .arg(
+            Arg::new("break-file")
+                .short('b')
+                .long("break-file")
+                .value_name("FILE")
+                .help("Word break characters in this FILE"),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .short('f')
+                .long("ignore-case")
+                .help("Fold lower case to upper case for sorting")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("gap-size")
+                .short('g')
+                .long("gap-size")
+                .value_name("NUMBER")
+                .help("Gap size in columns between output fields"),
+        )
+        .arg(
+            Arg::new("ignore-file")
+                .short('i')
+                .long("ignore-file")
+                .value_name("FILE")
+                .help("Read ignore word list from FILE"),
+        )
+        .arg(
+            Arg::new("only-file")
+                .short('o')
+                .long("only-file")
+                .value_name("FILE")
+                .help("Read only word list from this FILE"),
+        )
+        .arg(
+            Arg::new("references")
+                .short('r')
+                .long("references")
+                .help("First field of each line is a reference")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("typeset-mode")
+                .short('t')
+                .long("typeset-mode")
+                .help("Accepted for compatibility, has no effect")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("width")
+                .short('w')
+                .long("width")
+                .value_name("NUMBER")
+                .help("Output width in columns, reference excluded"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("INPUT")
+                .help("Input files")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let traditional = matches.get_flag("traditional");
+
+    let format = if matches.get_flag("tex") || matches.get_one::<String>("format").is_some_and(|f| f == "tex") {
+        Format::Tex
+    } else if traditional || matches.get_flag("roff") || matches.get_one::<String>("format").is_some_and(|f| f == "roff") {
+        // Traditional ptx only ever wrote roff
+        Format::Roff
+    } else {
+        Format::Dumb
+    };
+
+    let mut options = Options {
+        traditional,
+        auto_reference: matches.get_flag("auto-reference"),
+        input_reference: matches.get_flag("references"),
+        right_reference: matches.get_flag("right-side-refs"),
+        ignore_case: matches.get_flag("ignore-case"),
+        format,
+        macro_name: matches
+            .get_one::<String>("macro-name")
+            .cloned()
+            .unwrap_or_else(|| "xx".to_string()),
+        truncation: matches
+            .get_one::<String>("flag-truncation")
+            .map(|s| unescape(s))
+            .unwrap_or_else(|| b"/".to_vec()),
+        gap: 3,
+        width: 72,
+    };
+
+    if let Some(value) = matches.get_one::<String>("gap-size") {
+        options.gap = parse_positive(value).unwrap_or_else(|| fail("ptx", &format!("invalid gap width: '{}'", value)));
+    }
+    if let Some(value) = matches.get_one::<String>("width") {
+        options.width = parse_positive(value).unwrap_or_else(|| fail("ptx", &format!("invalid line width: '{}'", value)));
+    }
+
+    // In traditional mode the operands are INPUT and OUTPUT
+    let operands: Vec<String> = matches
+        .get_many::<String>("files")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+    let (file_names, output_name) = if traditional {
+        if operands.len() > 2 {
+            usage_error("ptx", &format!("extra operand '{}'", operands[2]));
+        }
+        (operands.iter().take(1).cloned().collect::<Vec<_>>(), operands.get(1).cloned())
+    } else {
+        (operands, None)
+    };
+    let file_names = if file_names.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        file_names
+    };
+
+    let words = word_matcher(
+        matches.get_one::<String>("word-regexp"),
+        matches.get_one::<String>("break-file"),
+        traditional,
+    );
+
+    let context = match matches.get_one::<String>("sentence-regexp") {
+        Some(source) if source.is_empty() => None,
+        Some(source) => Some(compile(source)),
+        None if !traditional && !options.input_reference => Some(compile(SENTENCE_REGEX)),
+        None => Some(compile("\n")),
+    };
+
+    let ignore = matches
+        .get_one::<String>("ignore-file")
+        .map(|path| read_word_list(path, options.ignore_case));
+    let only = matches
+        .get_one::<String>("only-file")
+        .map(|path| read_word_list(path, options.ignore_case));
+
+    let mut inputs = Vec::new();
+    let mut occurrences = Vec::new();
+    let mut reference_width: isize = 0;
+    let mut longest_word: isize = 0;
+    let mut line_count = 0;
+
+    for name in &file_names {
+        let text = read_file(name).unwrap_or_else(|err| fail("ptx", &format!("{}: {}", name, strerror(&err))));
+        let file = inputs.len();
+        inputs.push(Input {
+            name: (name != "-").then(|| name.clone()),
+            text,
+            line_count: 0,
+        });
+
+        let scan = Scan {
+            options: &options,
+            words: &words,
+            context: context.as_ref(),
+            ignore: ignore.as_deref(),
+            only: only.as_deref(),
+        };
+        scan.find_occurrences(
+            &inputs[file].text,
+            file,
+            &mut occurrences,
+            &mut line_count,
+            &mut reference_width,
+            &mut longest_word,
+        );
+        inputs[file].line_count = line_count;
+    }
+
+    occurrences.sort_by(|a, b| {
+        let key_a = &inputs[a.file].text[a.key_start..a.key_start + a.key_len];
+        let key_b = &inputs[b.file].text[b.key_start..b.key_start + b.key_len];
+        compare_words(key_a, key_b, options.ignore_case)
+            .then(a.file.cmp(&b.file))
+            .then(a.key_start.cmp(&b.key_start))
+    });
+
+    let layout = layout(&options, &inputs, reference_width, longest_word);
+
+    let result = match output_name {
+        Some(path) => File::create(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path, strerror(&err))))
+            .and_then(|file| write_index(&options, &layout, &words, &inputs, &occurrences, &mut BufWriter::new(file))),
+        None => {
+            let stdout = io::stdout();
+            let mut out = BufWriter::new(stdout.lock());
+            write_index(&options, &layout, &words, &inputs, &occurrences, &mut out)
+        }
+    };
+
+    if let Err(err) = result {
+        fail("ptx", &strerror(&err));
+    }
+}
+
+fn parse_positive(value: &str) -> Option<isize> {
+    value.parse::<isize>().ok().filter(|&n| n > 0)
+}
+
+fn compile(source: &str) -> Regex {
+    Regex::new(source).unwrap_or_else(|err| fail("ptx", &format!("{}: invalid regular expression: {}", source, err)))
+}
+
+fn read_file(name: &str) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    if name == "-" {
+        io::stdin().lock().read_to_end(&mut data)?;
+    } else {
+        File::open(name)?.read_to_end(&mut data)?;
+    }
+    Ok(data)
+}
+
+// Handles the usual backslash escapes in the -F string
+fn unescape(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut result = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        index += 1;
+        if byte != b'\\' || index == bytes.len() {
+            result.push(byte);
+            continue;
+        }
+
+        let escape = bytes[index];
+        index += 1;
+        match escape {
+            b'x' => {
+                let digits = bytes[index..].iter().take(2).take_while(|b| b.is_ascii_hexdigit()).count();
+                let value = std::str::from_utf8(&bytes[index..index + digits])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match value {
+                    Some(value) => result.push(value),
+                    None => result.extend_from_slice(b"\\x"),
+                }
+                index += digits;
+            }
+            b'0' => {
+                let digits = bytes[index..]
+                    .iter()
+                    .take(3)
+                    .take_while(|b| (b'0'..=b'7').contains(b))
+                    .count();
+                let value = bytes[index..index + digits]
+                    .iter()
+                    .fold(0u32, |value, b| value * 8 + (b - b'0') as u32);
+                result.push(value as u8);
+                index += digits;
+            }
+            b'a' => result.push(0x07),
+            b'b' => result.push(0x08),
+            b'c' => break,
+            b'f' => result.push(0x0c),
+            b'n' => result.push(b'\n'),
+            b'r' => result.push(b'\r'),
+            b't' => result.push(b'\t'),
+            b'v' => result.push(0x0b),
+            other => {
+                result.push(b'\\');
+                result.push(other);
+            }
+        }
+    }
+
+    result
+}
+
+// Words are runs of letters by default, runs of non-blanks in traditional
+// mode, runs of anything not in the break file with -b, or whatever -W says
+fn word_matcher(regex: Option<&String>, break_file: Option<&String>, traditional: bool) -> Words {
+    if let Some(source) = regex {
+        return Words::Regex {
+            search: compile(source),
+            anchored: compile(&format!(r"\A(?:{})", source)),
+        };
+    }
+
+    let mut map = [false; 256];
+    if let Some(path) = break_file {
+        let breaks = fs::read(path).unwrap_or_else(|err| fail("ptx", &format!("{}: {}", path, strerror(&err))));
+        map = [true; 256];
+        for byte in breaks {
+            map[byte as usize] = false;
+        }
+        // Only GNU mode lets the break file leave out the blanks
+        if traditional {
+            for byte in [b' ', b'\t', b'\n'] {
+                map[byte as usize] = false;
+            }
+        }
+    } else if traditional {
+        map = [true; 256];
+        for byte in [b' ', b'\t', b'\n'] {
+            map[byte as usize] = false;
+        }
+    } else {
+        for byte in 0..=255u8 {
+            map[byte as usize] = byte.is_ascii_alphabetic();
+        }
+    }
+
+    Words::Chars(map)
+}
+
+// One word per line, sorted for binary search
+fn read_word_list(path: &str, ignore_case: bool) -> Vec<Vec<u8>> {
+    let text = fs::read(path).unwrap_or_else(|err| fail("ptx", &format!("{}: {}", path, strerror(&err))));
+    let mut words: Vec<Vec<u8>> = text
+        .split(|&b| b == b'\n')
+        .filter(|word| !word.is_empty())
+        .map(<[u8]>::to_vec)
+        .collect();
+    words.sort_by(|a, b| compare_words(a, b, ignore_case));
+    words
+}
+
+fn compare_words(a: &[u8], b: &[u8], ignore_case: bool) -> Ordering {
+    if ignore_case {
+        a.iter()
+            .map(u8::to_ascii_uppercase)
+            .cmp(b.iter().map(u8::to_ascii_uppercase))
+    } else {
+        a.cmp(b)
+    }
+}
+
+fn is_space(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+fn skip_white(text: &[u8], mut cursor: isize, limit: isize) -> isize {
+    while cursor < limit && is_space(text[cursor as usize]) {
+        cursor += 1;
+    }
+    cursor
+}
+
+fn skip_white_backwards(text: &[u8], mut cursor: isize, start: isize) -> isize {
+    while cursor > start && is_space(text[cursor as usize - 1]) {
+        cursor -= 1;
+    }
+    cursor
+}
+
+fn skip_non_white(text: &[u8], mut cursor: usize, limit: usize) -> usize {
+    while cursor < limit && !is_space(text[cursor]) {
+        cursor += 1;
+    }
+    cursor
+}
+
+struct Scan<'a> {
+    options: &'a Options,
+    words: &'a Words,
+    context: Option<&'a Regex>,
+    ignore: Option<&'a [Vec<u8>]>,
+    only: Option<&'a [Vec<u8>]>,
+}
+
+impl Scan<'_> {
+    // Splits the text into contexts (sentences or lines) and records every
+    // word of each that passes the ignore and only lists
+    fn find_occurrences(
+        &self,
+        text: &[u8],
+        file: usize,
+        occurrences: &mut Vec<Occurrence>,
+        line_count: &mut usize,
+        reference_width: &mut isize,
+        longest_word: &mut isize,
+    ) {
+        let options = self.options;
+        let mut line_start = 0;
+        let mut line_scan = 0;
+        let mut reference_length = 0;
+        if options.input_reference {
+            line_scan = skip_non_white(text, 0, text.len());
+            reference_length = line_scan;
+            line_scan = skip_white(text, line_scan as isize, text.len() as isize) as usize;
+        }
+
+        let mut cursor = 0;
+        while cursor < text.len() {
+            let context_start = cursor;
+
+            let next_context_start = self
+                .context
+                .and_then(|regex| regex.find_at(text, cursor))
+                .filter(|m| m.end() > m.start())
+                .map_or(text.len(), |m| m.end());
+
+            // The separator belongs to the context, its trailing blanks don't
+            let context_end =
+                skip_white_backwards(text, next_context_start as isize, context_start as isize) as usize;
+
+            let mut context_start = context_start;
+
+            while let Some((word_start, word_end)) = self.words.find(text, cursor, context_end) {
+                if word_start == word_end {
+                    cursor = word_start + 1;
+                    continue;
+                }
+                cursor = word_end;
+
+                let key = &text[word_start..word_end];
+                *longest_word = (*longest_word).max(key.len() as isize);
+
+                if options.auto_reference || options.input_reference {
+                    while line_scan < word_start {
+                        if text[line_scan] == b'\n' {
+                            *line_count += 1;
+                            line_scan += 1;
+                            line_start = line_scan;
+                            if options.input_reference {
+                                line_scan = skip_non_white(text, line_scan, text.len());
+                                reference_length = line_scan - line_start;
+                            }
+                        } else {
+                            line_scan += 1;
+                        }
+                    }
+                    if options.input_reference && line_scan > word_start {
+                        continue;
+                    }
+                }
+
+                let ignore_case = options.ignore_case;
+                let listed = |list: &[Vec<u8>]| {
+                    list.binary_search_by(|word| compare_words(word, key, ignore_case))
+                        .is_ok()
+                };
+                if self.ignore.is_some_and(listed) || self.only.is_some_and(|list| !listed(list)) {
+                    continue;
+                }
+
+                let reference = if options.auto_reference {
+                    *line_count
+                } else {
+                    if options.input_reference {
+                        *reference_width = (*reference_width).max(reference_length as isize);
+                    }
+                    line_start
+                };
+
+                // Leave the reference out of the context when it starts it
+                if options.input_reference && line_start == context_start {
+                    context_start = skip_non_white(text, context_start, context_end);
+                    context_start = skip_white(text, context_start as isize, context_end as isize) as usize;
+                }
+
+                occurrences.push(Occurrence {
+                    file,
+                    key_start: word_start,
+                    key_len: key.len(),
+                    context_start,
+                    context_end,
+                    reference,
+                });
+            }
+
+            cursor = next_context_start;
+        }
+    }
+}
+
+fn layout(options: &Options, inputs: &[Input], input_reference_width: isize, longest_word: isize) -> Layout {
+    let mut reference_width = input_reference_width;
+
+    // Wide enough for "file:line" of the last line of any file
+    if options.auto_reference {
+        reference_width = 0;
+        let mut previous = 0;
+        for input in inputs {
+            let ordinal = input.line_count + 2 - previous;
+            previous = input.line_count;
+            let width = ordinal.to_string().len() + input.name.as_ref().map_or(0, String::len);
+            reference_width = reference_width.max(width as isize);
+        }
+        reference_width += 1;
+    }
+
+    let mut line_width = options.width;
+    if (options.auto_reference || options.input_reference) && !options.right_reference {
+        line_width -= reference_width + options.gap;
+    }
+    line_width = line_width.max(0);
+
+    // The left half holds the context before the keyword and the gap, the
+    // right half the keyword and what follows it
+    let half_width = line_width / 2;
+    let mut before_max = half_width - options.gap;
+    let mut keyafter_max = half_width;
+
+    let truncation = options.truncation.len() as isize;
+    if options.traditional {
+        keyafter_max -= 2 * truncation + 1;
+    } else {
+        // Room for a truncation mark on either side of each half
+        before_max = (before_max - 2 * truncation).max(0);
+        keyafter_max -= 2 * truncation;
+    }
+
+    Layout {
+        half_width,
+        before_max,
+        keyafter_max,
+        reference_width,
+        longest_word,
+    }
+}
+
+// Works out the fields of one output line. The keyword and what follows
+// it fill the right half and the context before it the left half; text
+// that doesn't fit on one side wraps around into the free space on the
+// other, as the tail (from the right context) or head (from the left).
+fn define_fields(options: &Options, layout: &Layout, words: &Words, text: &[u8], occurrence: &Occurrence) -> Line {
+    let truncating = !options.truncation.is_empty();
+    let text_end = text.len() as isize;
+    let left_context_start = occurrence.context_start as isize;
+    let right_context_end = occurrence.context_end as isize;
+    let gap = options.gap;
+
+    let key_start = occurrence.key_start as isize;
+    let mut keyafter = Field {
+        start: key_start,
+        end: key_start + occurrence.key_len as isize,
+    };
+
+    let mut cursor = keyafter.end;
+    while cursor < right_context_end && cursor <= keyafter.start + layout.keyafter_max {
+        keyafter.end = cursor;
+        cursor = words.skip(text, cursor, right_context_end);
+    }
+    if cursor <= keyafter.start + layout.keyafter_max {
+        keyafter.end = cursor;
+    }
+    let mut keyafter_truncated = truncating && keyafter.end < right_context_end;
+    keyafter.end = skip_white_backwards(text, keyafter.end, keyafter.start);
+
+    // Don't walk all the way from the start of a long context
+    let left_field_start = if key_start - left_context_start > layout.half_width + layout.longest_word {
+        let start = key_start - (layout.half_width + layout.longest_word);
+        words.skip(text, start, key_start)
+    } else {
+        left_context_start
+    };
+
+    let mut before = Field {
+        start: left_field_start,
+        end: skip_white_backwards(text, key_start, left_field_start),
+    };
+    while before.start + layout.before_max < before.end {
+        before.start = words.skip(text, before.start, before.end);
+    }
+    let mut before_truncated = truncating && skip_white_backwards(text, before.start, 0) > left_context_start;
+    before.start = skip_white(text, before.start, text_end);
+
+    let tail_max = layout.before_max - before.len() - gap;
+    let mut tail = Field { start: 0, end: 0 };
+    let mut tail_truncated = false;
+    if tail_max > 0 {
+        tail.start = skip_white(text, keyafter.end, text_end);
+        tail.end = tail.start;
+        let mut cursor = tail.end;
+        while cursor < right_context_end && cursor < tail.start + tail_max {
+            tail.end = cursor;
+            cursor = words.skip(text, cursor, right_context_end);
+        }
+        if cursor < tail.start + tail_max {
+            tail.end = cursor;
+        }
+
+        if tail.end > tail.start {
+            keyafter_truncated = false;
+            tail_truncated = truncating && tail.end < right_context_end;
+        }
+        tail.end = skip_white_backwards(text, tail.end, tail.start);
+    }
+
+    let head_max = layout.keyafter_max - keyafter.len() - gap;
+    let mut head = Field { start: 0, end: 0 };
+    let mut head_truncated = false;
+    if head_max > 0 {
+        head.end = skip_white_backwards(text, before.start, 0);
+        head.start = left_field_start;
+        while head.start + head_max < head.end {
+            head.start = words.skip(text, head.start, head.end);
+        }
+
+        if head.end > head.start {
+            before_truncated = false;
+            head_truncated = truncating && head.start > left_context_start;
+        }
+        head.start = skip_white(text, head.start, head.end);
+    }
+
+    Line {
+        tail,
+        before,
+        keyafter,
+        head,
+        tail_truncated,
+        before_truncated,
+        keyafter_truncated,
+        head_truncated,
+    }
+}
+
+fn write_index(
+    options: &Options,
+    layout: &Layout,
+    words: &Words,
+    inputs: &[Input],
+    occurrences: &[Occurrence],
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    for occurrence in occurrences {
+        let input = &inputs[occurrence.file];
+        let text = &input.text;
+        let line = define_fields(options, layout, words, text, occurrence);
+
+        let reference: Vec<u8> = if options.auto_reference {
+            let previous = match occurrence.file {
+                0 => 0,
+                file => inputs[file - 1].line_count,
+            };
+            let name = input.name.as_deref().unwrap_or("");
+            format!("{}:{}", name, occurrence.reference + 1 - previous).into_bytes()
+        } else if options.input_reference {
+            let start = occurrence.reference;
+            text[start..skip_non_white(text, start, text.len())].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        let mut writer = LineWriter {
+            out: &mut *out,
+            options,
+            text,
+        };
+        match options.format {
+            Format::Dumb => writer.dumb_line(layout, &line, &reference)?,
+            Format::Roff => writer.roff_line(&line, &reference)?,
+            Format::Tex => writer.tex_line(&line, occurrence, &reference)?,
+        }
+    }
+
+    out.flush()
+}
+
+struct LineWriter<'a> {
+    out: &'a mut dyn Write,
+    options: &'a Options,
+    text: &'a [u8],
+}
+
+impl LineWriter<'_> {
+    fn field(&mut self, field: Field) -> io::Result<()> {
+        if field.end > field.start {
+            let bytes = &self.text[field.start as usize..field.end as usize];
+            self.escaped(bytes)?;
+        }
+        Ok(())
+    }
+
+    // Blanks become spaces; roff and TeX get their special characters quoted
+    fn escaped(&mut self, bytes: &[u8]) -> io::Result<()> {
+        for &byte in bytes {
+            match (self.options.format, byte) {
+                (_, b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r') => self.out.write_all(b" ")?,
+                (Format::Roff, b'"') => self.out.write_all(b"\"\"")?,
+                (Format::Tex, b'$' | b'%' | b'&' | b'#' | b'_') => self.out.write_all(&[b'\\', byte])?,
+                (Format::Tex, b'{' | b'}') => self.out.write_all(&[b'$', b'\\', byte, b'$'])?,
+                (Format::Tex, b'\\') => self.out.write_all(b"\\backslash{}")?,
+                _ => self.out.write_all(&[byte])?,
+            }
+        }
+        Ok(())
+    }
+
+    fn spaces(&mut self, count: isize) -> io::Result<()> {
+        for _ in 0..count.max(0) {
+            self.out.write_all(b" ")?;
+        }
+        Ok(())
+    }
+
+    fn truncation(&mut self, flagged: bool) -> io::Result<()> {
+        if flagged {
+            self.out.write_all(&self.options.truncation)?;
+        }
+        Ok(())
+    }
+
+    fn dumb_line(&mut self, layout: &Layout, line: &Line, reference: &[u8]) -> io::Result<()> {
+        let options = self.options;
+        let has_reference = options.auto_reference || options.input_reference;
+        let gap = options.gap;
+        let mark = |flagged: bool| if flagged { options.truncation.len() as isize } else { 0 };
+
+        // Without references this is just a leading gap
+        if !options.right_reference {
+            self.escaped(reference)?;
+            if options.auto_reference {
+                self.out.write_all(b":")?;
+                self.spaces(layout.reference_width + gap - reference.len() as isize - 1)?;
+            } else {
+                self.spaces(layout.reference_width + gap - reference.len() as isize)?;
+            }
+        }
+
+        let before_width = line.before.len() + mark(line.before_truncated);
+        if line.tail.start < line.tail.end {
+            self.field(line.tail)?;
+            self.truncation(line.tail_truncated)?;
+            self.spaces(layout.half_width - gap - before_width - line.tail.len() - mark(line.tail_truncated))?;
+        } else {
+            self.spaces(layout.half_width - gap - before_width)?;
+        }
+
+        self.truncation(line.before_truncated)?;
+        self.field(line.before)?;
+        self.spaces(gap)?;
+
+        self.field(line.keyafter)?;
+        self.truncation(line.keyafter_truncated)?;
+
+        let keyafter_width = line.keyafter.len() + mark(line.keyafter_truncated);
+        if line.head.start < line.head.end {
+            self.spaces(layout.half_width - keyafter_width - line.head.len() - mark(line.head_truncated))?;
+            self.truncation(line.head_truncated)?;
+            self.field(line.head)?;
+        } else if has_reference && options.right_reference {
+            self.spaces(layout.half_width - keyafter_width)?;
+        }
+
+        if has_reference && options.right_reference {
+            self.spaces(gap)?;
+            self.escaped(reference)?;
+        }
+
+        self.out.write_all(b"\n")
+    }
+
+    // .xx "tail" "before" "keyword and after" "head" ["reference"]
+    fn roff_line(&mut self, line: &Line, reference: &[u8]) -> io::Result<()> {
+        write!(self.out, ".{} \"", self.options.macro_name)?;
+        self.field(line.tail)?;
+        self.truncation(line.tail_truncated)?;
+
+        self.out.write_all(b"\" \"")?;
+        self.truncation(line.before_truncated)?;
+        self.field(line.before)?;
+
+        self.out.write_all(b"\" \"")?;
+        self.field(line.keyafter)?;
+        self.truncation(line.keyafter_truncated)?;
+
+        self.out.write_all(b"\" \"")?;
+        self.truncation(line.head_truncated)?;
+        self.field(line.head)?;
+        self.out.write_all(b"\"")?;
+
+        if self.options.auto_reference || self.options.input_reference {
+            self.out.write_all(b" \"")?;
+            self.escaped(reference)?;
+            self.out.write_all(b"\"")?;
+        }
+        self.out.write_all(b"\n")
+    }
+
+    // \xx {tail}{before}{keyword}{after}{head}[{reference}]
+    fn tex_line(&mut self, line: &Line, occurrence: &Occurrence, reference: &[u8]) -> io::Result<()> {
+        let key_end = line.keyafter.start + occurrence.key_len as isize;
+
+        write!(self.out, "\\{} {{", self.options.macro_name)?;
+        self.field(line.tail)?;
+        self.out.write_all(b"}{")?;
+        self.field(line.before)?;
+        self.out.write_all(b"}{")?;
+        self.field(Field {
+            start: line.keyafter.start,
+            end: key_end,
+        })?;
+        self.out.write_all(b"}{")?;
+        self.field(Field {
+            start: key_end,
+            end: line.keyafter.end,
+        })?;
+        self.out.write_all(b"}{")?;
+        self.field(line.head)?;
+        self.out.write_all(b"}")?;
+
+        if self.options.auto_reference || self.options.input_reference {
+            self.out.write_all(b"{")?;
+            self.escaped(reference)?;
+            self.out.write_all(b"}")?;
+        }
+        self.out.write_all(b"\n")
+    }
+}
@@ -0,0 +1,125 @@
+// ASD CoreUtils - realpath
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// What realpath and readlink share: turning a file name into an absolute
+// one with no ".", ".." or symbolic links in it, with GNU's rules for the
+// parts that need to exist.
+
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf};
+
+// Links followed for one name before giving up, as the kernel does
+const MAX_LINKS: usize = 40;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    // -e: every component must exist
+    Existing,
+    // The default for realpath, and readlink -f: all but the last
+    AllButLast,
+    // -m: none need exist
+    Missing,
+}
+
+// NAME made absolute and resolved, links being resolved as they are met,
+// so that ".." after one goes up from where it points
+pub fn canonicalize(name: &Path, mode: Mode) -> io::Result<PathBuf> {
+    if name.as_os_str().is_empty() {
+        return Err(io::Error::from_raw_os_error(libc::ENOENT));
+    }
+
+    let absolute = absolute(name)?;
+    // A trailing slash asks for a directory, as it does to the kernel
+    let wants_directory = name.as_os_str().as_bytes().ends_with(b"/");
+    resolve(&absolute, wants_directory, mode)
+}
+
+pub fn absolute(name: &Path) -> io::Result<PathBuf> {
+    if name.is_absolute() {
+        Ok(name.to_path_buf())
+    } else {
+        Ok(env::current_dir()?.join(name))
+    }
+}
+
+fn resolve(name: &Path, wants_directory: bool, mode: Mode) -> io::Result<PathBuf> {
+    let mut pending: VecDeque<OsString> = parts(name);
+    let mut result = PathBuf::from("/");
+    let mut links = 0;
+
+    while let Some(part) = pending.pop_front() {
+        if part == ".." {
+            result.pop();
+            continue;
+        }
+        result.push(&part);
+        let last = pending.is_empty();
+
+        let metadata = match fs::symlink_metadata(&result) {
+            Ok(metadata) => metadata,
+            Err(_) if mode == Mode::Missing => continue,
+            Err(err)
+                if mode == Mode::AllButLast && last && err.kind() == io::ErrorKind::NotFound =>
+            {
+                continue
+            }
+            Err(err) => return Err(err),
+        };
+
+        if metadata.file_type().is_symlink() {
+            links += 1;
+            if links > MAX_LINKS {
+                // With -m a loop is just another name that goes nowhere
+                if mode == Mode::Missing {
+                    continue;
+                }
+                return Err(io::Error::from_raw_os_error(libc::ELOOP));
+            }
+            let target = fs::read_link(&result)?;
+            result.pop();
+            if target.is_absolute() {
+                result = PathBuf::from("/");
+            }
+            for part in parts(&target).into_iter().rev() {
+                pending.push_front(part);
+            }
+            continue;
+        }
+
+        if !metadata.is_dir() && (!last || wants_directory) && mode != Mode::Missing {
+            return Err(io::Error::from_raw_os_error(libc::ENOTDIR));
+        }
+    }
+    Ok(result)
+}
+
+// The components of NAME that name something, ".." included
+fn parts(name: &Path) -> VecDeque<OsString> {
+    name.components()
+        .filter_map(|component| match component {
+            Component::ParentDir => Some(OsString::from("..")),
+            Component::Normal(part) => Some(part.to_os_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+// NAME as error messages show it: as it is, unless it would be hard to
+// make out, such as an empty one
+pub fn quote(name: &str) -> String {
+    if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"') {
+        if name.contains('\'') {
+            format!("\"{}\"", name)
+        } else {
+            format!("'{}'", name)
+        }
+    } else {
+        name.to_string()
+    }
+}
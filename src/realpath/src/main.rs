@@ -0,0 +1,247 @@
+// ASD CoreUtils - realpath
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod canonical;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use canonical::Mode;
+use clap::{Arg, ArgAction, Command};
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Symlinks {
+    // -P: resolve links as they are met
+    Physical,
+    // -L: take ".." off the name first, then resolve links
+    Logical,
+    // -s: resolve no links at all
+    Unresolved,
+}
+
+fn main() {
+    let matches = Command::new("realpath")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils realpath - print the resolved absolute file name")
+        .arg(
+            Arg::new("canonicalize-existing")
+                .short('e')
+                .long("canonicalize-existing")
+                .action(ArgAction::SetTrue)
+                .overrides_with("canonicalize-missing")
+                .help("All components of the path must exist"),
+        )
+        .arg(
+            Arg::new("canonicalize-missing")
+                .short('m')
+                .long("canonicalize-missing")
+                .action(ArgAction::SetTrue)
+                .overrides_with("canonicalize-existing")
+                .help("No path components need exist or be a directory"),
+        )
+        .arg(
+            Arg::new("logical")
+                .short('L')
+                .long("logical")
+                .action(ArgAction::SetTrue)
+                .overrides_with("physical")
+                .help("Resolve '..' components before symlinks"),
+        )
+        .arg(
+            Arg::new("physical")
+                .short('P')
+                .long("physical")
+                .action(ArgAction::SetTrue)
+                .overrides_with("logical")
+                .help("Resolve symlinks as encountered (default)"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .action(ArgAction::SetTrue)
+                .help("Suppress most error messages"),
+        )
+        .arg(
+            Arg::new("relative-to")
+                .long("relative-to")
+                .value_name("DIR")
+                .help("Print the resolved path relative to DIR"),
+        )
+        .arg(
+            Arg::new("relative-base")
+                .long("relative-base")
+                .value_name("DIR")
+                .help("Print absolute paths unless paths below DIR"),
+        )
+        .arg(
+            Arg::new("strip")
+                .short('s')
+                .long("strip")
+                .visible_alias("no-symlinks")
+                .action(ArgAction::SetTrue)
+                .help("Don't expand symlinks"),
+        )
+        .arg(
+            Arg::new("zero")
+                .short('z')
+                .long("zero")
+                .action(ArgAction::SetTrue)
+                .help("End each output line with NUL, not newline"),
+        )
+        .arg(Arg::new("files").value_name("FILE").num_args(0..))
+        .get_matches();
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("files")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    if files.is_empty() {
+        usage_error("realpath", "missing operand");
+    }
+
+    let mode = if matches.get_flag("canonicalize-existing") {
+        Mode::Existing
+    } else if matches.get_flag("canonicalize-missing") {
+        Mode::Missing
+    } else {
+        Mode::AllButLast
+    };
+    let symlinks = if matches.get_flag("strip") {
+        Symlinks::Unresolved
+    } else if matches.get_flag("logical") {
+        Symlinks::Logical
+    } else {
+        Symlinks::Physical
+    };
+    let quiet = matches.get_flag("quiet");
+    let delimiter = if matches.get_flag("zero") {
+        b'\0'
+    } else {
+        b'\n'
+    };
+
+    // The directories are canonicalized like the files
+    let directory = |option: &str| {
+        matches.get_one::<String>(option).map(|dir| {
+            resolve(Path::new(dir), mode, symlinks).unwrap_or_else(|err| {
+                eprintln!("realpath: {}: {}", canonical::quote(dir), strerror(&err));
+                process::exit(1);
+            })
+        })
+    };
+    let mut relative_to = directory("relative-to");
+    let mut relative_base = directory("relative-base");
+    if relative_to.is_none() {
+        relative_to = relative_base.clone();
+    }
+    // A base that is not above the directory to be relative to leaves
+    // every name absolute
+    if let (Some(to), Some(base)) = (&relative_to, &relative_base) {
+        if !is_within(to, base) {
+            relative_to = None;
+            relative_base = None;
+        }
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut ok = true;
+    for file in files {
+        let resolved = match resolve(Path::new(file), mode, symlinks) {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                if !quiet {
+                    eprintln!("realpath: {}: {}", canonical::quote(file), strerror(&err));
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        let shown: PathBuf = match (&relative_to, &relative_base) {
+            (Some(to), Some(base)) if is_within(&resolved, base) => relative(&resolved, to),
+            (Some(to), None) => relative(&resolved, to),
+            _ => resolved,
+        };
+        let result = out
+            .write_all(shown.as_os_str().as_bytes())
+            .and_then(|()| out.write_all(&[delimiter]));
+        if let Err(err) = result {
+            fail("realpath", &format!("write error: {}", strerror(&err)));
+        }
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
+
+fn resolve(name: &Path, mode: Mode, symlinks: Symlinks) -> io::Result<PathBuf> {
+    if symlinks == Symlinks::Physical || name.as_os_str().is_empty() {
+        return canonical::canonicalize(name, mode);
+    }
+
+    let normalized = normalize(&canonical::absolute(name)?);
+    // Still a directory if the name asked for one
+    let mut checked = OsString::from(&normalized);
+    if name.as_os_str().as_bytes().ends_with(b"/") {
+        checked.push("/");
+    }
+    if symlinks == Symlinks::Logical {
+        return canonical::canonicalize(Path::new(&checked), mode);
+    }
+
+    // Nothing is resolved, but what must exist still must
+    match (mode, fs::metadata(&checked)) {
+        (Mode::Missing, _) | (_, Ok(_)) => Ok(normalized),
+        (Mode::AllButLast, Err(err)) if err.kind() == io::ErrorKind::NotFound => Ok(normalized),
+        (_, Err(err)) => Err(err),
+    }
+}
+
+// NAME with "." and ".." taken out as text, without looking at the files
+fn normalize(name: &Path) -> PathBuf {
+    let mut result = PathBuf::from("/");
+    for component in name.components() {
+        match component {
+            Component::ParentDir => {
+                result.pop();
+            }
+            Component::Normal(part) => result.push(part),
+            _ => {}
+        }
+    }
+    result
+}
+
+// Whether NAME is BASE or something under it
+fn is_within(name: &Path, base: &Path) -> bool {
+    name.starts_with(base)
+}
+
+// NAME relative to the directory BASE; both are canonical
+fn relative(name: &Path, base: &Path) -> PathBuf {
+    let name_parts: Vec<Component> = name.components().collect();
+    let base_parts: Vec<Component> = base.components().collect();
+    let common = name_parts
+        .iter()
+        .zip(&base_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut result = PathBuf::new();
+    for _ in common..base_parts.len() {
+        result.push("..");
+    }
+    for part in &name_parts[common..] {
+        result.push(part);
+    }
+    if result.as_os_str().is_empty() {
+        result.push(".");
+    }
+    result
+}
@@ -0,0 +1,137 @@
+// ASD CoreUtils - readlink
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod canonical;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use canonical::Mode;
+use clap::{Arg, ArgAction, Command};
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::process;
+
+fn main() {
+    let matches = Command::new("readlink")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils readlink - print resolved symbolic links or canonical file names")
+        .arg(
+            Arg::new("canonicalize")
+                .short('f')
+                .long("canonicalize")
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["canonicalize-existing", "canonicalize-missing"])
+                .help("Canonicalize by following every symlink in every component of the given name recursively; all but the last component must exist"),
+        )
+        .arg(
+            Arg::new("canonicalize-existing")
+                .short('e')
+                .long("canonicalize-existing")
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["canonicalize", "canonicalize-missing"])
+                .help("Canonicalize by following every symlink in every component of the given name recursively; all components must exist"),
+        )
+        .arg(
+            Arg::new("canonicalize-missing")
+                .short('m')
+                .long("canonicalize-missing")
+                .action(ArgAction::SetTrue)
+                .overrides_with_all(["canonicalize", "canonicalize-existing"])
+                .help("Canonicalize by following every symlink in every component of the given name recursively, without requirements on components existence"),
+        )
+        .arg(
+            Arg::new("no-newline")
+                .short('n')
+                .long("no-newline")
+                .action(ArgAction::SetTrue)
+                .help("Do not output the trailing delimiter"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .visible_alias("silent")
+                .short_alias('s')
+                .action(ArgAction::SetTrue)
+                .overrides_with("verbose")
+                .help("Suppress most error messages (on by default)"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .overrides_with("quiet")
+                .help("Report error messages"),
+        )
+        .arg(
+            Arg::new("zero")
+                .short('z')
+                .long("zero")
+                .action(ArgAction::SetTrue)
+                .help("End each output line with NUL, not newline"),
+        )
+        .arg(Arg::new("files").value_name("FILE").num_args(0..))
+        .get_matches();
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("files")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    if files.is_empty() {
+        usage_error("readlink", "missing operand");
+    }
+
+    let mode = if matches.get_flag("canonicalize") {
+        Some(Mode::AllButLast)
+    } else if matches.get_flag("canonicalize-existing") {
+        Some(Mode::Existing)
+    } else if matches.get_flag("canonicalize-missing") {
+        Some(Mode::Missing)
+    } else {
+        None
+    };
+    let verbose = matches.get_flag("verbose");
+    let mut no_newline = matches.get_flag("no-newline");
+    if no_newline && files.len() > 1 {
+        eprintln!("readlink: ignoring --no-newline with multiple arguments");
+        no_newline = false;
+    }
+    let delimiter = if matches.get_flag("zero") {
+        b'\0'
+    } else {
+        b'\n'
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut ok = true;
+    for file in files {
+        let result = match mode {
+            Some(mode) => canonical::canonicalize(Path::new(file), mode),
+            None => fs::read_link(file),
+        };
+        let target = match result {
+            Ok(target) => target,
+            Err(err) => {
+                if verbose {
+                    eprintln!("readlink: {}: {}", canonical::quote(file), strerror(&err));
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        let mut result = out.write_all(target.as_os_str().as_bytes());
+        if !no_newline {
+            result = result.and_then(|()| out.write_all(&[delimiter]));
+        }
+        if let Err(err) = result {
+            fail("readlink", &format!("write error: {}", strerror(&err)));
+        }
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
@@ -0,0 +1,90 @@
+// ASD CoreUtils - rev
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::strerror;
+use clap::{Arg, Command};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+use unicode_segmentation::UnicodeSegmentation;
+
+fn main() {
+    let matches = Command::new("rev")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils rev - reverse lines characterwise")
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to reverse, or - for standard input")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let files: Vec<&str> = matches
+        .get_many::<String>("file")
+        .map(|files| files.map(|f| f.as_str()).collect())
+        .unwrap_or_else(|| vec!["-"]);
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut exit_code = 0;
+
+    for file in files {
+        let result = if file == "-" {
+            reverse_lines(&mut io::stdin().lock(), &mut out)
+        } else {
+            File::open(file).and_then(|f| reverse_lines(&mut BufReader::new(f), &mut out))
+        };
+
+        if let Err(err) = result {
+            let _ = out.flush();
+            eprintln!("rev: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    if let Err(err) = out.flush() {
+        eprintln!("rev: {}", strerror(&err));
+        exit_code = 1;
+    }
+
+    process::exit(exit_code);
+}
+
+fn reverse_lines(input: &mut impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+
+        let (content, newline) = match line.strip_suffix(b"\n") {
+            Some(content) => (content, true),
+            None => (&line[..], false),
+        };
+
+        for unit in split_units(content).iter().rev() {
+            out.write_all(unit)?;
+        }
+        if newline {
+            out.write_all(b"\n")?;
+        }
+    }
+}
+
+// Splits a line into grapheme clusters so combining marks and emoji
+// sequences stay intact; bytes that are not valid UTF-8 stand alone
+fn split_units(line: &[u8]) -> Vec<&[u8]> {
+    let mut units = Vec::new();
+
+    for chunk in line.utf8_chunks() {
+        units.extend(chunk.valid().graphemes(true).map(str::as_bytes));
+        units.extend(chunk.invalid().chunks(1));
+    }
+
+    units
+}
@@ -7,8 +7,13 @@
 #include <string.h>
 #include <unistd.h>
 #include <errno.h>
-#include <ftw.h>
+#include <dirent.h>
+#include <pthread.h>
 #include <getopt.h>
+#include <fcntl.h>
+#include <libgen.h>
+#include <limits.h>
+#include <time.h>
 #include <sys/stat.h>
 
 #define PROGRAM_NAME "rm"
@@ -20,8 +25,11 @@ struct rm_options {
     int force;         // -f, --force
     int verbose;       // -v, --verbose
     int interactive;   // -i, --interactive
-    int preserve_root; // --preserve-root
-    int no_preserve_root; // --no-preserve-root
+    int interactive_once; // -I: prompt once for many or recursive removals
+    int preserve_root; // --preserve-root, cleared by --no-preserve-root
+    int one_file_system; // --one-file-system
+    long jobs;         // -j, --jobs: threads for recursive removal
+    int trash;         // --trash, or ASD_RM_TRASH set to 1 in the environment
 };
 
 static struct rm_options options = {
@@ -29,22 +37,21 @@ static struct rm_options options = {
     .force = 0,
     .verbose = 0,
     .interactive = 0,
+    .interactive_once = 0,
     .preserve_root = 1,
-    .no_preserve_root = 0
+    .one_file_system = 0,
+    .jobs = 1,
+    .trash = 0
 };
 
 // Function prototypes
 static int remove_file(const char *path);
 static int remove_directory_recursive(const char *path);
+static int trash_file(const char *path);
 static void usage(int status);
 
-// Custom implementation of remove_directory_recursive using nftw
-static int handle_remove(const char *fpath, const struct stat *sb, 
-                        int typeflag, struct FTW *ftwbuf) {
-    (void)sb;      // Suppress unused parameter warning
-    (void)typeflag; // Suppress unused parameter warning
-    (void)ftwbuf;  // Suppress unused parameter warning
-    
+// Remove one entry met while removing a directory recursively
+static int handle_remove(const char *fpath) {
     int rv = remove(fpath);
     
     if (rv && !options.force) {
@@ -60,6 +67,237 @@ static int handle_remove(const char *fpath, const struct stat *sb,
     return 0;
 }
 
+// Create DIR if it does not exist yet; trash directories are private
+static int ensure_directory(const char *dir) {
+    if (mkdir(dir, 0700) == 0 || errno == EEXIST) {
+        return 0;
+    }
+    return -1;
+}
+
+// Create the files and info subdirectories of the trash directory TRASH
+static int prepare_trash(const char *trash) {
+    char sub[PATH_MAX];
+
+    if (ensure_directory(trash) != 0) {
+        return -1;
+    }
+    snprintf(sub, sizeof(sub), "%s/files", trash);
+    if (ensure_directory(sub) != 0) {
+        return -1;
+    }
+    snprintf(sub, sizeof(sub), "%s/info", trash);
+    return ensure_directory(sub);
+}
+
+// Find the trash directory for files on device DEV, whose mount point is
+// TOPDIR, storing it in TRASH and whether it is the home trash in IN_HOME.
+// The home trash is used for the filesystem
+// holding it; any other filesystem gets $topdir/.Trash/$uid when an
+// administrator has set up a shared, sticky .Trash there, and
+// $topdir/.Trash-$uid otherwise.
+static int find_trash(dev_t dev, const char *topdir, char *trash, size_t size, int *in_home) {
+    const char *data_home = getenv("XDG_DATA_HOME");
+    const char *home = getenv("HOME");
+    char home_trash[PATH_MAX];
+    struct stat st;
+
+    if (data_home && data_home[0] == '/') {
+        snprintf(home_trash, sizeof(home_trash), "%s/Trash", data_home);
+    } else if (home && home[0]) {
+        snprintf(home_trash, sizeof(home_trash), "%s/.local/share/Trash", home);
+    } else {
+        home_trash[0] = '\0';
+    }
+
+    if (home_trash[0]) {
+        // The trash may not exist yet, so go by the nearest directory that does
+        char probe[PATH_MAX];
+        snprintf(probe, sizeof(probe), "%s", home_trash);
+        while (stat(probe, &st) != 0 && strcmp(probe, "/") != 0) {
+            char *slash = strrchr(probe, '/');
+            if (slash == probe) {
+                probe[1] = '\0';
+            } else {
+                *slash = '\0';
+            }
+        }
+        if (st.st_dev == dev) {
+            if (stat(home_trash, &st) != 0) {
+                // Only the last two levels are created here; ~/.local/share
+                // is created if missing as other desktop programs would
+                char parent[PATH_MAX];
+                snprintf(parent, sizeof(parent), "%s", home_trash);
+                for (char *p = parent + 1; *p; p++) {
+                    if (*p == '/') {
+                        *p = '\0';
+                        ensure_directory(parent);
+                        *p = '/';
+                    }
+                }
+            }
+            snprintf(trash, size, "%s", home_trash);
+            *in_home = 1;
+            return prepare_trash(trash);
+        }
+    }
+
+    *in_home = 0;
+    snprintf(trash, size, "%s/.Trash", strcmp(topdir, "/") == 0 ? "" : topdir);
+    if (lstat(trash, &st) == 0 && S_ISDIR(st.st_mode) && (st.st_mode & S_ISVTX)) {
+        size_t len = strlen(trash);
+        snprintf(trash + len, size - len, "/%lu", (unsigned long)getuid());
+        if (prepare_trash(trash) == 0) {
+            return 0;
+        }
+    }
+
+    snprintf(trash, size, "%s/.Trash-%lu", strcmp(topdir, "/") == 0 ? "" : topdir,
+             (unsigned long)getuid());
+    return prepare_trash(trash);
+}
+
+// Find the mount point of the filesystem holding DIR, by climbing up until
+// the device changes
+static int find_topdir(const char *dir, char *topdir, size_t size) {
+    struct stat st, parent_st;
+    char parent[PATH_MAX];
+
+    snprintf(topdir, size, "%s", dir);
+    if (stat(topdir, &st) != 0) {
+        return -1;
+    }
+    while (strcmp(topdir, "/") != 0) {
+        snprintf(parent, sizeof(parent), "%s", topdir);
+        char *slash = strrchr(parent, '/');
+        if (slash == parent) {
+            parent[1] = '\0';
+        } else {
+            *slash = '\0';
+        }
+        if (stat(parent, &parent_st) != 0 || parent_st.st_dev != st.st_dev) {
+            break;
+        }
+        snprintf(topdir, size, "%s", parent);
+    }
+    return 0;
+}
+
+// Append S to BUF percent-encoded as a URI path
+static void append_encoded(char *buf, size_t size, const char *s) {
+    size_t len = strlen(buf);
+
+    for (; *s && len + 4 < size; s++) {
+        unsigned char c = (unsigned char)*s;
+        if ((c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || (c >= '0' && c <= '9') ||
+            strchr("/-_.~!$&'()*+,;=:@", c)) {
+            buf[len++] = c;
+        } else {
+            len += snprintf(buf + len, size - len, "%%%02X", c);
+        }
+    }
+    buf[len] = '\0';
+}
+
+// Move PATH into the trash following the FreeDesktop.org Trash
+// specification, recording where it came from so it can be restored
+static int trash_file(const char *path) {
+    char *path_copy = strdup(path);
+    char *dir_copy = strdup(path);
+    char parent[PATH_MAX], absolute[PATH_MAX + NAME_MAX + 2], topdir[PATH_MAX];
+    char trash[PATH_MAX + 32], name[NAME_MAX + 32];
+    char info_path[sizeof(trash) + sizeof(name) + 16], files_path[sizeof(trash) + sizeof(name) + 16];
+    char info[sizeof(absolute) * 3 + 128];
+    struct stat st;
+    int fd = -1;
+    int in_home;
+
+    if (!path_copy || !dir_copy) {
+        free(path_copy);
+        free(dir_copy);
+        fprintf(stderr, "%s: memory exhausted\n", PROGRAM_NAME);
+        return 1;
+    }
+
+    // The entry itself is not resolved, so a symbolic link is trashed as a link
+    const char *base = basename(path_copy);
+    if (!realpath(dirname(dir_copy), parent) || stat(parent, &st) != 0 ||
+        find_topdir(parent, topdir, sizeof(topdir)) != 0) {
+        goto fail;
+    }
+    snprintf(absolute, sizeof(absolute), "%s/%s", strcmp(parent, "/") == 0 ? "" : parent, base);
+    if (find_trash(st.st_dev, topdir, trash, sizeof(trash), &in_home) != 0) {
+        goto fail;
+    }
+
+    // Claim a name by creating its info file; NAME, NAME.2, NAME.3 and so on
+    for (unsigned long n = 1; fd == -1; n++) {
+        if (n == 1) {
+            snprintf(name, sizeof(name), "%s", base);
+        } else {
+            snprintf(name, sizeof(name), "%s.%lu", base, n);
+        }
+        snprintf(info_path, sizeof(info_path), "%s/info/%s.trashinfo", trash, name);
+        snprintf(files_path, sizeof(files_path), "%s/files/%s", trash, name);
+        fd = open(info_path, O_WRONLY | O_CREAT | O_EXCL, 0600);
+        if (fd == -1 && errno != EEXIST) {
+            goto fail;
+        }
+        if (fd != -1 && lstat(files_path, &st) == 0) {
+            // Left behind without its info file; leave it alone
+            close(fd);
+            unlink(info_path);
+            fd = -1;
+        }
+    }
+
+    // Paths in the home trash are absolute, those in a trash at the top of
+    // a mount point relative to it
+    const char *recorded = absolute;
+    if (!in_home && strcmp(topdir, "/") != 0) {
+        recorded = absolute + strlen(topdir) + 1;
+    }
+
+    time_t now = time(NULL);
+    char date[32];
+    strftime(date, sizeof(date), "%Y-%m-%dT%H:%M:%S", localtime(&now));
+    snprintf(info, sizeof(info), "[Trash Info]\nPath=");
+    append_encoded(info, sizeof(info), recorded);
+    size_t len = strlen(info);
+    snprintf(info + len, sizeof(info) - len, "\nDeletionDate=%s\n", date);
+
+    len = strlen(info);
+    if (write(fd, info, len) != (ssize_t)len || close(fd) != 0) {
+        fd = -1;
+        unlink(info_path);
+        goto fail;
+    }
+    fd = -1;
+
+    if (rename(path, files_path) != 0) {
+        int saved = errno;
+        unlink(info_path);
+        errno = saved;
+        goto fail;
+    }
+
+    if (options.verbose) {
+        printf("trashed '%s'\n", path);
+    }
+    free(path_copy);
+    free(dir_copy);
+    return 0;
+
+fail:
+    fprintf(stderr, "%s: cannot move '%s' to trash: %s\n", PROGRAM_NAME, path, strerror(errno));
+    if (fd != -1) {
+        close(fd);
+    }
+    free(path_copy);
+    free(dir_copy);
+    return 1;
+}
+
 // Remove a single file
 static int remove_file(const char *path) {
     if (options.interactive && !options.force) {
@@ -74,6 +312,10 @@ static int remove_file(const char *path) {
         }
     }
 
+    if (options.trash) {
+        return trash_file(path);
+    }
+
     if (remove(path) != 0) {
         if (!options.force) {
             fprintf(stderr, "%s: cannot remove '%s': %s\n", 
@@ -90,11 +332,222 @@ static int remove_file(const char *path) {
     return 0;
 }
 
-// Remove directory recursively using nftw
+// A directory being removed by remove_tree. It can go once it has been
+// read through and every subdirectory found in it is gone.
+struct dir_node {
+    char *path;
+    struct dir_node *parent;
+    int pending;              // Subdirectories left, plus one until it has been read
+    int failed;               // Something below could not be removed
+    struct dir_node *next;    // Next directory waiting to be read
+};
+
+// Work shared by the removal threads, or done by rm itself without --jobs.
+// The queue is a stack kept on the heap, so however deep the tree goes a
+// thread has only one directory open. The lock guards every field and the
+// pending and failed counts of all nodes.
+static struct {
+    pthread_mutex_t lock;
+    pthread_cond_t work;
+    struct dir_node *queue;   // Directories waiting to be read, newest first
+    int done;                 // The top directory has been dealt with
+    int failed;
+    dev_t root_dev;
+} tree = { PTHREAD_MUTEX_INITIALIZER, PTHREAD_COND_INITIALIZER, NULL, 0, 0, 0 };
+
+// Record that one more piece of NODE is finished, and remove NODE itself
+// once nothing is left, which may in turn finish its parent. Called with
+// the lock held; it is dropped around the removal itself.
+static void finish_node(struct dir_node *node, int failed) {
+    while (node) {
+        struct dir_node *parent = node->parent;
+
+        node->failed |= failed;
+        if (--node->pending > 0) {
+            return;
+        }
+
+        // Whatever was left behind has been reported already
+        failed = node->failed;
+        if (!failed) {
+            pthread_mutex_unlock(&tree.lock);
+            failed = handle_remove(node->path) != 0;
+            pthread_mutex_lock(&tree.lock);
+        }
+        if (!parent) {
+            tree.failed = failed;
+            tree.done = 1;
+            pthread_cond_broadcast(&tree.work);
+        }
+        free(node->path);
+        free(node);
+        node = parent;
+    }
+}
+
+// Read the directory NODE, removing everything in it but subdirectories,
+// which are queued for any thread to take up
+static void scan_node(struct dir_node *node) {
+    int failed = 0;
+    DIR *dir = opendir(node->path);
+
+    if (dir) {
+        struct dirent *entry;
+        size_t path_len = strlen(node->path);
+        const char *sep = node->path[path_len - 1] == '/' ? "" : "/";
+
+        while ((entry = readdir(dir)) != NULL) {
+            if (strcmp(entry->d_name, ".") == 0 || strcmp(entry->d_name, "..") == 0) {
+                continue;
+            }
+            size_t len = path_len + strlen(entry->d_name) + 2;
+            char *child = malloc(len);
+            if (!child) {
+                fprintf(stderr, "%s: memory exhausted\n", PROGRAM_NAME);
+                failed = 1;
+                break;
+            }
+            snprintf(child, len, "%s%s%s", node->path, sep, entry->d_name);
+
+            struct stat st;
+            int is_dir = entry->d_type == DT_DIR;
+            if (entry->d_type == DT_UNKNOWN || (is_dir && options.one_file_system)) {
+                if (lstat(child, &st) != 0) {
+                    if (!(errno == ENOENT && options.force)) {
+                        fprintf(stderr, "%s: cannot remove '%s': %s\n", PROGRAM_NAME, child,
+                                strerror(errno));
+                        failed = 1;
+                    }
+                    free(child);
+                    continue;
+                }
+                is_dir = S_ISDIR(st.st_mode);
+            }
+
+            if (!is_dir) {
+                failed |= handle_remove(child) != 0;
+                free(child);
+            } else if (options.one_file_system && st.st_dev != tree.root_dev) {
+                fprintf(stderr, "%s: skipping '%s', since it's on a different device\n",
+                        PROGRAM_NAME, child);
+                failed = 1;
+                free(child);
+            } else {
+                struct dir_node *sub = calloc(1, sizeof(*sub));
+                if (!sub) {
+                    fprintf(stderr, "%s: memory exhausted\n", PROGRAM_NAME);
+                    failed = 1;
+                    free(child);
+                    break;
+                }
+                sub->path = child;
+                sub->parent = node;
+                sub->pending = 1;
+                pthread_mutex_lock(&tree.lock);
+                node->pending++;
+                sub->next = tree.queue;
+                tree.queue = sub;
+                pthread_cond_signal(&tree.work);
+                pthread_mutex_unlock(&tree.lock);
+            }
+        }
+        closedir(dir);
+    }
+
+    // An unreadable directory is still tried, as it may be empty
+    pthread_mutex_lock(&tree.lock);
+    finish_node(node, failed);
+    pthread_mutex_unlock(&tree.lock);
+}
+
+static void *removal_worker(void *arg) {
+    (void)arg;
+
+    pthread_mutex_lock(&tree.lock);
+    for (;;) {
+        while (!tree.queue && !tree.done) {
+            pthread_cond_wait(&tree.work, &tree.lock);
+        }
+        if (!tree.queue) {
+            break;
+        }
+        struct dir_node *node = tree.queue;
+        tree.queue = node->next;
+        pthread_mutex_unlock(&tree.lock);
+        scan_node(node);
+        pthread_mutex_lock(&tree.lock);
+    }
+    pthread_mutex_unlock(&tree.lock);
+    return NULL;
+}
+
+// Remove the directory PATH on device DEV and everything below it,
+// children before their parents. Only with --jobs are threads started;
+// otherwise the order of removals, and of what -v prints, is fixed.
+static int remove_tree(const char *path, dev_t dev) {
+    pthread_t *threads = options.jobs > 1 ? malloc(options.jobs * sizeof(*threads)) : NULL;
+    struct dir_node *root = calloc(1, sizeof(*root));
+    long started = 0;
+
+    if ((options.jobs > 1 && !threads) || !root || !(root->path = strdup(path))) {
+        fprintf(stderr, "%s: memory exhausted\n", PROGRAM_NAME);
+        free(threads);
+        free(root);
+        return -1;
+    }
+    root->pending = 1;
+    tree.queue = root;
+    tree.done = 0;
+    tree.failed = 0;
+    tree.root_dev = dev;
+
+    for (long i = 0; threads && i < options.jobs; i++) {
+        if (pthread_create(&threads[i], NULL, removal_worker, NULL) != 0) {
+            break;
+        }
+        started++;
+    }
+    // Without any thread, the work is done here
+    if (started == 0) {
+        removal_worker(NULL);
+    }
+    for (long i = 0; i < started; i++) {
+        pthread_join(threads[i], NULL);
+    }
+    free(threads);
+
+    return tree.failed ? -1 : 0;
+}
+
+// Remove directory recursively, without following symbolic links
 static int remove_directory_recursive(const char *path) {
-    // FTW_DEPTH: Perform post-order traversal
-    // FTW_PHYS: Do not follow symbolic links
-    return nftw(path, handle_remove, 64, FTW_DEPTH | FTW_PHYS);
+    struct stat st;
+
+    if (lstat(path, &st) != 0) {
+        fprintf(stderr, "%s: cannot remove '%s': %s\n", PROGRAM_NAME, path, strerror(errno));
+        return -1;
+    }
+    if (!S_ISDIR(st.st_mode)) {
+        return handle_remove(path);
+    }
+    return remove_tree(path, st.st_dev);
+}
+
+// Ask once before a removal that -I considers worth confirming: more than
+// three arguments, or anything recursive
+static int confirm_once(int count) {
+    int c, answer;
+
+    if (count <= 3 && !options.recursive) {
+        return 1;
+    }
+    fprintf(stderr, "%s: remove %d argument%s%s? ", PROGRAM_NAME, count,
+            count == 1 ? "" : "s", options.recursive ? " recursively" : "");
+    answer = c = getchar();
+    while (c != '\n' && c != EOF) {
+        c = getchar();
+    }
+    return answer == 'y' || answer == 'Y';
 }
 
 // Print usage information
@@ -106,13 +559,25 @@ static void usage(int status) {
         printf("Remove (unlink) the FILE(s).\n\n");
         printf("  -f, --force           ignore nonexistent files and arguments, never prompt\n");
         printf("  -i, --interactive     prompt before every removal\n");
+        printf("  -I                    prompt once before removing more than three files, or\n");
+        printf("                          when removing recursively; less intrusive than -i,\n");
+        printf("                          while still giving protection against most mistakes\n");
+        printf("      --one-file-system  when removing a hierarchy recursively, skip any\n");
+        printf("                          directory that is on a file system different from\n");
+        printf("                          that of the corresponding command line argument\n");
+        printf("  -j, --jobs=N          remove directory contents with N threads (default: 1)\n");
         printf("  -r, -R, --recursive   remove directories and their contents recursively\n");
         printf("  -v, --verbose         explain what is being done\n");
         printf("      --help            display this help and exit\n");
         printf("      --preserve-root   do not remove '/' (default)\n");
         printf("      --no-preserve-root  do not treat '/' specially\n");
+        printf("      --trash           move files to the trash instead of unlinking them\n");
+        printf("      --no-trash        unlink files even if ASD_RM_TRASH is set\n");
         printf("\nBy default, rm does not remove directories. Use the --recursive (-r or -R)\n");
         printf("option to remove each listed directory, too, along with all of its contents.\n\n");
+        printf("With --trash, or with ASD_RM_TRASH=1 in the environment, files go to the\n");
+        printf("trash of the desktop (by default ~/.local/share/Trash), from where they can\n");
+        printf("be restored.\n\n");
     }
     exit(status);
 }
@@ -120,28 +585,54 @@ static void usage(int status) {
 int main(int argc, char *argv[]) {
     int c;
     int exit_status = EXIT_SUCCESS;
+    const char *trash_env = getenv("ASD_RM_TRASH");
+
+    if (trash_env && strcmp(trash_env, "1") == 0) {
+        options.trash = 1;
+    }
 
     static struct option long_options[] = {
         {"force",       no_argument, NULL, 'f'},
         {"interactive", no_argument, NULL, 'i'},
+        {"jobs",        required_argument, NULL, 'j'},
         {"recursive",   no_argument, NULL, 'r'},
         {"verbose",     no_argument, NULL, 'v'},
         {"help",        no_argument, NULL, 'h'},
         {"preserve-root", no_argument, &options.preserve_root, 1},
-        {"no-preserve-root", no_argument, &options.no_preserve_root, 1},
+        {"no-preserve-root", no_argument, &options.preserve_root, 0},
+        {"one-file-system", no_argument, &options.one_file_system, 1},
+        {"trash",       no_argument, &options.trash, 1},
+        {"no-trash",    no_argument, &options.trash, 0},
         {NULL, 0, NULL, 0}
     };
 
-    while ((c = getopt_long(argc, argv, "firRv", long_options, NULL)) != -1) {
+    while ((c = getopt_long(argc, argv, "fiIj:rRv", long_options, NULL)) != -1) {
         switch (c) {
             case 'f':
                 options.force = 1;
                 options.interactive = 0;
+                options.interactive_once = 0;
                 break;
             case 'i':
                 options.interactive = 1;
+                options.interactive_once = 0;
                 options.force = 0;
                 break;
+            case 'I':
+                options.interactive_once = 1;
+                options.interactive = 0;
+                options.force = 0;
+                break;
+            case 'j': {
+                char *end;
+                errno = 0;
+                options.jobs = strtol(optarg, &end, 10);
+                if (errno || *end || end == optarg || options.jobs < 1) {
+                    fprintf(stderr, "%s: invalid number of jobs: '%s'\n", PROGRAM_NAME, optarg);
+                    usage(EXIT_FAILURE);
+                }
+                break;
+            }
             case 'r':
             case 'R':
                 options.recursive = 1;
@@ -165,6 +656,15 @@ int main(int argc, char *argv[]) {
         usage(EXIT_FAILURE);
     }
 
+    if (options.interactive_once && !confirm_once(argc - optind)) {
+        return EXIT_SUCCESS;
+    }
+
+    // The root directory is recognised by identity, so that '//' or '/.'
+    // are caught as well
+    struct stat root_st;
+    int have_root = stat("/", &root_st) == 0;
+
     // Process each file argument
     for (int i = optind; i < argc; i++) {
         struct stat st;
@@ -180,9 +680,16 @@ int main(int argc, char *argv[]) {
         }
 
         // Check for root directory protection
-        if (options.preserve_root && !options.no_preserve_root && 
-            strcmp(argv[i], "/") == 0) {
-            fprintf(stderr, "%s: refusing to remove root directory '/'\n", 
+        if (options.recursive && options.preserve_root && have_root && S_ISDIR(st.st_mode) &&
+            st.st_dev == root_st.st_dev && st.st_ino == root_st.st_ino) {
+            if (strcmp(argv[i], "/") == 0) {
+                fprintf(stderr, "%s: it is dangerous to operate recursively on '/'\n",
+                        PROGRAM_NAME);
+            } else {
+                fprintf(stderr, "%s: it is dangerous to operate recursively on '%s' (same as '/')\n",
+                        PROGRAM_NAME, argv[i]);
+            }
+            fprintf(stderr, "%s: use --no-preserve-root to override this failsafe\n",
                     PROGRAM_NAME);
             exit_status = EXIT_FAILURE;
             continue;
@@ -195,6 +702,13 @@ int main(int argc, char *argv[]) {
                 exit_status = EXIT_FAILURE;
                 continue;
             }
+            // A directory goes to the trash whole
+            if (options.trash) {
+                if (remove_file(argv[i]) != 0) {
+                    exit_status = EXIT_FAILURE;
+                }
+                continue;
+            }
             if (remove_directory_recursive(argv[i]) != 0) {
                 exit_status = EXIT_FAILURE;
             }
@@ -0,0 +1,175 @@
+// ASD CoreUtils - runcon
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod selinux;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use selinux::Context;
+use std::ffi::OsString;
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process;
+
+const EXIT_CANNOT_INVOKE: i32 = 126;
+const EXIT_ENOENT: i32 = 127;
+
+fn main() {
+    let matches = Command::new("runcon")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils runcon - run a program in a different SELinux security context")
+        .override_usage(
+            "runcon CONTEXT COMMAND [args]\n       \
+             runcon [-c] [-u USER] [-r ROLE] [-t TYPE] [-l RANGE] COMMAND [args]",
+        )
+        .after_help("With neither CONTEXT nor COMMAND, print the current security context.")
+        .trailing_var_arg(true)
+        .arg(
+            Arg::new("compute")
+                .short('c')
+                .long("compute")
+                .help("Compute process transition context before modifying")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("user")
+                .short('u')
+                .long("user")
+                .value_name("USER")
+                .help("User identity"),
+        )
+        .arg(
+            Arg::new("role")
+                .short('r')
+                .long("role")
+                .value_name("ROLE")
+                .help("Role"),
+        )
+        .arg(
+            Arg::new("type")
+                .short('t')
+                .long("type")
+                .value_name("TYPE")
+                .help("Type (for same role as parent)"),
+        )
+        .arg(
+            Arg::new("range")
+                .short('l')
+                .long("range")
+                .value_name("RANGE")
+                .help("Level range"),
+        )
+        .arg(
+            Arg::new("args")
+                .value_name("ARG")
+                .help("CONTEXT when no field option is given, then the command and its arguments")
+                .num_args(1..)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .get_matches();
+
+    let mut args: Vec<OsString> = matches
+        .get_many::<OsString>("args")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+
+    if args.is_empty() {
+        match selinux::current_context() {
+            Ok(context) => println!("{}", context),
+            Err(err) => {
+                fail("runcon", &format!("failed to get current context: {}", strerror(&err)))
+            }
+        }
+        return;
+    }
+
+    let field = |id: &str| matches.get_one::<String>(id).cloned();
+    let (user, role, kind, range) = (field("user"), field("role"), field("type"), field("range"));
+    let compute = matches.get_flag("compute");
+
+    // Without any of the field options, the first operand is a whole context
+    let full_context = if !compute && user.is_none() && role.is_none() && kind.is_none() && range.is_none() {
+        Some(args.remove(0).to_string_lossy().into_owned())
+    } else {
+        None
+    };
+
+    if args.is_empty() {
+        usage_error("runcon", "no command specified");
+    }
+
+    if !selinux::is_enabled() {
+        fail("runcon", "runcon may be used only on a SELinux kernel");
+    }
+
+    let program = args.remove(0);
+
+    let context = match full_context {
+        Some(text) => {
+            if Context::parse(&text).is_none() {
+                fail("runcon", &format!("invalid context: '{}'", text));
+            }
+            text
+        }
+        None => {
+            let mut base = selinux::current_context().unwrap_or_else(|err| {
+                fail("runcon", &format!("failed to get current context: {}", strerror(&err)))
+            });
+
+            if compute {
+                let target = selinux::file_context(Path::new(&program)).unwrap_or_else(|err| {
+                    fail(
+                        "runcon",
+                        &format!(
+                            "failed to get security context of '{}': {}",
+                            program.to_string_lossy(),
+                            strerror(&err)
+                        ),
+                    )
+                });
+                base = selinux::compute_process_transition(&base, &target).unwrap_or_else(|err| {
+                    fail("runcon", &format!("failed to compute a new context: {}", strerror(&err)))
+                });
+            }
+
+            let mut context = Context::parse(&base).unwrap_or_else(|| fail("runcon", &format!("invalid context: '{}'", base)));
+            if let Some(user) = user {
+                context.user = user;
+            }
+            if let Some(role) = role {
+                context.role = role;
+            }
+            if let Some(kind) = kind {
+                context.kind = kind;
+            }
+            if range.is_some() {
+                context.range = range;
+            }
+            context.to_string()
+        }
+    };
+
+    if let Err(err) = selinux::set_exec_context(&context) {
+        fail(
+            "runcon",
+            &format!("unable to set security context '{}': {}", context, strerror(&err)),
+        );
+    }
+
+    let err = process::Command::new(&program).args(args).exec();
+
+    let code = if err.kind() == io::ErrorKind::NotFound {
+        EXIT_ENOENT
+    } else {
+        EXIT_CANNOT_INVOKE
+    };
+    eprintln!(
+        "runcon: failed to run command '{}': {}",
+        program.to_string_lossy(),
+        strerror(&err)
+    );
+    process::exit(code);
+}
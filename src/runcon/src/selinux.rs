@@ -0,0 +1,131 @@
+// ASD CoreUtils - runcon
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Minimal access to SELinux through the kernel's own interfaces, the
+// process attributes in /proc and the selinuxfs mount, so that no
+// libselinux is needed at build or run time
+
+use std::ffi::CString;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+const SELINUXFS: &str = "/sys/fs/selinux";
+
+// A security context, "user:role:type" with an optional MLS range, which
+// may itself contain colons ("s0-s0:c0.c1023")
+pub struct Context {
+    pub user: String,
+    pub role: String,
+    pub kind: String,
+    pub range: Option<String>,
+}
+
+impl Context {
+    pub fn parse(text: &str) -> Option<Context> {
+        let mut fields = text.splitn(4, ':');
+        let user = fields.next().filter(|field| !field.is_empty())?;
+        let role = fields.next().filter(|field| !field.is_empty())?;
+        let kind = fields.next().filter(|field| !field.is_empty())?;
+        let range = match fields.next() {
+            Some("") => return None,
+            range => range,
+        };
+
+        Some(Context {
+            user: user.to_string(),
+            role: role.to_string(),
+            kind: kind.to_string(),
+            range: range.map(str::to_string),
+        })
+    }
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.user, self.role, self.kind)?;
+        if let Some(range) = &self.range {
+            write!(f, ":{}", range)?;
+        }
+        Ok(())
+    }
+}
+
+// The policy is loaded when selinuxfs is mounted and populated
+pub fn is_enabled() -> bool {
+    Path::new(SELINUXFS).join("enforce").exists()
+}
+
+pub fn current_context() -> io::Result<String> {
+    read_attribute("/proc/thread-self/attr/current")
+}
+
+// Context the next execve() of this thread will run in
+pub fn set_exec_context(context: &str) -> io::Result<()> {
+    let mut value = context.as_bytes().to_vec();
+    value.push(0);
+    OpenOptions::new()
+        .write(true)
+        .open("/proc/thread-self/attr/exec")?
+        .write_all(&value)
+}
+
+pub fn file_context(path: &Path) -> io::Result<String> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    let name = b"security.selinux\0";
+    let mut buffer = vec![0u8; 256];
+
+    loop {
+        let size = unsafe {
+            libc::getxattr(
+                c_path.as_ptr(),
+                name.as_ptr() as *const libc::c_char,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+            )
+        };
+        if size >= 0 {
+            buffer.truncate(size as usize);
+            return Ok(trim_context(&buffer));
+        }
+
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() != Some(libc::ERANGE) {
+            return Err(err);
+        }
+        buffer.resize(buffer.len() * 2, 0);
+    }
+}
+
+// Asks the policy which context a process in SOURCE gets when it executes
+// a file labelled TARGET, like security_compute_create(3)
+pub fn compute_process_transition(source: &str, target: &str) -> io::Result<String> {
+    let class_index = fs::read_to_string(Path::new(SELINUXFS).join("class/process/index"))?;
+
+    let mut interface = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(Path::new(SELINUXFS).join("create"))?;
+    interface.write_all(format!("{} {} {}", source, target, class_index.trim()).as_bytes())?;
+
+    let mut result = Vec::new();
+    interface.read_to_end(&mut result)?;
+    Ok(trim_context(&result))
+}
+
+fn read_attribute(path: &str) -> io::Result<String> {
+    Ok(trim_context(&fs::read(path)?))
+}
+
+// The kernel hands contexts back with a trailing NUL or newline
+fn trim_context(bytes: &[u8]) -> String {
+    let end = bytes
+        .iter()
+        .position(|&b| b == 0 || b == b'\n')
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
@@ -0,0 +1,192 @@
+// ASD CoreUtils - seq
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The -f FORMAT: one printf floating point conversion, %e, %f or %g, with
+// text around it.
+
+pub struct Format {
+    prefix: String,
+    suffix: String,
+    left: bool,
+    sign: bool,
+    space: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+impl Format {
+    pub fn parse(text: &str) -> Result<Format, String> {
+        let bytes = text.as_bytes();
+        let Some(start) = find_directive(bytes, 0) else {
+            return Err(format!("format '{}' has no % directive", text));
+        };
+        let ends = || format!("format '{}' ends in %", text);
+
+        let mut format = Format {
+            prefix: text[..start].replace("%%", "%"),
+            suffix: String::new(),
+            left: false,
+            sign: false,
+            space: false,
+            alternate: false,
+            zero: false,
+            width: 0,
+            precision: None,
+            conversion: 'g',
+        };
+
+        let mut i = start + 1;
+        while let Some(&flag) = bytes.get(i) {
+            match flag {
+                b'-' => format.left = true,
+                b'+' => format.sign = true,
+                b' ' => format.space = true,
+                b'#' => format.alternate = true,
+                b'0' => format.zero = true,
+                // Grouping, which the C locale has none of
+                b'\'' => {}
+                _ => break,
+            }
+            i += 1;
+        }
+        let digits = |i: &mut usize| {
+            let begin = *i;
+            while bytes.get(*i).is_some_and(u8::is_ascii_digit) {
+                *i += 1;
+            }
+            text[begin..*i].parse::<usize>().unwrap_or(0)
+        };
+        format.width = digits(&mut i);
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            format.precision = Some(digits(&mut i));
+        }
+        if bytes.get(i) == Some(&b'L') {
+            i += 1;
+        }
+
+        let Some(conversion) = text[i..].chars().next() else {
+            return Err(ends());
+        };
+        if !"eEfFgG".contains(conversion) {
+            return Err(format!(
+                "format '{}' has unknown %{} directive",
+                text, conversion
+            ));
+        }
+        format.conversion = conversion;
+
+        let rest = i + conversion.len_utf8();
+        if find_directive(bytes, rest).is_some() {
+            return Err(format!("format '{}' has too many % directives", text));
+        }
+        format.suffix = text[rest..].replace("%%", "%");
+        Ok(format)
+    }
+
+    pub fn render(&self, value: f64) -> String {
+        let precision = self.precision.unwrap_or(6);
+        let magnitude = value.abs();
+        let mut body = if !value.is_finite() {
+            if value.is_nan() { "nan" } else { "inf" }.to_string()
+        } else {
+            match self.conversion.to_ascii_lowercase() {
+                'f' => fixed(magnitude, precision, self.alternate),
+                'e' => exponential(magnitude, precision, self.alternate),
+                _ => general(magnitude, precision, self.alternate),
+            }
+        };
+        if self.conversion.is_ascii_uppercase() {
+            body = body.to_ascii_uppercase();
+        }
+
+        let sign = if value.is_sign_negative() && !value.is_nan() {
+            "-"
+        } else if self.sign {
+            "+"
+        } else if self.space {
+            " "
+        } else {
+            ""
+        };
+        let fill = self.width.saturating_sub(sign.len() + body.len());
+        let field = if self.left {
+            format!("{}{}{}", sign, body, " ".repeat(fill))
+        } else if self.zero && value.is_finite() {
+            format!("{}{}{}", sign, "0".repeat(fill), body)
+        } else {
+            format!("{}{}{}", " ".repeat(fill), sign, body)
+        };
+        format!("{}{}{}", self.prefix, field, self.suffix)
+    }
+}
+
+// Where the first % that is not part of a %% is, from FROM on
+fn find_directive(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut i = from;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if bytes.get(i + 1) != Some(&b'%') {
+                return Some(i);
+            }
+            i += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+// %f
+fn fixed(value: f64, precision: usize, alternate: bool) -> String {
+    let mut text = format!("{:.*}", precision, value);
+    if alternate && precision == 0 {
+        text.push('.');
+    }
+    text
+}
+
+// %e, with the exponent signed and at least two digits long
+fn exponential(value: f64, precision: usize, alternate: bool) -> String {
+    let (mut mantissa, exponent) = split_exponent(value, precision);
+    if alternate && precision == 0 {
+        mantissa.push('.');
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{}{:02}", mantissa, sign, exponent.unsigned_abs())
+}
+
+// %g: %e when the exponent is below -4 or not below the precision, %f
+// otherwise, without trailing zeros unless '#' asks for them
+fn general(value: f64, precision: usize, alternate: bool) -> String {
+    let precision = precision.max(1);
+    let (_, exponent) = split_exponent(value, precision - 1);
+    let mut text = if exponent < -4 || exponent >= precision as i32 {
+        exponential(value, precision - 1, alternate)
+    } else {
+        fixed(value, (precision as i32 - 1 - exponent) as usize, alternate)
+    };
+    if alternate {
+        return text;
+    }
+
+    let exponent_part = text
+        .find('e')
+        .map(|at| text.split_off(at))
+        .unwrap_or_default();
+    if text.contains('.') {
+        let kept = text.trim_end_matches('0').trim_end_matches('.').len();
+        text.truncate(kept);
+    }
+    text + &exponent_part
+}
+
+// VALUE rounded to PRECISION digits after the first, and its exponent
+fn split_exponent(value: f64, precision: usize) -> (String, i32) {
+    let text = format!("{:.*e}", precision, value);
+    let (mantissa, exponent) = text.split_once('e').unwrap_or((&text, "0"));
+    (mantissa.to_string(), exponent.parse().unwrap_or(0))
+}
@@ -0,0 +1,317 @@
+// ASD CoreUtils - seq
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod format;
+mod number;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use format::Format;
+use number::{Operand, Value};
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, BufWriter, Write};
+use std::os::unix::ffi::OsStrExt;
+
+fn main() {
+    // Like other tools, stop quietly when the reader goes away; "seq inf"
+    // never ends otherwise
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    let matches = Command::new("seq")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils seq - print numbers from FIRST to LAST, in steps of INCREMENT")
+        .override_usage(
+            "seq [OPTION]... LAST\n       \
+             seq [OPTION]... FIRST LAST\n       \
+             seq [OPTION]... FIRST INCREMENT LAST",
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .help("Use printf style floating-point FORMAT"),
+        )
+        .arg(
+            Arg::new("separator")
+                .short('s')
+                .long("separator")
+                .value_name("STRING")
+                .default_value("\n")
+                .hide_default_value(true)
+                .help("Use STRING to separate numbers (default: \\n)"),
+        )
+        .arg(
+            Arg::new("equal-width")
+                .short('w')
+                .long("equal-width")
+                .action(ArgAction::SetTrue)
+                .help("Equalize width by padding with leading zeroes"),
+        )
+        .arg(Arg::new("numbers").value_name("NUMBER").num_args(0..))
+        .after_help(
+            "If FIRST or INCREMENT is omitted, it defaults to 1. That is, an\n\
+             omitted INCREMENT defaults to 1 even when LAST is smaller than FIRST.\n\
+             The sequence of numbers ends when the sum of the current number and\n\
+             INCREMENT would become greater than LAST.\n\
+             FIRST, INCREMENT, and LAST are interpreted as floating point values.\n\
+             FORMAT must be suitable for printing one argument of type 'double';\n\
+             it defaults to %.PRECf if FIRST, INCREMENT, and LAST are all fixed point\n\
+             decimal numbers with maximum precision PREC, and to %g otherwise.",
+        )
+        .get_matches_from(mark_operands(env::args_os().collect()));
+
+    let numbers: Vec<&String> = matches
+        .get_many::<String>("numbers")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    let equal_width = matches.get_flag("equal-width");
+    let separator = matches.get_one::<String>("separator").unwrap();
+
+    let format = matches.get_one::<String>("format").map(|text| {
+        if equal_width {
+            usage_error(
+                "seq",
+                "format string may not be specified when printing equal width strings",
+            );
+        }
+        Format::parse(text).unwrap_or_else(|message| fail("seq", &message))
+    });
+
+    if numbers.is_empty() {
+        usage_error("seq", "missing operand");
+    }
+    if numbers.len() > 3 {
+        usage_error("seq", &format!("extra operand '{}'", numbers[3]));
+    }
+    let operands: Vec<Operand> = numbers
+        .iter()
+        .map(|text| number::parse(text).unwrap_or_else(|message| usage_error("seq", &message)))
+        .collect();
+    let one = || Operand {
+        value: Value::Exact(1, 0),
+        precision: 0,
+        width: 1,
+    };
+    let mut operands = operands.into_iter();
+    let (first, step, last) = match numbers.len() {
+        1 => (one(), one(), operands.next().unwrap()),
+        2 => (operands.next().unwrap(), one(), operands.next().unwrap()),
+        _ => (
+            operands.next().unwrap(),
+            operands.next().unwrap(),
+            operands.next().unwrap(),
+        ),
+    };
+    if step.value.is_zero() {
+        usage_error(
+            "seq",
+            &format!("invalid Zero increment value: '{}'", numbers[1]),
+        );
+    }
+
+    let style = match format {
+        Some(format) => Style::Custom(format),
+        None => default_style(&first, &step, &last, equal_width),
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if let Err(err) =
+        print_sequence(&mut out, &first, &step, &last, &style, separator).and_then(|()| out.flush())
+    {
+        fail("seq", &format!("write error: {}", strerror(&err)));
+    }
+}
+
+// Options come only before the first operand, and "-1" or "-.5" is an
+// operand, a negative number; a "--" put in front of the first operand has
+// clap see them that way too
+fn mark_operands(args: Vec<OsString>) -> Vec<OsString> {
+    let mut marked = Vec::with_capacity(args.len() + 1);
+    let mut args = args.into_iter();
+    marked.extend(args.next());
+
+    while let Some(arg) = args.next() {
+        let bytes = arg.as_bytes();
+        if arg == "--" {
+            marked.push(arg);
+            break;
+        }
+        let is_option =
+            bytes.len() > 1 && bytes[0] == b'-' && bytes[1] != b'.' && !bytes[1].is_ascii_digit();
+        if !is_option {
+            marked.push(OsString::from("--"));
+            marked.push(arg);
+            break;
+        }
+
+        // -f and -s take the next argument unless their value is attached
+        let takes_value = if bytes.starts_with(b"--") {
+            bytes == b"--format" || bytes == b"--separator"
+        } else {
+            bytes[1..]
+                .iter()
+                .position(|&b| b == b'f' || b == b's')
+                .is_some_and(|at| at + 2 == bytes.len())
+        };
+        marked.push(arg);
+        if takes_value {
+            marked.extend(args.next());
+        }
+    }
+    marked.extend(args);
+    marked
+}
+
+enum Style {
+    // -f
+    Custom(Format),
+    // As many digits after the point as FIRST or INCREMENT has, and with
+    // -w, zeros in front up to WIDTH
+    Fixed { precision: usize, width: usize },
+}
+
+fn default_style(first: &Operand, step: &Operand, last: &Operand, equal_width: bool) -> Style {
+    let precision = first.precision.max(step.precision);
+    if !equal_width {
+        return Style::Fixed {
+            precision,
+            width: 0,
+        };
+    }
+
+    // LAST is never printed as written, so its width is what it would be
+    // with the sequence's precision
+    let mut first_width = first.width + (precision - first.precision);
+    let mut last_width = (last.width + precision).saturating_sub(last.precision);
+    if last.precision > 0 && precision == 0 {
+        last_width = last_width.saturating_sub(1);
+    }
+    if last.precision == 0 && precision > 0 {
+        last_width += 1;
+    }
+    if first.precision == 0 && precision > 0 {
+        first_width += 1;
+    }
+    Style::Fixed {
+        precision,
+        width: first_width.max(last_width),
+    }
+}
+
+fn print_sequence(
+    out: &mut impl Write,
+    first: &Operand,
+    step: &Operand,
+    last: &Operand,
+    style: &Style,
+    separator: &str,
+) -> io::Result<()> {
+    let mut printed = false;
+    let mut emit = |out: &mut dyn Write, text: &str| -> io::Result<()> {
+        if printed {
+            out.write_all(separator.as_bytes())?;
+        }
+        printed = true;
+        out.write_all(text.as_bytes())
+    };
+    let descending = step.value.is_negative();
+
+    // Exactly, in units of the smallest digit any operand has, where
+    // FIRST and INCREMENT fit; otherwise in floating point, each number
+    // worked out afresh from FIRST so that errors do not build up
+    let scale = [first.value, step.value, last.value]
+        .iter()
+        .filter_map(|value| match *value {
+            Value::Exact(_, scale) => Some(scale),
+            Value::Float(_) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    if let (Some(mut current), Some(increment)) =
+        (first.value.rescale(scale), step.value.rescale(scale))
+    {
+        let bound = last.value.rescale(scale);
+        loop {
+            let beyond = match bound {
+                Some(bound) if descending => current < bound,
+                Some(bound) => current > bound,
+                None => {
+                    let current = Value::Exact(current, scale).to_f64();
+                    let bound = last.value.to_f64();
+                    if descending {
+                        current < bound
+                    } else {
+                        current > bound
+                    }
+                }
+            };
+            if beyond {
+                break;
+            }
+            emit(out, &render_exact(current, scale, style))?;
+            match current.checked_add(increment) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+    } else {
+        let (start, increment, bound) = (
+            first.value.to_f64(),
+            step.value.to_f64(),
+            last.value.to_f64(),
+        );
+        let mut index = 0u64;
+        loop {
+            let current = start + index as f64 * increment;
+            if (descending && current < bound) || (!descending && current > bound) {
+                break;
+            }
+            emit(out, &render_float(current, style))?;
+            index += 1;
+        }
+    }
+
+    if printed {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+// DIGITS / 10^SCALE, which has no more digits after the point than the
+// precision asks for
+fn render_exact(digits: i128, scale: u32, style: &Style) -> String {
+    match *style {
+        Style::Custom(ref format) => format.render(Value::Exact(digits, scale).to_f64()),
+        Style::Fixed { precision, width } => {
+            let precision = precision as u32;
+            let digits = digits / 10i128.pow(scale - precision);
+            pad(number::decimal(digits, precision), width)
+        }
+    }
+}
+
+fn render_float(value: f64, style: &Style) -> String {
+    match *style {
+        Style::Custom(ref format) => format.render(value),
+        Style::Fixed { precision, width } => pad(format!("{:.*}", precision, value), width),
+    }
+}
+
+// Zeros after any sign, up to WIDTH
+fn pad(text: String, width: usize) -> String {
+    if text.len() >= width {
+        return text;
+    }
+    let zeros = "0".repeat(width - text.len());
+    match text.strip_prefix('-') {
+        Some(rest) => format!("-{}{}", zeros, rest),
+        None => format!("{}{}", zeros, text),
+    }
+}
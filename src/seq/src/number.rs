@@ -0,0 +1,189 @@
+// ASD CoreUtils - seq
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The operands: their values, held exactly as scaled integers so that
+// decimal steps do not drift, along with the precision and width their
+// spelling gives the default output format.
+
+#[derive(Clone, Copy)]
+pub enum Value {
+    // DIGITS / 10^SCALE
+    Exact(i128, u32),
+    // Too large to hold exactly, or infinite
+    Float(f64),
+}
+
+impl Value {
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Value::Exact(digits, scale) => decimal(digits, scale).parse().unwrap_or(f64::NAN),
+            Value::Float(value) => value,
+        }
+    }
+
+    pub fn is_zero(self) -> bool {
+        match self {
+            Value::Exact(digits, _) => digits == 0,
+            Value::Float(value) => value == 0.0,
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        match self {
+            Value::Exact(digits, _) => digits < 0,
+            Value::Float(value) => value < 0.0,
+        }
+    }
+
+    // The digits at SCALE, which must be no smaller than the value's own
+    pub fn rescale(self, scale: u32) -> Option<i128> {
+        match self {
+            Value::Exact(digits, own) => 10i128
+                .checked_pow(scale - own)
+                .and_then(|factor| digits.checked_mul(factor)),
+            Value::Float(_) => None,
+        }
+    }
+}
+
+pub struct Operand {
+    pub value: Value,
+    // Digits after the decimal point
+    pub precision: usize,
+    // Characters it takes when printed with that precision
+    pub width: usize,
+}
+
+// DIGITS / 10^SCALE written out in full
+pub fn decimal(digits: i128, scale: u32) -> String {
+    let scale = scale as usize;
+    let padded = format!("{:0>width$}", digits.unsigned_abs(), width = scale + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - scale);
+    let sign = if digits < 0 { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Operand, String> {
+    let invalid = || format!("invalid floating point argument: '{}'", text);
+    let trimmed = text.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let (negative, body) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+
+    let lower = body.to_ascii_lowercase();
+    if lower == "nan" {
+        return Err(format!("invalid 'not-a-number' argument: '{}'", text));
+    }
+    if lower == "inf" || lower == "infinity" {
+        let value = if negative {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        return Ok(Operand {
+            value: Value::Float(value),
+            precision: 0,
+            width: 0,
+        });
+    }
+    if let Some(hex) = lower.strip_prefix("0x") {
+        if hex.is_empty() || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let value = match i128::from_str_radix(hex, 16) {
+            Ok(digits) => Value::Exact(if negative { -digits } else { digits }, 0),
+            Err(_) => {
+                let magnitude = hex.bytes().fold(0.0, |value, b| {
+                    value * 16.0 + f64::from((b as char).to_digit(16).unwrap_or(0))
+                });
+                if !magnitude.is_finite() {
+                    return Err(invalid());
+                }
+                Value::Float(if negative { -magnitude } else { magnitude })
+            }
+        };
+        let width = value_width(value, 0);
+        return Ok(Operand {
+            value,
+            precision: 0,
+            width,
+        });
+    }
+
+    let (mantissa, exponent) = match body.find(['e', 'E']) {
+        Some(at) => (&body[..at], Some(&body[at + 1..])),
+        None => (body, None),
+    };
+    let has_exponent = exponent.is_some();
+    let point = mantissa.contains('.');
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+    let exponent: i64 = match exponent {
+        Some(exponent)
+            if !exponent.is_empty() && is_digits(exponent.trim_start_matches(['+', '-'])) =>
+        {
+            exponent.parse().map_err(|_| invalid())?
+        }
+        Some(_) => return Err(invalid()),
+        None => 0,
+    };
+
+    // What is left after the point once the exponent has moved it
+    let scale = fraction.len() as i64 - exponent;
+    let precision = scale.max(0) as usize;
+
+    let digits = format!("{}{}", whole, fraction);
+    let exact = digits
+        .parse::<i128>()
+        .ok()
+        .and_then(|digits| match u32::try_from(-scale) {
+            Ok(shift) => 10i128
+                .checked_pow(shift)
+                .and_then(|factor| digits.checked_mul(factor)),
+            Err(_) => Some(digits),
+        })
+        .zip(u32::try_from(scale.max(0)).ok());
+    let value = match exact {
+        Some((digits, scale)) => Value::Exact(if negative { -digits } else { digits }, scale),
+        None => match trimmed.parse::<f64>() {
+            // Out of range, as strtold would say
+            Ok(value) if value.is_finite() => Value::Float(value),
+            _ => return Err(invalid()),
+        },
+    };
+
+    // As written, unless an exponent makes that meaningless; "5." prints
+    // as "5" and ".5" as "0.5"
+    let width = if has_exponent {
+        value_width(value, precision)
+    } else if point && fraction.is_empty() {
+        trimmed.len() - 1
+    } else if point && whole.is_empty() {
+        trimmed.len() + 1
+    } else {
+        trimmed.len()
+    };
+
+    Ok(Operand {
+        value,
+        precision,
+        width,
+    })
+}
+
+fn value_width(value: Value, precision: usize) -> usize {
+    match value {
+        Value::Exact(digits, scale) => decimal(digits, scale).len(),
+        Value::Float(value) => format!("{:.*}", precision, value).len(),
+    }
+}
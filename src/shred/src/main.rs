@@ -0,0 +1,420 @@
+// ASD CoreUtils - shred
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, ArgAction, Command};
+use std::ffi::{CString, OsStr};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process;
+
+const DEFAULT_PASSES: usize = 3;
+const BUFFER_SIZE: usize = 64 * 1024;
+const RANDOM_SOURCE: &str = "/dev/urandom";
+
+// File systems that write changed blocks to new locations, leaving the
+// old contents on disk no matter how often the file is overwritten
+const COPY_ON_WRITE_FILESYSTEMS: &[(i64, &str)] = &[
+    (0x9123683e, "btrfs"),
+    (0x2fc12fc1, "zfs"),
+    (0xca451a4e, "bcachefs"),
+    (0xf2f52010, "f2fs"),
+    (0x3434, "nilfs2"),
+];
+
+// Patterns for the passes between random ones, chosen to flip each bit
+// both ways and to exercise the common disk encodings
+const PATTERNS: &[[u8; 3]] = &[
+    [0x00, 0x00, 0x00],
+    [0xff, 0xff, 0xff],
+    [0x55, 0x55, 0x55],
+    [0xaa, 0xaa, 0xaa],
+    [0x24, 0x92, 0x49],
+    [0x49, 0x24, 0x92],
+    [0x92, 0x49, 0x24],
+    [0x6d, 0xb6, 0xdb],
+    [0xb6, 0xdb, 0x6d],
+    [0xdb, 0x6d, 0xb6],
+    [0x11, 0x11, 0x11],
+    [0x22, 0x22, 0x22],
+    [0x33, 0x33, 0x33],
+    [0x44, 0x44, 0x44],
+    [0x66, 0x66, 0x66],
+    [0x77, 0x77, 0x77],
+    [0x88, 0x88, 0x88],
+    [0x99, 0x99, 0x99],
+    [0xbb, 0xbb, 0xbb],
+    [0xcc, 0xcc, 0xcc],
+    [0xdd, 0xdd, 0xdd],
+    [0xee, 0xee, 0xee],
+];
+
+// Characters the names a file is renamed through before removal are made of
+const NAME_CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_.";
+
+#[derive(Clone, Copy)]
+enum Pass {
+    Random,
+    Pattern([u8; 3]),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Remove {
+    // Just unlink
+    Unlink,
+    // Rename to ever shorter names first, to hide the original one
+    Wipe,
+    // As Wipe, syncing the directory after each rename
+    WipeSync,
+}
+
+struct Options {
+    passes: usize,
+    zero: bool,
+    remove: Option<Remove>,
+    size: Option<u64>,
+    exact: bool,
+    verbose: bool,
+}
+
+// Where random passes get their data; running out of it, or failing to
+// read it, ends shred
+struct RandomSource {
+    name: String,
+    file: File,
+}
+
+impl RandomSource {
+    fn fill(&mut self, buffer: &mut [u8]) {
+        if let Err(err) = self.file.read_exact(buffer) {
+            let message = if err.kind() == io::ErrorKind::UnexpectedEof {
+                "end of file".to_string()
+            } else {
+                strerror(&err)
+            };
+            fail("shred", &format!("{}: {}", self.name, message));
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("shred")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils shred - overwrite files to hide their contents, and optionally delete them")
+        .after_help(
+            "CAUTION: shred relies on the file system overwriting data in place. That is not\n\
+             the case on copy-on-write or log-structured file systems (btrfs, zfs, f2fs, ...),\n\
+             with data journaling, snapshots, or on SSDs that remap blocks internally.",
+        )
+        .arg(
+            Arg::new("iterations")
+                .short('n')
+                .long("iterations")
+                .value_name("N")
+                .help("Overwrite N times instead of the default (3)"),
+        )
+        .arg(
+            Arg::new("random-source")
+                .long("random-source")
+                .value_name("FILE")
+                .help("Get random bytes from FILE"),
+        )
+        .arg(
+            Arg::new("size")
+                .short('s')
+                .long("size")
+                .value_name("N")
+                .help("Shred this many bytes (suffixes like K, M, G accepted)"),
+        )
+        .arg(
+            Arg::new("remove")
+                .short('u')
+                .long("remove")
+                .value_name("HOW")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("wipesync")
+                .value_parser(["unlink", "wipe", "wipesync"])
+                .help("Deallocate and remove file after overwriting; HOW is 'unlink', 'wipe' or, by default, 'wipesync'"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Show progress")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("exact")
+                .short('x')
+                .long("exact")
+                .help("Do not round file sizes up to the next full block; this is the default for non-regular files")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("zero")
+                .short('z')
+                .long("zero")
+                .help("Add a final overwrite with zeros to hide shredding")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to shred")
+                .num_args(1..)
+                .required(true),
+        )
+        .get_matches();
+
+    let passes = match matches.get_one::<String>("iterations") {
+        Some(value) => value.parse::<usize>().unwrap_or_else(|_| {
+            fail("shred", &format!("invalid number of passes: '{}'", value))
+        }),
+        None => DEFAULT_PASSES,
+    };
+
+    let size = matches.get_one::<String>("size").map(|value| {
+        parse_size(value, &[("b", 512)])
+            .unwrap_or_else(|| fail("shred", &format!("invalid file size: '{}'", value)))
+    });
+
+    let remove = matches
+        .get_one::<String>("remove")
+        .map(|how| match how.as_str() {
+            "unlink" => Remove::Unlink,
+            "wipe" => Remove::Wipe,
+            _ => Remove::WipeSync,
+        });
+
+    let options = Options {
+        passes,
+        zero: matches.get_flag("zero"),
+        remove,
+        size,
+        exact: matches.get_flag("exact"),
+        verbose: matches.get_flag("verbose"),
+    };
+
+    let name = matches
+        .get_one::<String>("random-source")
+        .map_or(RANDOM_SOURCE, String::as_str);
+    let mut random = RandomSource {
+        name: name.to_string(),
+        file: File::open(name)
+            .unwrap_or_else(|err| fail("shred", &format!("{}: {}", name, strerror(&err)))),
+    };
+
+    let mut exit_code = 0;
+
+    for file in matches.get_many::<String>("file").unwrap() {
+        if let Err(err) = shred(file, &options, &mut random) {
+            eprintln!("shred: {}: {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+fn shred(path: &str, options: &Options, random: &mut RandomSource) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let metadata = file.metadata()?;
+
+    if let Some(name) = copy_on_write_filesystem(path) {
+        eprintln!(
+            "shred: warning: {}: on a {} file system, overwriting may leave the original data on disk",
+            path, name
+        );
+    }
+
+    // Regular files are overwritten up to the end of their last block, where
+    // stale data from earlier contents can linger, unless -x or -s asks for
+    // an exact size; devices have no length in their metadata and are
+    // measured by seeking
+    let size = match options.size {
+        Some(size) => size,
+        None if metadata.is_file() && !options.exact => {
+            let block = metadata.blksize().max(1);
+            metadata.len().div_ceil(block) * block
+        }
+        None if metadata.is_file() => metadata.len(),
+        None => file.seek(SeekFrom::End(0))?,
+    };
+
+    let mut plan = plan(options.passes);
+    if options.zero {
+        plan.push(Pass::Pattern([0; 3]));
+    }
+
+    for (index, pass) in plan.iter().enumerate() {
+        if options.verbose {
+            let label = match pass {
+                Pass::Random => "random".to_string(),
+                Pass::Pattern(bytes) => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            };
+            eprintln!("shred: {}: pass {}/{} ({})...", path, index + 1, plan.len(), label);
+        }
+        overwrite(&mut file, size, *pass, random)?;
+    }
+
+    if let Some(how) = options.remove {
+        file.set_len(0)?;
+        file.sync_all()?;
+        drop(file);
+        remove(Path::new(path), how, options.verbose)?;
+    }
+
+    Ok(())
+}
+
+// PASSES passes: up to three are all random; beyond that the first, the
+// middle and the last are, and patterns fill the rest
+fn plan(passes: usize) -> Vec<Pass> {
+    if passes <= 3 {
+        return vec![Pass::Random; passes];
+    }
+    let mut patterns = PATTERNS.iter().cycle();
+    (0..passes)
+        .map(|index| {
+            if index == 0 || index == passes / 2 || index == passes - 1 {
+                Pass::Random
+            } else {
+                Pass::Pattern(*patterns.next().unwrap())
+            }
+        })
+        .collect()
+}
+
+fn overwrite(file: &mut File, size: u64, pass: Pass, random: &mut RandomSource) -> io::Result<()> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    if let Pass::Pattern(bytes) = pass {
+        // Chunks hold whole patterns, so each starts where the last ended
+        for (index, byte) in buffer.iter_mut().enumerate() {
+            *byte = bytes[index % 3];
+        }
+    }
+    let chunk_size = BUFFER_SIZE - BUFFER_SIZE % 3;
+    let mut remaining = size;
+
+    file.seek(SeekFrom::Start(0))?;
+
+    while remaining > 0 {
+        let chunk = remaining.min(chunk_size as u64) as usize;
+        if let Pass::Random = pass {
+            random.fill(&mut buffer[..chunk]);
+        }
+        file.write_all(&buffer[..chunk])?;
+        remaining -= chunk as u64;
+    }
+
+    // Each pass has to reach the disk, or the cache would simply keep the
+    // last one
+    file.sync_data()
+}
+
+// Removes PATH, first renaming it to names of its length made of one
+// repeated character, then of each shorter length, so that the directory
+// keeps neither its name nor its length
+fn remove(path: &Path, how: Remove, verbose: bool) -> io::Result<()> {
+    let shown = path.display();
+    if verbose {
+        eprintln!("shred: {}: removing", shown);
+    }
+
+    let mut current = path.to_path_buf();
+    if how != Remove::Unlink {
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => File::open(parent).ok(),
+            _ => File::open(".").ok(),
+        };
+        let length = path.file_name().map_or(0, |name| name.len());
+
+        for length in (1..=length).rev() {
+            let mut name = vec![NAME_CHARS[0]; length];
+            loop {
+                let candidate = path.with_file_name(OsStr::from_bytes(&name));
+                match rename_no_replace(&current, &candidate) {
+                    Ok(()) => {
+                        if how == Remove::WipeSync {
+                            if let Some(directory) = &directory {
+                                let _ = directory.sync_all();
+                            }
+                        }
+                        if verbose {
+                            eprintln!("shred: {}: renamed to {}", current.display(), candidate.display());
+                        }
+                        current = candidate;
+                        break;
+                    }
+                    // Taken: try the next name of this length, if any
+                    Err(err) if err.kind() == io::ErrorKind::AlreadyExists && next_name(&mut name) => {}
+                    Err(_) => break,
+                }
+            }
+        }
+    }
+
+    std::fs::remove_file(&current)?;
+    if verbose {
+        eprintln!("shred: {}: removed", shown);
+    }
+    Ok(())
+}
+
+fn rename_no_replace(from: &Path, to: &Path) -> io::Result<()> {
+    let from = CString::new(from.as_os_str().as_bytes())?;
+    let to = CString::new(to.as_os_str().as_bytes())?;
+    let result = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            from.as_ptr(),
+            libc::AT_FDCWD,
+            to.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+// Counts NAME up through NAME_CHARS like an odometer; false once every
+// name of its length has been tried
+fn next_name(name: &mut [u8]) -> bool {
+    for byte in name.iter_mut().rev() {
+        let index = NAME_CHARS.iter().position(|c| c == byte).unwrap_or(0);
+        if index + 1 < NAME_CHARS.len() {
+            *byte = NAME_CHARS[index + 1];
+            return true;
+        }
+        *byte = NAME_CHARS[0];
+    }
+    false
+}
+
+fn copy_on_write_filesystem(path: &str) -> Option<&'static str> {
+    let c_path = CString::new(path).ok()?;
+
+    let f_type = unsafe {
+        let mut stats: libc::statfs = mem::zeroed();
+        if libc::statfs(c_path.as_ptr(), &mut stats) != 0 {
+            return None;
+        }
+        stats.f_type as i64
+    };
+
+    COPY_ON_WRITE_FILESYSTEMS
+        .iter()
+        .find(|(magic, _)| *magic == f_type)
+        .map(|(_, name)| *name)
+}
@@ -0,0 +1,330 @@
+// ASD CoreUtils - shuf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+const DEFAULT_RANDOM_SOURCE: &str = "/dev/urandom";
+// Room reserved up front for -n lines; a larger COUNT grows as needed
+const RESERVOIR_PREALLOCATION: usize = 4096;
+
+// Draws random numbers from a byte stream, so a fixed --random-source file
+// always yields the same permutation
+struct RandomSource {
+    name: String,
+    reader: BufReader<File>,
+}
+
+impl RandomSource {
+    fn open(name: &str) -> io::Result<Self> {
+        let file = File::open(name)?;
+        Ok(RandomSource {
+            name: name.to_string(),
+            reader: BufReader::new(file),
+        })
+    }
+
+    fn next_u64(&mut self) -> io::Result<u64> {
+        let mut bytes = [0u8; 8];
+        self.reader.read_exact(&mut bytes).map_err(|err| {
+            if err.kind() == io::ErrorKind::UnexpectedEof {
+                io::Error::new(err.kind(), format!("{}: end of file", self.name))
+            } else {
+                io::Error::new(err.kind(), format!("{}: {}", self.name, strerror(&err)))
+            }
+        })?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    // Uniform value in 0..bound; values from the biased tail are rejected
+    fn below(&mut self, bound: u64) -> io::Result<u64> {
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64()?;
+            if value < limit {
+                return Ok(value % bound);
+            }
+        }
+    }
+}
+
+fn main() {
+    // Like other tools, stop quietly when the reader goes away; with -r the
+    // output never ends otherwise
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    let matches = Command::new("shuf")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils shuf - write a random permutation of the input lines")
+        .arg(
+            Arg::new("echo")
+                .short('e')
+                .long("echo")
+                .help("Treat each ARG as an input line")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("input-range")
+                .short('i')
+                .long("input-range")
+                .value_name("LO-HI")
+                .help("Treat each number LO through HI as an input line"),
+        )
+        .arg(
+            Arg::new("head-count")
+                .short('n')
+                .long("head-count")
+                .value_name("COUNT")
+                .help("Output at most COUNT lines"),
+        )
+        .arg(
+            Arg::new("random-source")
+                .long("random-source")
+                .value_name("FILE")
+                .help("Get random bytes from FILE"),
+        )
+        .arg(
+            Arg::new("repeat")
+                .short('r')
+                .long("repeat")
+                .help("Output lines can be repeated")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("args")
+                .value_name("FILE|ARG")
+                .help("Input file (default: standard input), or lines with -e")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let head_count = matches.get_one::<String>("head-count").map(|value| {
+        value
+            .parse::<usize>()
+            .unwrap_or_else(|_| fail("shuf", &format!("invalid line count: '{}'", value)))
+    });
+    let repeat = matches.get_flag("repeat");
+
+    let args: Vec<String> = matches
+        .get_many::<String>("args")
+        .map(|args| args.cloned().collect())
+        .unwrap_or_default();
+
+    let range = matches.get_one::<String>("input-range").map(|value| {
+        if matches.get_flag("echo") {
+            usage_error("shuf", "cannot combine -e and -i options");
+        }
+        if let Some(extra) = args.first() {
+            fail("shuf", &format!("extra operand '{}'", extra));
+        }
+        parse_range(value)
+            .unwrap_or_else(|| fail("shuf", &format!("invalid input range: '{}'", value)))
+    });
+
+    let source = matches
+        .get_one::<String>("random-source")
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_RANDOM_SOURCE);
+    let mut random = RandomSource::open(source)
+        .unwrap_or_else(|err| fail("shuf", &format!("{}: {}", source, strerror(&err))));
+
+    if head_count == Some(0) {
+        return;
+    }
+
+    let result = if let Some((low, high)) = range {
+        if repeat {
+            repeat_range(low, high, head_count, &mut random)
+        } else {
+            shuffle_range(low, high, head_count, &mut random)
+        }
+    } else if matches.get_flag("echo") {
+        let lines: Vec<Vec<u8>> = args.into_iter().map(String::into_bytes).collect();
+        output_lines(lines, head_count, repeat, &mut random)
+    } else {
+        if args.len() > 1 {
+            fail("shuf", &format!("extra operand '{}'", args[1]));
+        }
+        let file = args.first().map(String::as_str).unwrap_or("-");
+        let reader = open_input(file)
+            .unwrap_or_else(|err| fail("shuf", &format!("{}: {}", file, strerror(&err))));
+
+        // Only K lines need to be kept to pick K of them
+        let lines = match head_count {
+            Some(count) if !repeat => sample_lines(reader, count, &mut random),
+            _ => reader.split(b'\n').collect(),
+        };
+        let lines =
+            lines.unwrap_or_else(|err| fail("shuf", &format!("{}: {}", file, strerror(&err))));
+        output_lines(lines, head_count, repeat, &mut random)
+    };
+
+    if let Err(err) = result {
+        fail("shuf", &strerror(&err));
+    }
+}
+
+// Parses LO-HI for -i, both ends included
+fn parse_range(value: &str) -> Option<(u64, u64)> {
+    let (low, high) = value.split_once('-')?;
+    let low = low.parse::<u64>().ok()?;
+    let high = high.parse::<u64>().ok()?;
+    if high < low && low - high > 1 {
+        return None;
+    }
+    // HI one below LO is an empty range
+    Some((low, high))
+}
+
+fn open_input(file: &str) -> io::Result<Box<dyn BufRead>> {
+    if file == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(file)?)))
+    }
+}
+
+// Reservoir sampling: keeps a uniformly chosen COUNT of the lines read so
+// far, however long the input, then shuffles them since the order they
+// were kept in is not random
+fn sample_lines(
+    reader: impl BufRead,
+    count: usize,
+    random: &mut RandomSource,
+) -> io::Result<Vec<Vec<u8>>> {
+    let mut reservoir = Vec::with_capacity(count.min(RESERVOIR_PREALLOCATION));
+
+    for (seen, line) in reader.split(b'\n').enumerate() {
+        let line = line?;
+        if seen < count {
+            reservoir.push(line);
+            continue;
+        }
+        let pick = random.below(seen as u64 + 1)? as usize;
+        if pick < count {
+            reservoir[pick] = line;
+        }
+    }
+
+    let total = reservoir.len();
+    shuffle(&mut reservoir, total, random)?;
+    Ok(reservoir)
+}
+
+fn output_lines(
+    mut lines: Vec<Vec<u8>>,
+    head_count: Option<usize>,
+    repeat: bool,
+    random: &mut RandomSource,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    if repeat {
+        if lines.is_empty() {
+            fail("shuf", "no lines to repeat");
+        }
+        let mut remaining = head_count;
+        while remaining != Some(0) {
+            let line = &lines[random.below(lines.len() as u64)? as usize];
+            out.write_all(line)?;
+            out.write_all(b"\n")?;
+            remaining = remaining.map(|count| count - 1);
+        }
+        return out.flush();
+    }
+
+    let count = head_count.map_or(lines.len(), |count| count.min(lines.len()));
+    shuffle(&mut lines, count, random)?;
+    for line in &lines[..count] {
+        out.write_all(line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+// -i -r: numbers drawn from LO through HI, each time afresh
+fn repeat_range(
+    low: u64,
+    high: u64,
+    head_count: Option<usize>,
+    random: &mut RandomSource,
+) -> io::Result<()> {
+    if high < low {
+        fail("shuf", "no lines to repeat");
+    }
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    let mut remaining = head_count;
+    while remaining != Some(0) {
+        let value = match (high - low).checked_add(1) {
+            Some(span) => low + random.below(span)?,
+            None => random.next_u64()?,
+        };
+        writeln!(out, "{}", value)?;
+        remaining = remaining.map(|count| count - 1);
+    }
+    out.flush()
+}
+
+// -i: Fisher-Yates over LO through HI without laying the numbers out; only
+// the positions swapped so far are remembered, so picking a few numbers
+// from a huge range stays cheap
+fn shuffle_range(
+    low: u64,
+    high: u64,
+    head_count: Option<usize>,
+    random: &mut RandomSource,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if high < low {
+        return Ok(());
+    }
+
+    // The whole u64 range holds one number more than a u64 can count, so
+    // it has no total and its first pick is any u64, as with -r
+    let total = (high - low).checked_add(1);
+    let count = match head_count {
+        Some(count) => total.map_or(count as u64, |total| (count as u64).min(total)),
+        None => total.unwrap_or(u64::MAX),
+    };
+    let mut swapped: HashMap<u64, u64> = HashMap::new();
+
+    for index in 0..count {
+        let left = match total {
+            Some(total) => Some(total - index),
+            None => (u64::MAX - index).checked_add(1),
+        };
+        let offset = match left {
+            Some(left) => random.below(left)?,
+            None => random.next_u64()?,
+        };
+        let pick = index + offset;
+        let at_pick = swapped.get(&pick).copied().unwrap_or(pick);
+        let at_index = swapped.remove(&index).unwrap_or(index);
+        if pick != index {
+            swapped.insert(pick, at_index);
+        }
+        writeln!(out, "{}", low + at_pick)?;
+    }
+    out.flush()
+}
+
+// Fisher-Yates, stopped once the first COUNT positions are settled
+fn shuffle(lines: &mut [Vec<u8>], count: usize, random: &mut RandomSource) -> io::Result<()> {
+    let total = lines.len();
+    for index in 0..count.min(total.saturating_sub(1)) {
+        let pick = index + random.below((total - index) as u64)? as usize;
+        lines.swap(index, pick);
+    }
+    Ok(())
+}
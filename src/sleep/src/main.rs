@@ -0,0 +1,63 @@
+// ASD CoreUtils - sleep
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::duration::parse_duration;
+use asd_coreutils_core::error::usage_error;
+use clap::{Arg, Command};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    let matches = Command::new("sleep")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils sleep - pause for NUMBER seconds")
+        .override_usage("sleep NUMBER[SUFFIX]...")
+        .after_help(
+            "SUFFIX may be 's' for seconds (the default), 'm' for minutes, 'h' for hours\n\
+             or 'd' for days. NUMBER need not be an integer, and may be 'infinity'.\n\
+             Given two or more arguments, pause for the amount of time specified by\n\
+             the sum of their values.",
+        )
+        .arg(
+            Arg::new("durations")
+                .value_name("NUMBER[SUFFIX]")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let operands: Vec<&String> = matches
+        .get_many::<String>("durations")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    if operands.is_empty() {
+        usage_error("sleep", "missing operand");
+    }
+
+    // Every bad operand is reported before giving up
+    let mut total = 0.0;
+    let mut valid = true;
+    for operand in operands {
+        match parse_duration(operand) {
+            Some(seconds) => total += seconds,
+            None => {
+                eprintln!("sleep: invalid time interval '{}'", operand);
+                valid = false;
+            }
+        }
+    }
+    if !valid {
+        eprintln!("Try 'sleep --help' for more information.");
+        process::exit(1);
+    }
+
+    // Anything too long for a Duration, infinity included, is forever
+    match Duration::try_from_secs_f64(total) {
+        Ok(duration) => thread::sleep(duration),
+        Err(_) => loop {
+            thread::sleep(Duration::MAX);
+        },
+    }
+}
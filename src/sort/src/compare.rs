@@ -0,0 +1,709 @@
+// ASD CoreUtils - sort
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Line comparison: sort keys as given with -k, the orderings they can use,
+// and the whole-line comparison that settles ties
+
+use std::cmp::Ordering;
+
+const MONTHS: [&[u8]; 12] = [
+    b"JAN", b"FEB", b"MAR", b"APR", b"MAY", b"JUN", b"JUL", b"AUG", b"SEP", b"OCT", b"NOV", b"DEC",
+];
+
+// Suffixes understood by -h, smallest first
+const HUMAN_UNITS: &[u8] = b"KMGTPEZYRQ";
+
+// Characters -d and -i leave out of comparisons
+#[derive(Clone, Copy, PartialEq)]
+pub enum Ignore {
+    NonDictionary,
+    NonPrinting,
+}
+
+// A sort key: where it lies in the line and how it is compared. Field and
+// character positions are counted from zero, as GNU sort keeps them.
+#[derive(Clone)]
+pub struct Key {
+    start_field: usize,
+    start_char: usize,
+    // Field and character of the end, character 0 meaning the end of the
+    // field; None for the end of the line
+    end: Option<(usize, usize)>,
+    skip_start_blanks: bool,
+    skip_end_blanks: bool,
+    ignore: Option<Ignore>,
+    fold_case: bool,
+    numeric: bool,
+    general_numeric: bool,
+    human_numeric: bool,
+    month: bool,
+    version: bool,
+    reverse: bool,
+}
+
+impl Key {
+    // The whole line, compared as plain text
+    pub fn whole_line() -> Key {
+        Key {
+            start_field: 0,
+            start_char: 0,
+            end: None,
+            skip_start_blanks: false,
+            skip_end_blanks: false,
+            ignore: None,
+            fold_case: false,
+            numeric: false,
+            general_numeric: false,
+            human_numeric: false,
+            month: false,
+            version: false,
+            reverse: false,
+        }
+    }
+
+    // Parse a -k argument, POS1[,POS2], where a position is F[.C][OPTS]
+    pub fn parse(spec: &str) -> Result<Key, String> {
+        let invalid = |reason: &str| {
+            if reason.is_empty() {
+                format!("invalid field specification '{}'", spec)
+            } else {
+                format!("{}: invalid field specification '{}'", reason, spec)
+            }
+        };
+        let mut key = Key::whole_line();
+        let (start, end) = match spec.split_once(',') {
+            Some((start, end)) => (start, Some(end)),
+            None => (spec, None),
+        };
+
+        let (field, char, flags) = parse_position(start).ok_or_else(|| invalid(""))?;
+        key.start_field = field
+            .checked_sub(1)
+            .ok_or_else(|| invalid("field number is zero"))?;
+        key.start_char = match char {
+            Some(0) => return Err(invalid("character offset is zero")),
+            Some(char) => char - 1,
+            None => 0,
+        };
+        key.set_flags(flags, true)
+            .map_err(|_| invalid("stray character in field spec"))?;
+
+        if let Some(end) = end {
+            let (field, char, flags) = parse_position(end).ok_or_else(|| invalid(""))?;
+            let field = field
+                .checked_sub(1)
+                .ok_or_else(|| invalid("field number is zero"))?;
+            key.end = Some((field, char.unwrap_or(0)));
+            key.set_flags(flags, false)
+                .map_err(|_| invalid("stray character in field spec"))?;
+        }
+        Ok(key)
+    }
+
+    // Apply ordering option letters; b means the start or the end blanks
+    // depending on where it was given
+    pub fn set_flags(&mut self, flags: &str, at_start: bool) -> Result<(), char> {
+        for flag in flags.chars() {
+            match flag {
+                'b' if at_start => self.skip_start_blanks = true,
+                'b' => self.skip_end_blanks = true,
+                'd' => self.ignore = Some(Ignore::NonDictionary),
+                'f' => self.fold_case = true,
+                'g' => self.general_numeric = true,
+                'h' => self.human_numeric = true,
+                'i' => self.ignore = Some(Ignore::NonPrinting),
+                'M' => self.month = true,
+                'n' => self.numeric = true,
+                'r' => self.reverse = true,
+                'V' => self.version = true,
+                other => return Err(other),
+            }
+        }
+        Ok(())
+    }
+
+    // Whether no ordering option was given for this key, so that the global
+    // ones apply to it
+    pub fn has_default_ordering(&self) -> bool {
+        !(self.skip_start_blanks
+            || self.skip_end_blanks
+            || self.ignore.is_some()
+            || self.fold_case
+            || self.numeric
+            || self.general_numeric
+            || self.human_numeric
+            || self.month
+            || self.version
+            || self.reverse)
+    }
+
+    // Take the ordering options of GLOBAL, keeping this key's position
+    pub fn inherit(&mut self, global: &Key) {
+        let (start_field, start_char, end) = (self.start_field, self.start_char, self.end);
+        *self = global.clone();
+        self.start_field = start_field;
+        self.start_char = start_char;
+        self.end = end;
+    }
+
+    // The options of this key as letters, if more than one way of comparing
+    // was asked for
+    pub fn incompatible_options(&self) -> Option<String> {
+        let orderings = [
+            self.numeric,
+            self.general_numeric,
+            self.human_numeric,
+            self.month,
+            self.version || self.ignore.is_some(),
+        ];
+        if orderings.iter().filter(|&&set| set).count() < 2 {
+            return None;
+        }
+
+        let letters = [
+            (self.skip_start_blanks || self.skip_end_blanks, 'b'),
+            (self.ignore == Some(Ignore::NonDictionary), 'd'),
+            (self.fold_case, 'f'),
+            (self.general_numeric, 'g'),
+            (self.human_numeric, 'h'),
+            (self.ignore == Some(Ignore::NonPrinting), 'i'),
+            (self.month, 'M'),
+            (self.numeric, 'n'),
+            (self.reverse, 'r'),
+            (self.version, 'V'),
+        ];
+        Some(
+            letters
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, letter)| letter)
+                .collect(),
+        )
+    }
+
+    // The part of LINE this key covers
+    fn extract<'a>(&self, line: &'a [u8], tab: Option<u8>) -> &'a [u8] {
+        let start = self.start(line, tab);
+        let end = self.end(line, tab).max(start);
+        &line[start..end]
+    }
+
+    fn start(&self, line: &[u8], tab: Option<u8>) -> usize {
+        let mut pos = skip_fields(line, 0, self.start_field, tab, true);
+        if self.skip_start_blanks {
+            pos = skip_blanks(line, pos);
+        }
+        (pos + self.start_char).min(line.len())
+    }
+
+    fn end(&self, line: &[u8], tab: Option<u8>) -> usize {
+        let Some((field, char)) = self.end else {
+            return line.len();
+        };
+        // Character 0 means all of the field
+        let fields = if char == 0 { field + 1 } else { field };
+        let mut pos = skip_fields(line, 0, fields, tab, char != 0);
+        if char != 0 {
+            if self.skip_end_blanks {
+                pos = skip_blanks(line, pos);
+            }
+            pos = (pos + char).min(line.len());
+        }
+        pos
+    }
+
+    // Compare key texts A and B; characters left out or folded with -d, -i
+    // or -f are so for every ordering
+    fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+        let order = if self.ignore.is_some() || self.fold_case {
+            self.compare_text(&self.translate(a), &self.translate(b))
+        } else {
+            self.compare_text(a, b)
+        };
+        if self.reverse {
+            order.reverse()
+        } else {
+            order
+        }
+    }
+
+    fn compare_text(&self, a: &[u8], b: &[u8]) -> Ordering {
+        if self.numeric {
+            compare_numeric(a, b)
+        } else if self.general_numeric {
+            compare_general_numeric(a, b)
+        } else if self.human_numeric {
+            compare_human_numeric(a, b)
+        } else if self.month {
+            month(a).cmp(&month(b))
+        } else if self.version {
+            compare_versions(a, b)
+        } else {
+            a.cmp(b)
+        }
+    }
+
+    fn translate(&self, text: &[u8]) -> Vec<u8> {
+        text.iter()
+            .copied()
+            .filter(|&c| !self.ignores(c))
+            .map(|c| {
+                if self.fold_case {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+
+    fn ignores(&self, c: u8) -> bool {
+        match self.ignore {
+            Some(Ignore::NonDictionary) => !(c.is_ascii_alphanumeric() || is_blank(c)),
+            Some(Ignore::NonPrinting) => !(0x20..0x7f).contains(&c),
+            None => false,
+        }
+    }
+}
+
+pub struct Settings {
+    pub separator: u8,
+    // -t: the character between fields; by default a field starts at a
+    // blank following a non-blank
+    pub tab: Option<u8>,
+    pub keys: Vec<Key>,
+    // -r: also reverses the last-resort comparison
+    pub reverse: bool,
+    pub unique: bool,
+    // -s: lines equal in all keys stay in input order
+    pub stable: bool,
+}
+
+impl Settings {
+    // Compare lines by their keys in turn; lines equal in all keys are put
+    // in byte order, unless -u asks only whether they are equal or -s
+    // leaves them as they came
+    pub fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+        if !self.keys.is_empty() {
+            for key in &self.keys {
+                let order = key.compare(key.extract(a, self.tab), key.extract(b, self.tab));
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            if self.unique || self.stable {
+                return Ordering::Equal;
+            }
+        }
+        let order = a.cmp(b);
+        if self.reverse {
+            order.reverse()
+        } else {
+            order
+        }
+    }
+
+    // Whether -u keeps only the first of A and B
+    pub fn is_duplicate(&self, a: &[u8], b: &[u8]) -> bool {
+        self.compare(a, b) == Ordering::Equal
+    }
+}
+
+// F[.C][OPTS] as field, character and option letters
+fn parse_position(text: &str) -> Option<(usize, Option<usize>, &str)> {
+    let digits = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let field = text[..digits].parse().ok()?;
+    let rest = &text[digits..];
+    match rest.strip_prefix('.') {
+        Some(rest) => {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let char = rest[..digits].parse().ok()?;
+            Some((field, Some(char), &rest[digits..]))
+        }
+        None => Some((field, None, rest)),
+    }
+}
+
+fn is_blank(c: u8) -> bool {
+    c == b' ' || c == b'\t' || c == b'\n'
+}
+
+fn skip_blanks(line: &[u8], mut pos: usize) -> usize {
+    while pos < line.len() && is_blank(line[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+// Move past COUNT fields from POS. With a tab, the tab ending the last of
+// them is skipped only when PAST_TAB is set, so that an end position
+// stops before it.
+fn skip_fields(
+    line: &[u8],
+    mut pos: usize,
+    count: usize,
+    tab: Option<u8>,
+    past_tab: bool,
+) -> usize {
+    for remaining in (0..count).rev() {
+        if pos >= line.len() {
+            break;
+        }
+        match tab {
+            Some(tab) => {
+                while pos < line.len() && line[pos] != tab {
+                    pos += 1;
+                }
+                if pos < line.len() && (remaining > 0 || past_tab) {
+                    pos += 1;
+                }
+            }
+            None => {
+                pos = skip_blanks(line, pos);
+                while pos < line.len() && !is_blank(line[pos]) {
+                    pos += 1;
+                }
+            }
+        }
+    }
+    pos
+}
+
+// The number at the start of TEXT as sign, integer digits without leading
+// zeros and fraction digits without trailing zeros; anything else is zero
+fn split_number(text: &[u8]) -> (bool, &[u8], &[u8]) {
+    let mut pos = skip_blanks(text, 0);
+    let negative = text.get(pos) == Some(&b'-');
+    if negative {
+        pos += 1;
+    }
+    let int_start = pos;
+    while pos < text.len() && text[pos].is_ascii_digit() {
+        pos += 1;
+    }
+    let mut integer = &text[int_start..pos];
+    while let [b'0', rest @ ..] = integer {
+        integer = rest;
+    }
+    let mut fraction: &[u8] = &[];
+    if text.get(pos) == Some(&b'.') {
+        let frac_start = pos + 1;
+        pos = frac_start;
+        while pos < text.len() && text[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        fraction = &text[frac_start..pos];
+        while let [rest @ .., b'0'] = fraction {
+            fraction = rest;
+        }
+    }
+    let zero = integer.is_empty() && fraction.is_empty();
+    (negative && !zero, integer, fraction)
+}
+
+// -n: compare decimal numbers of any length digit by digit
+fn compare_numeric(a: &[u8], b: &[u8]) -> Ordering {
+    let (a_negative, a_int, a_frac) = split_number(a);
+    let (b_negative, b_int, b_frac) = split_number(b);
+    let magnitude = |int: &[u8], frac: &[u8], other_int: &[u8], other_frac: &[u8]| {
+        int.len()
+            .cmp(&other_int.len())
+            .then_with(|| int.cmp(other_int))
+            .then_with(|| frac.cmp(other_frac))
+    };
+    match (a_negative, b_negative) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        (false, false) => magnitude(a_int, a_frac, b_int, b_frac),
+        (true, true) => magnitude(b_int, b_frac, a_int, a_frac),
+    }
+}
+
+// -h: numbers with an SI suffix compare by suffix first, then as numbers
+fn compare_human_numeric(a: &[u8], b: &[u8]) -> Ordering {
+    unit_order(a)
+        .cmp(&unit_order(b))
+        .then_with(|| compare_numeric(a, b))
+}
+
+// The order of the suffix of the number starting TEXT, negative for a
+// negative number and 0 for none or for zero
+fn unit_order(text: &[u8]) -> i32 {
+    let mut pos = skip_blanks(text, 0);
+    let negative = text.get(pos) == Some(&b'-');
+    if negative {
+        pos += 1;
+    }
+    let mut nonzero = false;
+    while pos < text.len() && text[pos].is_ascii_digit() {
+        nonzero |= text[pos] != b'0';
+        pos += 1;
+    }
+    if text.get(pos) == Some(&b'.') {
+        pos += 1;
+        while pos < text.len() && text[pos].is_ascii_digit() {
+            nonzero |= text[pos] != b'0';
+            pos += 1;
+        }
+    }
+    let order = match text.get(pos) {
+        Some(b'k') => 1,
+        Some(&unit) => HUMAN_UNITS
+            .iter()
+            .position(|&known| known == unit)
+            .map_or(0, |index| index as i32 + 1),
+        None => 0,
+    };
+    match (nonzero, negative) {
+        (false, _) => 0,
+        (true, true) => -order,
+        (true, false) => order,
+    }
+}
+
+// -g: compare as floating point; text that is no number comes first, then
+// NaN, then the numbers
+fn compare_general_numeric(a: &[u8], b: &[u8]) -> Ordering {
+    match (general_number(a), general_number(b)) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        },
+    }
+}
+
+// The longest prefix of TEXT strtod would take, after white space
+fn general_number(text: &[u8]) -> Option<f64> {
+    let start = text
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(text.len());
+    let text = &text[start..];
+    let mut pos = usize::from(matches!(text.first(), Some(b'+') | Some(b'-')));
+
+    let rest = &text[pos..];
+    if let Some(value) = hex_number(rest) {
+        return Some(if text[0] == b'-' { -value } else { value });
+    }
+    for word in ["infinity", "inf", "nan"] {
+        if rest.len() >= word.len() && rest[..word.len()].eq_ignore_ascii_case(word.as_bytes()) {
+            return std::str::from_utf8(&text[..pos + word.len()])
+                .ok()?
+                .parse()
+                .ok();
+        }
+    }
+
+    let digits_from = |mut pos: usize| {
+        while pos < text.len() && text[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        pos
+    };
+    let int_end = digits_from(pos);
+    let mut end = int_end;
+    if text.get(end) == Some(&b'.') {
+        end = digits_from(end + 1);
+    }
+    // A lone sign or point is no number
+    if end - pos == usize::from(end > int_end) {
+        return None;
+    }
+    pos = end;
+    if matches!(text.get(pos), Some(b'e') | Some(b'E')) {
+        let mut exp = pos + 1;
+        if matches!(text.get(exp), Some(b'+') | Some(b'-')) {
+            exp += 1;
+        }
+        let exp_end = digits_from(exp);
+        if exp_end > exp {
+            end = exp_end;
+        }
+    }
+    std::str::from_utf8(&text[..end]).ok()?.parse().ok()
+}
+
+// A C99 hexadecimal floating point number such as "0x1.8p3" at the start
+// of TEXT, sign already taken off
+fn hex_number(text: &[u8]) -> Option<f64> {
+    if text.len() < 3 || !text[..2].eq_ignore_ascii_case(b"0x") {
+        return None;
+    }
+    let mut pos = 2;
+    let mut value = 0.0f64;
+    let mut digits = 0;
+    let mut scale = 0i32;
+    let mut seen_point = false;
+    while pos < text.len() {
+        match text[pos] {
+            b'.' if !seen_point => seen_point = true,
+            c if c.is_ascii_hexdigit() => {
+                value = value * 16.0 + (c as char).to_digit(16).unwrap() as f64;
+                digits += 1;
+                if seen_point {
+                    scale -= 4;
+                }
+            }
+            _ => break,
+        }
+        pos += 1;
+    }
+    // "0x" with no digits is just the 0 before the x
+    if digits == 0 {
+        return None;
+    }
+    if matches!(text.get(pos), Some(b'p') | Some(b'P')) {
+        let mut exp = pos + 1;
+        let negative = text.get(exp) == Some(&b'-');
+        if matches!(text.get(exp), Some(b'+') | Some(b'-')) {
+            exp += 1;
+        }
+        let exp_start = exp;
+        let mut exponent = 0i32;
+        while exp < text.len() && text[exp].is_ascii_digit() {
+            exponent = exponent
+                .saturating_mul(10)
+                .saturating_add((text[exp] - b'0') as i32);
+            exp += 1;
+        }
+        if exp > exp_start {
+            scale = scale.saturating_add(if negative { -exponent } else { exponent });
+        }
+    }
+    Some(value * 2f64.powi(scale))
+}
+
+// -M: 1 to 12 for a month name abbreviation after blanks, 0 for anything else
+fn month(text: &[u8]) -> usize {
+    let start = skip_blanks(text, 0);
+    let name = &text[start..];
+    if name.len() < 3 {
+        return 0;
+    }
+    MONTHS
+        .iter()
+        .position(|month| name[..3].eq_ignore_ascii_case(month))
+        .map_or(0, |index| index + 1)
+}
+
+// -V: the file name version ordering of GNU's filevercmp. "." and ".."
+// come first, then other hidden files; suffixes such as ".tar.gz" only
+// decide between names equal without them.
+fn compare_versions(a: &[u8], b: &[u8]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (false, false) => {}
+    }
+
+    match (a[0] == b'.', b[0] == b'.') {
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (true, true) => {
+            for special in [&b"."[..], &b".."[..]] {
+                match (a == special, b == special) {
+                    (true, true) => return Ordering::Equal,
+                    (true, false) => return Ordering::Less,
+                    (false, true) => return Ordering::Greater,
+                    (false, false) => {}
+                }
+            }
+        }
+        (false, false) => {}
+    }
+
+    let a_prefix = suffix_start(a);
+    let b_prefix = suffix_start(b);
+    let order = compare_version_parts(&a[..a_prefix], &b[..b_prefix]);
+    if order != Ordering::Equal || (a_prefix == a.len() && b_prefix == b.len()) {
+        return order;
+    }
+    compare_version_parts(a, b)
+}
+
+// Where the file suffix of NAME starts: trailing ".ext" parts that begin
+// with a letter or '~', so that all of a hidden name like ".profile" is one
+fn suffix_start(name: &[u8]) -> usize {
+    let mut prefix = 0;
+    let mut i = 0;
+    while i < name.len() {
+        while i + 1 < name.len()
+            && name[i] == b'.'
+            && (name[i + 1].is_ascii_alphabetic() || name[i + 1] == b'~')
+        {
+            i += 2;
+            while i < name.len() && (name[i].is_ascii_alphanumeric() || name[i] == b'~') {
+                i += 1;
+            }
+        }
+        if i < name.len() {
+            i += 1;
+            prefix = i;
+        }
+    }
+    prefix
+}
+
+// The Debian version comparison: runs of non-digits compare with letters
+// before other characters and '~' before anything, even the end; runs of
+// digits compare as numbers
+fn compare_version_parts(a: &[u8], b: &[u8]) -> Ordering {
+    let weight = |c: u8| -> i32 {
+        if c.is_ascii_digit() {
+            0
+        } else if c.is_ascii_alphabetic() {
+            c as i32
+        } else if c == b'~' {
+            -1
+        } else {
+            c as i32 + 256
+        }
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !a[i].is_ascii_digit()) || (j < b.len() && !b[j].is_ascii_digit()) {
+            let a_weight = if i < a.len() { weight(a[i]) } else { 0 };
+            let b_weight = if j < b.len() { weight(b[j]) } else { 0 };
+            if a_weight != b_weight {
+                return a_weight.cmp(&b_weight);
+            }
+            i += 1;
+            j += 1;
+        }
+        while i < a.len() && a[i] == b'0' {
+            i += 1;
+        }
+        while j < b.len() && b[j] == b'0' {
+            j += 1;
+        }
+        let mut first_difference = Ordering::Equal;
+        while i < a.len() && j < b.len() && a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            if first_difference == Ordering::Equal {
+                first_difference = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if i < a.len() && a[i].is_ascii_digit() {
+            return Ordering::Greater;
+        }
+        if j < b.len() && b[j].is_ascii_digit() {
+            return Ordering::Less;
+        }
+        if first_difference != Ordering::Equal {
+            return first_difference;
+        }
+    }
+    Ordering::Equal
+}
@@ -0,0 +1,220 @@
+// ASD CoreUtils - sort
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Sorting inputs larger than the sort buffer: each buffer full of lines is
+// sorted and written to a temporary file as a run, and the runs are merged
+// back together at the end
+
+use crate::compare::Settings;
+use asd_coreutils_core::error::strerror;
+use std::cmp::Ordering;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Most runs merged at once; more take several passes, each merging groups
+// of runs into a new temporary file, as GNU sort does
+pub const MERGE_WAYS: usize = 16;
+
+// Where temporary files go, taking turns when -T was given several times
+pub struct TempDirs {
+    dirs: Vec<PathBuf>,
+    next: usize,
+}
+
+impl TempDirs {
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        TempDirs { dirs, next: 0 }
+    }
+
+    // A new temporary file, unlinked right away so that nothing is left
+    // behind however sort ends
+    pub fn create(&mut self) -> io::Result<File> {
+        let dir = &self.dirs[self.next % self.dirs.len()];
+        self.next += 1;
+
+        loop {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.subsec_nanos())
+                .unwrap_or(0);
+            let path = dir.join(format!("sort{:x}{:06x}", process::id(), nanos & 0xffffff));
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => {
+                    let _ = fs::remove_file(&path);
+                    return Ok(file);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => {
+                    return Err(io::Error::new(
+                        err.kind(),
+                        format!(
+                            "cannot create temporary file in '{}': {}",
+                            dir.display(),
+                            strerror(&err)
+                        ),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+// Finish writing a run and rewind it for merging
+pub fn rewind(writer: BufWriter<File>) -> io::Result<File> {
+    let mut file = writer.into_inner().map_err(|err| err.into_error())?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(file)
+}
+
+// One sorted stream of lines being merged, positioned on its next line
+pub struct LineSource {
+    reader: Box<dyn BufRead>,
+    name: String,
+    line: Vec<u8>,
+    separator: u8,
+}
+
+impl LineSource {
+    // The source positioned on its first line, or None if it has none
+    pub fn new(reader: Box<dyn BufRead>, name: &str, separator: u8) -> io::Result<Option<Self>> {
+        let mut source = LineSource {
+            reader,
+            name: name.to_string(),
+            line: Vec::new(),
+            separator,
+        };
+        Ok(if source.advance()? {
+            Some(source)
+        } else {
+            None
+        })
+    }
+
+    pub fn from_run(run: File, separator: u8) -> io::Result<Option<Self>> {
+        LineSource::new(Box::new(BufReader::new(run)), "temporary file", separator)
+    }
+
+    pub fn line(&self) -> &[u8] {
+        &self.line
+    }
+
+    // Move on to the next line; false at the end
+    pub fn advance(&mut self) -> io::Result<bool> {
+        self.line.clear();
+        let read = self
+            .reader
+            .read_until(self.separator, &mut self.line)
+            .map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("cannot read: {}: {}", self.name, strerror(&err)),
+                )
+            })?;
+        if read == 0 {
+            return Ok(false);
+        }
+        if self.line.last() == Some(&self.separator) {
+            self.line.pop();
+        }
+        Ok(true)
+    }
+}
+
+// Merge the sorted SOURCES into OUT. Equal lines come out in the order of
+// their sources, so merging runs made in input order keeps the sort stable.
+pub fn merge(
+    mut sources: Vec<LineSource>,
+    settings: &Settings,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    // A heap of source indices, the source with the least line on top
+    let mut heap: Vec<usize> = (0..sources.len()).collect();
+    let before = |a: usize, b: usize, sources: &[LineSource]| match settings
+        .compare(sources[a].line(), sources[b].line())
+    {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => a < b,
+    };
+    for start in (0..heap.len() / 2).rev() {
+        sift_down(&mut heap, start, &|a, b| before(a, b, &sources));
+    }
+
+    let mut last: Option<Vec<u8>> = None;
+    while let Some(&top) = heap.first() {
+        let line = sources[top].line();
+        let repeated = settings.unique
+            && last
+                .as_deref()
+                .is_some_and(|last| settings.is_duplicate(last, line));
+        if !repeated {
+            out.write_all(line)?;
+            out.write_all(&[settings.separator])?;
+            if settings.unique {
+                last = Some(line.to_vec());
+            }
+        }
+
+        if !sources[top].advance()? {
+            heap.swap_remove(0);
+        }
+        sift_down(&mut heap, 0, &|a, b| before(a, b, &sources));
+    }
+    Ok(())
+}
+
+fn sift_down(heap: &mut [usize], mut parent: usize, before: &dyn Fn(usize, usize) -> bool) {
+    loop {
+        let mut least = parent;
+        for child in [2 * parent + 1, 2 * parent + 2] {
+            if child < heap.len() && before(heap[child], heap[least]) {
+                least = child;
+            }
+        }
+        if least == parent {
+            return;
+        }
+        heap.swap(parent, least);
+        parent = least;
+    }
+}
+
+// Merge RUNS in passes until few enough are left for the final merge, each
+// pass combining neighbouring groups so that run order is kept
+pub fn reduce_runs(
+    mut runs: Vec<File>,
+    settings: &Settings,
+    temps: &mut TempDirs,
+) -> io::Result<Vec<File>> {
+    while runs.len() > MERGE_WAYS {
+        let mut merged = Vec::new();
+        let mut pending = runs.into_iter().peekable();
+        while pending.peek().is_some() {
+            let group: Vec<File> = pending.by_ref().take(MERGE_WAYS).collect();
+            let mut writer = BufWriter::new(temps.create()?);
+            merge(open_runs(group, settings.separator)?, settings, &mut writer)?;
+            merged.push(rewind(writer)?);
+        }
+        runs = merged;
+    }
+    Ok(runs)
+}
+
+pub fn open_runs(runs: Vec<File>, separator: u8) -> io::Result<Vec<LineSource>> {
+    let mut sources = Vec::new();
+    for run in runs {
+        if let Some(source) = LineSource::from_run(run, separator)? {
+            sources.push(source);
+        }
+    }
+    Ok(sources)
+}
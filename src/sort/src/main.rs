@@ -0,0 +1,710 @@
+// ASD CoreUtils - sort
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod compare;
+mod external;
+mod parallel;
+
+use asd_coreutils_core::error::strerror;
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use compare::{Key, Settings};
+use external::{LineSource, TempDirs};
+use std::cmp::Ordering;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+
+// Bookkeeping charged against the buffer for every line on top of its text
+const LINE_OVERHEAD: usize = std::mem::size_of::<(usize, usize)>();
+
+// Smallest sort buffer, whatever -S asks for
+const MIN_BUFFER_SIZE: usize = 64 * 1024;
+
+// Most threads used without --parallel, as in GNU sort
+const DEFAULT_MAX_THREADS: usize = 8;
+
+// Global ordering options, with the -k option letter standing for each
+const ORDERING_FLAGS: [(&str, char); 9] = [
+    ("ignore-leading-blanks", 'b'),
+    ("dictionary-order", 'd'),
+    ("ignore-case", 'f'),
+    ("general-numeric-sort", 'g'),
+    ("human-numeric-sort", 'h'),
+    ("ignore-nonprinting", 'i'),
+    ("month-sort", 'M'),
+    ("numeric-sort", 'n'),
+    ("version-sort", 'V'),
+];
+
+// Lines read into the sort buffer, as ranges of one block of text
+struct Chunk {
+    data: Vec<u8>,
+    lines: Vec<(usize, usize)>,
+}
+
+impl Chunk {
+    fn new() -> Self {
+        Chunk {
+            data: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.data.len() + self.lines.len() * LINE_OVERHEAD
+    }
+
+    fn line(&self, range: (usize, usize)) -> &[u8] {
+        &self.data[range.0..range.1]
+    }
+
+    fn sort(&mut self, settings: &Settings, threads: usize) {
+        let data = &self.data;
+        parallel::sort_by(&mut self.lines, threads, |a, b| {
+            settings.compare(&data[a.0..a.1], &data[b.0..b.1])
+        });
+    }
+
+    // Write the sorted lines, dropping repeats with -u
+    fn write(&self, settings: &Settings, out: &mut dyn Write) -> io::Result<()> {
+        let mut last: Option<&[u8]> = None;
+        for &range in &self.lines {
+            let line = self.line(range);
+            if settings.unique && last.is_some_and(|last| settings.is_duplicate(last, line)) {
+                continue;
+            }
+            out.write_all(line)?;
+            out.write_all(&[settings.separator])?;
+            last = Some(line);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.lines.clear();
+    }
+}
+
+fn main() {
+    // Like other tools, stop quietly when the reader goes away
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    let matches = Command::new("sort")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils sort - sort lines of text files")
+        .after_help(
+            "With no FILE, or when FILE is -, read standard input.\n\n\
+             SIZE may be followed by the following multiplicative suffixes:\n\
+             % 1% of memory, b 1, K 1024 (default), and so on for M, G, T, P, E.\n\n\
+             KEYDEF is F[.C][OPTS][,F[.C][OPTS]] for start and stop position, where F is a\n\
+             field number and C a character position in the field; both are origin 1, and\n\
+             the stop position defaults to the line's end. If neither -t nor -b is in\n\
+             effect, characters in a field are counted from the beginning of the preceding\n\
+             whitespace. OPTS is one or more single-letter ordering options [bdfghiMnrV],\n\
+             which override global ordering options for that key. If no key is given, use\n\
+             the entire line as the key.\n\n\
+             Input larger than the buffer is sorted in runs written to temporary\n\
+             files, which are then merged.",
+        )
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .arg(
+            Arg::new("ignore-leading-blanks")
+                .short('b')
+                .long("ignore-leading-blanks")
+                .help("Ignore leading blanks")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("check")
+                .short('c')
+                .long("check")
+                .value_name("WHEN")
+                .help("Check for sorted input; do not sort")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("diagnose-first")
+                .value_parser(["diagnose-first", "quiet", "silent"]),
+        )
+        .arg(
+            Arg::new("check-silent")
+                .short('C')
+                .help("Like -c, but do not report first bad line")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("dictionary-order")
+                .short('d')
+                .long("dictionary-order")
+                .help("Consider only blanks and alphanumeric characters")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .short('f')
+                .long("ignore-case")
+                .help("Fold lower case to upper case characters")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("general-numeric-sort")
+                .short('g')
+                .long("general-numeric-sort")
+                .help("Compare according to general numerical value")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ignore-nonprinting")
+                .short('i')
+                .long("ignore-nonprinting")
+                .help("Consider only printable characters")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("month-sort")
+                .short('M')
+                .long("month-sort")
+                .help("Compare (unknown) < 'JAN' < ... < 'DEC'")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("human-numeric-sort")
+                .short('h')
+                .long("human-numeric-sort")
+                .help("Compare human readable numbers (e.g., 2K 1G)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("merge")
+                .short('m')
+                .long("merge")
+                .help("Merge already sorted files; do not sort")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("numeric-sort")
+                .short('n')
+                .long("numeric-sort")
+                .help("Compare according to string numerical value")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("version-sort")
+                .short('V')
+                .long("version-sort")
+                .help("Natural sort of (version) numbers within text")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("key")
+                .short('k')
+                .long("key")
+                .value_name("KEYDEF")
+                .help("Sort via a key; KEYDEF gives location and type")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("field-separator")
+                .short('t')
+                .long("field-separator")
+                .value_name("SEP")
+                .help("Use SEP instead of non-blank to blank transition"),
+        )
+        .arg(
+            Arg::new("parallel")
+                .long("parallel")
+                .value_name("N")
+                .help("Change the number of sorts run concurrently to N"),
+        )
+        .arg(
+            Arg::new("reverse")
+                .short('r')
+                .long("reverse")
+                .help("Reverse the result of comparisons")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("stable")
+                .short('s')
+                .long("stable")
+                .help("Stabilize sort by disabling last-resort comparison")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("unique")
+                .short('u')
+                .long("unique")
+                .help("Output only the first of an equal run")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("Write result to FILE instead of standard output"),
+        )
+        .arg(
+            Arg::new("buffer-size")
+                .short('S')
+                .long("buffer-size")
+                .value_name("SIZE")
+                .help("Use SIZE for main memory buffer"),
+        )
+        .arg(
+            Arg::new("temporary-directory")
+                .short('T')
+                .long("temporary-directory")
+                .value_name("DIR")
+                .help("Use DIR for temporaries, not $TMPDIR or /tmp; multiple options specify multiple directories")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("zero-terminated")
+                .short('z')
+                .long("zero-terminated")
+                .help("Line delimiter is NUL, not newline")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .help("Files to sort")
+                .num_args(0..),
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
+        .arg(
+            Arg::new("version")
+                .long("version")
+                .help("Print version")
+                .action(ArgAction::Version),
+        )
+        .get_matches();
+
+    let settings = Settings {
+        separator: if matches.get_flag("zero-terminated") {
+            b'\0'
+        } else {
+            b'\n'
+        },
+        tab: parse_tab(matches.get_one::<String>("field-separator")),
+        keys: parse_keys(&matches),
+        reverse: matches.get_flag("reverse"),
+        unique: matches.get_flag("unique"),
+        stable: matches.get_flag("stable"),
+    };
+
+    let buffer_size = match matches.get_one::<String>("buffer-size") {
+        Some(size) => parse_buffer_size(size).unwrap_or_else(|| {
+            eprintln!("sort: invalid -S argument '{}'", size);
+            process::exit(2);
+        }),
+        None => default_buffer_size(),
+    }
+    .max(MIN_BUFFER_SIZE);
+
+    let threads = match matches.get_one::<String>("parallel") {
+        Some(count) => match count.parse::<usize>() {
+            Ok(0) => {
+                eprintln!("sort: number in parallel must be nonzero");
+                process::exit(2);
+            }
+            Ok(count) => count,
+            Err(_) => {
+                eprintln!("sort: invalid --parallel argument '{}'", count);
+                process::exit(2);
+            }
+        },
+        None => thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(DEFAULT_MAX_THREADS),
+    };
+
+    let temp_dirs: Vec<PathBuf> = match matches.get_many::<String>("temporary-directory") {
+        Some(dirs) => dirs.map(PathBuf::from).collect(),
+        None => vec![env::var_os("TMPDIR")
+            .filter(|dir| !dir.is_empty())
+            .map_or_else(|| PathBuf::from("/tmp"), PathBuf::from)],
+    };
+    let mut temps = TempDirs::new(temp_dirs);
+
+    let files: Vec<String> = matches
+        .get_many::<String>("files")
+        .map(|files| files.cloned().collect())
+        .unwrap_or_else(|| vec!["-".to_string()]);
+
+    let output = matches.get_one::<String>("output");
+
+    // -c and -C: --check=quiet and --check=silent are the same as -C
+    let check_quiet = match (
+        matches.get_one::<String>("check"),
+        matches.get_flag("check-silent"),
+    ) {
+        (Some(_), true) => {
+            eprintln!("sort: options '-cC' are incompatible");
+            process::exit(2);
+        }
+        (Some(when), false) => Some(when != "diagnose-first"),
+        (None, true) => Some(true),
+        (None, false) => None,
+    };
+    if let Some(quiet) = check_quiet {
+        let letter = if matches.get_flag("check-silent") {
+            'C'
+        } else {
+            'c'
+        };
+        if output.is_some() {
+            eprintln!("sort: options '-{}o' are incompatible", letter);
+            process::exit(2);
+        }
+        if let Some(extra) = files.get(1) {
+            eprintln!(
+                "sort: extra operand '{}' not allowed with -{}",
+                extra, letter
+            );
+            process::exit(2);
+        }
+        match check(&files[0], &settings, quiet) {
+            Ok(true) => process::exit(0),
+            Ok(false) => process::exit(1),
+            Err(err) => {
+                eprintln!("sort: {}", strerror(&err));
+                process::exit(2);
+            }
+        }
+    }
+
+    if matches.get_flag("merge") {
+        if let Err(err) = merge(&files, output, &settings, &mut temps) {
+            eprintln!("sort: {}", strerror(&err));
+            process::exit(2);
+        }
+        return;
+    }
+
+    if let Err(err) = sort(&files, output, &settings, buffer_size, threads, &mut temps) {
+        eprintln!("sort: {}", strerror(&err));
+        process::exit(2);
+    }
+}
+
+fn parse_tab(tab: Option<&String>) -> Option<u8> {
+    let tab = tab?;
+    match tab.as_bytes() {
+        [] => {
+            eprintln!("sort: empty tab");
+            process::exit(2);
+        }
+        [c] => Some(*c),
+        b"\\0" => Some(b'\0'),
+        _ => {
+            eprintln!("sort: multi-character tab '{}'", tab);
+            process::exit(2);
+        }
+    }
+}
+
+// The -k keys, with the global ordering options applied to those that have
+// none of their own; with no -k but global options, one key covering the
+// whole line carries them
+fn parse_keys(matches: &ArgMatches) -> Vec<Key> {
+    let mut global = Key::whole_line();
+    let mut flags: String = ORDERING_FLAGS
+        .iter()
+        .filter(|(name, _)| matches.get_flag(name))
+        .map(|(_, letter)| *letter)
+        .collect();
+    if matches.get_flag("reverse") {
+        flags.push('r');
+    }
+    global.set_flags(&flags, true).ok();
+    if flags.contains('b') {
+        global.set_flags("b", false).ok();
+    }
+
+    let mut keys = Vec::new();
+    for spec in matches.get_many::<String>("key").into_iter().flatten() {
+        let mut key = Key::parse(spec).unwrap_or_else(|err| {
+            eprintln!("sort: {}", err);
+            process::exit(2);
+        });
+        if key.has_default_ordering() {
+            key.inherit(&global);
+        }
+        keys.push(key);
+    }
+    if keys.is_empty() && !global.has_default_ordering() {
+        keys.push(global);
+    }
+
+    for key in &keys {
+        if let Some(options) = key.incompatible_options() {
+            eprintln!("sort: options '-{}' are incompatible", options);
+            process::exit(2);
+        }
+    }
+    keys
+}
+
+// Read every input, spilling sorted runs whenever the buffer fills up, and
+// write the sorted result
+fn sort(
+    files: &[String],
+    output: Option<&String>,
+    settings: &Settings,
+    buffer_size: usize,
+    threads: usize,
+    temps: &mut TempDirs,
+) -> io::Result<()> {
+    let mut chunk = Chunk::new();
+    let mut runs = Vec::new();
+
+    for file in files {
+        let mut reader = open_input(file)?;
+        loop {
+            let start = chunk.data.len();
+            let read = reader
+                .read_until(settings.separator, &mut chunk.data)
+                .map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("cannot read: {}: {}", file, strerror(&err)),
+                    )
+                })?;
+            if read == 0 {
+                break;
+            }
+            let mut end = chunk.data.len();
+            if chunk.data[end - 1] == settings.separator {
+                end -= 1;
+            }
+            chunk.lines.push((start, end));
+
+            if chunk.size() >= buffer_size {
+                runs.push(spill(&mut chunk, settings, threads, temps)?);
+            }
+        }
+    }
+
+    // The output is opened only now, as it may be one of the inputs
+    if runs.is_empty() {
+        chunk.sort(settings, threads);
+        return write_output(output, |out| chunk.write(settings, out));
+    }
+
+    if !chunk.lines.is_empty() {
+        runs.push(spill(&mut chunk, settings, threads, temps)?);
+    }
+    drop(chunk);
+    let runs = external::reduce_runs(runs, settings, temps)?;
+    let sources = external::open_runs(runs, settings.separator)?;
+    write_output(output, |out| external::merge(sources, settings, out))
+}
+
+// -c and -C: whether FILE is already sorted, reporting the first line out
+// of order unless QUIET. With -u, equal neighbours are out of order too.
+fn check(file: &str, settings: &Settings, quiet: bool) -> io::Result<bool> {
+    let Some(mut source) = LineSource::new(open_input(file)?, file, settings.separator)? else {
+        return Ok(true);
+    };
+    let mut previous = source.line().to_vec();
+    let mut number: u64 = 1;
+    while source.advance()? {
+        number += 1;
+        let order = settings.compare(&previous, source.line());
+        if order == Ordering::Greater || (settings.unique && order == Ordering::Equal) {
+            if !quiet {
+                let mut stderr = io::stderr().lock();
+                let _ = write!(stderr, "sort: {}:{}: disorder: ", file, number);
+                let _ = stderr.write_all(source.line());
+                let _ = stderr.write_all(b"\n");
+            }
+            return Ok(false);
+        }
+        previous.clear();
+        previous.extend_from_slice(source.line());
+    }
+    Ok(true)
+}
+
+// -m: merge inputs that are each sorted already. More of them than can be
+// merged at once are merged in groups into runs first.
+fn merge(
+    files: &[String],
+    output: Option<&String>,
+    settings: &Settings,
+    temps: &mut TempDirs,
+) -> io::Result<()> {
+    if files.len() <= external::MERGE_WAYS {
+        let sources = open_sources(files, output, settings, temps)?;
+        return write_output(output, |out| external::merge(sources, settings, out));
+    }
+
+    let mut runs = Vec::new();
+    for group in files.chunks(external::MERGE_WAYS) {
+        let sources = open_sources(group, None, settings, temps)?;
+        let mut writer = BufWriter::new(temps.create()?);
+        external::merge(sources, settings, &mut writer)?;
+        runs.push(external::rewind(writer)?);
+    }
+    let runs = external::reduce_runs(runs, settings, temps)?;
+    let sources = external::open_runs(runs, settings.separator)?;
+    write_output(output, |out| external::merge(sources, settings, out))
+}
+
+// Open FILES for merging. One that is also the OUTPUT is copied to a
+// temporary file first, since opening the output empties it.
+fn open_sources(
+    files: &[String],
+    output: Option<&String>,
+    settings: &Settings,
+    temps: &mut TempDirs,
+) -> io::Result<Vec<LineSource>> {
+    let identity = |file: &str| fs::metadata(file).ok().map(|meta| (meta.dev(), meta.ino()));
+    let output = output.and_then(|file| identity(file));
+
+    let mut sources = Vec::new();
+    for file in files {
+        let mut reader = open_input(file)?;
+        if file != "-" && output.is_some() && identity(file) == output {
+            let mut copy = BufWriter::new(temps.create()?);
+            io::copy(&mut reader, &mut copy).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("cannot read: {}: {}", file, strerror(&err)),
+                )
+            })?;
+            reader = Box::new(BufReader::new(external::rewind(copy)?));
+        }
+        if let Some(source) = LineSource::new(reader, file, settings.separator)? {
+            sources.push(source);
+        }
+    }
+    Ok(sources)
+}
+
+// Sort the lines in CHUNK into a new run and empty it for more
+fn spill(
+    chunk: &mut Chunk,
+    settings: &Settings,
+    threads: usize,
+    temps: &mut TempDirs,
+) -> io::Result<File> {
+    chunk.sort(settings, threads);
+    let mut writer = BufWriter::new(temps.create()?);
+    chunk.write(settings, &mut writer)?;
+    chunk.clear();
+    external::rewind(writer)
+}
+
+fn open_input(file: &str) -> io::Result<Box<dyn BufRead>> {
+    if file == "-" {
+        return Ok(Box::new(BufReader::new(io::stdin())));
+    }
+    let handle = File::open(file).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("cannot read: {}: {}", file, strerror(&err)),
+        )
+    })?;
+    Ok(Box::new(BufReader::new(handle)))
+}
+
+// The sorted output, naming itself in write errors as those are reported
+// through the same path as read errors
+struct Output {
+    writer: BufWriter<Box<dyn Write>>,
+    name: String,
+}
+
+impl Output {
+    fn failed(&self, err: io::Error) -> io::Error {
+        io::Error::new(
+            err.kind(),
+            format!("write failed: {}: {}", self.name, strerror(&err)),
+        )
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf).map_err(|err| self.failed(err))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush().map_err(|err| self.failed(err))
+    }
+}
+
+fn write_output(
+    output: Option<&String>,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    let (name, handle): (&str, Box<dyn Write>) = match output {
+        Some(file) => {
+            let handle = File::create(file).map_err(|err| {
+                io::Error::new(
+                    err.kind(),
+                    format!("open failed: {}: {}", file, strerror(&err)),
+                )
+            })?;
+            (file, Box::new(handle))
+        }
+        None => ("standard output", Box::new(io::stdout().lock())),
+    };
+    let mut out = Output {
+        writer: BufWriter::new(handle),
+        name: name.to_string(),
+    };
+    write(&mut out)?;
+    out.flush()
+}
+
+// Parse a -S size: a number with an optional unit, kibibytes by default
+fn parse_buffer_size(text: &str) -> Option<usize> {
+    let digits = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(digits);
+    let number: u64 = number.parse().ok()?;
+    let bytes = match unit {
+        "%" => (physical_memory() / 100).checked_mul(number)?,
+        "" => number.checked_mul(1 << 10)?,
+        _ => parse_size(text, &[("b", 1)])?,
+    };
+    Some(usize::try_from(bytes).unwrap_or(usize::MAX))
+}
+
+fn physical_memory() -> u64 {
+    let pages = unsafe { libc::sysconf(libc::_SC_PHYS_PAGES) };
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if pages <= 0 || page_size <= 0 {
+        return 0;
+    }
+    pages as u64 * page_size as u64
+}
+
+// An eighth of physical memory leaves room for the rest of the system and
+// for the buffer growing past its mark by a line
+fn default_buffer_size() -> usize {
+    match physical_memory() / 8 {
+        0 => 64 << 20,
+        bytes => usize::try_from(bytes).unwrap_or(usize::MAX),
+    }
+}
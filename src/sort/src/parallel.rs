@@ -0,0 +1,91 @@
+// ASD CoreUtils - sort
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Sorting one buffer with several threads: each sorts a part of it, and
+// neighbouring parts are then merged pairwise, again in parallel, until one
+// is left
+
+use std::cmp::Ordering;
+use std::thread;
+
+// Fewer lines than this per thread are sorted faster than threads start
+const MIN_LINES_PER_THREAD: usize = 1 << 14;
+
+// Stable sort of ITEMS by COMPARE with up to THREADS threads
+pub fn sort_by<T, F>(items: &mut Vec<T>, threads: usize, compare: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let threads = threads.min(items.len() / MIN_LINES_PER_THREAD);
+    if threads <= 1 {
+        items.sort_by(&compare);
+        return;
+    }
+
+    let part = items.len().div_ceil(threads);
+    thread::scope(|scope| {
+        for slice in items.chunks_mut(part) {
+            let compare = &compare;
+            scope.spawn(move || slice.sort_by(compare));
+        }
+    });
+
+    // Boundaries of the sorted parts, which each pass halves in number
+    let mut bounds: Vec<usize> = (0..items.len()).step_by(part).collect();
+    bounds.push(items.len());
+    let mut other = items.clone();
+    while bounds.len() > 2 {
+        merge_pass(items, &mut other, &bounds, &compare);
+        std::mem::swap(items, &mut other);
+        let mut merged: Vec<usize> = bounds.iter().step_by(2).copied().collect();
+        if merged.last() != bounds.last() {
+            merged.push(items.len());
+        }
+        bounds = merged;
+    }
+}
+
+// Merge each pair of neighbouring parts of SOURCE, as delimited by BOUNDS,
+// into the same place in TARGET; a part without a partner is copied
+fn merge_pass<T, F>(source: &[T], target: &mut [T], bounds: &[usize], compare: &F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    thread::scope(|scope| {
+        let mut rest = target;
+        let mut offset = 0;
+        for pair in bounds.windows(3).step_by(2) {
+            let (start, middle, end) = (pair[0], pair[1], pair[2]);
+            let (out, tail) = std::mem::take(&mut rest).split_at_mut(end - offset);
+            rest = tail;
+            offset = end;
+            let (left, right) = (&source[start..middle], &source[middle..end]);
+            scope.spawn(move || merge_into(left, right, out, compare));
+        }
+        let copied = source.len() - offset;
+        rest[..copied].copy_from_slice(&source[offset..]);
+    });
+}
+
+// Merge the sorted LEFT and RIGHT into OUT, taking from LEFT on ties
+fn merge_into<T: Copy, F: Fn(&T, &T) -> Ordering>(
+    left: &[T],
+    right: &[T],
+    out: &mut [T],
+    compare: &F,
+) {
+    let (mut i, mut j) = (0, 0);
+    for slot in out.iter_mut() {
+        if j == right.len() || (i < left.len() && compare(&left[i], &right[j]) != Ordering::Greater)
+        {
+            *slot = left[i];
+            i += 1;
+        } else {
+            *slot = right[j];
+            j += 1;
+        }
+    }
+}
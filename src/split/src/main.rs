@@ -0,0 +1,702 @@
+// ASD CoreUtils - split
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, ArgAction, Command};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, Read, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{self, Child, ChildStdin, Stdio};
+
+const DEFAULT_LINES: u64 = 1000;
+const DEFAULT_SUFFIX_LENGTH: usize = 2;
+const BUFFER_SIZE: usize = 64 * 1024;
+
+enum Mode {
+    Lines(u64),
+    Bytes(u64),
+    // -n: N pieces of about the same size, or only piece K of them
+    Chunks {
+        kind: ChunkKind,
+        extract: Option<u64>,
+        count: u64,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ChunkKind {
+    Bytes,
+    // Pieces end at line ends, l/N
+    Lines,
+    // Lines dealt out in turn, r/N
+    RoundRobin,
+}
+
+#[derive(Clone, Copy)]
+enum SuffixKind {
+    Alphabetic,
+    Numeric,
+    Hex,
+}
+
+impl SuffixKind {
+    fn digits(self) -> &'static [u8] {
+        match self {
+            SuffixKind::Alphabetic => b"abcdefghijklmnopqrstuvwxyz",
+            SuffixKind::Numeric => b"0123456789",
+            SuffixKind::Hex => b"0123456789abcdef",
+        }
+    }
+}
+
+// Where a piece goes: a file, or with --filter the input of a command
+enum Sink {
+    File(File),
+    Filter(ChildStdin),
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Sink::File(file) => file.write(buf),
+            // A command that stops reading early just doesn't get the rest
+            Sink::Filter(stdin) => match stdin.write(buf) {
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(buf.len()),
+                result => result,
+            },
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Sink::File(file) => file.flush(),
+            Sink::Filter(stdin) => match stdin.flush() {
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                result => result,
+            },
+        }
+    }
+}
+
+// One output piece being written
+struct Chunk {
+    name: String,
+    writer: BufWriter<Sink>,
+    child: Option<Child>,
+}
+
+impl Write for Chunk {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl Chunk {
+    // Finish writing, and with --filter wait for the command. As in GNU
+    // split, a command that fails ends split with its exit status.
+    fn finish(self, filter: Option<&str>) -> io::Result<()> {
+        let Chunk { name, writer, child } = self;
+        drop(writer.into_inner().map_err(|err| err.into_error())?);
+
+        let (Some(mut child), Some(filter)) = (child, filter) else {
+            return Ok(());
+        };
+        let status = child.wait()?;
+        if let Some(code) = status.code().filter(|&code| code != 0) {
+            eprintln!("split: with FILE={}, exit {} from command: {}", name, code, filter);
+            process::exit(code);
+        }
+        if let Some(signal) = status.signal().filter(|&signal| signal != libc::SIGPIPE) {
+            eprintln!("split: with FILE={}, signal {} from command: {}", name, signal, filter);
+            process::exit(128 + signal);
+        }
+        Ok(())
+    }
+}
+
+// Hands out output files named PREFIX + suffix + additional suffix,
+// opening them on demand
+struct Splitter {
+    prefix: String,
+    suffix_kind: SuffixKind,
+    suffix_length: usize,
+    // Number the first suffix stands for, from -d=FROM or -x=FROM
+    suffix_start: u64,
+    additional_suffix: String,
+    filter: Option<String>,
+    next_index: u64,
+    current: Option<Chunk>,
+}
+
+impl Splitter {
+    fn start_chunk(&mut self) -> io::Result<&mut Chunk> {
+        self.finish_chunk()?;
+        let chunk = self.open_chunk(self.next_index)?;
+        self.next_index += 1;
+        Ok(self.current.insert(chunk))
+    }
+
+    fn finish_chunk(&mut self) -> io::Result<()> {
+        match self.current.take() {
+            Some(chunk) => chunk.finish(self.filter.as_deref()),
+            None => Ok(()),
+        }
+    }
+
+    // Create the output for the INDEXth piece, counting from 0
+    fn open_chunk(&self, index: u64) -> io::Result<Chunk> {
+        let suffix = self
+            .suffix_start
+            .checked_add(index)
+            .and_then(|number| make_suffix(number, self.suffix_length, self.suffix_kind))
+            .ok_or_else(|| io::Error::other("output file suffixes exhausted"))?;
+        let name = format!("{}{}{}", self.prefix, suffix, self.additional_suffix);
+
+        let Some(filter) = &self.filter else {
+            let file = File::create(&name)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", name, strerror(&err))))?;
+            return Ok(Chunk {
+                name,
+                writer: BufWriter::new(Sink::File(file)),
+                child: None,
+            });
+        };
+
+        let mut child = process::Command::new("sh")
+            .arg("-c")
+            .arg(filter)
+            .env("FILE", &name)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|err| io::Error::new(err.kind(), format!("sh: {}", strerror(&err))))?;
+        let stdin = child.stdin.take().expect("stdin was piped");
+        Ok(Chunk {
+            name,
+            writer: BufWriter::new(Sink::Filter(stdin)),
+            child: Some(child),
+        })
+    }
+}
+
+fn main() {
+    let matches = Command::new("split")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils split - split a file into pieces")
+        .arg(
+            Arg::new("lines")
+                .short('l')
+                .long("lines")
+                .value_name("NUMBER")
+                .help("Put NUMBER lines per output file (default: 1000)")
+                .conflicts_with_all(["bytes", "number"]),
+        )
+        .arg(
+            Arg::new("bytes")
+                .short('b')
+                .long("bytes")
+                .value_name("SIZE")
+                .help("Put SIZE bytes per output file (K, M, G suffixes allowed)")
+                .conflicts_with("number"),
+        )
+        .arg(
+            Arg::new("number")
+                .short('n')
+                .long("number")
+                .value_name("CHUNKS")
+                .help("Generate CHUNKS output files: N, K/N, l/N, l/K/N, r/N or r/K/N"),
+        )
+        .arg(
+            Arg::new("suffix-length")
+                .short('a')
+                .long("suffix-length")
+                .value_name("N")
+                .help("Generate suffixes of length N (default: 2)"),
+        )
+        .arg(
+            Arg::new("numeric-suffixes")
+                .short('d')
+                .long("numeric-suffixes")
+                .value_name("FROM")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("0")
+                .overrides_with("hex-suffixes")
+                .help("Use numeric suffixes starting at FROM (default: 0)"),
+        )
+        .arg(
+            Arg::new("hex-suffixes")
+                .short('x')
+                .long("hex-suffixes")
+                .value_name("FROM")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("0")
+                .overrides_with("numeric-suffixes")
+                .help("Use hexadecimal suffixes starting at FROM (default: 0)"),
+        )
+        .arg(
+            Arg::new("additional-suffix")
+                .long("additional-suffix")
+                .value_name("SUFFIX")
+                .help("Append SUFFIX to file names"),
+        )
+        .arg(
+            Arg::new("elide-empty-files")
+                .short('e')
+                .long("elide-empty-files")
+                .action(ArgAction::SetTrue)
+                .help("Do not generate empty output files with -n"),
+        )
+        .arg(
+            Arg::new("filter")
+                .long("filter")
+                .value_name("COMMAND")
+                .help("Write each piece to the shell COMMAND, with the file name in $FILE"),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Input file, or - for standard input")
+                .default_value("-"),
+        )
+        .arg(
+            Arg::new("prefix")
+                .value_name("PREFIX")
+                .help("Prefix for output file names")
+                .default_value("x"),
+        )
+        .args_override_self(true)
+        .get_matches();
+
+    let mode = if let Some(value) = matches.get_one::<String>("bytes") {
+        match parse_size(value, &[("b", 512)]) {
+            Some(size) if size > 0 => Mode::Bytes(size),
+            _ => fail("split", &format!("invalid number of bytes: '{}'", value)),
+        }
+    } else if let Some(value) = matches.get_one::<String>("lines") {
+        match value.parse::<u64>() {
+            Ok(lines) if lines > 0 => Mode::Lines(lines),
+            _ => fail("split", &format!("invalid number of lines: '{}'", value)),
+        }
+    } else if let Some(value) = matches.get_one::<String>("number") {
+        match parse_chunks(value) {
+            Ok(mode) => mode,
+            Err(message) => fail("split", &message),
+        }
+    } else {
+        Mode::Lines(DEFAULT_LINES)
+    };
+
+    let filter = matches.get_one::<String>("filter").cloned();
+    if filter.is_some() && matches!(mode, Mode::Chunks { extract: Some(_), .. }) {
+        usage_error("split", "--filter does not process a chunk extracted to stdout");
+    }
+
+    let (suffix_kind, suffix_start) = match (
+        matches.get_one::<String>("numeric-suffixes"),
+        matches.get_one::<String>("hex-suffixes"),
+    ) {
+        (Some(from), _) => (SuffixKind::Numeric, parse_suffix_start(from, 10)),
+        (_, Some(from)) => (SuffixKind::Hex, parse_suffix_start(from, 16)),
+        _ => (SuffixKind::Alphabetic, 0),
+    };
+
+    // Enough suffix for the first name, and with -n for every piece
+    let mut needed = suffix_width(suffix_start, suffix_kind);
+    if let Mode::Chunks { count, .. } = mode {
+        needed = needed.max(suffix_width(suffix_start.saturating_add(count - 1), suffix_kind));
+    }
+
+    let suffix_length = match matches.get_one::<String>("suffix-length") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(length) if length >= needed => length,
+            Ok(length) if length > 0 => fail(
+                "split",
+                &format!("the suffix length needs to be at least {}", needed),
+            ),
+            _ => fail("split", &format!("invalid suffix length: '{}'", value)),
+        },
+        None => DEFAULT_SUFFIX_LENGTH.max(needed),
+    };
+
+    let file = matches.get_one::<String>("file").unwrap();
+    let prefix = matches.get_one::<String>("prefix").unwrap();
+
+    let input: Box<dyn Read> = if file == "-" {
+        Box::new(io::stdin())
+    } else {
+        match File::open(file) {
+            Ok(f) => Box::new(f),
+            Err(err) => fail(
+                "split",
+                &format!("cannot open '{}' for reading: {}", file, strerror(&err)),
+            ),
+        }
+    };
+
+    let mut splitter = Splitter {
+        prefix: prefix.clone(),
+        suffix_kind,
+        suffix_length,
+        suffix_start,
+        additional_suffix: matches
+            .get_one::<String>("additional-suffix")
+            .cloned()
+            .unwrap_or_default(),
+        filter,
+        next_index: 0,
+        current: None,
+    };
+    let elide = matches.get_flag("elide-empty-files");
+    let mut reader = BufReader::new(input);
+
+    let result = match mode {
+        Mode::Lines(lines) => split_by_lines(&mut reader, &mut splitter, lines),
+        Mode::Bytes(bytes) => split_by_bytes(&mut reader, &mut splitter, bytes),
+        Mode::Chunks {
+            kind: ChunkKind::RoundRobin,
+            extract,
+            count,
+        } => split_round_robin(&mut reader, &mut splitter, count, extract, elide),
+        Mode::Chunks {
+            kind,
+            extract,
+            count,
+        } => input_size(file, reader).and_then(|(mut reader, size)| {
+            if kind == ChunkKind::Lines {
+                split_line_chunks(&mut reader, &mut splitter, count, extract, size, elide)
+            } else {
+                split_byte_chunks(&mut reader, &mut splitter, count, extract, size, elide)
+            }
+        }),
+    }
+    .and_then(|_| splitter.finish_chunk());
+
+    if let Err(err) = result {
+        fail("split", &strerror(&err));
+    }
+}
+
+// Parses -n CHUNKS: N, K/N, l/N, l/K/N, r/N or r/K/N
+fn parse_chunks(value: &str) -> Result<Mode, String> {
+    let (kind, rest) = if let Some(rest) = value.strip_prefix("l/") {
+        (ChunkKind::Lines, rest)
+    } else if let Some(rest) = value.strip_prefix("r/") {
+        (ChunkKind::RoundRobin, rest)
+    } else {
+        (ChunkKind::Bytes, value)
+    };
+
+    let (extract, count) = match rest.split_once('/') {
+        Some((k, n)) => (Some(k), n),
+        None => (None, rest),
+    };
+
+    let count = match count.parse::<u64>() {
+        Ok(0) => return Err(format!("invalid number of chunks: '{}'", count)),
+        Ok(count) => count,
+        Err(_) => return Err(format!("invalid number of chunks: '{}'", count)),
+    };
+    let extract = match extract.map(|k| (k, k.parse::<u64>())) {
+        None => None,
+        Some((_, Ok(k))) if k > 0 && k <= count => Some(k),
+        Some((k, _)) => return Err(format!("invalid chunk number: '{}'", k)),
+    };
+
+    Ok(Mode::Chunks {
+        kind,
+        extract,
+        count,
+    })
+}
+
+fn parse_suffix_start(value: &str, radix: u32) -> u64 {
+    match u64::from_str_radix(value, radix) {
+        Ok(start) => start,
+        Err(_) => fail("split", &format!("invalid start value for suffixes: '{}'", value)),
+    }
+}
+
+// How many digits NUMBER takes written with the suffix's digits
+fn suffix_width(mut number: u64, kind: SuffixKind) -> usize {
+    let base = kind.digits().len() as u64;
+    let mut width = 1;
+    while number >= base {
+        number /= base;
+        width += 1;
+    }
+    width
+}
+
+// Byte and line chunks are measured against the input's size: that of the
+// rest of a regular file, or of anything else once read into memory
+fn input_size(file: &str, mut reader: BufReader<Box<dyn Read>>) -> io::Result<(Box<dyn BufRead>, u64)> {
+    if file != "-" {
+        let metadata = std::fs::metadata(file)?;
+        if metadata.is_file() {
+            return Ok((Box::new(reader), metadata.len()));
+        }
+    }
+
+    let mut contents = Vec::new();
+    reader
+        .read_to_end(&mut contents)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file, strerror(&err))))?;
+    let size = contents.len() as u64;
+    Ok((Box::new(Cursor::new(contents)), size))
+}
+
+fn split_by_lines(reader: &mut impl BufRead, splitter: &mut Splitter, lines: u64) -> io::Result<()> {
+    let mut line = Vec::new();
+    let mut written = lines;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(());
+        }
+
+        if written == lines {
+            splitter.start_chunk()?;
+            written = 0;
+        }
+
+        splitter.current.as_mut().unwrap().write_all(&line)?;
+        written += 1;
+    }
+}
+
+fn split_by_bytes(reader: &mut impl Read, splitter: &mut Splitter, bytes: u64) -> io::Result<()> {
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut remaining = 0u64;
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        let mut data = &buffer[..read];
+        while !data.is_empty() {
+            if remaining == 0 {
+                splitter.start_chunk()?;
+                remaining = bytes;
+            }
+
+            let take = data.len().min(remaining as usize);
+            splitter.current.as_mut().unwrap().write_all(&data[..take])?;
+            remaining -= take as u64;
+            data = &data[take..];
+        }
+    }
+}
+
+// -n N and -n K/N: every piece but the last has SIZE / N bytes, the last
+// also takes what is left over
+fn split_byte_chunks(
+    reader: &mut impl Read,
+    splitter: &mut Splitter,
+    count: u64,
+    extract: Option<u64>,
+    size: u64,
+    elide: bool,
+) -> io::Result<()> {
+    let chunk_size = (size / count).max(1);
+
+    for number in 1..=count {
+        let start = ((number - 1) * chunk_size).min(size);
+        let end = if number == count {
+            size
+        } else {
+            (number * chunk_size).min(size)
+        };
+        let mut piece = reader.by_ref().take(end - start);
+
+        match extract {
+            Some(k) if k == number => {
+                let mut stdout = io::stdout().lock();
+                io::copy(&mut piece, &mut stdout)?;
+                return stdout.flush();
+            }
+            Some(_) => {
+                io::copy(&mut piece, &mut io::sink())?;
+            }
+            None if end == start && elide => {}
+            None => {
+                let chunk = splitter.start_chunk()?;
+                io::copy(&mut piece, chunk)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// -n l/N and -n l/K/N: piece K ends with the first line end at or after
+// byte K * SIZE / N, the way GNU split places them, so a long line can
+// leave the pieces it spans empty
+fn split_line_chunks(
+    reader: &mut impl Read,
+    splitter: &mut Splitter,
+    count: u64,
+    extract: Option<u64>,
+    size: u64,
+    elide: bool,
+) -> io::Result<()> {
+    let chunk_size = size / count;
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut stdout = io::stdout().lock();
+    let mut number = 1;
+    let mut chunk_end = chunk_size;
+    let mut written = 0u64;
+    let mut new_file = true;
+    let mut truncated = false;
+
+    let empty_chunk = |splitter: &mut Splitter| -> io::Result<()> {
+        if extract.is_none() && !elide {
+            splitter.start_chunk()?;
+        }
+        Ok(())
+    };
+
+    while written < size {
+        let want = buffer.len().min((size - written) as usize);
+        let read = match reader.read(&mut buffer[..want]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        truncated = false;
+
+        let mut data = &buffer[..read];
+        while !data.is_empty() {
+            let skip = (chunk_end.saturating_sub(1).saturating_sub(written) as usize).min(data.len());
+            let (take, mut next) = match data[skip..].iter().position(|&byte| byte == b'\n') {
+                Some(position) => (skip + position + 1, true),
+                None => (data.len(), false),
+            };
+
+            match extract {
+                Some(k) if k == number => stdout.write_all(&data[..take])?,
+                Some(_) => {}
+                None => {
+                    if new_file {
+                        splitter.start_chunk()?;
+                    }
+                    splitter.current.as_mut().unwrap().write_all(&data[..take])?;
+                }
+            }
+            written += take as u64;
+            data = &data[take..];
+            new_file = next;
+
+            // Move on to the piece the next byte belongs in
+            while next || chunk_end <= written {
+                if !next && data.is_empty() {
+                    // The line goes on in the next read
+                    truncated = true;
+                    break;
+                }
+                number += 1;
+                if extract.is_some_and(|k| number > k) {
+                    return stdout.flush();
+                }
+                chunk_end = if number == count {
+                    size
+                } else {
+                    chunk_end + chunk_size
+                };
+                if chunk_end <= written {
+                    empty_chunk(splitter)?;
+                } else {
+                    next = false;
+                }
+            }
+        }
+    }
+
+    if truncated {
+        number += 1;
+    }
+    while number <= count {
+        empty_chunk(splitter)?;
+        number += 1;
+    }
+    stdout.flush()
+}
+
+// -n r/N and -n r/K/N: line I goes to piece I mod N
+fn split_round_robin(
+    reader: &mut impl BufRead,
+    splitter: &mut Splitter,
+    count: u64,
+    extract: Option<u64>,
+    elide: bool,
+) -> io::Result<()> {
+    let mut line = Vec::new();
+
+    if let Some(k) = extract {
+        let mut stdout = io::stdout().lock();
+        let mut index = 0u64;
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            if index % count == k - 1 {
+                stdout.write_all(&line)?;
+            }
+            index += 1;
+            line.clear();
+        }
+        return stdout.flush();
+    }
+
+    let mut chunks: Vec<Option<Chunk>> = Vec::new();
+    for index in 0..count {
+        chunks.push(if elide {
+            None
+        } else {
+            Some(splitter.open_chunk(index)?)
+        });
+    }
+
+    let mut index = 0usize;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        let chunk = match &mut chunks[index] {
+            Some(chunk) => chunk,
+            slot => slot.insert(splitter.open_chunk(index as u64)?),
+        };
+        chunk.write_all(&line)?;
+        index = (index + 1) % chunks.len();
+        line.clear();
+    }
+
+    for chunk in chunks.into_iter().flatten() {
+        chunk.finish(splitter.filter.as_deref())?;
+    }
+    Ok(())
+}
+
+// Maps 0, 1, 2, ... to "aa", "ab", "ac", ... (or "00", "01", "02", ... with
+// numeric suffixes) for the given suffix length
+fn make_suffix(mut index: u64, length: usize, kind: SuffixKind) -> Option<String> {
+    let digits = kind.digits();
+    let base = digits.len() as u64;
+    let mut suffix = vec![digits[0]; length];
+
+    for slot in suffix.iter_mut().rev() {
+        *slot = digits[(index % base) as usize];
+        index /= base;
+    }
+
+    if index > 0 {
+        return None;
+    }
+    Some(String::from_utf8(suffix).unwrap())
+}
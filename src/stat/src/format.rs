@@ -0,0 +1,244 @@
+// ASD CoreUtils - stat
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// What a directive stands for; how it is printed depends on the kind, as
+// with printf's conversions
+pub enum Value {
+    Text(Vec<u8>),
+    Unsigned(u64),
+    Octal(u64),
+    Hex(u64),
+    // Seconds and nanoseconds since the epoch; a precision shows a fraction
+    Time(i64, u32),
+}
+
+// The printf-style part between '%' and the directive, as in "%-10s"
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    zero: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+// Expands the directives of FORMAT, each '%' followed by optional flags,
+// width and precision and then the directive; "H" and "L" ahead of one ask
+// for the major or minor part of a device number, as in "%Hd". VALUE gives
+// the value of a directive, or None for one it does not know, printed as
+// '?'.
+pub fn expand(format: &[u8], value: &mut dyn FnMut(&str) -> Option<Value>) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut chars = format.iter().copied().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != b'%' {
+            out.push(c);
+            continue;
+        }
+
+        let mut spec = Spec::default();
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                b'-' => spec.left = true,
+                b'0' => spec.zero = true,
+                b'+' => spec.plus = true,
+                b' ' => spec.space = true,
+                b'#' => spec.alternate = true,
+                // Thousands grouping is accepted and ignored
+                b'\'' => {}
+                _ => break,
+            }
+            chars.next();
+        }
+        while let Some(digit) = chars.peek().and_then(|&c| char::from(c).to_digit(10)) {
+            spec.width = spec.width * 10 + digit as usize;
+            chars.next();
+        }
+        if chars.next_if_eq(&b'.').is_some() {
+            let mut precision = None;
+            while let Some(digit) = chars.peek().and_then(|&c| char::from(c).to_digit(10)) {
+                precision = Some(precision.unwrap_or(0) * 10 + digit as usize);
+                chars.next();
+            }
+            // A bare "." is the whole fraction for the times, as GNU has it
+            spec.precision = Some(precision.unwrap_or(9));
+        }
+
+        let mut directive = String::new();
+        if let Some(&part @ (b'H' | b'L')) = chars.peek() {
+            directive.push(char::from(part));
+            chars.next();
+        }
+        match chars.next() {
+            // A '%' at the very end stands for itself
+            None => {
+                out.push(b'%');
+                out.extend_from_slice(directive.as_bytes());
+            }
+            Some(b'%') if directive.is_empty() => out.push(b'%'),
+            Some(c) => {
+                directive.push(char::from(c));
+                match value(&directive) {
+                    Some(value) => out.extend(render(&spec, value)),
+                    None => out.push(b'?'),
+                }
+            }
+        }
+    }
+    out
+}
+
+fn render(spec: &Spec, value: Value) -> Vec<u8> {
+    let (sign, prefix, digits) = match value {
+        Value::Text(text) => {
+            let text = match spec.precision {
+                Some(precision) => text.into_iter().take(precision).collect(),
+                None => text,
+            };
+            return pad(spec, "", text, false);
+        }
+        Value::Unsigned(number) => (number_sign(spec, false), "", number.to_string()),
+        Value::Octal(number) => {
+            let digits = format!("{:o}", number);
+            let prefix = if spec.alternate && number != 0 {
+                "0"
+            } else {
+                ""
+            };
+            ("", prefix, digits)
+        }
+        Value::Hex(number) => {
+            let prefix = if spec.alternate && number != 0 {
+                "0x"
+            } else {
+                ""
+            };
+            ("", prefix, format!("{:x}", number))
+        }
+        Value::Time(seconds, nanoseconds) => {
+            let mut digits = seconds.unsigned_abs().to_string();
+            if let Some(precision) = spec.precision.filter(|&precision| precision > 0) {
+                let fraction = format!("{:09}", nanoseconds);
+                digits.push('.');
+                digits.push_str(&fraction[..precision.min(9)]);
+                digits.push_str(&"0".repeat(precision.saturating_sub(9)));
+            }
+            return pad(
+                spec,
+                number_sign(spec, seconds < 0),
+                digits.into_bytes(),
+                true,
+            );
+        }
+    };
+
+    // For the integers the precision is a minimum number of digits
+    let digits = match spec.precision {
+        Some(precision) if digits.len() < precision => {
+            format!("{}{}", "0".repeat(precision - digits.len()), digits)
+        }
+        _ => digits,
+    };
+    let body = format!("{}{}", prefix, digits).into_bytes();
+    pad(spec, sign, body, spec.precision.is_none())
+}
+
+fn number_sign(spec: &Spec, negative: bool) -> &'static str {
+    if negative {
+        "-"
+    } else if spec.plus {
+        "+"
+    } else if spec.space {
+        " "
+    } else {
+        ""
+    }
+}
+
+// Pads BODY, after SIGN, to the width; zeros go between the two
+fn pad(spec: &Spec, sign: &str, body: Vec<u8>, zero_ok: bool) -> Vec<u8> {
+    let fill = spec.width.saturating_sub(sign.len() + body.len());
+    let (before, between, after) = if fill == 0 {
+        (0, 0, 0)
+    } else if spec.left {
+        (0, 0, fill)
+    } else if spec.zero && zero_ok {
+        (0, fill, 0)
+    } else {
+        (fill, 0, 0)
+    };
+
+    let mut out = vec![b' '; before];
+    out.extend_from_slice(sign.as_bytes());
+    out.resize(out.len() + between, b'0');
+    out.extend(body);
+    out.resize(out.len() + after, b' ');
+    out
+}
+
+// The backslash escapes of --printf. Unknown ones are warned about and
+// stand for the character after the backslash.
+pub fn unescape(format: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut chars = format.iter().copied().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != b'\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            None => {
+                eprintln!("stat: warning: backslash at end of format");
+                out.push(b'\\');
+            }
+            Some(digit @ b'0'..=b'7') => {
+                let mut code = u32::from(digit - b'0');
+                for _ in 0..2 {
+                    match chars.peek().and_then(|&c| char::from(c).to_digit(8)) {
+                        Some(digit) => {
+                            code = code * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                out.push((code & 0xff) as u8);
+            }
+            Some(b'x') if chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) => {
+                let mut code = 0;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|&c| char::from(c).to_digit(16)) {
+                        Some(digit) => {
+                            code = code * 16 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                out.push(code as u8);
+            }
+            Some(b'a') => out.push(b'\x07'),
+            Some(b'b') => out.push(b'\x08'),
+            Some(b'e') => out.push(b'\x1b'),
+            Some(b'f') => out.push(b'\x0c'),
+            Some(b'n') => out.push(b'\n'),
+            Some(b'r') => out.push(b'\r'),
+            Some(b't') => out.push(b'\t'),
+            Some(b'v') => out.push(b'\x0b'),
+            Some(c @ (b'\\' | b'"')) => out.push(c),
+            Some(c) => {
+                eprintln!(
+                    "stat: warning: unrecognized escape '\\{}'",
+                    String::from_utf8_lossy(&[c])
+                );
+                out.push(c);
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,505 @@
+// ASD CoreUtils - stat
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod format;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::metadata::{self, file_type};
+use asd_coreutils_core::mode::mode_string;
+use clap::{Arg, ArgAction, Command};
+use format::Value;
+use std::ffi::{CString, OsStr};
+use std::fs::{self, File, Metadata};
+use std::io::{self, Write};
+use std::mem::{self, ManuallyDrop};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, MetadataExt};
+use std::os::unix::io::FromRawFd;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::UNIX_EPOCH;
+
+const FILE_TERSE: &str = "%n %s %b %f %u %g %D %i %h %t %T %X %Y %Z %W %o";
+const FILE_SYSTEM_FORMAT: &str = "  File: \"%n\"\n    ID: %-8i Namelen: %-7l Type: %T\n\
+                                  Block size: %-10s Fundamental block size: %S\n\
+                                  Blocks: Total: %-10b Free: %-10f Available: %a\n\
+                                  Inodes: Total: %-10c Free: %d";
+const FILE_SYSTEM_TERSE: &str = "%n %i %l %t %s %S %b %f %a %c %d";
+
+// Names for the magic numbers statfs gives as the file system type
+const FILE_SYSTEM_TYPES: &[(u64, &str)] = &[
+    (0x0000_9fa0, "proc"),
+    (0x0000_1cd1, "devpts"),
+    (0x0000_4d44, "msdos"),
+    (0x0000_6969, "nfs"),
+    (0x0000_ef53, "ext2/ext3"),
+    (0x0102_1994, "tmpfs"),
+    (0x2fc1_2fc1, "zfs"),
+    (0x5846_5342, "xfs"),
+    (0x6265_6572, "sysfs"),
+    (0x6367_7270, "cgroup2fs"),
+    (0x6462_6720, "debugfs"),
+    (0x6573_5546, "fuseblk"),
+    (0x7363_6673, "securityfs"),
+    (0x7371_7368, "squashfs"),
+    (0x794c_7630, "overlayfs"),
+    (0x8584_58f6, "ramfs"),
+    (0x9123_683e, "btrfs"),
+    (0xcafe_4a11, "bpf_fs"),
+    (0xf15f, "ecryptfs"),
+    (0x0027_e0eb, "cgroupfs"),
+    (0x5346_544e, "ntfs"),
+    (0x0000_3434, "nilfs"),
+    (0x0000_9660, "isofs"),
+    (0x2011_bab0, "exfat"),
+    (0xf2f5_2010, "f2fs"),
+];
+
+struct Options {
+    dereference: bool,
+    // Whether %N quotes, as it does in formats given on the command line
+    quote_names: bool,
+}
+
+fn main() {
+    let matches = Command::new("stat")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils stat - display file or file system status")
+        .arg(
+            Arg::new("dereference")
+                .short('L')
+                .long("dereference")
+                .action(ArgAction::SetTrue)
+                .help("Follow links"),
+        )
+        .arg(
+            Arg::new("file-system")
+                .short('f')
+                .long("file-system")
+                .action(ArgAction::SetTrue)
+                .help("Display file system status instead of file status"),
+        )
+        .arg(
+            Arg::new("format")
+                .short('c')
+                .long("format")
+                .value_name("FORMAT")
+                .help("Use the specified FORMAT instead of the default; output a newline after each use of FORMAT"),
+        )
+        .arg(
+            Arg::new("printf")
+                .long("printf")
+                .value_name("FORMAT")
+                .overrides_with("format")
+                .help("Like --format, but interpret backslash escapes, and do not output a mandatory trailing newline"),
+        )
+        .arg(
+            Arg::new("terse")
+                .short('t')
+                .long("terse")
+                .action(ArgAction::SetTrue)
+                .help("Print the information in terse form"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .num_args(0..)
+                .help("Files to describe; '-' is standard input"),
+        )
+        .after_help(
+            "Valid format sequences for files (without --file-system):\n\n  \
+             %a   permission bits in octal (see '#' and '0' printf flags)\n  \
+             %A   permission bits and file type in human readable form\n  \
+             %b   number of blocks allocated (see %B)\n  \
+             %B   the size in bytes of each block reported by %b\n  \
+             %d   device number in decimal (st_dev)\n  \
+             %D   device number in hex (st_dev)\n  \
+             %Hd  major device number in decimal\n  \
+             %Ld  minor device number in decimal\n  \
+             %f   raw mode in hex\n  \
+             %F   file type\n  \
+             %g   group ID of owner\n  \
+             %G   group name of owner\n  \
+             %h   number of hard links\n  \
+             %i   inode number\n  \
+             %m   mount point\n  \
+             %n   file name\n  \
+             %N   quoted file name with dereference if symbolic link\n  \
+             %o   optimal I/O transfer size hint\n  \
+             %r   device type in decimal (st_rdev)\n  \
+             %R   device type in hex (st_rdev)\n  \
+             %Hr  major device type in decimal, for character/block device special files\n  \
+             %Lr  minor device type in decimal, for character/block device special files\n  \
+             %s   total size, in bytes\n  \
+             %t   major device type in hex, for character/block device special files\n  \
+             %T   minor device type in hex, for character/block device special files\n  \
+             %u   user ID of owner\n  \
+             %U   user name of owner\n  \
+             %w   time of file birth, human-readable; - if unknown\n  \
+             %W   time of file birth, seconds since Epoch; 0 if unknown\n  \
+             %x   time of last access, human-readable\n  \
+             %X   time of last access, seconds since Epoch\n  \
+             %y   time of last data modification, human-readable\n  \
+             %Y   time of last data modification, seconds since Epoch\n  \
+             %z   time of last status change, human-readable\n  \
+             %Z   time of last status change, seconds since Epoch\n\n\
+             Valid format sequences for file systems:\n\n  \
+             %a   free blocks available to non-superuser\n  \
+             %b   total data blocks in file system\n  \
+             %c   total file nodes in file system\n  \
+             %d   free file nodes in file system\n  \
+             %f   free blocks in file system\n  \
+             %i   file system ID in hex\n  \
+             %l   maximum length of filenames\n  \
+             %n   file name\n  \
+             %s   block size (for faster transfers)\n  \
+             %S   fundamental block size (for block counts)\n  \
+             %t   file system type in hex\n  \
+             %T   file system type in human readable form\n\n\
+             A precision on %X, %Y, %Z or %W, as in '%.3Y', adds that many digits of\n\
+             the fraction of a second.",
+        )
+        .get_matches();
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("files")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+    if files.is_empty() {
+        usage_error("stat", "missing operand");
+    }
+
+    let file_system = matches.get_flag("file-system");
+    let terse = matches.get_flag("terse");
+    // The format, and whether a newline follows each use of it
+    let user_format = match (
+        matches.get_one::<String>("format"),
+        matches.get_one::<String>("printf"),
+    ) {
+        (_, Some(format)) => Some((format::unescape(format.as_bytes()), false)),
+        (Some(format), None) => Some((format.as_bytes().to_vec(), true)),
+        (None, None) => None,
+    };
+    let options = Options {
+        dereference: matches.get_flag("dereference"),
+        quote_names: user_format.is_some(),
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut ok = true;
+    for file in files {
+        let output = if file_system {
+            file_system_output(file, user_format.as_ref(), terse)
+        } else {
+            file_output(file, user_format.as_ref(), terse, &options)
+        };
+        match output {
+            Some(output) => {
+                if let Err(err) = out.write_all(&output).and_then(|()| out.flush()) {
+                    fail("stat", &format!("write error: {}", strerror(&err)));
+                }
+            }
+            None => ok = false,
+        }
+    }
+    process::exit(if ok { 0 } else { 1 });
+}
+
+// The format to use and whether a newline ends it
+fn chosen_format(
+    user_format: Option<&(Vec<u8>, bool)>,
+    terse: bool,
+    terse_format: &str,
+    default_format: impl FnOnce() -> String,
+) -> (Vec<u8>, bool) {
+    match user_format {
+        Some((format, newline)) => (format.clone(), *newline),
+        None if terse => (terse_format.as_bytes().to_vec(), true),
+        None => (default_format().into_bytes(), true),
+    }
+}
+
+fn file_output(
+    file: &str,
+    user_format: Option<&(Vec<u8>, bool)>,
+    terse: bool,
+    options: &Options,
+) -> Option<Vec<u8>> {
+    let result = if file == "-" {
+        // Standard input is whatever is open there, not a name to look up
+        let stdin = ManuallyDrop::new(unsafe { File::from_raw_fd(0) });
+        stdin.metadata()
+    } else {
+        metadata::metadata(file, options.dereference)
+    };
+    let metadata = match result {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            eprintln!("stat: cannot stat '{}': {}", file, strerror(&err));
+            return None;
+        }
+    };
+
+    let (format, newline) = chosen_format(user_format, terse, FILE_TERSE, || {
+        default_file_format(&metadata)
+    });
+    let path = Path::new(file);
+    let mut output = format::expand(&format, &mut |directive| {
+        file_value(directive, path, &metadata, options)
+    });
+    if newline {
+        output.push(b'\n');
+    }
+    Some(output)
+}
+
+fn default_file_format(metadata: &Metadata) -> String {
+    let file_type = metadata.file_type();
+    // Only devices have anything after the link count
+    let links = if file_type.is_char_device() || file_type.is_block_device() {
+        "%-5h Device type: %Hr,%Lr"
+    } else {
+        "%h"
+    };
+    format!(
+        "  File: %N\n  Size: %-10s\tBlocks: %-10b IO Block: %-6o %F\n\
+         Device: %Hd,%Ld\tInode: %-11i Links: {}\n\
+         Access: (%04a/%10.10A)  Uid: (%5u/%8U)   Gid: (%5g/%8G)\n\
+         Access: %x\nModify: %y\nChange: %z\n Birth: %w",
+        links
+    )
+}
+
+fn file_value(
+    directive: &str,
+    path: &Path,
+    metadata: &Metadata,
+    options: &Options,
+) -> Option<Value> {
+    let name = path.as_os_str().as_bytes();
+    let value = match directive {
+        "a" => Value::Octal(u64::from(metadata.mode() & 0o7777)),
+        "A" => Value::Text(mode_string(metadata.mode()).into_bytes()),
+        "b" => Value::Unsigned(metadata.blocks()),
+        "B" => Value::Unsigned(512),
+        "d" => Value::Unsigned(metadata.dev()),
+        "D" => Value::Hex(metadata.dev()),
+        "Hd" => Value::Unsigned(u64::from(libc::major(metadata.dev()))),
+        "Ld" => Value::Unsigned(u64::from(libc::minor(metadata.dev()))),
+        "f" => Value::Hex(u64::from(metadata.mode())),
+        "F" => Value::Text(file_type(metadata).as_bytes().to_vec()),
+        "g" => Value::Unsigned(u64::from(metadata.gid())),
+        "G" => Value::Text(group_name(metadata.gid()).into_bytes()),
+        "h" => Value::Unsigned(metadata.nlink()),
+        "i" => Value::Unsigned(metadata.ino()),
+        "m" => Value::Text(
+            mount_point(path, metadata)
+                .into_os_string()
+                .into_encoded_bytes(),
+        ),
+        "n" => Value::Text(name.to_vec()),
+        "N" => Value::Text(quoted_name(path, metadata, options)),
+        "o" => Value::Unsigned(metadata.blksize()),
+        "r" => Value::Unsigned(metadata.rdev()),
+        "R" => Value::Hex(metadata.rdev()),
+        "Hr" => Value::Unsigned(u64::from(libc::major(metadata.rdev()))),
+        "Lr" => Value::Unsigned(u64::from(libc::minor(metadata.rdev()))),
+        "s" => Value::Unsigned(metadata.size()),
+        "t" => Value::Hex(u64::from(libc::major(metadata.rdev()))),
+        "T" => Value::Hex(u64::from(libc::minor(metadata.rdev()))),
+        "u" => Value::Unsigned(u64::from(metadata.uid())),
+        "U" => Value::Text(user_name(metadata.uid()).into_bytes()),
+        "w" => match birth_time(metadata) {
+            Some((seconds, nanoseconds)) => {
+                Value::Text(format_time(seconds, nanoseconds).into_bytes())
+            }
+            None => Value::Text(b"-".to_vec()),
+        },
+        "W" => {
+            let (seconds, nanoseconds) = birth_time(metadata).unwrap_or((0, 0));
+            Value::Time(seconds, nanoseconds)
+        }
+        "x" => {
+            Value::Text(format_time(metadata.atime(), metadata.atime_nsec() as u32).into_bytes())
+        }
+        "X" => Value::Time(metadata.atime(), metadata.atime_nsec() as u32),
+        "y" => {
+            Value::Text(format_time(metadata.mtime(), metadata.mtime_nsec() as u32).into_bytes())
+        }
+        "Y" => Value::Time(metadata.mtime(), metadata.mtime_nsec() as u32),
+        "z" => {
+            Value::Text(format_time(metadata.ctime(), metadata.ctime_nsec() as u32).into_bytes())
+        }
+        "Z" => Value::Time(metadata.ctime(), metadata.ctime_nsec() as u32),
+        _ => return None,
+    };
+    Some(value)
+}
+
+// The name, and for a symbolic link its target, quoted for the shell when
+// the format was given; the default format shows them as they are
+fn quoted_name(path: &Path, metadata: &Metadata, options: &Options) -> Vec<u8> {
+    let quote = |text: &[u8]| {
+        if options.quote_names {
+            shell_quote(text)
+        } else {
+            text.to_vec()
+        }
+    };
+
+    let mut text = quote(path.as_os_str().as_bytes());
+    if metadata.file_type().is_symlink() {
+        if let Ok(target) = fs::read_link(path) {
+            text.extend_from_slice(b" -> ");
+            text.extend(quote(target.as_os_str().as_bytes()));
+        }
+    }
+    text
+}
+
+fn shell_quote(text: &[u8]) -> Vec<u8> {
+    if !text.contains(&b'\'') {
+        return [b"'", text, b"'"].concat();
+    }
+    if !text.iter().any(|c| b"\"$`\\!".contains(c)) {
+        return [b"\"", text, b"\""].concat();
+    }
+    let mut quoted = b"'".to_vec();
+    for &c in text {
+        if c == b'\'' {
+            quoted.extend_from_slice(b"'\\''");
+        } else {
+            quoted.push(c);
+        }
+    }
+    quoted.push(b'\'');
+    quoted
+}
+
+// The directory the file system holding PATH is mounted on: the last one
+// going up from it that is still on the same device
+fn mount_point(path: &Path, metadata: &Metadata) -> PathBuf {
+    let Ok(mut current) = fs::canonicalize(path) else {
+        return PathBuf::from("?");
+    };
+    while let Some(parent) = current.parent() {
+        match fs::metadata(parent) {
+            Ok(parent_metadata) if parent_metadata.dev() == metadata.dev() => {
+                current = parent.to_path_buf();
+            }
+            _ => break,
+        }
+    }
+    current
+}
+
+fn birth_time(metadata: &Metadata) -> Option<(i64, u32)> {
+    let since = metadata.created().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((since.as_secs() as i64, since.subsec_nanos()))
+}
+
+// "2025-01-31 12:00:00.123456789 +0100", in local time
+fn format_time(seconds: i64, nanoseconds: u32) -> String {
+    let mut tm: libc::tm = unsafe { mem::zeroed() };
+    let time = seconds as libc::time_t;
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return format!("{}.{:09}", seconds, nanoseconds);
+    }
+    let offset = tm.tm_gmtoff / 60;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:09} {}{:02}{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec,
+        nanoseconds,
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
+
+// The user's name, or UNKNOWN when the ID has none
+fn user_name(uid: libc::uid_t) -> String {
+    metadata::user_name(uid).unwrap_or_else(|| "UNKNOWN".to_string())
+}
+
+fn group_name(gid: libc::gid_t) -> String {
+    metadata::group_name(gid).unwrap_or_else(|| "UNKNOWN".to_string())
+}
+
+fn file_system_output(
+    file: &str,
+    user_format: Option<&(Vec<u8>, bool)>,
+    terse: bool,
+) -> Option<Vec<u8>> {
+    let mut info: libc::statfs = unsafe { mem::zeroed() };
+    let result = if file == "-" {
+        unsafe { libc::fstatfs(0, &mut info) }
+    } else {
+        match CString::new(OsStr::new(file).as_bytes()) {
+            Ok(path) => unsafe { libc::statfs(path.as_ptr(), &mut info) },
+            Err(_) => {
+                unsafe { *libc::__errno_location() = libc::EINVAL };
+                -1
+            }
+        }
+    };
+    if result != 0 {
+        eprintln!(
+            "stat: cannot read file system information for '{}': {}",
+            file,
+            strerror(&io::Error::last_os_error())
+        );
+        return None;
+    }
+
+    let (format, newline) = chosen_format(user_format, terse, FILE_SYSTEM_TERSE, || {
+        FILE_SYSTEM_FORMAT.to_string()
+    });
+    let mut output = format::expand(&format, &mut |directive| {
+        file_system_value(directive, file, &info)
+    });
+    if newline {
+        output.push(b'\n');
+    }
+    Some(output)
+}
+
+fn file_system_value(directive: &str, file: &str, info: &libc::statfs) -> Option<Value> {
+    let value = match directive {
+        "a" => Value::Unsigned(info.f_bavail),
+        "b" => Value::Unsigned(info.f_blocks),
+        "c" => Value::Unsigned(info.f_files),
+        "d" => Value::Unsigned(info.f_ffree),
+        "f" => Value::Unsigned(info.f_bfree),
+        "i" => {
+            // The two words of the ID, the first one high, as GNU shows it
+            let words: [u32; 2] = unsafe { mem::transmute(info.f_fsid) };
+            Value::Hex((u64::from(words[0]) << 32) | u64::from(words[1]))
+        }
+        "l" => Value::Unsigned(info.f_namelen as u64),
+        "n" => Value::Text(file.as_bytes().to_vec()),
+        "s" => Value::Unsigned(info.f_bsize as u64),
+        "S" => Value::Unsigned(info.f_frsize as u64),
+        "t" => Value::Hex(info.f_type as u64),
+        "T" => {
+            let magic = info.f_type as u64;
+            let name = FILE_SYSTEM_TYPES
+                .iter()
+                .find(|&&(known, _)| known == magic)
+                .map_or_else(
+                    || format!("UNKNOWN (0x{:x})", magic),
+                    |(_, name)| name.to_string(),
+                );
+            Value::Text(name.into_bytes())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
@@ -0,0 +1,55 @@
+// ASD CoreUtils - libstdbuf, the preload shim used by stdbuf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// Loaded into the target command through LD_PRELOAD; before main() runs it
+// reads the _STDBUF_I, _STDBUF_O and _STDBUF_E variables set by stdbuf and
+// applies them to the C library's standard streams with setvbuf(3)
+
+use std::env;
+use std::ptr;
+
+extern "C" {
+    static mut stdin: *mut libc::FILE;
+    static mut stdout: *mut libc::FILE;
+    static mut stderr: *mut libc::FILE;
+}
+
+#[used]
+#[link_section = ".init_array"]
+static INIT: extern "C" fn() = apply_buffering;
+
+extern "C" fn apply_buffering() {
+    unsafe {
+        configure(stdin, "_STDBUF_I", "stdin");
+        configure(stdout, "_STDBUF_O", "stdout");
+        configure(stderr, "_STDBUF_E", "stderr");
+    }
+}
+
+unsafe fn configure(stream: *mut libc::FILE, variable: &str, name: &str) {
+    let Ok(mode) = env::var(variable) else {
+        return;
+    };
+
+    let result = match mode.as_str() {
+        "0" => libc::setvbuf(stream, ptr::null_mut(), libc::_IONBF, 0),
+        "L" => libc::setvbuf(stream, ptr::null_mut(), libc::_IOLBF, 0),
+        size => match size.parse::<usize>() {
+            // The buffer lives as long as the stream, i.e. the process
+            Ok(size) if size > 0 => {
+                let buffer = libc::malloc(size) as *mut libc::c_char;
+                if buffer.is_null() {
+                    -1
+                } else {
+                    libc::setvbuf(stream, buffer, libc::_IOFBF, size)
+                }
+            }
+            _ => -1,
+        },
+    };
+
+    if result != 0 {
+        eprintln!("stdbuf: could not set buffering of {} to mode {}", name, mode);
+    }
+}
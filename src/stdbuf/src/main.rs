@@ -0,0 +1,139 @@
+// ASD CoreUtils - stdbuf
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail_status, strerror, usage_error_status};
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, Command};
+use std::env;
+use std::ffi::OsString;
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process;
+
+const SHIM_NAME: &str = "libstdbuf.so";
+
+// Exit statuses shared with env, nice and timeout
+const EXIT_FAILURE: i32 = 125;
+const EXIT_CANNOT_INVOKE: i32 = 126;
+const EXIT_ENOENT: i32 = 127;
+
+fn main() {
+    let matches = Command::new("stdbuf")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils stdbuf - run COMMAND with modified buffering operations for its standard streams")
+        .after_help(
+            "If MODE is 'L' the corresponding stream will be line buffered.\n\
+             This option is invalid with standard input.\n\n\
+             If MODE is '0' the corresponding stream will be unbuffered.\n\n\
+             Otherwise MODE is a number which may be followed by one of the following:\n\
+             KB 1000, K 1024, MB 1000*1000, M 1024*1024, and so on for G, T, P, E.\n\
+             In this case the corresponding stream will be fully buffered with the buffer\n\
+             size set to MODE bytes.",
+        )
+        .trailing_var_arg(true)
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("MODE")
+                .help("Adjust standard input stream buffering"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("MODE")
+                .help("Adjust standard output stream buffering"),
+        )
+        .arg(
+            Arg::new("error")
+                .short('e')
+                .long("error")
+                .value_name("MODE")
+                .help("Adjust standard error stream buffering"),
+        )
+        .arg(
+            Arg::new("command")
+                .value_name("COMMAND")
+                .help("Command to run, followed by its arguments")
+                .required(true)
+                .num_args(1..)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .try_get_matches()
+        .unwrap_or_else(|err| {
+            // Usage errors exit with 125 so they can't be mistaken for the
+            // command's own status
+            let code = if err.use_stderr() { EXIT_FAILURE } else { 0 };
+            let _ = err.print();
+            process::exit(code);
+        });
+
+    let mut any = false;
+    for (id, variable) in [("input", "_STDBUF_I"), ("output", "_STDBUF_O"), ("error", "_STDBUF_E")] {
+        let Some(mode) = matches.get_one::<String>(id) else {
+            continue;
+        };
+        if id == "input" && mode == "L" {
+            fail_status("stdbuf", EXIT_FAILURE, "line buffering stdin is meaningless");
+        }
+        let value = parse_mode(mode).unwrap_or_else(|| {
+            fail_status("stdbuf", EXIT_FAILURE, &format!("invalid mode '{}'", mode))
+        });
+        env::set_var(variable, value);
+        any = true;
+    }
+
+    if !any {
+        usage_error_status("stdbuf", EXIT_FAILURE, "you must specify a buffering mode option");
+    }
+
+    let shim = find_shim().unwrap_or_else(|| {
+        fail_status("stdbuf", EXIT_FAILURE, &format!("failed to find {}", SHIM_NAME))
+    });
+
+    // Keep any preload the caller already had, after ours
+    let mut preload = OsString::from(shim.as_os_str());
+    if let Some(existing) = env::var_os("LD_PRELOAD").filter(|value| !value.is_empty()) {
+        preload.push(":");
+        preload.push(existing);
+    }
+
+    let mut command = matches.get_many::<OsString>("command").unwrap();
+    let program = command.next().unwrap();
+
+    let err = process::Command::new(program).args(command).env("LD_PRELOAD", preload).exec();
+
+    let code = if err.kind() == io::ErrorKind::NotFound {
+        EXIT_ENOENT
+    } else {
+        EXIT_CANNOT_INVOKE
+    };
+    eprintln!("stdbuf: failed to run command '{}': {}", program.to_string_lossy(), strerror(&err));
+    process::exit(code);
+}
+
+// Normalizes MODE to what the shim understands: "0", "L" or a byte count
+fn parse_mode(mode: &str) -> Option<String> {
+    if mode == "L" || mode == "0" {
+        return Some(mode.to_string());
+    }
+
+    let size = parse_size(mode, &[])?;
+    usize::try_from(size).ok().filter(|&size| size > 0).map(|size| size.to_string())
+}
+
+// The shim is installed alongside the stdbuf binary, or in the matching
+// lib directory of an installation prefix
+fn find_shim() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?;
+    let dir = exe.parent()?;
+
+    [dir.join(SHIM_NAME), dir.join("../lib").join(SHIM_NAME), dir.join("../libexec/coreutils").join(SHIM_NAME)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+}
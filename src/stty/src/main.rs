@@ -0,0 +1,709 @@
+// ASD CoreUtils - stty
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use std::env;
+use std::ffi::CString;
+use std::io;
+use std::mem;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Group {
+    Control,
+    Input,
+    Output,
+    Local,
+}
+
+// What the "sane" preset does with a mode
+#[derive(Clone, Copy, PartialEq)]
+enum Sane {
+    Set,
+    Unset,
+    Keep,
+}
+
+struct Mode {
+    name: &'static str,
+    group: Group,
+    // Bits the mode occupies, and the value they hold when it is on; for a
+    // plain flag the two are the same, for fields such as cs8 they differ
+    mask: libc::tcflag_t,
+    value: libc::tcflag_t,
+    // Whether "-name" is accepted and printed
+    reversible: bool,
+    sane: Sane,
+}
+
+const fn flag(name: &'static str, group: Group, bit: libc::tcflag_t, sane: Sane) -> Mode {
+    Mode {
+        name,
+        group,
+        mask: bit,
+        value: bit,
+        reversible: true,
+        sane,
+    }
+}
+
+const fn field(name: &'static str, group: Group, mask: libc::tcflag_t, value: libc::tcflag_t, sane: Sane) -> Mode {
+    Mode {
+        name,
+        group,
+        mask,
+        value,
+        reversible: false,
+        sane,
+    }
+}
+
+// In the order stty -a prints them
+const MODES: &[Mode] = &[
+    flag("parenb", Group::Control, libc::PARENB, Sane::Keep),
+    flag("parodd", Group::Control, libc::PARODD, Sane::Keep),
+    flag("cmspar", Group::Control, libc::CMSPAR, Sane::Keep),
+    field("cs5", Group::Control, libc::CSIZE, libc::CS5, Sane::Keep),
+    field("cs6", Group::Control, libc::CSIZE, libc::CS6, Sane::Keep),
+    field("cs7", Group::Control, libc::CSIZE, libc::CS7, Sane::Keep),
+    field("cs8", Group::Control, libc::CSIZE, libc::CS8, Sane::Keep),
+    flag("hupcl", Group::Control, libc::HUPCL, Sane::Keep),
+    flag("cstopb", Group::Control, libc::CSTOPB, Sane::Keep),
+    flag("cread", Group::Control, libc::CREAD, Sane::Set),
+    flag("clocal", Group::Control, libc::CLOCAL, Sane::Keep),
+    flag("crtscts", Group::Control, libc::CRTSCTS, Sane::Keep),
+    flag("ignbrk", Group::Input, libc::IGNBRK, Sane::Unset),
+    flag("brkint", Group::Input, libc::BRKINT, Sane::Set),
+    flag("ignpar", Group::Input, libc::IGNPAR, Sane::Keep),
+    flag("parmrk", Group::Input, libc::PARMRK, Sane::Keep),
+    flag("inpck", Group::Input, libc::INPCK, Sane::Keep),
+    flag("istrip", Group::Input, libc::ISTRIP, Sane::Keep),
+    flag("inlcr", Group::Input, libc::INLCR, Sane::Unset),
+    flag("igncr", Group::Input, libc::IGNCR, Sane::Unset),
+    flag("icrnl", Group::Input, libc::ICRNL, Sane::Set),
+    flag("ixon", Group::Input, libc::IXON, Sane::Keep),
+    flag("ixoff", Group::Input, libc::IXOFF, Sane::Unset),
+    flag("iuclc", Group::Input, libc::IUCLC, Sane::Unset),
+    flag("ixany", Group::Input, libc::IXANY, Sane::Unset),
+    flag("imaxbel", Group::Input, libc::IMAXBEL, Sane::Set),
+    flag("iutf8", Group::Input, libc::IUTF8, Sane::Keep),
+    flag("opost", Group::Output, libc::OPOST, Sane::Set),
+    flag("olcuc", Group::Output, libc::OLCUC, Sane::Unset),
+    flag("ocrnl", Group::Output, libc::OCRNL, Sane::Unset),
+    flag("onlcr", Group::Output, libc::ONLCR, Sane::Set),
+    flag("onocr", Group::Output, libc::ONOCR, Sane::Unset),
+    flag("onlret", Group::Output, libc::ONLRET, Sane::Unset),
+    flag("ofill", Group::Output, libc::OFILL, Sane::Unset),
+    flag("ofdel", Group::Output, libc::OFDEL, Sane::Unset),
+    field("nl0", Group::Output, libc::NLDLY, libc::NL0, Sane::Set),
+    field("nl1", Group::Output, libc::NLDLY, libc::NL1, Sane::Keep),
+    field("cr0", Group::Output, libc::CRDLY, libc::CR0, Sane::Set),
+    field("cr1", Group::Output, libc::CRDLY, libc::CR1, Sane::Keep),
+    field("cr2", Group::Output, libc::CRDLY, libc::CR2, Sane::Keep),
+    field("cr3", Group::Output, libc::CRDLY, libc::CR3, Sane::Keep),
+    field("tab0", Group::Output, libc::TABDLY, libc::TAB0, Sane::Set),
+    field("tab1", Group::Output, libc::TABDLY, libc::TAB1, Sane::Keep),
+    field("tab2", Group::Output, libc::TABDLY, libc::TAB2, Sane::Keep),
+    field("tab3", Group::Output, libc::TABDLY, libc::TAB3, Sane::Keep),
+    field("bs0", Group::Output, libc::BSDLY, libc::BS0, Sane::Set),
+    field("bs1", Group::Output, libc::BSDLY, libc::BS1, Sane::Keep),
+    field("vt0", Group::Output, libc::VTDLY, libc::VT0, Sane::Set),
+    field("vt1", Group::Output, libc::VTDLY, libc::VT1, Sane::Keep),
+    field("ff0", Group::Output, libc::FFDLY, libc::FF0, Sane::Set),
+    field("ff1", Group::Output, libc::FFDLY, libc::FF1, Sane::Keep),
+    flag("isig", Group::Local, libc::ISIG, Sane::Set),
+    flag("icanon", Group::Local, libc::ICANON, Sane::Set),
+    flag("iexten", Group::Local, libc::IEXTEN, Sane::Set),
+    flag("echo", Group::Local, libc::ECHO, Sane::Set),
+    flag("echoe", Group::Local, libc::ECHOE, Sane::Set),
+    flag("echok", Group::Local, libc::ECHOK, Sane::Set),
+    flag("echonl", Group::Local, libc::ECHONL, Sane::Unset),
+    flag("noflsh", Group::Local, libc::NOFLSH, Sane::Unset),
+    flag("xcase", Group::Local, libc::XCASE, Sane::Unset),
+    flag("tostop", Group::Local, libc::TOSTOP, Sane::Unset),
+    flag("echoprt", Group::Local, libc::ECHOPRT, Sane::Unset),
+    flag("echoctl", Group::Local, libc::ECHOCTL, Sane::Set),
+    flag("echoke", Group::Local, libc::ECHOKE, Sane::Set),
+    flag("flusho", Group::Local, libc::FLUSHO, Sane::Unset),
+    flag("extproc", Group::Local, libc::EXTPROC, Sane::Unset),
+];
+
+// Special characters with the values "sane" restores
+const CONTROL_CHARS: &[(&str, usize, libc::cc_t)] = &[
+    ("intr", libc::VINTR, 3),
+    ("quit", libc::VQUIT, 28),
+    ("erase", libc::VERASE, 127),
+    ("kill", libc::VKILL, 21),
+    ("eof", libc::VEOF, 4),
+    ("eol", libc::VEOL, 0),
+    ("eol2", libc::VEOL2, 0),
+    ("swtch", libc::VSWTC, 0),
+    ("start", libc::VSTART, 17),
+    ("stop", libc::VSTOP, 19),
+    ("susp", libc::VSUSP, 26),
+    ("rprnt", libc::VREPRINT, 18),
+    ("werase", libc::VWERASE, 23),
+    ("lnext", libc::VLNEXT, 22),
+    ("discard", libc::VDISCARD, 15),
+];
+
+const RAW_SETTINGS: &[&str] = &[
+    "-ignbrk", "-brkint", "-ignpar", "-parmrk", "-inpck", "-istrip", "-inlcr", "-igncr", "-icrnl", "-ixon", "-ixoff",
+    "-icanon", "-opost", "-isig", "-iuclc", "-ixany", "-imaxbel", "-xcase",
+];
+
+const COOKED_SETTINGS: &[&str] = &["brkint", "ignpar", "istrip", "icrnl", "ixon", "opost", "isig", "icanon"];
+
+const SPEEDS: &[(u32, libc::speed_t)] = &[
+    (0, libc::B0),
+    (50, libc::B50),
+    (75, libc::B75),
+    (110, libc::B110),
+    (134, libc::B134),
+    (150, libc::B150),
+    (200, libc::B200),
+    (300, libc::B300),
+    (600, libc::B600),
+    (1200, libc::B1200),
+    (1800, libc::B1800),
+    (2400, libc::B2400),
+    (4800, libc::B4800),
+    (9600, libc::B9600),
+    (19200, libc::B19200),
+    (38400, libc::B38400),
+    (57600, libc::B57600),
+    (115200, libc::B115200),
+    (230400, libc::B230400),
+    (460800, libc::B460800),
+    (500000, libc::B500000),
+    (576000, libc::B576000),
+    (921600, libc::B921600),
+    (1000000, libc::B1000000),
+    (1152000, libc::B1152000),
+    (1500000, libc::B1500000),
+    (2000000, libc::B2000000),
+    (2500000, libc::B2500000),
+    (3000000, libc::B3000000),
+    (3500000, libc::B3500000),
+    (4000000, libc::B4000000),
+];
+
+// Queries among the settings, answered once the changes are made
+enum Report {
+    Size,
+    Speed,
+}
+
+// Everything the operands ask for, applied to the terminal in one go
+struct Changes {
+    termios: libc::termios,
+    rows: Option<u16>,
+    columns: Option<u16>,
+    reports: Vec<Report>,
+}
+
+fn main() {
+    let matches = Command::new("stty")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils stty - print or change terminal characteristics")
+        .after_help(
+            "Settings:\n  \
+             N               set the input and output speeds to N bauds\n  \
+             ispeed N        set the input speed to N\n  \
+             ospeed N        set the output speed to N\n  \
+             rows N          tell the kernel that the terminal has N rows\n  \
+             cols N          tell the kernel that the terminal has N columns\n  \
+             min N           with -icanon, read at least N characters\n  \
+             time N          with -icanon, time out reads after N tenths of a second\n  \
+             [-]FLAG         set or clear a mode such as echo, icanon, isig or onlcr\n  \
+             raw             same as -ignbrk -brkint -ignpar -parmrk -inpck -istrip\n                  \
+             -inlcr -igncr -icrnl -ixon -ixoff -icanon -opost -isig\n                  \
+             -iuclc -ixany -imaxbel -xcase min 1 time 0\n  \
+             -raw, cooked    same as brkint ignpar istrip icrnl ixon opost isig icanon\n  \
+             sane            reset the modes and special characters to reasonable values\n  \
+             size            print the number of rows and columns of the terminal\n  \
+             speed           print the terminal speed\n\n\
+             The output of -g may be given as a setting to restore what it saved.",
+        )
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .help("Print all current settings in human-readable form")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("save")
+                .short('g')
+                .long("save")
+                .help("Print all current settings in a stty-readable form")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .short('F')
+                .long("file")
+                .value_name("DEVICE")
+                .help("Open and use the specified DEVICE instead of stdin"),
+        )
+        .arg(
+            Arg::new("settings")
+                .value_name("SETTING")
+                .help("Settings to apply")
+                .num_args(0..)
+                .allow_hyphen_values(true),
+        )
+        .get_matches();
+
+    let settings: Vec<&String> = matches
+        .get_many::<String>("settings")
+        .map(|values| values.collect())
+        .unwrap_or_default();
+
+    let save = matches.get_flag("save");
+    if matches.get_flag("all") && save {
+        fail("stty", "the options for verbose and stty-readable output styles are\nmutually exclusive");
+    }
+    if (matches.get_flag("all") || save) && !settings.is_empty() {
+        fail("stty", "when specifying an output style, modes may not be set");
+    }
+
+    let (fd, device) = match matches.get_one::<String>("file") {
+        Some(path) => (open_device(path), path.clone()),
+        None => (libc::STDIN_FILENO, "'standard input'".to_string()),
+    };
+
+    let termios = get_termios(fd)
+        .unwrap_or_else(|err| fail("stty", &format!("{}: {}", device, strerror(&err))));
+
+    if save {
+        println!("{}", saved_form(&termios));
+        return;
+    }
+    if settings.is_empty() {
+        display(fd, &termios, matches.get_flag("all"));
+        return;
+    }
+
+    let mut changes = Changes {
+        termios,
+        rows: None,
+        columns: None,
+        reports: Vec::new(),
+    };
+    if let Err(message) = parse_settings(&settings, &mut changes) {
+        usage_error("stty", &message);
+    }
+
+    if let Err(err) = apply(fd, &changes) {
+        fail("stty", &format!("{}: {}", device, strerror(&err)));
+    }
+
+    for report in &changes.reports {
+        match report {
+            Report::Size => {
+                let size = get_window_size(fd).unwrap_or_else(|err| {
+                    fail("stty", &format!("{}: {}", device, strerror(&err)))
+                });
+                println!("{} {}", size.ws_row, size.ws_col);
+            }
+            Report::Speed => {
+                let ispeed = baud_rate(unsafe { libc::cfgetispeed(&changes.termios) });
+                let ospeed = baud_rate(unsafe { libc::cfgetospeed(&changes.termios) });
+                if ispeed == ospeed || ispeed == 0 {
+                    println!("{}", ospeed);
+                } else {
+                    println!("{} {}", ispeed, ospeed);
+                }
+            }
+        }
+    }
+}
+
+// Opening a serial line must not block waiting for carrier, but reads
+// afterwards should behave normally again
+fn open_device(path: &str) -> libc::c_int {
+    let c_path = CString::new(path)
+        .unwrap_or_else(|_| fail("stty", &format!("'{}': invalid file name", path)));
+
+    let fd = unsafe { libc::open(c_path.as_ptr(), libc::O_RDONLY | libc::O_NONBLOCK) };
+    if fd < 0 {
+        let err = io::Error::last_os_error();
+        fail("stty", &format!("{}: {}", path, strerror(&err)));
+    }
+
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags < 0 || libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) < 0 {
+            let err = io::Error::last_os_error();
+            fail("stty", &format!("{}: {}", path, strerror(&err)));
+        }
+    }
+    fd
+}
+
+fn get_termios(fd: libc::c_int) -> io::Result<libc::termios> {
+    let mut termios: libc::termios = unsafe { mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut termios) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(termios)
+}
+
+fn get_window_size(fd: libc::c_int) -> io::Result<libc::winsize> {
+    let mut size: libc::winsize = unsafe { mem::zeroed() };
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(size)
+}
+
+fn parse_settings(settings: &[&String], changes: &mut Changes) -> Result<(), String> {
+    let mut iter = settings.iter();
+
+    while let Some(setting) = iter.next() {
+        let setting = setting.as_str();
+        let mut argument = || {
+            iter.next()
+                .map(|value| value.as_str())
+                .ok_or_else(|| format!("missing argument to '{}'", setting))
+        };
+
+        match setting {
+            "rows" => changes.rows = Some(parse_integer(argument()?)),
+            "cols" | "columns" => changes.columns = Some(parse_integer(argument()?)),
+            "ispeed" => set_speed(&mut changes.termios, argument()?, true, false)?,
+            "ospeed" => set_speed(&mut changes.termios, argument()?, false, true)?,
+            "min" => changes.termios.c_cc[libc::VMIN] = parse_integer(argument()?),
+            "time" => changes.termios.c_cc[libc::VTIME] = parse_integer(argument()?),
+            "raw" => {
+                for name in RAW_SETTINGS {
+                    set_mode(&mut changes.termios, name);
+                }
+                changes.termios.c_cc[libc::VMIN] = 1;
+                changes.termios.c_cc[libc::VTIME] = 0;
+            }
+            "cooked" | "-raw" => {
+                for name in COOKED_SETTINGS {
+                    set_mode(&mut changes.termios, name);
+                }
+            }
+            "sane" => make_sane(&mut changes.termios),
+            "size" => changes.reports.push(Report::Size),
+            "speed" => changes.reports.push(Report::Speed),
+            _ if setting.bytes().all(|b| b.is_ascii_digit()) => {
+                set_speed(&mut changes.termios, setting, true, true)?
+            }
+            _ => {
+                if !set_mode(&mut changes.termios, setting) && !restore(&mut changes.termios, setting) {
+                    return Err(format!("invalid argument '{}'", setting));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Bad numbers are reported without the usage hint, like GNU stty
+fn parse_integer<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| fail("stty", &format!("invalid integer argument: '{}'", value)))
+}
+
+fn set_speed(termios: &mut libc::termios, value: &str, input: bool, output: bool) -> Result<(), String> {
+    let speed = value
+        .parse::<u32>()
+        .ok()
+        .and_then(|baud| SPEEDS.iter().find(|(rate, _)| *rate == baud))
+        .map(|(_, speed)| *speed)
+        .ok_or_else(|| format!("invalid argument '{}'", value))?;
+
+    unsafe {
+        if input {
+            libc::cfsetispeed(termios, speed);
+        }
+        if output {
+            libc::cfsetospeed(termios, speed);
+        }
+    }
+    Ok(())
+}
+
+fn flags_mut(termios: &mut libc::termios, group: Group) -> &mut libc::tcflag_t {
+    match group {
+        Group::Control => &mut termios.c_cflag,
+        Group::Input => &mut termios.c_iflag,
+        Group::Output => &mut termios.c_oflag,
+        Group::Local => &mut termios.c_lflag,
+    }
+}
+
+fn flags(termios: &libc::termios, group: Group) -> libc::tcflag_t {
+    match group {
+        Group::Control => termios.c_cflag,
+        Group::Input => termios.c_iflag,
+        Group::Output => termios.c_oflag,
+        Group::Local => termios.c_lflag,
+    }
+}
+
+// Applies "name" or "-name"; returns false for anything that isn't a mode
+fn set_mode(termios: &mut libc::termios, setting: &str) -> bool {
+    let (name, clear) = match setting.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (setting, false),
+    };
+
+    let Some(mode) = MODES.iter().find(|mode| mode.name == name) else {
+        return false;
+    };
+    if clear && !mode.reversible {
+        return false;
+    }
+
+    let bits = flags_mut(termios, mode.group);
+    *bits &= !mode.mask;
+    if !clear {
+        *bits |= mode.value;
+    }
+    true
+}
+
+// The settings as -g prints them: the input, output, control and local
+// flags, then every special character, all in hexadecimal
+fn saved_form(termios: &libc::termios) -> String {
+    let mut text = format!(
+        "{:x}:{:x}:{:x}:{:x}",
+        termios.c_iflag, termios.c_oflag, termios.c_cflag, termios.c_lflag
+    );
+    for c in termios.c_cc {
+        text.push_str(&format!(":{:x}", c));
+    }
+    text
+}
+
+// Takes back settings saved by -g; returns false if SETTING isn't in that
+// form. The speeds are kept in the control flags and follow from them
+fn restore(termios: &mut libc::termios, setting: &str) -> bool {
+    let fields: Vec<&str> = setting.split(':').collect();
+    if fields.len() != 4 + libc::NCCS {
+        return false;
+    }
+    let Ok(flags) = fields[..4]
+        .iter()
+        .map(|field| libc::tcflag_t::from_str_radix(field, 16))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    let Ok(chars) = fields[4..]
+        .iter()
+        .map(|field| libc::cc_t::from_str_radix(field, 16))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+
+    termios.c_iflag = flags[0];
+    termios.c_oflag = flags[1];
+    termios.c_cflag = flags[2];
+    termios.c_lflag = flags[3];
+    termios.c_cc.copy_from_slice(&chars);
+    unsafe {
+        let speed = termios.c_cflag & libc::CBAUD;
+        libc::cfsetospeed(termios, speed);
+        libc::cfsetispeed(termios, speed);
+    }
+    true
+}
+
+fn make_sane(termios: &mut libc::termios) {
+    for mode in MODES {
+        let bits = flags_mut(termios, mode.group);
+        match mode.sane {
+            Sane::Set => *bits = (*bits & !mode.mask) | mode.value,
+            Sane::Unset => *bits &= !mode.mask,
+            Sane::Keep => {}
+        }
+    }
+
+    for &(_, index, value) in CONTROL_CHARS {
+        termios.c_cc[index] = value;
+    }
+    termios.c_cc[libc::VMIN] = 1;
+    termios.c_cc[libc::VTIME] = 0;
+}
+
+fn apply(fd: libc::c_int, changes: &Changes) -> io::Result<()> {
+    if unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, &changes.termios) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    if changes.rows.is_some() || changes.columns.is_some() {
+        let mut size = get_window_size(fd)?;
+        if let Some(rows) = changes.rows {
+            size.ws_row = rows;
+        }
+        if let Some(columns) = changes.columns {
+            size.ws_col = columns;
+        }
+        if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ, &size) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    // tcsetattr succeeds if any of the changes took, so read the settings
+    // back to find out whether the driver accepted all of them
+    let actual = get_termios(fd)?;
+    let wanted = &changes.termios;
+    let same = actual.c_iflag == wanted.c_iflag
+        && actual.c_oflag == wanted.c_oflag
+        && actual.c_cflag == wanted.c_cflag
+        && actual.c_lflag == wanted.c_lflag
+        && actual.c_cc == wanted.c_cc
+        && actual.c_ispeed == wanted.c_ispeed
+        && actual.c_ospeed == wanted.c_ospeed;
+
+    if !same {
+        return Err(io::Error::other("unable to perform all requested operations"));
+    }
+    Ok(())
+}
+
+// Collects space-separated items, wrapping before the terminal width
+struct Wrapper {
+    width: usize,
+    line: String,
+}
+
+impl Wrapper {
+    fn push(&mut self, item: &str) {
+        if !self.line.is_empty() {
+            if self.line.len() + 1 + item.len() > self.width {
+                println!("{}", self.line);
+                self.line.clear();
+            } else {
+                self.line.push(' ');
+            }
+        }
+        self.line.push_str(item);
+    }
+
+    fn finish_line(&mut self) {
+        if !self.line.is_empty() {
+            println!("{}", self.line);
+            self.line.clear();
+        }
+    }
+}
+
+fn display(fd: libc::c_int, termios: &libc::termios, all: bool) {
+    let size = get_window_size(fd).ok();
+    let width = output_width();
+    let mut out = Wrapper {
+        width,
+        line: String::new(),
+    };
+
+    let ispeed = baud_rate(unsafe { libc::cfgetispeed(termios) });
+    let ospeed = baud_rate(unsafe { libc::cfgetospeed(termios) });
+    if ispeed == ospeed || ispeed == 0 {
+        out.push(&format!("speed {} baud;", ospeed));
+    } else {
+        out.push(&format!("ispeed {} baud; ospeed {} baud;", ispeed, ospeed));
+    }
+    if all {
+        if let Some(size) = size {
+            out.push(&format!("rows {}; columns {};", size.ws_row, size.ws_col));
+        }
+    }
+    out.push(&format!("line = {};", termios.c_line));
+    if all {
+        out.finish_line();
+    }
+
+    for &(name, index, default) in CONTROL_CHARS {
+        let value = termios.c_cc[index];
+        if all || value != default {
+            out.push(&format!("{} = {};", name, visible_char(value)));
+        }
+    }
+
+    // min and time only matter, and are only shown, in noncanonical mode
+    let canonical = termios.c_lflag & libc::ICANON != 0;
+    if all || !canonical {
+        out.push(&format!("min = {}; time = {};", termios.c_cc[libc::VMIN], termios.c_cc[libc::VTIME]));
+    }
+    out.finish_line();
+
+    for group in [Group::Control, Group::Input, Group::Output, Group::Local] {
+        let bits = flags(termios, group);
+        for mode in MODES.iter().filter(|mode| mode.group == group) {
+            let on = bits & mode.mask == mode.value;
+
+            if !all {
+                let differs = match mode.sane {
+                    Sane::Set => !on,
+                    Sane::Unset => on,
+                    Sane::Keep => false,
+                };
+                if !differs {
+                    continue;
+                }
+            }
+
+            if on {
+                out.push(mode.name);
+            } else if mode.reversible {
+                out.push(&format!("-{}", mode.name));
+            }
+        }
+        out.finish_line();
+    }
+}
+
+fn baud_rate(speed: libc::speed_t) -> u32 {
+    SPEEDS
+        .iter()
+        .find(|(_, value)| *value == speed)
+        .map(|(rate, _)| *rate)
+        .unwrap_or(0)
+}
+
+// Wrap to the width of the terminal on stdout, then $COLUMNS, then 80
+fn output_width() -> usize {
+    if let Ok(size) = get_window_size(libc::STDOUT_FILENO) {
+        if size.ws_col > 0 {
+            return size.ws_col as usize;
+        }
+    }
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(80)
+}
+
+fn visible_char(value: libc::cc_t) -> String {
+    if value == 0 {
+        return "<undef>".to_string();
+    }
+
+    let mut text = String::new();
+    let mut c = value;
+    if c >= 128 {
+        text.push_str("M-");
+        c -= 128;
+    }
+    match c {
+        0..=31 => {
+            text.push('^');
+            text.push((c + 64) as char);
+        }
+        127 => text.push_str("^?"),
+        _ => text.push(c as char),
+    }
+    text
+}
@@ -0,0 +1,128 @@
+// ASD CoreUtils - sync
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use std::ffi::CString;
+use std::io;
+use std::process;
+
+#[derive(Clone, Copy)]
+enum SyncMode {
+    File,
+    Data,
+    FileSystem,
+}
+
+fn main() {
+    let matches = Command::new("sync")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils sync - synchronize cached writes to persistent storage")
+        .arg(
+            Arg::new("data")
+                .short('d')
+                .long("data")
+                .help("Sync only file data, no unneeded metadata")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file-system")
+                .short('f')
+                .long("file-system")
+                .help("Sync the file systems that contain the files")
+                .conflicts_with("data")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to synchronize")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let mode = if matches.get_flag("data") {
+        SyncMode::Data
+    } else if matches.get_flag("file-system") {
+        SyncMode::FileSystem
+    } else {
+        SyncMode::File
+    };
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("file")
+        .map(|files| files.collect())
+        .unwrap_or_default();
+
+    // Without operands the whole system is flushed, which is all POSIX sync does
+    if files.is_empty() {
+        if matches.get_flag("data") {
+            fail("sync", "--data needs at least one argument");
+        }
+        unsafe { libc::sync() };
+        return;
+    }
+
+    let mut exit_code = 0;
+
+    for file in files {
+        if let Err(err) = sync_file(file, mode) {
+            eprintln!("sync: error syncing '{}': {}", file, strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+fn sync_file(path: &str, mode: SyncMode) -> io::Result<()> {
+    let c_path = CString::new(path)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contains a NUL byte"))?;
+
+    // O_NONBLOCK keeps the open from hanging on FIFOs; write-only is the
+    // fallback for files we may write but not read
+    let mut fd = unsafe { libc::open(c_path.as_ptr(), libc::O_RDONLY | libc::O_NONBLOCK) };
+    if fd < 0 {
+        let read_err = io::Error::last_os_error();
+        fd = unsafe { libc::open(c_path.as_ptr(), libc::O_WRONLY | libc::O_NONBLOCK) };
+        if fd < 0 {
+            return Err(read_err);
+        }
+    }
+
+    let result = unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags >= 0 {
+            libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK);
+        }
+
+        match mode {
+            SyncMode::File => libc::fsync(fd),
+            SyncMode::Data => libc::fdatasync(fd),
+            SyncMode::FileSystem => syncfs(fd),
+        }
+    };
+
+    let err = io::Error::last_os_error();
+    unsafe { libc::close(fd) };
+
+    if result != 0 {
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn syncfs(fd: libc::c_int) -> libc::c_int {
+    libc::syncfs(fd)
+}
+
+// Without syncfs(2) the best we can do is flush everything
+#[cfg(not(target_os = "linux"))]
+unsafe fn syncfs(_fd: libc::c_int) -> libc::c_int {
+    libc::sync();
+    0
+}
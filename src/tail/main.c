@@ -15,21 +15,63 @@
  #include <sys/stat.h>
  #include <ctype.h>
  #include <getopt.h>
+ #include <poll.h>
+ #include <signal.h>
+ #include <time.h>
+ #include <libgen.h>
+ #include <limits.h>
+ #include <stdint.h>
+ #ifdef __linux__
+ #include <sys/inotify.h>
+ #endif
  
  #define DEFAULT_LINES 10
  #define BUFFER_SIZE 8192
  #define MAX_FILENAME_LENGTH 4096
  
+ // How -f identifies the file it keeps reading
+ #define FOLLOW_NONE 0
+ #define FOLLOW_DESCRIPTOR 1
+ #define FOLLOW_NAME 2
+ 
+ // Long options without a short equivalent
+ #define RETRY_OPTION 256
+ #define PID_OPTION 257
+ #define DISABLE_INOTIFY_OPTION 258
+ 
  typedef struct {
-     int lines;              // Number of lines to output (-n option)
-     int bytes;              // Number of bytes to output (-c option)
-     int follow;             // Whether to follow file (-f option)
+     long long count;        // Number of lines or bytes to output (-n, -c options)
+     int count_bytes;        // Count bytes rather than lines (-c option)
+     int from_start;         // Output from the NUMth item on (+NUM)
+     char delimiter;         // Line delimiter, NUL with -z
+     int follow;             // FOLLOW_DESCRIPTOR (-f) or FOLLOW_NAME (-F)
+     int retry;              // Keep trying to open inaccessible files (--retry)
+     pid_t pid;              // Stop following once this process dies (--pid)
+     int use_inotify;        // Wait for inotify events instead of polling
      int quiet;              // Suppress headers (-q option)
-     int sleep_interval;     // Sleep interval for -f option (in seconds)
+     double sleep_interval;  // Sleep interval for -f option (in seconds)
      char **files;           // Array of filenames
      int file_count;         // Number of files
  } options_t;
  
+ // State of one file while following it
+ typedef struct {
+     const char *name;       // Name as given on the command line
+     int fd;                 // Open descriptor, or -1 while inaccessible
+     dev_t dev;              // Device and inode of the open file, used to
+     ino_t ino;              // notice when the name is given to a new file
+     mode_t mode;            // File type of the open file
+     off_t size;             // Bytes of a regular file output so far
+     int wd;                 // inotify watch on the file, or -1
+     int dir_wd;             // inotify watch on its directory with --follow=name
+     int ignore;             // Set once the file is no longer followed
+ } followed_t;
+ 
+ // Index of the file whose contents were output last, so that following
+ // several files only prints a header when the output switches files
+ static int current_file = -1;
+ static int header_printed = 0;
+ 
  // Print usage information
  void print_usage(const char *program_name) {
      fprintf(stderr, "ASD CoreUtils Tail - Faster alternative to GNU tail\n");
@@ -37,15 +79,31 @@
      fprintf(stderr, "Print the last 10 lines of each FILE to standard output.\n");
      fprintf(stderr, "With more than one FILE, precede each with a header giving the file name.\n\n");
      fprintf(stderr, "Options:\n");
-     fprintf(stderr, "  -n, --lines=NUM       output the last NUM lines, instead of the last 10\n");
-     fprintf(stderr, "  -c, --bytes=NUM       output the last NUM bytes\n");
-     fprintf(stderr, "  -f, --follow          output appended data as the file grows\n");
+     fprintf(stderr, "  -n, --lines=[+]NUM    output the last NUM lines, instead of the last 10;\n");
+     fprintf(stderr, "                        or use -n +NUM to output starting with line NUM\n");
+     fprintf(stderr, "  -c, --bytes=[+]NUM    output the last NUM bytes; or use -c +NUM to\n");
+     fprintf(stderr, "                        output starting with byte NUM of each file\n");
+     fprintf(stderr, "  -f, --follow[={name|descriptor}]\n");
+     fprintf(stderr, "                        output appended data as the file grows;\n");
+     fprintf(stderr, "                        an absent option argument means 'descriptor'\n");
+     fprintf(stderr, "  -F                    same as --follow=name --retry\n");
+     fprintf(stderr, "      --pid=PID         with -f, terminate after process ID, PID dies\n");
      fprintf(stderr, "  -q, --quiet           never output headers giving file names\n");
+     fprintf(stderr, "      --retry           keep trying to open a file if it is inaccessible\n");
      fprintf(stderr, "  -s, --sleep-interval=NUM  with -f, sleep for approximately NUM seconds\n");
+     fprintf(stderr, "                        between checks when inotify is unavailable;\n");
+     fprintf(stderr, "                        with --pid=PID, check PID at least that often\n");
+     fprintf(stderr, "  -z, --zero-terminated line delimiter is NUL, not newline\n");
      fprintf(stderr, "      --help            display this help and exit\n");
      fprintf(stderr, "      --version         output version information and exit\n\n");
-     fprintf(stderr, "If the first character of NUM is '+', output starts with the NUMth item.\n");
+     fprintf(stderr, "NUM may have a multiplier suffix: b 512, kB 1000, K 1024, MB 1000*1000,\n");
+     fprintf(stderr, "M 1024*1024, GB 1000*1000*1000, G 1024*1024*1024, and so on for T, P, E.\n");
+     fprintf(stderr, "Binary prefixes can be used, too: KiB=K, MiB=M, and so on.\n\n");
      fprintf(stderr, "With no FILE, or when FILE is -, read standard input.\n\n");
+     fprintf(stderr, "With --follow (-f), tail defaults to following the file descriptor, which\n");
+     fprintf(stderr, "means that even if a tail'ed file is renamed, tail will continue to track\n");
+     fprintf(stderr, "its end. Use --follow=name to track the actual name of the file instead,\n");
+     fprintf(stderr, "reopening it when it is rotated or recreated.\n\n");
  }
  
  // Print version information
@@ -58,12 +116,81 @@
      printf("Written by AnmiTaliDev.\n");
  }
  
+ // The value of a multiplier suffix on a count, or 0 if SUFFIX is not one
+ unsigned long long suffix_multiplier(const char *suffix) {
+     static const char prefixes[] = "KMGTPE";
+ 
+     if (*suffix == '\0') {
+         return 1;
+     }
+     if (strcmp(suffix, "b") == 0) {
+         return 512;
+     }
+ 
+     char prefix = *suffix == 'k' || *suffix == 'm' ? (char)toupper((unsigned char)*suffix) : *suffix;
+     const char *found = strchr(prefixes, prefix);
+     if (prefix == '\0' || found == NULL) {
+         return 0;
+     }
+ 
+     unsigned long long base;
+     if (suffix[1] == '\0' || strcmp(suffix + 1, "iB") == 0) {
+         base = 1024;
+     } else if (strcmp(suffix + 1, "B") == 0) {
+         base = 1000;
+     } else {
+         return 0;
+     }
+ 
+     unsigned long long multiplier = 1;
+     for (const char *p = prefixes; p <= found; p++) {
+         multiplier *= base;
+     }
+     return multiplier;
+ }
+ 
+ // Parse the NUM of -n or -c. A leading '+' counts from the start of the
+ // input; a leading '-' is allowed and means the same as none. Counts too
+ // large to represent are taken as the largest there is.
+ int parse_count(const char *arg, long long *count, int *from_start) {
+     const char *p = arg;
+ 
+     *from_start = *p == '+';
+     if (*p == '+' || *p == '-') {
+         p++;
+     }
+     if (!isdigit((unsigned char)*p)) {
+         return 0;
+     }
+ 
+     char *end;
+     errno = 0;
+     unsigned long long value = strtoull(p, &end, 10);
+     int overflow = errno == ERANGE;
+     unsigned long long multiplier = suffix_multiplier(end);
+     if (multiplier == 0) {
+         return 0;
+     }
+ 
+     if (overflow || value > (unsigned long long)LLONG_MAX / multiplier) {
+         *count = LLONG_MAX;
+     } else {
+         *count = (long long)(value * multiplier);
+     }
+     return 1;
+ }
+ 
  // Parse command-line options
  options_t parse_options(int argc, char *argv[]) {
      options_t opts = {
-         .lines = DEFAULT_LINES,
-         .bytes = -1,
-         .follow = 0,
+         .count = DEFAULT_LINES,
+         .count_bytes = 0,
+         .from_start = 0,
+         .delimiter = '\n',
+         .follow = FOLLOW_NONE,
+         .retry = 0,
+         .pid = 0,
+         .use_inotify = 1,
          .quiet = 0,
          .sleep_interval = 1,
          .files = NULL,
@@ -73,44 +200,77 @@
      static struct option long_options[] = {
          {"lines", required_argument, 0, 'n'},
          {"bytes", required_argument, 0, 'c'},
-         {"follow", no_argument, 0, 'f'},
+         {"follow", optional_argument, 0, 'f'},
+         {"retry", no_argument, 0, RETRY_OPTION},
+         {"pid", required_argument, 0, PID_OPTION},
+         {"-disable-inotify", no_argument, 0, DISABLE_INOTIFY_OPTION},
          {"quiet", no_argument, 0, 'q'},
          {"silent", no_argument, 0, 'q'},
          {"sleep-interval", required_argument, 0, 's'},
          {"help", no_argument, 0, 'h'},
          {"version", no_argument, 0, 'v'},
+         {"zero-terminated", no_argument, 0, 'z'},
          {0, 0, 0, 0}
      };
  
      int c;
-     while ((c = getopt_long(argc, argv, "n:c:fqs:hv", long_options, NULL)) != -1) {
+     while ((c = getopt_long(argc, argv, "n:c:fFqs:hvz", long_options, NULL)) != -1) {
          switch (c) {
              case 'n':
-                 if (optarg[0] == '+') {
-                     opts.lines = -atoi(optarg + 1);
-                 } else {
-                     opts.lines = atoi(optarg);
+             case 'c':
+                 if (!parse_count(optarg, &opts.count, &opts.from_start)) {
+                     fprintf(stderr, "tail: invalid number of %s: '%s'\n", c == 'n' ? "lines" : "bytes", optarg);
+                     exit(EXIT_FAILURE);
                  }
+                 opts.count_bytes = c == 'c';
                  break;
-             case 'c':
-                 if (optarg[0] == '+') {
-                     opts.bytes = -atoi(optarg + 1);
+             case 'f':
+                 if (optarg == NULL || strcmp(optarg, "descriptor") == 0) {
+                     opts.follow = FOLLOW_DESCRIPTOR;
+                 } else if (strcmp(optarg, "name") == 0) {
+                     opts.follow = FOLLOW_NAME;
                  } else {
-                     opts.bytes = atoi(optarg);
+                     fprintf(stderr, "tail: invalid argument '%s' for '--follow'\n", optarg);
+                     fprintf(stderr, "Valid arguments are:\n  - 'descriptor'\n  - 'name'\n");
+                     exit(EXIT_FAILURE);
                  }
                  break;
-             case 'f':
-                 opts.follow = 1;
+             case 'F':
+                 opts.follow = FOLLOW_NAME;
+                 opts.retry = 1;
+                 break;
+             case RETRY_OPTION:
+                 opts.retry = 1;
+                 break;
+             case PID_OPTION: {
+                 char *end;
+                 errno = 0;
+                 long pid = strtol(optarg, &end, 10);
+                 if (errno != 0 || end == optarg || *end != '\0' || pid < 0 || (pid_t)pid != pid) {
+                     fprintf(stderr, "tail: invalid PID: '%s'\n", optarg);
+                     exit(EXIT_FAILURE);
+                 }
+                 opts.pid = (pid_t)pid;
+                 break;
+             }
+             case DISABLE_INOTIFY_OPTION:
+                 opts.use_inotify = 0;
                  break;
              case 'q':
                  opts.quiet = 1;
                  break;
-             case 's':
-                 opts.sleep_interval = atoi(optarg);
-                 if (opts.sleep_interval < 0) {
-                     opts.sleep_interval = 1;
+             case 'z':
+                 opts.delimiter = '\0';
+                 break;
+             case 's': {
+                 char *end;
+                 opts.sleep_interval = strtod(optarg, &end);
+                 if (end == optarg || *end != '\0' || !(opts.sleep_interval >= 0)) {
+                     fprintf(stderr, "tail: invalid number of seconds: '%s'\n", optarg);
+                     exit(EXIT_FAILURE);
                  }
                  break;
+             }
              case 'h':
                  print_usage(argv[0]);
                  exit(EXIT_SUCCESS);
@@ -135,279 +295,634 @@
          opts.file_count = 1;
      }
  
+     if (opts.follow == FOLLOW_NONE) {
+         if (opts.retry) {
+             fprintf(stderr, "tail: warning: --retry ignored; --retry is useful only when following\n");
+         }
+         if (opts.pid != 0) {
+             fprintf(stderr, "tail: warning: PID ignored; --pid=PID is useful only when following\n");
+         }
+     } else if (opts.follow == FOLLOW_DESCRIPTOR && opts.retry) {
+         fprintf(stderr, "tail: warning: --retry only effective for the initial open\n");
+     }
+ 
+     if (opts.follow == FOLLOW_NAME) {
+         for (int i = 0; i < opts.file_count; i++) {
+             if (strcmp(opts.files[i], "-") == 0) {
+                 fprintf(stderr, "tail: cannot follow '-' by name\n");
+                 exit(EXIT_FAILURE);
+             }
+         }
+     }
+ 
      return opts;
  }
  
- // Count lines in a buffer
- int count_lines(const char *buffer, size_t size) {
-     int count = 0;
-     for (size_t i = 0; i < size; i++) {
-         if (buffer[i] == '\n') {
-             count++;
-         }
+ // Copy the rest of FD to standard output
+ void copy_rest(int fd) {
+     char buffer[BUFFER_SIZE];
+     ssize_t bytes_read;
+ 
+     while ((bytes_read = read(fd, buffer, sizeof(buffer))) > 0) {
+         fwrite(buffer, 1, bytes_read, stdout);
+     }
+     if (bytes_read == -1) {
+         perror("Error reading file");
      }
-     return count;
  }
  
- // Tail a file by lines
- void tail_by_lines(FILE *file, int lines, int start_from_line) {
-     if (lines <= 0) {
-         return;
+ // Copy the rest of FILE to standard output
+ void copy_rest_buffered(FILE *file) {
+     char buffer[BUFFER_SIZE];
+     size_t bytes_read;
+ 
+     while ((bytes_read = fread(buffer, 1, sizeof(buffer), file)) > 0) {
+         fwrite(buffer, 1, bytes_read, stdout);
+     }
+     if (ferror(file)) {
+         perror("Error reading file");
      }
+ }
  
-     // If start_from_line is provided (+ syntax was used)
-     if (start_from_line > 0) {
-         int current_line = 0;
-         char buffer[BUFFER_SIZE];
-         
-         // Skip lines until we reach the start line
-         while (current_line < start_from_line - 1 && fgets(buffer, sizeof(buffer), file) != NULL) {
-             if (strchr(buffer, '\n') != NULL) {
-                 current_line++;
-             }
+ // Print the last LINES lines of a regular file by scanning blocks backwards
+ // from its end, so only the part being output is ever read. Returns 0,
+ // having read nothing, if the file cannot be handled this way.
+ int tail_lines_from_end(FILE *file, long long lines, char delimiter) {
+     int fd = fileno(file);
+     struct stat stat_buf;
+ 
+     // Files in /proc and the like claim to be empty and must be read through
+     if (fstat(fd, &stat_buf) == -1 || !S_ISREG(stat_buf.st_mode) || stat_buf.st_size == 0) {
+         return 0;
+     }
+ 
+     // Standard input may already be positioned partway into the file
+     off_t start = lseek(fd, 0, SEEK_CUR);
+     off_t end = lseek(fd, 0, SEEK_END);
+     if (start == -1 || end == -1) {
+         return 0;
+     }
+ 
+     char buffer[BUFFER_SIZE];
+     off_t from = start;
+     off_t block_end = end;
+     long long delimiters = 0;
+ 
+     while (block_end > start && from == start) {
+         size_t block_size = block_end - start < BUFFER_SIZE ? (size_t)(block_end - start) : BUFFER_SIZE;
+         off_t block_start = block_end - block_size;
+ 
+         // Should the file shrink underneath us, output what is left of it
+         if (pread(fd, buffer, block_size, block_start) != (ssize_t)block_size) {
+             break;
          }
-         
-         // Output the rest of the file
-         while (fgets(buffer, sizeof(buffer), file) != NULL) {
-             fputs(buffer, stdout);
+ 
+         for (size_t i = block_size; i-- > 0; ) {
+             // A delimiter at the very end terminates the last line rather
+             // than starting another
+             if (buffer[i] == delimiter && block_start + (off_t)i != end - 1 && ++delimiters == lines) {
+                 from = block_start + i + 1;
+                 break;
+             }
          }
-         return;
+         block_end = block_start;
      }
  
-     // For regular tail behavior, using a circular buffer approach
-     char **line_buffer = (char **)malloc(lines * sizeof(char *));
-     if (!line_buffer) {
-         perror("Memory allocation error");
-         return;
+     if (lseek(fd, from, SEEK_SET) == -1) {
+         perror("Error seeking file");
+         return 1;
      }
-     
-     for (int i = 0; i < lines; i++) {
-         line_buffer[i] = NULL;
+ 
+     copy_rest(fd);
+     return 1;
+ }
+ 
+ // One line kept by tail_by_lines, with the allocation getdelim reuses
+ typedef struct {
+     char *text;
+     size_t capacity;
+     ssize_t length;
+ } line_slot_t;
+ 
+ // Tail a file by lines. Returns 0, or -1 if memory ran out.
+ int tail_by_lines(FILE *file, long long lines, char delimiter) {
+     if (lines <= 0) {
+         return 0;
+     }
+ 
+     // Seekable files are read backwards from the end
+     if (tail_lines_from_end(file, lines, delimiter)) {
+         return 0;
+     }
+ 
+     // Pipes can only be read through, keeping the last lines seen in a
+     // circular buffer. Each slot's allocation is reused by getdelim once
+     // the buffer wraps around; the extra slot is the one being read into,
+     // which a failed read at the end of input may have clobbered. The
+     // buffer starts small and doubles until it holds LINES + 1 slots, so a
+     // huge count costs no more than the input does.
+     size_t limit = (unsigned long long)lines < SIZE_MAX / sizeof(line_slot_t) ? (size_t)lines + 1
+                                                                            : SIZE_MAX / sizeof(line_slot_t);
+     size_t slots = limit < 64 ? limit : 64;
+     line_slot_t *ring = (line_slot_t *)calloc(slots, sizeof(line_slot_t));
+     int status = 0;
+     if (!ring) {
+         fprintf(stderr, "tail: memory exhausted\n");
+         return -1;
      }
      
-     int line_count = 0;
-     int pos = 0;
-     char buffer[BUFFER_SIZE];
+     size_t line_count = 0;
+     size_t pos = 0;
      
-     while (fgets(buffer, sizeof(buffer), file) != NULL) {
-         size_t len = strlen(buffer);
-         
-         // Free previous line if it exists
-         if (line_buffer[pos]) {
-             free(line_buffer[pos]);
-         }
-         
-         // Store the new line
-         line_buffer[pos] = (char *)malloc(len + 1);
-         if (!line_buffer[pos]) {
-             perror("Memory allocation error");
-             break;
+     while ((ring[pos].length = getdelim(&ring[pos].text, &ring[pos].capacity, delimiter, file)) != -1) {
+         // Until the buffer first wraps, the lines are in order from slot 0
+         // and it can simply be extended
+         if (pos + 1 == slots && slots < limit) {
+             size_t grown = slots <= limit / 2 ? slots * 2 : limit;
+             line_slot_t *bigger = (line_slot_t *)realloc(ring, grown * sizeof(line_slot_t));
+             if (!bigger) {
+                 fprintf(stderr, "tail: memory exhausted\n");
+                 status = -1;
+                 break;
+             }
+             memset(bigger + slots, 0, (grown - slots) * sizeof(line_slot_t));
+             ring = bigger;
+             slots = grown;
          }
-         
-         strcpy(line_buffer[pos], buffer);
-         pos = (pos + 1) % lines;
-         if (line_count < lines) {
+         pos = (pos + 1) % slots;
+         if (line_count < (size_t)lines) {
              line_count++;
          }
      }
      
-     // Output the lines in the right order
-     for (int i = 0; i < line_count; i++) {
-         int idx = (pos + i) % lines;
-         fputs(line_buffer[idx], stdout);
+     // Output the lines in the right order, oldest first; the buffer
+     // only wrapped around if the file had more lines than were kept
+     if (status == 0) {
+         size_t first = (pos + slots - line_count) % slots;
+         for (size_t i = 0; i < line_count; i++) {
+             size_t idx = (first + i) % slots;
+             fwrite(ring[idx].text, 1, ring[idx].length, stdout);
+         }
      }
      
      // Free all allocated memory
-     for (int i = 0; i < lines; i++) {
-         if (line_buffer[i]) {
-             free(line_buffer[i]);
-         }
+     for (size_t i = 0; i < slots; i++) {
+         free(ring[i].text);
      }
-     free(line_buffer);
+     free(ring);
+     return status;
  }
  
- // Tail a file by bytes
- void tail_by_bytes(FILE *file, int bytes, int start_from_byte) {
-     if (bytes <= 0) {
-         return;
+ // Output a file from its line START on, counting from 1
+ void tail_from_line(FILE *file, long long start, char delimiter) {
+     int c;
+ 
+     while (start > 1 && (c = getc(file)) != EOF) {
+         if (c == delimiter) {
+             start--;
+         }
      }
+     copy_rest_buffered(file);
+ }
  
-     // If start_from_byte is provided (+ syntax was used)
-     if (start_from_byte > 0) {
-         // Skip to the specified byte position
-         fseek(file, start_from_byte - 1, SEEK_SET);
-         
-         // Output the rest of the file
+ // Output a file from its byte START on, counting from 1
+ void tail_from_byte(FILE *file, long long start) {
+     off_t skip = start > 1 ? (off_t)(start - 1) : 0;
+ 
+     // Pipes and terminals cannot seek, and the bytes are read past instead
+     if (skip > 0 && fseeko(file, skip, SEEK_CUR) != 0) {
          char buffer[BUFFER_SIZE];
          size_t bytes_read;
-         while ((bytes_read = fread(buffer, 1, sizeof(buffer), file)) > 0) {
-             fwrite(buffer, 1, bytes_read, stdout);
+         while (skip > 0 && (bytes_read = fread(buffer, 1, skip < BUFFER_SIZE ? (size_t)skip : BUFFER_SIZE, file)) > 0) {
+             skip -= bytes_read;
          }
-         return;
      }
+     copy_rest_buffered(file);
+ }
  
-     // For regular tail behavior
-     int fd = fileno(file);
-     off_t file_size;
-     
-     // Get file size
-     file_size = lseek(fd, 0, SEEK_END);
-     if (file_size == -1) {
-         perror("Error seeking file");
+ // Tail a file by bytes
+ void tail_by_bytes(FILE *file, long long bytes) {
+     if (bytes <= 0) {
          return;
      }
-     
-     // If bytes is greater than file size, just output the whole file
-     if (bytes >= file_size) {
-         lseek(fd, 0, SEEK_SET);
-         FILE *new_file = fdopen(dup(fd), "r");
-         if (!new_file) {
-             perror("Error duplicating file descriptor");
+ 
+     int fd = fileno(file);
+     struct stat stat_buf;
+ 
+     // Regular files are read from where the last bytes start, or from
+     // where standard input was left if that is nearer the end
+     if (fstat(fd, &stat_buf) == 0 && S_ISREG(stat_buf.st_mode) && stat_buf.st_size > 0) {
+         off_t start = lseek(fd, 0, SEEK_CUR);
+         off_t end = lseek(fd, 0, SEEK_END);
+         if (start != -1 && end != -1) {
+             off_t from = end - start > bytes ? end - (off_t)bytes : start;
+             if (lseek(fd, from, SEEK_SET) == -1) {
+                 perror("Error seeking file");
+                 return;
+             }
+             copy_rest(fd);
              return;
          }
-         
-         char buffer[BUFFER_SIZE];
-         size_t bytes_read;
-         while ((bytes_read = fread(buffer, 1, sizeof(buffer), new_file)) > 0) {
-             fwrite(buffer, 1, bytes_read, stdout);
+     }
+ 
+     // Anything else is read through, keeping what may still be among the
+     // last bytes. The buffer at least doubles each time it grows, so each
+     // byte is moved to its front a bounded number of times.
+     char *data = NULL;
+     size_t size = 0;
+     size_t capacity = 0;
+     ssize_t bytes_read;
+ 
+     do {
+         if (capacity - size < BUFFER_SIZE) {
+             if ((unsigned long long)size > (unsigned long long)bytes) {
+                 memmove(data, data + size - bytes, bytes);
+                 size = bytes;
+             }
+             if (capacity - size < BUFFER_SIZE) {
+                 char *grown = (char *)realloc(data, capacity * 2 + BUFFER_SIZE);
+                 if (!grown) {
+                     perror("Memory allocation error");
+                     free(data);
+                     return;
+                 }
+                 data = grown;
+                 capacity = capacity * 2 + BUFFER_SIZE;
+             }
          }
-         fclose(new_file);
-         return;
+         bytes_read = read(fd, data + size, capacity - size);
+         if (bytes_read > 0) {
+             size += bytes_read;
+         }
+     } while (bytes_read > 0 || (bytes_read == -1 && errno == EINTR));
+ 
+     if (bytes_read == -1) {
+         perror("Error reading file");
      }
-     
-     // Seek to the right position
-     if (lseek(fd, -bytes, SEEK_END) == -1) {
-         perror("Error seeking file");
-         return;
+ 
+     size_t keep = (unsigned long long)size > (unsigned long long)bytes ? (size_t)bytes : size;
+     fwrite(data + size - keep, 1, keep, stdout);
+     free(data);
+ }
+ 
+ // Print the header naming a file, separated from earlier output by a blank line
+ void print_header(const char *filename) {
+     printf("%s==> %s <==\n", header_printed ? "\n" : "",
+            strcmp(filename, "-") == 0 ? "standard input" : filename);
+     header_printed = 1;
+ }
+ 
+ // Make FD the descriptor read for a followed file
+ void set_descriptor(followed_t *f, int fd, const struct stat *stat_buf) {
+     f->fd = fd;
+     f->dev = stat_buf->st_dev;
+     f->ino = stat_buf->st_ino;
+     f->mode = stat_buf->st_mode;
+     f->size = 0;
+ 
+     // Reading a pipe or device must not hold up the other files
+     if (!S_ISREG(stat_buf->st_mode)) {
+         fcntl(fd, F_SETFL, fcntl(fd, F_GETFL) | O_NONBLOCK);
      }
-     
-     // Read and output the last 'bytes' bytes
-     char *buffer = (char *)malloc(bytes);
-     if (!buffer) {
-         perror("Memory allocation error");
+ }
+ 
+ // Set up following FILENAME once its initial tail has been output. FD is
+ // the descriptor it was read from, or -1 if it could not be opened.
+ // Returns 0 if there is nothing to follow.
+ int start_following(followed_t *f, const char *filename, int fd, const options_t *opts) {
+     struct stat stat_buf;
+ 
+     f->name = filename;
+     f->fd = -1;
+     f->size = 0;
+     f->wd = -1;
+     f->dir_wd = -1;
+     f->ignore = 0;
+ 
+     if (fd == -1 || fstat(fd, &stat_buf) == -1) {
+         if (fd != -1) {
+             close(fd);
+         }
+         f->ignore = !opts->retry;
+         return 1;
+     }
+ 
+     // A pipe on standard input ends when its writer does; there is
+     // nothing more to wait for
+     if (strcmp(filename, "-") == 0 && S_ISFIFO(stat_buf.st_mode)) {
+         close(fd);
+         return 0;
+     }
+ 
+     set_descriptor(f, fd, &stat_buf);
+     if (S_ISREG(stat_buf.st_mode)) {
+         f->size = lseek(fd, 0, SEEK_END);
+     }
+     return 1;
+ }
+ 
+ // Watch the file open for F, and with --follow=name the directory holding
+ // its name, so that replacing the file is noticed too
+ void watch_file(followed_t *f, int inotify_fd, const options_t *opts) {
+ #ifdef __linux__
+     if (inotify_fd == -1 || strcmp(f->name, "-") == 0) {
          return;
      }
-     
-     ssize_t bytes_read = read(fd, buffer, bytes);
-     if (bytes_read > 0) {
+ 
+     if (f->fd != -1 && f->wd == -1) {
+         f->wd = inotify_add_watch(inotify_fd, f->name,
+                                   IN_MODIFY | IN_ATTRIB | IN_DELETE_SELF | IN_MOVE_SELF);
+     }
+ 
+     if (opts->follow == FOLLOW_NAME && f->dir_wd == -1) {
+         char *name = strdup(f->name);
+         if (name) {
+             f->dir_wd = inotify_add_watch(inotify_fd, dirname(name),
+                                           IN_CREATE | IN_MOVED_TO | IN_MOVED_FROM | IN_DELETE | IN_ATTRIB);
+             free(name);
+         }
+     }
+ #else
+     (void)f;
+     (void)inotify_fd;
+     (void)opts;
+ #endif
+ }
+ 
+ // Stop reading a followed file, keeping its name for a later reopen
+ void close_followed(followed_t *files, int count, int index, int inotify_fd) {
+     followed_t *f = &files[index];
+ 
+ #ifdef __linux__
+     if (f->wd != -1) {
+         // Naming the same file twice gives both the same watch
+         int shared = 0;
+         for (int i = 0; i < count; i++) {
+             if (i != index && files[i].wd == f->wd) {
+                 shared = 1;
+             }
+         }
+         if (!shared) {
+             inotify_rm_watch(inotify_fd, f->wd);
+         }
+     }
+ #else
+     (void)count;
+     (void)inotify_fd;
+ #endif
+ 
+     f->wd = -1;
+     close(f->fd);
+     f->fd = -1;
+ }
+ 
+ // Output whatever can be read from a followed file right now
+ void dump_remainder(followed_t *f, int index, int print_headers) {
+     char buffer[BUFFER_SIZE];
+     ssize_t bytes_read;
+ 
+     while ((bytes_read = read(f->fd, buffer, sizeof(buffer))) > 0) {
+         if (index != current_file) {
+             if (print_headers) {
+                 print_header(f->name);
+             }
+             current_file = index;
+         }
          fwrite(buffer, 1, bytes_read, stdout);
+         f->size += bytes_read;
+     }
+ 
+     if (bytes_read == -1 && errno != EAGAIN && errno != EINTR) {
+         fprintf(stderr, "tail: error reading '%s': %s\n", f->name, strerror(errno));
      }
-     free(buffer);
+     fflush(stdout);
  }
  
- // Follow a file for changes
- void follow_file(const char *filename, const options_t *opts) {
-     FILE *file;
+ // Output anything appended to a followed file since the last check. With
+ // --follow=name, or while --retry waits for the initial open, the name is
+ // looked up again and a file that appeared or replaced the old one is
+ // output from its start. A name that goes away is waited for.
+ void check_file(followed_t *files, int count, int index, const options_t *opts, int inotify_fd) {
+     followed_t *f = &files[index];
+     int print_headers = opts->file_count > 1 && !opts->quiet;
      struct stat stat_buf;
-     off_t last_size;
-     
-     // Open the file
-     if (strcmp(filename, "-") == 0) {
-         file = stdin;
-         fprintf(stderr, "Cannot follow standard input\n");
+ 
+     if (f->ignore) {
          return;
-     } else {
-         file = fopen(filename, "r");
-         if (!file) {
-             perror(filename);
+     }
+ 
+     if (opts->follow == FOLLOW_NAME || f->fd == -1) {
+         if (stat(f->name, &stat_buf) == -1) {
+             int error = errno;
+             if (f->fd != -1) {
+                 dump_remainder(f, index, print_headers);
+                 if (opts->retry) {
+                     fprintf(stderr, "tail: '%s' has become inaccessible: %s\n", f->name, strerror(error));
+                 } else {
+                     fprintf(stderr, "tail: %s: %s\n", f->name, strerror(error));
+                 }
+                 close_followed(files, count, index, inotify_fd);
+             }
              return;
          }
+ 
+         if (f->fd == -1 || stat_buf.st_dev != f->dev || stat_buf.st_ino != f->ino) {
+             int fd = open(f->name, O_RDONLY);
+             if (fd == -1 || fstat(fd, &stat_buf) == -1) {
+                 // Most likely still being created; try again next time
+                 if (fd != -1) {
+                     close(fd);
+                 }
+                 return;
+             }
+ 
+             if (f->fd != -1) {
+                 dump_remainder(f, index, print_headers);
+                 fprintf(stderr, "tail: '%s' has been replaced;  following new file\n", f->name);
+                 close_followed(files, count, index, inotify_fd);
+             } else {
+                 fprintf(stderr, "tail: '%s' has appeared;  following new file\n", f->name);
+             }
+             set_descriptor(f, fd, &stat_buf);
+             watch_file(f, inotify_fd, opts);
+         }
      }
-     
-     // Get initial file size
-     int fd = fileno(file);
-     if (fstat(fd, &stat_buf) == -1) {
-         perror("fstat");
-         fclose(file);
+ 
+     if (S_ISREG(f->mode)) {
+         if (fstat(f->fd, &stat_buf) == -1) {
+             fprintf(stderr, "tail: cannot fstat '%s': %s\n", f->name, strerror(errno));
+             close_followed(files, count, index, inotify_fd);
+             f->ignore = !(opts->follow == FOLLOW_NAME && opts->retry);
+             return;
+         }
+ 
+         if (stat_buf.st_size < f->size) {
+             fprintf(stderr, "tail: %s: file truncated\n", f->name);
+             lseek(f->fd, 0, SEEK_SET);
+             f->size = 0;
+         } else if (stat_buf.st_size == f->size) {
+             return;
+         }
+     }
+ 
+     dump_remainder(f, index, print_headers);
+ }
+ 
+ // Sleep until inotify reports a change, or for INTERVAL seconds when
+ // polling; a negative INTERVAL waits for inotify alone
+ void wait_for_change(followed_t *files, int count, int inotify_fd, double interval) {
+ #ifdef __linux__
+     if (inotify_fd != -1) {
+         struct pollfd pfd = { .fd = inotify_fd, .events = POLLIN };
+         int timeout = -1;
+         if (interval >= 0) {
+             timeout = interval * 1000 >= 0x7fffffff ? 0x7fffffff : (int)(interval * 1000);
+         }
+ 
+         if (poll(&pfd, 1, timeout) <= 0) {
+             return;
+         }
+ 
+         // Only the removal of watches matters here: every wakeup checks
+         // all the files anyway
+         char buffer[4096] __attribute__((aligned(__alignof__(struct inotify_event))));
+         ssize_t length;
+         while ((length = read(inotify_fd, buffer, sizeof(buffer))) > 0) {
+             for (char *p = buffer; p < buffer + length; ) {
+                 struct inotify_event *event = (struct inotify_event *)p;
+                 if (event->mask & IN_IGNORED) {
+                     for (int i = 0; i < count; i++) {
+                         if (files[i].wd == event->wd) {
+                             files[i].wd = -1;
+                         }
+                         if (files[i].dir_wd == event->wd) {
+                             files[i].dir_wd = -1;
+                         }
+                     }
+                 }
+                 p += sizeof(struct inotify_event) + event->len;
+             }
+         }
          return;
      }
-     last_size = stat_buf.st_size;
-     
-     // First output the end of the file
-     if (opts->bytes > 0) {
-         tail_by_bytes(file, opts->bytes, 0);
-     } else {
-         tail_by_lines(file, opts->lines, 0);
+ #else
+     (void)files;
+     (void)count;
+     (void)inotify_fd;
+ #endif
+ 
+     struct timespec delay;
+     delay.tv_sec = (time_t)interval;
+     delay.tv_nsec = (long)((interval - (double)delay.tv_sec) * 1e9);
+     nanosleep(&delay, NULL);
+ }
+ 
+ // Keep outputting data appended to the files until none is left to follow
+ // or the process given with --pid has died
+ int follow_files(followed_t *files, int count, const options_t *opts) {
+     int inotify_fd = -1;
+ 
+ #ifdef __linux__
+     if (opts->use_inotify) {
+         inotify_fd = inotify_init1(IN_NONBLOCK | IN_CLOEXEC);
      }
-     fflush(stdout);
-     
-     // Then keep checking for changes
+ #endif
+ 
+     for (int i = 0; i < count; i++) {
+         watch_file(&files[i], inotify_fd, opts);
+     }
+ 
      while (1) {
-         sleep(opts->sleep_interval);
-         
-         // Check if file has changed
-         if (fstat(fd, &stat_buf) == -1) {
-             perror("fstat");
-             break;
-         }
-         
-         // If file size increased, output the new data
-         if (stat_buf.st_size > last_size) {
-             if (fseek(file, last_size, SEEK_SET) == -1) {
-                 perror("fseek");
-                 break;
-             }
-             
-             char buffer[BUFFER_SIZE];
-             size_t bytes_read;
-             while ((bytes_read = fread(buffer, 1, sizeof(buffer), file)) > 0) {
-                 fwrite(buffer, 1, bytes_read, stdout);
+         // Checked before the files are read, so output written just before
+         // the process died is not lost
+         int writer_dead = opts->pid != 0 && kill(opts->pid, 0) == -1 && errno == ESRCH;
+         int remaining = 0;
+         int polling = inotify_fd == -1 || opts->pid != 0;
+ 
+         for (int i = 0; i < count; i++) {
+             check_file(files, count, i, opts, inotify_fd);
+             if (files[i].ignore) {
+                 continue;
              }
-             fflush(stdout);
-             
-             last_size = stat_buf.st_size;
-         } else if (stat_buf.st_size < last_size) {
-             // File was truncated, reopen it
-             fclose(file);
-             file = fopen(filename, "r");
-             if (!file) {
-                 perror(filename);
-                 break;
+             remaining++;
+ 
+             // Files without a usable watch, such as standard input or ones
+             // in a directory that does not exist yet, have to be polled
+             watch_file(&files[i], inotify_fd, opts);
+             if (files[i].wd == -1 && files[i].dir_wd == -1) {
+                 polling = 1;
              }
-             fd = fileno(file);
-             last_size = stat_buf.st_size;
          }
+ 
+         if (remaining == 0) {
+             fprintf(stderr, "tail: no files remaining\n");
+             return EXIT_FAILURE;
+         }
+         if (writer_dead) {
+             return EXIT_SUCCESS;
+         }
+ 
+         wait_for_change(files, count, inotify_fd, polling ? opts->sleep_interval : -1);
      }
-     
-     fclose(file);
  }
  
  // Main function
  int main(int argc, char *argv[]) {
      options_t opts = parse_options(argc, argv);
+     int status = EXIT_SUCCESS;
+     followed_t *followed = NULL;
+     int followed_count = 0;
+ 
+     if (opts.follow != FOLLOW_NONE) {
+         followed = (followed_t *)malloc(opts.file_count * sizeof(followed_t));
+         if (!followed) {
+             perror("Memory allocation error");
+             return EXIT_FAILURE;
+         }
+     }
      
      for (int i = 0; i < opts.file_count; i++) {
          FILE *file;
          
-         // Print header if multiple files and not quiet
-         if (opts.file_count > 1 && !opts.quiet) {
-             printf("\n==> %s <==\n", strcmp(opts.files[i], "-") == 0 ? "standard input" : opts.files[i]);
-         }
-         
          // Open file
          if (strcmp(opts.files[i], "-") == 0) {
              file = stdin;
          } else {
              file = fopen(opts.files[i], "r");
              if (!file) {
-                 perror(opts.files[i]);
+                 fprintf(stderr, "tail: cannot open '%s' for reading: %s\n", opts.files[i], strerror(errno));
+                 status = EXIT_FAILURE;
+                 if (followed) {
+                     followed_count += start_following(&followed[followed_count], opts.files[i], -1, &opts);
+                 }
                  continue;
              }
          }
          
-         // Follow file if requested
-         if (opts.follow) {
-             follow_file(opts.files[i], &opts);
+         // Print header if multiple files and not quiet
+         if (opts.file_count > 1 && !opts.quiet) {
+             print_header(opts.files[i]);
+         }
+         
+         // Either tail by bytes or lines
+         if (opts.from_start && opts.count_bytes) {
+             tail_from_byte(file, opts.count);
+         } else if (opts.from_start) {
+             tail_from_line(file, opts.count, opts.delimiter);
+         } else if (opts.count_bytes) {
+             tail_by_bytes(file, opts.count);
          } else {
-             // Either tail by bytes or lines
-             if (opts.bytes > 0) {
-                 tail_by_bytes(file, opts.bytes, 0);
-             } else if (opts.bytes < 0) {
-                 tail_by_bytes(file, opts.bytes, -opts.bytes);
-             } else if (opts.lines < 0) {
-                 tail_by_lines(file, -1, -opts.lines);
-             } else {
-                 tail_by_lines(file, opts.lines, 0);
+             if (tail_by_lines(file, opts.count, opts.delimiter) != 0) {
+                 status = EXIT_FAILURE;
+             }
+         }
+         
+         // Keep a descriptor of our own for following; it shares the
+         // offset the tail was read up to
+         if (followed) {
+             current_file = -1;
+             if (start_following(&followed[followed_count], opts.files[i], dup(fileno(file)), &opts)) {
+                 current_file = followed_count++;
              }
          }
          
@@ -417,5 +932,13 @@
          }
      }
      
-     return EXIT_SUCCESS;
- }
\ No newline at end of file
+     if (followed_count > 0) {
+         fflush(stdout);
+         if (follow_files(followed, followed_count, &opts) != EXIT_SUCCESS) {
+             status = EXIT_FAILURE;
+         }
+     }
+     free(followed);
+     
+     return status;
+ }
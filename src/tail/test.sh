@@ -0,0 +1,26 @@
+#!/bin/sh
+# Build tail and check its behaviour on pipes, which it cannot seek
+set -e
+
+dir=$(mktemp -d)
+trap 'rm -rf "$dir"' EXIT
+cc -O2 -o "$dir/tail" "$(dirname "$0")/main.c"
+
+check() {
+    if [ "$2" != "$3" ]; then
+        echo "FAIL: $1: expected '$3', got '$2'" >&2
+        exit 1
+    fi
+}
+
+check "-n 3 on a pipe" "$(seq 10 | "$dir/tail" -n 3 | tr '\n' ' ')" "8 9 10 "
+check "-n larger than the input" "$(seq 5 | "$dir/tail" -n 100 | tr '\n' ' ')" "1 2 3 4 5 "
+check "-n past the buffer's first growth" "$(seq 1000 | "$dir/tail" -n 130 | head -n 1)" "871"
+
+# A huge count must not be allocated up front
+out=$(seq 5 | "$dir/tail" -n 3000000000 2>"$dir/err" | tr '\n' ' ')
+check "-n 3000000000 on a pipe" "$out" "1 2 3 4 5 "
+check "-n 3000000000 on a pipe (stderr)" "$(cat "$dir/err")" ""
+seq 5 | "$dir/tail" -n 3000000000 >/dev/null || { echo "FAIL: -n 3000000000 on a pipe exited nonzero" >&2; exit 1; }
+
+echo "tail: all tests passed"
@@ -0,0 +1,228 @@
+// ASD CoreUtils - tee
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::strerror;
+use clap::{Arg, ArgAction, Command};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::process;
+
+#[derive(Clone, Copy, PartialEq)]
+enum OutputError {
+    // No -p or --output-error: a closed pipe ends tee through SIGPIPE,
+    // other errors are diagnosed
+    Default,
+    Warn,
+    WarnNoPipe,
+    Exit,
+    ExitNoPipe,
+}
+
+impl OutputError {
+    fn parse(mode: &str) -> Self {
+        match mode {
+            "warn" => OutputError::Warn,
+            "exit" => OutputError::Exit,
+            "exit-nopipe" => OutputError::ExitNoPipe,
+            _ => OutputError::WarnNoPipe,
+        }
+    }
+
+    // Whether a write error is reported; a closed pipe is only with the
+    // modes that do not make an exception of pipes
+    fn reports(self, err: &io::Error) -> bool {
+        err.kind() != io::ErrorKind::BrokenPipe
+            || self == OutputError::Warn
+            || self == OutputError::Exit
+    }
+
+    fn exits(self) -> bool {
+        self == OutputError::Exit || self == OutputError::ExitNoPipe
+    }
+}
+
+enum Sink {
+    Stdout(io::Stdout),
+    File(File),
+}
+
+struct Output {
+    name: String,
+    sink: Sink,
+}
+
+impl Output {
+    fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        match &mut self.sink {
+            Sink::Stdout(stdout) => stdout.write_all(data).and_then(|()| stdout.flush()),
+            Sink::File(file) => file.write_all(data),
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("tee")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils tee - copy standard input to each FILE, and also to standard output")
+        .arg(
+            Arg::new("append")
+                .short('a')
+                .long("append")
+                .action(ArgAction::SetTrue)
+                .help("Append to the given FILEs, do not overwrite"),
+        )
+        .arg(
+            Arg::new("ignore-interrupts")
+                .short('i')
+                .long("ignore-interrupts")
+                .action(ArgAction::SetTrue)
+                .help("Ignore interrupt signals"),
+        )
+        .arg(
+            Arg::new("pipe")
+                .short('p')
+                .action(ArgAction::SetTrue)
+                .help("Diagnose errors writing to non pipes"),
+        )
+        .arg(
+            Arg::new("output-error")
+                .long("output-error")
+                .value_name("MODE")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("warn-nopipe")
+                .value_parser(["warn", "warn-nopipe", "exit", "exit-nopipe"])
+                .help("Set behavior on write error, MODE={warn,warn-nopipe(default),exit,exit-nopipe}"),
+        )
+        .arg(
+            Arg::new("fsync")
+                .long("fsync")
+                .action(ArgAction::SetTrue)
+                .help("Synchronize each FILE to storage before exiting"),
+        )
+        .arg(Arg::new("files").value_name("FILE").num_args(0..))
+        .after_help(
+            "MODE determines behavior with write errors on the outputs:\n  \
+             warn           diagnose errors writing to any output\n  \
+             warn-nopipe    diagnose errors writing to any output not a pipe\n  \
+             exit           exit on error writing to any output\n  \
+             exit-nopipe    exit on error writing to any output not a pipe\n\
+             The default MODE for the -p option is 'warn-nopipe'. With the 'nopipe'\n\
+             MODEs, tee stops once every output is a closed pipe.\n\
+             The default operation when --output-error is not specified, is to\n\
+             exit immediately on error writing to a pipe, and diagnose errors\n\
+             writing to non pipe outputs.",
+        )
+        .get_matches();
+
+    let mode = match matches.get_one::<String>("output-error") {
+        Some(mode) => OutputError::parse(mode),
+        None if matches.get_flag("pipe") => OutputError::WarnNoPipe,
+        None => OutputError::Default,
+    };
+    // A closed pipe is an error to handle only when a mode says how;
+    // otherwise it ends tee, as it would any other program
+    if mode == OutputError::Default {
+        unsafe {
+            libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+        }
+    }
+    if matches.get_flag("ignore-interrupts") {
+        unsafe {
+            libc::signal(libc::SIGINT, libc::SIG_IGN);
+        }
+    }
+
+    let append = matches.get_flag("append");
+    let mut ok = true;
+    let mut outputs = vec![Some(Output {
+        name: "standard output".to_string(),
+        sink: Sink::Stdout(io::stdout()),
+    })];
+    for name in matches.get_many::<String>("files").into_iter().flatten() {
+        let opened = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(name);
+        match opened {
+            Ok(file) => outputs.push(Some(Output {
+                name: name.clone(),
+                sink: Sink::File(file),
+            })),
+            Err(err) => {
+                eprintln!("tee: {}: {}", quote(name), strerror(&err));
+                ok = false;
+            }
+        }
+    }
+
+    ok &= copy(&mut outputs, mode);
+
+    if matches.get_flag("fsync") {
+        for output in outputs.iter().flatten() {
+            if let Sink::File(file) = &output.sink {
+                if let Err(err) = file.sync_all() {
+                    eprintln!("tee: {}: {}", quote(&output.name), strerror(&err));
+                    ok = false;
+                }
+            }
+        }
+    }
+
+    process::exit(if ok { 0 } else { 1 });
+}
+
+// Copies standard input to every output still open, a chunk at a time so
+// that each output sees the data as soon as it arrives. An output that
+// fails is dropped; tee goes on while any is left
+fn copy(outputs: &mut [Option<Output>], mode: OutputError) -> bool {
+    let mut ok = true;
+    let mut input = io::stdin().lock();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    while outputs.iter().any(Option::is_some) {
+        let count = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(count) => count,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                eprintln!("tee: standard input: {}", strerror(&err));
+                return false;
+            }
+        };
+
+        for slot in outputs.iter_mut() {
+            let Some(output) = slot else { continue };
+            let Err(err) = output.write_all(&buffer[..count]) else {
+                continue;
+            };
+            if mode.reports(&err) {
+                eprintln!("tee: {}: {}", quote(&output.name), strerror(&err));
+                if mode.exits() {
+                    process::exit(1);
+                }
+                ok = false;
+            }
+            *slot = None;
+        }
+    }
+    ok
+}
+
+// NAME as error messages show it: as it is, unless it would be hard to
+// make out, such as one with blanks in it
+fn quote(name: &str) -> String {
+    if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"') {
+        if name.contains('\'') {
+            format!("\"{}\"", name)
+        } else {
+            format!("'{}'", name)
+        }
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,332 @@
+// ASD CoreUtils - timeout
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::duration::parse_duration;
+use asd_coreutils_core::error::{fail_status, strerror, usage_error_status};
+use asd_coreutils_core::signal::parse_signal;
+use clap::{Arg, ArgAction, Command};
+use std::ffi::OsString;
+use std::io;
+use std::mem;
+use std::os::unix::process::CommandExt;
+use std::process;
+use std::ptr;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+const EXIT_TIMED_OUT: i32 = 124;
+const EXIT_FAILURE: i32 = 125;
+const EXIT_CANNOT_INVOKE: i32 = 126;
+const EXIT_ENOENT: i32 = 127;
+
+// Signals that timeout passes on to the command if it receives them itself
+const FORWARDED_SIGNALS: &[i32] = &[libc::SIGINT, libc::SIGTERM, libc::SIGHUP, libc::SIGQUIT];
+
+// Last signal caught by the handler, or 0; the main loop consumes it
+static RECEIVED: AtomicI32 = AtomicI32::new(0);
+
+extern "C" fn record_signal(signal: libc::c_int) {
+    // SIGCHLD only needs to interrupt sigsuspend
+    if signal != libc::SIGCHLD {
+        RECEIVED.store(signal, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let matches = Command::new("timeout")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils timeout - run a command with a time limit")
+        .after_help(
+            "DURATION is a floating point number with an optional suffix: 's' for seconds\n\
+             (the default), 'm' for minutes, 'h' for hours or 'd' for days. A duration\n\
+             of 0 disables the associated timeout.\n\n\
+             If the command times out, and --preserve-status is not set, then exit with\n\
+             status 124. Otherwise exit with the status of COMMAND; 125 means timeout\n\
+             itself failed, 126 that COMMAND was found but could not be invoked and 127\n\
+             that COMMAND was not found. The KILL signal cannot be caught, so when it\n\
+             is sent timeout itself is killed too and the status is 137.\n\n\
+             Unless --foreground is given, COMMAND runs in a process group of its own\n\
+             with timeout, and signals go to the whole group, so that the processes\n\
+             COMMAND starts are stopped as well.",
+        )
+        .trailing_var_arg(true)
+        .arg(
+            Arg::new("signal")
+                .short('s')
+                .long("signal")
+                .value_name("SIGNAL")
+                .help("Specify the signal to send on timeout, by name or number (default TERM)"),
+        )
+        .arg(
+            Arg::new("kill-after")
+                .short('k')
+                .long("kill-after")
+                .value_name("DURATION")
+                .help("Also send a KILL signal if COMMAND is still running this long after the first signal"),
+        )
+        .arg(
+            Arg::new("foreground")
+                .long("foreground")
+                .action(ArgAction::SetTrue)
+                .help("Let COMMAND read from the TTY and get TTY signals; its children are not timed out"),
+        )
+        .arg(
+            Arg::new("preserve-status")
+                .long("preserve-status")
+                .action(ArgAction::SetTrue)
+                .help("Exit with the same status as COMMAND, even when it times out"),
+        )
+        .arg(
+            Arg::new("duration")
+                .value_name("DURATION")
+                .help("Time limit for the command")
+                .required(true),
+        )
+        .arg(
+            Arg::new("command")
+                .value_name("COMMAND")
+                .help("Command to run, followed by its arguments")
+                .required(true)
+                .num_args(1..)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .try_get_matches()
+        .unwrap_or_else(|err| {
+            let code = if err.use_stderr() { EXIT_FAILURE } else { 0 };
+            let _ = err.print();
+            process::exit(code);
+        });
+
+    let signal = match matches.get_one::<String>("signal") {
+        Some(spec) => parse_signal(spec).unwrap_or_else(|| {
+            usage_error_status("timeout", EXIT_FAILURE, &format!("'{}': invalid signal", spec))
+        }),
+        None => libc::SIGTERM,
+    };
+
+    let duration = duration_arg(matches.get_one::<String>("duration").unwrap());
+    let mut kill_after = matches
+        .get_one::<String>("kill-after")
+        .map_or(0.0, |spec| duration_arg(spec));
+    let foreground = matches.get_flag("foreground");
+    let preserve_status = matches.get_flag("preserve-status");
+
+    // In a group of its own with the command, timeout can signal whatever
+    // the command starts too; in the foreground it must stay in the
+    // terminal's group instead
+    if !foreground {
+        unsafe { libc::setpgid(0, 0) };
+    }
+    // Not stopped when the command, in the background, touches the TTY
+    unsafe {
+        libc::signal(libc::SIGTTIN, libc::SIG_IGN);
+        libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+    }
+
+    let mut command = matches.get_many::<OsString>("command").unwrap();
+    let program = command.next().unwrap();
+
+    // The signals stay blocked except inside sigsuspend, so none can slip in
+    // between checking for them and waiting
+    let old_mask = install_handlers().unwrap_or_else(|err| {
+        fail_status(
+            "timeout",
+            EXIT_FAILURE,
+            &format!("failed to set up signal handling: {}", strerror(&err)),
+        )
+    });
+
+    let mut child = process::Command::new(program);
+    child.args(command);
+    // The command must not inherit the blocked or ignored signals
+    unsafe {
+        child.pre_exec(move || {
+            libc::signal(libc::SIGTTIN, libc::SIG_DFL);
+            libc::signal(libc::SIGTTOU, libc::SIG_DFL);
+            if libc::sigprocmask(libc::SIG_SETMASK, &old_mask, ptr::null_mut()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    let child = child
+        .spawn()
+        .unwrap_or_else(|err| {
+            let code = if err.kind() == io::ErrorKind::NotFound {
+                EXIT_ENOENT
+            } else {
+                EXIT_CANNOT_INVOKE
+            };
+            eprintln!("timeout: failed to run command '{}': {}", program.to_string_lossy(), strerror(&err));
+            process::exit(code);
+        });
+    let pid = child.id() as libc::pid_t;
+
+    if let Err(err) = start_timer(duration) {
+        eprintln!("timeout: failed to set the timer: {}", strerror(&err));
+        unsafe { libc::kill(pid, libc::SIGKILL) };
+        process::exit(EXIT_FAILURE);
+    }
+
+    let mut timed_out = false;
+    let status = loop {
+        let mut status = 0;
+        match unsafe { libc::waitpid(pid, &mut status, libc::WNOHANG) } {
+            0 => {}
+            -1 => {
+                let err = io::Error::last_os_error();
+                fail_status(
+                    "timeout",
+                    EXIT_FAILURE,
+                    &format!("error waiting for command: {}", strerror(&err)),
+                );
+            }
+            _ => break status,
+        }
+
+        let received = RECEIVED.swap(0, Ordering::SeqCst);
+        let to_send = match received {
+            0 => None,
+            // The second alarm is the end of --kill-after
+            libc::SIGALRM if timed_out => Some(libc::SIGKILL),
+            libc::SIGALRM => {
+                timed_out = true;
+                Some(signal)
+            }
+            forwarded => Some(forwarded),
+        };
+        if let Some(to_send) = to_send {
+            send_signal(pid, to_send, foreground);
+            if kill_after > 0.0 && to_send != libc::SIGKILL {
+                if let Err(err) = start_timer(kill_after) {
+                    eprintln!("timeout: failed to set the timer: {}", strerror(&err));
+                }
+                kill_after = 0.0;
+            }
+        }
+
+        unsafe { libc::sigsuspend(&old_mask) };
+    };
+
+    if libc::WIFSIGNALED(status) {
+        let child_signal = libc::WTERMSIG(status);
+        if !timed_out {
+            exit_like(child_signal);
+        }
+        // A command that had to be killed outright is reported as such,
+        // with the shell's status for it
+        if preserve_status || child_signal == libc::SIGKILL {
+            process::exit(128 + child_signal);
+        }
+    }
+
+    if timed_out && !preserve_status {
+        process::exit(EXIT_TIMED_OUT);
+    }
+    process::exit(libc::WEXITSTATUS(status));
+}
+
+fn duration_arg(spec: &str) -> f64 {
+    parse_duration(spec).unwrap_or_else(|| {
+        usage_error_status("timeout", EXIT_FAILURE, &format!("invalid time interval '{}'", spec))
+    })
+}
+
+// Dies from the same signal as the command so the caller sees the same
+// status, falling back to the shell's 128+N convention
+fn exit_like(signal: i32) -> ! {
+    unsafe {
+        let no_core = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        if libc::setrlimit(libc::RLIMIT_CORE, &no_core) == 0 {
+            let mut unblock: libc::sigset_t = mem::zeroed();
+            libc::sigemptyset(&mut unblock);
+            libc::sigaddset(&mut unblock, signal);
+            libc::signal(signal, libc::SIG_DFL);
+            libc::sigprocmask(libc::SIG_UNBLOCK, &unblock, ptr::null_mut());
+            libc::raise(signal);
+        }
+    }
+    process::exit(128 + signal);
+}
+
+fn install_handlers() -> io::Result<libc::sigset_t> {
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = record_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        let mut blocked: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut blocked);
+
+        for &signal in FORWARDED_SIGNALS.iter().chain(&[libc::SIGALRM, libc::SIGCHLD]) {
+            if libc::sigaction(signal, &action, ptr::null_mut()) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            libc::sigaddset(&mut blocked, signal);
+        }
+
+        let mut old_mask: libc::sigset_t = mem::zeroed();
+        if libc::sigprocmask(libc::SIG_BLOCK, &blocked, &mut old_mask) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(old_mask)
+    }
+}
+
+fn start_timer(seconds: f64) -> io::Result<()> {
+    if seconds <= 0.0 {
+        return Ok(());
+    }
+
+    // setitimer takes a time_t; longer limits are as good as none
+    let seconds = seconds.min(libc::time_t::MAX as f64 / 2.0);
+    let whole = seconds.trunc();
+    let mut micros = ((seconds - whole) * 1_000_000.0).ceil() as libc::suseconds_t;
+    let mut whole = whole as libc::time_t;
+    if micros >= 1_000_000 {
+        whole += 1;
+        micros = 0;
+    } else if whole == 0 && micros == 0 {
+        micros = 1;
+    }
+
+    let timer = libc::itimerval {
+        it_interval: libc::timeval { tv_sec: 0, tv_usec: 0 },
+        it_value: libc::timeval {
+            tv_sec: whole,
+            tv_usec: micros,
+        },
+    };
+    if unsafe { libc::setitimer(libc::ITIMER_REAL, &timer, ptr::null_mut()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// A stopped command would never act on the signal, so wake it up as well.
+// Outside --foreground the rest of the process group gets it too; timeout
+// ignores it from then on so it does not act on its own signal.
+fn send_signal(pid: libc::pid_t, signal: i32, foreground: bool) {
+    let wake = signal != libc::SIGKILL && signal != libc::SIGCONT;
+    unsafe {
+        libc::kill(pid, signal);
+        if !foreground {
+            // Ignoring SIGCHLD would reap the command behind our back
+            if signal != libc::SIGCHLD {
+                libc::signal(signal, libc::SIG_IGN);
+            }
+            libc::kill(0, signal);
+        }
+        if wake {
+            libc::kill(pid, libc::SIGCONT);
+            if !foreground {
+                libc::kill(0, libc::SIGCONT);
+            }
+        }
+    }
+}
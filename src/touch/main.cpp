@@ -5,15 +5,18 @@
 #include <sstream>
 #include <iomanip>
 #include <ctime>
+#include <cctype>
+#include <cerrno>
+#include <cstring>
+#include <cstdlib>
 #include <sys/types.h>
 #include <sys/stat.h>
+#include <fcntl.h>
 #include <unistd.h>
 #include <stdexcept>
 
 #ifdef _WIN32
 # include <windows.h>
-#else
-# include <utime.h>
 #endif
 
 const std::string VERSION = "1.0.0";
@@ -24,124 +27,432 @@ USAGE:
    touch [OPTIONS] FILE...
 
 OPTIONS:
-   -a             change only the access time
-   -c             do not create any files
-   -m             change only the modification time
-   -r REF         use this file's times instead of current time
-   -t STAMP       use [[CC]YY]MMDDhhmm[.ss] instead of current time
-   -h, --help     display this help and exit
-   -v             output version information and exit
+   -a                   change only the access time
+   -c, --no-create      do not create any files
+   -d, --date=STRING    use the time STRING describes instead of current time
+   -h, --no-dereference change the times of symbolic links, not what they
+                        point to (never creates files)
+   -m                   change only the modification time
+   -r, --reference=REF  use this file's times instead of current time
+   -t STAMP             use [[CC]YY]MMDDhhmm[.ss] instead of current time
+       --help           display this help and exit
+   -v, --version        output version information and exit
+
+DATE STRINGS:
+   -d takes "now", "today", "yesterday", "tomorrow", "@SECONDS",
+   ISO dates and times such as "2023-01-01 12:00:30.5" or
+   "2023-01-01T12:00Z" (zones as Z, UTC, GMT or +hh:mm), and relative
+   items such as "2 days ago", "+3 hours" or "next week". With -r, the
+   relative items apply to the reference file's times.
 
 Examples:
    touch file.txt             Create file.txt or update its timestamps
    touch -c existing.txt     Update timestamps only if file exists
    touch -r ref.txt file     Copy timestamps from ref.txt to file
    touch -t 202301011200 f   Set timestamp to Jan 1, 2023, 12:00
+   touch -d '1 hour ago' f   Set timestamp to an hour ago
+   touch -h link             Update the link itself, not its target
 )";
 
 struct Options {
     bool access = false;
     bool create = true;
     bool modify = false;
+    bool noDereference = false;
     std::string reference = "";
     std::string timestamp = "";
+    std::string date = "";
+    bool haveDate = false;
     bool help = false;
     bool version = false;
     std::vector<std::string> files;
 };
 
-time_t parseTimestamp(const std::string& stamp) {
+// The times to set; UTIME_NOW in tv_nsec means the current time
+struct Times {
+    struct timespec access;
+    struct timespec modify;
+};
+
+[[noreturn]] void usageError(const std::string& message) {
+    std::cerr << "touch: " << message << std::endl;
+    std::cerr << "Try 'touch --help' for more information." << std::endl;
+    exit(1);
+}
+
+[[noreturn]] void invalidDate(const std::string& text) {
+    std::cerr << "touch: invalid date format '" << text << "'" << std::endl;
+    exit(1);
+}
+
+// Seconds since the epoch for a broken-down local time, refusing dates
+// that do not exist such as February 30 or times skipped by DST changes
+bool localTime(int year, int month, int day, int hour, int minute, int second, time_t& result) {
     std::tm t{};
-    std::string s = stamp;
-
-    if (s.length() >= 12) { // CCYYMMDDhhmm
-        std::istringstream ss(s.substr(0, 4));
-        ss >> t.tm_year;
-        if (ss.fail()) throw std::runtime_error("Invalid year in timestamp");
-        t.tm_year -= 1900; // tm_year is years since 1900
-        s = s.substr(4);
-    } else if (s.length() >= 10) { // YYMMDDhhmm
-        int year_short;
-        std::istringstream ss(s.substr(0, 2));
-        ss >> year_short;
-        if (ss.fail()) throw std::runtime_error("Invalid year in timestamp");
-        t.tm_year = (year_short >= 70 ? 1900 : 2000) + year_short - 1900;
-        s = s.substr(2);
+    t.tm_year = year - 1900;
+    t.tm_mon = month - 1;
+    t.tm_mday = day;
+    t.tm_hour = hour;
+    t.tm_min = minute;
+    t.tm_sec = second;
+    t.tm_isdst = -1; // Let the system determine if DST is in effect
+
+    result = std::mktime(&t);
+    return t.tm_year == year - 1900 && t.tm_mon == month - 1 && t.tm_mday == day &&
+           t.tm_hour == hour && t.tm_min == minute && t.tm_sec == second;
+}
+
+// Seconds since the epoch for a time given in UTC
+time_t utcTime(int year, int month, int day, int hour, int minute, int second) {
+    // Days from 1970-01-01 to the given date, from the proleptic Gregorian calendar
+    long long y = year - (month <= 2);
+    long long era = (y >= 0 ? y : y - 399) / 400;
+    long long yearOfEra = y - era * 400;
+    long long dayOfYear = (153 * (month + (month > 2 ? -3 : 9)) + 2) / 5 + day - 1;
+    long long dayOfEra = yearOfEra * 365 + yearOfEra / 4 - yearOfEra / 100 + dayOfYear;
+    long long days = era * 146097 + dayOfEra - 719468;
+    return static_cast<time_t>(days * 86400 + hour * 3600 + minute * 60 + second);
+}
+
+bool allDigits(const std::string& s) {
+    if (s.empty()) return false;
+    for (char c : s) {
+        if (!std::isdigit(static_cast<unsigned char>(c))) return false;
     }
+    return true;
+}
 
-    if (s.length() >= 8) {
-        std::istringstream ss_month(s.substr(0, 2));
-        ss_month >> t.tm_mon;
-        if (ss_month.fail() || t.tm_mon < 1 || t.tm_mon > 12) throw std::runtime_error("Invalid month in timestamp");
-        t.tm_mon--; // tm_mon is 0-indexed
+// [[CC]YY]MMDDhhmm[.ss], in local time
+struct timespec parseTimestamp(const std::string& stamp) {
+    std::string digits = stamp;
+    int second = 0;
+
+    size_t dot = stamp.find('.');
+    if (dot != std::string::npos) {
+        std::string seconds = stamp.substr(dot + 1);
+        if (seconds.length() != 2 || !allDigits(seconds)) invalidDate(stamp);
+        second = std::stoi(seconds);
+        digits = stamp.substr(0, dot);
+    }
+    if (!allDigits(digits)) invalidDate(stamp);
+
+    auto field = [&](size_t start) { return std::stoi(digits.substr(start, 2)); };
+    int year;
+    size_t rest;
+    if (digits.length() == 12) {
+        year = std::stoi(digits.substr(0, 4));
+        rest = 4;
+    } else if (digits.length() == 10) {
+        // POSIX: 69-99 are 1969-1999, 00-68 are 2000-2068
+        int yy = field(0);
+        year = yy >= 69 ? 1900 + yy : 2000 + yy;
+        rest = 2;
+    } else if (digits.length() == 8) {
+        time_t now = time(nullptr);
+        std::tm current{};
+        localtime_r(&now, &current);
+        year = current.tm_year + 1900;
+        rest = 0;
+    } else {
+        invalidDate(stamp);
+    }
 
-        std::istringstream ss_day(s.substr(2, 2));
-        ss_day >> t.tm_mday;
-        if (ss_day.fail() || t.tm_mday < 1 || t.tm_mday > 31) throw std::runtime_error("Invalid day in timestamp");
+    struct timespec result{};
+    // A leap second, as POSIX allows, is the first second of the next minute
+    bool leap = second == 60;
+    if (!localTime(year, field(rest), field(rest + 2), field(rest + 4), field(rest + 6),
+                   leap ? 59 : second, result.tv_sec)) {
+        invalidDate(stamp);
+    }
+    result.tv_sec += leap;
+    return result;
+}
 
-        std::istringstream ss_hour(s.substr(4, 2));
-        ss_hour >> t.tm_hour;
-        if (ss_hour.fail() || t.tm_hour < 0 || t.tm_hour > 23) throw std::runtime_error("Invalid hour in timestamp");
+// Reads "hh:mm[:ss[.frac]]" from the front of TEXT; returns the characters used
+size_t parseClock(const std::string& text, int& hour, int& minute, int& second, long& nsec) {
+    size_t i = 0;
+    auto number = [&](size_t maxDigits) {
+        size_t start = i;
+        while (i < text.length() && i - start < maxDigits &&
+               std::isdigit(static_cast<unsigned char>(text[i]))) {
+            i++;
+        }
+        return i == start ? -1 : std::stoi(text.substr(start, i - start));
+    };
+
+    hour = number(2);
+    if (hour < 0 || i >= text.length() || text[i] != ':') return 0;
+    i++;
+    minute = number(2);
+    if (minute < 0) return 0;
+    second = 0;
+    nsec = 0;
+    if (i < text.length() && text[i] == ':') {
+        i++;
+        second = number(2);
+        if (second < 0) return 0;
+        if (i < text.length() && (text[i] == '.' || text[i] == ',')) {
+            i++;
+            long scale = 100000000;
+            size_t start = i;
+            while (i < text.length() && std::isdigit(static_cast<unsigned char>(text[i]))) {
+                nsec += (text[i] - '0') * scale;
+                scale /= 10;
+                i++;
+            }
+            if (i == start) return 0;
+        }
+    }
+    if (hour > 23 || minute > 59 || second > 59) return 0;
+    return i;
+}
 
-        std::istringstream ss_minute(s.substr(6, 2));
-        ss_minute >> t.tm_min;
-        if (ss_minute.fail() || t.tm_min < 0 || t.tm_min > 59) throw std::runtime_error("Invalid minute in timestamp");
+// A zone as Z, UTC, GMT, +hh, +hhmm or +hh:mm, in seconds east of UTC
+bool parseZone(const std::string& text, long& offset) {
+    if (text == "z" || text == "utc" || text == "gmt") {
+        offset = 0;
+        return true;
+    }
+    if (text.length() < 2 || (text[0] != '+' && text[0] != '-')) return false;
+
+    std::string digits = text.substr(1);
+    size_t colon = digits.find(':');
+    if (colon != std::string::npos) digits.erase(colon, 1);
+    if (!allDigits(digits) || (digits.length() != 2 && digits.length() != 4)) return false;
+    if (colon != std::string::npos && colon != 2) return false;
+
+    int hours = std::stoi(digits.substr(0, 2));
+    int minutes = digits.length() == 4 ? std::stoi(digits.substr(2)) : 0;
+    if (hours > 24 || minutes > 59) return false;
+    offset = (hours * 3600L + minutes * 60L) * (text[0] == '-' ? -1 : 1);
+    return true;
+}
+
+// Seconds a relative unit stands for, with months and years counted apart
+bool parseUnit(std::string word, long& seconds, int& months) {
+    if (word.length() > 1 && word.back() == 's') word.pop_back();
+    seconds = 0;
+    months = 0;
+    if (word == "year") months = 12;
+    else if (word == "month") months = 1;
+    else if (word == "fortnight") seconds = 14 * 86400L;
+    else if (word == "week") seconds = 7 * 86400L;
+    else if (word == "day") seconds = 86400L;
+    else if (word == "hour") seconds = 3600L;
+    else if (word == "minute" || word == "min") seconds = 60L;
+    else if (word == "second" || word == "sec") seconds = 1L;
+    else return false;
+    return true;
+}
+
+// The -d date strings described in the help, relative to BASE
+struct timespec parseDate(const std::string& text, struct timespec base) {
+    std::string lower;
+    for (char c : text) lower += static_cast<char>(std::tolower(static_cast<unsigned char>(c)));
+
+    std::vector<std::string> words;
+    std::istringstream split(lower);
+    for (std::string word; split >> word; ) words.push_back(word);
+
+    struct timespec result = base;
+    if (words.size() == 1 && words[0][0] == '@') {
+        // @SECONDS[.FRACTION]
+        std::string number = words[0].substr(1);
+        char* end = nullptr;
+        errno = 0;
+        long double value = std::strtold(number.c_str(), &end);
+        if (number.empty() || *end != '\0' || errno != 0 ||
+            !(std::isdigit(static_cast<unsigned char>(number[0])) || number[0] == '-' || number[0] == '+')) {
+            invalidDate(text);
+        }
+        long double whole = value < 0 ? -static_cast<long double>(static_cast<long long>(-value + 0.9999999999L))
+                                      : static_cast<long double>(static_cast<long long>(value));
+        result.tv_sec = static_cast<time_t>(whole);
+        result.tv_nsec = static_cast<long>((value - whole) * 1000000000.0L + 0.5L);
+        if (result.tv_nsec >= 1000000000L) {
+            result.tv_sec++;
+            result.tv_nsec -= 1000000000L;
+        }
+        return result;
+    }
 
-        if (s.length() > 8 && s[8] == '.') {
-            std::istringstream ss_second(s.substr(9));
-            ss_second >> t.tm_sec;
-            if (ss_second.fail() || t.tm_sec < 0 || t.tm_sec > 59) throw std::runtime_error("Invalid second in timestamp");
+    std::tm now{};
+    localtime_r(&base.tv_sec, &now);
+    int year = now.tm_year + 1900, month = now.tm_mon + 1, day = now.tm_mday;
+    int hour = now.tm_hour, minute = now.tm_min, second = now.tm_sec;
+    long nsec = base.tv_nsec;
+    bool dateSeen = false, timeSeen = false, zoneSeen = false, relativeSeen = false;
+    long zone = 0;
+    long relativeSeconds = 0;
+    int relativeMonths = 0;
+    long lastSeconds = 0;
+    int lastMonths = 0;
+    bool canAgo = false;
+
+    for (size_t i = 0; i < words.size(); i++) {
+        std::string word = words[i];
+        long seconds;
+        int months;
+
+        if (word == "ago") {
+            if (!canAgo) invalidDate(text);
+            relativeSeconds -= 2 * lastSeconds;
+            relativeMonths -= 2 * lastMonths;
+            canAgo = false;
+            continue;
+        }
+        canAgo = false;
+
+        if (word == "now" || word == "today" || word == "yesterday" || word == "tomorrow") {
+            lastSeconds = word == "yesterday" ? -86400L : word == "tomorrow" ? 86400L : 0;
+            lastMonths = 0;
+        } else if (parseUnit(word, seconds, months)) {
+            // A unit on its own counts once
+            lastSeconds = seconds;
+            lastMonths = months;
+        } else if ((word == "next" || word == "last" || word == "this") && i + 1 < words.size() &&
+                   parseUnit(words[i + 1], seconds, months)) {
+            int count = word == "next" ? 1 : word == "last" ? -1 : 0;
+            lastSeconds = seconds * count;
+            lastMonths = months * count;
+            i++;
+        } else if ((allDigits(word) || ((word[0] == '+' || word[0] == '-') && allDigits(word.substr(1)))) &&
+                   i + 1 < words.size() && parseUnit(words[i + 1], seconds, months)) {
+            long count = std::stol(word);
+            lastSeconds = seconds * count;
+            lastMonths = static_cast<int>(months * count);
+            i++;
+        } else if (!dateSeen && !timeSeen && word.length() >= 8 && word[4] == '-' && word[7] == '-') {
+            // YYYY-MM-DD, perhaps with THH:MM[:SS[.frac]][zone] attached
+            std::string date = word.substr(0, 10);
+            if (!allDigits(date.substr(0, 4)) || !allDigits(date.substr(5, 2)) || date.length() != 10 ||
+                !allDigits(date.substr(8, 2))) {
+                invalidDate(text);
+            }
+            year = std::stoi(date.substr(0, 4));
+            month = std::stoi(date.substr(5, 2));
+            day = std::stoi(date.substr(8, 2));
+            dateSeen = true;
+
+            if (word.length() > 10) {
+                if (word[10] != 't') invalidDate(text);
+                std::string clock = word.substr(11);
+                size_t used = parseClock(clock, hour, minute, second, nsec);
+                if (used == 0) invalidDate(text);
+                timeSeen = true;
+                if (used < clock.length()) {
+                    if (!parseZone(clock.substr(used), zone)) invalidDate(text);
+                    zoneSeen = true;
+                }
+            }
+            continue;
+        } else if (!timeSeen && word.find(':') != std::string::npos) {
+            size_t used = parseClock(word, hour, minute, second, nsec);
+            if (used == 0) invalidDate(text);
+            timeSeen = true;
+            if (used < word.length()) {
+                if (!parseZone(word.substr(used), zone)) invalidDate(text);
+                zoneSeen = true;
+            }
+            continue;
+        } else if (!zoneSeen && parseZone(word, zone)) {
+            zoneSeen = true;
+            continue;
         } else {
-            t.tm_sec = 0;
+            invalidDate(text);
         }
-    } else if (!s.empty()) {
-        throw std::runtime_error("Invalid timestamp format");
-    } else {
-        t.tm_sec = 0;
+
+        relativeSeconds += lastSeconds;
+        relativeMonths += lastMonths;
+        relativeSeen = true;
+        canAgo = true;
     }
 
-    t.tm_isdst = -1; // Let the system determine if DST is in effect
-    std::time_t tt = std::mktime(&t);
-    if (tt == -1) {
-        throw std::runtime_error("Error converting timestamp");
+    // Anything but relative items means the start of the day unless a
+    // time is given
+    if (!timeSeen && (dateSeen || zoneSeen || !relativeSeen)) {
+        hour = minute = second = 0;
+        nsec = 0;
     }
-    return tt;
+
+    if (zoneSeen) {
+        if (month < 1 || month > 12 || day < 1 || day > 31) invalidDate(text);
+        result.tv_sec = utcTime(year, month, day, hour, minute, second) - zone;
+        time_t check = result.tv_sec + zone;
+        std::tm parts{};
+        gmtime_r(&check, &parts);
+        if (parts.tm_mday != day) invalidDate(text);
+    } else if (!localTime(year, month, day, hour, minute, second, result.tv_sec)) {
+        invalidDate(text);
+    }
+
+    if (relativeMonths != 0) {
+        // Months and years move the calendar date, keeping the time of day
+        std::tm parts{};
+        localtime_r(&result.tv_sec, &parts);
+        parts.tm_mon += relativeMonths;
+        parts.tm_isdst = -1;
+        result.tv_sec = std::mktime(&parts);
+    }
+    result.tv_sec += relativeSeconds;
+    result.tv_nsec = nsec;
+    return result;
+}
+
+// Splits "--name=value" so long options can take values either way
+bool takeValue(int argc, char* argv[], int& i, const std::string& arg,
+               const std::string& shortName, const std::string& longName, std::string& value) {
+    if (arg == shortName || arg == longName) {
+        if (i + 1 >= argc) {
+            usageError("option '" + arg + "' requires an argument");
+        }
+        value = argv[++i];
+        return true;
+    }
+    if (arg.compare(0, longName.length() + 1, longName + "=") == 0) {
+        value = arg.substr(longName.length() + 1);
+        return true;
+    }
+    if (arg.length() > 2 && arg.compare(0, 2, shortName) == 0 && arg[1] != '-') {
+        value = arg.substr(2);
+        return true;
+    }
+    return false;
 }
 
 Options parseOptions(int argc, char *argv[]) {
     Options options;
+    bool endOfOptions = false;
     for (int i = 1; i < argc; ++i) {
         std::string arg = argv[i];
-        if (arg == "-a") {
+        std::string value;
+        if (endOfOptions || arg == "-" || arg[0] != '-') {
+            options.files.push_back(arg);
+        } else if (arg == "--") {
+            endOfOptions = true;
+        } else if (arg == "-a") {
             options.access = true;
-        } else if (arg == "-c") {
+        } else if (arg == "-c" || arg == "--no-create") {
             options.create = false;
         } else if (arg == "-m") {
             options.modify = true;
-        } else if (arg == "-r") {
-            if (i + 1 < argc) {
-                options.reference = argv[++i];
-            } else {
-                std::cerr << "touch: option '-r' requires an argument" << std::endl;
-                exit(1);
-            }
-        } else if (arg == "-t") {
-            if (i + 1 < argc) {
-                options.timestamp = argv[++i];
-            } else {
-                std::cerr << "touch: option '-t' requires an argument" << std::endl;
-                exit(1);
-            }
-        } else if (arg == "-h" || arg == "--help") {
+        } else if (arg == "-h" || arg == "--no-dereference") {
+            options.noDereference = true;
+        } else if (takeValue(argc, argv, i, arg, "-r", "--reference", value)) {
+            options.reference = value;
+        } else if (takeValue(argc, argv, i, arg, "-t", "-t", value)) {
+            options.timestamp = value;
+        } else if (takeValue(argc, argv, i, arg, "-d", "--date", value)) {
+            options.date = value;
+            options.haveDate = true;
+        } else if (arg == "--help") {
             options.help = true;
-        } else if (arg == "-v") {
+        } else if (arg == "-v" || arg == "--version") {
             options.version = true;
-        } else if (arg[0] == '-') {
+        } else {
             std::cerr << "touch: invalid option '" << arg << "'" << std::endl;
             std::cerr << "Try 'touch --help' for more information." << std::endl;
             exit(1);
-        } else {
-            options.files.push_back(arg);
         }
     }
 
@@ -150,207 +461,109 @@ Options parseOptions(int argc, char *argv[]) {
         options.modify = true;
     }
 
+    // -d can adjust the -r times, but -t stands alone
+    if (!options.timestamp.empty() && (options.haveDate || !options.reference.empty())) {
+        usageError("cannot specify times from more than one source");
+    }
+
     return options;
 }
 
-bool fileExists(const std::string& path) {
-    std::ifstream f(path.c_str());
-    return f.good();
-}
+// The times every file gets, worked out once before touching any of them
+Times resolveTimes(const Options& options) {
+    Times times;
+    times.access.tv_sec = 0;
+    times.access.tv_nsec = UTIME_NOW;
+    times.modify = times.access;
 
-time_t getLastAccessTime(const std::string& path) {
-    struct stat fileInfo;
-    if (stat(path.c_str(), &fileInfo) == 0) {
-#ifdef _WIN32
-        FILETIME ftAccess;
-        HANDLE hFile = CreateFileA(path.c_str(), GENERIC_READ, FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL, NULL);
-        if (hFile != INVALID_HANDLE_VALUE) {
-            if (GetFileTime(hFile, NULL, &ftAccess, NULL)) {
-                ULARGE_INTEGER ui;
-                ui.LowPart = ftAccess.dwLowDateTime;
-                ui.HighPart = ftAccess.dwHighDateTime;
-                CloseHandle(hFile);
-                return (ui.QuadPart - 116444736000000000ULL) / 10000000ULL;
-            }
-            CloseHandle(hFile);
+    if (!options.reference.empty()) {
+        struct stat info;
+        int result = options.noDereference ? lstat(options.reference.c_str(), &info)
+                                           : stat(options.reference.c_str(), &info);
+        if (result != 0) {
+            std::cerr << "touch: failed to get attributes of '" << options.reference
+                      << "': " << std::strerror(errno) << std::endl;
+            exit(1);
         }
-        return 0; // Error case
-#else
-        return fileInfo.st_atime;
-#endif
-    } else {
-        return 0; // Error case
-    }
-}
-
-time_t getLastModificationTime(const std::string& path) {
-    struct stat fileInfo;
-    if (stat(path.c_str(), &fileInfo) == 0) {
-#ifdef _WIN32
-        FILETIME ftWrite;
-        HANDLE hFile = CreateFileA(path.c_str(), GENERIC_WRITE, FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL, NULL);
-        if (hFile != INVALID_HANDLE_VALUE) {
-            if (GetFileTime(hFile, NULL, NULL, &ftWrite)) {
-                ULARGE_INTEGER ui;
-                ui.LowPart = ftWrite.dwLowDateTime;
-                ui.HighPart = ftWrite.dwHighDateTime;
-                CloseHandle(hFile);
-                return (ui.QuadPart - 116444736000000000ULL) / 10000000ULL;
-            }
-            CloseHandle(hFile);
+        times.access = info.st_atim;
+        times.modify = info.st_mtim;
+        if (options.haveDate) {
+            times.access = parseDate(options.date, times.access);
+            times.modify = parseDate(options.date, times.modify);
         }
-        return 0; // Error case
-#else
-        return fileInfo.st_mtime;
-#endif
-    } else {
-        return 0; // Error case
+    } else if (!options.timestamp.empty()) {
+        times.access = parseTimestamp(options.timestamp);
+        times.modify = times.access;
+    } else if (options.haveDate) {
+        struct timespec now;
+        clock_gettime(CLOCK_REALTIME, &now);
+        times.access = parseDate(options.date, now);
+        times.modify = times.access;
     }
+
+    // Leave alone whichever of the two was not asked for
+    if (!options.access) times.access.tv_nsec = UTIME_OMIT;
+    if (!options.modify) times.modify.tv_nsec = UTIME_OMIT;
+    return times;
 }
 
-bool setFileTimes(const std::string& path, time_t accessTime, time_t modifyTime) {
 #ifdef _WIN32
-    FILETIME ftAccess, ftWrite;
-    ULARGE_INTEGER uiAccess, uiWrite;
-
-    uiAccess.QuadPart = (static_cast<ULONGLONG>(accessTime) * 10000000ULL) + 116444736000000000ULL;
-    ftAccess.dwLowDateTime = uiAccess.LowPart;
-    ftAccess.dwHighDateTime = uiAccess.HighPart;
-
-    uiWrite.QuadPart = (static_cast<ULONGLONG>(modifyTime) * 10000000ULL) + 116444736000000000ULL;
-    ftWrite.dwLowDateTime = uiWrite.LowPart;
-    ftWrite.dwHighDateTime = uiWrite.HighPart;
+bool setFileTimes(const std::string& path, const Times& times, bool) {
+    auto toFileTime = [](const struct timespec& ts) {
+        time_t seconds = ts.tv_nsec == UTIME_NOW ? time(nullptr) : ts.tv_sec;
+        ULARGE_INTEGER ui;
+        ui.QuadPart = (static_cast<ULONGLONG>(seconds) * 10000000ULL) + 116444736000000000ULL;
+        FILETIME ft;
+        ft.dwLowDateTime = ui.LowPart;
+        ft.dwHighDateTime = ui.HighPart;
+        return ft;
+    };
+    FILETIME ftAccess = toFileTime(times.access);
+    FILETIME ftWrite = toFileTime(times.modify);
 
     HANDLE hFile = CreateFileA(path.c_str(), GENERIC_WRITE, FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL, NULL);
     if (hFile != INVALID_HANDLE_VALUE) {
-        BOOL result = SetFileTime(hFile, NULL, &ftAccess, &ftWrite);
+        BOOL result = SetFileTime(hFile, NULL,
+                                  times.access.tv_nsec == UTIME_OMIT ? NULL : &ftAccess,
+                                  times.modify.tv_nsec == UTIME_OMIT ? NULL : &ftWrite);
         CloseHandle(hFile);
         return result != 0;
     }
     return false;
-#else
-    struct utimbuf times;
-    times.actime = accessTime;
-    times.modtime = modifyTime;
-    return utime(path.c_str(), &times) == 0;
-#endif
 }
-
-bool setLastAccessTime(const std::string& path, time_t accessTime) {
-#ifdef _WIN32
-    FILETIME ftAccess;
-    ULARGE_INTEGER uiAccess;
-
-    uiAccess.QuadPart = (static_cast<ULONGLONG>(accessTime) * 10000000ULL) + 116444736000000000ULL;
-    ftAccess.dwLowDateTime = uiAccess.LowPart;
-    ftAccess.dwHighDateTime = uiAccess.HighPart;
-
-    HANDLE hFile = CreateFileA(path.c_str(), GENERIC_WRITE, FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL, NULL);
-    if (hFile != INVALID_HANDLE_VALUE) {
-        FILETIME ftCreation, ftWrite;
-        if (GetFileTime(hFile, &ftCreation, NULL, &ftWrite)) {
-            BOOL result = SetFileTime(hFile, &ftCreation, &ftAccess, &ftWrite);
-            CloseHandle(hFile);
-            return result != 0;
-        }
-        CloseHandle(hFile);
-    }
-    return false;
 #else
-    struct stat fileInfo;
-    if (stat(path.c_str(), &fileInfo) == 0) {
-        struct utimbuf times;
-        times.actime = accessTime;
-        times.modtime = fileInfo.st_mtime;
-        return utime(path.c_str(), &times) == 0;
-    }
-    return false;
-#endif
+bool setFileTimes(const std::string& path, const Times& times, bool noDereference) {
+    struct timespec both[2] = {times.access, times.modify};
+    return utimensat(AT_FDCWD, path.c_str(), both, noDereference ? AT_SYMLINK_NOFOLLOW : 0) == 0;
 }
-
-bool setLastModificationTime(const std::string& path, time_t modifyTime) {
-#ifdef _WIN32
-    FILETIME ftWrite;
-    ULARGE_INTEGER uiWrite;
-
-    uiWrite.QuadPart = (static_cast<ULONGLONG>(modifyTime) * 10000000ULL) + 116444736000000000ULL;
-    ftWrite.dwLowDateTime = uiWrite.LowPart;
-    ftWrite.dwHighDateTime = uiWrite.HighPart;
-
-    HANDLE hFile = CreateFileA(path.c_str(), GENERIC_WRITE, FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL, NULL);
-    if (hFile != INVALID_HANDLE_VALUE) {
-        FILETIME ftCreation, ftAccess;
-        if (GetFileTime(hFile, &ftCreation, &ftAccess, NULL)) {
-            BOOL result = SetFileTime(hFile, &ftCreation, &ftAccess, &ftWrite);
-            CloseHandle(hFile);
-            return result != 0;
-        }
-        CloseHandle(hFile);
-    }
-    return false;
-#else
-    struct stat fileInfo;
-    if (stat(path.c_str(), &fileInfo) == 0) {
-        struct utimbuf times;
-        times.actime = fileInfo.st_atime;
-        times.modtime = modifyTime;
-        return utime(path.c_str(), &times) == 0;
-    }
-    return false;
 #endif
-}
-
-void touchFile(const std::string& path, const Options& options) {
-    if (!fileExists(path) && !options.create) {
-        return;
-    }
-
-    time_t accessTime;
-    time_t modifyTime;
 
-    if (!options.reference.empty()) {
-        if (!fileExists(options.reference)) {
-            std::cerr << "Error: reference file '" << options.reference << "' not found" << std::endl;
-            return;
+bool touchFile(const std::string& path, const Options& options, const Times& times) {
+    // With -h nothing is created; a missing file is an error unless -c
+    if (!options.noDereference && options.create) {
+        int fd = open(path.c_str(), O_WRONLY | O_CREAT | O_NONBLOCK | O_NOCTTY, 0666);
+        if (fd == -1 && errno != EISDIR) {
+            int openError = errno;
+            // Existing files we cannot open may still have their times set
+            if (setFileTimes(path, times, false)) {
+                return true;
+            }
+            std::cerr << "touch: cannot touch '" << path << "': " << std::strerror(openError) << std::endl;
+            return false;
         }
-        accessTime = getLastAccessTime(options.reference);
-        modifyTime = getLastModificationTime(options.reference);
-    } else if (!options.timestamp.empty()) {
-        try {
-            time_t parsedTime = parseTimestamp(options.timestamp);
-            accessTime = parsedTime;
-            modifyTime = parsedTime;
-        } catch (const std::runtime_error& e) {
-            std::cerr << "Error: " << e.what() << std::endl;
-            exit(1);
+        if (fd != -1) {
+            close(fd);
         }
-    } else {
-        time(&accessTime);
-        modifyTime = accessTime;
     }
 
-    if (!fileExists(path)) {
-        std::ofstream f(path);
-        if (f.fail()) {
-            std::cerr << "Error touching file: " << path << std::endl;
-            return;
-        }
-        f.close();
-    }
-
-    if (options.access && options.modify) {
-        if (!setFileTimes(path, accessTime, modifyTime)) {
-            std::cerr << "Error touching file: " << path << std::endl;
-        }
-    } else if (options.access) {
-        if (!setLastAccessTime(path, accessTime)) {
-            std::cerr << "Error touching file: " << path << std::endl;
-        }
-    } else if (options.modify) {
-        if (!setLastModificationTime(path, modifyTime)) {
-            std::cerr << "Error touching file: " << path << std::endl;
+    if (!setFileTimes(path, times, options.noDereference)) {
+        if (errno == ENOENT && !options.create) {
+            return true;
         }
+        std::cerr << "touch: setting times of '" << path << "': " << std::strerror(errno) << std::endl;
+        return false;
     }
+    return true;
 }
 
 int main(int argc, char *argv[]) {
@@ -375,8 +588,13 @@ int main(int argc, char *argv[]) {
         std::cerr << "Try 'touch --help' for more information." << std::endl;
         return 1;
     }
+
+    Times times = resolveTimes(options);
+    int status = 0;
     for (const auto& file : options.files) {
-        touchFile(file, options);
+        if (!touchFile(file, options, times)) {
+            status = 1;
+        }
     }
-    return 0;
-}
\ No newline at end of file
+    return status;
+}
@@ -0,0 +1,233 @@
+// ASD CoreUtils - tr
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod set;
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, Command};
+use set::{Class, Element};
+use std::ffi::OsString;
+use std::io::{self, Read, Write};
+use std::os::unix::ffi::OsStrExt;
+
+fn main() {
+    let matches = Command::new("tr")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils tr - translate, squeeze, and/or delete characters")
+        .override_usage("tr [OPTION]... SET1 [SET2]")
+        .arg(
+            Arg::new("complement")
+                .short('c')
+                .short_alias('C')
+                .long("complement")
+                .action(ArgAction::SetTrue)
+                .help("Use the complement of SET1"),
+        )
+        .arg(
+            Arg::new("delete")
+                .short('d')
+                .long("delete")
+                .action(ArgAction::SetTrue)
+                .help("Delete characters in SET1, do not translate"),
+        )
+        .arg(
+            Arg::new("squeeze-repeats")
+                .short('s')
+                .long("squeeze-repeats")
+                .action(ArgAction::SetTrue)
+                .help("Replace each sequence of a repeated character that is listed in the last specified SET, with a single occurrence of that character"),
+        )
+        .arg(
+            Arg::new("truncate-set1")
+                .short('t')
+                .long("truncate-set1")
+                .action(ArgAction::SetTrue)
+                .help("First truncate SET1 to length of SET2"),
+        )
+        .arg(
+            Arg::new("sets")
+                .value_name("SET")
+                .num_args(0..)
+                .allow_hyphen_values(true)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .after_help(
+            "SETs are specified as strings of characters. Most represent themselves.\n\
+             Interpreted sequences are:\n\n  \
+             \\NNN            character with octal value NNN (1 to 3 octal digits)\n  \
+             \\\\              backslash\n  \
+             \\a \\b \\f \\n \\r \\t \\v  the usual control characters\n  \
+             CHAR1-CHAR2     all characters from CHAR1 to CHAR2 in ascending order\n  \
+             [CHAR*]         in SET2, copies of CHAR until length of SET1\n  \
+             [CHAR*REPEAT]   REPEAT copies of CHAR, REPEAT octal if starting with 0\n  \
+             [:alnum:] [:alpha:] [:blank:] [:cntrl:] [:digit:] [:graph:]\n  \
+             [:lower:] [:print:] [:punct:] [:space:] [:upper:] [:xdigit:]\n                  \
+             all characters of that class\n  \
+             [=CHAR=]        all characters which are equivalent to CHAR\n\n\
+             Translation occurs if -d is not given and both SET1 and SET2 appear.\n\
+             -t may be used only when translating. SET2 is extended to length of\n\
+             SET1 by repeating its last character as necessary. -s uses the last\n\
+             specified SET, and occurs after translation or deletion.",
+        )
+        .get_matches();
+
+    let sets: Vec<OsString> = matches
+        .get_many::<OsString>("sets")
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default();
+    let complement = matches.get_flag("complement");
+    let delete = matches.get_flag("delete");
+    let squeeze = matches.get_flag("squeeze-repeats");
+    let truncate = matches.get_flag("truncate-set1");
+
+    // How many sets this combination of options takes; -s alone takes
+    // either one or two
+    let wanted = match (delete, squeeze) {
+        (true, false) => 1,
+        (false, true) => sets.len().clamp(1, 2),
+        _ => 2,
+    };
+    if sets.is_empty() {
+        usage_error("tr", "missing operand");
+    }
+    if sets.len() < wanted {
+        let why = if delete {
+            "Two strings must be given when both deleting and squeezing repeats."
+        } else {
+            "Two strings must be given when translating."
+        };
+        usage_error(
+            "tr",
+            &format!(
+                "missing operand after '{}'\n{}",
+                sets[0].to_string_lossy(),
+                why
+            ),
+        );
+    }
+    if sets.len() > wanted {
+        let mut message = format!("extra operand '{}'", sets[wanted].to_string_lossy());
+        if delete && !squeeze {
+            message.push_str(
+                "\nOnly one string may be given when deleting without squeezing repeats.",
+            );
+        }
+        usage_error("tr", &message);
+    }
+
+    let elements1 = parse(&sets[0]);
+    let mut set1 = set::expand_set1(&elements1).unwrap_or_else(|message| fail("tr", &message));
+    if complement {
+        set1 = (0..=255).filter(|c| !set1.contains(c)).collect();
+    }
+
+    let mut map: [u8; 256] = std::array::from_fn(|c| c as u8);
+    let mut deleted = [false; 256];
+    let mut squeezed = [false; 256];
+    let translating = !delete && sets.len() == 2;
+
+    if translating {
+        let elements2 = parse(&sets[1]);
+        let (mut set2, fill) =
+            set::expand_set2(&elements2, set1.len()).unwrap_or_else(|message| fail("tr", &message));
+        check_classes(&elements1, &elements2, fill, complement);
+        if set2.is_empty() && !set1.is_empty() && !truncate {
+            fail("tr", "when not truncating set1, string2 must be non-empty");
+        }
+        if truncate {
+            set1.truncate(set2.len());
+        } else if let Some(&last) = set2.last() {
+            set2.resize(set1.len(), last);
+        }
+        for (&from, &to) in set1.iter().zip(&set2) {
+            map[usize::from(from)] = to;
+        }
+        if squeeze {
+            for &c in &set2 {
+                squeezed[usize::from(c)] = true;
+            }
+        }
+    } else if delete {
+        for &c in &set1 {
+            deleted[usize::from(c)] = true;
+        }
+        if squeeze {
+            let elements2 = parse(&sets[1]);
+            let set2 = set::expand_set1(&elements2).unwrap_or_else(|message| fail("tr", &message));
+            for &c in &set2 {
+                squeezed[usize::from(c)] = true;
+            }
+        }
+    } else {
+        for &c in &set1 {
+            squeezed[usize::from(c)] = true;
+        }
+    }
+
+    if let Err(err) = filter(&map, &deleted, &squeezed) {
+        fail("tr", &format!("write error: {}", strerror(&err)));
+    }
+}
+
+fn parse(text: &OsString) -> Vec<Element> {
+    set::parse(text.as_bytes()).unwrap_or_else(|message| fail("tr", &message))
+}
+
+// When translating, SET2 may only have [:lower:] and [:upper:], each one
+// opposite a [:lower:] or [:upper:] of SET1, as in case conversion
+fn check_classes(elements1: &[Element], elements2: &[Element], fill: usize, complement: bool) {
+    let classes1 = set::class_offsets(elements1, 0);
+    let classes2 = set::class_offsets(elements2, fill);
+    for &(offset, class) in &classes2 {
+        if class != Class::Lower && class != Class::Upper {
+            fail(
+                "tr",
+                "when translating, the only character classes that may appear in\n\
+                 string2 are 'upper' and 'lower'",
+            );
+        }
+        let aligned = !complement
+            && classes1.iter().any(|&(other_offset, other)| {
+                other_offset == offset && (other == Class::Lower || other == Class::Upper)
+            });
+        if !aligned {
+            fail("tr", "misaligned [:upper:] and/or [:lower:] construct");
+        }
+    }
+}
+
+fn filter(map: &[u8; 256], deleted: &[bool; 256], squeezed: &[bool; 256]) -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut output = Vec::with_capacity(buffer.len());
+    let mut last: Option<u8> = None;
+    loop {
+        let count = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(count) => count,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => fail("tr", &format!("read error: {}", strerror(&err))),
+        };
+
+        output.clear();
+        for &c in &buffer[..count] {
+            if deleted[usize::from(c)] {
+                continue;
+            }
+            let c = map[usize::from(c)];
+            if squeezed[usize::from(c)] && last == Some(c) {
+                continue;
+            }
+            output.push(c);
+            last = Some(c);
+        }
+        out.write_all(&output)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,282 @@
+// ASD CoreUtils - tr
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The SET operands: characters with backslash escapes, "a-z" ranges,
+// "[:class:]", "[=c=]" and, in SET2, the "[c*n]" repeats. Characters are
+// bytes, as in the C locale.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Class {
+    Alnum,
+    Alpha,
+    Blank,
+    Cntrl,
+    Digit,
+    Graph,
+    Lower,
+    Print,
+    Punct,
+    Space,
+    Upper,
+    Xdigit,
+}
+
+impl Class {
+    fn from_name(name: &[u8]) -> Option<Class> {
+        let class = match name {
+            b"alnum" => Class::Alnum,
+            b"alpha" => Class::Alpha,
+            b"blank" => Class::Blank,
+            b"cntrl" => Class::Cntrl,
+            b"digit" => Class::Digit,
+            b"graph" => Class::Graph,
+            b"lower" => Class::Lower,
+            b"print" => Class::Print,
+            b"punct" => Class::Punct,
+            b"space" => Class::Space,
+            b"upper" => Class::Upper,
+            b"xdigit" => Class::Xdigit,
+            _ => return None,
+        };
+        Some(class)
+    }
+
+    fn contains(self, c: u8) -> bool {
+        match self {
+            Class::Alnum => c.is_ascii_alphanumeric(),
+            Class::Alpha => c.is_ascii_alphabetic(),
+            Class::Blank => c == b' ' || c == b'\t',
+            Class::Cntrl => c.is_ascii_control(),
+            Class::Digit => c.is_ascii_digit(),
+            Class::Graph => c.is_ascii_graphic(),
+            Class::Lower => c.is_ascii_lowercase(),
+            Class::Print => c.is_ascii_graphic() || c == b' ',
+            Class::Punct => c.is_ascii_punctuation(),
+            Class::Space => c.is_ascii_whitespace() || c == b'\x0b',
+            Class::Upper => c.is_ascii_uppercase(),
+            Class::Xdigit => c.is_ascii_hexdigit(),
+        }
+    }
+
+    // The members in ascending order, so that [:lower:] and [:upper:]
+    // line up letter for letter
+    fn members(self) -> impl Iterator<Item = u8> {
+        (0..=255).filter(move |&c| self.contains(c))
+    }
+}
+
+pub enum Element {
+    Char(u8),
+    Range(u8, u8),
+    Class(Class),
+    // [c*n]; [c*] and [c*0] fill SET2 out to the length of SET1
+    Repeat(u8, Option<usize>),
+}
+
+pub fn parse(text: &[u8]) -> Result<Vec<Element>, String> {
+    let mut elements = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        if text[i] == b'[' {
+            if let Some((element, end)) = bracket(text, i)? {
+                elements.push(element);
+                i = end;
+                continue;
+            }
+        }
+
+        let start = i;
+        let low = next_char(text, &mut i);
+        if i + 1 < text.len() && text[i] == b'-' {
+            i += 1;
+            let high = next_char(text, &mut i);
+            if low > high {
+                return Err(format!(
+                    "range-endpoints of '{}' are in reverse collating sequence order",
+                    String::from_utf8_lossy(&text[start..i])
+                ));
+            }
+            elements.push(Element::Range(low, high));
+        } else {
+            elements.push(Element::Char(low));
+        }
+    }
+    Ok(elements)
+}
+
+// A "[:class:]", "[=c=]" or "[c*n]" at START, with where it ends, or None
+// when the '[' there is just a '['
+fn bracket(text: &[u8], start: usize) -> Result<Option<(Element, usize)>, String> {
+    let rest = &text[start + 1..];
+
+    for (delimiter, is_class) in [(b':', true), (b'=', false)] {
+        if rest.first() != Some(&delimiter) {
+            continue;
+        }
+        let Some(length) = rest[1..]
+            .windows(2)
+            .position(|pair| pair == [delimiter, b']'])
+        else {
+            continue;
+        };
+        let name = &rest[1..1 + length];
+        let end = start + length + 4;
+        if is_class {
+            return match Class::from_name(name) {
+                Some(class) => Ok(Some((Element::Class(class), end))),
+                None => Err(format!(
+                    "invalid character class '{}'",
+                    String::from_utf8_lossy(name)
+                )),
+            };
+        }
+        if name.len() == 1 {
+            return Ok(Some((Element::Char(name[0]), end)));
+        }
+        return Err(format!(
+            "{}: equivalence class operand must be a single character",
+            String::from_utf8_lossy(name)
+        ));
+    }
+
+    // [c*n]: the character may be an escape
+    let mut i = start + 1;
+    if i >= text.len() {
+        return Ok(None);
+    }
+    let c = next_char(text, &mut i);
+    if text.get(i) != Some(&b'*') {
+        return Ok(None);
+    }
+    let Some(length) = text[i + 1..].iter().position(|&b| b == b']') else {
+        return Ok(None);
+    };
+    let count_text = &text[i + 1..i + 1 + length];
+    let end = i + length + 2;
+    if count_text.is_empty() {
+        return Ok(Some((Element::Repeat(c, None), end)));
+    }
+
+    let count = std::str::from_utf8(count_text).ok().and_then(|count| {
+        if count.len() > 1 && count.starts_with('0') {
+            usize::from_str_radix(count, 8).ok()
+        } else {
+            count.parse().ok()
+        }
+    });
+    match count {
+        Some(0) => Ok(Some((Element::Repeat(c, None), end))),
+        Some(count) => Ok(Some((Element::Repeat(c, Some(count)), end))),
+        None => Err(format!(
+            "invalid repeat count '{}' in [c*n] construct",
+            String::from_utf8_lossy(count_text)
+        )),
+    }
+}
+
+// One character at I, which may be a backslash escape
+fn next_char(text: &[u8], i: &mut usize) -> u8 {
+    let c = text[*i];
+    *i += 1;
+    if c != b'\\' {
+        return c;
+    }
+
+    let Some(&escaped) = text.get(*i) else {
+        eprintln!("tr: warning: an unescaped backslash at end of string is not portable");
+        return b'\\';
+    };
+    *i += 1;
+    match escaped {
+        b'0'..=b'7' => {
+            let mut value = u32::from(escaped - b'0');
+            for _ in 0..2 {
+                match text.get(*i) {
+                    // A third digit only while the value still fits a byte
+                    Some(&digit @ b'0'..=b'7') if value * 8 + u32::from(digit - b'0') <= 0o377 => {
+                        value = value * 8 + u32::from(digit - b'0');
+                        *i += 1;
+                    }
+                    _ => break,
+                }
+            }
+            value as u8
+        }
+        b'a' => 0x07,
+        b'b' => 0x08,
+        b'f' => 0x0c,
+        b'n' => b'\n',
+        b'r' => b'\r',
+        b't' => b'\t',
+        b'v' => 0x0b,
+        other => other,
+    }
+}
+
+// SET1 as the characters it stands for, in order
+pub fn expand_set1(elements: &[Element]) -> Result<Vec<u8>, String> {
+    let mut set = Vec::new();
+    for element in elements {
+        match *element {
+            Element::Char(c) => set.push(c),
+            Element::Range(low, high) => set.extend(low..=high),
+            Element::Class(class) => set.extend(class.members()),
+            Element::Repeat(c, Some(count)) => set.extend(std::iter::repeat_n(c, count)),
+            Element::Repeat(_, None) => {
+                return Err("the [c*] repeat construct may not appear in string1".to_string())
+            }
+        }
+    }
+    Ok(set)
+}
+
+// Where in the expanded set each class starts
+pub fn class_offsets(elements: &[Element], fill: usize) -> Vec<(usize, Class)> {
+    let mut offsets = Vec::new();
+    let mut length = 0;
+    for element in elements {
+        length += match *element {
+            Element::Char(_) => 1,
+            Element::Range(low, high) => usize::from(high - low) + 1,
+            Element::Class(class) => {
+                offsets.push((length, class));
+                class.members().count()
+            }
+            Element::Repeat(_, Some(count)) => count,
+            Element::Repeat(_, None) => fill,
+        };
+    }
+    offsets
+}
+
+// SET2 when translating: a [c*] takes up whatever SET1 has left over
+pub fn expand_set2(elements: &[Element], set1_length: usize) -> Result<(Vec<u8>, usize), String> {
+    let mut fixed = 0;
+    let mut fillers = 0;
+    for element in elements {
+        match *element {
+            Element::Char(_) => fixed += 1,
+            Element::Range(low, high) => fixed += usize::from(high - low) + 1,
+            Element::Class(class) => fixed += class.members().count(),
+            Element::Repeat(_, Some(count)) => fixed += count,
+            Element::Repeat(_, None) => fillers += 1,
+        }
+    }
+    if fillers > 1 {
+        return Err("only one [c*] repeat construct may appear in string2".to_string());
+    }
+    let fill = set1_length.saturating_sub(fixed);
+
+    let mut set = Vec::new();
+    for element in elements {
+        match *element {
+            Element::Char(c) => set.push(c),
+            Element::Range(low, high) => set.extend(low..=high),
+            Element::Class(class) => set.extend(class.members()),
+            Element::Repeat(c, count) => set.extend(std::iter::repeat_n(c, count.unwrap_or(fill))),
+        }
+    }
+    Ok((set, fill))
+}
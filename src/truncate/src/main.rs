@@ -0,0 +1,210 @@
+// ASD CoreUtils - truncate
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use asd_coreutils_core::size::parse_size;
+use clap::{Arg, ArgAction, Command};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::process;
+
+// How a SIZE relates to the size a file ends up with
+#[derive(Clone, Copy, PartialEq)]
+enum Relation {
+    Set,
+    // '+' or '-': grow or shrink by SIZE
+    Adjust,
+    // '<': at most SIZE
+    AtMost,
+    // '>': at least SIZE
+    AtLeast,
+    // '/': down to a multiple of SIZE
+    RoundDown,
+    // '%': up to a multiple of SIZE
+    RoundUp,
+}
+
+struct Target {
+    relation: Relation,
+    size: i64,
+    // SIZE counts I/O blocks of each file rather than bytes
+    blocks: bool,
+    // Relative sizes go from this rather than from each file's own size
+    reference: Option<u64>,
+}
+
+fn main() {
+    let matches = Command::new("truncate")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils truncate - shrink or extend the size of each FILE to the specified size")
+        .after_help(
+            "SIZE is an integer with an optional unit: K, M, G, T, P, E (powers of 1024)\n\
+             or KB, MB, ... (powers of 1000). KiB, MiB, ... are the same as K, M, ...\n\n\
+             SIZE may also be prefixed by one of the following modifying characters:\n\
+             '+' extend by, '-' reduce by, '<' at most, '>' at least,\n\
+             '/' round down to multiple of, '%' round up to multiple of.",
+        )
+        .arg(
+            Arg::new("no-create")
+                .short('c')
+                .long("no-create")
+                .help("Do not create any files")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("io-blocks")
+                .short('o')
+                .long("io-blocks")
+                .help("Treat SIZE as number of IO blocks instead of bytes")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("reference")
+                .short('r')
+                .long("reference")
+                .value_name("RFILE")
+                .help("Base size on RFILE"),
+        )
+        .arg(
+            Arg::new("size")
+                .short('s')
+                .long("size")
+                .value_name("SIZE")
+                .allow_hyphen_values(true)
+                .help("Set or adjust the file size by SIZE bytes"),
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Files to resize")
+                .num_args(1..)
+                .required(true),
+        )
+        .get_matches();
+
+    let size = matches.get_one::<String>("size").map(|value| {
+        // A sign after '<', '>', '/' or '%' would be a second modifier
+        let mut chars = value.chars();
+        if chars.next().is_some_and(|c| "<>/%".contains(c)) && chars.next().is_some_and(|c| "+-".contains(c)) {
+            usage_error("truncate", "multiple relative modifiers specified");
+        }
+        parse_relative_size(value)
+            .unwrap_or_else(|| fail("truncate", &format!("invalid number: '{}'", value)))
+    });
+    let blocks = matches.get_flag("io-blocks");
+    let reference = matches.get_one::<String>("reference");
+
+    let (relation, size) = match (size, reference) {
+        (None, None) => usage_error("truncate", "you must specify either '--size' or '--reference'"),
+        (None, Some(_)) if blocks => usage_error("truncate", "'--io-blocks' was specified but '--size' was not"),
+        (Some((Relation::Set, _)), Some(_)) => {
+            usage_error("truncate", "you must specify a relative '--size' with '--reference'")
+        }
+        (Some((relation, size)), _) => (relation, size),
+        // -r alone is a relative size of nothing
+        (None, Some(_)) => (Relation::Adjust, 0),
+    };
+    if size == 0 && matches!(relation, Relation::RoundDown | Relation::RoundUp) {
+        fail("truncate", "division by zero");
+    }
+
+    let reference = reference.map(|path| match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(err) => fail("truncate", &format!("cannot stat '{}': {}", path, strerror(&err))),
+    });
+
+    let target = Target {
+        relation,
+        size,
+        blocks,
+        reference,
+    };
+    let no_create = matches.get_flag("no-create");
+    let mut exit_code = 0;
+
+    for file in matches.get_many::<String>("file").unwrap() {
+        if let Err(err) = truncate(file, &target, no_create) {
+            eprintln!("truncate: {}", strerror(&err));
+            exit_code = 1;
+        }
+    }
+
+    process::exit(exit_code);
+}
+
+fn truncate(path: &str, target: &Target, no_create: bool) -> io::Result<()> {
+    let mut file = match OpenOptions::new().write(true).create(!no_create).truncate(false).open(path) {
+        Ok(file) => file,
+        // -c quietly skips files that don't exist
+        Err(err) if no_create && err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(io::Error::new(
+                err.kind(),
+                format!("cannot open '{}' for writing: {}", path, strerror(&err)),
+            ))
+        }
+    };
+
+    let size = new_size(&mut file, path, target)?;
+    file.set_len(size).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("failed to truncate '{}' at {} bytes: {}", path, size, strerror(&err)),
+        )
+    })
+}
+
+// The size TARGET gives the open FILE
+fn new_size(file: &mut File, path: &str, target: &Target) -> io::Result<u64> {
+    let metadata = file.metadata()?;
+    let overflow = |what: String| io::Error::new(io::ErrorKind::InvalidInput, what);
+
+    let size = if target.blocks {
+        let block = metadata.blksize() as i64;
+        target.size.checked_mul(block).ok_or_else(|| {
+            overflow(format!(
+                "overflow in {} * {} byte blocks for file '{}'",
+                target.size, block, path
+            ))
+        })?
+    } else {
+        target.size
+    };
+    if target.relation == Relation::Set {
+        return Ok(size as u64);
+    }
+
+    // Devices have no length in their metadata and are measured by seeking
+    let base = match target.reference {
+        Some(reference) => reference,
+        None if metadata.is_file() => metadata.len(),
+        None => file.seek(SeekFrom::End(0))?,
+    } as i64;
+    let size = match target.relation {
+        Relation::Adjust => base.checked_add(size).map(|size| size.max(0)),
+        Relation::AtMost => Some(base.min(size)),
+        Relation::AtLeast => Some(base.max(size)),
+        Relation::RoundDown => Some(base / size * size),
+        Relation::RoundUp => base.checked_add(size - 1).map(|sum| sum / size * size),
+        Relation::Set => Some(size),
+    };
+    size.map(|size| size as u64)
+        .ok_or_else(|| overflow(format!("overflow extending size of file '{}'", path)))
+}
+
+// A SIZE with the character in front that says how it applies
+fn parse_relative_size(value: &str) -> Option<(Relation, i64)> {
+    let (relation, rest) = match value.chars().next()? {
+        '+' | '-' => (Relation::Adjust, &value[1..]),
+        '<' => (Relation::AtMost, &value[1..]),
+        '>' => (Relation::AtLeast, &value[1..]),
+        '/' => (Relation::RoundDown, &value[1..]),
+        '%' => (Relation::RoundUp, &value[1..]),
+        _ => (Relation::Set, value),
+    };
+    let size = i64::try_from(parse_size(rest, &[])?).ok()?;
+    Some((relation, if value.starts_with('-') { -size } else { size }))
+}
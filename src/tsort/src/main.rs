@@ -0,0 +1,221 @@
+// ASD CoreUtils - tsort
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, Command};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::process;
+
+struct Item {
+    name: Vec<u8>,
+    // Number of predecessors not yet output
+    count: usize,
+    // Successors, most recently recorded last
+    successors: Vec<usize>,
+    // Next item in the output queue, or in the path being traced while
+    // looking for a loop
+    link: Option<usize>,
+    done: bool,
+}
+
+struct Graph {
+    items: Vec<Item>,
+    index: HashMap<Vec<u8>, usize>,
+}
+
+impl Graph {
+    fn item(&mut self, name: &[u8]) -> usize {
+        if let Some(&id) = self.index.get(name) {
+            return id;
+        }
+        let id = self.items.len();
+        self.items.push(Item {
+            name: name.to_vec(),
+            count: 0,
+            successors: Vec::new(),
+            link: None,
+            done: false,
+        });
+        self.index.insert(name.to_vec(), id);
+        id
+    }
+
+    // "a a" only declares the item
+    fn relate(&mut self, before: usize, after: usize) {
+        if before != after {
+            self.items[after].count += 1;
+            self.items[before].successors.push(after);
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("tsort")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils tsort - perform topological sort")
+        .after_help(
+            "Write totally ordered list consistent with the partial ordering in FILE.\n\
+             With no FILE, or when FILE is -, read standard input.",
+        )
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("File of whitespace separated pairs")
+                .default_value("-"),
+        )
+        .get_matches();
+
+    let file = matches.get_one::<String>("file").unwrap();
+
+    let input = read_input(file)
+        .unwrap_or_else(|err| fail("tsort", &format!("{}: {}", file, strerror(&err))));
+
+    let mut graph = Graph {
+        items: Vec::new(),
+        index: HashMap::new(),
+    };
+
+    let tokens: Vec<&[u8]> = input
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    if !tokens.len().is_multiple_of(2) {
+        fail("tsort", &format!("{}: input contains an odd number of tokens", file));
+    }
+
+    for pair in tokens.chunks(2) {
+        let before = graph.item(pair[0]);
+        let after = graph.item(pair[1]);
+        graph.relate(before, after);
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    match sort(&mut graph, file, &mut out).and_then(|ok| out.flush().map(|_| ok)) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => fail("tsort", &format!("write error: {}", strerror(&err))),
+    }
+}
+
+fn read_input(file: &str) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    if file == "-" {
+        io::stdin().lock().read_to_end(&mut data)?;
+    } else {
+        File::open(file)?.read_to_end(&mut data)?;
+    }
+    Ok(data)
+}
+
+// Knuth's algorithm T: output items without predecessors, releasing their
+// successors as it goes. Whenever that stalls, the remaining items contain
+// a loop, which is reported and broken by dropping one of its relations.
+// Items are always scanned in name order, so the output is deterministic.
+// Returns false if a loop was found.
+fn sort(graph: &mut Graph, file: &str, out: &mut dyn Write) -> io::Result<bool> {
+    let mut order: Vec<usize> = (0..graph.items.len()).collect();
+    order.sort_by(|&a, &b| graph.items[a].name.cmp(&graph.items[b].name));
+
+    let mut remaining = graph.items.len();
+    let mut ok = true;
+
+    while remaining > 0 {
+        let mut head = None;
+        let mut tail: Option<usize> = None;
+        for &id in &order {
+            let item = &graph.items[id];
+            if item.count == 0 && !item.done {
+                enqueue(graph, &mut head, &mut tail, id);
+            }
+        }
+
+        while let Some(id) = head {
+            out.write_all(&graph.items[id].name)?;
+            out.write_all(b"\n")?;
+            graph.items[id].done = true;
+            remaining -= 1;
+
+            for index in (0..graph.items[id].successors.len()).rev() {
+                let successor = graph.items[id].successors[index];
+                graph.items[successor].count -= 1;
+                if graph.items[successor].count == 0 {
+                    enqueue(graph, &mut head, &mut tail, successor);
+                }
+            }
+
+            head = graph.items[id].link.take();
+        }
+
+        if remaining > 0 {
+            out.flush()?;
+            eprintln!("tsort: {}: input contains a loop:", file);
+            ok = false;
+            break_loop(graph, &order);
+        }
+    }
+
+    Ok(ok)
+}
+
+fn enqueue(graph: &mut Graph, head: &mut Option<usize>, tail: &mut Option<usize>, id: usize) {
+    match *tail {
+        Some(last) => graph.items[last].link = Some(id),
+        None => *head = Some(id),
+    }
+    *tail = Some(id);
+}
+
+// Walks the items still waiting on a predecessor, chaining each one that
+// leads back to the start of the current path onto it, until an item
+// already on the path closes a cycle
+fn break_loop(graph: &mut Graph, order: &[usize]) {
+    let mut path: Option<usize> = None;
+
+    loop {
+        for &id in order {
+            if graph.items[id].count == 0 {
+                continue;
+            }
+            let Some(start) = path else {
+                path = Some(id);
+                continue;
+            };
+
+            // Successors are searched most recent first
+            let Some(position) = graph.items[id].successors.iter().rposition(|&s| s == start) else {
+                continue;
+            };
+
+            if graph.items[id].link.is_none() {
+                graph.items[id].link = Some(start);
+                path = Some(id);
+                continue;
+            }
+
+            // Found the loop: report the path back to ID, then cut the
+            // relation that closed it
+            let mut current = Some(start);
+            while let Some(member) = current {
+                eprintln!("tsort: {}", String::from_utf8_lossy(&graph.items[member].name));
+                if member == id {
+                    graph.items[id].successors.remove(position);
+                    graph.items[start].count -= 1;
+                    current = graph.items[member].link.take();
+                    break;
+                }
+                current = graph.items[member].link.take();
+            }
+            while let Some(member) = current {
+                current = graph.items[member].link.take();
+            }
+            return;
+        }
+    }
+}
@@ -0,0 +1,16 @@
+// ASD CoreUtils - arch
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use clap::Command;
+use uname::get_system_info;
+
+fn main() {
+    Command::new("arch")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils arch - print machine hardware name (same as uname -m)")
+        .get_matches();
+
+    println!("{}", get_system_info().machine);
+}
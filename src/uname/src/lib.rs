@@ -0,0 +1,91 @@
+// ASD CoreUtils - system information shared by uname and arch
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror};
+use std::io;
+
+pub struct SystemInfo {
+    pub kernel_name: String,
+    pub nodename: String,
+    pub kernel_release: String,
+    pub kernel_version: String,
+    pub machine: String,
+    pub processor: String,
+    pub hardware_platform: String,
+    pub operating_system: String,
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_system_info() -> SystemInfo {
+    use std::ffi::CStr;
+    
+    unsafe {
+        let mut utsname: libc::utsname = std::mem::zeroed();
+        if libc::uname(&mut utsname) != 0 {
+            let err = io::Error::last_os_error();
+            fail("uname", &format!("cannot get system name: {}", strerror(&err)));
+        }
+
+        let kernel_name = CStr::from_ptr(utsname.sysname.as_ptr()).to_string_lossy().into_owned();
+        let nodename = CStr::from_ptr(utsname.nodename.as_ptr()).to_string_lossy().into_owned();
+        let kernel_release = CStr::from_ptr(utsname.release.as_ptr()).to_string_lossy().into_owned();
+        let kernel_version = CStr::from_ptr(utsname.version.as_ptr()).to_string_lossy().into_owned();
+        let machine = CStr::from_ptr(utsname.machine.as_ptr()).to_string_lossy().into_owned();
+        
+        // Get processor info from /proc/cpuinfo
+        let processor = match std::fs::read_to_string("/proc/cpuinfo") {
+            Ok(contents) => {
+                contents
+                    .lines()
+                    .find(|line| line.starts_with("model name"))
+                    .and_then(|line| line.split(':').nth(1))
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|| "unknown".to_string())
+            },
+            Err(_) => "unknown".to_string(),
+        };
+
+        // Hardware platform - can be same as machine in some cases
+        let hardware_platform = machine.clone();
+        
+        // Operating system detection
+        let operating_system = if std::path::Path::new("/etc/os-release").exists() {
+            match std::fs::read_to_string("/etc/os-release") {
+                Ok(contents) => {
+                    contents
+                        .lines()
+                        .find(|line| line.starts_with("PRETTY_NAME="))
+                        .and_then(|line| {
+                            let parts: Vec<&str> = line.splitn(2, '=').collect();
+                            if parts.len() == 2 {
+                                Some(parts[1].trim_matches('"').to_string())
+                            } else {
+                                None
+                            }
+                        })
+                        .unwrap_or_else(|| "Linux".to_string())
+                },
+                Err(_) => "Linux".to_string(),
+            }
+        } else {
+            "Linux".to_string()
+        };
+
+        SystemInfo {
+            kernel_name,
+            nodename,
+            kernel_release,
+            kernel_version,
+            machine,
+            processor,
+            hardware_platform,
+            operating_system,
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_system_info() -> SystemInfo {
+    fail("uname", "this version of uname only supports Linux systems");
+}
\ No newline at end of file
@@ -3,11 +3,7 @@
 // Licensed under the Apache License, Version 2.0
 
 use clap::{Arg, ArgAction, Command};
-use std::env;
-use std::process;
-
-#[cfg(target_os = "linux")]
-extern crate libc;
+use uname::get_system_info;
 
 fn main() {
     let matches = Command::new("uname")
@@ -133,89 +129,3 @@ fn main() {
 
     println!("{}", output.join(" "));
 }
-
-struct SystemInfo {
-    kernel_name: String,
-    nodename: String,
-    kernel_release: String,
-    kernel_version: String,
-    machine: String,
-    processor: String,
-    hardware_platform: String,
-    operating_system: String,
-}
-
-#[cfg(target_os = "linux")]
-fn get_system_info() -> SystemInfo {
-    use std::ffi::CStr;
-    
-    unsafe {
-        let mut utsname: libc::utsname = std::mem::zeroed();
-        if libc::uname(&mut utsname) != 0 {
-            eprintln!("Failed to get system information");
-            process::exit(1);
-        }
-
-        let kernel_name = CStr::from_ptr(utsname.sysname.as_ptr()).to_string_lossy().into_owned();
-        let nodename = CStr::from_ptr(utsname.nodename.as_ptr()).to_string_lossy().into_owned();
-        let kernel_release = CStr::from_ptr(utsname.release.as_ptr()).to_string_lossy().into_owned();
-        let kernel_version = CStr::from_ptr(utsname.version.as_ptr()).to_string_lossy().into_owned();
-        let machine = CStr::from_ptr(utsname.machine.as_ptr()).to_string_lossy().into_owned();
-        
-        // Get processor info from /proc/cpuinfo
-        let processor = match std::fs::read_to_string("/proc/cpuinfo") {
-            Ok(contents) => {
-                contents
-                    .lines()
-                    .find(|line| line.starts_with("model name"))
-                    .and_then(|line| line.split(':').nth(1))
-                    .map(|s| s.trim().to_string())
-                    .unwrap_or_else(|| "unknown".to_string())
-            },
-            Err(_) => "unknown".to_string(),
-        };
-
-        // Hardware platform - can be same as machine in some cases
-        let hardware_platform = machine.clone();
-        
-        // Operating system detection
-        let operating_system = if std::path::Path::new("/etc/os-release").exists() {
-            match std::fs::read_to_string("/etc/os-release") {
-                Ok(contents) => {
-                    contents
-                        .lines()
-                        .find(|line| line.starts_with("PRETTY_NAME="))
-                        .and_then(|line| {
-                            let parts: Vec<&str> = line.splitn(2, '=').collect();
-                            if parts.len() == 2 {
-                                Some(parts[1].trim_matches('"').to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .unwrap_or_else(|| "Linux".to_string())
-                },
-                Err(_) => "Linux".to_string(),
-            }
-        } else {
-            "Linux".to_string()
-        };
-
-        SystemInfo {
-            kernel_name,
-            nodename,
-            kernel_release,
-            kernel_version,
-            machine,
-            processor,
-            hardware_platform,
-            operating_system,
-        }
-    }
-}
-
-#[cfg(not(target_os = "linux"))]
-fn get_system_info() -> SystemInfo {
-    eprintln!("This version of uname only supports Linux systems");
-    process::exit(1);
-}
\ No newline at end of file
@@ -0,0 +1,399 @@
+// ASD CoreUtils - uniq
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+// Empty lines around the groups printed by -D
+#[derive(Clone, Copy, PartialEq)]
+enum Delimit {
+    None,
+    Prepend,
+    Separate,
+}
+
+// Empty lines around the groups printed by --group
+#[derive(Clone, Copy, PartialEq)]
+enum Grouping {
+    None,
+    Prepend,
+    Append,
+    Separate,
+    Both,
+}
+
+struct Options {
+    skip_fields: usize,
+    skip_chars: usize,
+    check_chars: usize,
+    ignore_case: bool,
+    delimiter: u8,
+    count: bool,
+    // Which lines are printed: those without duplicates, the first of each
+    // group of duplicates, and the rest of each group
+    output_unique: bool,
+    output_first_repeated: bool,
+    output_later_repeated: bool,
+    delimit: Delimit,
+    grouping: Grouping,
+}
+
+impl Options {
+    // The part of LINE that is compared: what is left after skipping -f
+    // fields and -s characters, cut to -w characters. A field is a run of
+    // blanks followed by a run of non-blanks.
+    fn key<'a>(&self, line: &'a [u8]) -> &'a [u8] {
+        let is_blank = |c: &u8| *c == b' ' || *c == b'\t';
+        let mut pos = 0;
+        for _ in 0..self.skip_fields {
+            if pos == line.len() {
+                break;
+            }
+            pos += line[pos..].iter().take_while(|c| is_blank(c)).count();
+            pos += line[pos..].iter().take_while(|c| !is_blank(c)).count();
+        }
+        pos += self.skip_chars.min(line.len() - pos);
+        let end = pos + self.check_chars.min(line.len() - pos);
+        &line[pos..end]
+    }
+
+    fn same(&self, a: &[u8], b: &[u8]) -> bool {
+        if self.ignore_case {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    }
+}
+
+fn main() {
+    let matches = Command::new("uniq")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils uniq - report or omit repeated lines")
+        .args_override_self(true)
+        .after_help(
+            "Filter adjacent matching lines from INPUT (or standard input),\n\
+             writing to OUTPUT (or standard output).\n\n\
+             A field is a run of blanks (usually spaces and/or TABs), then\n\
+             non-blank characters. Fields are skipped before characters.\n\n\
+             Note: 'uniq' does not detect repeated lines unless they are adjacent.",
+        )
+        .arg(
+            Arg::new("count")
+                .short('c')
+                .long("count")
+                .help("Prefix lines by the number of occurrences")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("repeated")
+                .short('d')
+                .long("repeated")
+                .help("Only print duplicate lines, one for each group")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("all-repeated")
+                .short('D')
+                .long("all-repeated")
+                .value_name("METHOD")
+                .help("Print all duplicate lines; groups can be delimited with an empty line, METHOD={none(default),prepend,separate}")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("none")
+                .value_parser(["none", "prepend", "separate"]),
+        )
+        .arg(
+            Arg::new("skip-fields")
+                .short('f')
+                .long("skip-fields")
+                .value_name("N")
+                .help("Avoid comparing the first N fields"),
+        )
+        .arg(
+            Arg::new("group")
+                .long("group")
+                .value_name("METHOD")
+                .help("Show all items, separating groups with an empty line; METHOD={separate(default),prepend,append,both}")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("separate")
+                .value_parser(["separate", "prepend", "append", "both"]),
+        )
+        .arg(
+            Arg::new("ignore-case")
+                .short('i')
+                .long("ignore-case")
+                .help("Ignore differences in case when comparing")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("skip-chars")
+                .short('s')
+                .long("skip-chars")
+                .value_name("N")
+                .help("Avoid comparing the first N characters"),
+        )
+        .arg(
+            Arg::new("unique")
+                .short('u')
+                .long("unique")
+                .help("Only print unique lines")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("zero-terminated")
+                .short('z')
+                .long("zero-terminated")
+                .help("Line delimiter is NUL, not newline")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("check-chars")
+                .short('w')
+                .long("check-chars")
+                .value_name("N")
+                .help("Compare no more than N characters in lines"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .help("INPUT and OUTPUT files")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let options = parse_options(&matches);
+
+    let files: Vec<&String> = matches
+        .get_many::<String>("files")
+        .map(|files| files.collect())
+        .unwrap_or_default();
+    if let Some(extra) = files.get(2) {
+        usage_error("uniq", &format!("extra operand '{}'", extra));
+    }
+    let input = files.first().map_or("-", |file| file.as_str());
+    let output = files.get(1).map_or("-", |file| file.as_str());
+
+    let reader: Box<dyn BufRead> = if input == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        match File::open(input) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(err) => {
+                fail("uniq", &format!("{}: {}", input, strerror(&err)));
+            }
+        }
+    };
+    let writer: Box<dyn Write> = if output == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        match File::create(output) {
+            Ok(file) => Box::new(file),
+            Err(err) => {
+                fail("uniq", &format!("{}: {}", output, strerror(&err)));
+            }
+        }
+    };
+
+    let mut out = BufWriter::new(writer);
+    let result = if options.output_unique && options.output_first_repeated && !options.count {
+        write_groups(reader, &mut out, &options)
+    } else {
+        write_selected(reader, &mut out, &options)
+    }
+    .and_then(|()| out.flush());
+    if let Err(err) = result {
+        fail("uniq", &strerror(&err));
+    }
+}
+
+fn parse_options(matches: &ArgMatches) -> Options {
+    let number = |name: &str, what: &str| -> usize {
+        match matches.get_one::<String>(name) {
+            None => 0,
+            Some(text) if !text.is_empty() && text.bytes().all(|c| c.is_ascii_digit()) => {
+                // Counts too large to represent are as good as infinite
+                text.parse().unwrap_or(usize::MAX)
+            }
+            Some(text) => fail("uniq", &format!("{}: {}", text, what)),
+        }
+    };
+
+    let mut options = Options {
+        skip_fields: number("skip-fields", "invalid number of fields to skip"),
+        skip_chars: number("skip-chars", "invalid number of bytes to skip"),
+        check_chars: match matches.get_one::<String>("check-chars") {
+            Some(_) => number("check-chars", "invalid number of bytes to compare"),
+            None => usize::MAX,
+        },
+        ignore_case: matches.get_flag("ignore-case"),
+        delimiter: if matches.get_flag("zero-terminated") {
+            b'\0'
+        } else {
+            b'\n'
+        },
+        count: matches.get_flag("count"),
+        output_unique: true,
+        output_first_repeated: true,
+        output_later_repeated: false,
+        delimit: Delimit::None,
+        grouping: Grouping::None,
+    };
+
+    if matches.get_flag("repeated") {
+        options.output_unique = false;
+    }
+    if let Some(method) = matches.get_one::<String>("all-repeated") {
+        options.output_unique = false;
+        options.output_later_repeated = true;
+        options.delimit = match method.as_str() {
+            "prepend" => Delimit::Prepend,
+            "separate" => Delimit::Separate,
+            _ => Delimit::None,
+        };
+    }
+    if matches.get_flag("unique") {
+        options.output_first_repeated = false;
+    }
+    if let Some(method) = matches.get_one::<String>("group") {
+        options.grouping = match method.as_str() {
+            "prepend" => Grouping::Prepend,
+            "append" => Grouping::Append,
+            "both" => Grouping::Both,
+            _ => Grouping::Separate,
+        };
+    }
+
+    let selecting = matches.get_flag("repeated")
+        || matches.contains_id("all-repeated")
+        || matches.get_flag("unique");
+    if options.grouping != Grouping::None && (selecting || options.count) {
+        usage_error("uniq", "--group is mutually exclusive with -c/-d/-D/-u");
+    }
+    if options.count && options.output_later_repeated {
+        usage_error(
+            "uniq",
+            "printing all duplicated lines and repeat counts is meaningless",
+        );
+    }
+    options
+}
+
+// Read the next line into LINE, without its delimiter; false at the end
+fn read_line(input: &mut dyn BufRead, line: &mut Vec<u8>, delimiter: u8) -> io::Result<bool> {
+    line.clear();
+    if input.read_until(delimiter, line)? == 0 {
+        return Ok(false);
+    }
+    if line.last() == Some(&delimiter) {
+        line.pop();
+    }
+    Ok(true)
+}
+
+fn write_line(out: &mut dyn Write, line: &[u8], delimiter: u8) -> io::Result<()> {
+    out.write_all(line)?;
+    out.write_all(&[delimiter])
+}
+
+// Every line starting a group, or with --group every line with the groups
+// set apart by empty lines
+fn write_groups(
+    mut input: Box<dyn BufRead>,
+    out: &mut dyn Write,
+    options: &Options,
+) -> io::Result<()> {
+    let delimiter = options.delimiter;
+    let mut previous = Vec::new();
+    let mut line = Vec::new();
+    let mut first = true;
+
+    while read_line(&mut input, &mut line, delimiter)? {
+        let new_group = first || !options.same(options.key(&previous), options.key(&line));
+        if new_group
+            && match options.grouping {
+                Grouping::None => false,
+                Grouping::Prepend | Grouping::Both => true,
+                Grouping::Append | Grouping::Separate => !first,
+            }
+        {
+            out.write_all(&[delimiter])?;
+        }
+        // Outside --group, lines are compared with the first of their group
+        if new_group || options.grouping != Grouping::None {
+            write_line(out, &line, delimiter)?;
+            std::mem::swap(&mut previous, &mut line);
+            first = false;
+        }
+    }
+
+    if matches!(options.grouping, Grouping::Append | Grouping::Both) && !first {
+        out.write_all(&[delimiter])?;
+    }
+    Ok(())
+}
+
+// The lines chosen by -d, -D and -u, with -c counts
+fn write_selected(
+    mut input: Box<dyn BufRead>,
+    out: &mut dyn Write,
+    options: &Options,
+) -> io::Result<()> {
+    let delimiter = options.delimiter;
+    let mut previous = Vec::new();
+    let mut line = Vec::new();
+    if !read_line(&mut input, &mut previous, delimiter)? {
+        return Ok(());
+    }
+
+    // Lines so far matching the first of the current group
+    let mut matches: u64 = 0;
+    let mut first_delimiter = true;
+    let write = |out: &mut dyn Write, line: &[u8], repeated: bool, matches: u64| {
+        let wanted = if matches == 0 {
+            options.output_unique
+        } else if !repeated {
+            options.output_first_repeated
+        } else {
+            options.output_later_repeated
+        };
+        if !wanted {
+            return Ok(());
+        }
+        if options.count {
+            write!(out, "{:7} ", matches + 1)?;
+        }
+        write_line(out, line, delimiter)
+    };
+
+    while read_line(&mut input, &mut line, delimiter)? {
+        let repeated = options.same(options.key(&previous), options.key(&line));
+        if repeated {
+            matches += 1;
+        }
+
+        if options.delimit != Delimit::None {
+            if !repeated {
+                if matches > 0 {
+                    first_delimiter = false;
+                }
+            } else if matches == 1 && (options.delimit == Delimit::Prepend || !first_delimiter) {
+                out.write_all(&[delimiter])?;
+            }
+        }
+
+        if !repeated || options.output_later_repeated {
+            write(out, &previous, repeated, matches)?;
+            std::mem::swap(&mut previous, &mut line);
+            if !repeated {
+                matches = 0;
+            }
+        }
+    }
+    write(out, &previous, false, matches)
+}
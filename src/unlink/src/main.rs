@@ -0,0 +1,52 @@
+// ASD CoreUtils - unlink
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::{fail, strerror, usage_error};
+use clap::{Arg, Command};
+use std::ffi::CString;
+use std::io;
+
+fn main() {
+    let matches = Command::new("unlink")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils unlink - call the unlink function to remove the specified FILE")
+        .override_usage("unlink FILE")
+        .arg(
+            Arg::new("operands")
+                .value_name("FILE")
+                .help("File to remove")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let operands: Vec<&String> = matches
+        .get_many::<String>("operands")
+        .map(|operands| operands.collect())
+        .unwrap_or_default();
+
+    // A single operand only; unlike rm there are no options to soften this
+    match operands.len() {
+        0 => usage_error("unlink", "missing operand"),
+        1 => {}
+        _ => usage_error("unlink", &format!("extra operand '{}'", operands[1])),
+    }
+
+    if let Err(err) = unlink(operands[0]) {
+        fail(
+            "unlink",
+            &format!("cannot unlink '{}': {}", operands[0], strerror(&err)),
+        );
+    }
+}
+
+fn unlink(path: &str) -> io::Result<()> {
+    let path = CString::new(path).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    if unsafe { libc::unlink(path.as_ptr()) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
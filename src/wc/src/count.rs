@@ -0,0 +1,355 @@
+// ASD CoreUtils - wc
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+// The counting engine: input is taken a buffer at a time, newlines are
+// found with memchr's vectorized search and word starts are counted with a
+// branch-free loop the compiler can vectorize. Large regular files are
+// split into ranges counted by several threads at once.
+//
+// As in GNU wc, a word is made of printable characters and ended by white
+// space; other bytes neither start nor end one. In a UTF-8 locale text that
+// is not plain ASCII is decoded, and bytes that are not part of a valid
+// character are neither counted as characters nor take up any width.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::fs::FileExt;
+use std::thread;
+
+pub const BUFFER_SIZE: usize = 256 * 1024;
+
+// Parts of a file smaller than this are not worth a thread of their own
+pub const MIN_PART_SIZE: u64 = 16 << 20;
+
+extern "C" {
+    fn iswprint(wc: u32) -> libc::c_int;
+    fn iswspace(wc: u32) -> libc::c_int;
+    fn wcwidth(wc: libc::wchar_t) -> libc::c_int;
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Counts {
+    pub lines: u64,
+    pub words: u64,
+    pub chars: u64,
+    pub bytes: u64,
+    pub max_line: u64,
+}
+
+impl Counts {
+    pub fn add(&mut self, other: &Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+        self.max_line = self.max_line.max(other.max_line);
+    }
+}
+
+// What is counted beyond lines and bytes, and how the input is decoded
+#[derive(Clone, Copy)]
+pub struct Options {
+    pub words: bool,
+    pub chars: bool,
+    pub max_line: bool,
+    pub utf8: bool,
+    // No-break spaces end words too, unless POSIXLY_CORRECT is set
+    pub nbsp_separates: bool,
+}
+
+// How a character takes part in words
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Space,
+    Word,
+    Other,
+}
+
+// Counts for input seen so far, carrying whether a word is open, how wide
+// the current line is, and any character cut off by the end of a buffer
+pub struct Counter {
+    counts: Counts,
+    options: Options,
+    in_word: bool,
+    // Whether the first character that is not transparent to words is part
+    // of one, which is all a count of what came before needs to know
+    leading_word: Option<bool>,
+    line_width: u64,
+    partial: Vec<u8>,
+}
+
+impl Counter {
+    pub fn new(options: Options) -> Self {
+        Counter {
+            counts: Counts::default(),
+            options,
+            in_word: false,
+            leading_word: None,
+            line_width: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    // The counts so far, taking the input to end here. A character still
+    // cut off is invalid and does not count.
+    pub fn counts(&self) -> Counts {
+        Counts {
+            max_line: self.counts.max_line.max(self.line_width),
+            ..self.counts
+        }
+    }
+
+    pub fn update(&mut self, buf: &[u8]) {
+        if buf.is_empty() {
+            return;
+        }
+        self.counts.bytes += buf.len() as u64;
+        self.counts.lines += memchr::memchr_iter(b'\n', buf).count() as u64;
+
+        let options = self.options;
+        if !options.words && !options.chars && !options.max_line {
+            return;
+        }
+        if !options.utf8 || (self.partial.is_empty() && buf.is_ascii()) {
+            // Every byte is a character
+            self.counts.chars += buf.len() as u64;
+            if options.max_line {
+                buf.iter().for_each(|&c| self.update_char(c as u32));
+            } else if options.words {
+                self.update_words(buf);
+            }
+        } else if self.partial.is_empty() {
+            self.update_utf8(buf);
+        } else {
+            let mut joined = std::mem::take(&mut self.partial);
+            joined.extend_from_slice(buf);
+            self.update_utf8(&joined);
+        }
+    }
+
+    // Words in bytes that are each a character
+    fn update_words(&mut self, buf: &[u8]) {
+        let last = buf[buf.len() - 1];
+        if buf.iter().all(|&c| is_space(c) | is_word(c)) {
+            // Every word start is then a word byte after a space
+            self.leading_word.get_or_insert(is_word(buf[0]));
+            let first = (!self.in_word && is_word(buf[0])) as u64;
+            let starts: u64 = buf
+                .iter()
+                .zip(&buf[1..])
+                .map(|(&before, &c)| (is_space(before) & is_word(c)) as u64)
+                .sum();
+            self.counts.words += first + starts;
+            self.in_word = is_word(last);
+        } else {
+            if self.leading_word.is_none() {
+                self.leading_word = buf
+                    .iter()
+                    .find(|&&c| is_space(c) | is_word(c))
+                    .map(|&c| is_word(c));
+            }
+            let mut in_word = self.in_word;
+            let mut words = 0;
+            for &c in buf {
+                let word = is_word(c);
+                words += (word & !in_word) as u64;
+                in_word = word | (in_word & !is_space(c));
+            }
+            self.counts.words += words;
+            self.in_word = in_word;
+        }
+    }
+
+    // Decode BUF as UTF-8, keeping a character cut off at its end for the
+    // next buffer
+    fn update_utf8(&mut self, buf: &[u8]) {
+        let mut rest = buf;
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => return self.update_text(text),
+                Err(err) => {
+                    let (valid, after) = rest.split_at(err.valid_up_to());
+                    // SAFETY: from_utf8 checked everything up to here
+                    self.update_text(unsafe { std::str::from_utf8_unchecked(valid) });
+                    match err.error_len() {
+                        Some(invalid) => rest = &after[invalid..],
+                        None => {
+                            self.partial.extend_from_slice(after);
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn update_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.options.chars {
+            self.counts.chars += text.chars().count() as u64;
+        }
+        if self.options.max_line || !text.is_ascii() {
+            text.chars().for_each(|c| self.update_char(c as u32));
+        } else if self.options.words {
+            self.update_words(text.as_bytes());
+        }
+    }
+
+    // Words and line width for one character, as GNU wc does them
+    fn update_char(&mut self, c: u32) {
+        let class = match c {
+            0x0a | 0x0d | 0x0c => {
+                self.counts.max_line = self.counts.max_line.max(self.line_width);
+                self.line_width = 0;
+                Class::Space
+            }
+            0x09 => {
+                self.line_width += 8 - self.line_width % 8;
+                Class::Space
+            }
+            0x20 => {
+                self.line_width += 1;
+                Class::Space
+            }
+            0x0b => Class::Space,
+            0..=0x7f if is_word(c as u8) => {
+                self.line_width += 1;
+                Class::Word
+            }
+            0..=0xff if !self.options.utf8 => Class::Other,
+            _ if unsafe { iswprint(c) } != 0 => {
+                self.line_width += unsafe { wcwidth(c as libc::wchar_t) }.max(0) as u64;
+                if unsafe { iswspace(c) } != 0 || (self.options.nbsp_separates && is_nbsp(c)) {
+                    Class::Space
+                } else {
+                    Class::Word
+                }
+            }
+            _ => Class::Other,
+        };
+
+        if class == Class::Other {
+            return;
+        }
+        let word = class == Class::Word;
+        self.leading_word.get_or_insert(word);
+        self.counts.words += (word && !self.in_word) as u64;
+        self.in_word = word;
+    }
+}
+
+// Whitespace in the C locale: space, \t, \n, \v, \f and \r
+fn is_space(c: u8) -> bool {
+    (c == b' ') | (c.wrapping_sub(b'\t') < 5)
+}
+
+// Printable characters other than space
+fn is_word(c: u8) -> bool {
+    c.wrapping_sub(b'!') < 94
+}
+
+// No-break space, figure space, narrow no-break space and word joiner
+fn is_nbsp(c: u32) -> bool {
+    matches!(c, 0x00a0 | 0x2007 | 0x202f | 0x2060)
+}
+
+// Count everything READER has left. Counts up to a read error stay in
+// COUNTER.
+pub fn count_stream(reader: &mut dyn Read, counter: &mut Counter) -> io::Result<()> {
+    let mut buf = vec![0; BUFFER_SIZE];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(read) => counter.update(&buf[..read]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+// Count the first SIZE bytes of FILE with THREADS threads, each reading
+// its own range with pread. A word cut by a range boundary is counted in
+// both ranges, once too often. Not for -L, as the width of a line cut in
+// two would be lost.
+pub fn count_parallel(
+    file: &File,
+    size: u64,
+    threads: usize,
+    options: Options,
+) -> io::Result<Counts> {
+    let part = size.div_ceil(threads as u64);
+    let mut bounds = vec![0];
+    for index in 1..threads as u64 {
+        let mut bound = (index * part).min(size);
+        if options.utf8 {
+            bound = character_start(file, bound, size)?;
+        }
+        if bound > *bounds.last().unwrap() {
+            bounds.push(bound);
+        }
+    }
+    if size > *bounds.last().unwrap() {
+        bounds.push(size);
+    }
+
+    let results: Vec<io::Result<Counter>> = thread::scope(|scope| {
+        let workers: Vec<_> = bounds
+            .windows(2)
+            .map(|range| scope.spawn(move || count_range(file, range[0], range[1], options)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("counting thread panicked"))
+            .collect()
+    });
+
+    let mut total = Counts::default();
+    let mut open_word = false;
+    for counter in results {
+        let counter = counter?;
+        total.add(&counter.counts());
+        if open_word && counter.leading_word == Some(true) {
+            total.words -= 1;
+        }
+        // A range with nothing but transparent bytes leaves a word open
+        if counter.leading_word.is_some() {
+            open_word = counter.in_word;
+        }
+    }
+    Ok(total)
+}
+
+// OFFSET moved past the continuation bytes of a UTF-8 character, so that
+// no thread starts in the middle of one. More than three in a row are
+// invalid wherever they are decoded from.
+fn character_start(file: &File, offset: u64, size: u64) -> io::Result<u64> {
+    let mut bytes = [0; 3];
+    let read = file.read_at(&mut bytes, offset)?;
+    let skip = bytes[..read]
+        .iter()
+        .take_while(|&&c| c & 0xc0 == 0x80)
+        .count();
+    Ok((offset + skip as u64).min(size))
+}
+
+fn count_range(file: &File, mut offset: u64, end: u64, options: Options) -> io::Result<Counter> {
+    let mut counter = Counter::new(options);
+    let mut buf = vec![0; BUFFER_SIZE];
+    while offset < end {
+        let want = (end - offset).min(BUFFER_SIZE as u64) as usize;
+        match file.read_at(&mut buf[..want], offset) {
+            // The file shrank while being counted
+            Ok(0) => break,
+            Ok(read) => {
+                counter.update(&buf[..read]);
+                offset += read as u64;
+            }
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(counter)
+}
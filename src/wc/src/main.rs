@@ -0,0 +1,321 @@
+// ASD CoreUtils - wc
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+mod count;
+
+use asd_coreutils_core::error::{fail, strerror};
+use clap::{Arg, ArgAction, Command};
+use count::{Counter, Counts, Options};
+use std::env;
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{self, Seek, Write};
+use std::mem::ManuallyDrop;
+use std::os::unix::io::FromRawFd;
+use std::process;
+use std::thread;
+
+// Most threads used without --parallel
+const DEFAULT_MAX_THREADS: usize = 8;
+
+// Which counts are printed, in this order
+struct Columns {
+    lines: bool,
+    words: bool,
+    chars: bool,
+    bytes: bool,
+    max_line: bool,
+}
+
+impl Columns {
+    fn len(&self) -> usize {
+        [
+            self.lines,
+            self.words,
+            self.chars,
+            self.bytes,
+            self.max_line,
+        ]
+        .iter()
+        .filter(|&&shown| shown)
+        .count()
+    }
+}
+
+fn main() {
+    let matches = Command::new("wc")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils wc - print newline, word, and byte counts for each file")
+        .after_help(
+            "Print newline, word, and byte counts for each FILE, and a total line if\n\
+             more than one FILE is specified. A word is a non-zero-length sequence of\n\
+             printable characters delimited by white space.\n\n\
+             The options below may be used to select which counts are printed, always in\n\
+             the following order: newline, word, character, byte, maximum line length.\n\n\
+             With no FILE, or when FILE is -, read standard input.\n\n\
+             Large regular files are counted by several threads at once.",
+        )
+        .arg(
+            Arg::new("bytes")
+                .short('c')
+                .long("bytes")
+                .help("Print the byte counts")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("chars")
+                .short('m')
+                .long("chars")
+                .help("Print the character counts")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("lines")
+                .short('l')
+                .long("lines")
+                .help("Print the newline counts")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("max-line-length")
+                .short('L')
+                .long("max-line-length")
+                .help("Print the maximum display width")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("words")
+                .short('w')
+                .long("words")
+                .help("Print the word counts")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("parallel")
+                .long("parallel")
+                .value_name("N")
+                .help("Count large files with up to N threads"),
+        )
+        .arg(
+            Arg::new("files")
+                .value_name("FILE")
+                .help("Files to count")
+                .num_args(0..),
+        )
+        .get_matches();
+
+    let mut columns = Columns {
+        lines: matches.get_flag("lines"),
+        words: matches.get_flag("words"),
+        chars: matches.get_flag("chars"),
+        bytes: matches.get_flag("bytes"),
+        max_line: matches.get_flag("max-line-length"),
+    };
+    if columns.len() == 0 {
+        columns = Columns {
+            lines: true,
+            words: true,
+            chars: false,
+            bytes: true,
+            max_line: false,
+        };
+    }
+
+    // Characters are multibyte only in a UTF-8 locale
+    let utf8 = unsafe {
+        libc::setlocale(libc::LC_ALL, c"".as_ptr());
+        CStr::from_ptr(libc::nl_langinfo(libc::CODESET)).to_bytes() == b"UTF-8"
+    };
+    let options = Options {
+        words: columns.words,
+        chars: columns.chars,
+        max_line: columns.max_line,
+        utf8,
+        nbsp_separates: env::var_os("POSIXLY_CORRECT").is_none(),
+    };
+
+    let threads = match matches.get_one::<String>("parallel") {
+        Some(count) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => count,
+            _ => fail("wc", &format!("invalid --parallel argument '{}'", count)),
+        },
+        None => thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(DEFAULT_MAX_THREADS),
+    };
+
+    let named: Vec<String> = matches
+        .get_many::<String>("files")
+        .map(|files| files.cloned().collect())
+        .unwrap_or_default();
+    let files = if named.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        named.clone()
+    };
+
+    let width = number_width(&files, &columns);
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut total = Counts::default();
+    let mut status = 0;
+
+    for file in &files {
+        let (counts, ok) = count_file(file, &columns, options, threads);
+        if !ok {
+            status = 1;
+        }
+        let Some(counts) = counts else {
+            continue;
+        };
+        total.add(&counts);
+        let name = if named.is_empty() {
+            None
+        } else {
+            Some(file.as_str())
+        };
+        if write_counts(&mut out, &counts, &columns, width, name).is_err() {
+            process::exit(1);
+        }
+    }
+
+    if files.len() > 1 && write_counts(&mut out, &total, &columns, width, Some("total")).is_err() {
+        process::exit(1);
+    }
+    if out.flush().is_err() {
+        process::exit(1);
+    }
+    process::exit(status);
+}
+
+// Width of each count: enough for the total size of the regular files, at
+// least 7 when some input is not a regular file, and no padding at all for
+// a single count of a single file
+fn number_width(files: &[String], columns: &Columns) -> usize {
+    if files.len() == 1 && columns.len() == 1 {
+        return 1;
+    }
+    let mut minimum = 1;
+    let mut total: u64 = 0;
+    for file in files {
+        let metadata = if file == "-" {
+            stdin_file().metadata()
+        } else {
+            std::fs::metadata(file)
+        };
+        match metadata {
+            Ok(metadata) if metadata.is_file() => total += metadata.len(),
+            Ok(_) => minimum = 7,
+            Err(_) => {}
+        }
+    }
+    total.to_string().len().max(minimum)
+}
+
+// Standard input as a file, left open when dropped
+fn stdin_file() -> ManuallyDrop<File> {
+    ManuallyDrop::new(unsafe { File::from_raw_fd(0) })
+}
+
+// Count FILE; the counts are None if it could not be opened, and the flag
+// false if anything went wrong
+fn count_file(
+    file: &str,
+    columns: &Columns,
+    options: Options,
+    threads: usize,
+) -> (Option<Counts>, bool) {
+    let opened;
+    let stdin;
+    let handle: &File = if file == "-" {
+        stdin = stdin_file();
+        &stdin
+    } else {
+        match File::open(file) {
+            Ok(handle) => {
+                opened = handle;
+                &opened
+            }
+            Err(err) => {
+                eprintln!("wc: {}: {}", file, strerror(&err));
+                return (None, false);
+            }
+        }
+    };
+
+    match count(file, handle, columns, options, threads) {
+        Ok(counts) => (Some(counts), true),
+        Err((counts, err)) => {
+            eprintln!("wc: {}: {}", file, strerror(&err));
+            (Some(counts), false)
+        }
+    }
+}
+
+fn count(
+    file: &str,
+    mut handle: &File,
+    columns: &Columns,
+    options: Options,
+    threads: usize,
+) -> Result<Counts, (Counts, io::Error)> {
+    let regular = handle.metadata().ok().filter(|metadata| metadata.is_file());
+
+    if let Some(metadata) = &regular {
+        // The size is all a byte count needs, from wherever the input is
+        // positioned
+        if columns.bytes && columns.len() == 1 {
+            if let Ok(position) = handle.stream_position() {
+                let bytes = metadata.len().saturating_sub(position);
+                if handle.seek(io::SeekFrom::Current(bytes as i64)).is_ok() && bytes > 0 {
+                    return Ok(Counts {
+                        bytes,
+                        ..Counts::default()
+                    });
+                }
+            }
+        }
+
+        // Standard input is read in sequence so its offset ends up where a
+        // later reader expects it
+        let threads = threads.min((metadata.len() / count::MIN_PART_SIZE) as usize);
+        if file != "-" && threads > 1 && !columns.max_line {
+            return count::count_parallel(handle, metadata.len(), threads, options)
+                .map_err(|err| (Counts::default(), err));
+        }
+    }
+
+    let mut counter = Counter::new(options);
+    count::count_stream(&mut handle, &mut counter).map_err(|err| (counter.counts(), err))?;
+    Ok(counter.counts())
+}
+
+fn write_counts(
+    out: &mut dyn Write,
+    counts: &Counts,
+    columns: &Columns,
+    width: usize,
+    name: Option<&str>,
+) -> io::Result<()> {
+    let shown = [
+        (columns.lines, counts.lines),
+        (columns.words, counts.words),
+        (columns.chars, counts.chars),
+        (columns.bytes, counts.bytes),
+        (columns.max_line, counts.max_line),
+    ];
+    let mut line = shown
+        .iter()
+        .filter(|(shown, _)| *shown)
+        .map(|(_, count)| format!("{:>width$}", count, width = width))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(name) = name {
+        line.push(' ');
+        line.push_str(name);
+    }
+    line.push('\n');
+    out.write_all(line.as_bytes())
+}
@@ -2,8 +2,10 @@
 // Copyright (c) 2025 AnmiTaliDev
 // Licensed under the Apache License, Version 2.0
 
+use asd_coreutils_core::error::fail;
 use std::env;
-use std::process::{Command, exit};
+#[cfg(windows)]
+use std::process::Command;
 use std::time::Instant;
 use clap::{Arg, Command as ClapCommand};
 
@@ -54,17 +56,14 @@ fn main() {
                 eprintln!("Execution time: {:.6} ms", elapsed.as_secs_f64() * 1000.0);
             }
         },
-        Err(err) => {
-            eprintln!("Error: {}", err);
-            exit(1);
-        }
+        Err(err) => fail("whoami", &err),
     }
 }
 
 #[cfg(unix)]
 fn get_username() -> Result<String, String> {
     use std::ffi::CStr;
-    use libc::{getuid, getpwuid_r, passwd, geteuid};
+    use libc::{getpwuid_r, passwd, geteuid};
     use std::ptr;
     use std::mem;
 
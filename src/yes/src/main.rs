@@ -0,0 +1,82 @@
+// ASD CoreUtils - yes
+// Copyright (c) 2025 AnmiTaliDev
+// Licensed under the Apache License, Version 2.0
+
+use asd_coreutils_core::error::strerror;
+use clap::{Arg, Command};
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, IoSlice, Write};
+use std::mem::ManuallyDrop;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::FromRawFd;
+use std::process;
+
+// The buffer holds whole lines up to about this size, and each write hands
+// the kernel that many copies of it
+const BUFFER_SIZE: usize = 64 * 1024;
+const SLICES: usize = 8;
+
+fn main() {
+    // A closed pipe is how yes is normally stopped
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+
+    let matches = Command::new("yes")
+        .version("1.0.0")
+        .author("AnmiTaliDev")
+        .about("ASD CoreUtils yes - repeatedly output a line with all specified STRING(s), or 'y'")
+        .arg(
+            Arg::new("strings")
+                .value_name("STRING")
+                .num_args(0..)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+        .get_matches();
+
+    let mut line: Vec<u8> = match matches.get_many::<OsString>("strings") {
+        Some(strings) => strings
+            .map(|string| string.as_bytes())
+            .collect::<Vec<_>>()
+            .join(&b' '),
+        None => b"y".to_vec(),
+    };
+    line.push(b'\n');
+
+    let copies = (BUFFER_SIZE / line.len()).max(1);
+    let buffer = line.repeat(copies);
+
+    let err = write_forever(&buffer);
+    // When SIGPIPE is ignored a closed pipe shows up as EPIPE instead; it
+    // still means the reader is done
+    if err.kind() != io::ErrorKind::BrokenPipe {
+        eprintln!("yes: standard output: {}", strerror(&err));
+    }
+    process::exit(1);
+}
+
+// Writes BUFFER over and over with vectored writes, going around partial
+// writes, until one fails
+fn write_forever(buffer: &[u8]) -> io::Error {
+    let mut stdout = ManuallyDrop::new(unsafe { File::from_raw_fd(1) });
+    let slices = [IoSlice::new(buffer); SLICES];
+    let total = buffer.len() * SLICES;
+
+    loop {
+        let mut written = 0;
+        while written < total {
+            // Start part way into the slice the last write stopped in
+            let first = written / buffer.len();
+            let offset = written % buffer.len();
+            let mut pending = slices;
+            pending[first] = IoSlice::new(&buffer[offset..]);
+            match stdout.write_vectored(&pending[first..]) {
+                Ok(0) => return io::Error::from(io::ErrorKind::WriteZero),
+                Ok(count) => written += count,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return err,
+            }
+        }
+    }
+}